@@ -0,0 +1,255 @@
+//! Implementation of the AAPCS64 calling convention, which is used by all
+//! `aarch64` targets. Apple platforms use a variant of the convention which
+//! differs in how small integers are passed.
+//!
+//! Homogeneous aggregates of up to four floating point or vector members are
+//! passed in the SIMD registers, other aggregates of up to 16 bytes are passed
+//! in the general purpose registers, and larger aggregates are passed by
+//! reference to a copy that is made by the caller. Aggregates that are passed
+//! in registers are cast into an array of their members, or an array of
+//! integers for aggregates that aren't homogeneous.
+//!
+//! Ref: <https://github.com/ARM-software/abi-aa/blob/main/aapcs64/aapcs64.rst> (Parameter Passing)
+
+use hash_repr::{compute::LayoutComputer, TyInfo, Variants};
+use hash_storage::store::statics::StoreId;
+use hash_target::{
+    abi::{AbiRepresentation, ScalarKind},
+    size::Size,
+    Target,
+};
+
+use crate::{ArgAbi, FnAbi, Reg, Uniform};
+
+/// The maximum number of members that a homogeneous aggregate can have.
+const MAX_HOMOGENEOUS_MEMBERS: u64 = 4;
+
+/// The largest aggregate that can be passed in the general purpose
+/// registers.
+const MAX_REGISTER_AGGREGATE_SIZE: u64 = 16;
+
+/// The kind of the members of a homogeneous aggregate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// A floating point member of the given size.
+    Float(Size),
+
+    /// A short vector member of the given size.
+    Vector(Size),
+}
+
+impl Unit {
+    /// Get the size of the member.
+    fn size(self) -> Size {
+        match self {
+            Unit::Float(size) | Unit::Vector(size) => size,
+        }
+    }
+
+    /// Get the register that the member is passed in.
+    fn reg(self) -> Reg {
+        match self {
+            Unit::Float(size) => Reg::float(size),
+            Unit::Vector(size) => Reg::vector(size),
+        }
+    }
+}
+
+/// Denotes that the argument is not a homogeneous aggregate.
+#[derive(Debug, Clone, Copy)]
+pub struct Heterogeneous;
+
+/// Find the [Unit] that all of the members of the given [TyInfo] are made
+/// of, returning [None] if the type has no members.
+fn homogeneous_unit(lc: LayoutComputer, info: TyInfo) -> Result<Option<Unit>, Heterogeneous> {
+    // We have to `.value()` since we might compute the layouts of fields
+    // whilst walking the type.
+    let layout = info.layout.value();
+
+    match layout.abi {
+        AbiRepresentation::Uninhabited => Err(Heterogeneous),
+        AbiRepresentation::Scalar(scalar) => match scalar.kind() {
+            ScalarKind::Float { kind } => Ok(Some(Unit::Float(kind.size()))),
+            ScalarKind::Int { .. } | ScalarKind::Pointer(_) => Err(Heterogeneous),
+        },
+        // Only the 64-bit and 128-bit short vectors are members.
+        AbiRepresentation::Vector { .. } => match layout.size.bytes() {
+            8 | 16 => Ok(Some(Unit::Vector(layout.size))),
+            _ => Err(Heterogeneous),
+        },
+        AbiRepresentation::Pair(_, _) | AbiRepresentation::Aggregate => {
+            // Enums always have a tag which is an integer.
+            if matches!(layout.variants, Variants::Multiple { .. }) {
+                return Err(Heterogeneous);
+            }
+
+            let mut unit = None;
+
+            for field in info.fields_in_memory_order(lc) {
+                match (unit, homogeneous_unit(lc, field.info)?) {
+                    (_, None) => {}
+                    (None, field) => unit = field,
+                    (Some(existing), Some(field)) if existing == field => {}
+                    _ => return Err(Heterogeneous),
+                }
+            }
+
+            Ok(unit)
+        }
+    }
+}
+
+/// Check if the given argument is a homogeneous floating point or vector
+/// aggregate, returning the [Unit] and the number of members if it is.
+pub fn homogeneous_aggregate(lc: LayoutComputer, arg: &ArgAbi) -> Option<(Unit, u64)> {
+    let unit = homogeneous_unit(lc, arg.info).ok()??;
+    let size = arg.info.size();
+
+    // Any padding between the members makes the type not homogeneous.
+    if size.bytes() % unit.size().bytes() != 0 {
+        return None;
+    }
+
+    let members = size.bytes() / unit.size().bytes();
+    (1..=MAX_HOMOGENEOUS_MEMBERS).contains(&members).then_some((unit, members))
+}
+
+/// Adjust the provided [FnAbi] to follow the AAPCS64 calling convention.
+pub fn compute_abi_info(lc: LayoutComputer, fn_abi: &mut FnAbi, target: &Target) {
+    let adjust_arg = |arg: &mut ArgAbi| {
+        if !matches!(
+            arg.info.layout.borrow().abi,
+            AbiRepresentation::Pair(_, _) | AbiRepresentation::Aggregate
+        ) {
+            // Apple platforms require that integers smaller than 32 bits
+            // are extended by the caller.
+            if target.is_like_osx() {
+                arg.extend_integer_width_to(32);
+            }
+
+            return;
+        }
+
+        let size = arg.info.size();
+
+        if let Some((unit, members)) = homogeneous_aggregate(lc, arg) {
+            arg.cast_to(Uniform { unit: unit.reg(), total: unit.size() * members });
+            return;
+        }
+
+        // Large aggregates are passed by reference, and returned via a
+        // pointer that is passed in `x8`.
+        if size.bytes() > MAX_REGISTER_AGGREGATE_SIZE {
+            arg.make_indirect();
+            return;
+        }
+
+        // Other aggregates are passed in (up to two) general purpose
+        // registers, aggregates that are aligned to 16 bytes occupy an
+        // even and odd pair of registers.
+        let unit = if size.bytes() <= 8 {
+            Reg::integer(size)
+        } else if arg.info.abi_alignment().bytes() > 8 {
+            Reg::integer(Size::from_bytes(16))
+        } else {
+            Reg::i64()
+        };
+
+        if size.bytes() != 0 {
+            arg.cast_to(Uniform { unit, total: size });
+        }
+    };
+
+    if !fn_abi.ret_abi.is_ignored() {
+        adjust_arg(&mut fn_abi.ret_abi);
+    }
+
+    for arg in fn_abi.args.iter_mut() {
+        if arg.is_ignored() {
+            continue;
+        }
+
+        adjust_arg(arg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hash_repr::{
+        compute::LayoutComputer,
+        ty::{ReprTy, ReprTyId, COMMON_REPR_TYS},
+        LayoutStorage, TyInfo,
+    };
+    use hash_target::{data_layout::TargetDataLayout, size::Size, Target};
+
+    use super::{compute_abi_info, homogeneous_aggregate, Unit};
+    use crate::{ArgAbi, ArgAttributes, CallingConvention, FnAbi, PassMode, Reg, Uniform};
+
+    fn arg_abi(lc: LayoutComputer, ty: ReprTyId) -> ArgAbi {
+        ArgAbi::new(TyInfo::new(ty, lc.layout_of_ty(ty).unwrap()), |_| ArgAttributes::new())
+    }
+
+    #[test]
+    fn test_homogeneous_aggregates() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let floats =
+            ReprTy::make_tuple(&[COMMON_REPR_TYS.f32, COMMON_REPR_TYS.f32, COMMON_REPR_TYS.f32]);
+        assert_eq!(
+            homogeneous_aggregate(lc, &arg_abi(lc, floats)),
+            Some((Unit::Float(Size::from_bytes(4)), 3))
+        );
+
+        // Members of different kinds or sizes make the aggregate heterogeneous.
+        let mixed = ReprTy::make_tuple(&[COMMON_REPR_TYS.f32, COMMON_REPR_TYS.i32]);
+        assert_eq!(homogeneous_aggregate(lc, &arg_abi(lc, mixed)), None);
+
+        let sizes = ReprTy::make_tuple(&[COMMON_REPR_TYS.f32, COMMON_REPR_TYS.f64]);
+        assert_eq!(homogeneous_aggregate(lc, &arg_abi(lc, sizes)), None);
+
+        // Nested aggregates are flattened into their members, but there can
+        // not be more than four of them.
+        let nested = ReprTy::make_tuple(&[floats, COMMON_REPR_TYS.f32]);
+        assert_eq!(
+            homogeneous_aggregate(lc, &arg_abi(lc, nested)),
+            Some((Unit::Float(Size::from_bytes(4)), 4))
+        );
+
+        let many = ReprTy::make_tuple(&[floats, floats]);
+        assert_eq!(homogeneous_aggregate(lc, &arg_abi(lc, many)), None);
+    }
+
+    #[test]
+    fn test_aggregates_are_cast() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let doubles = ReprTy::make_tuple(&[COMMON_REPR_TYS.f64, COMMON_REPR_TYS.f64]);
+        let small = ReprTy::make_tuple(&[COMMON_REPR_TYS.i32, COMMON_REPR_TYS.f32]);
+        let pair = ReprTy::make_tuple(&[COMMON_REPR_TYS.i64, COMMON_REPR_TYS.u64]);
+        let large =
+            ReprTy::make_tuple(&[COMMON_REPR_TYS.i64, COMMON_REPR_TYS.i64, COMMON_REPR_TYS.i64]);
+
+        let mut fn_abi = FnAbi {
+            args: [small, pair, large].into_iter().map(|ty| arg_abi(lc, ty)).collect(),
+            ret_abi: arg_abi(lc, doubles),
+            calling_convention: CallingConvention::C,
+            c_variadic: false,
+            fixed_count: 3,
+        };
+        compute_abi_info(lc, &mut fn_abi, &Target::default());
+
+        let hfa = Uniform { unit: Reg::f64(), total: Size::from_bytes(16) };
+        assert!(matches!(fn_abi.ret_abi.mode, PassMode::Cast(target) if target == hfa.into()));
+
+        let small = Uniform { unit: Reg::i64(), total: Size::from_bytes(8) };
+        assert!(matches!(fn_abi.args[0].mode, PassMode::Cast(target) if target == small.into()));
+
+        let pair = Uniform { unit: Reg::i64(), total: Size::from_bytes(16) };
+        assert!(matches!(fn_abi.args[1].mode, PassMode::Cast(target) if target == pair.into()));
+
+        // Aggregates that are larger than 16 bytes are passed by reference.
+        assert!(fn_abi.args[2].mode.is_indirect());
+    }
+}
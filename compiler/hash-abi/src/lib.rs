@@ -3,12 +3,20 @@
 //! and to be able to call functions from other languages, but to also provide
 //! information to code generation backends about how values are represented.
 
-use hash_repr::{LayoutId, TyInfo};
+pub mod aarch64;
+pub mod win64;
+pub mod x86_64;
+
+use hash_ir::ty::{Mutability, RefKind, ReprTy, ReprTyId, ReprTyListId};
+use hash_repr::{
+    compute::{LayoutComputer, LayoutError},
+    LayoutId, TyInfo,
+};
 use hash_storage::{new_store_key, store::statics::StoreId};
 use hash_target::{
-    abi::{Abi, AbiRepresentation, Scalar},
+    abi::{Abi, AbiRepresentation, Scalar, ScalarKind},
     size::Size,
-    Target,
+    Target, TargetArch,
 };
 use hash_utils::bitflags;
 
@@ -21,14 +29,30 @@ pub enum CallingConvention {
     /// Equivalent to the `ccc` calling convention in LLVM.
     ///
     /// Ref: <https://llvm.org/docs/LangRef.html#calling-conventions> (ccc)
-    C = 0,
+    C,
+
+    /// The default calling convention of Hash functions. The convention is
+    /// unspecified, and so backends are free to choose how the function is
+    /// called.
+    Hash,
+
+    /// The `fastcall` calling convention of 32-bit x86 targets.
+    ///
+    /// Equivalent to the `x86_fastcallcc` calling convention in LLVM.
+    Fast,
+
+    /// The `stdcall` calling convention of 32-bit x86 Windows targets, this is
+    /// only used for [`Abi::System`] on such targets.
+    ///
+    /// Equivalent to the `x86_stdcallcc` calling convention in LLVM.
+    System,
 
     /// Cold calling convention for functions that are unlikely to be called.
     ///
     /// Equivalent to the `coldcc` calling convention in LLVM.
     ///
     /// Ref: <https://llvm.org/docs/LangRef.html#calling-conventions> (coldcc)
-    Cold = 9,
+    Cold,
 }
 
 impl CallingConvention {
@@ -37,7 +61,10 @@ impl CallingConvention {
     /// [Target].
     pub fn make_from_abi_and_target(abi: Abi, target: &Target) -> Self {
         match target.adjust_abi(abi) {
-            Abi::C | Abi::Hash => CallingConvention::C,
+            Abi::C => CallingConvention::C,
+            Abi::Hash => CallingConvention::Hash,
+            Abi::Fast => CallingConvention::Fast,
+            Abi::System => CallingConvention::System,
             Abi::Cold => CallingConvention::Cold,
         }
     }
@@ -46,9 +73,6 @@ impl CallingConvention {
 new_store_key!(pub FnAbiId);
 
 /// Defines ABI specific information about a function.
-///
-/// @@TODO: Do we need to record information about variadics here (when we add
-/// them)?
 #[derive(Debug, Clone)]
 pub struct FnAbi {
     /// All the types of the arguments in order, and how they should
@@ -61,6 +85,154 @@ pub struct FnAbi {
 
     /// The calling convention that should be used when invoking the function.
     pub calling_convention: CallingConvention,
+
+    /// Whether the function accepts a variable number of arguments after its
+    /// parameters. The ABI of a call to such a function also includes the
+    /// arguments that are passed in the variadic part of the call.
+    pub c_variadic: bool,
+
+    /// The number of arguments that correspond to the parameters of the
+    /// function, any arguments after these are variadic arguments.
+    pub fixed_count: usize,
+}
+
+impl FnAbi {
+    /// Compute the [FnAbi] of a function type, i.e. either a [`ReprTy::FnDef`]
+    /// or a function pointer [`ReprTy::Fn`], using the given
+    /// [CallingConvention].
+    ///
+    /// The computed ABI is not specific to any target, in order to make it
+    /// compatible with the platform's conventions, the ABI should then be
+    /// adjusted with [`FnAbi::adjust_for_abi`].
+    ///
+    /// N.B. the passed "ty" must be a function type.
+    pub fn of_fn_ty(
+        ty: ReprTyId,
+        lc: LayoutComputer,
+        conv: CallingConvention,
+    ) -> Result<Self, LayoutError> {
+        let (params, ret_ty) = ty.map(|ty| match ty {
+            ReprTy::FnDef { instance } => {
+                instance.map(|instance| (instance.params, instance.ret_ty))
+            }
+            ReprTy::Fn { params, return_ty, .. } => (*params, *return_ty),
+            _ => panic!("expected a function type, but got `{ty}`"),
+        });
+
+        Self::of_fn_sig(params, ret_ty, lc, conv)
+    }
+
+    /// Compute the [FnAbi] from the parameter types and the return type of a
+    /// function.
+    pub fn of_fn_sig(
+        params: ReprTyListId,
+        ret_ty: ReprTyId,
+        lc: LayoutComputer,
+        conv: CallingConvention,
+    ) -> Result<Self, LayoutError> {
+        let make_arg_abi = |ty: ReprTyId, is_return: bool| -> Result<ArgAbi, LayoutError> {
+            let info = TyInfo::new(ty, lc.layout_of_ty(ty)?);
+
+            let mut arg = ArgAbi::new(info, |scalar| {
+                let mut attributes = ArgAttributes::new();
+                adjust_arg_attributes(&mut attributes, ty, scalar, is_return);
+                attributes
+            });
+
+            // @@Todo: we might have to adjust the attribute pass mode
+            // for ZSTs on specific platforms since they don't ignore them?
+            if is_return && info.is_zst() {
+                arg.mode = PassMode::Ignore;
+            }
+
+            Ok(arg)
+        };
+
+        let args: Box<[ArgAbi]> =
+            params.borrow().iter().map(|ty| make_arg_abi(*ty, false)).collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            fixed_count: args.len(),
+            args,
+            ret_abi: make_arg_abi(ret_ty, true)?,
+            calling_convention: conv,
+            c_variadic: false,
+        })
+    }
+
+    /// Adjust the [FnAbi] to follow the conventions of the specified [Abi] on
+    /// the current [Target]. The [`Abi::Hash`] and [`Abi::Cold`] ABIs are
+    /// unspecified, and so they are left as is, foreign functions are adjusted
+    /// to match the convention of the platform so that they are compatible
+    /// with C code.
+    pub fn adjust_for_abi(&mut self, lc: LayoutComputer, abi: Abi, target: &Target) {
+        if !matches!(target.adjust_abi(abi), Abi::C | Abi::Fast | Abi::System) {
+            return;
+        }
+
+        match target.arch {
+            TargetArch::X86_64 if target.is_like_windows() => win64::compute_abi_info(self),
+            TargetArch::X86_64 => x86_64::compute_abi_info(lc, self),
+            TargetArch::Aarch64 => aarch64::compute_abi_info(lc, self, target),
+            // @@Todo: implement the conventions for the other targets.
+            _ => {}
+        }
+    }
+}
+
+/// Adjust the attributes of an argument ABI based on the provided
+/// [Layout] and [Scalar] information. This is required to do since
+/// the scalar maybe a pair of values.
+fn adjust_arg_attributes(
+    attributes: &mut ArgAttributes,
+    ty: ReprTyId,
+    scalar: Scalar,
+    is_return: bool,
+) {
+    // Booleans are always "noundef" values...
+    if scalar.is_bool() {
+        attributes.extend_with(ArgExtension::ZeroExtend);
+        attributes.set(ArgAttributeFlag::NO_UNDEF);
+        return;
+    }
+
+    // If this scalar should always be initialised then we can set the "noundef"
+    // attribute.
+    if !scalar.is_union() {
+        attributes.set(ArgAttributeFlag::NO_UNDEF);
+    }
+
+    // If this scalar represents a pointer, then we can deduce more
+    // information about this particular argument.
+    let Scalar::Initialised { kind: ScalarKind::Pointer { .. }, valid_range } = scalar else {
+        return;
+    };
+
+    // If the pointer is never null, then we can set the "non_null" attribute.
+    if !valid_range.contains(0) {
+        attributes.set(ArgAttributeFlag::NON_NULL);
+    }
+
+    // If the pointer type is a read-only, then we can set the "read_only"
+    // attribute.
+    ty.map(|ty| {
+        let ReprTy::Ref(_, mutability, kind) = ty else {
+            return;
+        };
+
+        // @@Future: can we deduce the same thing for an `Rc` pointer?
+        if !is_return
+            && matches!(kind, RefKind::Raw | RefKind::Normal)
+            && *mutability == Mutability::Immutable
+        {
+            attributes.set(ArgAttributeFlag::READ_ONLY);
+        }
+    });
+
+    // @@Todo: we currently can't deduce any information about aliasing of
+    // pointer data, so we can't really derive the "no_alias" attribute. If
+    // we become stricter with these rules, then we can possibly emit more
+    // useful information here.
 }
 
 /// Defines ABI specific information about an argument. [ArgAbi] is also
@@ -84,6 +256,8 @@ impl ArgAbi {
             AbiRepresentation::Pair(scalar_a, scalar_b) => {
                 PassMode::Pair(attributes_from_scalar(scalar_a), attributes_from_scalar(scalar_b))
             }
+            // Vectors are passed in vector registers, backends that don't
+            // support them should make the argument indirect.
             AbiRepresentation::Vector { .. } => PassMode::Direct(ArgAttributes::new()),
             AbiRepresentation::Aggregate => PassMode::Direct(ArgAttributes::new()),
         });
@@ -110,7 +284,7 @@ impl ArgAbi {
     pub fn make_indirect(&mut self) {
         // Firstly, verify that that we aren't making an ignored argument indirect.
         match self.mode {
-            PassMode::Direct(_) | PassMode::Pair(_, _) => {}
+            PassMode::Direct(_) | PassMode::Pair(_, _) | PassMode::Cast(_) => {}
             PassMode::Indirect { on_stack: false, .. } => return,
             kind => panic!("tried to make this argument with mode {kind:?} indirectly"),
         }
@@ -130,6 +304,41 @@ impl ArgAbi {
         }
     }
 
+    /// Make the argument be passed in the registers of the given
+    /// [CastTarget].
+    pub fn cast_to(&mut self, target: impl Into<CastTarget>) {
+        self.mode = PassMode::Cast(target.into());
+    }
+
+    /// Extend the argument to be at least `bits` wide if it is an integer
+    /// scalar that is passed directly. Some ABIs require that small integers
+    /// are sign or zero extended by the caller.
+    pub fn extend_integer_width_to(&mut self, bits: u64) {
+        let AbiRepresentation::Scalar(scalar) = self.info.layout.borrow().abi else {
+            return;
+        };
+
+        let ScalarKind::Int { kind, signed } = scalar.kind() else {
+            return;
+        };
+
+        if let PassMode::Direct(ref mut attributes) = self.mode {
+            if kind.size().bits() < bits {
+                attributes.extend_with(if signed {
+                    ArgExtension::SignExtend
+                } else {
+                    ArgExtension::ZeroExtend
+                });
+            }
+        }
+    }
+
+    /// Check if the argument is an aggregate, i.e. it isn't represented as
+    /// a scalar, pair, or vector.
+    pub fn is_aggregate(&self) -> bool {
+        self.info.layout.borrow().abi.is_aggregate()
+    }
+
     /// Check if the [PassMode] of the [ArgAbi] is "indirect".
     pub fn is_indirect(&self) -> bool {
         matches!(self.mode, PassMode::Indirect { .. })
@@ -293,6 +502,12 @@ pub enum PassMode {
         /// stack.
         on_stack: bool,
     },
+
+    /// Pass the argument by re-interpreting its memory as the registers of
+    /// the [CastTarget]. This is used when the ABI requires an aggregate to
+    /// be split into registers that differ from its fields, i.e. a `{f32,
+    /// f32}` being passed in a single vector register.
+    Cast(CastTarget),
 }
 
 impl PassMode {
@@ -302,3 +517,122 @@ impl PassMode {
         matches!(self, Self::Indirect { .. })
     }
 }
+
+/// The kind of a [Reg].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegKind {
+    /// A general purpose register.
+    Integer,
+
+    /// A floating point register.
+    Float,
+
+    /// A vector register.
+    Vector,
+}
+
+/// A register (or a part of a register) of a particular size that an
+/// argument is passed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reg {
+    /// The kind of the register.
+    pub kind: RegKind,
+
+    /// The size of the value in the register.
+    pub size: Size,
+}
+
+impl Reg {
+    /// Create an integer register of the given size.
+    pub fn integer(size: Size) -> Self {
+        Self { kind: RegKind::Integer, size }
+    }
+
+    /// Create a 64-bit integer register.
+    pub fn i64() -> Self {
+        Self::integer(Size::from_bytes(8))
+    }
+
+    /// Create a floating point register of the given size.
+    pub fn float(size: Size) -> Self {
+        Self { kind: RegKind::Float, size }
+    }
+
+    /// Create a 32-bit floating point register.
+    pub fn f32() -> Self {
+        Self::float(Size::from_bytes(4))
+    }
+
+    /// Create a 64-bit floating point register.
+    pub fn f64() -> Self {
+        Self::float(Size::from_bytes(8))
+    }
+
+    /// Create a vector register of the given size.
+    pub fn vector(size: Size) -> Self {
+        Self { kind: RegKind::Vector, size }
+    }
+}
+
+/// A sequence of `unit` registers that cover `total` bytes. If `total` isn't
+/// a multiple of the size of `unit`, then the remaining bytes are passed in
+/// an integer register of the remaining size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uniform {
+    /// The register that is repeated.
+    pub unit: Reg,
+
+    /// The total size that is covered by the registers.
+    pub total: Size,
+}
+
+impl Uniform {
+    /// Get the number of whole `unit` registers, and the number of bytes
+    /// that remain after them.
+    pub fn unit_count(&self) -> (u64, u64) {
+        let unit = self.unit.size.bytes();
+        (self.total.bytes() / unit, self.total.bytes() % unit)
+    }
+}
+
+impl From<Reg> for Uniform {
+    fn from(unit: Reg) -> Self {
+        Self { unit, total: unit.size }
+    }
+}
+
+/// The registers that an argument is passed in when it has the pass mode
+/// [`PassMode::Cast`]. The registers are laid out in order, the `prefix` (if
+/// any) is followed by the `rest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CastTarget {
+    /// A register that precedes the uniform part of the target.
+    pub prefix: Option<Reg>,
+
+    /// The uniform part of the target.
+    pub rest: Uniform,
+}
+
+impl CastTarget {
+    /// Create a [CastTarget] of two registers.
+    pub fn pair(lo: Reg, hi: Reg) -> Self {
+        Self { prefix: Some(lo), rest: Uniform::from(hi) }
+    }
+
+    /// Get the size that is covered by the registers of the [CastTarget].
+    pub fn size(&self) -> Size {
+        self.prefix.map_or(Size::ZERO, |reg| reg.size) + self.rest.total
+    }
+}
+
+impl From<Reg> for CastTarget {
+    fn from(reg: Reg) -> Self {
+        Self::from(Uniform::from(reg))
+    }
+}
+
+impl From<Uniform> for CastTarget {
+    fn from(rest: Uniform) -> Self {
+        Self { prefix: None, rest }
+    }
+}
@@ -0,0 +1,103 @@
+//! Implementation of the Windows x64 calling convention, which is used by
+//! all `x86_64` Windows targets.
+//!
+//! Unlike System V, each argument occupies exactly one register or stack
+//! slot. Values that are 1, 2, 4, or 8 bytes in size are passed in a register,
+//! and all other values are passed by reference to a copy that is made by the
+//! caller. Aggregates that are passed in a register are cast into an integer
+//! of the same size, i.e. an `{i32, i32}` is passed as an `i64`.
+//!
+//! Ref: <https://learn.microsoft.com/en-us/cpp/build/x64-calling-convention>
+
+use hash_storage::store::statics::StoreId;
+use hash_target::abi::AbiRepresentation;
+
+use crate::{ArgAbi, FnAbi, Reg};
+
+/// Adjust the provided [FnAbi] to follow the Windows x64 calling convention.
+pub fn compute_abi_info(fn_abi: &mut FnAbi) {
+    let adjust_arg = |arg: &mut ArgAbi, is_arg: bool| {
+        let size = arg.info.size();
+        let abi = arg.info.layout.borrow().abi;
+
+        match abi {
+            AbiRepresentation::Uninhabited => {}
+            AbiRepresentation::Pair(_, _) | AbiRepresentation::Aggregate => match size.bytes() {
+                1 | 2 | 4 | 8 => arg.cast_to(Reg::integer(size)),
+                _ => arg.make_indirect(),
+            },
+            // Vectors are passed by reference, but are returned in `xmm0`.
+            AbiRepresentation::Vector { .. } if is_arg => arg.make_indirect(),
+            AbiRepresentation::Vector { .. } => {}
+            // Scalars that don't fit into a register, i.e. `i128`, are passed
+            // by reference.
+            AbiRepresentation::Scalar(_) if size.bytes() > 8 => arg.make_indirect(),
+            AbiRepresentation::Scalar(_) => {}
+        }
+    };
+
+    if !fn_abi.ret_abi.is_ignored() {
+        adjust_arg(&mut fn_abi.ret_abi, false);
+    }
+
+    for arg in fn_abi.args.iter_mut() {
+        if arg.is_ignored() {
+            continue;
+        }
+
+        adjust_arg(arg, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hash_repr::{
+        compute::LayoutComputer,
+        ty::{ReprTy, ReprTyId, COMMON_REPR_TYS},
+        LayoutStorage, TyInfo,
+    };
+    use hash_target::{data_layout::TargetDataLayout, size::Size};
+
+    use super::compute_abi_info;
+    use crate::{ArgAbi, ArgAttributes, CallingConvention, CastTarget, FnAbi, PassMode, Reg};
+
+    fn arg_abi(lc: LayoutComputer, ty: ReprTyId) -> ArgAbi {
+        ArgAbi::new(TyInfo::new(ty, lc.layout_of_ty(ty).unwrap()), |_| ArgAttributes::new())
+    }
+
+    #[test]
+    fn test_small_aggregates_are_cast() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let pair = ReprTy::make_tuple(&[COMMON_REPR_TYS.i32, COMMON_REPR_TYS.i32]);
+        let bytes = ReprTy::make_tuple(&[COMMON_REPR_TYS.u8, COMMON_REPR_TYS.u8]);
+        let odd = ReprTy::make_tuple(&[COMMON_REPR_TYS.u8, COMMON_REPR_TYS.u8, COMMON_REPR_TYS.u8]);
+        let large = ReprTy::make_tuple(&[COMMON_REPR_TYS.i64, COMMON_REPR_TYS.i64]);
+
+        let mut fn_abi = FnAbi {
+            args: [pair, bytes, odd, large, COMMON_REPR_TYS.f64]
+                .into_iter()
+                .map(|ty| arg_abi(lc, ty))
+                .collect(),
+            ret_abi: arg_abi(lc, pair),
+            calling_convention: CallingConvention::C,
+            c_variadic: false,
+            fixed_count: 5,
+        };
+        compute_abi_info(&mut fn_abi);
+
+        let i64 = CastTarget::from(Reg::i64());
+        assert!(matches!(fn_abi.ret_abi.mode, PassMode::Cast(target) if target == i64));
+        assert!(matches!(fn_abi.args[0].mode, PassMode::Cast(target) if target == i64));
+
+        let i16 = CastTarget::from(Reg::integer(Size::from_bytes(2)));
+        assert!(matches!(fn_abi.args[1].mode, PassMode::Cast(target) if target == i16));
+
+        // Aggregates that aren't the size of a register are passed by
+        // reference.
+        assert!(fn_abi.args[2].mode.is_indirect());
+        assert!(fn_abi.args[3].mode.is_indirect());
+        assert!(matches!(fn_abi.args[4].mode, PassMode::Direct(_)));
+    }
+}
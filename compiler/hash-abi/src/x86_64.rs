@@ -0,0 +1,377 @@
+//! Implementation of the System V AMD64 calling convention, which is used by
+//! all `x86_64` targets apart from Windows.
+//!
+//! Each argument is split into "eightbytes" (8 byte chunks), and each of the
+//! eightbytes is given a [Class] depending on the fields that fall into it.
+//! Arguments that are too large, or cannot be classified are passed in
+//! memory. Aggregates that are passed in registers are cast into the
+//! registers of their eightbytes, i.e. a `{f32, f32}` occupies a single
+//! eightbyte of the `SSE` class and so is passed in a single vector register.
+//!
+//! Ref: <https://gitlab.com/x86-psABIs/x86-64-ABI> (3.2.3 Parameter Passing)
+
+use hash_repr::{compute::LayoutComputer, TyInfo, Variants};
+use hash_storage::store::statics::StoreId;
+use hash_target::{
+    abi::{AbiRepresentation, ScalarKind},
+    size::Size,
+};
+
+use crate::{ArgAbi, CastTarget, FnAbi, Reg};
+
+/// The class of an eightbyte of an argument.
+///
+/// The ordering of the variants matters, since when two fields share
+/// an eightbyte, the eightbyte is given the "lowest" class of the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Class {
+    /// The eightbyte is passed in one of the general purpose registers.
+    Integer,
+
+    /// The eightbyte is passed in one of the vector registers.
+    Sse,
+
+    /// The eightbyte is passed in the upper half of the vector register
+    /// that was used by the previous [`Class::Sse`] eightbyte.
+    SseUp,
+}
+
+/// Denotes that the argument is of the `MEMORY` class, and is passed in
+/// memory on the stack.
+#[derive(Debug, Clone, Copy)]
+pub struct Memory;
+
+/// The size of the largest vector register, i.e. AVX-512 registers.
+const LARGEST_VECTOR_SIZE: u64 = 512;
+
+/// The maximum number of eightbytes that an argument can be made of to be
+/// passed in registers.
+const MAX_EIGHTBYTES: usize = (LARGEST_VECTOR_SIZE / 64) as usize;
+
+/// The number of general purpose registers that are used to pass arguments.
+const MAX_INT_REGS: usize = 6;
+
+/// The number of vector registers that are used to pass arguments.
+const MAX_SSE_REGS: usize = 8;
+
+/// Classify each of the eightbytes of the given [TyInfo] that is located at
+/// `offset` within the argument.
+fn classify(
+    lc: LayoutComputer,
+    info: TyInfo,
+    classes: &mut [Option<Class>],
+    offset: Size,
+) -> Result<(), Memory> {
+    // We have to `.value()` since we might compute the layouts of fields
+    // whilst classifying the argument.
+    let layout = info.layout.value();
+
+    // Any un-aligned fields make the whole argument be passed in memory.
+    if offset.align_to(layout.alignment.abi) != offset {
+        return if layout.is_zst() { Ok(()) } else { Err(Memory) };
+    }
+
+    let mut class = match layout.abi {
+        AbiRepresentation::Uninhabited => return Ok(()),
+        AbiRepresentation::Scalar(scalar) => match scalar.kind() {
+            ScalarKind::Int { .. } | ScalarKind::Pointer(_) => Class::Integer,
+            ScalarKind::Float { .. } => Class::Sse,
+        },
+        AbiRepresentation::Vector { .. } => Class::Sse,
+        AbiRepresentation::Pair(_, _) | AbiRepresentation::Aggregate => {
+            for field in info.fields_in_memory_order(lc) {
+                classify(lc, field.info, classes, offset + field.offset)?;
+            }
+
+            // The variants of an enum all overlap, so we classify all
+            // of them at the same offset.
+            if matches!(layout.variants, Variants::Multiple { .. }) {
+                for (_, variant) in info.variants() {
+                    classify(lc, variant, classes, offset)?;
+                }
+            }
+
+            return Ok(());
+        }
+    };
+
+    // Fill in all of the eightbytes that the value covers, vectors may
+    // span multiple eightbytes in which case the remaining eightbytes are
+    // in the upper half of the register.
+    let first = (offset.bytes() / 8) as usize;
+    let last = ((offset.bytes() + layout.size.bytes() - 1) / 8) as usize;
+
+    for slot in &mut classes[first..=last] {
+        *slot = Some(slot.map_or(class, |existing| existing.min(class)));
+
+        if class == Class::Sse {
+            class = Class::SseUp;
+        }
+    }
+
+    Ok(())
+}
+
+/// Classify an argument into the classes of each of its eightbytes, or
+/// [Memory] if the argument must be passed in memory.
+pub fn classify_arg(
+    lc: LayoutComputer,
+    arg: &ArgAbi,
+) -> Result<[Option<Class>; MAX_EIGHTBYTES], Memory> {
+    let count = arg.info.size().bytes().div_ceil(8) as usize;
+
+    if count > MAX_EIGHTBYTES {
+        return Err(Memory);
+    }
+
+    let mut classes = [None; MAX_EIGHTBYTES];
+    classify(lc, arg.info, &mut classes, Size::ZERO)?;
+
+    if count > 2 {
+        // Arguments that are larger than two eightbytes can only be passed in
+        // registers if they are a single vector.
+        if classes[0] != Some(Class::Sse) {
+            return Err(Memory);
+        }
+
+        if classes[1..count].iter().any(|class| *class != Some(Class::SseUp)) {
+            return Err(Memory);
+        }
+    } else {
+        // Any `SSEUP` eightbytes that don't follow an `SSE` or `SSEUP`
+        // eightbyte are converted into `SSE`.
+        let mut index = 0;
+
+        while index < count {
+            if classes[index] == Some(Class::SseUp) {
+                classes[index] = Some(Class::Sse);
+            } else if classes[index] == Some(Class::Sse) {
+                index += 1;
+
+                while index != count && classes[index] == Some(Class::SseUp) {
+                    index += 1;
+                }
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    Ok(classes)
+}
+
+/// Get the [Reg] of the eightbyte at `index`, and advance `index` past all
+/// of the eightbytes that are passed in the same register. The `size` is the
+/// remaining size of the argument from the eightbyte.
+fn reg_component(classes: &[Option<Class>], index: &mut usize, size: Size) -> Option<Reg> {
+    if *index >= classes.len() {
+        return None;
+    }
+
+    match classes[*index] {
+        None => None,
+        Some(Class::Integer) => {
+            *index += 1;
+
+            // The last eightbyte only needs to cover the remaining bytes.
+            Some(if size.bytes() < 8 { Reg::integer(size) } else { Reg::i64() })
+        }
+        Some(Class::Sse) => {
+            let len = 1 + classes[*index + 1..]
+                .iter()
+                .take_while(|class| **class == Some(Class::SseUp))
+                .count();
+            *index += len;
+
+            Some(match (len, size.bytes()) {
+                (1, 4) => Reg::f32(),
+                (1, _) => Reg::f64(),
+                _ => Reg::vector(Size::from_bytes(8) * len as u64),
+            })
+        }
+        Some(Class::SseUp) => unreachable!("`SSEUP` eightbyte doesn't follow an `SSE` eightbyte"),
+    }
+}
+
+/// Compute the [CastTarget] of an argument of the given `size` from the
+/// classes of its eightbytes.
+pub fn cast_target(classes: &[Option<Class>], size: Size) -> CastTarget {
+    let mut index = 0;
+    let lo = reg_component(classes, &mut index, size).unwrap();
+    let offset = Size::from_bytes(8) * index as u64;
+
+    let hi = if size > offset { reg_component(classes, &mut index, size - offset) } else { None };
+    let target = match hi {
+        Some(hi) => CastTarget::pair(lo, hi),
+        None => CastTarget::from(lo),
+    };
+
+    debug_assert!(reg_component(classes, &mut index, Size::ZERO).is_none());
+    target
+}
+
+/// Adjust the provided [FnAbi] to follow the System V AMD64 calling
+/// convention.
+pub fn compute_abi_info(lc: LayoutComputer, fn_abi: &mut FnAbi) {
+    let mut int_regs = MAX_INT_REGS;
+    let mut sse_regs = MAX_SSE_REGS;
+
+    let mut adjust_arg = |arg: &mut ArgAbi, is_arg: bool| {
+        let mut classes = classify_arg(lc, arg);
+
+        if let (true, Ok(cls)) = (is_arg, classes) {
+            let needed_int = cls.iter().filter(|class| **class == Some(Class::Integer)).count();
+            let needed_sse = cls.iter().filter(|class| **class == Some(Class::Sse)).count();
+
+            match (int_regs.checked_sub(needed_int), sse_regs.checked_sub(needed_sse)) {
+                (Some(remaining_int), Some(remaining_sse)) => {
+                    int_regs = remaining_int;
+                    sse_regs = remaining_sse;
+                }
+                // If there aren't enough registers left, then the argument
+                // is passed on the stack. Scalars are already put onto the
+                // stack by the backend, so only aggregates are marked.
+                _ if arg.is_aggregate() => classes = Err(Memory),
+                _ => {}
+            }
+        }
+
+        match classes {
+            Err(Memory) if is_arg => arg.make_indirect_by_stack(),
+            Err(Memory) => {
+                // The pointer to the return value is passed in the first
+                // general purpose register.
+                arg.make_indirect();
+                int_regs -= 1;
+            }
+            // Aggregates are cast into the registers of their eightbytes.
+            Ok(ref classes)
+                if matches!(
+                    arg.info.layout.borrow().abi,
+                    AbiRepresentation::Pair(_, _) | AbiRepresentation::Aggregate
+                ) =>
+            {
+                let size = arg.info.size();
+
+                if size.bytes() != 0 {
+                    arg.cast_to(cast_target(classes, size));
+                }
+            }
+            Ok(_) => {
+                // Integers that are smaller than 32 bits are extended by
+                // the caller.
+                arg.extend_integer_width_to(32);
+            }
+        }
+    };
+
+    if !fn_abi.ret_abi.is_ignored() {
+        adjust_arg(&mut fn_abi.ret_abi, false);
+    }
+
+    for arg in fn_abi.args.iter_mut() {
+        if arg.is_ignored() {
+            continue;
+        }
+
+        adjust_arg(arg, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hash_repr::{
+        compute::LayoutComputer,
+        ty::{ReprTy, ReprTyId, COMMON_REPR_TYS},
+        LayoutStorage, TyInfo,
+    };
+    use hash_storage::store::statics::SingleStoreValue;
+    use hash_target::{data_layout::TargetDataLayout, size::Size};
+
+    use super::{cast_target, classify_arg, Class, Memory, MAX_EIGHTBYTES};
+    use crate::{ArgAbi, ArgAttributes, CastTarget, Reg};
+
+    fn classify_ty(
+        lc: LayoutComputer,
+        ty: ReprTyId,
+    ) -> Result<[Option<Class>; MAX_EIGHTBYTES], Memory> {
+        let arg =
+            ArgAbi::new(TyInfo::new(ty, lc.layout_of_ty(ty).unwrap()), |_| ArgAttributes::new());
+        classify_arg(lc, &arg)
+    }
+
+    #[test]
+    fn test_classify_float_pair() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        // Both of the floats fall into the same eightbyte, so they are passed
+        // in a single vector register.
+        let ty = ReprTy::make_tuple(&[COMMON_REPR_TYS.f32, COMMON_REPR_TYS.f32]);
+        let classes = classify_ty(lc, ty).unwrap();
+        assert_eq!(classes[..2], [Some(Class::Sse), None]);
+        assert_eq!(cast_target(&classes, Size::from_bytes(8)), CastTarget::from(Reg::f64()));
+
+        // The float in the last eightbyte is passed in the lower half of a
+        // vector register.
+        let ty =
+            ReprTy::make_tuple(&[COMMON_REPR_TYS.f32, COMMON_REPR_TYS.f32, COMMON_REPR_TYS.f32]);
+        let classes = classify_ty(lc, ty).unwrap();
+        assert_eq!(classes[..2], [Some(Class::Sse), Some(Class::Sse)]);
+        assert_eq!(
+            cast_target(&classes, Size::from_bytes(12)),
+            CastTarget::pair(Reg::f64(), Reg::f32())
+        );
+    }
+
+    #[test]
+    fn test_classify_mixed_aggregates() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        // An integer and a float that share an eightbyte are passed in a
+        // general purpose register.
+        let ty = ReprTy::make_tuple(&[COMMON_REPR_TYS.i32, COMMON_REPR_TYS.f32]);
+        let classes = classify_ty(lc, ty).unwrap();
+        assert_eq!(classes[..2], [Some(Class::Integer), None]);
+        assert_eq!(cast_target(&classes, Size::from_bytes(8)), CastTarget::from(Reg::i64()));
+
+        // The last eightbyte only covers the remaining bytes.
+        let ty =
+            ReprTy::make_tuple(&[COMMON_REPR_TYS.i32, COMMON_REPR_TYS.i32, COMMON_REPR_TYS.i32]);
+        let classes = classify_ty(lc, ty).unwrap();
+        assert_eq!(classes[..2], [Some(Class::Integer), Some(Class::Integer)]);
+        assert_eq!(
+            cast_target(&classes, Size::from_bytes(12)),
+            CastTarget::pair(Reg::i64(), Reg::integer(Size::from_bytes(4)))
+        );
+
+        let ty = ReprTy::make_tuple(&[COMMON_REPR_TYS.i64, COMMON_REPR_TYS.f64]);
+        let classes = classify_ty(lc, ty).unwrap();
+        let size = lc.layout_of_ty(ty).unwrap().size();
+        let target = cast_target(&classes, size);
+        assert_eq!(target.size(), Size::from_bytes(16));
+        assert!(target.prefix.is_some());
+    }
+
+    #[test]
+    fn test_classify_large_aggregates() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        // Aggregates of more than two eightbytes are passed in memory.
+        let ty =
+            ReprTy::make_tuple(&[COMMON_REPR_TYS.i64, COMMON_REPR_TYS.i64, COMMON_REPR_TYS.i64]);
+        assert!(classify_ty(lc, ty).is_err());
+
+        let ty = ReprTy::create(ReprTy::Array { ty: COMMON_REPR_TYS.f32, length: 8 });
+        assert!(classify_ty(lc, ty).is_err());
+
+        // Scalars are classified by their kind.
+        let classes = classify_ty(lc, COMMON_REPR_TYS.f64).unwrap();
+        assert_eq!(classes[0], Some(Class::Sse));
+
+        let classes = classify_ty(lc, COMMON_REPR_TYS.u8).unwrap();
+        assert_eq!(classes[0], Some(Class::Integer));
+    }
+}
@@ -26,15 +26,19 @@ impl AstDesugaring {
     ///
     /// ```text
     /// loop {
-    ///     match next(<iterator>) {
-    ///         Some(<pat>) => <block>;
-    ///         None        => break;
+    ///     match <iterator> {
+    ///         Option::Some(<pat>) => <block>;
+    ///         Option::None        => break;
     ///     }
     /// }
     /// ```
     ///
     /// So essentially the for-loop becomes a simple loop with a match block on
-    /// the given iterator since for-loops only support iterators.
+    /// the given iterator. The match is marked with [`ast::MatchOrigin::For`],
+    /// which tells name resolution that the subject is the iterator of the
+    /// loop rather than a value to match on. The iterator is kept as is, so
+    /// a range `<lo>..<hi>` or `<lo>..<<hi>` remains visible to the later
+    /// stages.
     pub(crate) fn desugar_for_loop_block(&self, node: ast::Block, parent_span: Span) -> ast::Block {
         // Since this function expects it to be a for-loop block, we match it and unwrap
         let block = match node {
@@ -47,14 +51,23 @@ impl AstDesugaring {
         let ast::ForLoopBlock { pat, iterator, for_body } = block;
         let (iter_span, pat_span, body_span) = (iterator.span(), pat.span(), for_body.span());
 
-        // Utility to create binding patterns for when de-sugaring the result of the
-        // iterator.
-        let make_binding_pat = |label: &str| -> ast::AstNode<ast::Pat> {
+        // Utility to create the `Option::<variant>` patterns for when de-sugaring
+        // the result of the iterator.
+        let make_variant_pat = |variant: &str| -> ast::AstNode<ast::Pat> {
+            let name =
+                |ident: &str| ast::AstNode::new(ast::Name { ident: ident.into() }, iter_span);
+
             ast::AstNode::new(
-                ast::Pat::Binding(ast::BindingPat {
-                    name: ast::AstNode::new(ast::Name { ident: label.into() }, iter_span),
-                    visibility: None,
-                    mutability: None,
+                ast::Pat::Access(ast::AccessPat {
+                    subject: ast::AstNode::new(
+                        ast::Pat::Binding(ast::BindingPat {
+                            name: name("Option"),
+                            visibility: None,
+                            mutability: None,
+                        }),
+                        iter_span,
+                    ),
+                    property: name(variant),
                 }),
                 iter_span,
             )
@@ -63,7 +76,7 @@ impl AstDesugaring {
         // Convert the pattern into a constructor pattern like `Some(<pat>)`
         let pat = ast::AstNode::new(
             ast::Pat::Constructor(ast::ConstructorPat {
-                subject: make_binding_pat("Some"),
+                subject: make_variant_pat("Some"),
                 spread: None,
                 fields: ast::AstNodes::new(
                     thin_vec![ast::AstNode::new(
@@ -95,7 +108,7 @@ impl AstDesugaring {
                     ast::MatchCase {
                         pat: ast::AstNode::new(
                             ast::Pat::Constructor(ast::ConstructorPat {
-                                subject: make_binding_pat("None"),
+                                subject: make_variant_pat("None"),
                                 spread: None,
                                 fields: ast::AstNodes::empty(pat_span),
                             },),
@@ -117,27 +130,7 @@ impl AstDesugaring {
         ast::Block::Loop(ast::LoopBlock {
             contents: ast::AstNode::new(
                 ast::Block::Match(ast::MatchBlock {
-                    subject: ast::AstNode::new(
-                        ast::Expr::Call(ast::CallExpr {
-                            subject: ast::AstNode::new(
-                                ast::Expr::Variable(ast::VariableExpr {
-                                    name: ast::AstNode::new(
-                                        ast::Name { ident: "next".into() },
-                                        iter_span,
-                                    ),
-                                }),
-                                iter_span,
-                            ),
-                            args: ast::AstNodes::new(
-                                thin_vec![ast::AstNode::new(
-                                    ast::ExprArg { name: None, value: iterator, macros: None },
-                                    iter_span
-                                )],
-                                iter_span,
-                            ),
-                        }),
-                        body_span,
-                    ),
+                    subject: iterator,
                     cases: match_cases,
                     origin: ast::MatchOrigin::For,
                 }),
@@ -441,4 +434,160 @@ impl AstDesugaring {
             origin: ast::MatchOrigin::If,
         })
     }
+
+    /// This function is responsible for de-sugaring a chain of ordering
+    /// comparisons into a conjunction of the comparisons between each pair of
+    /// adjacent operands. Every operand apart from the last is bound to a
+    /// local so that it is only evaluated once, in the order that the operands
+    /// are written in.
+    ///
+    /// The de-sugaring process is as follows, take the following chain:
+    ///
+    /// ```text
+    /// a < b <= c
+    /// ```
+    ///
+    /// will be transformed into...
+    ///
+    /// ```text
+    /// {
+    ///     chain#0 := a;
+    ///     {
+    ///         chain#1 := b;
+    ///         chain#0 < chain#1 && chain#1 <= c
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Since each comparison is only reached when the previous comparison
+    /// holds, the operands that come after a comparison which doesn't hold
+    /// are never evaluated. The names of the locals can't be written in the
+    /// source and so they never clash with the names of the user.
+    pub(crate) fn desugar_chained_comparison(
+        &self,
+        node: ast::Expr,
+        parent_span: Span,
+    ) -> ast::Expr {
+        let ast::Expr::ChainedComparison(chain) = node else {
+            panic_on_span!(parent_span, "lowering: expected chained comparison")
+        };
+
+        let ast::ChainedComparisonExpr { operands, operators } = chain;
+        let mut operands = operands.nodes.into_iter();
+        let mut operators = operators.nodes.into_iter();
+
+        let first = operands.next().unwrap();
+        let span = first.span();
+        let declaration = self.bind_comparison_operand(first, 0);
+
+        let lhs = self.make_comparison_operand(0, span);
+        let comparisons = self.desugar_comparisons(lhs, &mut operators, &mut operands, 1);
+
+        self.make_block_expr(declaration, comparisons, parent_span).into_body()
+    }
+
+    /// Compare the `lhs` with the next operand of the chain, and then continue
+    /// with the rest of the chain if there are any operands left.
+    fn desugar_comparisons(
+        &self,
+        lhs: ast::AstNode<ast::Expr>,
+        operators: &mut impl Iterator<Item = ast::AstNode<ast::BinOp>>,
+        operands: &mut impl ExactSizeIterator<Item = ast::AstNode<ast::Expr>>,
+        index: usize,
+    ) -> ast::AstNode<ast::Expr> {
+        let operator = operators.next().unwrap();
+        let rhs = operands.next().unwrap();
+
+        let (lhs_span, rhs_span) = (lhs.span(), rhs.span());
+        let span = lhs_span.join(rhs_span);
+
+        // The last operand is only used once, so it doesn't need a local.
+        if operands.len() == 0 {
+            let comparison = ast::BinaryExpr { lhs, rhs, operator };
+            return ast::AstNode::new(ast::Expr::BinaryExpr(comparison), span);
+        }
+
+        let declaration = self.bind_comparison_operand(rhs, index);
+        let rhs = self.make_comparison_operand(index, rhs_span);
+
+        let comparison =
+            ast::AstNode::new(ast::Expr::BinaryExpr(ast::BinaryExpr { lhs, rhs, operator }), span);
+
+        let lhs = self.make_comparison_operand(index, rhs_span);
+        let rest = self.desugar_comparisons(lhs, operators, operands, index + 1);
+        let span = span.join(rest.span());
+
+        let conjunction = ast::AstNode::new(
+            ast::Expr::BinaryExpr(ast::BinaryExpr {
+                lhs: comparison,
+                rhs: rest,
+                operator: ast::AstNode::new(ast::BinOp::And, span),
+            }),
+            span,
+        );
+
+        self.make_block_expr(declaration, conjunction, span)
+    }
+
+    /// Create the name of the local that the operand of a chained comparison
+    /// at the given `index` is bound to.
+    fn make_comparison_operand_name(&self, index: usize, span: Span) -> ast::AstNode<ast::Name> {
+        ast::AstNode::new(ast::Name { ident: format!("chain#{index}").into() }, span)
+    }
+
+    /// Create a variable that refers to the operand of a chained comparison at
+    /// the given `index`.
+    fn make_comparison_operand(&self, index: usize, span: Span) -> ast::AstNode<ast::Expr> {
+        let name = self.make_comparison_operand_name(index, span);
+        ast::AstNode::new(ast::Expr::Variable(ast::VariableExpr { name }), span)
+    }
+
+    /// Bind the operand of a chained comparison at the given `index` to a
+    /// local.
+    fn bind_comparison_operand(
+        &self,
+        operand: ast::AstNode<ast::Expr>,
+        index: usize,
+    ) -> ast::AstNode<ast::Expr> {
+        let span = operand.span();
+
+        ast::AstNode::new(
+            ast::Expr::Declaration(ast::Declaration {
+                pat: ast::AstNode::new(
+                    ast::Pat::Binding(ast::BindingPat {
+                        name: self.make_comparison_operand_name(index, span),
+                        visibility: None,
+                        mutability: None,
+                    }),
+                    span,
+                ),
+                ty: None,
+                value: Some(operand),
+                otherwise: None,
+            }),
+            span,
+        )
+    }
+
+    /// Create a block expression that consists of the `declaration` which is
+    /// followed by the `expr`.
+    fn make_block_expr(
+        &self,
+        declaration: ast::AstNode<ast::Expr>,
+        expr: ast::AstNode<ast::Expr>,
+        span: Span,
+    ) -> ast::AstNode<ast::Expr> {
+        ast::AstNode::new(
+            ast::Expr::Block(ast::BlockExpr {
+                data: ast::AstNode::new(
+                    ast::Block::Body(ast::BodyBlock {
+                        statements: ast::AstNodes::new(thin_vec![declaration], span),
+                        expr: Some(expr),
+                    }),
+                    span,
+                ),
+            }),
+            span,
+        )
+    }
 }
@@ -58,6 +58,10 @@ impl<Ctx: AstDesugaringCtxQuery> CompilerStage<Ctx> for AstDesugaringPass {
     /// the "if-guard" pattern to express all of the branches in the
     /// if-statement.
     ///
+    /// Any chains of ordering comparisons, i.e. `a < b < c`, are transformed
+    /// into a conjunction of the comparisons between adjacent operands, where
+    /// each operand is bound to a local so that it is only evaluated once.
+    ///
     /// This function utilised the pipeline thread pool in order to make the
     /// transformations as parallel as possible. There is a queue that is
     /// queues all of the expressions within each [hash_ast::ast::Module].
@@ -82,8 +86,8 @@ impl<Ctx: AstDesugaringCtxQuery> CompilerStage<Ctx> for AstDesugaringPass {
 
             // Iterate over all of the modules and add the expressions
             // to the queue so it can be distributed over the threads
-            for (id, module) in node_map.iter_mut_modules().enumerate() {
-                let source_id = SourceId::new_module(id as u32);
+            for (id, module) in node_map.iter_mut_modules() {
+                let source_id = SourceId::from(id);
                 let stage_info = source_stage_info.get(source_id);
 
                 // Skip any modules that have already been de-sugared
@@ -1,7 +1,7 @@
 use std::convert::Infallible;
 
 use hash_ast::{
-    ast::Block,
+    ast::{Block, Expr},
     ast_visitor_mut_default_impl,
     visitor::{walk_mut, AstVisitorMut},
 };
@@ -18,7 +18,7 @@ impl AstDesugaring {
 
 impl AstVisitorMut for AstDesugaring {
     type Error = Infallible;
-    ast_visitor_mut_default_impl!(hiding: Block);
+    ast_visitor_mut_default_impl!(hiding: Block, Expr);
 
     type BlockRet = ();
 
@@ -48,4 +48,22 @@ impl AstVisitorMut for AstDesugaring {
 
         Ok(())
     }
+
+    type ExprRet = ();
+
+    fn visit_expr(
+        &mut self,
+        mut node: hash_ast::ast::AstNodeRefMut<hash_ast::ast::Expr>,
+    ) -> Result<Self::ExprRet, Self::Error> {
+        let parent_span = node.span();
+
+        if let Expr::ChainedComparison(_) = node.body() {
+            node.replace(|old| self.desugar_chained_comparison(old, parent_span));
+        }
+
+        // We still need to walk the expression now
+        let _ = walk_mut::walk_expr(self, node);
+
+        Ok(())
+    }
 }
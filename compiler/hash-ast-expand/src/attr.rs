@@ -18,8 +18,8 @@ use hash_target::HasTarget;
 use hash_tir::{
     intrinsics::definitions::Primitive,
     tir::{
-        validate_and_reorder_args_against_params, Arg, Lit, Node, NodeOrigin, ParamIndex, Term, Ty,
-        TyId,
+        validate_and_reorder_args_against_params, Arg, Lit, Node, NodeOrigin, ParamError,
+        ParamIndex, SomeArgId, Term, Ty, TyId,
     },
 };
 
@@ -74,6 +74,45 @@ impl AstExpander<'_> {
         }
     }
 
+    /// Mark all of the functions that are declared within an `#extern(...)`
+    /// module as `#foreign`. This should be called after the members of the
+    /// module have been expanded, since expanding a member overwrites any
+    /// attributes that it has.
+    pub fn mark_extern_members(&mut self, node: ast::AstNodeRef<ast::ModDef>) {
+        let Some(extern_attr) = attr_store().get_attr(node.id(), attrs::EXTERN) else {
+            return;
+        };
+
+        for member in node.entries.iter() {
+            // Skip past any macro invocations that are applied onto the member,
+            // i.e. `#[link_name(...)] name := ...`.
+            let mut member = member.ast_ref();
+            while let ast::Expr::Macro(invocation) = member.body() {
+                member = invocation.subject.ast_ref();
+            }
+
+            let ast::Expr::Declaration(ast::Declaration { value: Some(value), .. }) = member.body()
+            else {
+                continue;
+            };
+
+            // Skip past any macro invocations that are applied onto the value.
+            let value = match value.body() {
+                ast::Expr::Macro(invocation) => invocation.subject.ast_ref(),
+                _ => value.ast_ref(),
+            };
+
+            if let ast::Expr::FnDef(_) = value.body() {
+                let mut attrs = attr_store().map_with_default(value.id(), |attrs| attrs.clone());
+
+                if !attrs.has_attr(attrs::FOREIGN) {
+                    attrs.add_attr(Attr::new(attrs::FOREIGN, extern_attr.origin));
+                    attr_store().insert(value.id(), attrs);
+                }
+            }
+        }
+    }
+
     /// Check that the attribute argument type matches the expected parameter
     /// type which is registered in [hash_attrs::ty::ATTR_MAP]. If the types
     /// mismatch, the function emits an error, and returns false.
@@ -199,23 +238,51 @@ impl AstExpander<'_> {
                 Node::create_at(Node::<Arg>::empty_seq(), NodeOrigin::Given(node.id()))
             };
 
-            if is_valid
-                && let Err(param_err) =
-                    validate_and_reorder_args_against_params(mac_args, attr_ty.params)
-            {
-                self.add_error(ExpansionError::new(param_err.into(), node.id));
+            if attr_ty.variadic {
+                // Variadic attributes only accept positional arguments, since
+                // they only have a single parameter.
+                if is_valid
+                    && let Some(arg) = mac_args
+                        .iter()
+                        .find(|arg| matches!(arg.borrow().target, ParamIndex::Name(_)))
+                {
+                    let param_err = ParamError::ArgNameNotFoundInParams {
+                        arg: SomeArgId::Arg(arg),
+                        params: attr_ty.params,
+                    };
+                    self.add_error(ExpansionError::new(param_err.into(), node.id));
 
-                is_valid = false;
-            }
+                    is_valid = false;
+                }
 
-            // Now we want to check that all of the types of the params and args match
-            // up. @@Future: perhaps we could get TC do this for us, but for now we avoid
-            // the dependency on TC.
-            for (param_id, arg_id) in attr_ty.params.iter().zip(mac_args.iter()) {
-                let arg_idx = arg_id.borrow().target.into();
-                let param_ty = param_id.borrow().ty;
+                // All of the arguments are checked against the single parameter.
+                for arg_id in mac_args.iter() {
+                    let target = arg_id.borrow().target;
 
-                is_valid &= self.check_attr_arg_and_param_tys_match(&attr, arg_idx, param_ty);
+                    if let Some(param_ty) = attr_ty.ty_of_param(target) {
+                        is_valid &=
+                            self.check_attr_arg_and_param_tys_match(&attr, target.into(), param_ty);
+                    }
+                }
+            } else {
+                if is_valid
+                    && let Err(param_err) =
+                        validate_and_reorder_args_against_params(mac_args, attr_ty.params)
+                {
+                    self.add_error(ExpansionError::new(param_err.into(), node.id));
+
+                    is_valid = false;
+                }
+
+                // Now we want to check that all of the types of the params and args match
+                // up. @@Future: perhaps we could get TC do this for us, but for now we avoid
+                // the dependency on TC.
+                for (param_id, arg_id) in attr_ty.params.iter().zip(mac_args.iter()) {
+                    let arg_idx = arg_id.borrow().target.into();
+                    let param_ty = param_id.borrow().ty;
+
+                    is_valid &= self.check_attr_arg_and_param_tys_match(&attr, arg_idx, param_ty);
+                }
             }
 
             // Check that the subject of the attribute is correct...
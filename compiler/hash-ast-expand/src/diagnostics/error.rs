@@ -86,6 +86,7 @@ impl From<ExpansionError> for Reports {
             ExpansionErrorKind::UnknownAttribute { name } => {
                 reporter
                     .error()
+                    .code(HashErrorCode::UnknownAttribute)
                     .title(format!("could not resolve macro `{name}`"))
                     .add_labelled_span(
                         subject,
@@ -95,6 +96,7 @@ impl From<ExpansionError> for Reports {
             ExpansionErrorKind::DuplicateAttributes { name, first } => {
                 reporter
                     .error()
+                    .code(HashErrorCode::DuplicateAttribute)
                     .title(format!("duplicate application of attribute `{name}`"))
                     .add_labelled_span(
                         first.span(),
@@ -115,6 +117,7 @@ impl From<ExpansionError> for Reports {
 
                 reporter
                     .error()
+                    .code(HashErrorCode::InvalidAttributeSubject)
                     .title(format!("attribute `{name}` cannot be applied to an {target}"))
                     .add_labelled_span(subject, format!("`{name}` cannot be applied to {target}"))
                     .add_help(format!("`{name}` can only be applied to {}", attr.subject));
@@ -122,6 +125,7 @@ impl From<ExpansionError> for Reports {
             ExpansionErrorKind::InvalidAttributeArg(target) => {
                 reporter
                     .error()
+                    .code(HashErrorCode::InvalidAttributeArg)
                     .title("invalid attribute argument")
                     .add_labelled_span(
                         subject,
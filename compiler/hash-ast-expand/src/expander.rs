@@ -28,7 +28,7 @@ use hash_attrs::checks::AttrChecker;
 use hash_pipeline::{interface::CompilerOutputStream, settings::CompilerSettings};
 use hash_reporting::diagnostic::DiagnosticsMut;
 use hash_source::SourceId;
-use hash_target::data_layout::TargetDataLayout;
+use hash_target::{data_layout::TargetDataLayout, HasTarget};
 use hash_utils::crossbeam_channel::Sender;
 
 use crate::diagnostics::{
@@ -63,7 +63,7 @@ impl<'ctx> AstExpander<'ctx> {
             settings,
             stdout,
             diagnostics: ExpansionDiagnostics::new(),
-            checker: AttrChecker::new(id, data_layout),
+            checker: AttrChecker::new(id, data_layout, settings.target()),
         }
     }
 
@@ -101,6 +101,11 @@ impl AstVisitorMutSelf for AstExpander<'_> {
         self.check_macro_invocations(node.macros.ast_ref(), target);
 
         walk_mut_self::walk_expr_macro_invocation(self, node)?;
+
+        if let AttrNode::ModDef(def) = target {
+            self.mark_extern_members(def);
+        }
+
         Ok(())
     }
 
@@ -8,7 +8,7 @@ use expander::AstExpander;
 use hash_ast::ast::{AstVisitorMutSelf, OwnsAstNode};
 use hash_pipeline::{
     interface::{CompilerInterface, CompilerOutputStream, CompilerStage},
-    settings::{CompilerSettings, CompilerStageKind},
+    settings::{CompilerSettings, CompilerStageKind, EmitKind},
     workspace::{SourceStageInfo, Workspace},
 };
 use hash_reporting::reporter::Reports;
@@ -88,8 +88,8 @@ impl<Ctx: AstExpansionCtxQuery> CompilerStage<Ctx> for AstExpansionPass {
                 expander.emit_diagnostics_to(&sender);
             }
 
-            for (id, module) in node_map.iter_modules().enumerate() {
-                let source_id = SourceId::new_module(id as u32);
+            for (id, module) in node_map.iter_modules() {
+                let source_id = SourceId::from(id);
                 let stage_info = source_stage_info.get(source_id);
 
                 // Skip any modules that have already been de-sugared
@@ -136,7 +136,7 @@ impl<Ctx: AstExpansionCtxQuery> CompilerStage<Ctx> for AstExpansionPass {
         let settings = ctx.settings();
         let mut stdout = ctx.output_stream();
 
-        if settings.stage > CompilerStageKind::Parse && settings.ast_settings().dump {
+        if settings.stage > CompilerStageKind::Parse && settings.emits(EmitKind::Ast) {
             let set = settings.character_set;
             let mode = settings.ast_settings.dump_mode;
             ctx.workspace().print_sources(entry_point, mode, set, &mut stdout).unwrap();
@@ -130,7 +130,7 @@ impl AttrTarget {
             ast::Expr::Ty(_) => AttrTarget::Ty,
 
             // If this is a declaration, we have to recurse into the subject...
-            ast::Expr::Declaration(ast::Declaration { value, .. }) => {
+            ast::Expr::Declaration(ast::Declaration { value: Some(value), .. }) => {
                 AttrTarget::classify_expr(value.body())
             }
             _ => AttrTarget::Expr,
@@ -323,12 +323,13 @@ impl<'ast> AttrNode<'ast> {
     ///
     /// - If the expression is a declaration, we apply recurse and try to get
     ///   [ApplicationTarget] from the subject of the declaration. If the
-    ///   declaration does not have a `value` we return an empty [AttrTarget].
+    ///   declaration does not have a `value` then the declaration itself is the
+    ///   target.
     ///
     /// - Otherwise, get the equivalent [AttrTarget] from the expression.
     pub fn from_expr(expr: ast::AstNodeRef<'ast, ast::Expr>) -> Self {
         match expr.body() {
-            ast::Expr::Declaration(ast::Declaration { value, .. }) => {
+            ast::Expr::Declaration(ast::Declaration { value: Some(value), .. }) => {
                 Self::from_expr(value.ast_ref())
             }
             ast::Expr::Lit(lit) => Self::Lit(expr.with_body(lit.data.body())),
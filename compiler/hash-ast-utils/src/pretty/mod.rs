@@ -154,7 +154,7 @@ where
         &mut self,
         node: ast::AstNodeRef<ast::Declaration>,
     ) -> Result<Self::DeclarationRet, Self::Error> {
-        let ast::Declaration { pat, ty, value } = node.body();
+        let ast::Declaration { pat, ty, value, otherwise } = node.body();
 
         self.visit_pat(pat.ast_ref())?;
 
@@ -164,12 +164,24 @@ where
         if let Some(ty) = ty {
             self.write(" ")?;
             self.visit_ty(ty.ast_ref())?;
-            self.write(" ")?;
         }
 
-        // Visit the initialiser
-        self.write("= ")?;
-        self.visit_expr(value.ast_ref())
+        // Visit the initialiser, if there is one
+        if let Some(value) = value {
+            if ty.is_some() {
+                self.write(" ")?;
+            }
+
+            self.write("= ")?;
+            self.visit_expr(value.ast_ref())?;
+        }
+
+        if let Some(otherwise) = otherwise {
+            self.write(" else ")?;
+            self.visit_block(otherwise.ast_ref())?;
+        }
+
+        Ok(())
     }
 
     type EnumDefEntryRet = ();
@@ -283,6 +295,19 @@ where
         self.write("]")
     }
 
+    type RangeExprRet = ();
+
+    fn visit_range_expr(
+        &mut self,
+        node: ast::AstNodeRef<ast::RangeExpr>,
+    ) -> Result<Self::RangeExprRet, Self::Error> {
+        let ast::RangeExpr { lo, hi, end } = node.body();
+
+        self.visit_expr(lo.ast_ref())?;
+        self.write(format!("{}", end))?;
+        self.visit_expr(hi.ast_ref())
+    }
+
     type RepeatExprRet = ();
 
     fn visit_repeat_expr(
@@ -405,6 +430,15 @@ where
         self.write("continue")
     }
 
+    type HoleExprRet = ();
+
+    fn visit_hole_expr(
+        &mut self,
+        _: ast::AstNodeRef<ast::HoleExpr>,
+    ) -> Result<Self::HoleExprRet, Self::Error> {
+        self.write("?")
+    }
+
     type StrLitRet = ();
 
     fn visit_str_lit(
@@ -795,17 +829,35 @@ where
         &mut self,
         node: ast::AstNodeRef<ast::FnDef>,
     ) -> Result<Self::FnDefRet, Self::Error> {
-        let ast::FnDef { params, return_ty, fn_body } = node.body();
+        let ast::FnDef { params, return_ty, fn_body, c_variadic } = node.body();
 
-        self.visit_params(params.ast_ref())?;
+        if *c_variadic {
+            let mut opts = CollectionPrintingOptions::separated(", ");
+
+            if !params.params.is_empty() {
+                opts.with_ending_separator(", ");
+            }
+
+            self.write("(")?;
+            self.print_separated_collection(&params.params, opts, |this, param| {
+                this.visit_param(param)
+            })?;
+            self.write("...)")?;
+        } else {
+            self.visit_params(params.ast_ref())?;
+        }
 
         if let Some(return_ty) = return_ty {
             self.write(" -> ")?;
             self.visit_ty(return_ty.ast_ref())?;
         }
 
-        self.write(" => ")?;
-        self.visit_expr(fn_body.ast_ref())
+        if let Some(fn_body) = fn_body {
+            self.write(" => ")?;
+            self.visit_expr(fn_body.ast_ref())?;
+        }
+
+        Ok(())
     }
 
     type ImportExprRet = ();
@@ -940,10 +992,10 @@ where
 
         self.write("&")?;
 
-        if let Some(ref_kind) = kind
-            && *ref_kind.body() == ast::RefKind::Raw
-        {
-            self.write("raw ")?;
+        match kind.as_ref().map(|kind| *kind.body()) {
+            Some(ast::RefKind::Raw) => self.write("raw ")?,
+            Some(ast::RefKind::Rc) => self.write("rc ")?,
+            _ => {}
         }
 
         if let Some(mutability) = mutability {
@@ -1166,6 +1218,24 @@ where
         self.visit_expr(rhs.ast_ref())
     }
 
+    type ChainedComparisonExprRet = ();
+
+    fn visit_chained_comparison_expr(
+        &mut self,
+        node: ast::AstNodeRef<ast::ChainedComparisonExpr>,
+    ) -> Result<Self::ChainedComparisonExprRet, Self::Error> {
+        let ast::ChainedComparisonExpr { operands, operators } = node.body();
+
+        self.visit_expr(operands[0].ast_ref())?;
+
+        for (operator, operand) in operators.iter().zip(operands.iter().skip(1)) {
+            self.write(format!(" {} ", operator.body()))?;
+            self.visit_expr(operand.ast_ref())?;
+        }
+
+        Ok(())
+    }
+
     type LoopBlockRet = ();
 
     fn visit_loop_block(
@@ -1236,8 +1306,10 @@ where
         let ast::RefExpr { inner_expr, kind, mutability } = node.body();
         self.write("&")?;
 
-        if *kind == ast::RefKind::Raw {
-            self.write("raw ")?;
+        match kind {
+            ast::RefKind::Raw => self.write("raw ")?,
+            ast::RefKind::Rc => self.write("rc ")?,
+            ast::RefKind::Normal => {}
         }
 
         if let Some(value) = mutability
@@ -411,10 +411,10 @@ impl AstVisitor for AstTreePrinter {
     ) -> Result<Self::RefTyRet, Self::Error> {
         let walk::RefTy { inner, mutability, .. } = walk::walk_ref_ty(self, node)?;
 
-        let label = if node.kind.as_ref().map_or(false, |t| *t.body() == ast::RefKind::Raw) {
-            "raw_ref"
-        } else {
-            "ref"
+        let label = match node.kind.as_ref().map(|t| *t.body()) {
+            Some(ast::RefKind::Raw) => "raw_ref",
+            Some(ast::RefKind::Rc) => "rc_ref",
+            _ => "ref",
         };
 
         Ok(TreeNode::branch(
@@ -489,8 +489,9 @@ impl AstVisitor for AstTreePrinter {
         Ok(TreeNode::branch(
             "function_def",
             iter::once(params)
+                .chain(node.c_variadic.then(|| TreeNode::leaf("c_variadic")))
                 .chain(return_ty.map(|r| TreeNode::branch("return_type", vec![r])))
-                .chain(iter::once(fn_body))
+                .chain(fn_body)
                 .collect(),
         ))
     }
@@ -710,6 +711,14 @@ impl AstVisitor for AstTreePrinter {
         Ok(TreeNode::leaf("continue"))
     }
 
+    type HoleExprRet = TreeNode;
+    fn visit_hole_expr(
+        &self,
+        _: ast::AstNodeRef<ast::HoleExpr>,
+    ) -> Result<Self::HoleExprRet, Self::Error> {
+        Ok(TreeNode::leaf("hole"))
+    }
+
     type VisibilityRet = TreeNode;
     fn visit_visibility(
         &self,
@@ -747,13 +756,14 @@ impl AstVisitor for AstTreePrinter {
         &self,
         node: ast::AstNodeRef<ast::Declaration>,
     ) -> Result<Self::DeclarationRet, Self::Error> {
-        let walk::Declaration { pat, ty, value } = walk::walk_declaration(self, node)?;
+        let walk::Declaration { pat, ty, value, otherwise } = walk::walk_declaration(self, node)?;
 
         Ok(TreeNode::branch(
             "declaration",
             iter::once(TreeNode::branch("pattern", vec![pat]))
                 .chain(ty.map(|t| TreeNode::branch("type", vec![t])))
-                .chain(iter::once(TreeNode::branch("value", vec![value])))
+                .chain(value.map(|v| TreeNode::branch("value", vec![v])))
+                .chain(otherwise.map(|o| TreeNode::branch("otherwise", vec![o])))
                 .collect(),
         ))
     }
@@ -795,6 +805,20 @@ impl AstVisitor for AstTreePrinter {
         ))
     }
 
+    type ChainedComparisonExprRet = TreeNode;
+    fn visit_chained_comparison_expr(
+        &self,
+        node: ast::AstNodeRef<ast::ChainedComparisonExpr>,
+    ) -> Result<Self::ChainedComparisonExprRet, Self::Error> {
+        let walk::ChainedComparisonExpr { operands, operators } =
+            walk::walk_chained_comparison_expr(self, node)?;
+
+        Ok(TreeNode::branch(
+            "chained_comparison",
+            vec![TreeNode::branch("operators", operators), TreeNode::branch("operands", operands)],
+        ))
+    }
+
     type UnaryExprRet = TreeNode;
 
     fn visit_unary_expr(
@@ -822,6 +846,23 @@ impl AstVisitor for AstTreePrinter {
         ))
     }
 
+    type RangeExprRet = TreeNode;
+    fn visit_range_expr(
+        &self,
+        node: ast::AstNodeRef<ast::RangeExpr>,
+    ) -> Result<Self::RangeExprRet, Self::Error> {
+        let walk::RangeExpr { lo, hi } = walk::walk_range_expr(self, node)?;
+
+        Ok(TreeNode::branch(
+            "range",
+            vec![
+                TreeNode::branch("lo", vec![lo]),
+                TreeNode::branch("hi", vec![hi]),
+                TreeNode::leaf(labelled("end", format!("{}", node.body().end), "`")),
+            ],
+        ))
+    }
+
     type RepeatExprRet = TreeNode;
 
     fn visit_repeat_expr(
@@ -680,12 +680,15 @@ define_tree! {
         pub property: Child!(Name),
     }
 
-    /// Reference kind representing either a raw reference or a normal reference.
+    /// Reference kind representing either a raw reference, a reference
+    /// counted reference or a normal reference.
     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
     #[node]
     pub enum RefKind {
         /// Raw reference type
         Raw,
+        /// Reference counted reference type
+        Rc,
         /// Normal reference type
         Normal,
     }
@@ -1256,7 +1259,8 @@ define_tree! {
         pub fn_body: Child!(Expr),
     }
 
-    /// A declaration, e.g. `x := 3;`.
+    /// A declaration, e.g. `x := 3;`, or `Some(x) := y else { return };`
+    /// if the pattern is refutable.
     #[derive(Debug, PartialEq, Clone)]
     #[node]
     pub struct Declaration {
@@ -1267,8 +1271,13 @@ define_tree! {
         pub ty: OptionalChild!(Ty),
 
         /// Any value that is assigned to the binding, simply
-        /// an expression.
-        pub value: Child!(Expr),
+        /// an expression. Declarations within a body block can omit the
+        /// value, the binding is then initialised later by an assignment.
+        pub value: OptionalChild!(Expr),
+
+        /// The block that is run if the value doesn't match the pattern, the
+        /// block must diverge.
+        pub otherwise: OptionalChild!(Block),
     }
 
     /// Unary operators that are defined within the core of the language.
@@ -1539,6 +1548,13 @@ define_tree! {
     #[node]
     pub struct ContinueStatement;
 
+    /// A typed hole expression, either `?` or `_`. A hole always fails to
+    /// typecheck, and instead reports the type that is expected in its place
+    /// along with the types of the bindings that are in scope.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[node]
+    pub struct HoleExpr;
+
     /// A branch/"case" of a `match` block.
     #[derive(Debug, PartialEq, Clone)]
     #[node]
@@ -1944,7 +1960,15 @@ define_tree! {
         pub return_ty: OptionalChild!(Ty),
 
         /// The body/contents of the function, in the form of an expression.
-        pub fn_body: Child!(Expr),
+        ///
+        /// This is [None] for declarations of foreign functions, i.e. the
+        /// functions within an `#extern(...)` module.
+        pub fn_body: OptionalChild!(Expr),
+
+        /// Whether the parameters of the function are followed by a `...`,
+        /// meaning that the function accepts a variable number of arguments
+        /// in the same way as a C variadic function.
+        pub c_variadic: bool,
     }
 
     /// Generic argument with a optional name, expression and optional
@@ -2110,6 +2134,18 @@ define_tree! {
         pub operator: Child!(BinOp),
     }
 
+    /// A chain of ordering comparisons `a < b <= c`, which means the same as
+    /// `a < b && b <= c` except that `b` is only evaluated once.
+    #[derive(Debug, PartialEq, Clone)]
+    #[node]
+    pub struct ChainedComparisonExpr {
+        /// The operands of the chain, there is always one more operand than
+        /// there are operators.
+        pub operands: Children!(Expr),
+        /// The comparison operators between each pair of adjacent operands.
+        pub operators: Children!(BinOp),
+    }
+
     /// A unary expression `!a`.
     #[derive(Debug, PartialEq, Clone)]
     #[node]
@@ -2130,6 +2166,21 @@ define_tree! {
         pub index_expr: Child!(Expr),
     }
 
+    /// A range expression, which has a `lo` and `hi` bound, and an `end`
+    /// which specifies if `hi` is included in the range, e.g. `0..<len`.
+    #[derive(Debug, PartialEq, Clone)]
+    #[node]
+    pub struct RangeExpr {
+        /// Initial bound of the range
+        pub lo: Child!(Expr),
+
+        /// Upper bound of the range
+        pub hi: Child!(Expr),
+
+        /// Whether the `end` is included or not
+        pub end: RangeEnd,
+    }
+
     /// A repeat expression `[x; 5]`.
     #[derive(Debug, PartialEq, Clone)]
     #[node]
@@ -2221,6 +2272,9 @@ define_tree! {
         /// Continue statement e.g. `continue`
         Continue(ContinueStatement),
 
+        /// A typed hole e.g. `?` or `_`
+        Hole(HoleExpr),
+
         /// Expression to index a subject e.g. `arr[x]`
         Index(IndexExpr),
 
@@ -2228,6 +2282,10 @@ define_tree! {
         /// repeated a certain number of times, e.g. `[1; 5]`.
         Repeat(RepeatExpr),
 
+        /// A range of values e.g. `0..<10`, this is only used as the iterable
+        /// of a `for` loop.
+        Range(RangeExpr),
+
         /// An expression that captures a variable or a pattern being assigned
         /// to a right hand-side expression such as `x = 3`.
         Assign(AssignExpr),
@@ -2240,6 +2298,9 @@ define_tree! {
         /// operator
         BinaryExpr(BinaryExpr),
 
+        /// A chain of ordering comparisons, e.g. `a < b < c`
+        ChainedComparison(ChainedComparisonExpr),
+
         /// Unary Expression composed of a unary operator and an expression
         UnaryExpr(UnaryExpr),
     }
@@ -2269,7 +2330,7 @@ mod size_asserts {
 
     use super::*;
 
-    static_assert_size!(Expr, 56);
+    static_assert_size!(Expr, 72);
     static_assert_size!(Pat, 56);
     static_assert_size!(Ty, 56);
 }
@@ -2,8 +2,8 @@
 //! This is used to ensure that the same node is not parsed and to retrieve
 //! nodes in later compilation stages.
 use std::{
+    collections::BTreeMap,
     path::{Path, PathBuf},
-    slice::{Iter, IterMut},
 };
 
 use hash_source::{InteractiveId, ModuleId, SourceId};
@@ -112,8 +112,11 @@ pub enum SourceRef<'i> {
 /// parsed within the current [Workspace].
 #[derive(Debug, Default)]
 pub struct NodeMap {
-    /// All [Module] nodes that have been parsed.
-    modules: IndexVec<ModuleId, ModuleEntry>,
+    /// All [Module] nodes that have been parsed. The modules are stored by
+    /// their [ModuleId] rather than in the order that they were parsed, since
+    /// the ids are allocated by the global source map, and may be shared
+    /// with other compilations within the same process.
+    modules: BTreeMap<ModuleId, ModuleEntry>,
     /// All [InteractiveBlock] nodes that have been parsed.
     interactive_blocks: IndexVec<InteractiveId, InteractiveBlock>,
 }
@@ -133,7 +136,7 @@ pub trait HasNodeMap {
 impl NodeMap {
     /// Create a new [NodeMap]
     pub fn new() -> Self {
-        Self { modules: index_vec![], interactive_blocks: index_vec![] }
+        Self { modules: BTreeMap::new(), interactive_blocks: index_vec![] }
     }
 
     /// Add a [InteractiveBlock] to the [NodeMap]
@@ -141,9 +144,9 @@ impl NodeMap {
         self.interactive_blocks.push(block);
     }
 
-    /// Add a [Module] to the [NodeMap]
-    pub fn add_module(&mut self, module: ModuleEntry) {
-        self.modules.push(module);
+    /// Add the [Module] with the given [ModuleId] to the [NodeMap]
+    pub fn add_module(&mut self, id: ModuleId, module: ModuleEntry) {
+        self.modules.insert(id, module);
     }
 
     /// Get a [SourceRef] by [SourceId].
@@ -170,22 +173,24 @@ impl NodeMap {
     /// Get a mutable reference to an [Module], panics if the [SourceId]
     /// has no backing [Module].
     pub fn get_module(&self, id: ModuleId) -> &ModuleEntry {
-        self.modules.get(id).unwrap()
+        self.modules.get(&id).unwrap()
     }
 
     /// Get a reference to an [Module], panics if the [SourceId]
     /// has no backing [Module].
     pub fn get_module_mut(&mut self, id: ModuleId) -> &mut ModuleEntry {
-        self.modules.get_mut(id).unwrap()
+        self.modules.get_mut(&id).unwrap()
     }
 
-    /// /// Create an [Iter] over the currently stores modules within [NodeMap]
-    pub fn iter_modules(&self) -> Iter<ModuleEntry> {
-        self.modules.iter()
+    /// Iterate over the currently stored modules within [NodeMap], in the
+    /// order of their [ModuleId]s.
+    pub fn iter_modules(&self) -> impl Iterator<Item = (ModuleId, &ModuleEntry)> {
+        self.modules.iter().map(|(id, module)| (*id, module))
     }
 
-    /// Create an [IterMut] over the currently stores modules within [NodeMap].
-    pub fn iter_mut_modules(&mut self) -> IterMut<ModuleEntry> {
-        self.modules.iter_mut()
+    /// Iterate mutably over the currently stored modules within [NodeMap], in
+    /// the order of their [ModuleId]s.
+    pub fn iter_mut_modules(&mut self) -> impl Iterator<Item = (ModuleId, &mut ModuleEntry)> {
+        self.modules.iter_mut().map(|(id, module)| (*id, module))
     }
 }
@@ -4,24 +4,27 @@ use std::{fmt, sync::OnceLock};
 
 use hash_ast::{ast, ast::AstNodeId};
 use hash_ast_utils::lit::{LitHelpers, LitParseResult};
-use hash_repr::constant::Const;
+use hash_repr::constant::{Const, ConstKind};
 use hash_source::{identifier::Identifier, location::Span};
 use hash_storage::store::{DefaultPartialStore, PartialStore};
-use hash_target::{primitives::IntTy, size::Size};
+use hash_target::{abi::Abi, alignment::Alignment, primitives::IntTy, size::Size};
 use hash_tir::tir::{ParamIndex, TyId};
 use hash_utils::{
     derive_more::From, fxhash::FxHashMap, lazy_static::lazy_static, num_bigint::BigInt,
 };
 
 use crate::{
+    builtin::attrs,
     diagnostics::{AttrError, AttrResult},
     ty::{repr_ty_from_primitive_ty, AttrId},
 };
 
 /// Valid `#[repr(...)]` options, ideally we should be able to just generate
 /// this in the macro.
-pub(crate) const REPR_OPTIONS: &[&str] =
-    &["c", "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128"];
+pub(crate) const REPR_OPTIONS: &[&str] = &[
+    "c", "packed", "simd", "align(n)", "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64",
+    "u128", "i128",
+];
 
 /// A representation of the variants that the `repr` attribute
 /// can be.
@@ -34,6 +37,19 @@ pub enum ReprAttr {
     /// The representation is annotated with a `u8`, `u16`, `u32`, `u64`,
     /// `u128`, or `usize`.
     Int(IntTy),
+
+    /// The representation specifies that the fields of the type should
+    /// be laid out without any padding, i.e. every field has an alignment
+    /// of one byte.
+    Packed,
+
+    /// The representation specifies a minimum alignment for the type, the
+    /// alignment must be a power of two.
+    Align(Alignment),
+
+    /// The representation specifies that the type is a SIMD vector, all of
+    /// the fields of the type must be of the same integer or float type.
+    Simd,
 }
 
 impl ReprAttr {
@@ -42,8 +58,21 @@ impl ReprAttr {
         let arg = attr.get_arg(0).unwrap();
         let inner = arg.value.as_alloc().to_str();
 
+        if let Some(value) = inner.strip_prefix("align(").and_then(|inner| inner.strip_suffix(')'))
+        {
+            // The alignment must be a non-zero power of two.
+            return match value.trim().parse::<u64>() {
+                Ok(bytes) if bytes != 0 => Alignment::from_bytes(bytes)
+                    .map(ReprAttr::Align)
+                    .map_err(|_| AttrError::InvalidReprAlign { arg: *arg }),
+                _ => Err(AttrError::InvalidReprAlign { arg: *arg }),
+            };
+        }
+
         match inner.as_str() {
             "c" => Ok(ReprAttr::C),
+            "packed" => Ok(ReprAttr::Packed),
+            "simd" => Ok(ReprAttr::Simd),
             kind => {
                 let Ok(ty) = IntTy::try_from(Identifier::from(kind)) else {
                     return Err(AttrError::UnknownReprArg { arg: *arg });
@@ -60,6 +89,213 @@ impl ReprAttr {
     }
 }
 
+/// Valid `#derive(...)` options.
+pub(crate) const DERIVE_OPTIONS: &[&str] = &["eq", "hash", "debug", "serialize"];
+
+/// A representation of the items that can be synthesised by
+/// the `derive` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeriveAttr {
+    /// Synthesise a field-wise equality function for the type, with
+    /// the signature `(&T, &T) -> bool`.
+    Eq,
+
+    /// Synthesise a field-wise hashing function for the type, with
+    /// the signature `(&T) -> u64`.
+    Hash,
+
+    /// Synthesise a function which prints the type to the standard
+    /// output, with the signature `(&T) -> ()`.
+    Debug,
+
+    /// Synthesise a function which encodes the type into a little-endian
+    /// binary format, with the signature `(&T, &raw u8, usize) -> usize`. The
+    /// function writes the value at the given offset of the buffer, and
+    /// returns the offset after the written bytes.
+    Serialize,
+
+    /// Synthesise a function which decodes the type from the binary format
+    /// that is produced by [DeriveAttr::Serialize], with the signature
+    /// `(&raw u8, usize, &mut T) -> usize`. This item cannot be requested
+    /// directly, it is always synthesised alongside [DeriveAttr::Serialize].
+    Deserialize,
+}
+
+impl DeriveAttr {
+    /// Parse a [DeriveAttr] from an argument of the `#derive` attribute.
+    pub fn parse(arg: &AttrValue) -> AttrResult<Self> {
+        match arg.value.as_alloc().to_str().as_str() {
+            "eq" => Ok(DeriveAttr::Eq),
+            "hash" => Ok(DeriveAttr::Hash),
+            "debug" => Ok(DeriveAttr::Debug),
+            "serialize" => Ok(DeriveAttr::Serialize),
+            _ => Err(AttrError::UnknownDeriveArg { arg: *arg }),
+        }
+    }
+
+    /// Parse all of the arguments of a `#derive` attribute in the order that
+    /// they were specified.
+    pub fn parse_all(attr: &Attr) -> AttrResult<Vec<Self>> {
+        (0..attr.args.len() as u32)
+            .filter_map(|index| attr.get_arg(AttrArgIdx::Position(index)))
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// Get all of the items that are synthesised when deriving this item,
+    /// deriving `serialize` produces both an encoder and a decoder.
+    pub fn synthesised(self) -> &'static [Self] {
+        match self {
+            DeriveAttr::Eq => &[DeriveAttr::Eq],
+            DeriveAttr::Hash => &[DeriveAttr::Hash],
+            DeriveAttr::Debug => &[DeriveAttr::Debug],
+            DeriveAttr::Serialize | DeriveAttr::Deserialize => {
+                &[DeriveAttr::Serialize, DeriveAttr::Deserialize]
+            }
+        }
+    }
+
+    /// Get all of the [DeriveAttr]s that have been applied onto the given
+    /// node, including any items that are synthesised alongside them. This
+    /// assumes that the attribute has already been checked.
+    pub fn on_node(id: AstNodeId) -> Vec<Self> {
+        attr_store()
+            .get_attr(id, attrs::DERIVE)
+            .map(|attr| {
+                Self::parse_all(&attr)
+                    .unwrap()
+                    .into_iter()
+                    .flat_map(|item| item.synthesised().iter().copied())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for DeriveAttr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeriveAttr::Eq => write!(f, "eq"),
+            DeriveAttr::Hash => write!(f, "hash"),
+            DeriveAttr::Debug => write!(f, "debug"),
+            DeriveAttr::Serialize => write!(f, "serialize"),
+            DeriveAttr::Deserialize => write!(f, "deserialize"),
+        }
+    }
+}
+
+/// Valid `#optimize(...)` options.
+pub(crate) const OPTIMIZE_OPTIONS: &[&str] = &["none", "speed", "size"];
+
+/// A representation of the optimisation level that is requested for a
+/// particular function by the `optimize` attribute. The attribute overrides
+/// the optimisation level of the compilation for the function body only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptimizeAttr {
+    /// Don't optimise the function at all, this is useful when debugging the
+    /// generated code of a single function.
+    None,
+
+    /// Optimise the function for speed.
+    Speed,
+
+    /// Optimise the function for size rather than speed.
+    Size,
+}
+
+impl OptimizeAttr {
+    /// Parse a [OptimizeAttr] from an [Attr].
+    pub fn parse(attr: &Attr) -> AttrResult<Self> {
+        let arg = attr.get_arg(0).unwrap();
+
+        match arg.value.as_alloc().to_str().as_str() {
+            "none" => Ok(OptimizeAttr::None),
+            "speed" => Ok(OptimizeAttr::Speed),
+            "size" => Ok(OptimizeAttr::Size),
+            _ => Err(AttrError::UnknownOptimizeArg { arg: *arg }),
+        }
+    }
+
+    /// Get the [OptimizeAttr] that has been applied onto the given node, if
+    /// any. This assumes that the attribute has already been checked.
+    pub fn on_node(id: AstNodeId) -> Option<Self> {
+        attr_store().get_attr(id, attrs::OPTIMIZE).map(|attr| Self::parse(&attr).unwrap())
+    }
+}
+
+/// Valid `#convention(...)` options.
+pub(crate) const CONVENTION_OPTIONS: &[&str] = &["hash", "C", "fast", "system", "cold"];
+
+/// The calling convention that is requested for a particular function by the
+/// `convention` attribute. Functions use the [`Abi::Hash`] convention by
+/// default, and foreign functions use the [`Abi::C`] convention by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConventionAttr(pub Abi);
+
+impl ConventionAttr {
+    /// Parse a [ConventionAttr] from an [Attr].
+    pub fn parse(attr: &Attr) -> AttrResult<Self> {
+        let arg = attr.get_arg(0).unwrap();
+
+        match arg.value.as_alloc().to_str().as_str() {
+            "hash" => Ok(ConventionAttr(Abi::Hash)),
+            "C" => Ok(ConventionAttr(Abi::C)),
+            "fast" => Ok(ConventionAttr(Abi::Fast)),
+            "system" => Ok(ConventionAttr(Abi::System)),
+            "cold" => Ok(ConventionAttr(Abi::Cold)),
+            _ => Err(AttrError::UnknownConventionArg { arg: *arg }),
+        }
+    }
+
+    /// Get the [ConventionAttr] that has been applied onto the given node, if
+    /// any. This assumes that the attribute has already been checked.
+    pub fn on_node(id: AstNodeId) -> Option<Self> {
+        attr_store().get_attr(id, attrs::CONVENTION).map(|attr| Self::parse(&attr).unwrap())
+    }
+
+    /// Get the calling convention of the function that is defined by the
+    /// given node, this is the [ConventionAttr] of the function if it has
+    /// one, otherwise [`Abi::C`] for `#foreign` functions, and [`Abi::Hash`]
+    /// for all other functions.
+    pub fn abi_of_fn(id: AstNodeId) -> Abi {
+        match Self::on_node(id) {
+            Some(ConventionAttr(abi)) => abi,
+            None if attr_store().node_has_attr(id, attrs::FOREIGN) => Abi::C,
+            None => Abi::Hash,
+        }
+    }
+}
+
+/// Valid `#extern(...)` options.
+pub(crate) const EXTERN_OPTIONS: &[&str] = &["C"];
+
+/// The ABI of the functions that are declared within an `#extern(...)`
+/// module. All of the functions within the module are treated as being
+/// `#foreign`, and are called using the given ABI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternAttr {
+    /// The functions use the C calling convention of the target.
+    C,
+}
+
+impl ExternAttr {
+    /// Parse a [ExternAttr] from an [Attr].
+    pub fn parse(attr: &Attr) -> AttrResult<Self> {
+        let arg = attr.get_arg(0).unwrap();
+
+        match arg.value.as_alloc().to_str().as_str() {
+            "C" => Ok(ExternAttr::C),
+            _ => Err(AttrError::UnknownExternArg { arg: *arg }),
+        }
+    }
+
+    /// Get the [ExternAttr] that has been applied onto the given node, if
+    /// any. This assumes that the attribute has already been checked.
+    pub fn on_node(id: AstNodeId) -> Option<Self> {
+        attr_store().get_attr(id, attrs::EXTERN).map(|attr| Self::parse(&attr).unwrap())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Attr {
     /// The name of the attribute.
@@ -172,7 +408,11 @@ impl AttrValue {
 
 impl fmt::Display for AttrValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.value)
+        match self.value.kind() {
+            // String arguments are printed without their allocation details.
+            ConstKind::Pair { data, .. } => write!(f, "{}", data.to_str()),
+            _ => write!(f, "{:?}", self.value),
+        }
     }
 }
 
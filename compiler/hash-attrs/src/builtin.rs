@@ -27,6 +27,15 @@ macro_rules! define_attr {
     // better done using a procedural macro, so that we can emit better errors.
     //
     // @@Improve: ensure that provided argument names are unique!
+    ($table:expr, $name:ident, { (.. $arg:ident : $ty:ident), $subject:expr }) => {
+        let name: Identifier = stringify!($name).into();
+        let params = gen::params([(gen::sym(stringify!($arg)), make_ty!($ty), None)]);
+
+        let index = $table.map.push(AttrTy::new_variadic(name, params, $subject));
+        if $table.name_map.insert(name, index).is_some() {
+            panic!("duplicate attribute name: `{}`", name);
+        }
+    };
     ($table:expr, $name:ident, { ($($arg:ident : $ty:ident),*), $subject:expr }) => {
         let name: Identifier = stringify!($name).into();
 
@@ -98,15 +107,25 @@ define_attrs!(
     // ------------------------------------------
     lang {  AttrTarget::FnDef },
     intrinsics {AttrTarget::ModDef },
+    extern { (abi: str), AttrTarget::ModDef },
     entry_point {  AttrTarget::FnDef },
     pure {  AttrTarget::FnDef },
     foreign {  AttrTarget::FnDef },
+    unsafe_fn {  AttrTarget::FnDef },
     no_mangle {  AttrTarget::FnDef },
     link_name { (name: str), AttrTarget::FnDef },
+    link { (library: str), AttrTarget::FnDef },
+    optimize { (level: str), AttrTarget::FnDef },
+    convention { (name: str), AttrTarget::FnDef },
 
     // ------------------------------------------
     // Type representation attributes.
     // ------------------------------------------
     repr { (abi: str), AttrTarget::StructDef | AttrTarget::EnumDef },
-    discriminant { (value: i128), AttrTarget::EnumVariant }
+    discriminant { (value: i128), AttrTarget::EnumVariant },
+
+    // ------------------------------------------
+    // Item synthesis attributes.
+    // ------------------------------------------
+    derive { (..traits: str), AttrTarget::StructDef | AttrTarget::EnumDef }
 );
@@ -3,10 +3,10 @@
 //!  
 use hash_ast_utils::attr::{AttrNode, AttrTarget};
 use hash_source::SourceId;
-use hash_target::data_layout::TargetDataLayout;
+use hash_target::{abi::Abi, data_layout::TargetDataLayout, Target};
 
 use crate::{
-    attr::{Attr, Attrs, ReprAttr},
+    attr::{Attr, Attrs, ConventionAttr, DeriveAttr, ExternAttr, OptimizeAttr, ReprAttr},
     builtin::attrs,
     diagnostics::{AttrError, AttrResult, AttrWarning},
 };
@@ -21,14 +21,21 @@ pub struct AttrChecker<'env> {
     /// Any warnings collected by the checker.
     warnings: Vec<AttrWarning>,
 
-    /// The current compilation target.
+    /// The data layout of the current compilation target.
     _data_layout: &'env TargetDataLayout,
+
+    /// The current compilation target.
+    target: &'env Target,
 }
 
 impl<'env> AttrChecker<'env> {
     /// Create a new [AttrChecker].
-    pub fn new(source: SourceId, target: &'env TargetDataLayout) -> Self {
-        Self { source, warnings: Vec::new(), _data_layout: target }
+    pub fn new(
+        source: SourceId,
+        data_layout: &'env TargetDataLayout,
+        target: &'env Target,
+    ) -> Self {
+        Self { source, warnings: Vec::new(), _data_layout: data_layout, target }
     }
 
     /// Take any warnings that the checker has collected.
@@ -41,7 +48,10 @@ impl<'env> AttrChecker<'env> {
         match attr.id {
             attrs::INTRINSICS => self.check_intrinsics_attr(attrs, attr, node)?,
             attrs::REPR => self.check_repr_attr(attrs, attr, node)?,
-            attrs::LAYOUT_OF => self.check_layout_of_attr(attrs, attr, node)?,
+            attrs::DERIVE => self.check_derive_attr(attrs, attr, node)?,
+            attrs::OPTIMIZE => self.check_optimize_attr(attrs, attr)?,
+            attrs::EXTERN => self.check_extern_attr(attrs, attr)?,
+            attrs::CONVENTION => self.check_convention_attr(attrs, attr, node)?,
             _ => {
                 // By default, check if we are trying to apply the attribute twice.
                 self.check_duplicate_attr(attrs, attr)?;
@@ -94,6 +104,9 @@ impl<'env> AttrChecker<'env> {
     /// - If the repr hint is given as `u8`, `u16`, `u32`, `u64`, or `u128` and
     ///   attempted to be applied to a struct definition.
     ///
+    /// - If the repr hint is given as `packed` or `simd` and attempted to be
+    ///   applied to an enum definition.
+    ///
     /// - If a previous repr hint has been applied to the item, and the new repr
     ///   are incompatible.
     fn check_repr_attr(&mut self, attrs: &Attrs, attr: &Attr, node: AttrNode<'_>) -> AttrResult {
@@ -112,6 +125,16 @@ impl<'env> AttrChecker<'env> {
             });
         }
 
+        if let ReprAttr::Packed | ReprAttr::Simd = repr
+            && let AttrNode::EnumDef(_) = node
+        {
+            return Err(AttrError::InvalidReprForItem {
+                origin: attr.origin,
+                item: AttrTarget::EnumDef,
+                arg: *arg,
+            });
+        }
+
         // Check if we have a conflicting representation argument with a previously
         // applied representation argument.
         if let Some(prev) = attrs.get_attr(attr.id) {
@@ -131,31 +154,110 @@ impl<'env> AttrChecker<'env> {
         Ok(())
     }
 
-    /// Check that the `#layout_of` attribute application is valid.
+    /// Check that the `#derive` attribute application is valid.
     ///
     /// # Errors
+    /// - If any of the arguments is not an item that can be derived.
+    ///
     /// - If the attribute is applied to a struct or enum definition with
     ///   generics.
-    fn check_layout_of_attr(
-        &mut self,
-        attrs: &Attrs,
-        attr: &Attr,
-        node: AttrNode<'_>,
-    ) -> AttrResult {
+    fn check_derive_attr(&mut self, attrs: &Attrs, attr: &Attr, node: AttrNode<'_>) -> AttrResult {
         self.check_duplicate_attr(attrs, attr)?;
+        DeriveAttr::parse_all(attr)?;
 
         let (maybe_params, item) = match &node {
             AttrNode::StructDef(def) => (def.ty_params.as_ref(), AttrTarget::StructDef),
             AttrNode::EnumDef(def) => (def.ty_params.as_ref(), AttrTarget::EnumDef),
-            _ => unreachable!("`#layout_of` attribute applied to non-struct/enum item"),
+            _ => unreachable!("`#derive` attribute applied to non-struct/enum item"),
         };
 
+        // @@Future: support deriving items for generic types, this requires
+        // synthesising the items for each instantiation of the type.
         if let Some(params) = maybe_params
             && !params.body().params.is_empty()
         {
-            Err(AttrError::LayoutOfGenericItem { origin: node.id(), generics: params.id(), item })
+            Err(AttrError::DeriveOnGenericItem { origin: node.id(), generics: params.id(), item })
         } else {
             Ok(())
         }
     }
+
+    /// Check that an `#optimize(...)` attribute application is valid.
+    ///
+    /// # Errors
+    /// - If the argument is not a known optimisation level.
+    ///
+    /// - If a previous `#optimize` attribute has been applied to the item with
+    ///   a different argument.
+    fn check_optimize_attr(&mut self, attrs: &Attrs, attr: &Attr) -> AttrResult {
+        let level = OptimizeAttr::parse(attr)?;
+
+        if let Some(prev) = attrs.get_attr(attr.id) {
+            if OptimizeAttr::parse(prev).unwrap() != level {
+                return Err(AttrError::IncompatibleOptimizeArgs {
+                    origin: prev.origin,
+                    second: attr.origin,
+                });
+            } else {
+                self.check_duplicate_attr(attrs, attr)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that an `#extern(...)` attribute application is valid.
+    ///
+    /// # Errors
+    /// - If the argument is not a known ABI.
+    fn check_extern_attr(&mut self, attrs: &Attrs, attr: &Attr) -> AttrResult {
+        ExternAttr::parse(attr)?;
+        self.check_duplicate_attr(attrs, attr)
+    }
+
+    /// Check that a `#convention(...)` attribute application is valid.
+    ///
+    /// # Errors
+    /// - If the argument is not a known calling convention.
+    ///
+    /// - If the calling convention is not supported by the current target.
+    ///
+    /// - If the function accepts a variable number of arguments, and the
+    ///   calling convention is not `C`.
+    ///
+    /// - If a previous `#convention` attribute has been applied to the item
+    ///   with a different argument.
+    fn check_convention_attr(
+        &mut self,
+        attrs: &Attrs,
+        attr: &Attr,
+        node: AttrNode<'_>,
+    ) -> AttrResult {
+        let arg = attr.get_arg(0).unwrap();
+        let ConventionAttr(abi) = ConventionAttr::parse(attr)?;
+
+        if !abi.is_supported_on(self.target.arch) {
+            return Err(AttrError::UnsupportedConvention { arg: *arg, arch: self.target.arch });
+        }
+
+        if let AttrNode::FnDef(def) = node
+            && def.c_variadic
+            && abi != Abi::C
+        {
+            return Err(AttrError::NonCVariadicConvention { arg: *arg, item: def.id() });
+        }
+
+        if let Some(prev) = attrs.get_attr(attr.id) {
+            if ConventionAttr::parse(prev).unwrap() != ConventionAttr(abi) {
+                return Err(AttrError::IncompatibleConventionArgs {
+                    origin: prev.origin,
+                    second: attr.origin,
+                });
+            } else {
+                self.check_duplicate_attr(attrs, attr)?;
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -2,10 +2,16 @@
 
 use hash_ast::ast::AstNodeId;
 use hash_ast_utils::attr::AttrTarget;
-use hash_reporting::reporter::Reporter;
+use hash_reporting::{
+    hash_error_codes::{error_codes::HashErrorCode, warning_codes::HashWarningCode},
+    reporter::Reporter,
+};
+use hash_target::TargetArch;
 use hash_utils::printing::SequenceDisplay;
 
-use crate::attr::{AttrValue, REPR_OPTIONS};
+use crate::attr::{
+    AttrValue, CONVENTION_OPTIONS, DERIVE_OPTIONS, EXTERN_OPTIONS, OPTIMIZE_OPTIONS, REPR_OPTIONS,
+};
 
 /// Utility type which wraps a [Result] with an [AttrError].
 pub type AttrResult<T = ()> = Result<T, AttrError>;
@@ -51,13 +57,26 @@ pub enum AttrError {
         arg: AttrValue,
     },
 
-    /// When `#layout_of` is being applied to a `struct` or
-    /// `enum` value with a non-zero amount of generics.
-    LayoutOfGenericItem {
-        /// The origin of the `#layout_of` attribute.
+    /// When a `ubig` or `ibig` is being used as a `repr` value.
+    InvalidReprIntKind { arg: AttrValue },
+
+    /// When the alignment of an `align(n)` repr value is not a non-zero
+    /// power of two.
+    InvalidReprAlign { arg: AttrValue },
+
+    /// When a `derive` value is not a known item that can be derived.
+    UnknownDeriveArg {
+        /// The unknown argument of the `derive` attribute.
+        arg: AttrValue,
+    },
+
+    /// When `#derive` is being applied to a `struct` or `enum` with
+    /// generic parameters.
+    DeriveOnGenericItem {
+        /// The origin of the `#derive` attribute.
         origin: AstNodeId,
 
-        /// The item that the `#layout_of` is being applied to. It will always
+        /// The item that the `#derive` is being applied to. It will always
         /// be either a `enum` or a `struct` here.
         item: AttrTarget,
 
@@ -65,8 +84,63 @@ pub enum AttrError {
         generics: AstNodeId,
     },
 
-    /// When a `ubig` or `ibig` is being used as a `repr` value.
-    InvalidReprIntKind { arg: AttrValue },
+    /// When an `optimize` value is not a known optimisation level.
+    UnknownOptimizeArg {
+        /// The unknown argument of the `optimize` attribute.
+        arg: AttrValue,
+    },
+
+    /// When multiple `optimize` attributes are being applied whilst
+    /// having different arguments.
+    IncompatibleOptimizeArgs {
+        /// The origin of the first `optimize` attribute.
+        origin: AstNodeId,
+
+        /// The origin of the second `optimize` attribute.
+        second: AstNodeId,
+    },
+
+    /// When an `extern` value is not a known ABI.
+    UnknownExternArg {
+        /// The unknown argument of the `extern` attribute.
+        arg: AttrValue,
+    },
+
+    /// When a `convention` value is not a known calling convention.
+    UnknownConventionArg {
+        /// The unknown argument of the `convention` attribute.
+        arg: AttrValue,
+    },
+
+    /// When a `convention` value is a calling convention that cannot be used
+    /// on the current target.
+    UnsupportedConvention {
+        /// The argument of the `convention` attribute.
+        arg: AttrValue,
+
+        /// The architecture of the current target.
+        arch: TargetArch,
+    },
+
+    /// When a function that accepts a variable number of arguments is
+    /// given a calling convention other than `C`.
+    NonCVariadicConvention {
+        /// The argument of the `convention` attribute.
+        arg: AttrValue,
+
+        /// The function that the attribute is applied to.
+        item: AstNodeId,
+    },
+
+    /// When multiple `convention` attributes are being applied whilst
+    /// having different arguments.
+    IncompatibleConventionArgs {
+        /// The origin of the first `convention` attribute.
+        origin: AstNodeId,
+
+        /// The origin of the second `convention` attribute.
+        second: AstNodeId,
+    },
 }
 
 impl AttrError {
@@ -76,6 +150,7 @@ impl AttrError {
             AttrError::NonPreludeIntrinsics { origin } => {
                 reporter
                     .error()
+                    .code(HashErrorCode::NonPreludeIntrinsics)
                     .title("cannot use `#intrinsics` in non-prelude module")
                     .add_labelled_span(origin.span(), "this `#intrinsics` is not allowed here")
                     .add_note("only the prelude module can use `#intrinsics`");
@@ -83,6 +158,7 @@ impl AttrError {
             AttrError::IncompatibleReprArgs { origin, second } => {
                 reporter
                     .error()
+                    .code(HashErrorCode::ConflictingAttributeArgs)
                     .title("conflicting `repr` arguments")
                     .add_labelled_span(origin.span(), "this `repr` argument is incompatible")
                     .add_labelled_span(second.span(), "this `repr` argument is incompatible");
@@ -90,6 +166,7 @@ impl AttrError {
             AttrError::UnknownReprArg { arg: value } => {
                 reporter
                     .error()
+                    .code(HashErrorCode::UnknownAttributeArg)
                     .title(format!("unknown `repr` option `{value}`"))
                     .add_labelled_span(value.origin.span(), "this `repr` argument is unknown")
                     .add_note(format!(
@@ -100,23 +177,112 @@ impl AttrError {
             AttrError::InvalidReprForItem { origin, item, arg: value } => {
                 reporter
                     .error()
+                    .code(HashErrorCode::InvalidRepr)
                     .title(format!("invalid `repr` for {item}"))
                     .add_labelled_span(origin.span(), "this `repr` is not valid for this item")
-                    .add_note(format!("`{value}` cannot be applied to a {item}"));
+                    .add_note(format!("`{value}` cannot be applied to {item}"));
             }
-            AttrError::LayoutOfGenericItem { origin, generics, item } => {
+            AttrError::InvalidReprIntKind { arg } => {
                 reporter
                     .error()
-                    .title(format!("cannot use `#layout_of` on {item} with generic parameters"))
+                    .code(HashErrorCode::InvalidRepr)
+                    .title("invalid `repr` integer kind")
+                    .add_labelled_span(arg.origin.span(), "this `repr` argument is invalid")
+                    .add_note("`ubig` and `ibig` cannot be used as a `repr` argument because they are unbounded integer types.");
+            }
+            AttrError::InvalidReprAlign { arg } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::InvalidRepr)
+                    .title("invalid `repr` alignment")
+                    .add_labelled_span(arg.origin.span(), "this `repr` argument is invalid")
+                    .add_note("the alignment of `align(n)` must be a non-zero power of two");
+            }
+            AttrError::UnknownDeriveArg { arg: value } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnknownAttributeArg)
+                    .title(format!("unknown `derive` option `{value}`"))
+                    .add_labelled_span(value.origin.span(), "this item cannot be derived")
+                    .add_note(format!(
+                        "valid arguments are {}",
+                        SequenceDisplay::either(DERIVE_OPTIONS)
+                    ));
+            }
+            AttrError::DeriveOnGenericItem { origin, generics, item } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::DeriveOnGenericItem)
+                    .title(format!("cannot use `#derive` on {item} with generic parameters"))
                     .add_labelled_span(origin.span(), "this item is generic")
                     .add_labelled_span(generics.span(), "generic parameters declared here");
             }
-            AttrError::InvalidReprIntKind { arg } => {
+            AttrError::UnknownOptimizeArg { arg: value } => {
                 reporter
                     .error()
-                    .title("invalid `repr` integer kind")
-                    .add_labelled_span(arg.origin.span(), "this `repr` argument is invalid")
-                    .add_note("`ubig` and `ibig` cannot be used as a `repr` argument because they are unbounded integer types.");
+                    .code(HashErrorCode::UnknownAttributeArg)
+                    .title(format!("unknown `optimize` option `{value}`"))
+                    .add_labelled_span(value.origin.span(), "this `optimize` argument is unknown")
+                    .add_note(format!(
+                        "valid arguments are {}",
+                        SequenceDisplay::either(OPTIMIZE_OPTIONS)
+                    ));
+            }
+            AttrError::IncompatibleOptimizeArgs { origin, second } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::ConflictingAttributeArgs)
+                    .title("conflicting `optimize` arguments")
+                    .add_labelled_span(origin.span(), "this `optimize` argument is incompatible")
+                    .add_labelled_span(second.span(), "this `optimize` argument is incompatible");
+            }
+            AttrError::UnknownExternArg { arg: value } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnknownAttributeArg)
+                    .title(format!("unknown `extern` ABI `{value}`"))
+                    .add_labelled_span(value.origin.span(), "this `extern` ABI is unknown")
+                    .add_note(format!("valid ABIs are {}", SequenceDisplay::all(EXTERN_OPTIONS)));
+            }
+            AttrError::UnknownConventionArg { arg: value } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnknownAttributeArg)
+                    .title(format!("unknown calling convention `{value}`"))
+                    .add_labelled_span(value.origin.span(), "this calling convention is unknown")
+                    .add_note(format!(
+                        "valid calling conventions are {}",
+                        SequenceDisplay::either(CONVENTION_OPTIONS)
+                    ));
+            }
+            AttrError::UnsupportedConvention { arg: value, arch } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnsupportedConvention)
+                    .title(format!("the `{value}` calling convention is not supported on `{arch}`"))
+                    .add_labelled_span(
+                        value.origin.span(),
+                        "this calling convention cannot be used on the current target",
+                    );
+            }
+            AttrError::NonCVariadicConvention { arg: value, item } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnsupportedConvention)
+                    .title(format!(
+                        "functions with a variable number of arguments cannot use the `{value}` \
+                        calling convention"
+                    ))
+                    .add_labelled_span(value.origin.span(), "this calling convention is not `C`")
+                    .add_labelled_span(item.span(), "this function accepts `...` arguments");
+            }
+            AttrError::IncompatibleConventionArgs { origin, second } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::ConflictingAttributeArgs)
+                    .title("conflicting `convention` arguments")
+                    .add_labelled_span(origin.span(), "this `convention` argument is incompatible")
+                    .add_labelled_span(second.span(), "this `convention` argument is incompatible");
             }
         }
     }
@@ -150,6 +316,7 @@ impl AttrWarning {
             AttrWarning::Unused { origin, preceeding } => {
                 reporter
                     .warning()
+                    .code(HashWarningCode::UnusedAttribute)
                     .title("unused attribute")
                     .add_labelled_span(origin.span(), "remove this attribute")
                     .add_labelled_span(preceeding.span(), "previously specified here");
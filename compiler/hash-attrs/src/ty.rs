@@ -87,19 +87,41 @@ pub struct AttrTy {
     /// The expected kind of subject that the attribute is allowed to be
     /// applied onto.
     pub subject: AttrTarget,
+
+    /// Whether the attribute accepts any number of positional arguments. If
+    /// so, then the attribute only has a single parameter which specifies the
+    /// type of all of the arguments.
+    pub variadic: bool,
 }
 
 impl AttrTy {
     /// Create a new [AttrTy] with the given name, parameters and subject.
     pub fn new(name: impl Into<Identifier>, params: ParamsId, subject: AttrTarget) -> Self {
-        Self { name: name.into(), params, subject }
+        Self { name: name.into(), params, subject, variadic: false }
+    }
+
+    /// Create a new variadic [AttrTy], the `params` should only contain a
+    /// single parameter which specifies the type of all the arguments.
+    pub fn new_variadic(
+        name: impl Into<Identifier>,
+        params: ParamsId,
+        subject: AttrTarget,
+    ) -> Self {
+        Self { name: name.into(), params, subject, variadic: true }
     }
 
     /// Get the type of a parameter at a given [ParamIndex].
     ///
-    /// If the parameter doesn't exist, then `None` is returned.
+    /// If the parameter doesn't exist, then `None` is returned. For variadic
+    /// attributes, all positional arguments have the type of the
+    /// single parameter.
     pub fn ty_of_param(&self, index: ParamIndex) -> Option<TyId> {
-        self.params.at_index(index).map(|param| param.borrow().ty)
+        match index {
+            ParamIndex::Position(_) if self.variadic => {
+                self.params.at_index(ParamIndex::pos(0)).map(|param| param.borrow().ty)
+            }
+            _ => self.params.at_index(index).map(|param| param.borrow().ty),
+        }
     }
 }
 
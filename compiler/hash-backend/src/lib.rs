@@ -59,7 +59,7 @@ impl<Ctx: BackendCtxQuery> CompilerStage<Ctx> for CodeGenPass {
         }
 
         // Create a new instance of a backend, and then run it...
-        let mut backend = match settings.codegen_settings.backend {
+        let mut backend = match settings.codegen_settings.backend() {
             #[cfg(feature = "llvm")]
             CodeGenBackend::LLVM => create_llvm_backend(ctx.data(), &mut self.metrics),
             CodeGenBackend::VM => create_vm_backend(ctx.data(), &mut self.metrics),
@@ -32,7 +32,7 @@ use hash_codegen::{
 use hash_ir::{ir::BodySource, ty::InstanceHelpers, IrStorage};
 use hash_pipeline::{
     interface::{CompilerOutputStream, CompilerResult, StageMetrics},
-    settings::CompilerSettings,
+    settings::{CompilerSettings, EmitKind},
     workspace::Workspace,
 };
 use hash_reporting::report::Report;
@@ -124,6 +124,9 @@ impl<'b, 'm> LLVMBackend<'b> {
             TargetArch::Aarch64 => {
                 llvm::targets::Target::initialize_aarch64(&config);
             }
+            TargetArch::Msp430 => {
+                llvm::targets::Target::initialize_msp430(&config);
+            }
             TargetArch::Unknown => unreachable!(),
         }
 
@@ -189,9 +192,9 @@ impl<'b, 'm> LLVMBackend<'b> {
             std::fs::create_dir_all(parent).unwrap();
         }
 
-        // If the `-Cemit=asm` flag was specified then we will also emit the
-        // assembly code for the module.
-        if self.settings.codegen_settings.dump_assembly {
+        // If `--emit=asm` or `--asm-dump` was specified then we will also emit
+        // the assembly code for the module.
+        if self.settings.emits(EmitKind::Asm) {
             let asm_path = self.workspace.module_bitcode_path(id, "s");
             self.target_machine
                 .write_to_file(module, FileType::Assembly, &asm_path)
@@ -207,6 +210,14 @@ impl<'b, 'm> LLVMBackend<'b> {
         self.target_machine
             .write_to_file(module, FileType::Object, &path)
             .map_err(|err| CodeGenError::ModuleWriteFailed { reason: err })?;
+
+        // The object files aren't linked when they are the requested artefact,
+        // so the user is notified of where they can be found.
+        if self.settings.emits(EmitKind::Obj) {
+            let report = info_report(format!("wrote object file to `{}`", path.to_string_lossy()));
+            stream_writeln!(self.stdout, "{}", report);
+        }
+
         self.workspace.code_map.add_object_file(id, path);
         Ok(())
     }
@@ -345,7 +356,7 @@ impl<'b> CompilerBackend<'b> for LLVMBackend<'b> {
 
         // If the settings specify that the bytecode should be emitted, then
         // we write the emitted bytecode to standard output.
-        if self.settings.codegen_settings.dump_bytecode {
+        if self.settings.emits(EmitKind::Bytecode) {
             let stdout = &mut self.stdout;
             stream_writeln!(
                 stdout,
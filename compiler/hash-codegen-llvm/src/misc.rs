@@ -2,6 +2,7 @@
 //! generation types and the LLVM backend specific data types.
 
 use hash_codegen::{
+    abi::CallingConvention,
     common::{AtomicOrdering, IntComparisonKind, RealComparisonKind},
     target::link::{CodeModel, RelocationModel},
 };
@@ -82,6 +83,30 @@ impl From<AtomicOrdering> for AtomicOrderingWrapper {
     }
 }
 
+/// Wrapper type around the LLVM calling convention ids to allow for conversion
+/// from [CallingConvention].
+///
+/// Defined in <https://github.com/llvm/llvm-project/blob/main/llvm/include/llvm/IR/CallingConv.h>
+pub struct CallingConventionWrapper(pub u32);
+
+impl CallingConventionWrapper {
+    /// The id of the default C calling convention.
+    pub const C: u32 = 0;
+}
+
+impl From<CallingConvention> for CallingConventionWrapper {
+    fn from(value: CallingConvention) -> Self {
+        match value {
+            // @@Future: use a calling convention that is specific to Hash,
+            // this requires that all function pointers use the same one.
+            CallingConvention::C | CallingConvention::Hash => Self(Self::C),
+            CallingConvention::Cold => Self(9),
+            CallingConvention::System => Self(64),
+            CallingConvention::Fast => Self(65),
+        }
+    }
+}
+
 /// This defines the ids for the various `MetadataKind`s that are used in LLVM
 /// to annotate values with particular properties.
 ///
@@ -2,7 +2,8 @@
 
 use hash_codegen::{
     abi::{
-        ArgAbi, ArgAttributeFlag, ArgAttributes, ArgExtension, CallingConvention, FnAbi, PassMode,
+        ArgAbi, ArgAttributeFlag, ArgAttributes, ArgExtension, CastTarget, FnAbi, PassMode, Reg,
+        RegKind,
     },
     lower::{operands::OperandValue, place::PlaceRef},
     target::{
@@ -22,7 +23,10 @@ use inkwell::{
 };
 
 use super::{ty::ExtendedTyBuilderMethods, LLVMBuilder};
-use crate::{ctx::CodeGenCtx, misc::AttributeKind};
+use crate::{
+    ctx::CodeGenCtx,
+    misc::{AttributeKind, CallingConventionWrapper},
+};
 
 impl<'b, 'm> AbiBuilderMethods<'b> for LLVMBuilder<'_, 'b, 'm> {
     fn get_param(&mut self, index: usize) -> Self::Value {
@@ -86,6 +90,10 @@ impl<'m> ExtendedArgAbiMethods<'m> for ArgAbi {
         if self.is_indirect() {
             let alignment = self.info.abi_alignment();
             OperandValue::Ref(value, alignment).store(builder, destination)
+        } else if let PassMode::Cast(_) = self.mode {
+            // The value is of the cast type, which covers the same memory
+            // as the destination, so it can be stored directly.
+            builder.store(value, destination.value, destination.alignment);
         } else {
             OperandValue::Immediate(value).store(builder, destination)
         }
@@ -110,7 +118,7 @@ impl<'m> ExtendedArgAbiMethods<'m> for ArgAbi {
             PassMode::Pair(_, _) => {
                 OperandValue::Pair(next_arg(), next_arg()).store(builder, destination)
             }
-            PassMode::Direct(_) | PassMode::Indirect { .. } => {
+            PassMode::Direct(_) | PassMode::Indirect { .. } | PassMode::Cast(_) => {
                 let arg = next_arg();
                 self.store(builder, arg, destination)
             }
@@ -118,6 +126,53 @@ impl<'m> ExtendedArgAbiMethods<'m> for ArgAbi {
     }
 }
 
+pub trait ExtendedCastTargetMethods<'m> {
+    /// Produce an LLVM type for the registers that are described by
+    /// the [CastTarget].
+    fn llvm_ty(&self, ctx: &CodeGenCtx<'_, 'm>) -> AnyTypeEnum<'m>;
+}
+
+/// Produce an LLVM type for the given [Reg].
+fn reg_llvm_ty<'m>(ctx: &CodeGenCtx<'_, 'm>, reg: Reg) -> AnyTypeEnum<'m> {
+    match reg.kind {
+        RegKind::Integer => ctx.type_ix(reg.size.bits()),
+        RegKind::Float => match reg.size.bytes() {
+            4 => ctx.type_f32(),
+            8 => ctx.type_f64(),
+            size => panic!("unsupported float register of {size} bytes"),
+        },
+        RegKind::Vector => ctx.type_vector(ctx.type_i8(), reg.size.bytes()),
+    }
+}
+
+impl<'m> ExtendedCastTargetMethods<'m> for CastTarget {
+    fn llvm_ty(&self, ctx: &CodeGenCtx<'_, 'm>) -> AnyTypeEnum<'m> {
+        let unit = reg_llvm_ty(ctx, self.rest.unit);
+        let (count, remainder) = self.rest.unit_count();
+
+        if self.prefix.is_none() {
+            if self.rest.total <= self.rest.unit.size {
+                return unit;
+            }
+
+            if remainder == 0 {
+                return ctx.type_array(unit, count);
+            }
+        }
+
+        // Otherwise, the registers are laid out in a structure, with any
+        // remaining bytes being passed in an integer.
+        let mut fields = self.prefix.iter().map(|reg| reg_llvm_ty(ctx, *reg)).collect::<Vec<_>>();
+        fields.extend((0..count).map(|_| unit));
+
+        if remainder != 0 {
+            fields.push(ctx.type_ix(remainder * 8));
+        }
+
+        ctx.type_struct(&fields, false)
+    }
+}
+
 pub trait ExtendedArgAttributeMethods<'m> {
     /// Get a list of attributes that are currently set on the
     /// [ArhAttributes].
@@ -266,6 +321,7 @@ impl<'b, 'm> ExtendedFnAbiMethods<'b, 'm> for FnAbi {
         let return_ty = match &self.ret_abi.mode {
             PassMode::Ignore => ctx.type_void(),
             PassMode::Direct(_) | PassMode::Pair(_, _) => self.ret_abi.info.immediate_llvm_ty(ctx),
+            PassMode::Cast(target) => target.llvm_ty(ctx),
             PassMode::Indirect { .. } => {
                 // if the argument is being passed indirectly, then we push th e
                 // type through the argument as a pointer.
@@ -274,12 +330,15 @@ impl<'b, 'm> ExtendedFnAbiMethods<'b, 'm> for FnAbi {
             }
         };
 
-        for arg in self.args.iter() {
+        // Only the arguments that correspond to the parameters of the function
+        // are part of the type of a C-variadic function.
+        for arg in self.args[..self.fixed_count].iter() {
             match &arg.mode {
                 PassMode::Ignore => continue,
                 PassMode::Direct(_) => {
                     arg_tys.push(arg.info.immediate_llvm_ty(ctx));
                 }
+                PassMode::Cast(target) => arg_tys.push(target.llvm_ty(ctx)),
                 PassMode::Pair(_, _) => {
                     arg_tys.push(arg.info.scalar_pair_element_llvm_ty(ctx, 0, true));
                     arg_tys.push(arg.info.scalar_pair_element_llvm_ty(ctx, 1, true));
@@ -290,7 +349,11 @@ impl<'b, 'm> ExtendedFnAbiMethods<'b, 'm> for FnAbi {
             }
         }
 
-        ctx.type_function(&arg_tys, return_ty)
+        if self.c_variadic {
+            ctx.type_variadic_function(&arg_tys, return_ty)
+        } else {
+            ctx.type_function(&arg_tys, return_ty)
+        }
     }
 
     fn apply_attributes_to_fn(&self, ctx: &CodeGenCtx<'_, 'm>, func: FunctionValue<'m>) {
@@ -344,6 +407,9 @@ impl<'b, 'm> ExtendedFnAbiMethods<'b, 'm> for FnAbi {
                 | PassMode::Indirect { attributes, on_stack: false } => {
                     apply_attributes_to_arg(attributes);
                 }
+                PassMode::Cast(_) => {
+                    apply_attributes_to_arg(&ArgAttributes::new());
+                }
                 PassMode::Indirect { attributes, on_stack: true } => {
                     // If the argument is being passed on the stack, then we
                     // emit the `by_val` attribute on the argument.
@@ -436,6 +502,9 @@ impl<'b, 'm> ExtendedFnAbiMethods<'b, 'm> for FnAbi {
                 | PassMode::Indirect { attributes, on_stack: false } => {
                     apply_attributes_to_arg(builder.ctx, attributes);
                 }
+                PassMode::Cast(_) => {
+                    apply_attributes_to_arg(builder.ctx, &ArgAttributes::new());
+                }
                 PassMode::Indirect { attributes, on_stack: true } => {
                     let index = apply_attributes_to_arg(builder.ctx, attributes);
                     let byval_attribute = builder.ctx.ll_ctx.create_type_attribute(
@@ -449,8 +518,10 @@ impl<'b, 'm> ExtendedFnAbiMethods<'b, 'm> for FnAbi {
         }
 
         // Adjust the calling convention if necessary.
-        if self.calling_convention != CallingConvention::C {
-            call_site.set_call_convention(self.calling_convention as u32);
+        let CallingConventionWrapper(convention) = self.calling_convention.into();
+
+        if convention != CallingConventionWrapper::C {
+            call_site.set_call_convention(convention);
         }
 
         // @@Future: we might need to additional information about the
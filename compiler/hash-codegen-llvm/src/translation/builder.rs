@@ -110,6 +110,10 @@ impl<'a, 'b, 'm> LLVMBuilder<'a, 'b, 'm> {
             return Cow::Borrowed(args);
         }
 
+        // Any arguments that are passed to the variadic part of a call have no
+        // parameter type, and so they are passed as they are.
+        let variadic_args = args.iter().skip(func_params.len()).copied();
+
         let casted_args: Vec<_> = iter::zip(func_params, args)
             .map(|(expected_ty, &actual_val)| {
                 let actual_ty = self.ty_of_value(actual_val.as_any_value_enum());
@@ -122,6 +126,7 @@ impl<'a, 'b, 'm> LLVMBuilder<'a, 'b, 'm> {
                     actual_val
                 }
             })
+            .chain(variadic_args)
             .collect();
 
         Cow::Owned(casted_args)
@@ -245,6 +250,33 @@ impl<'a, 'b, 'm> BlockBuilderMethods<'a, 'b> for LLVMBuilder<'a, 'b, 'm> {
         }
     }
 
+    fn call_ptr(
+        &mut self,
+        fn_ptr: Self::Value,
+        args: &[Self::Value],
+        fn_abi: FnAbiId,
+    ) -> Self::Value {
+        let args: Vec<BasicMetadataValueEnum> =
+            args.iter().map(|v| (*v).try_into().unwrap()).collect();
+
+        let abis = self.ctx.cg_ctx().abis();
+        let fn_ty = abis.map_fast(fn_abi, |abi| abi.llvm_ty(self.ctx)).into_function_type();
+
+        let site = self
+            .builder
+            .build_indirect_call(fn_ty, fn_ptr.into_pointer_value(), &args, "")
+            .unwrap();
+
+        abis.map_fast(fn_abi, |abi| {
+            abi.apply_attributes_call_site(self, site);
+        });
+
+        match site.try_as_basic_value() {
+            Either::Left(val) => val.into(),
+            Either::Right(val) => val.into(),
+        }
+    }
+
     // @@Todo: would be nice to make this a macro...
 
     fn add(&mut self, lhs: Self::Value, rhs: Self::Value) -> Self::Value {
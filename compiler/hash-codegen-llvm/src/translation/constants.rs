@@ -6,6 +6,7 @@ use hash_codegen::{
     },
     traits::{constants::ConstValueBuilderMethods, ty::TypeBuilderMethods},
 };
+use hash_ir::ty::InstanceId;
 use hash_source::constant::{self, AllocId, AllocRange, Size};
 use hash_storage::store::statics::StoreId;
 use inkwell::{
@@ -174,6 +175,10 @@ impl<'b, 'm> ConstValueBuilderMethods<'b> for CodeGenCtx<'b, 'm> {
         }
     }
 
+    fn const_fn_addr(&self, instance: InstanceId) -> Self::Value {
+        self.get_fn_or_create_ref(instance).as_global_value().as_pointer_value().into()
+    }
+
     fn const_data_from_alloc(&self, alloc: constant::AllocId) -> Self::Value {
         alloc.map(|allocation| {
             let mut values = vec![];
@@ -8,7 +8,7 @@ use inkwell::{
 };
 
 use super::abi::ExtendedFnAbiMethods;
-use crate::ctx::CodeGenCtx;
+use crate::{ctx::CodeGenCtx, misc::CallingConventionWrapper};
 
 impl<'b, 'm> CodeGenCtx<'b, 'm> {
     /// Standard function to declare a C-like function. This should only be used
@@ -62,7 +62,7 @@ impl<'b, 'm> CodeGenCtx<'b, 'm> {
         };
 
         // We need to apply the calling convention to the function
-        func.set_call_conventions(calling_convention as u32);
+        func.set_call_conventions(CallingConventionWrapper::from(calling_convention).0);
         func.as_global_value().set_unnamed_address(addr);
         func.as_global_value().set_visibility(visibility);
 
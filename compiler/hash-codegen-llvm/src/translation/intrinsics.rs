@@ -208,6 +208,7 @@ impl<'b, 'm> LLVMBuilder<'_, 'b, 'm> {
 
         // Overflow intrinsics
         intrinsic_on!("llvm.expect.i1", fn(bool, bool) -> bool);
+        intrinsic_on!("llvm.assume", fn(bool) -> void);
 
         intrinsic_on!("llvm.sadd.with.overflow.i8", fn(i8, i8) -> struct_ty! {i8, bool});
         intrinsic_on!("llvm.sadd.with.overflow.i16", fn(i16, i16) -> struct_ty! {i16, bool});
@@ -351,4 +352,9 @@ impl<'b, 'm> IntrinsicBuilderMethods<'b> for LLVMBuilder<'_, 'b, 'm> {
         let expected = self.const_bool(expected);
         self.call_intrinsic("llvm.expect.i1", &[value, expected])
     }
+
+    fn codegen_assume_intrinsic(&mut self, value: Self::Value) {
+        debug_assert!(value.is_int_value(), "expected `i1` value, got: `{value:?}`");
+        self.call_intrinsic("llvm.assume", &[value]);
+    }
 }
@@ -23,6 +23,10 @@ impl<'b> LayoutMethods<'b> for CodeGenCtx<'b, '_> {
     fn is_backend_scalar_pair(&self, info: TyInfo) -> bool {
         info.layout.map(|layout| layout.is_llvm_scalar_pair())
     }
+
+    fn supports_vector_registers(&self) -> bool {
+        true
+    }
 }
 
 impl<'b, 'm> LayoutMethods<'b> for LLVMBuilder<'_, 'b, 'm> {
@@ -37,6 +41,10 @@ impl<'b, 'm> LayoutMethods<'b> for LLVMBuilder<'_, 'b, 'm> {
     fn is_backend_scalar_pair(&self, ty: TyInfo) -> bool {
         self.ctx.is_backend_scalar_pair(ty)
     }
+
+    fn supports_vector_registers(&self) -> bool {
+        self.ctx.supports_vector_registers()
+    }
 }
 
 pub trait ExtendedLayoutMethods<'m> {
@@ -1,6 +1,6 @@
 //! Implements various miscellaneous methods for the LLVM backend.
 
-use hash_attrs::builtin::attrs;
+use hash_attrs::{attr::OptimizeAttr, builtin::attrs};
 use hash_codegen::{
     abi::{CallingConvention, FnAbi},
     symbols::mangle::compute_symbol_name,
@@ -10,13 +10,14 @@ use hash_codegen::{
 use hash_ir::ty::{InstanceHelpers, InstanceId};
 use hash_storage::store::{statics::StoreId, Store};
 use inkwell::{
+    attributes::AttributeLoc,
     module::Linkage,
     values::{AnyValue, FunctionValue, UnnamedAddress},
     GlobalVisibility,
 };
 
 use super::abi::ExtendedFnAbiMethods;
-use crate::ctx::CodeGenCtx;
+use crate::{ctx::CodeGenCtx, misc::AttributeKind};
 
 impl<'b, 'm> CodeGenCtx<'b, 'm> {
     /// Generate code for a reference to a function or method item. The
@@ -108,6 +109,19 @@ impl<'b, 'm> MiscBuilderMethods<'b> for CodeGenCtx<'b, 'm> {
             decl.set_linkage(Linkage::External);
         }
 
+        // If the instance overrides the optimisation level, then we need to
+        // tell LLVM to either skip optimising the function, or to prefer
+        // smaller code. LLVM requires `optnone` functions to never be inlined.
+        let kinds: &[AttributeKind] = match OptimizeAttr::on_node(instance.borrow().attr_id) {
+            Some(OptimizeAttr::None) => &[AttributeKind::OptimizeNone, AttributeKind::NoInline],
+            Some(OptimizeAttr::Size) => &[AttributeKind::OptimizeForSize],
+            Some(OptimizeAttr::Speed) | None => &[],
+        };
+
+        for kind in kinds {
+            decl.add_attribute(AttributeLoc::Function, kind.create_attribute(self));
+        }
+
         // We insert the function into the cache so that we can
         // reference it later on...
         self.instances.borrow_mut().insert(instance, decl);
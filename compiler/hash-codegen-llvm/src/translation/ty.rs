@@ -4,9 +4,9 @@
 use core::panic;
 
 use hash_codegen::{
-    abi::FnAbi,
+    abi::{CastTarget, FnAbi},
     common::TypeKind,
-    repr::{Layout, LayoutShape, TyInfo, Variants},
+    repr::{FieldInfo, Layout, LayoutShape, TyInfo, Variants},
     target::{
         abi::{AbiRepresentation, AddressSpace, Integer, Scalar, ScalarKind},
         alignment::Alignment,
@@ -27,7 +27,7 @@ use llvm_sys::{
     LLVMTypeKind,
 };
 
-use super::abi::ExtendedFnAbiMethods;
+use super::abi::{ExtendedCastTargetMethods, ExtendedFnAbiMethods};
 use crate::ctx::CodeGenCtx;
 
 /// Convert a [BasicTypeEnum] into a [AnyTypeEnum].
@@ -82,6 +82,24 @@ impl<'b, 'm> CodeGenCtx<'b, 'm> {
     }
 }
 
+/// Create an LLVM function type from the given argument types and return
+/// type, optionally accepting a variable number of additional arguments.
+fn fn_type<'m>(
+    args: &[AnyTypeEnum<'m>],
+    ret: AnyTypeEnum<'m>,
+    is_var_args: bool,
+) -> AnyTypeEnum<'m> {
+    let args = args.iter().map(|ty| (*ty).try_into().unwrap()).collect::<Vec<_>>();
+
+    // @@Inkwell: somehow `void` types aren't in the `BasicTypeEnum` enum??
+    if let AnyTypeEnum::VoidType(_) = ret {
+        ret.into_void_type().fn_type(&args, is_var_args).into()
+    } else {
+        let ret: BasicTypeEnum = ret.try_into().unwrap();
+        ret.fn_type(&args, is_var_args).into()
+    }
+}
+
 impl<'b, 'm> TypeBuilderMethods<'b> for CodeGenCtx<'b, 'm> {
     fn type_i1(&self) -> Self::Type {
         self.ll_ctx.bool_type().into()
@@ -108,7 +126,7 @@ impl<'b, 'm> TypeBuilderMethods<'b> for CodeGenCtx<'b, 'm> {
     }
 
     fn type_isize(&self) -> Self::Type {
-        self.ll_ctx.i64_type().into()
+        self.size_ty.into()
     }
 
     fn type_ix(&self, bits: u64) -> Self::Type {
@@ -129,15 +147,11 @@ impl<'b, 'm> TypeBuilderMethods<'b> for CodeGenCtx<'b, 'm> {
     }
 
     fn type_function(&self, args: &[Self::Type], ret: Self::Type) -> Self::Type {
-        let args = args.iter().map(|ty| (*ty).try_into().unwrap()).collect::<Vec<_>>();
+        fn_type(args, ret, false)
+    }
 
-        // @@Inkwell: somehow `void` types aren't in the `BasicTypeEnum` enum??
-        if let AnyTypeEnum::VoidType(_) = ret {
-            ret.into_void_type().fn_type(&args, false).into()
-        } else {
-            let ret: BasicTypeEnum = ret.try_into().unwrap();
-            ret.fn_type(&args, false).into()
-        }
+    fn type_variadic_function(&self, args: &[Self::Type], ret: Self::Type) -> Self::Type {
+        fn_type(args, ret, true)
     }
 
     fn type_struct(&self, fields: &[Self::Type], packed: bool) -> Self::Type {
@@ -248,6 +262,10 @@ impl<'b, 'm> TypeBuilderMethods<'b> for CodeGenCtx<'b, 'm> {
     fn backend_ty_from_abi(&self, abi: &FnAbi) -> Self::Type {
         abi.llvm_ty(self)
     }
+
+    fn backend_ty_from_cast(&self, target: &CastTarget) -> Self::Type {
+        target.llvm_ty(self)
+    }
 }
 
 /// A [TyMemoryRemap] is a type that is used to represent the occurred
@@ -498,10 +516,9 @@ fn create_and_pad_struct_fields_from_layout<'m>(
     let mut fields = Vec::with_capacity(1 + field_count * 2);
     let mut field_map = smallvec![0; field_count];
 
-    for i in layout.shape.iter_increasing_offsets() {
-        let target_offset = layout.shape.offset(i);
-        let field = info.field(ctx.layouts(), i);
-
+    for FieldInfo { index: i, offset: target_offset, info: field } in
+        info.fields_in_memory_order(ctx.layouts())
+    {
         // @@Todo: maybe re-use the pre-computed padding size here that is available on
         // the layout?
         field.layout.map(|field_layout| {
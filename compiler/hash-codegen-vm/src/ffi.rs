@@ -0,0 +1,80 @@
+//! Utilities for computing the signature of foreign functions that are
+//! called from the VM. The signature is derived from the [FnAbi] of the
+//! function so that arguments are passed in the same way that the native
+//! backends would pass them.
+
+use hash_codegen::{
+    abi::{ArgAbi, CallingConvention, FnAbi, PassMode},
+    repr::{
+        compute::LayoutComputer,
+        ty::{Instance, InstanceId},
+    },
+    target::{
+        abi::{AbiRepresentation, ScalarKind},
+        Target,
+    },
+};
+use hash_storage::store::statics::StoreId;
+use hash_vm::ffi::{ForeignSignature, ForeignValue};
+
+/// Compute the [ForeignSignature] of a foreign function instance under the
+/// calling convention of the given [Target]. If the VM can't call the
+/// function, then [None] is returned.
+pub fn instance_signature(
+    instance: InstanceId,
+    lc: LayoutComputer<'_>,
+    target: &Target,
+) -> Option<ForeignSignature> {
+    let Instance { params, ret_ty, abi, is_c_variadic, .. } = instance.value();
+
+    let convention = CallingConvention::make_from_abi_and_target(abi, target);
+    let mut fn_abi = FnAbi::of_fn_sig(params, ret_ty, lc, convention).ok()?;
+    fn_abi.c_variadic = is_c_variadic;
+    fn_abi.adjust_for_abi(lc, abi, target);
+
+    foreign_signature(&fn_abi).filter(ForeignSignature::is_supported)
+}
+
+/// Compute the [ForeignSignature] of a function from its [FnAbi]. If the
+/// function has an argument or return value that cannot be passed to a
+/// foreign function by the VM, or if the function is C-variadic, then [None]
+/// is returned.
+pub fn foreign_signature(abi: &FnAbi) -> Option<ForeignSignature> {
+    if abi.c_variadic {
+        return None;
+    }
+
+    let args = abi
+        .args
+        .iter()
+        .filter(|arg| !matches!(arg.mode, PassMode::Ignore))
+        .map(foreign_value)
+        .collect::<Option<Vec<_>>>()?;
+
+    let ret = match abi.ret_abi.mode {
+        PassMode::Ignore => None,
+        _ => Some(foreign_value(&abi.ret_abi)?),
+    };
+
+    Some(ForeignSignature { args, ret })
+}
+
+/// Convert an [ArgAbi] into a [ForeignValue], only scalars that are passed
+/// directly are supported.
+fn foreign_value(arg: &ArgAbi) -> Option<ForeignValue> {
+    let PassMode::Direct(_) = arg.mode else {
+        return None;
+    };
+
+    let AbiRepresentation::Scalar(scalar) = arg.info.layout.map(|layout| layout.abi) else {
+        return None;
+    };
+
+    Some(match scalar.kind() {
+        ScalarKind::Int { kind, signed } => {
+            ForeignValue::Integer { size: kind.size().bytes() as u8, signed }
+        }
+        ScalarKind::Float { kind } => ForeignValue::Float { size: kind.size().bytes() as u8 },
+        ScalarKind::Pointer(_) => ForeignValue::Pointer,
+    })
+}
@@ -2,18 +2,32 @@
 //! all of the logic of transforming generated Hash IR into Hash Bytecode
 //! so that it can be processed by the Hash VM.
 #![allow(unused)]
+#![feature(let_chains)]
+
+pub mod ffi;
+mod regalloc;
+mod translate;
+
+use std::io::Write;
 
 use hash_codegen::{
     backend::{BackendCtx, CodeGenStorage, CompilerBackend},
     repr::LayoutStorage,
+    target::HasTarget,
 };
-use hash_ir::IrStorage;
+use hash_ir::{fingerprint::Fingerprint, ty::ReprTy, IrStorage};
 use hash_pipeline::{
-    interface::{CompilerOutputStream, CompilerResult},
-    settings::CompilerSettings,
+    interface::{CompilerInterface, CompilerOutputStream, CompilerResult, CompilerStage},
+    settings::{CodeGenBackend, CodeGenSettings, CompilerSettings, CompilerStageKind, EmitKind},
     workspace::Workspace,
 };
+use hash_reporting::report::{Report, ReportKind};
+use hash_repr::compute::LayoutComputer;
+use hash_source::SourceId;
+use hash_storage::store::statics::StoreId;
 use hash_utils::profiling::{HasMutMetrics, StageMetrics};
+use hash_vm::{error::RuntimeError, observer::TraceObserver, vm::Interpreter};
+pub use translate::{ForeignItem, Program, Trap};
 
 pub struct VMBackend<'b> {
     /// The stream to use for printing out the results
@@ -70,7 +84,189 @@ impl<'b> VMBackend<'b> {
 }
 
 impl<'b> CompilerBackend<'b> for VMBackend<'b> {
+    /// The bytecode of the program is generated by the [BytecodeGen] stage,
+    /// and so there is nothing left for the backend to emit.
     fn run(&mut self) -> CompilerResult<()> {
-        todo!()
+        Ok(())
     }
 }
+
+/// The [BytecodeGenCtx] represents all of the required information
+/// that the [BytecodeGen] stage needs to query from the pipeline.
+pub struct BytecodeGenCtx<'b> {
+    /// Reference to the current compiler workspace.
+    pub workspace: &'b Workspace,
+
+    /// Reference to the IR storage that is used to store
+    /// the lowered IR, and all metadata about the IR.
+    pub icx: &'b IrStorage,
+
+    /// All of the layout information about the types in the
+    /// current session.
+    pub lcx: &'b LayoutStorage,
+
+    /// The settings of the current session.
+    pub settings: &'b CompilerSettings,
+
+    /// The stream that the trace of the VM is written to.
+    pub stdout: CompilerOutputStream,
+}
+
+pub trait BytecodeGenCtxQuery: CompilerInterface {
+    fn data(&mut self) -> BytecodeGenCtx<'_>;
+}
+
+/// The bytecode generation stage. When the VM is the selected backend, this
+/// stage translates all of the lowered bodies into a [Program], and runs the
+/// program in the VM if the workspace yields an executable.
+#[derive(Default)]
+pub struct BytecodeGen {
+    /// The metrics of the stage.
+    metrics: StageMetrics,
+
+    /// The program that was generated by the last run of the stage.
+    program: Option<Program>,
+
+    /// The hash of the bodies that the `program` was translated from. If the
+    /// hash is the same when the stage is run again, e.g. in watch mode, the
+    /// program is re-used rather than translated again.
+    program_hash: Option<Fingerprint>,
+}
+
+impl BytecodeGen {
+    /// Get the [Program] that was generated by the last run of the stage.
+    pub fn program(&self) -> Option<&Program> {
+        self.program.as_ref()
+    }
+}
+
+impl HasMutMetrics for BytecodeGen {
+    fn metrics(&mut self) -> &mut StageMetrics {
+        &mut self.metrics
+    }
+}
+
+impl<Ctx: BytecodeGenCtxQuery> CompilerStage<Ctx> for BytecodeGen {
+    fn kind(&self) -> CompilerStageKind {
+        CompilerStageKind::CodeGen
+    }
+
+    fn metrics(&self) -> StageMetrics {
+        self.metrics.clone()
+    }
+
+    fn reset_metrics(&mut self) {
+        self.metrics = StageMetrics::default();
+    }
+
+    fn run(&mut self, _: SourceId, ctx: &mut Ctx) -> CompilerResult<()> {
+        let BytecodeGenCtx { workspace, icx, lcx, settings, stdout } = ctx.data();
+
+        // The VM doesn't support all of the IR yet, so programs are only
+        // translated when the VM is explicitly chosen with `--backend vm`, and
+        // not when it is the default backend.
+        if settings.codegen_settings.backend != Some(CodeGenBackend::VM) {
+            return Ok(());
+        }
+
+        let entry = icx.entry_point.def();
+        let hash = program_hash(icx);
+
+        let program = match self.program.take() {
+            Some(program) if hash.is_some() && hash == self.program_hash => program,
+            _ => self.record("translate", |_| {
+                translate::translate_program(
+                    &icx.bodies,
+                    entry,
+                    LayoutComputer::new(lcx),
+                    &icx.ctx.link_items(),
+                    settings.target(),
+                )
+            }),
+        };
+
+        let result = if settings.stage == CompilerStageKind::Exe
+            && workspace.yields_executable(settings)
+            && entry.is_some()
+        {
+            self.record("run", |_| run_program(&program, &settings.codegen_settings, stdout))
+                .map_err(|report| vec![report])
+        } else {
+            Ok(())
+        };
+
+        self.program = Some(program);
+        self.program_hash = hash;
+        result
+    }
+
+    /// Print the instructions of the generated [Program] if `--emit=bytecode`
+    /// was specified.
+    fn cleanup(&mut self, _entry_point: SourceId, ctx: &mut Ctx) {
+        let BytecodeGenCtx { settings, mut stdout, .. } = ctx.data();
+
+        if let Some(program) = &self.program
+            && settings.emits(EmitKind::Bytecode)
+        {
+            for (index, instruction) in program.instructions.iter().enumerate() {
+                writeln!(stdout, "{index:>6}: {instruction:?}").unwrap();
+            }
+        }
+    }
+}
+
+/// Compute the hash of the [Program] that would be translated from the bodies
+/// of the given [IrStorage]. This combines the hashes of all of the bodies,
+/// the entry point, and the libraries that foreign functions are linked
+/// against. This is [None] if the hashes of the bodies haven't been computed.
+fn program_hash(icx: &IrStorage) -> Option<Fingerprint> {
+    if icx.body_hashes.len() != icx.bodies.len() {
+        return None;
+    }
+
+    let entry = icx.entry_point.def();
+    let entry = icx.bodies.iter().position(|body| {
+        matches!(*body.metadata().ty().borrow(), ReprTy::FnDef { instance } if Some(instance) == entry)
+    });
+
+    let link_items: Vec<_> = icx
+        .ctx
+        .link_items()
+        .iter()
+        .map(|(_, item)| (item.library.as_str(), item.symbol.as_str()))
+        .collect();
+
+    Some(Fingerprint::new((&icx.body_hashes, entry, link_items)))
+}
+
+/// Run the [Program] in a new instance of the VM, which is configured by the
+/// given [CodeGenSettings].
+fn run_program(
+    program: &Program,
+    settings: &CodeGenSettings,
+    stdout: CompilerOutputStream,
+) -> Result<(), Report> {
+    let mut vm = settings.stack_size.map_or_else(Interpreter::new, Interpreter::with_stack_size);
+    vm.set_program(program.instructions.clone());
+
+    // Resolve all of the foreign functions in the order that they were
+    // added, so that their indices match the indices within the program.
+    for ForeignItem { library, symbol, signature } in &program.foreign_items {
+        vm.foreign_fns_mut().resolve(library, symbol, signature.clone())?;
+    }
+
+    if settings.trace_vm {
+        vm.set_observer(TraceObserver::new(stdout));
+    }
+
+    vm.run().map_err(|err| match err {
+        RuntimeError::Trap { id } => {
+            let trap = &program.traps[id as usize];
+
+            let mut report = Report::new();
+            report.kind(ReportKind::Error).title(&trap.message).add_span(trap.span);
+            report
+        }
+        err => err.into(),
+    })
+}
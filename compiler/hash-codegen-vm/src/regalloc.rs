@@ -0,0 +1,201 @@
+//! Assignment of the registers of the VM to the [Local]s of a [Body]. Each
+//! local is kept within a contiguous range of registers: a scalar takes up a
+//! single register, the fields of a struct or a tuple are laid out one after
+//! the other, and an enum starts with its discriminant which is followed by
+//! the fields of all of its variants. Values that can't be split into
+//! scalars, i.e. strings, references and arrays, aren't supported yet.
+//!
+//! All bodies use the same registers for their locals, which is why a caller
+//! saves all of its registers onto the stack before it makes a call, and
+//! restores them once the call returns.
+
+use hash_ir::ir::{Body, Local};
+use hash_repr::{
+    compute::LayoutComputer,
+    ty::{ReprTy, ReprTyId, VariantIdx},
+};
+use hash_storage::store::statics::StoreId;
+use hash_utils::{fxhash::FxHashMap, index_vec::IndexVec};
+use hash_vm::register::Register;
+pub(crate) use hash_vm::vm::ARGUMENT_REGISTERS;
+
+/// The reason why a construct can't be translated into bytecode.
+pub(crate) type Unsupported = &'static str;
+
+/// The registers that hold the intermediate values of an operation, their
+/// values are never kept between statements.
+pub(crate) const SCRATCH: [Register; 8] = {
+    let mut registers = [Register::new(0); 8];
+    let mut index = 0;
+
+    while index < registers.len() {
+        registers[index] = Register::new((ARGUMENT_REGISTERS + index) as u8);
+        index += 1;
+    }
+
+    registers
+};
+
+/// The first register that is assigned to a local.
+const FIRST_LOCAL: usize = ARGUMENT_REGISTERS + SCRATCH.len();
+
+/// The registers from [`Register::BASE_POINTER`] onwards are reserved by the
+/// VM.
+const LOCAL_LIMIT: usize = 253;
+
+/// Get the register at the given index.
+pub(crate) fn register(index: usize) -> Register {
+    Register::new(index.try_into().expect("register index is out of range"))
+}
+
+/// Computes how many registers the values of a type take up.
+pub(crate) struct RegisterAllocator<'l> {
+    lc: LayoutComputer<'l>,
+
+    /// The number of registers of each type that has been seen so far.
+    counts: FxHashMap<ReprTyId, Result<usize, Unsupported>>,
+}
+
+impl<'l> RegisterAllocator<'l> {
+    pub fn new(lc: LayoutComputer<'l>) -> Self {
+        Self { lc, counts: FxHashMap::default() }
+    }
+
+    /// Get the number of registers that a value of the given type takes up.
+    pub fn count_of(&mut self, ty: ReprTyId) -> Result<usize, Unsupported> {
+        if let Some(count) = self.counts.get(&ty) {
+            return *count;
+        }
+
+        let count = self.compute_count_of(ty);
+        self.counts.insert(ty, count);
+        count
+    }
+
+    fn compute_count_of(&mut self, ty: ReprTyId) -> Result<usize, Unsupported> {
+        let repr = *ty.borrow();
+
+        match repr {
+            ReprTy::Int(_) | ReprTy::UInt(_) => {
+                let size = self.lc.size_of_ty(ty).map_err(|_| "types without a layout")?;
+
+                if size.bytes() > 8 {
+                    Err("integers that are wider than 64 bits")
+                } else {
+                    Ok(1)
+                }
+            }
+            ReprTy::Bool | ReprTy::Char | ReprTy::Float(_) | ReprTy::Fn { .. } => Ok(1),
+            ReprTy::Never | ReprTy::FnDef { .. } => Ok(0),
+            ReprTy::Adt(id) => {
+                let (flags, variants) = id.map(|adt| {
+                    let variants: Vec<Vec<_>> = adt
+                        .variants
+                        .iter()
+                        .map(|variant| variant.fields.iter().map(|field| field.ty).collect())
+                        .collect();
+
+                    (adt.flags, variants)
+                });
+
+                if flags.is_union() {
+                    return Err("unions");
+                }
+
+                let mut count = usize::from(flags.is_enum());
+
+                for ty in variants.into_iter().flatten() {
+                    count += self.count_of(ty)?;
+                }
+
+                Ok(count)
+            }
+            ReprTy::Str | ReprTy::Ref(..) | ReprTy::Slice(_) | ReprTy::Array { .. } => {
+                Err("strings, references and arrays")
+            }
+        }
+    }
+
+    /// Get the offset of the registers of a field within the registers of a
+    /// value of the type `ty`. The `variant` must be specified for enums.
+    pub fn field_offset(
+        &mut self,
+        ty: ReprTyId,
+        variant: Option<VariantIdx>,
+        field: usize,
+    ) -> Result<usize, Unsupported> {
+        let ReprTy::Adt(id) = *ty.borrow() else {
+            return Err("fields of values that aren't structs, tuples or enums");
+        };
+
+        let (is_enum, preceding) = id.map(|adt| {
+            let variant = variant.unwrap_or(VariantIdx::new(0));
+
+            // The fields of all of the previous variants, and the fields of
+            // the variant that come before the field.
+            let preceding: Vec<_> = adt
+                .variants
+                .iter()
+                .take(variant.index())
+                .flat_map(|variant| variant.fields.iter().map(|field| field.ty))
+                .chain(adt.variant(variant).fields.iter().take(field).map(|field| field.ty))
+                .collect();
+
+            (adt.flags.is_enum(), preceding)
+        });
+
+        let mut offset = usize::from(is_enum);
+
+        for ty in preceding {
+            offset += self.count_of(ty)?;
+        }
+
+        Ok(offset)
+    }
+
+    /// Assign registers to all of the locals of the [Body].
+    pub fn allocate(&mut self, body: &Body) -> Allocation {
+        let mut next = FIRST_LOCAL;
+
+        let locals = body
+            .locals
+            .iter()
+            .map(|local| {
+                let count = self.count_of(local.ty)?;
+
+                if next + count > LOCAL_LIMIT {
+                    return Err("functions with this many locals");
+                }
+
+                next += count;
+                Ok((next - count, count))
+            })
+            .collect();
+
+        Allocation { locals, end: next }
+    }
+}
+
+/// The registers that are assigned to the locals of a [Body].
+pub(crate) struct Allocation {
+    /// The index of the first register of each local, and the number of
+    /// registers of the local.
+    locals: IndexVec<Local, Result<(usize, usize), Unsupported>>,
+
+    /// The end of the registers that are assigned to the locals.
+    end: usize,
+}
+
+impl Allocation {
+    /// Get the first register of the local, and the number of registers
+    /// that the local takes up.
+    pub fn local(&self, local: Local) -> Result<(usize, usize), Unsupported> {
+        self.locals[local]
+    }
+
+    /// Get all of the registers that are assigned to locals, these must be
+    /// saved before a call.
+    pub fn used(&self) -> impl DoubleEndedIterator<Item = Register> + Clone {
+        (FIRST_LOCAL..self.end).map(register)
+    }
+}
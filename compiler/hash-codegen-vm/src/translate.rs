@@ -0,0 +1,1069 @@
+//! Translation of the lowered [Body]s of a program into bytecode for the VM.
+//! Each body becomes a function within the [Program], and each basic block of
+//! the body is given a [Label] so that terminators can jump to it. Functions
+//! are called through a table that maps each function to the label of its
+//! first instruction.
+//!
+//! Values are kept within the registers that are assigned to the locals by
+//! the [RegisterAllocator], integers are always kept zero-extended. The
+//! arguments of a call are passed in the registers `r0..r15`, and the callee
+//! returns its value in the same registers.
+//!
+//! Calls to `#foreign` functions are made through the table of [ForeignItem]s
+//! of the [Program], which are resolved from their libraries before the
+//! program is run.
+//!
+//! Constructs that the VM doesn't support, i.e. values that live in memory or
+//! calls to other functions without a body, are translated into a trap which
+//! stops the execution of the program with a message once it is reached. This
+//! means that a program can run as long as it doesn't reach any of them.
+
+use hash_ast::ast::AstNodeId;
+use hash_attrs::builtin::attrs;
+use hash_codegen::{symbols::mangle::compute_symbol_name, target::Target};
+use hash_ir::{
+    cast::CastKind,
+    ir::{
+        AggregateKind, BasicBlock, BinOp, Body, Const, ConstKind, ConstOp, Operand, Place,
+        PlaceProjection, RValue, Statement, StatementKind, SwitchTargets, Terminator,
+        TerminatorKind, UnOp, RETURN_PLACE,
+    },
+    link::LinkItems,
+    ty::InstanceHelpers,
+};
+use hash_repr::{
+    compute::LayoutComputer,
+    ty::{InstanceId, ReprTy, ReprTyId, VariantIdx},
+};
+use hash_source::{location::Span, FloatTy};
+use hash_storage::store::statics::StoreId;
+use hash_utils::{fxhash::FxHashMap, index_vec::IndexVec};
+use hash_vm::{
+    bytecode::Instruction,
+    bytecode_builder::{BytecodeBuilder, Label},
+    ffi::ForeignSignature,
+    register::Register,
+};
+
+use crate::{
+    ffi::instance_signature,
+    regalloc::{register, Allocation, RegisterAllocator, Unsupported, ARGUMENT_REGISTERS, SCRATCH},
+};
+
+/// Select the variant of a sized [Instruction] that operates on the given
+/// number of bits.
+macro_rules! sized {
+    ($bits:expr, [$op8:ident, $op16:ident, $op32:ident, $op64:ident], $l1:expr) => {
+        match $bits {
+            8 => Instruction::$op8 { l1: $l1 },
+            16 => Instruction::$op16 { l1: $l1 },
+            32 => Instruction::$op32 { l1: $l1 },
+            _ => Instruction::$op64 { l1: $l1 },
+        }
+    };
+    ($bits:expr, [$op8:ident, $op16:ident, $op32:ident, $op64:ident], $l1:expr, $l2:expr) => {
+        match $bits {
+            8 => Instruction::$op8 { l1: $l1, l2: $l2 },
+            16 => Instruction::$op16 { l1: $l1, l2: $l2 },
+            32 => Instruction::$op32 { l1: $l1, l2: $l2 },
+            _ => Instruction::$op64 { l1: $l1, l2: $l2 },
+        }
+    };
+}
+
+/// A program that has been translated into bytecode.
+#[derive(Debug)]
+pub struct Program {
+    /// The instructions of the program, the execution starts at the first
+    /// instruction by calling the entry point.
+    pub instructions: Vec<Instruction>,
+
+    /// Describes each [`Instruction::Trap`] of the program, the `id` of a
+    /// trap is its index within this list.
+    pub traps: Vec<Trap>,
+
+    /// The foreign functions that are called by the program, a
+    /// [`Instruction::CallForeign`] refers to a function by its index within
+    /// this list.
+    pub foreign_items: Vec<ForeignItem>,
+}
+
+/// A foreign function that is called by a [Program].
+#[derive(Debug)]
+pub struct ForeignItem {
+    /// The library that the function is resolved from, functions that
+    /// aren't linked against a specific library with `#link(...)` are
+    /// resolved from `c`.
+    pub library: String,
+
+    /// The name of the symbol of the function within the library.
+    pub symbol: String,
+
+    /// The signature that the function is called with.
+    pub signature: ForeignSignature,
+}
+
+/// Describes why a [`Instruction::Trap`] stops the execution of a program.
+#[derive(Debug)]
+pub struct Trap {
+    /// The message that is reported once the trap is reached.
+    pub message: String,
+
+    /// The location in the source that the trap originates from.
+    pub span: Span,
+}
+
+/// Where a value is read from when it is written into a register.
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    /// The value is read from the register at the given index.
+    Register(usize),
+
+    /// The value is a constant.
+    Immediate(u64),
+
+    /// The value is the location of a [Label].
+    Label(Label),
+}
+
+/// The function that is called by a [`TerminatorKind::Call`].
+#[derive(Debug, Clone, Copy)]
+enum Callee {
+    /// A function that has a body within the program.
+    Label(Label),
+
+    /// A foreign function at the given index of the [ForeignItem]s.
+    Foreign(usize),
+
+    /// A function pointer, whose value is the label of the function.
+    Pointer,
+}
+
+/// Collects the [ForeignItem]s that are called by a [Program].
+struct ForeignItems<'a> {
+    /// The libraries that foreign functions are linked against.
+    link_items: &'a LinkItems,
+
+    /// The target that the foreign functions are called on.
+    target: &'a Target,
+
+    /// The index of each function that has been added to the `items`.
+    indices: FxHashMap<InstanceId, usize>,
+
+    items: Vec<ForeignItem>,
+}
+
+/// Translate all of the `bodies` into a [Program] which calls the `entry`
+/// point of the program. Foreign functions are called with the calling
+/// convention of the given `target`.
+pub fn translate_program(
+    bodies: &[Body],
+    entry: Option<InstanceId>,
+    lc: LayoutComputer<'_>,
+    link_items: &LinkItems,
+    target: &Target,
+) -> Program {
+    let mut builder = BytecodeBuilder::default();
+    let mut traps = vec![];
+    let mut allocator = RegisterAllocator::new(lc);
+    let mut foreign =
+        ForeignItems { link_items, target, indices: FxHashMap::default(), items: vec![] };
+
+    let instances: Vec<_> = bodies
+        .iter()
+        .map(|body| match *body.metadata().ty().borrow() {
+            ReprTy::FnDef { instance } => Some(instance),
+            _ => None,
+        })
+        .collect();
+
+    let functions: FxHashMap<_, _> =
+        instances.iter().flatten().map(|instance| (*instance, builder.new_label())).collect();
+
+    // The program starts by calling the entry point, and then jumps past
+    // the end of the program once the entry point returns.
+    let end = builder.new_label();
+
+    if let Some(label) = entry.and_then(|entry| functions.get(&entry)) {
+        builder
+            .write_label(SCRATCH[0], *label)
+            .add_instruction(Instruction::Call { func: SCRATCH[0] })
+            .write_label(SCRATCH[0], end)
+            .add_instruction(Instruction::Jmp { location: SCRATCH[0] });
+    }
+
+    for (body, instance) in bodies.iter().zip(&instances) {
+        let Some(instance) = instance else {
+            continue;
+        };
+
+        builder.bind_label(functions[instance]);
+
+        let registers = allocator.allocate(body);
+        let blocks = body.blocks().indices().map(|_| builder.new_label()).collect();
+
+        BodyTranslator {
+            body,
+            lc,
+            builder: &mut builder,
+            traps: &mut traps,
+            allocator: &mut allocator,
+            functions: &functions,
+            foreign: &mut foreign,
+            registers,
+            blocks,
+        }
+        .translate();
+    }
+
+    builder.bind_label(end);
+    Program { instructions: builder.into(), traps, foreign_items: foreign.items }
+}
+
+/// Translates a single [Body] into a function of the program.
+struct BodyTranslator<'a, 'l> {
+    body: &'a Body,
+
+    lc: LayoutComputer<'l>,
+
+    builder: &'a mut BytecodeBuilder,
+
+    traps: &'a mut Vec<Trap>,
+
+    allocator: &'a mut RegisterAllocator<'l>,
+
+    /// The label of each function that has a body.
+    functions: &'a FxHashMap<InstanceId, Label>,
+
+    /// The foreign functions that are called by the program.
+    foreign: &'a mut ForeignItems<'l>,
+
+    /// The registers of the locals of the body.
+    registers: Allocation,
+
+    /// The label of each block of the body.
+    blocks: IndexVec<BasicBlock, Label>,
+}
+
+impl BodyTranslator<'_, '_> {
+    fn translate(&mut self) {
+        let body = self.body;
+
+        if let Err(reason) = self.prologue() {
+            self.unsupported(reason, body.origin());
+        }
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            self.builder.bind_label(self.blocks[block]);
+
+            let result = data
+                .statements
+                .iter()
+                .try_for_each(|statement| {
+                    self.statement(statement).map_err(|reason| (reason, statement.origin))
+                })
+                .and_then(|_| {
+                    let terminator = data.terminator();
+                    self.terminator(terminator).map_err(|reason| (reason, terminator.origin))
+                });
+
+            if let Err((reason, origin)) = result {
+                self.unsupported(reason, origin);
+            }
+        }
+    }
+
+    /// Move the arguments of the function into the registers of the locals
+    /// of the arguments.
+    fn prologue(&mut self) -> Result<(), Unsupported> {
+        let body = self.body;
+        let mut next = 0;
+
+        for arg in body.args_iter() {
+            let (start, count) = self.registers.local(arg)?;
+
+            if next + count > ARGUMENT_REGISTERS {
+                return Err("functions with this many arguments");
+            }
+
+            for index in 0..count {
+                self.mov(register(next + index), register(start + index));
+            }
+
+            next += count;
+        }
+
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<(), Unsupported> {
+        match &statement.kind {
+            StatementKind::Nop
+            | StatementKind::Live(_)
+            | StatementKind::Dead(_)
+            | StatementKind::Assume(_) => {}
+            StatementKind::Assign(place, rvalue) => self.assign(*place, rvalue)?,
+            StatementKind::Discriminate(place, variant) => {
+                let (start, _, ty) = self.place(*place)?;
+
+                if is_enum(ty) {
+                    let value = self.discriminant_of(ty, *variant)?;
+                    self.write(start, &[Source::Immediate(value)]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn assign(&mut self, place: Place, rvalue: &RValue) -> Result<(), Unsupported> {
+        let (dest, _, ty) = self.place(place)?;
+
+        match rvalue {
+            RValue::Use(operand) => {
+                let sources = self.sources(operand)?;
+                self.write(dest, &sources);
+            }
+            RValue::ConstOp(op, ty) => {
+                let layout = self.lc.layout_of_ty(*ty).map_err(|_| "types without a layout")?;
+                let value = layout.map(|layout| match op {
+                    ConstOp::SizeOf => layout.size.bytes(),
+                    ConstOp::AlignOf => layout.alignment.abi.bytes(),
+                });
+
+                self.write(dest, &[Source::Immediate(value)]);
+            }
+            RValue::UnaryOp(op, operand) => self.unary_op(*op, operand, register(dest))?,
+            RValue::BinaryOp(op, operands) => {
+                let (lhs, rhs) = operands.as_ref();
+                self.binary_op(*op, lhs, rhs, register(dest), None)?;
+            }
+            RValue::CheckedBinaryOp(op, operands) => {
+                let (lhs, rhs) = operands.as_ref();
+                self.binary_op(*op, lhs, rhs, register(dest), Some(register(dest + 1)))?;
+            }
+            RValue::Cast(kind, operand, ty) => self.cast(*kind, operand, *ty, register(dest))?,
+            RValue::Len(place) => {
+                let ReprTy::Array { length, .. } = *place.ty(&self.body.aux()).borrow() else {
+                    return Err("strings, references and arrays");
+                };
+
+                self.write(dest, &[Source::Immediate(length as u64)]);
+            }
+            RValue::Ref(..) | RValue::Repeat(..) => return Err("strings, references and arrays"),
+            RValue::Aggregate(AggregateKind::Array(_), _) => {
+                return Err("strings, references and arrays")
+            }
+            RValue::Aggregate(kind, operands) => {
+                let mut fields = vec![];
+
+                for operand in operands {
+                    fields.extend(self.sources(operand)?);
+                }
+
+                match kind {
+                    AggregateKind::Enum(_, variant) => {
+                        let offset = self.allocator.field_offset(ty, Some(*variant), 0)?;
+                        let value = self.discriminant_of(ty, *variant)?;
+
+                        self.write(dest + offset, &fields);
+                        self.write(dest, &[Source::Immediate(value)]);
+                    }
+                    _ => self.write(dest, &fields),
+                }
+            }
+            RValue::Discriminant(place) => {
+                let (start, _, ty) = self.place(*place)?;
+
+                // Values that aren't enums always have a discriminant of zero.
+                let source =
+                    if is_enum(ty) { Source::Register(start) } else { Source::Immediate(0) };
+
+                self.write(dest, &[source]);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn terminator(&mut self, terminator: &Terminator) -> Result<(), Unsupported> {
+        match &terminator.kind {
+            TerminatorKind::Goto(target) | TerminatorKind::Drop { target, .. } => {
+                self.jump(*target);
+            }
+            TerminatorKind::Return => {
+                let (start, count) = self.registers.local(RETURN_PLACE)?;
+
+                if count > ARGUMENT_REGISTERS {
+                    return Err("functions that return values this large");
+                }
+
+                for index in 0..count {
+                    self.mov(register(start + index), register(index));
+                }
+
+                self.builder.add_instruction(Instruction::Return);
+            }
+            TerminatorKind::Call { op, args, destination, target, .. } => {
+                self.call(op, args, *destination)?;
+
+                match target {
+                    Some(target) => self.jump(*target),
+                    None => self.trap("entered unreachable code", terminator.origin),
+                }
+            }
+            TerminatorKind::Switch { value, targets } => {
+                self.switch(value, targets)?;
+
+                if targets.otherwise.is_none() {
+                    self.trap("entered unreachable code", terminator.origin);
+                }
+            }
+            TerminatorKind::Assert { condition, expected, kind, target, .. } => {
+                self.load(condition, SCRATCH[0])?;
+
+                if *expected {
+                    let fail = self.builder.new_label();
+
+                    self.builder.write_label(SCRATCH[1], fail).add_instruction(
+                        Instruction::JmpZero { l1: SCRATCH[0], location: SCRATCH[1] },
+                    );
+                    self.jump(*target);
+                    self.builder.bind_label(fail);
+                } else {
+                    self.builder.write_label(SCRATCH[1], self.blocks[*target]).add_instruction(
+                        Instruction::JmpZero { l1: SCRATCH[0], location: SCRATCH[1] },
+                    );
+                }
+
+                self.trap(kind.message().trim_end(), terminator.origin);
+            }
+            TerminatorKind::Unreachable => self.trap("entered unreachable code", terminator.origin),
+            TerminatorKind::Resume => return Err("unwinding"),
+        }
+
+        Ok(())
+    }
+
+    /// Call a function, the caller saves all of its registers before the
+    /// call, and then moves the returned value into the `destination`.
+    fn call(
+        &mut self,
+        op: &Operand,
+        args: &[Operand],
+        destination: Place,
+    ) -> Result<(), Unsupported> {
+        // The function is either known statically, or it is a function
+        // pointer whose value is the label of the function.
+        let callee = match *op.ty(&self.body.aux()).borrow() {
+            ReprTy::FnDef { instance }
+                if !self.functions.contains_key(&instance)
+                    && instance.borrow().has_attr(attrs::FOREIGN) =>
+            {
+                Callee::Foreign(self.foreign_item(instance)?)
+            }
+            ReprTy::FnDef { instance } => Callee::Label(self.function(instance)?),
+            _ => Callee::Pointer,
+        };
+
+        let mut sources = vec![];
+
+        for arg in args {
+            sources.extend(self.sources(arg)?);
+        }
+
+        let (dest, count, _) = self.place(destination)?;
+
+        if sources.len() > ARGUMENT_REGISTERS || count > ARGUMENT_REGISTERS {
+            return Err("functions with this many arguments");
+        }
+
+        let saved = self.registers.used();
+
+        for l1 in saved.clone() {
+            self.builder.add_instruction(Instruction::Push64 { l1 });
+        }
+
+        for (index, source) in sources.into_iter().enumerate() {
+            self.load_source(source, register(index));
+        }
+
+        match callee {
+            Callee::Label(label) => {
+                self.builder
+                    .write_label(SCRATCH[0], label)
+                    .add_instruction(Instruction::Call { func: SCRATCH[0] });
+            }
+            Callee::Foreign(index) => {
+                self.builder
+                    .add_instruction(Instruction::Write { dest: SCRATCH[0], value: index as u64 })
+                    .add_instruction(Instruction::CallForeign { func: SCRATCH[0] });
+            }
+            Callee::Pointer => {
+                self.load(op, SCRATCH[0])?;
+                self.builder.add_instruction(Instruction::Call { func: SCRATCH[0] });
+            }
+        }
+
+        for l1 in saved.rev() {
+            self.builder.add_instruction(Instruction::Pop64 { l1 });
+        }
+
+        for index in 0..count {
+            self.mov(register(index), register(dest + index));
+        }
+
+        Ok(())
+    }
+
+    /// Jump to the target whose value is equal to the `value`, or to the
+    /// `otherwise` target if there is one.
+    fn switch(&mut self, value: &Operand, targets: &SwitchTargets) -> Result<(), Unsupported> {
+        self.load(value, SCRATCH[0])?;
+
+        for (value, target) in targets.iter() {
+            self.builder
+                .add_instruction(Instruction::Write { dest: SCRATCH[1], value: value as u64 })
+                .add_instruction(Instruction::Mov { src: SCRATCH[0], dest: SCRATCH[2] })
+                .add_instruction(Instruction::Cmp { l1: SCRATCH[2], l2: SCRATCH[1] })
+                .write_label(SCRATCH[3], self.blocks[target])
+                .add_instruction(Instruction::JmpZero { l1: SCRATCH[2], location: SCRATCH[3] });
+        }
+
+        if let Some(otherwise) = targets.otherwise {
+            self.jump(otherwise);
+        }
+
+        Ok(())
+    }
+
+    fn unary_op(&mut self, op: UnOp, operand: &Operand, dest: Register) -> Result<(), Unsupported> {
+        let ty = operand.ty(&self.body.aux());
+        let repr = *ty.borrow();
+        let (l1, l2) = (SCRATCH[0], SCRATCH[1]);
+
+        self.load(operand, l1)?;
+
+        match (op, repr) {
+            (UnOp::Not, ReprTy::Bool) => {
+                self.builder
+                    .add_instruction(Instruction::Write { dest: l2, value: 1 })
+                    .add_instruction(Instruction::Xor8 { l1, l2 });
+            }
+            (UnOp::Not | UnOp::BitNot, ReprTy::Int(_) | ReprTy::UInt(_)) => {
+                let bits = self.bits_of(ty)?;
+                self.builder.add_instruction(sized!(bits, [Not8, Not16, Not32, Not64], l1));
+            }
+            (UnOp::Neg, ReprTy::Int(_) | ReprTy::UInt(_)) => {
+                let bits = self.bits_of(ty)?;
+
+                self.builder
+                    .add_instruction(Instruction::Mov { src: l1, dest: l2 })
+                    .add_instruction(Instruction::Write { dest: l1, value: 0 })
+                    .add_instruction(sized!(bits, [Sub8, Sub16, Sub32, Sub64], l1, l2));
+            }
+            (UnOp::Neg, ReprTy::Float(float)) => {
+                let (value, xor) = match float {
+                    FloatTy::F32 => (1 << 31, Instruction::Xor32 { l1, l2 }),
+                    FloatTy::F64 => (1 << 63, Instruction::Xor64 { l1, l2 }),
+                };
+
+                self.builder
+                    .add_instruction(Instruction::Write { dest: l2, value })
+                    .add_instruction(xor);
+            }
+            _ => return Err("this unary operation"),
+        }
+
+        self.mov(l1, dest);
+        Ok(())
+    }
+
+    /// Compute a binary operation, and write the result into `dest`. If a
+    /// `flag` register is given, then it is set to whether the operation
+    /// overflowed.
+    fn binary_op(
+        &mut self,
+        op: BinOp,
+        lhs: &Operand,
+        rhs: &Operand,
+        dest: Register,
+        flag: Option<Register>,
+    ) -> Result<(), Unsupported> {
+        let ty = lhs.ty(&self.body.aux());
+        let repr = *ty.borrow();
+        let (l1, l2, overflow, temp) = (SCRATCH[0], SCRATCH[1], SCRATCH[2], SCRATCH[3]);
+
+        self.load(lhs, l1)?;
+        self.load(rhs, l2)?;
+
+        // Operations that can't overflow leave the flag unset.
+        self.builder.add_instruction(Instruction::Write { dest: overflow, value: 0 });
+
+        match repr {
+            ReprTy::Float(float) => self.float_op(op, float)?,
+            ReprTy::Int(_) | ReprTy::UInt(_) | ReprTy::Bool | ReprTy::Char => {
+                let bits = self.bits_of(ty)?;
+                let signed = repr.is_signed();
+
+                match op {
+                    _ if op.is_comparator() => self.compare(op, bits, signed),
+                    BinOp::Add | BinOp::Sub if signed => {
+                        self.mov(l1, overflow);
+                        self.mov(l2, temp);
+
+                        self.builder.add_instruction(if op == BinOp::Add {
+                            sized!(bits, [Add8, Add16, Add32, Add64], l1, l2)
+                        } else {
+                            sized!(bits, [Sub8, Sub16, Sub32, Sub64], l1, l2)
+                        });
+
+                        // An addition overflows if the sign of the result
+                        // differs from the sign of both operands, and a
+                        // subtraction overflows if the signs of the operands
+                        // differ, and the sign of the result differs from the
+                        // sign of the left-hand side.
+                        let (lhs, rhs) = (overflow, temp);
+
+                        self.builder.add_instruction(if op == BinOp::Add {
+                            Instruction::Xor64 { l1: rhs, l2: l1 }
+                        } else {
+                            Instruction::Xor64 { l1: rhs, l2: lhs }
+                        });
+
+                        self.builder
+                            .add_instruction(Instruction::Xor64 { l1: lhs, l2: l1 })
+                            .add_instruction(Instruction::And64 { l1: lhs, l2: rhs })
+                            .add_instruction(Instruction::Write { dest: rhs, value: bits - 1 })
+                            .add_instruction(Instruction::Shr64 { l1: lhs, l2: rhs });
+                    }
+                    BinOp::Add | BinOp::Sub | BinOp::Mul => {
+                        self.builder
+                            .add_instruction(match op {
+                                BinOp::Add => sized!(bits, [Add8, Add16, Add32, Add64], l1, l2),
+                                BinOp::Sub => sized!(bits, [Sub8, Sub16, Sub32, Sub64], l1, l2),
+                                _ if signed => {
+                                    sized!(bits, [IMul8, IMul16, IMul32, IMul64], l1, l2)
+                                }
+                                _ => sized!(bits, [Mul8, Mul16, Mul32, Mul64], l1, l2),
+                            })
+                            .add_instruction(Instruction::ReadOverflow { l1: overflow });
+                    }
+                    BinOp::Div if signed => {
+                        self.builder.add_instruction(sized!(
+                            bits,
+                            [IDiv8, IDiv16, IDiv32, IDiv64],
+                            l1,
+                            l2
+                        ));
+                    }
+                    BinOp::Div => {
+                        self.builder.add_instruction(sized!(
+                            bits,
+                            [Div8, Div16, Div32, Div64],
+                            l1,
+                            l2
+                        ));
+                    }
+                    // The remainder of a signed division is computed from
+                    // the quotient, i.e. `lhs - (lhs / rhs) * rhs`.
+                    BinOp::Mod if signed => {
+                        self.mov(l1, temp);
+
+                        self.builder
+                            .add_instruction(sized!(bits, [IDiv8, IDiv16, IDiv32, IDiv64], l1, l2))
+                            .add_instruction(sized!(bits, [IMul8, IMul16, IMul32, IMul64], l1, l2))
+                            .add_instruction(sized!(bits, [Sub8, Sub16, Sub32, Sub64], temp, l1));
+
+                        self.mov(temp, l1);
+                    }
+                    BinOp::Mod => {
+                        self.builder.add_instruction(sized!(
+                            bits,
+                            [Mod8, Mod16, Mod32, Mod64],
+                            l1,
+                            l2
+                        ));
+                    }
+                    BinOp::BitAnd => {
+                        self.builder.add_instruction(sized!(
+                            bits,
+                            [And8, And16, And32, And64],
+                            l1,
+                            l2
+                        ));
+                    }
+                    BinOp::BitOr => {
+                        self.builder.add_instruction(sized!(bits, [Or8, Or16, Or32, Or64], l1, l2));
+                    }
+                    BinOp::BitXor => {
+                        self.builder.add_instruction(sized!(
+                            bits,
+                            [Xor8, Xor16, Xor32, Xor64],
+                            l1,
+                            l2
+                        ));
+                    }
+                    BinOp::Shl | BinOp::Shr => self.shift(op, bits, signed),
+                    BinOp::Exp => return Err("exponentiation of integers"),
+                    _ => return Err("pointer arithmetic"),
+                }
+            }
+            _ => return Err("operations on values of this type"),
+        }
+
+        self.mov(l1, dest);
+
+        if let Some(flag) = flag {
+            self.mov(overflow, flag);
+        }
+
+        Ok(())
+    }
+
+    fn float_op(&mut self, op: BinOp, float: FloatTy) -> Result<(), Unsupported> {
+        let (l1, l2) = (SCRATCH[0], SCRATCH[1]);
+
+        let instruction = match (op, float) {
+            (BinOp::Add, FloatTy::F32) => Instruction::AddF32 { l1, l2 },
+            (BinOp::Add, FloatTy::F64) => Instruction::AddF64 { l1, l2 },
+            (BinOp::Sub, FloatTy::F32) => Instruction::SubF32 { l1, l2 },
+            (BinOp::Sub, FloatTy::F64) => Instruction::SubF64 { l1, l2 },
+            (BinOp::Mul, FloatTy::F32) => Instruction::MulF32 { l1, l2 },
+            (BinOp::Mul, FloatTy::F64) => Instruction::MulF64 { l1, l2 },
+            (BinOp::Div, FloatTy::F32) => Instruction::DivF32 { l1, l2 },
+            (BinOp::Div, FloatTy::F64) => Instruction::DivF64 { l1, l2 },
+            (BinOp::Mod, FloatTy::F32) => Instruction::ModF32 { l1, l2 },
+            (BinOp::Mod, FloatTy::F64) => Instruction::ModF64 { l1, l2 },
+            (BinOp::Exp, FloatTy::F32) => Instruction::PowF32 { l1, l2 },
+            (BinOp::Exp, FloatTy::F64) => Instruction::PowF64 { l1, l2 },
+            _ => return Err("comparisons of floats"),
+        };
+
+        self.builder.add_instruction(instruction);
+        Ok(())
+    }
+
+    /// Compare the values in the first two scratch registers, and write
+    /// whether the comparison holds into the first scratch register.
+    fn compare(&mut self, op: BinOp, bits: u64, signed: bool) {
+        let (l1, l2, result, location) = (SCRATCH[0], SCRATCH[1], SCRATCH[6], SCRATCH[7]);
+
+        // Flipping the sign bit of signed integers orders them in the same
+        // way as unsigned integers.
+        if signed {
+            self.builder
+                .add_instruction(Instruction::Write { dest: result, value: 1 << (bits - 1) })
+                .add_instruction(Instruction::Xor64 { l1, l2: result })
+                .add_instruction(Instruction::Xor64 { l1: l2, l2: result });
+        }
+
+        let done = self.builder.new_label();
+
+        self.builder
+            .add_instruction(Instruction::Cmp { l1, l2 })
+            .add_instruction(Instruction::Write { dest: result, value: 1 })
+            .write_label(location, done);
+
+        // Whether the comparison holds if the left-hand side is greater,
+        // smaller, or equal to the right-hand side.
+        let (greater, smaller, equal) = match op {
+            BinOp::Eq => (false, false, true),
+            BinOp::Neq => (true, true, false),
+            BinOp::Gt => (true, false, false),
+            BinOp::GtEq => (true, false, true),
+            BinOp::Lt => (false, true, false),
+            _ => (false, true, true),
+        };
+
+        if greater {
+            self.builder.add_instruction(Instruction::JmpPos { l1, location });
+        }
+
+        if smaller {
+            self.builder.add_instruction(Instruction::JmpNeg { l1, location });
+        }
+
+        if equal {
+            self.builder.add_instruction(Instruction::JmpZero { l1, location });
+        }
+
+        self.builder
+            .add_instruction(Instruction::Write { dest: result, value: 0 })
+            .bind_label(done);
+
+        self.mov(result, l1);
+    }
+
+    /// Shift the value in the first scratch register by the amount in the
+    /// second scratch register, and set the overflow flag to whether the
+    /// amount is too large. The amount is masked so that it is smaller than
+    /// the bit-width of the value.
+    fn shift(&mut self, op: BinOp, bits: u64, signed: bool) {
+        let (l1, l2, overflow, temp) = (SCRATCH[0], SCRATCH[1], SCRATCH[2], SCRATCH[3]);
+        let (value, amount) = (SCRATCH[4], SCRATCH[5]);
+
+        // The comparison of `amount >= bits` is computed on the first two
+        // scratch registers, so the operands are saved beforehand.
+        self.mov(l1, value);
+        self.mov(l2, amount);
+        self.mov(l2, l1);
+        self.builder.add_instruction(Instruction::Write { dest: l2, value: bits });
+        self.compare(BinOp::GtEq, 64, false);
+        self.mov(l1, overflow);
+        self.mov(value, l1);
+        self.mov(amount, l2);
+
+        self.builder
+            .add_instruction(Instruction::Write { dest: temp, value: bits - 1 })
+            .add_instruction(Instruction::And64 { l1: l2, l2: temp });
+
+        match op {
+            BinOp::Shl => {
+                self.builder.add_instruction(sized!(bits, [Shl8, Shl16, Shl32, Shl64], l1, l2));
+            }
+            // An arithmetic shift is computed from a logical shift, since
+            // `x >> n` is equal to `((x ^ m) >>> n) - (m >>> n)` where `m`
+            // is the sign bit.
+            _ if signed => {
+                self.builder
+                    .add_instruction(Instruction::Write { dest: temp, value: 1 << (bits - 1) })
+                    .add_instruction(sized!(bits, [Xor8, Xor16, Xor32, Xor64], l1, temp))
+                    .add_instruction(sized!(bits, [Shr8, Shr16, Shr32, Shr64], l1, l2))
+                    .add_instruction(sized!(bits, [Shr8, Shr16, Shr32, Shr64], temp, l2))
+                    .add_instruction(sized!(bits, [Sub8, Sub16, Sub32, Sub64], l1, temp));
+            }
+            _ => {
+                self.builder.add_instruction(sized!(bits, [Shr8, Shr16, Shr32, Shr64], l1, l2));
+            }
+        }
+    }
+
+    fn cast(
+        &mut self,
+        kind: CastKind,
+        operand: &Operand,
+        ty: ReprTyId,
+        dest: Register,
+    ) -> Result<(), Unsupported> {
+        let CastKind::IntToInt = kind else {
+            return Err("casts between integers and floats");
+        };
+
+        let (l1, l2) = (SCRATCH[0], SCRATCH[1]);
+        let src = operand.ty(&self.body.aux());
+        let (from, to) = (self.bits_of(src)?, self.bits_of(ty)?);
+
+        self.load(operand, l1)?;
+
+        // Signed integers are sign-extended by computing `(x ^ m) - m` where
+        // `m` is the sign bit, and then truncated to the width of the type.
+        if src.borrow().is_signed() && to > from {
+            self.builder
+                .add_instruction(Instruction::Write { dest: l2, value: 1 << (from - 1) })
+                .add_instruction(Instruction::Xor64 { l1, l2 })
+                .add_instruction(Instruction::Sub64 { l1, l2 });
+        }
+
+        if to < 64 {
+            self.builder
+                .add_instruction(Instruction::Write { dest: l2, value: (1 << to) - 1 })
+                .add_instruction(Instruction::And64 { l1, l2 });
+        }
+
+        self.mov(l1, dest);
+        Ok(())
+    }
+
+    /// Resolve a [Place] into the index of its first register, the number of
+    /// registers that it takes up, and its type.
+    fn place(&mut self, place: Place) -> Result<(usize, usize, ReprTyId), Unsupported> {
+        let body = self.body;
+        let (mut start, _) = self.registers.local(place.local)?;
+        let mut ty = body.locals[place.local].ty;
+        let mut variant: Option<VariantIdx> = None;
+
+        for projection in body.projections().borrow(place.projections).iter() {
+            match *projection {
+                PlaceProjection::Field(field) => {
+                    start += self.allocator.field_offset(ty, variant, field)?;
+                    ty = ty
+                        .borrow()
+                        .on_field_access(field, variant)
+                        .ok_or("fields of values that aren't structs, tuples or enums")?;
+                    variant = None;
+                }
+                PlaceProjection::Downcast(index) => variant = Some(index),
+                _ => return Err("strings, references and arrays"),
+            }
+        }
+
+        Ok((start, self.allocator.count_of(ty)?, ty))
+    }
+
+    /// Get the values that make up an [Operand].
+    fn sources(&mut self, operand: &Operand) -> Result<Vec<Source>, Unsupported> {
+        match operand {
+            Operand::Place(place) => {
+                let (start, count, _) = self.place(*place)?;
+                Ok((start..start + count).map(Source::Register).collect())
+            }
+            Operand::Const(value) => self.constant(*value),
+        }
+    }
+
+    fn constant(&mut self, value: Const) -> Result<Vec<Source>, Unsupported> {
+        let sources = match value.kind() {
+            ConstKind::Zero => vec![],
+            ConstKind::Scalar(scalar) => {
+                if scalar.size().bytes() > 8 {
+                    return Err("integers that are wider than 64 bits");
+                }
+
+                vec![Source::Immediate(scalar.assert_bits(scalar.size()) as u64)]
+            }
+            ConstKind::FnAddr(instance) => vec![Source::Label(self.function(instance)?)],
+            ConstKind::Pair { .. } | ConstKind::Alloc { .. } => {
+                return Err("constants that are stored in memory")
+            }
+        };
+
+        if sources.len() != self.allocator.count_of(value.ty())? {
+            return Err("constants that are stored in memory");
+        }
+
+        Ok(sources)
+    }
+
+    /// Load an [Operand] that takes up a single register into `dest`.
+    fn load(&mut self, operand: &Operand, dest: Register) -> Result<(), Unsupported> {
+        match self.sources(operand)?.as_slice() {
+            [source] => {
+                self.load_source(*source, dest);
+                Ok(())
+            }
+            _ => Err("operations on values of this type"),
+        }
+    }
+
+    fn load_source(&mut self, source: Source, dest: Register) {
+        match source {
+            Source::Register(src) => self.mov(register(src), dest),
+            Source::Immediate(value) => {
+                self.builder.add_instruction(Instruction::Write { dest, value });
+            }
+            Source::Label(label) => {
+                self.builder.write_label(dest, label);
+            }
+        }
+    }
+
+    /// Write the `sources` into the registers starting from `dest`. If any of
+    /// the sources would be overwritten before it is read, then the values
+    /// are moved through the stack.
+    fn write(&mut self, dest: usize, sources: &[Source]) {
+        let range = dest..dest + sources.len();
+        let overlaps = sources.iter().enumerate().any(|(index, source)| {
+            matches!(source, Source::Register(src) if range.contains(src) && *src != dest + index)
+        });
+
+        if !overlaps {
+            for (index, source) in sources.iter().enumerate() {
+                self.load_source(*source, register(dest + index));
+            }
+
+            return;
+        }
+
+        for source in sources {
+            self.load_source(*source, SCRATCH[0]);
+            self.builder.add_instruction(Instruction::Push64 { l1: SCRATCH[0] });
+        }
+
+        for index in range.rev() {
+            self.builder.add_instruction(Instruction::Pop64 { l1: register(index) });
+        }
+    }
+
+    fn mov(&mut self, src: Register, dest: Register) {
+        if src != dest {
+            self.builder.add_instruction(Instruction::Mov { src, dest });
+        }
+    }
+
+    fn jump(&mut self, target: BasicBlock) {
+        self.builder
+            .write_label(SCRATCH[0], self.blocks[target])
+            .add_instruction(Instruction::Jmp { location: SCRATCH[0] });
+    }
+
+    /// Get the label of a function, the function must have a body.
+    fn function(&self, instance: InstanceId) -> Result<Label, Unsupported> {
+        self.functions.get(&instance).copied().ok_or("calls to functions without a body")
+    }
+
+    /// Get the index of a foreign function within the [ForeignItem]s of the
+    /// program, the function is added to them if it hasn't been called yet.
+    fn foreign_item(&mut self, instance: InstanceId) -> Result<usize, Unsupported> {
+        if let Some(index) = self.foreign.indices.get(&instance) {
+            return Ok(*index);
+        }
+
+        let signature = instance_signature(instance, self.lc, self.foreign.target)
+            .ok_or("calls to foreign functions with this signature")?;
+
+        let (library, symbol) = match self.foreign.link_items.get(instance) {
+            Some(item) => (item.library.to_string(), item.symbol.to_string()),
+            None => ("c".to_string(), compute_symbol_name(instance)),
+        };
+
+        let index = self.foreign.items.len();
+        self.foreign.items.push(ForeignItem { library, symbol, signature });
+        self.foreign.indices.insert(instance, index);
+        Ok(index)
+    }
+
+    /// Get the value of the discriminant of a variant of the enum `ty`.
+    fn discriminant_of(&self, ty: ReprTyId, variant: VariantIdx) -> Result<u64, Unsupported> {
+        let Some((_, value)) = ty.borrow().discriminant_for_variant(variant) else {
+            return Ok(0);
+        };
+
+        let size = self
+            .lc
+            .size_of_ty(ty.borrow().discriminant_ty())
+            .map_err(|_| "types without a layout")?;
+
+        Ok(size.truncate(value) as u64)
+    }
+
+    /// Get the width of an integral type in bits.
+    fn bits_of(&self, ty: ReprTyId) -> Result<u64, Unsupported> {
+        let size = self.lc.size_of_ty(ty).map_err(|_| "types without a layout")?;
+
+        match size.bits() {
+            bits @ (8 | 16 | 32 | 64) => Ok(bits),
+            _ => Err("integers that are wider than 64 bits"),
+        }
+    }
+
+    /// Stop the execution of the program with the given message once this
+    /// point is reached.
+    fn trap(&mut self, message: impl ToString, origin: AstNodeId) {
+        let id = self.traps.len() as u32;
+
+        self.traps.push(Trap { message: message.to_string(), span: origin.span() });
+        self.builder.add_instruction(Instruction::Trap { id });
+    }
+
+    /// Stop the execution of the program once it reaches a construct that
+    /// the VM doesn't support.
+    fn unsupported(&mut self, reason: Unsupported, origin: AstNodeId) {
+        self.trap(format!("the VM doesn't support {reason} yet"), origin);
+    }
+}
+
+/// Check whether the type is an enum.
+fn is_enum(ty: ReprTyId) -> bool {
+    matches!(*ty.borrow(), ReprTy::Adt(id) if id.borrow().flags.is_enum())
+}
@@ -1,71 +1,14 @@
 //! Contains logic for computing ABIs of function types and their
 //! arguments.
 
-use hash_abi::{
-    ArgAbi, ArgAttributeFlag, ArgAttributes, ArgExtension, CallingConvention, FnAbi, PassMode,
-};
-use hash_ir::ty::{Instance, InstanceId, Mutability, RefKind, ReprTy, ReprTyId};
+use hash_abi::{CallingConvention, FnAbi};
+use hash_ir::ty::{Instance, InstanceId, ReprTy, ReprTyId, ReprTyListId};
 use hash_repr::compute::LayoutError;
-use hash_storage::store::statics::StoreId;
-use hash_target::abi::{Scalar, ScalarKind};
+use hash_storage::store::{statics::StoreId, SequenceStoreKey};
+use hash_target::abi::Abi;
 
 use crate::traits::{layout::LayoutMethods, HasCtxMethods};
 
-/// Adjust the attributes of an argument ABI based on the provided
-/// [Layout] and [Scalar] information. This is required to do since
-/// the scalar maybe a pair of values.
-fn adjust_arg_attributes(
-    attributes: &mut ArgAttributes,
-    ty: ReprTyId,
-    scalar: Scalar,
-    is_return: bool,
-) {
-    // Booleans are always "noundef" values...
-    if scalar.is_bool() {
-        attributes.extend_with(ArgExtension::ZeroExtend);
-        attributes.set(ArgAttributeFlag::NO_UNDEF);
-        return;
-    }
-
-    // If this scalar should always be initialised then we can set the "noundef"
-    // attribute.
-    if !scalar.is_union() {
-        attributes.set(ArgAttributeFlag::NO_UNDEF);
-    }
-
-    // If this scalar represents a pointer, then we can deduce more
-    // information about this particular argument.
-    let Scalar::Initialised { kind: ScalarKind::Pointer { .. }, valid_range } = scalar else {
-        return;
-    };
-
-    // If the pointer is never null, then we can set the "non_null" attribute.
-    if !valid_range.contains(0) {
-        attributes.set(ArgAttributeFlag::NON_NULL);
-    }
-
-    // If the pointer type is a read-only, then we can set the "read_only"
-    // attribute.
-    ty.map(|ty| {
-        let ReprTy::Ref(_, mutability, kind) = ty else {
-            return;
-        };
-
-        // @@Future: can we deduce the same thing for an `Rc` pointer?
-        if !is_return
-            && matches!(kind, RefKind::Raw | RefKind::Normal)
-            && *mutability == Mutability::Immutable
-        {
-            attributes.set(ArgAttributeFlag::READ_ONLY);
-        }
-    });
-
-    // @@Todo: we currently can't deduce any information about aliasing of
-    // pointer data, so we can't really derive the "no_alias" attribute. If
-    // we become stricter with these rules, then we can possibly emit more
-    // useful information here.
-}
-
 /// Errors that may occur when computing the ABI of a function.
 #[derive(Debug)]
 pub enum FnAbiError {
@@ -83,42 +26,75 @@ pub fn compute_fn_abi_from_instance<'b, Ctx: HasCtxMethods<'b> + LayoutMethods<'
     ctx: &Ctx,
     instance: InstanceId,
 ) -> Result<FnAbi, FnAbiError> {
-    let Instance { params, ret_ty, abi, .. } = instance.value();
+    let Instance { params, ret_ty, abi, is_c_variadic, .. } = instance.value();
+
+    let mut fn_abi = compute_fn_abi(ctx, params, ret_ty, abi)?;
+    fn_abi.c_variadic = is_c_variadic;
+    Ok(fn_abi)
+}
 
+/// Compute an [FnAbi] for a call to a C-variadic [Instance] which passes
+/// arguments of the given types in the variadic part of the call.
+///
+/// N.B. the passed "instance" must be C-variadic.
+pub fn compute_variadic_fn_abi_from_instance<'b, Ctx: HasCtxMethods<'b> + LayoutMethods<'b>>(
+    ctx: &Ctx,
+    instance: InstanceId,
+    variadic_args: &[ReprTyId],
+) -> Result<FnAbi, FnAbiError> {
+    let Instance { params, ret_ty, abi, is_c_variadic, .. } = instance.value();
+    debug_assert!(is_c_variadic, "expected a C-variadic instance");
+
+    let mut args = params.borrow().to_vec();
+    args.extend_from_slice(variadic_args);
+
+    let mut fn_abi = compute_fn_abi(ctx, ReprTyListId::seq(args), ret_ty, abi)?;
+    fn_abi.c_variadic = true;
+    fn_abi.fixed_count = params.len();
+    Ok(fn_abi)
+}
+
+/// Compute an [FnAbi] from a function pointer type, i.e. a [`ReprTy::Fn`],
+/// using the ABI that is recorded in the pointer type.
+///
+/// N.B. the passed "ty" must be a function pointer type.
+pub fn compute_fn_abi_from_ty<'b, Ctx: HasCtxMethods<'b> + LayoutMethods<'b>>(
+    ctx: &Ctx,
+    ty: ReprTyId,
+) -> Result<FnAbi, FnAbiError> {
+    let ReprTy::Fn { params, return_ty, abi } = ty.value() else {
+        panic!("expected a function pointer type, but got `{ty}`")
+    };
+
+    compute_fn_abi(ctx, params, return_ty, abi)
+}
+
+/// Compute an [FnAbi] from the parameter types, return type and the specified
+/// [Abi] of a function.
+fn compute_fn_abi<'b, Ctx: HasCtxMethods<'b> + LayoutMethods<'b>>(
+    ctx: &Ctx,
+    params: ReprTyListId,
+    ret_ty: ReprTyId,
+    abi: Abi,
+) -> Result<FnAbi, FnAbiError> {
     // map the ABI to a calling convention whilst making any adjustments according
     // to the target.
     let calling_convention = CallingConvention::make_from_abi_and_target(abi, ctx.target());
 
-    // Closure to create a new argument for the ABI from a given type.
-    let make_arg_abi = |ty: ReprTyId, index: Option<usize>| {
-        let is_return = index.is_none();
-        let info = ctx.layout_of(ty);
+    let mut fn_abi = FnAbi::of_fn_sig(params, ret_ty, ctx.layouts(), calling_convention)
+        .map_err(FnAbiError::Layout)?;
 
-        let mut arg = ArgAbi::new(info, |scalar| {
-            let mut attributes = ArgAttributes::new();
-            adjust_arg_attributes(&mut attributes, ty, scalar, is_return);
-            attributes
-        });
+    fn_abi.adjust_for_abi(ctx.layouts(), abi, ctx.target());
 
-        // @@Todo: we might have to adjust the attribute pass mode
-        // for ZSTs on specific platforms since they don't ignore them?
-        if is_return && info.is_zst() {
-            arg.mode = PassMode::Ignore;
+    // Vectors are passed directly in vector registers, unless the backend
+    // has no notion of them.
+    if !ctx.supports_vector_registers() {
+        for arg in fn_abi.args.iter_mut().chain(std::iter::once(&mut fn_abi.ret_abi)) {
+            if arg.info.is_vector() && !arg.is_indirect() {
+                arg.make_indirect();
+            }
         }
-
-        Ok(arg)
-    };
-
-    let fn_abi = FnAbi {
-        args: params
-            .borrow()
-            .iter()
-            .enumerate()
-            .map(|(i, ty)| make_arg_abi(*ty, Some(i)))
-            .collect::<Result<_, _>>()?,
-        ret_abi: make_arg_abi(ret_ty, None)?,
-        calling_convention,
-    };
+    }
 
     Ok(fn_abi)
 }
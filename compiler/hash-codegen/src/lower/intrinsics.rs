@@ -229,7 +229,7 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         // We need to generate some additional code based on the `from -> to`
         use abi::ScalarKind::*;
 
-        match (from_scalar.kind(), to_scalar.kind()) {
+        value = match (from_scalar.kind(), to_scalar.kind()) {
             (Int { .. } | Float { .. }, Int { .. } | Float { .. }) => {
                 builder.bit_cast(value, to_ty)
             }
@@ -262,10 +262,13 @@ impl<'ir, 'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> IrVisitorMut<'ir>
             // If the projection is a field, and the type of the
             // base can be represented as an immediate value, then
             // we use the `ctx` as the base context since this
-            // is still an operand.
+            // is still an operand. Fields of vectors are always read
+            // from memory.
             match projection {
                 PlaceProjection::Field(_) if place_ctx.is_operand() => {
-                    if self.fn_builder.ctx.is_backend_immediate(base_layout) {
+                    if self.fn_builder.ctx.is_backend_immediate(base_layout)
+                        && !base_layout.is_vector()
+                    {
                         base_ctx = place_ctx;
                     }
                 }
@@ -325,7 +328,8 @@ impl<'ir, 'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> IrVisitorMut<'ir>
                 MutablePlaceCtx::Store
                 | MutablePlaceCtx::Projection
                 | MutablePlaceCtx::Ref
-                | MutablePlaceCtx::Discriminant,
+                | MutablePlaceCtx::Discriminant
+                | MutablePlaceCtx::Drop,
             )
             | PlaceCtx::Immutable(
                 ImmutablePlaceCtx::Inspect | ImmutablePlaceCtx::Ref | ImmutablePlaceCtx::Projection,
@@ -19,7 +19,7 @@ use crate::{
     common::MemFlags,
     traits::{
         builder::BlockBuilderMethods, constants::ConstValueBuilderMethods, layout::LayoutMethods,
-        statics::StaticMethods, ty::TypeBuilderMethods, CodeGenObject,
+        statics::StaticMethods, ty::TypeBuilderMethods, CodeGenObject, HasCtxMethods,
     },
 };
 
@@ -267,12 +267,16 @@ impl<'a, 'b, V: CodeGenObject> OperandRef<V> {
                     )
                 };
 
+                // Strings that are pooled with another string refer to the
+                // data of the other string.
+                let data = builder.ctx().ir_ctx().globals().data_of(data);
                 let (ptr, size) = builder.const_str(data);
                 OperandValue::Pair(ptr, size)
             }
             ConstKind::Alloc { offset, alloc } => {
                 return Self::from_alloc(builder, info, alloc, offset)
             }
+            ConstKind::FnAddr(instance) => OperandValue::Immediate(builder.const_fn_addr(instance)),
         };
 
         OperandRef { value, info }
@@ -284,6 +288,7 @@ impl<'a, 'b, V: CodeGenObject> OperandRef<V> {
         alloc: AllocId,
         offset: Size,
     ) -> Self {
+        let alloc = builder.ctx().ir_ctx().globals().data_of(alloc);
         let alloc_align = alloc.borrow().align();
         // Ensure that the alignment of the allocation is the same as the
         // computed alignment of the type.
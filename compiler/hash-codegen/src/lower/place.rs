@@ -6,7 +6,7 @@ use hash_ir::{
     ty::{PlaceTy, ReprTyId, VariantIdx},
 };
 use hash_reporting::macros::panic_on_span;
-use hash_repr::{LayoutShape, Variants};
+use hash_repr::{DiscriminantReadInfo, LayoutShape, TagEncoding, Variants};
 use hash_storage::store::{statics::StoreId, SequenceStoreKey};
 use hash_target::{
     abi::{AbiRepresentation, ScalarKind},
@@ -127,27 +127,12 @@ impl<'a, 'b, V: CodeGenObject> PlaceRef<V> {
         let cast_info = builder.layout_of(cast_to);
         let cast_to_ty = builder.immediate_backend_ty(cast_info);
 
-        // Check if this place is represented as "uninhabited" then we
-        // simply set the result of this as an undefined value of the `cast_to`
-        // type...
-        if self.info.is_uninhabited() {
-            return builder.const_undef(cast_to_ty);
-        }
-
-        let variants = self.info.layout.borrow().variants.clone();
-
-        match variants {
-            Variants::Single { index } => {
-                let value = self
-                    .info
-                    .ty
-                    .borrow()
-                    .discriminant_for_variant(index)
-                    .map_or(index.raw() as u128, |(_, value)| value);
-
-                builder.const_uint_big(cast_to_ty, value)
-            }
-            Variants::Multiple { field, tag, .. } => {
+        match self.info.discriminant_read_info(builder.layouts()) {
+            // If this place is represented as "uninhabited" then we simply set
+            // the result of this as an undefined value of the `cast_to` type...
+            DiscriminantReadInfo::Uninhabited => builder.const_undef(cast_to_ty),
+            DiscriminantReadInfo::Constant { value } => builder.const_uint_big(cast_to_ty, value),
+            DiscriminantReadInfo::Tag { field, tag, encoding: TagEncoding::Direct, .. } => {
                 let tag_ptr = self.project_field(builder, field);
                 let tag_operand = builder.load_operand(tag_ptr);
                 let tag_immediate = tag_operand.immediate_value();
@@ -232,9 +217,10 @@ impl<'a, 'b, V: CodeGenObject> PlaceRef<V> {
             AbiRepresentation::Scalar(_)
             | AbiRepresentation::Pair(..)
             | AbiRepresentation::Vector { .. }
-                if field_info.is_zst() =>
+                if field_info.is_zst() || abi.is_vector() =>
             {
-                // If this is a zst field, we have to manually offset the pointer.
+                // If this is a zst field, or an element of a vector which has no
+                // structural type to index into, we have to manually offset the pointer.
                 builder.get_element_pointer(
                     builder.type_i8(),
                     self.value,
@@ -312,7 +298,7 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
             LocalRef::Place(place) => place,
             LocalRef::Operand(None) => {
                 panic_on_span!(
-                    self.body.span(),
+                    self.body.source_span(),
                     "using still-pending operand local `{:?}` as place",
                     place.local
                 )
@@ -9,6 +9,7 @@ use hash_ir::{
     ty::{self, RefKind, ReprTyId, VariantIdx, COMMON_REPR_TYS},
 };
 use hash_storage::store::statics::StoreId;
+use hash_target::overflow::ShiftAmountSemantics;
 
 use super::{
     locals::LocalRef,
@@ -99,6 +100,44 @@ fn apply_shift_mask<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>>(
     builder.and(shift_value, mask)
 }
 
+/// Check whether the shift amount `rhs` is not smaller than the bit-width of
+/// `lhs`, i.e. whether any of the bits of `rhs` that are outside of the shift
+/// mask are set. The amount is treated as unsigned, so negative amounts are
+/// always too large.
+fn shift_amount_too_large<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>>(
+    builder: &mut Builder,
+    lhs: Builder::Value,
+    rhs: Builder::Value,
+) -> Builder::Value {
+    let lhs_ty = builder.ty_of_value(lhs);
+    let rhs_ty = builder.ty_of_value(rhs);
+
+    let invert_mask = shift_mask_value(builder, lhs_ty, rhs_ty, true);
+    let outer_bits = builder.and(rhs, invert_mask);
+
+    builder.icmp(IntComparisonKind::Ne, outer_bits, builder.const_null(rhs_ty))
+}
+
+/// Apply the [ShiftAmountSemantics] of the target onto the result of a shift
+/// whose amount was masked. If the amounts are saturated, then the result is
+/// replaced by `saturated` when the shift amount `rhs` is too large.
+fn saturate_shift<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>>(
+    builder: &mut Builder,
+    lhs: Builder::Value,
+    rhs: Builder::Value,
+    value: Builder::Value,
+    saturated: impl FnOnce(&mut Builder) -> Builder::Value,
+) -> Builder::Value {
+    match builder.target().int_overflow.shift_amount {
+        ShiftAmountSemantics::Mask => value,
+        ShiftAmountSemantics::Saturate => {
+            let too_large = shift_amount_too_large(builder, lhs, rhs);
+            let saturated = saturated(builder);
+            builder.select(too_large, saturated, value)
+        }
+    }
+}
+
 /// This function will ensure that the operands to a `>>` (bitwise right-shift)
 /// operator are always valid. This means that if the shifting value is greater
 /// in size than the bit-width of the left-hand side operand, it needs
@@ -111,15 +150,24 @@ fn build_unchecked_rshift<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>>(
     lhs: Builder::Value,
     rhs: Builder::Value,
 ) -> Builder::Value {
-    let rhs = cast_shift_value(builder, lhs, rhs);
-    let rhs = apply_shift_mask(builder, rhs);
+    let amount = cast_shift_value(builder, lhs, rhs);
+    let amount = apply_shift_mask(builder, amount);
 
     if ty.borrow().is_signed() {
-        // Arithmetic right shift
-        builder.ashr(lhs, rhs)
+        // Arithmetic right shift, which fills the value with the sign bit
+        // when the amount is saturated.
+        let value = builder.ashr(lhs, amount);
+        saturate_shift(builder, lhs, rhs, value, |builder| {
+            let lhs_ty = builder.ty_of_value(lhs);
+            let bits = shift_mask_value(builder, lhs_ty, lhs_ty, false);
+            builder.ashr(lhs, bits)
+        })
     } else {
         // Logical right shift
-        builder.lshr(lhs, rhs)
+        let value = builder.lshr(lhs, amount);
+        saturate_shift(builder, lhs, rhs, value, |builder| {
+            builder.const_null(builder.ty_of_value(lhs))
+        })
     }
 }
 
@@ -128,9 +176,11 @@ fn build_unchecked_lshift<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>>(
     lhs: Builder::Value,
     rhs: Builder::Value,
 ) -> Builder::Value {
-    let rhs = cast_shift_value(builder, lhs, rhs);
-    let rhs = apply_shift_mask(builder, rhs);
-    builder.shl(lhs, rhs)
+    let amount = cast_shift_value(builder, lhs, rhs);
+    let amount = apply_shift_mask(builder, amount);
+    let value = builder.shl(lhs, amount);
+
+    saturate_shift(builder, lhs, rhs, value, |builder| builder.const_null(builder.ty_of_value(lhs)))
 }
 
 impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
@@ -480,10 +530,9 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                 if is_float {
                     builder.fpow(lhs_value, rhs_value)
                 } else {
-                    // Integer scalars don't support, @@Todo: maybe we can use:
-                    //
-                    // https://llvm.org/docs/LangRef.html#llvm-experimental-constrained-powi-intrinsic
-                    unreachable!("`**` for integer operands is illegal")
+                    // Integer exponentiation is lowered into a loop of
+                    // multiplications when building the IR.
+                    unreachable!("`**` for integer operands should have been lowered")
                 }
             }
 
@@ -494,6 +543,14 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
             ir::BinOp::Shr => build_unchecked_rshift(builder, ty, lhs_value, rhs_value),
             ir::BinOp::Shl => build_unchecked_lshift(builder, lhs_value, rhs_value),
 
+            // Pointer offsets are computed in terms of the pointee type, so
+            // we emit a `gep` using the layout of the pointee.
+            ir::BinOp::Offset => {
+                let pointee = ty.borrow().on_deref().expect("expected pointer for `offset`");
+                let pointee_ty = builder.backend_ty_from_info(builder.layout_of(pointee));
+                builder.bounded_get_element_pointer(pointee_ty, lhs_value, &[rhs_value])
+            }
+
             ir::BinOp::Eq
             | ir::BinOp::Neq
             | ir::BinOp::Gt
@@ -550,17 +607,7 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                 (value, overflow)
             }
             BinOp::Shl | BinOp::Shr => {
-                let lhs_ty = builder.ty_of_value(lhs_value);
-                let rhs_ty = builder.ty_of_value(rhs_value);
-
-                let invert_mask = shift_mask_value(builder, lhs_ty, rhs_ty, true);
-                let outer_bits = builder.and(rhs_value, invert_mask);
-
-                // If the outer_bits don't equal to zero, then an overflow will occur
-                // since we know that if the result is non-zero, then it is greater
-                // than the maximum legal bit-shift value.
-                let overflow =
-                    builder.icmp(IntComparisonKind::Ne, outer_bits, builder.const_null(rhs_ty));
+                let overflow = shift_amount_too_large(builder, lhs_value, rhs_value);
                 let value = self.codegen_scalar_binop(builder, operator, lhs_value, rhs_value, ty);
 
                 (value, overflow)
@@ -6,7 +6,7 @@ use hash_reporting::macros::panic_on_span;
 use hash_storage::store::statics::StoreId;
 
 use super::{locals::LocalRef, FnBuilder};
-use crate::traits::builder::BlockBuilderMethods;
+use crate::traits::{builder::BlockBuilderMethods, constants::ConstValueBuilderMethods};
 
 impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
     /// Lower a Hash IR [Statement] into a target backend code.
@@ -65,6 +65,15 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                     place.storage_dead(builder);
                 }
             }
+            StatementKind::Assume(ref operand) => {
+                let value = self.codegen_operand(builder, operand).immediate_value();
+
+                // If the assumption is trivially true, then there is no
+                // point in emitting it.
+                if builder.const_to_optional_u128(value, false) != Some(1) {
+                    builder.codegen_assume_intrinsic(value);
+                }
+            }
             StatementKind::Nop => {}
         }
     }
@@ -10,10 +10,15 @@
 //! whether two blocks have been merged together.
 
 use hash_abi::{ArgAbi, FnAbiId, PassMode};
-use hash_ir::{intrinsics::Intrinsic, ir, lang_items::LangItem, ty::COMMON_REPR_TYS};
+use hash_ir::{
+    intrinsics::Intrinsic,
+    ir,
+    lang_items::LangItem,
+    ty::{ReprTy, COMMON_REPR_TYS},
+};
 use hash_pipeline::settings::OptimisationLevel;
-use hash_source::constant::AllocId;
-use hash_storage::store::{statics::StoreId, Store};
+use hash_source::constant::{AllocId, SIntTy, UIntTy};
+use hash_storage::store::{statics::StoreId, SequenceStoreKey, Store};
 use hash_target::abi::{AbiRepresentation, ValidScalarRange};
 
 use super::{
@@ -49,6 +54,16 @@ pub enum ReturnDestinationKind<V> {
     DirectOperand(ir::Local),
 }
 
+/// [FnCallee] defines the subject of a function call, which is either
+/// a known function, or a function pointer value.
+pub enum FnCallee<F, V> {
+    /// A direct call to the specified function.
+    Direct(F),
+
+    /// An indirect call through the provided function pointer value.
+    Indirect(V),
+}
+
 impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
     /// Emit the target backend IR for a Hash IR [Terminator]. This
     /// function returns whether the block is a candidate for merging
@@ -86,7 +101,10 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
             ir::TerminatorKind::Goto(target) => {
                 self.codegen_goto_terminator(builder, *target, can_merge())
             }
-            ir::TerminatorKind::Call { ref op, ref args, destination, target } => {
+            // @@Future: emit an `invoke` with a landing pad for the `unwind`
+            // edge. Currently, panics abort the program and so the cleanup
+            // blocks are never reached.
+            ir::TerminatorKind::Call { ref op, ref args, destination, target, .. } => {
                 self.codegen_call_terminator(builder, op, args, *destination, *target, can_merge())
             }
             ir::TerminatorKind::Return => {
@@ -97,11 +115,17 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                 builder.unreachable();
                 false
             }
+            // Cleanup blocks are never reached since panics abort the
+            // program, see the note on `Call` above.
+            ir::TerminatorKind::Resume => {
+                builder.unreachable();
+                false
+            }
             ir::TerminatorKind::Switch { ref value, ref targets } => {
                 self.codegen_switch_terminator(builder, value, targets);
                 false
             }
-            ir::TerminatorKind::Assert { ref condition, expected, kind, target } => self
+            ir::TerminatorKind::Assert { ref condition, expected, kind, target, .. } => self
                 .codegen_assert_terminator(
                     builder,
                     condition,
@@ -110,6 +134,12 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                     *target,
                     can_merge(),
                 ),
+            // @@Future: once types can specify destructors, the drop glue of
+            // the type should be called here. Currently, no type requires any
+            // cleanup, and so dropping a value is just a jump to the target.
+            ir::TerminatorKind::Drop { target, .. } => {
+                self.codegen_goto_terminator(builder, *target, can_merge())
+            }
         }
     }
 
@@ -152,13 +182,21 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         let call_subject = self.codegen_operand(builder, op);
 
         let ty = call_subject.info.ty;
-        let instance = ty.borrow().as_instance();
-        let is_intrinsic = instance.borrow().is_intrinsic();
+
+        // If the subject of the call is not a function definition, then it is
+        // a function pointer and so the function is called indirectly.
+        let instance = match *ty.borrow() {
+            ReprTy::FnDef { instance } => Some(instance),
+            _ => None,
+        };
+
         let mut maybe_intrinsic = None;
 
         // If this is an intrinsic, we will generate the required code
         // for the intrinsic here...
-        if is_intrinsic {
+        if let Some(instance) = instance
+            && instance.borrow().is_intrinsic()
+        {
             maybe_intrinsic = Intrinsic::from_str_name(instance.borrow().name().into());
 
             // We exit early for transmute since we don't need to compute the ABI
@@ -194,7 +232,20 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
 
         // compute the function pointer value and the ABI
         let abis = self.ctx.cg_ctx().abis();
-        let fn_abi = abis.create_fn_abi(builder, instance);
+        let fn_abi = match instance {
+            // Calls to C-variadic functions have an ABI which also describes the
+            // arguments that are passed in the variadic part of the call.
+            Some(instance) if instance.borrow().is_c_variadic() => {
+                let fixed_count = instance.borrow().params.len();
+                let info = self.body.aux();
+                let variadic_args =
+                    fn_args[fixed_count..].iter().map(|arg| arg.ty(&info)).collect::<Vec<_>>();
+
+                abis.create_variadic_fn_abi(builder, instance, &variadic_args)
+            }
+            Some(instance) => abis.create_fn_abi(builder, instance),
+            None => abis.create_fn_ptr_abi(builder, ty),
+        };
         let ret_abi = abis.map_fast(fn_abi, |abi| abi.ret_abi);
 
         // If the return ABI pass mode is "indirect", then this means that
@@ -270,14 +321,17 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
             };
         }
 
-        let fn_ptr = builder.get_fn_ptr(instance);
+        let callee = match instance {
+            Some(instance) => FnCallee::Direct(builder.get_fn_ptr(instance)),
+            None => FnCallee::Indirect(call_subject.immediate_value()),
+        };
 
         // Finally, generate the code for the function call and
         // cleanup
         self.codegen_fn_call(
             builder,
             fn_abi,
-            fn_ptr,
+            callee,
             &args,
             &copied_const_args,
             target.as_ref().map(|&target| (target, return_destination)),
@@ -316,7 +370,9 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         // indirectly, we have to force to be passed by reference.
         let (mut value, alignment, by_ref) = match arg.value {
             OperandValue::Immediate(_) | OperandValue::Pair(_, _) => match arg_abi.mode {
-                PassMode::Indirect { .. } => {
+                // Cast arguments are loaded from memory as the cast type, so
+                // the value is spilled into a temporary.
+                PassMode::Indirect { .. } | PassMode::Cast(_) => {
                     let temp = PlaceRef::new_stack(builder, arg_abi.info);
                     arg.value.store(builder, temp);
 
@@ -362,8 +418,12 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         };
 
         if by_ref && !arg_abi.is_indirect() {
-            // @@CastPassMode: here we might have to deal with a casting pass mode
-            // which means that we load the operand and then cast it
+            // If the argument is cast, then the memory of the operand is
+            // re-interpreted as the cast type.
+            if let PassMode::Cast(target) = arg_abi.mode {
+                let ty = builder.backend_ty_from_cast(&target);
+                value = builder.load(ty, value, alignment);
+            }
 
             // If it is direct, Here, we know that this value must be a boolean. In
             // the case that it is a boolean, we add additional metadata to the scalar
@@ -479,6 +539,23 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                     op.immediate_or_scalar_pair(builder)
                 }
             }
+            PassMode::Cast(target) => {
+                let op = self.codegen_consume_operand(builder, ir::Place::return_place());
+
+                // The return value is loaded from memory as the cast type, so
+                // an immediate value has to be spilled first.
+                let (value, alignment) = match op.value {
+                    OperandValue::Ref(value, alignment) => (value, alignment),
+                    _ => {
+                        let temp = PlaceRef::new_stack(builder, op.info);
+                        op.value.store(builder, temp);
+                        (temp.value, temp.alignment)
+                    }
+                };
+
+                let ty = builder.backend_ty_from_cast(target);
+                builder.load(ty, value, alignment)
+            }
         };
 
         builder.return_value(value);
@@ -551,7 +628,7 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         } else if targets_iter.len() == 2
             && self.body.blocks()[targets.otherwise()].is_empty_and_unreachable()
             && self.ctx.settings().optimisation_level == OptimisationLevel::Debug
-            && self.ctx.settings().codegen_settings().backend.is_llvm()
+            && self.ctx.settings().codegen_settings().backend().is_llvm()
         {
             let (value, target_1) = targets_iter.next().unwrap();
             let (_, target_2) = targets_iter.next().unwrap();
@@ -617,8 +694,18 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         // It must be that after this point, the block goes to the `failure_block`
         builder.switch_to_block(failure_block);
 
-        // we need to convert the assert into a message.
-        let (bytes, len) = builder.const_str(AllocId::str(assert_kind.message().into()));
+        // we need to convert the assert into a message. If the `checks` sanitiser
+        // is enabled, the values of the operands are reported first, and the
+        // panic message only terminates the report.
+        let message = if self.ctx.settings().sanitize.keeps_checks()
+            && self.codegen_assert_report(builder, assert_kind)
+        {
+            "\n"
+        } else {
+            assert_kind.message()
+        };
+
+        let (bytes, len) = builder.const_str(AllocId::str(message.into()));
         let args: [Builder::Value; 2] = (bytes, len).into();
 
         // Get the `panic` lang item.
@@ -626,7 +713,123 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         let abi = self.ctx.cg_ctx().abis().create_fn_abi(builder, instance);
 
         // Finally we emit this as a call to panic...
-        self.codegen_fn_call(builder, abi, fn_ptr, &args, &[], None, false)
+        self.codegen_fn_call(builder, abi, FnCallee::Direct(fn_ptr), &args, &[], None, false)
+    }
+
+    /// Emit a detailed report of a failed assertion, this prints the
+    /// message of the assertion along with the values of the operands
+    /// that caused the assertion to fail, for example:
+    /// ```text
+    /// attempt to add with overflow: 127 + 1
+    /// ```
+    ///
+    /// This returns whether the report was emitted, the report can't be
+    /// emitted if the printing lang items aren't available, or if any of
+    /// the operands can't be printed.
+    fn codegen_assert_report(
+        &mut self,
+        builder: &mut Builder,
+        assert_kind: &ir::AssertKind,
+    ) -> bool {
+        use ir::AssertKind::*;
+
+        let operands = match assert_kind {
+            Overflow { lhs, rhs, .. } | ShiftAmountTooLarge { lhs, amount: rhs, .. } => {
+                vec![lhs, rhs]
+            }
+            NegativeExponent { base, exponent } => vec![base, exponent],
+            DivisionByZero { operand }
+            | RemainderByZero { operand }
+            | NegativeOverflow { operand }
+            | InvalidDiscriminant { discriminant: operand } => vec![operand],
+            BoundsCheck { len, index } => vec![len, index],
+        };
+
+        let has_printers = [LangItem::Print, LangItem::DebugInt, LangItem::DebugUInt]
+            .into_iter()
+            .all(|item| self.ctx.ir_ctx().lang_items().get(item).is_some());
+
+        let info = self.body.aux();
+        let can_print = operands.iter().all(|operand| match *operand.ty(&info).borrow() {
+            ReprTy::Int(int_ty) => int_ty != SIntTy::I128,
+            ReprTy::UInt(int_ty) => int_ty != UIntTy::U128,
+            _ => false,
+        });
+
+        if !has_printers || !can_print {
+            return false;
+        }
+
+        let message = assert_kind.message().trim_end();
+        self.codegen_print_str(builder, &format!("{message}: "));
+
+        match assert_kind {
+            Overflow { op, lhs, rhs } | ShiftAmountTooLarge { op, lhs, amount: rhs } => {
+                self.codegen_print_int(builder, lhs);
+                self.codegen_print_str(builder, &format!(" {op} "));
+                self.codegen_print_int(builder, rhs);
+            }
+            NegativeExponent { base, exponent } => {
+                self.codegen_print_int(builder, base);
+                self.codegen_print_str(builder, " ** ");
+                self.codegen_print_int(builder, exponent);
+            }
+            DivisionByZero { operand } => {
+                self.codegen_print_int(builder, operand);
+                self.codegen_print_str(builder, " / 0");
+            }
+            RemainderByZero { operand } => {
+                self.codegen_print_int(builder, operand);
+                self.codegen_print_str(builder, " % 0");
+            }
+            NegativeOverflow { operand } => {
+                self.codegen_print_str(builder, "-(");
+                self.codegen_print_int(builder, operand);
+                self.codegen_print_str(builder, ")");
+            }
+            BoundsCheck { len, index } => {
+                self.codegen_print_str(builder, "the length is ");
+                self.codegen_print_int(builder, len);
+                self.codegen_print_str(builder, " but the index is ");
+                self.codegen_print_int(builder, index);
+            }
+            InvalidDiscriminant { discriminant } => {
+                self.codegen_print_int(builder, discriminant);
+            }
+        }
+
+        true
+    }
+
+    /// Emit a call to the `print` lang item with the given string.
+    fn codegen_print_str(&mut self, builder: &mut Builder, value: &str) {
+        let (bytes, len) = builder.const_str(AllocId::str(value.into()));
+        self.codegen_lang_item_call(builder, LangItem::Print, &[bytes, len]);
+    }
+
+    /// Emit a call to the `debug_int` or `debug_uint` lang item with the
+    /// given integral operand. The operand is widened to 64 bits before
+    /// it is printed.
+    fn codegen_print_int(&mut self, builder: &mut Builder, operand: &ir::Operand) {
+        let operand = self.codegen_operand(builder, operand);
+        let signed = operand.info.ty.borrow().is_signed();
+
+        let ty = builder.type_i64();
+        let value = builder.int_cast(operand.immediate_value(), ty, signed);
+        let item = if signed { LangItem::DebugInt } else { LangItem::DebugUInt };
+        self.codegen_lang_item_call(builder, item, &[value]);
+    }
+
+    /// Emit a call to the given lang item, ignoring the return value.
+    fn codegen_lang_item_call(
+        &mut self,
+        builder: &mut Builder,
+        item: LangItem,
+        args: &[Builder::Value],
+    ) {
+        let (instance, fn_ptr) = self.resolve_lang_item(builder, item);
+        let abi = self.ctx.cg_ctx().abis().create_fn_abi(builder, instance);
+        builder.call(fn_ptr, args, Some(abi));
     }
 
     /// Function that prepares a function call to be generated, and the emits
@@ -638,7 +841,7 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         &mut self,
         builder: &mut Builder,
         fn_abi: FnAbiId,
-        fn_ptr: Builder::Function,
+        callee: FnCallee<Builder::Function, Builder::Value>,
         args: &[Builder::Value],
         copied_const_args: &[PlaceRef<Builder::Value>],
         destination: Option<(ir::BasicBlock, ReturnDestinationKind<Builder::Value>)>,
@@ -648,7 +851,10 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
         // `builder::invoke()` API in order to instruct the backends to emit relevant
         // clean-up code for when the function starts to unwind (i.e. panic).
         // However for now, we simply emit a `builder::call()`
-        let return_value = builder.call(fn_ptr, args, Some(fn_abi));
+        let return_value = match callee {
+            FnCallee::Direct(fn_ptr) => builder.call(fn_ptr, args, Some(fn_abi)),
+            FnCallee::Indirect(fn_ptr) => builder.call_ptr(fn_ptr, args, fn_abi),
+        };
 
         if let Some((destination_block, return_destination)) = destination {
             // now that the function has finished, we essentially mark all of the
@@ -688,15 +894,20 @@ impl<'a, 'b, Builder: BlockBuilderMethods<'a, 'b>> FnBuilder<'a, 'b, Builder> {
                 builder.store_fn_call_arg(return_abi, value, destination)
             }
             ReturnDestinationKind::DirectOperand(local) => {
-                // @@CastPassMode: if it is a casting pass mode, then it needs to be stored
-                // as an alloca, then stored by `store_arg`m and then loaded, i.e. reloaded
-                // of the stack.
+                // If the return value is cast, then it needs to be stored onto
+                // the stack, and then re-loaded as the actual type.
+                let op = if let PassMode::Cast(_) = return_abi.mode {
+                    let temp = PlaceRef::new_stack(builder, return_abi.info);
+                    temp.storage_live(builder);
+                    builder.store_fn_call_arg(return_abi, value, temp);
+
+                    let op = builder.load_operand(temp);
+                    temp.storage_dead(builder);
+                    op
+                } else {
+                    OperandRef::from_immediate_value_or_scalar_pair(builder, value, return_abi.info)
+                };
 
-                let op = OperandRef::from_immediate_value_or_scalar_pair(
-                    builder,
-                    value,
-                    return_abi.info,
-                );
                 self.locals[local] = LocalRef::Operand(Some(op));
             }
             ReturnDestinationKind::IndirectOperand(temp, local) => {
@@ -4,12 +4,17 @@
 use std::cell::RefCell;
 
 use hash_abi::{ArgAbi, FnAbi, FnAbiId};
-use hash_ir::ty::InstanceId;
+use hash_ir::ty::{InstanceId, ReprTyId};
 use hash_storage::store::{DefaultStore, Store, StoreInternalData};
 use hash_utils::fxhash::FxHashMap;
 
 use super::{layout::LayoutMethods, BackendTypes, HasCtxMethods};
-use crate::lower::{abi::compute_fn_abi_from_instance, place::PlaceRef};
+use crate::lower::{
+    abi::{
+        compute_fn_abi_from_instance, compute_fn_abi_from_ty, compute_variadic_fn_abi_from_instance,
+    },
+    place::PlaceRef,
+};
 
 /// This trait defines functionality to construct the ABI of functions,
 /// arguments, etc.
@@ -48,6 +53,10 @@ pub struct FnAbiStore {
     /// A mapping from [InstanceId] to [FnAbiId]. This is used to re-use results
     /// of computing ABIs from instances.
     instance_abi_map: RefCell<FxHashMap<InstanceId, FnAbiId>>,
+
+    /// A mapping from function pointer types to [FnAbiId]. This is used to
+    /// re-use results of computing ABIs for indirect function calls.
+    fn_ptr_abi_map: RefCell<FxHashMap<ReprTyId, FnAbiId>>,
 }
 
 impl Store<FnAbiId, FnAbi> for FnAbiStore {
@@ -88,6 +97,39 @@ impl FnAbiStore {
         abi
     }
 
+    /// Create a [FnAbi] for a call to a C-variadic [InstanceId] that passes
+    /// arguments of the given types in the variadic part of the call. The
+    /// ABI is specific to the call site, and so it is not cached.
+    pub fn create_variadic_fn_abi<'b, Ctx>(
+        &self,
+        ctx: &Ctx,
+        instance: InstanceId,
+        variadic_args: &[ReprTyId],
+    ) -> FnAbiId
+    where
+        Ctx: HasCtxMethods<'b> + LayoutMethods<'b>,
+    {
+        // @@Todo: Emit a fatal error if the function ABI cannot be computed.
+        self.store
+            .create(compute_variadic_fn_abi_from_instance(ctx, instance, variadic_args).unwrap())
+    }
+
+    /// Create (or re-use) a [FnAbi] of a function pointer type. This function
+    /// returns the [FnAbiId] of the [FnAbi] that was created.
+    pub fn create_fn_ptr_abi<'b, Ctx>(&self, ctx: &Ctx, ty: ReprTyId) -> FnAbiId
+    where
+        Ctx: HasCtxMethods<'b> + LayoutMethods<'b>,
+    {
+        if let Some(abi) = self.fn_ptr_abi_map.borrow().get(&ty).copied() {
+            return abi;
+        }
+
+        // @@Todo: Emit a fatal error if the function ABI cannot be computed.
+        let abi = self.store.create(compute_fn_abi_from_ty(ctx, ty).unwrap());
+        self.fn_ptr_abi_map.borrow_mut().insert(ty, abi);
+        abi
+    }
+
     /// Get the ABI of the [InstanceId] assuming that it has already
     /// been created.
     fn try_get_fn_abi(&self, instance: InstanceId) -> Option<FnAbiId> {
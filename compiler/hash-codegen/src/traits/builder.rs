@@ -99,6 +99,19 @@ pub trait BlockBuilderMethods<'a, 'b>:
         fn_abi: Option<FnAbiId>,
     ) -> Self::Value;
 
+    /// Emit code for performing an indirect function call through a function
+    /// pointer value. The function ABI is used to compute the type of the
+    /// function that is being called.
+    ///
+    /// The function returns the corresponding "return" value of the
+    /// function.
+    fn call_ptr(
+        &mut self,
+        fn_ptr: Self::Value,
+        args: &[Self::Value],
+        fn_abi: FnAbiId,
+    ) -> Self::Value;
+
     // --- Arithmetic ---
 
     /// Perform an addition operation on the given values.
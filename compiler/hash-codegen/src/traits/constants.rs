@@ -2,7 +2,7 @@
 //! backend builder to emit constants of all primitive types when converting
 //! Hash IR into the target backend.
 
-use hash_ir::ir;
+use hash_ir::{ir, ty::InstanceId};
 use hash_source::constant::AllocId;
 use hash_target::{abi::Scalar, size::Size};
 
@@ -86,6 +86,10 @@ pub trait ConstValueBuilderMethods<'b>: BackendTypes {
     /// Emit a constant value from a [`ir::Scalar`] value.
     fn const_scalar_value(&self, scalar: ir::Scalar, abi: Scalar, ty: Self::Type) -> Self::Value;
 
+    /// Emit a constant function pointer to the function that is referred to
+    /// by the [InstanceId].
+    fn const_fn_addr(&self, instance: InstanceId) -> Self::Value;
+
     /// Convert an allocated constant value into a [`Self::Value`].
     fn const_data_from_alloc(&self, alloc: ir::AllocId) -> Self::Value;
 
@@ -32,4 +32,10 @@ pub trait IntrinsicBuilderMethods<'b>: BackendTypes {
     ///
     /// Ref: <https://llvm.org/docs/LangRef.html#llvm-expect-intrinsic>
     fn codegen_expect_intrinsic(&mut self, value: Self::Value, expected: bool) -> Self::Value;
+
+    /// Generate a call to the `assume` intrinsic function. This informs
+    /// the backend that the given boolean `value` is always `true`.
+    ///
+    /// Ref: <https://llvm.org/docs/LangRef.html#llvm-assume-intrinsic>
+    fn codegen_assume_intrinsic(&mut self, value: Self::Value);
 }
@@ -26,4 +26,10 @@ pub trait LayoutMethods<'b>: BackendTypes + HasCtxMethods<'b> {
     /// Check whether the [TyInfo] layout can be represented as a
     /// backend scalar pair.
     fn is_backend_scalar_pair(&self, ty: TyInfo) -> bool;
+
+    /// Check whether the backend can pass values with a vector ABI directly
+    /// in vector registers. If it can't, then vectors are passed indirectly.
+    fn supports_vector_registers(&self) -> bool {
+        false
+    }
 }
@@ -1,6 +1,6 @@
 //! Trait methods to do with emitting types for the backend.
 
-use hash_abi::FnAbi;
+use hash_abi::{CastTarget, FnAbi};
 use hash_ir::ty::{ReprTy, ReprTyId};
 use hash_repr::TyInfo;
 use hash_source::constant::FloatTy;
@@ -77,6 +77,10 @@ pub trait TypeBuilderMethods<'b>: BackendTypes + LayoutMethods<'b> {
     /// Create a function type.
     fn type_function(&self, args: &[Self::Type], ret: Self::Type) -> Self::Type;
 
+    /// Create a function type that accepts a variable number of arguments
+    /// after the given arguments.
+    fn type_variadic_function(&self, args: &[Self::Type], ret: Self::Type) -> Self::Type;
+
     /// Create a struct type.
     fn type_struct(&self, els: &[Self::Type], packed: bool) -> Self::Type;
 
@@ -124,6 +128,11 @@ pub trait TypeBuilderMethods<'b>: BackendTypes + LayoutMethods<'b> {
     /// is used to compute a function type from a [FnAbi].
     fn backend_ty_from_abi(&self, abi: &FnAbi) -> Self::Type;
 
+    /// Create a backend type that represents the registers of the provided
+    /// [CastTarget]. This is the type of an argument that is passed with
+    /// [`hash_abi::PassMode::Cast`].
+    fn backend_ty_from_cast(&self, target: &CastTarget) -> Self::Type;
+
     /// Check whether a given type has additional hidden metadata like the
     /// size of a slice or a string.
     fn ty_has_hidden_metadata(&self, ty: ReprTyId) -> bool {
@@ -41,8 +41,8 @@ impl<'ctx> ConstFolder<'ctx> {
                 self.binary_int_op(op, l_ty, l_bits, r_ty, r_bits)
             }
             ReprTy::Float(fl_ty) => match fl_ty {
-                FloatTy::F32 => Self::binary_float_op(op, left.to_f32(), left.to_f32()),
-                FloatTy::F64 => Self::binary_float_op(op, left.to_f64(), left.to_f64()),
+                FloatTy::F32 => Self::binary_float_op(op, left.to_f32(), right.to_f32()),
+                FloatTy::F64 => Self::binary_float_op(op, left.to_f64(), right.to_f64()),
             },
             ReprTy::Bool => {
                 let l: bool = left.try_into().ok()?;
@@ -195,6 +195,11 @@ impl<'ctx> ConstFolder<'ctx> {
                 Add => Some(i128::overflowing_add),
                 Sub => Some(i128::overflowing_sub),
                 Mul => Some(i128::overflowing_mul),
+                // Negative exponents are left to be checked at runtime.
+                Exp => Some(|lhs, rhs| match u32::try_from(rhs) {
+                    Ok(rhs) => lhs.overflowing_pow(rhs),
+                    Err(_) => (0, true),
+                }),
                 // @@ErrorHandling @@UB: we should somehow emit an error saying that a
                 // constant operation is divide by zero, and this is UB at runtime!!!
                 Div if rhs == 0 => return None,
@@ -245,11 +250,15 @@ impl<'ctx> ConstFolder<'ctx> {
             BitOr => Some(Const::from_scalar_like(lhs | rhs, lhs_ty, dl)),
             BitAnd => Some(Const::from_scalar_like(lhs & rhs, lhs_ty, dl)),
             BitXor => Some(Const::from_scalar_like(lhs ^ rhs, lhs_ty, dl)),
-            Add | Sub | Mul | Div | Mod => {
+            Add | Sub | Mul | Div | Mod | Exp => {
                 let op: fn(u128, u128) -> (u128, bool) = match bin_op {
                     Add => u128::overflowing_add,
                     Sub => u128::overflowing_sub,
                     Mul => u128::overflowing_mul,
+                    Exp => |lhs, rhs| match u32::try_from(rhs) {
+                        Ok(rhs) => lhs.overflowing_pow(rhs),
+                        Err(_) => (0, true),
+                    },
                     // @@ErrorHandling @@UB: we should somehow emit an error saying that a
                     // constant operation is divide by zero, and this is UB at runtime!!!
                     Div if rhs == 0 => return None,
@@ -272,7 +281,6 @@ impl<'ctx> ConstFolder<'ctx> {
 
                 Some(Const::new(lhs_ty, ConstKind::Scalar(Scalar::from_uint(truncated, size))))
             }
-            Exp => None,
             _ => panic!("invalid operator, `{bin_op}` should have been handled"),
         }
     }
@@ -69,6 +69,9 @@ pub enum BinOp {
     Div,
     /// '%'
     Mod,
+    /// Pointer arithmetic, offset the pointer on the left-hand side by
+    /// the number of elements specified on the right-hand side.
+    Offset,
 }
 
 impl BinOp {
@@ -105,7 +108,7 @@ impl BinOp {
             }
 
             // Always the `lhs`, but `lhs` and `rhs` can be different types.
-            BinOp::Shr | BinOp::Shl => lhs,
+            BinOp::Shr | BinOp::Shl | BinOp::Offset => lhs,
 
             // Comparisons
             BinOp::Eq | BinOp::Neq | BinOp::Gt | BinOp::GtEq | BinOp::Lt | BinOp::LtEq => {
@@ -135,6 +138,7 @@ impl fmt::Display for BinOp {
             BinOp::Mul => write!(f, "*"),
             BinOp::Div => write!(f, "/"),
             BinOp::Mod => write!(f, "%"),
+            BinOp::Offset => write!(f, "offset"),
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::{
+    cmp::Reverse,
     io::{self, Write},
     iter,
 };
@@ -9,7 +10,7 @@ use hash_repr::{
     ty::{AdtFlags, ReprTy, VariantIdx, COMMON_REPR_TYS},
 };
 use hash_source::{
-    constant::{Scalar, ScalarInt},
+    constant::{AllocRange, Scalar, ScalarInt},
     FloatTy, IntTy, Size,
 };
 use hash_storage::store::statics::StoreId;
@@ -17,11 +18,36 @@ use hash_target::data_layout::HasDataLayout;
 
 use crate::utils::ConstUtils;
 
+/// Options that control how [Const]s are printed.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstPrintOptions {
+    /// The maximum number of elements of an array constant that are
+    /// printed, any remaining elements are elided with `..`.
+    pub array_element_limit: usize,
+}
+
+impl Default for ConstPrintOptions {
+    fn default() -> Self {
+        Self { array_element_limit: 16 }
+    }
+}
+
+/// Pretty print a [Const] using the default [ConstPrintOptions], this
 /// is used when printing the generated IR.
 pub fn pretty_print_const(
     f: &mut impl Write,
     constant: &Const,
     lc: LayoutComputer<'_>,
+) -> io::Result<()> {
+    pretty_print_const_with_options(f, constant, lc, ConstPrintOptions::default())
+}
+
+/// Pretty print a [Const] with the specified [ConstPrintOptions].
+pub fn pretty_print_const_with_options(
+    f: &mut impl Write,
+    constant: &Const,
+    lc: LayoutComputer<'_>,
+    options: ConstPrintOptions,
 ) -> io::Result<()> {
     match (constant.kind(), constant.ty().value()) {
         (ConstKind::Pair { data, .. }, ReprTy::Ref(inner, _, _)) => match inner.value() {
@@ -29,11 +55,37 @@ pub fn pretty_print_const(
             _ => write!(f, "{:?}", data.value()),
         },
 
+        // Big integers are printed before the other scalars since they are
+        // represented as ADTs.
+        (ConstKind::Scalar(_) | ConstKind::Alloc { .. }, _) if constant.ty().is_big_int() => {
+            write!(f, "{}_{}", constant.as_big_int(), constant.ty())
+        }
         (ConstKind::Scalar(scalar), ty) => {
             pretty_print_scalar(f, scalar, &ty, lc.data_layout().pointer_size, false)
         }
         (ConstKind::Alloc { .. }, ReprTy::Array { .. }) => {
-            write!(f, "[{}]", 2)
+            let utils = ConstUtils::new(lc, constant);
+            let Some(destructured) = utils.destructure_const() else { return Ok(()) };
+
+            write!(f, "[")?;
+            for (index, element) in destructured.fields.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+
+                // Elide the remaining elements if we've reached the limit.
+                if index == options.array_element_limit {
+                    write!(f, "..")?;
+                    break;
+                }
+
+                pretty_print_const_with_options(f, element, lc, options)?;
+            }
+
+            write!(f, "]")
+        }
+        (ConstKind::FnAddr(instance), _) => {
+            write!(f, "fn_addr({})", instance.borrow().name())
         }
         // We put a `zero` for fndefs.
         (ConstKind::Zero, ReprTy::FnDef { .. }) => {
@@ -71,7 +123,7 @@ pub fn pretty_print_const(
                             }
 
                             write!(f, "{}: ", field.name)?;
-                            pretty_print_const(f, &constant, lc)?;
+                            pretty_print_const_with_options(f, &constant, lc, options)?;
 
                             first = false;
                         }
@@ -92,7 +144,7 @@ pub fn pretty_print_const(
                             }
 
                             write!(f, "{}: ", field.name)?;
-                            pretty_print_const(f, &constant, lc)?;
+                            pretty_print_const_with_options(f, &constant, lc, options)?;
 
                             first = false;
                         }
@@ -100,7 +152,42 @@ pub fn pretty_print_const(
                         write!(f, ")")
                     }
                     AdtFlags::UNION => {
-                        unimplemented!("union representations aren't implemented yet")
+                        write!(f, "{} {{", def.borrow().name)?;
+
+                        // Since a union doesn't record which field is active, we choose
+                        // the field that covers the most bytes of the union, preferring
+                        // the first field if multiple fields are of the same size. If the
+                        // size of any of the fields can't be computed, we fall back to
+                        // printing the raw bytes of the union.
+                        let sizes = destructured
+                            .fields
+                            .iter()
+                            .map(|field| lc.size_of_ty(field.ty()).ok())
+                            .collect::<Option<Vec<_>>>();
+
+                        match sizes {
+                            Some(sizes) => {
+                                let field = iter::zip(&sizes, &destructured.fields)
+                                    .enumerate()
+                                    .min_by_key(|(_, (size, _))| Reverse(**size));
+
+                                if let Some((index, (_, constant))) = field {
+                                    let name =
+                                        def.borrow().variant(VariantIdx::new(0)).fields[index].name;
+
+                                    write!(f, " {name}: ")?;
+                                    pretty_print_const_with_options(f, constant, lc, options)?;
+                                    write!(f, " ")?;
+                                }
+                            }
+                            None => {
+                                write!(f, " ")?;
+                                pretty_print_raw_bytes(f, constant, lc)?;
+                                write!(f, " ")?;
+                            }
+                        }
+
+                        write!(f, "}}")
                     }
                     _ => unreachable!(),
                 }
@@ -114,6 +201,34 @@ pub fn pretty_print_const(
     }
 }
 
+/// Print the bytes that back a [Const] allocation, this is used when the
+/// constant can't be printed in terms of its type. If the size of the type
+/// of the constant can't be computed, all of the remaining bytes of the
+/// allocation are printed.
+fn pretty_print_raw_bytes(
+    f: &mut impl Write,
+    constant: &Const,
+    lc: LayoutComputer<'_>,
+) -> io::Result<()> {
+    let ConstKind::Alloc { offset, alloc } = constant.kind() else {
+        return write!(f, "..");
+    };
+
+    let alloc = alloc.borrow();
+    let size = lc.size_of_ty(constant.ty()).unwrap_or_else(|_| alloc.size() - offset);
+    let bytes = alloc.read_bytes(AllocRange::new(offset, size));
+
+    write!(f, "[")?;
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+
+        write!(f, "{byte:#04x}")?;
+    }
+    write!(f, "]")
+}
+
 /// Pretty printing a [Scalar] value.
 pub fn pretty_print_scalar(
     f: &mut impl Write,
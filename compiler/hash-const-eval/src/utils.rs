@@ -85,10 +85,9 @@ impl ConstUtils<'_> {
     }
 
     /// Read the discriminant of the given [Const]. If the [Const] is not an
-    /// allocation then this will return `None`. This will return the
-    /// [VariantIdx] and the relative offset of the data that is stored in
-    /// the allocation.
-    fn read_discriminant(&self) -> Option<(Size, VariantIdx)> {
+    /// allocation then this will return `None`, otherwise the [VariantIdx]
+    /// of the value that is stored in the allocation.
+    fn read_discriminant(&self) -> Option<VariantIdx> {
         let info = self.ty_info();
         let ConstKind::Alloc { offset, alloc } = self.kind() else { return None };
 
@@ -97,7 +96,7 @@ impl ConstUtils<'_> {
         let (tag, field) = match info.layout.borrow().variants {
             // @@FixMe: `Single` is still used to represent the layout for an `enum`
             // which has no variatns. Perhaps this should return `0` on it?
-            Variants::Single { index } => return Some((offset, index)),
+            Variants::Single { index } => return Some(index),
             Variants::Multiple { tag, field, .. } => (tag, field),
         };
 
@@ -120,7 +119,7 @@ impl ConstUtils<'_> {
             _ => unreachable!(),
         };
 
-        Some((offset, variant))
+        Some(variant)
     }
 
     /// Destructure the [Const] into the given children fields. This is useful
@@ -131,15 +130,14 @@ impl ConstUtils<'_> {
     /// then destructure that field.
     pub fn destructure_const(&self) -> Option<DestructuredConst> {
         let info @ TyInfo { ty, layout } = self.ty_info();
-        let value @ ConstKind::Alloc { mut offset, .. } = self.kind() else { return None };
+        let value @ ConstKind::Alloc { offset, .. } = self.kind() else { return None };
 
         let (variant, field_count, downcasted_layout) = match ty.value() {
             ReprTy::Array { length, .. } => (None, length, layout),
             ReprTy::Adt(def) if def.borrow().variants.is_empty() => return None,
             ReprTy::Adt(def) => {
-                let (offset_with_tag, variant) = self.read_discriminant()?;
+                let variant = self.read_discriminant()?;
                 let variant_layout = info.for_variant(self.lc, variant);
-                offset = offset_with_tag;
 
                 (Some(variant), def.borrow().variant(variant).fields.len(), variant_layout.layout)
             }
@@ -159,13 +157,27 @@ impl ConstUtils<'_> {
             .map(|i| {
                 // We essentially have to make a new constant based on the type.
                 let field = downcasted_info.field(self.lc, i);
-                self.field_from_alloc(offset, field)
+                self.field_from_alloc(offset + downcasted_layout.offset_of(i), field)
             })
             .collect_vec();
 
         Some(DestructuredConst { variant, fields })
     }
 
+    /// Project the field at the given index out of the [Const], this works
+    /// for both ADTs (using the active variant) and arrays. If the [Const]
+    /// cannot be destructured, or the field does not exist, then this will
+    /// return `None`.
+    pub fn project_field(&self, index: usize) -> Option<Const> {
+        let DestructuredConst { mut fields, .. } = self.destructure_const()?;
+
+        if index < fields.len() {
+            Some(fields.swap_remove(index))
+        } else {
+            None
+        }
+    }
+
     /// Evaluate the value of the [Scalar] constant into a `u128` value.
     pub fn eval_bits(&self) -> u128 {
         let size = self.lc.size_of_ty(self.ty()).unwrap();
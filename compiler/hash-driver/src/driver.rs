@@ -1,28 +1,33 @@
 use std::{
     env::{self, current_dir},
     fs::File,
-    io::Write,
     ops::{Deref, DerefMut},
     path::Path,
     process::{self, Command, Stdio},
     thread,
+    time::Instant,
 };
 
+use hash_messaging::{CompilerMessagingFormat, CompilerOutputMessage};
 use hash_pipeline::{
+    error::PipelineError,
     fs::{resolve_path, PRELUDE},
     interface::{CompilerInterface, CompilerOutputStream, CompilerResult, CompilerStage},
-    settings::CompilerStageKind,
+    settings::{CompilerStageKind, LintLevel},
+};
+use hash_reporting::{
+    hash_error_codes::DiagnosticCode,
+    report::{Report, ReportKind},
+    reporter::{deduplicate_reports, limit_errors, Reporter},
 };
-use hash_reporting::reporter::Reporter;
 use hash_source::{ModuleKind, SourceId};
 use hash_utils::{
     indexmap::IndexMap,
     log,
     profiling::{get_resident_set_size, timed, MetricEntry, StageMetrics},
-    stream_writeln,
 };
 
-use crate::metrics::{MetricReporter, Metrics, StageMetricEntry};
+use crate::metrics::{write_chrome_trace, MetricReporter, Metrics, StageMetricEntry, StageSpan};
 
 /// The Hash Compiler interface. This interface allows a caller to create a
 /// [Driver] with a `compiler` and a collection of stages which will access
@@ -43,6 +48,13 @@ pub struct Driver<I: CompilerInterface> {
     /// A record of all of the stage metrics
     metrics: IndexMap<CompilerStageKind, StageMetricEntry>,
 
+    /// When the [Driver] was created, the [StageSpan]s are relative to this.
+    epoch: Instant,
+
+    /// Each run of a stage, which is recorded if `--timings-trace` was
+    /// specified.
+    spans: Vec<StageSpan>,
+
     /// Whether the pipeline is currently bootstrapping, i.e. when
     /// it is running the prelude module in order to place everything
     /// that is required for the core of the language to work.
@@ -77,7 +89,14 @@ impl<I: CompilerInterface> Driver<I> {
         // stage.
         assert!(stages.windows(2).all(|w| w[0].kind() <= w[1].kind()));
 
-        Self { compiler, stages, metrics: Metrics::new(), bootstrapping: false }
+        Self {
+            compiler,
+            stages,
+            metrics: Metrics::new(),
+            epoch: Instant::now(),
+            spans: vec![],
+            bootstrapping: false,
+        }
     }
 
     /// Function to report the collected metrics on the stages within the
@@ -93,8 +112,9 @@ impl<I: CompilerInterface> Driver<I> {
         let stage = &mut self.stages[index];
         let stage_kind = stage.kind();
         let start_rss = get_resident_set_size();
+        let start = self.epoch.elapsed();
 
-        timed(
+        let result = timed(
             || stage.run(entry_point, &mut self.compiler),
             log::Level::Info,
             |time| {
@@ -113,7 +133,18 @@ impl<I: CompilerInterface> Driver<I> {
                         children: StageMetrics::default(),
                     });
             },
-        )?;
+        );
+
+        if self.compiler.settings().timings_trace.is_some() && !self.bootstrapping {
+            self.spans.push(StageSpan {
+                kind: stage_kind,
+                start,
+                duration: self.epoch.elapsed() - start,
+                end_rss: get_resident_set_size(),
+            });
+        }
+
+        result?;
 
         self.metrics.entry(stage_kind).and_modify(|entry| entry.children.merge(&stage.metrics()));
 
@@ -127,10 +158,36 @@ impl<I: CompilerInterface> Driver<I> {
         Ok(())
     }
 
+    /// Apply the [LintLevel] that the settings give to a warning, an allowed
+    /// warning is removed and a denied warning becomes an error.
+    fn apply_lint_level(&self, mut report: Report) -> Option<Report> {
+        if !report.is_warning() {
+            return Some(report);
+        }
+
+        let code = match report.code {
+            Some(DiagnosticCode::Warning(code)) => Some(code),
+            _ => None,
+        };
+
+        match self.compiler.settings().lint_level(code) {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(report),
+            LintLevel::Deny => {
+                report.kind(ReportKind::Error).add_note("this warning is denied with `-D`");
+                Some(report)
+            }
+        }
+    }
+
     /// Helper function in order to check if the pipeline needs to terminate
     /// after any stage that is specified within the settings of the compiler.
     fn maybe_terminate(&mut self, result: CompilerResult<()>) -> Result<(), ()> {
         if let Err(diagnostics) = result {
+            let diagnostics: Vec<_> = diagnostics
+                .into_iter()
+                .filter_map(|report| self.apply_lint_level(report))
+                .collect();
             self.compiler.diagnostics_mut().extend(diagnostics);
 
             // Some diagnostics might not be errors and all just warnings, in this
@@ -156,8 +213,16 @@ impl<I: CompilerInterface> Driver<I> {
                 return Err(());
             }
 
+            // Don't start any further stages if the compilation has been
+            // cancelled in the meantime.
+            let result = self.compiler.workspace().cancellation.check();
+            self.maybe_terminate(result)?;
+
             let result = self.run_stage(entry_point, stage);
             self.maybe_terminate(result)?;
+
+            let result = self.compiler.settings().memory_limit().check(kind, None);
+            self.maybe_terminate(result)?;
         }
 
         Ok(())
@@ -174,6 +239,10 @@ impl<I: CompilerInterface> Driver<I> {
             let stage_kind = self.compiler.settings_mut().stage;
             self.compiler.settings_mut().stage = CompilerStageKind::Analysis;
 
+            // The prelude is always compiled, so the memory limit only applies
+            // to the actual compilation.
+            let max_memory = self.compiler.settings_mut().max_memory.take();
+
             // We don't need to run the prelude in the full pipeline, just until
             // IR-gen since that will be dealt by the actual pipeline.
 
@@ -187,7 +256,7 @@ impl<I: CompilerInterface> Driver<I> {
                 }
             };
 
-            let path = match resolve_path(PRELUDE, wd) {
+            let path = match resolve_path(self.workspace().file_system.as_ref(), PRELUDE, wd) {
                 Ok(path) => path,
                 Err(err) => {
                     self.compiler.diagnostics_mut().push(err.into());
@@ -199,10 +268,13 @@ impl<I: CompilerInterface> Driver<I> {
 
             // Reset the settings
             self.compiler.settings_mut().stage = stage_kind;
+            self.compiler.settings_mut().max_memory = max_memory;
 
             // The prelude shouldn't generate any errors, otherwise we just failed to
-            // bootstrap
-            if self.compiler.diagnostics().iter().any(|r| r.is_error()) {
+            // bootstrap. A cancelled bootstrap isn't a failure of the prelude.
+            if !self.workspace().cancellation.is_cancelled()
+                && self.compiler.diagnostics().iter().any(|r| r.is_error())
+            {
                 panic!(
                     "failed to bootstrap compiler:\n{}",
                     Reporter::from_reports(self.compiler.diagnostics().to_owned())
@@ -213,14 +285,29 @@ impl<I: CompilerInterface> Driver<I> {
         }
     }
 
-    /// Emit diagnostics to the error stream with the applied settings.
+    /// Emit diagnostics to the error stream with the applied settings. If the
+    /// diagnostics are written as JSON messages, they are written to the
+    /// output stream instead.
+    ///
+    /// Identical diagnostics are only emitted once, and if an `--error-limit`
+    /// is specified, the errors beyond the limit are omitted.
     pub fn emit_diagnostics(&self) {
         let mut err_count = 0;
         let mut warn_count = 0;
-        let mut stderr = self.compiler.error_stream();
+        let format = self.compiler.settings().message_format;
+        let mut stream = match format {
+            CompilerMessagingFormat::Normal => self.compiler.error_stream(),
+            CompilerMessagingFormat::Json => self.compiler.output_stream(),
+        };
+
+        let mut diagnostics = deduplicate_reports(self.compiler.diagnostics());
+        let omitted = match self.compiler.settings().error_limit {
+            Some(limit) => limit_errors(&mut diagnostics, limit),
+            None => 0,
+        };
 
         // @@Copying: Ideally, we would not want to copy here!
-        for diagnostic in self.compiler.diagnostics().iter() {
+        for diagnostic in diagnostics {
             if diagnostic.is_error() {
                 err_count += 1;
             }
@@ -229,13 +316,26 @@ impl<I: CompilerInterface> Driver<I> {
                 warn_count += 1;
             }
 
-            stream_writeln!(stderr, "{}", diagnostic);
+            CompilerOutputMessage::Report(diagnostic.clone()).write(format, &mut stream).unwrap();
+        }
+
+        if omitted != 0 {
+            err_count += omitted;
+
+            let mut report = Report::new();
+            report
+                .kind(ReportKind::Info)
+                .title(format!("{omitted} more error(s) omitted"))
+                .add_help("use `--error-limit` to change the number of errors that are reported");
+            CompilerOutputMessage::Report(report).write(format, &mut stream).unwrap();
         }
 
         // ##Hack: to prevent the compiler from printing this message when the pipeline
         // when it was instructed to terminate before all of the stages. For example, if
         // the compiler is just checking the source, then it will terminate early.
-        if err_count != 0 || warn_count != 0 {
+        //
+        // The summary isn't a message, so it would break the JSON output.
+        if (err_count != 0 || warn_count != 0) && format == CompilerMessagingFormat::Normal {
             log::info!(
                 "compiler terminated with {err_count} error(s), and {warn_count} warning(s)."
             );
@@ -247,6 +347,16 @@ impl<I: CompilerInterface> Driver<I> {
     pub fn run(&mut self, source: SourceId) {
         let result = self.run_pipeline(source);
 
+        if let Some(path) = self.compiler.settings().timings_trace.clone()
+            && !self.bootstrapping
+            && let Err(error) =
+                File::create(&path).and_then(|mut file| write_chrome_trace(&self.spans, &mut file))
+        {
+            self.compiler
+                .diagnostics_mut()
+                .push(PipelineError::ResourceCreation { path, error }.into());
+        }
+
         // we can print the diagnostics here
         if self.compiler.settings().emit_errors
             && (!self.compiler.diagnostics().is_empty() || result.is_err())
@@ -292,12 +402,14 @@ impl<I: CompilerInterface> Driver<I> {
     }
 
     /// Potentially run an executable, if the compiler has successfully
-    /// compiled an executable.
+    /// compiled an executable. Programs that are compiled for the VM are
+    /// run by the bytecode generation stage instead.
     pub fn maybe_run_executable(&mut self) {
         let settings = self.compiler.settings();
         let workspace = self.compiler.workspace();
 
         if settings.stage == CompilerStageKind::Exe
+            && !settings.codegen_settings.backend().is_vm()
             && workspace.yields_executable(settings)
             && !self.has_errors()
         {
@@ -9,9 +9,19 @@
 //! keeping the crate dependency graph clean.
 #![feature(let_chains, thread_id_value)]
 pub mod driver;
+pub mod listener;
 mod metrics;
-
-use std::collections::HashSet;
+pub mod watch;
+
+use std::{
+    collections::HashSet,
+    env,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
 
 use driver::Driver;
 use hash_ast::node_map::NodeMap;
@@ -19,14 +29,18 @@ use hash_ast_desugaring::{AstDesugaringCtx, AstDesugaringCtxQuery, AstDesugaring
 use hash_ast_expand::{AstExpansionCtx, AstExpansionCtxQuery, AstExpansionPass};
 use hash_backend::{BackendCtxQuery, CodeGenPass};
 use hash_codegen::backend::{BackendCtx, CodeGenStorage};
+use hash_codegen_vm::{BytecodeGen, BytecodeGenCtx, BytecodeGenCtxQuery};
 use hash_ir::IrStorage;
 use hash_link::{CompilerLinker, LinkerCtx, LinkerCtxQuery};
 use hash_lower::{IrGen, IrOptimiser, LoweringCtx, LoweringCtxQuery};
+use hash_messaging::CompilerMessagingFormat;
 use hash_parser::{Parser, ParserCtx, ParserCtxQuery};
 use hash_pipeline::{
     error::PipelineError,
-    interface::{CompilerInterface, CompilerOutputStream, CompilerStage},
+    interface::{CancellationToken, CompilerInterface, CompilerOutputStream, CompilerStage},
+    progress::Progress,
     settings::CompilerSettings,
+    vfs::OverlayFileSystem,
     workspace::Workspace,
 };
 use hash_reporting::report::Report;
@@ -34,22 +48,129 @@ use hash_repr::LayoutStorage;
 use hash_semantics::{
     storage::SemanticStorage, SemanticAnalysis, SemanticAnalysisCtx, SemanticAnalysisCtxQuery,
 };
-use hash_source::SourceId;
+use hash_source::{ModuleKind, SourceId, SourceMapUtils};
+use hash_tir::{index::ModuleIndex, tir::TermId};
 use hash_untyped_semantics::{
     UntypedSemanticAnalysis, UntypedSemanticAnalysisCtx, UntypedSemanticAnalysisCtxQuery,
 };
-use hash_utils::rayon;
+use hash_utils::{path::normalise_path, rayon};
 
 /// A struct that is used to construct a [Compiler] with
 /// either a default or a custom configuration.
+///
+/// The builder can also be used to embed the compiler within other programs.
+/// The sources are then provided from memory, and the results are collected
+/// into a [Compilation] rather than being written to the standard streams:
+///
+/// ```ignore
+/// let compilation = CompilerBuilder::new()
+///     .with_settings(settings)
+///     .with_source("main", "main := () => {}")
+///     .compile()?;
+/// ```
+///
+/// The stores of the compiler are global to the process, and so only one
+/// [Compilation] can exist at a time. Whilst a [Compilation] is alive, any
+/// further calls to [CompilerBuilder::compile] return
+/// [PipelineError::CompilationInProgress]. Compilations that run one after
+/// another don't share any sources, but the memory of the stores is only
+/// released once the process exits.
 /// @@Naming
-pub struct CompilerBuilder;
+#[derive(Default)]
+pub struct CompilerBuilder {
+    /// The settings that the compiler is created with.
+    settings: CompilerSettings,
+
+    /// The names and contents of the sources that have been added.
+    sources: Vec<(String, String)>,
+
+    /// The token that can be used to cancel the compilation.
+    cancellation: CancellationToken,
+}
 
 impl CompilerBuilder {
+    /// Create a new [CompilerBuilder] with the default settings, and without
+    /// any sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given [CompilerSettings] for the compilation.
+    pub fn with_settings(mut self, settings: CompilerSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Add a module with the given name and contents. The first module that
+    /// is added is the entry point, all of the other modules can be imported
+    /// by their name, e.g. `import("utils")`. The modules are stored in memory
+    /// within the current directory, and take precedence over any files on
+    /// the disk.
+    pub fn with_source(mut self, name: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.sources.push((name.into(), contents.into()));
+        self
+    }
+
+    /// Use the given [CancellationToken] for the compilation, cancelling the
+    /// token stops the compilation before it runs any further stages, modules
+    /// or bodies.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Run the compiler on the sources that have been added. The diagnostics
+    /// and the output of the compiler are collected into the [Compilation].
+    ///
+    /// This fails with [PipelineError::CompilationInProgress] if another
+    /// [Compilation] of the process is still alive.
+    pub fn compile(self) -> Result<Compilation, PipelineError> {
+        let Self { mut settings, sources, cancellation } = self;
+        let guard = CompilationGuard::acquire()?;
+
+        // The entry point is always the first source, and the diagnostics are
+        // reported by the embedder.
+        settings.entry_point = None;
+        settings.set_emit_errors(false);
+
+        let mut workspace = Workspace::new(&settings)?;
+        let output = Arc::new(Mutex::new(Vec::new()));
+
+        let root = env::current_dir().unwrap_or_default();
+        let paths: Vec<_> =
+            sources.iter().map(|(name, _)| root.join(name).with_extension("hash")).collect();
+
+        let file_system = Arc::new(OverlayFileSystem::new(workspace.file_system.clone()));
+
+        for (path, (_, contents)) in paths.iter().zip(sources) {
+            file_system.set_file(path, contents);
+        }
+
+        workspace.file_system = file_system.clone();
+
+        let stream = {
+            let output = output.clone();
+            move || CompilerOutputStream::Owned(output.clone())
+        };
+        let interface = Compiler::with(workspace, settings, stream.clone(), stream);
+        let mut driver = Self::build_with_interface(interface);
+
+        // The token is only used once the prelude has been bootstrapped, since
+        // the prelude is shared by all of the compilations of the driver.
+        driver.workspace_mut().cancellation = cancellation;
+
+        if let Some(entry_point) = paths.first() {
+            driver.run_filename(normalise_path(entry_point), ModuleKind::EntryPoint);
+        }
+
+        Ok(Compilation { driver, paths, file_system, output, _guard: guard })
+    }
+
     /// Create a new [Compiler] with the default stage configuration.
     pub fn build_with_settings(settings: CompilerSettings) -> Driver<Compiler> {
         let stream = CompilerOutputStream::Stdout(std::io::stdout());
-        let session = utils::emit_on_fatal_error(stream, || Compiler::new(settings));
+        let format = settings.message_format;
+        let session = utils::emit_on_fatal_error(stream, format, || Compiler::new(settings));
         Self::build_with_interface(session)
     }
 
@@ -67,6 +188,7 @@ impl CompilerBuilder {
                 Box::<SemanticAnalysis>::default(),
                 Box::<IrGen>::default(),
                 Box::<IrOptimiser>::default(),
+                Box::<BytecodeGen>::default(),
                 Box::<CodeGenPass>::default(),
                 Box::<CompilerLinker>::default(),
             ],
@@ -88,19 +210,149 @@ impl CompilerBuilder {
     }
 }
 
-pub mod utils {
-    use std::io::Write;
+/// Whether a [Compilation] currently exists within the process.
+static COMPILATION_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// Marks a [Compilation] as being in progress until the guard is dropped.
+struct CompilationGuard;
+
+impl CompilationGuard {
+    /// Mark a [Compilation] as being in progress, if there isn't already one
+    /// in progress.
+    fn acquire() -> Result<Self, PipelineError> {
+        if COMPILATION_IN_PROGRESS.swap(true, Ordering::AcqRel) {
+            return Err(PipelineError::CompilationInProgress);
+        }
+
+        Ok(Self)
+    }
+}
+
+impl Drop for CompilationGuard {
+    fn drop(&mut self) {
+        COMPILATION_IN_PROGRESS.store(false, Ordering::Release);
+    }
+}
+
+/// The result of compiling the sources of a [CompilerBuilder].
+pub struct Compilation {
+    /// The [Driver] that performed the compilation.
+    driver: Driver<Compiler>,
+
+    /// The paths of all of the sources, in the order that they were added.
+    paths: Vec<PathBuf>,
 
+    /// The file system that contains the sources of the compilation.
+    file_system: Arc<OverlayFileSystem>,
+
+    /// Everything that the compiler wrote to its output streams.
+    output: Arc<Mutex<Vec<u8>>>,
+
+    /// Keeps the compilation marked as being in progress whilst it is alive.
+    _guard: CompilationGuard,
+}
+
+impl Compilation {
+    /// Get the [SourceId] of the source with the given name, this is [None]
+    /// if the source was never imported.
+    pub fn source(&self, name: &str) -> Option<SourceId> {
+        self.paths
+            .iter()
+            .find(|path| path.file_stem().is_some_and(|stem| stem == name))
+            .and_then(|path| SourceMapUtils::id_by_path(&normalise_path(path)))
+    }
+
+    /// Get all of the diagnostics that were emitted during the compilation.
+    pub fn diagnostics(&self) -> &[Report] {
+        self.driver.diagnostics()
+    }
+
+    /// Check whether any of the diagnostics is an error.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics().iter().any(|report| report.is_error())
+    }
+
+    /// Check whether the compilation was cancelled, the diagnostics of a
+    /// cancelled compilation are incomplete.
+    pub fn is_cancelled(&self) -> bool {
+        self.driver.workspace().cancellation.is_cancelled()
+    }
+
+    /// Get everything that the compiler wrote to its output streams, e.g. the
+    /// IR when `--ir-dump` is specified.
+    pub fn output(&self) -> String {
+        String::from_utf8_lossy(&self.output.lock().unwrap()).into_owned()
+    }
+
+    /// Get the [ModuleIndex] of the terms of the given source, which is only
+    /// available once the source has been discovered by semantic analysis.
+    pub fn index(&self, source: SourceId) -> Option<ModuleIndex> {
+        let mod_def = *self.driver.semantic_storage.source_mod_defs.borrow().get(&source)?;
+        Some(ModuleIndex::new(source, mod_def))
+    }
+
+    /// Evaluate the given input as an interactive block, after all of the
+    /// sources of the compilation. The result is [None] if the input couldn't
+    /// be evaluated, the reason is then reported in the diagnostics.
+    pub fn evaluate(&mut self, input: impl Into<String>) -> Option<TermId> {
+        let source = self.driver.workspace_mut().add_interactive_block(input.into());
+        self.driver.run(source);
+        self.driver.semantic_storage.evaluations.borrow().get(&source).copied()
+    }
+
+    /// Replace the contents of the source with the given name, and compile
+    /// the entry point again. The source and all of the modules that depend
+    /// on it are compiled again, whilst the items of any other modules are
+    /// re-used from the previous compilation.
+    pub fn update_source(&mut self, name: &str, contents: impl Into<String>) {
+        let Some(path) = self
+            .paths
+            .iter()
+            .find(|path| path.file_stem().is_some_and(|stem| stem == name))
+            .cloned()
+        else {
+            return;
+        };
+
+        self.file_system.set_file(&path, contents);
+        self.driver.workspace_mut().invalidate_module(&normalise_path(&path));
+        self.driver.diagnostics_mut().clear();
+
+        let entry_point = normalise_path(&self.paths[0]);
+        self.driver.run_filename(entry_point, ModuleKind::EntryPoint);
+    }
+
+    /// Get the IR of the compilation, which is only populated when the
+    /// compilation reaches the lowering stage.
+    pub fn ir(&self) -> &IrStorage {
+        &self.driver.icx
+    }
+
+    /// Get the [Driver] that performed the compilation, which can be used to
+    /// run further sources, or to inspect the state of the compiler.
+    ///
+    /// The compilation is no longer marked as being in progress afterwards,
+    /// so the [Driver] shouldn't be used whilst another [Compilation] exists.
+    pub fn into_driver(self) -> Driver<Compiler> {
+        self.driver
+    }
+}
+
+pub mod utils {
+    use hash_messaging::{CompilerMessagingFormat, CompilerOutputMessage};
     use hash_pipeline::interface::CompilerOutputStream;
     use hash_reporting::report::Report;
-    use hash_utils::stream_writeln;
 
     /// Emit a fatal compiler error and exit the compiler. These kind of errors
     /// are not **panics** but they are neither recoverable. This function
     /// will convert the error into a [Report] and then write it to the
-    /// error stream.
-    pub fn emit_fatal_error<E: Into<Report>>(mut stream: CompilerOutputStream, error: E) -> ! {
-        stream_writeln!(stream, "{}", error.into());
+    /// given stream in the given [CompilerMessagingFormat].
+    pub fn emit_fatal_error<E: Into<Report>>(
+        mut stream: CompilerOutputStream,
+        format: CompilerMessagingFormat,
+        error: E,
+    ) -> ! {
+        CompilerOutputMessage::Report(error.into()).write(format, &mut stream).unwrap();
         std::process::exit(-1);
     }
 
@@ -112,11 +364,12 @@ pub mod utils {
     /// [Into<Report>] trait.
     pub fn emit_on_fatal_error<T, E: Into<Report>>(
         stream: CompilerOutputStream,
+        format: CompilerMessagingFormat,
         f: impl FnOnce() -> Result<T, E>,
     ) -> T {
         match f() {
             Ok(value) => value,
-            Err(err) => emit_fatal_error(stream, err),
+            Err(err) => emit_fatal_error(stream, format, err),
         }
     }
 }
@@ -189,7 +442,7 @@ impl Compiler {
 
     /// Create a new [DefaultCompilerInterface].
     pub fn with(
-        workspace: Workspace,
+        mut workspace: Workspace,
         mut settings: CompilerSettings,
         error_stream: impl Fn() -> CompilerOutputStream + 'static,
         output_stream: impl Fn() -> CompilerOutputStream + 'static,
@@ -206,12 +459,20 @@ impl Compiler {
         let target = &mut settings.codegen_settings.target_info.target;
 
         // @@Fixme: ideally this error should be handled else-where
-        let layout_info = target
-            .parse_data_layout()
-            .unwrap_or_else(|err| utils::emit_fatal_error(error_stream(), err));
+        let format = settings.message_format;
+        let layout_info = target.parse_data_layout().unwrap_or_else(|err| match format {
+            CompilerMessagingFormat::Normal => utils::emit_fatal_error(error_stream(), format, err),
+            CompilerMessagingFormat::Json => utils::emit_fatal_error(output_stream(), format, err),
+        });
 
         target.set_data_layout(layout_info.clone());
 
+        // Progress is reported on the error stream so that it isn't mixed up
+        // with the output of the compiler.
+        if let Some(mode) = settings.progress {
+            workspace.progress = Progress::with_mode(mode, error_stream());
+        }
+
         Self {
             error_stream: Box::new(error_stream),
             output_stream: Box::new(output_stream),
@@ -339,7 +600,7 @@ impl LoweringCtxQuery for Compiler {
             lcx: &self.lcx,
             icx: &mut self.icx,
             stdout: output_stream,
-            _pool: &self.pool,
+            pool: &self.pool,
         }
     }
 }
@@ -360,11 +621,25 @@ impl BackendCtxQuery for Compiler {
     }
 }
 
+impl BytecodeGenCtxQuery for Compiler {
+    fn data(&mut self) -> BytecodeGenCtx<'_> {
+        let stdout = self.output_stream();
+
+        BytecodeGenCtx {
+            workspace: &self.workspace,
+            icx: &self.icx,
+            lcx: &self.lcx,
+            settings: &self.settings,
+            stdout,
+        }
+    }
+}
+
 impl LinkerCtxQuery for Compiler {
     fn data(&mut self) -> hash_link::LinkerCtx<'_> {
         let stdout = self.output_stream();
 
-        LinkerCtx { workspace: &self.workspace, settings: &self.settings, stdout }
+        LinkerCtx { workspace: &self.workspace, icx: &self.icx, settings: &self.settings, stdout }
     }
 }
 
@@ -378,6 +653,7 @@ pub trait DefaultCtxQuery:
     + UntypedSemanticAnalysisCtxQuery
     + SemanticAnalysisCtxQuery
     + LoweringCtxQuery
+    + BytecodeGenCtxQuery
     + BackendCtxQuery
     + LinkerCtxQuery
 {
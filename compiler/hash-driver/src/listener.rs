@@ -0,0 +1,203 @@
+//! Hash Compiler listener. An external process drives the compiler by writing
+//! a [CompilerInputMessage] on each line of the input, and the messages of
+//! each session are written to the output as JSON, see [hash_messaging].
+//!
+//! The sessions run one at a time on a separate thread, so that messages can
+//! still be read whilst a session is running, i.e. in order to cancel it. Each
+//! session creates a new compiler with a [CompilerBuilder], and so the
+//! prelude is analysed again for every session.
+use std::{
+    env,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+
+use hash_messaging::{CompilerInputMessage, CompilerMessagingFormat, CompilerOutputMessage};
+use hash_pipeline::{
+    error::PipelineError,
+    fs::resolve_path,
+    interface::CancellationToken,
+    settings::{CompilerSettings, CompilerStageKind},
+    vfs::{FileSystem, RealFileSystem},
+};
+use hash_reporting::{
+    report::{Report, ReportKind},
+    reporter::deduplicate_reports,
+};
+
+use crate::CompilerBuilder;
+
+/// A session that has been started by a [CompilerInputMessage], and is
+/// waiting to be run.
+struct Session {
+    /// The id of the session, which is used to cancel it.
+    id: usize,
+
+    /// The message that started the session.
+    message: CompilerInputMessage,
+
+    /// The token that cancels the session.
+    cancellation: CancellationToken,
+}
+
+impl Session {
+    /// Run the session, and produce all of the messages of the session.
+    fn run(self, settings: &CompilerSettings) -> Vec<CompilerOutputMessage> {
+        let mut settings = settings.clone();
+        settings.set_stage(CompilerStageKind::Analysis);
+
+        // The messages are always written as JSON, this also stops the result
+        // of an evaluation from being printed by the compiler.
+        settings.message_format = CompilerMessagingFormat::Json;
+
+        let builder = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_cancellation_token(self.cancellation);
+        let mut evaluation = None;
+
+        let compilation = match self.message {
+            CompilerInputMessage::CheckSource { path } => {
+                read_source(&path).and_then(|(path, contents)| {
+                    builder
+                        .with_source(path.to_string_lossy(), contents)
+                        .compile()
+                        .map_err(Report::from)
+                })
+            }
+            CompilerInputMessage::EvaluateExpr { text } => {
+                builder.compile().map_err(Report::from).map(|mut compilation| {
+                    evaluation = compilation.evaluate(text);
+                    compilation
+                })
+            }
+            CompilerInputMessage::Cancel { .. } | CompilerInputMessage::Shutdown => {
+                unreachable!("only sessions are run")
+            }
+        };
+
+        let mut messages = vec![];
+        let cancelled = match compilation {
+            Ok(compilation) => {
+                let reports = deduplicate_reports(compilation.diagnostics()).into_iter().cloned();
+                messages.extend(reports.map(CompilerOutputMessage::Report));
+                compilation.is_cancelled()
+            }
+            Err(report) => {
+                messages.push(CompilerOutputMessage::Report(report));
+                false
+            }
+        };
+
+        if let Some(result) = evaluation {
+            messages.push(CompilerOutputMessage::Evaluation {
+                id: self.id,
+                result: result.to_string(),
+            });
+        }
+
+        messages.push(CompilerOutputMessage::SessionFinished { id: self.id, cancelled });
+        messages
+    }
+}
+
+/// Resolve the given path relative to the current directory, and read the
+/// contents of the module.
+fn read_source(path: &Path) -> Result<(PathBuf, String), Report> {
+    let wd = env::current_dir().unwrap_or_default();
+    let path = resolve_path(&RealFileSystem, path.to_string_lossy().as_ref(), wd)
+        .map_err(|err| Report::from(PipelineError::ImportPath(err)))?;
+
+    match RealFileSystem.read(&path) {
+        Ok(contents) => Ok((path, contents)),
+        Err(err) => {
+            let mut report = Report::new();
+            report
+                .kind(ReportKind::Error)
+                .title(format!("couldn't read `{}`: {err}", path.display()));
+            Err(report)
+        }
+    }
+}
+
+/// Write the given message as JSON, the messages are best effort since there
+/// is nobody to report a failure to.
+fn write_message(output: &Mutex<impl Write>, message: CompilerOutputMessage) {
+    let mut output = output.lock().unwrap();
+    let _ = message.write(CompilerMessagingFormat::Json, &mut *output);
+    let _ = output.flush();
+}
+
+/// Read messages from the `input` until it ends or a
+/// [CompilerInputMessage::Shutdown] is read, and run each of the sessions that
+/// they start with the given [CompilerSettings]. Every message that starts a
+/// session is answered with a [CompilerOutputMessage::SessionStarted], and
+/// each session ends with a [CompilerOutputMessage::SessionFinished]. This
+/// returns once all of the sessions have finished.
+pub fn listen(
+    settings: CompilerSettings,
+    input: impl BufRead,
+    output: impl Write + Send + 'static,
+) {
+    let output = Arc::new(Mutex::new(output));
+    let (sender, receiver) = mpsc::channel::<Session>();
+
+    let worker = {
+        let output = output.clone();
+
+        thread::spawn(move || {
+            for session in receiver {
+                for message in session.run(&settings) {
+                    write_message(&output, message);
+                }
+            }
+        })
+    };
+
+    // The cancellation token of each session, by the id of the session.
+    let mut sessions = vec![];
+
+    for line in input.lines() {
+        let Ok(line) = line else { break };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message = match CompilerInputMessage::parse(&line) {
+            Ok(message) => message,
+            Err(err) => {
+                let mut report = Report::new();
+                report.kind(ReportKind::Error).title(format!("invalid message: {}", err.message));
+                write_message(&output, CompilerOutputMessage::Report(report));
+                continue;
+            }
+        };
+
+        match message {
+            CompilerInputMessage::CheckSource { .. }
+            | CompilerInputMessage::EvaluateExpr { .. } => {
+                let id = sessions.len();
+                let cancellation = CancellationToken::new();
+                sessions.push(cancellation.clone());
+
+                // The session is announced before it is queued, so that its id
+                // is always written before any of its messages.
+                write_message(&output, CompilerOutputMessage::SessionStarted { id });
+                let _ = sender.send(Session { id, message, cancellation });
+            }
+            CompilerInputMessage::Cancel { id } => {
+                if let Some(cancellation) = sessions.get(id) {
+                    cancellation.cancel();
+                }
+            }
+            CompilerInputMessage::Shutdown => break,
+        }
+    }
+
+    // The worker finishes once all of the sessions that are still queued have
+    // been run.
+    drop(sender);
+    let _ = worker.join();
+}
@@ -1,10 +1,14 @@
 //! Utilities and functions to work with compiler metrics.
 
-use std::{io::Write, time::Duration};
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
 
 use hash_pipeline::settings::CompilerStageKind;
 use hash_utils::{
     indexmap::IndexMap,
+    json::JsonValue,
     profiling::{MetricEntry, StageMetrics},
     stream_write, stream_writeln,
 };
@@ -19,6 +23,53 @@ pub struct StageMetricEntry {
 
 pub type Metrics = IndexMap<CompilerStageKind, StageMetricEntry>;
 
+/// A single run of a stage, the `start` of the run is relative to the
+/// creation of the [Driver]. Unlike [Metrics], the runs of a stage aren't
+/// merged, so that they can be written as a trace of the whole compilation.
+///
+/// [Driver]: crate::driver::Driver
+#[derive(Debug, Clone, Copy)]
+pub struct StageSpan {
+    /// The stage that was run.
+    pub kind: CompilerStageKind,
+
+    /// When the stage started running.
+    pub start: Duration,
+
+    /// How long the stage took to run.
+    pub duration: Duration,
+
+    /// The resident set size of the process once the stage finished.
+    pub end_rss: Option<usize>,
+}
+
+/// Write the given [StageSpan]s in the Chrome trace event format, which can
+/// be loaded by `chrome://tracing`, Perfetto, or flamegraph tools. Each
+/// span is written as a "complete" event, and all times are in
+/// microseconds.
+pub fn write_chrome_trace(spans: &[StageSpan], stream: &mut impl Write) -> io::Result<()> {
+    let events = spans
+        .iter()
+        .map(|span| {
+            let rss = span.end_rss.map_or(JsonValue::Null, JsonValue::from);
+
+            JsonValue::object([
+                ("name", JsonValue::string(span.kind)),
+                ("cat", JsonValue::string("stage")),
+                ("ph", JsonValue::string("X")),
+                ("ts", JsonValue::from(span.start.as_micros() as usize)),
+                ("dur", JsonValue::from(span.duration.as_micros() as usize)),
+                ("pid", JsonValue::from(0usize)),
+                ("tid", JsonValue::from(0usize)),
+                ("args", JsonValue::object([("rss", rss)])),
+            ])
+        })
+        .collect();
+
+    let trace = JsonValue::object([("traceEvents", JsonValue::Array(events))]);
+    writeln!(stream, "{trace}")
+}
+
 /// Utility struct to report compiler metrics.
 pub struct MetricReporter<'a> {
     /// The metrics that are going to be reported.
@@ -38,7 +89,9 @@ impl<'a> MetricReporter<'a> {
                 metrics
                     .children
                     .iter()
-                    .map(|(item, _)| label_size + item.len() + 2)
+                    .map(|(item, _)| item)
+                    .chain(metrics.children.iter_counters().map(|(item, _)| item))
+                    .map(|item| label_size + item.len() + 2)
                     .max()
                     .unwrap_or(label_size)
             })
@@ -83,6 +136,16 @@ impl<'a> MetricReporter<'a> {
         for (name, metric) in metrics.iter() {
             self.report_metric(stream, kind, Some(name), &metric)
         }
+
+        for (name, count) in metrics.iter_counters() {
+            stream_writeln!(
+                stream,
+                "{: <width$}: {:>10}",
+                format!("{kind}::{name}"),
+                count,
+                width = self.longest_metric_key
+            );
+        }
     }
 
     fn report_metric(
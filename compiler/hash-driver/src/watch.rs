@@ -0,0 +1,157 @@
+//! Hash Compiler watch mode. The entry point is compiled, and then compiled
+//! again whenever it, or any of the modules that it imports, changes.
+//!
+//! The same compiler is used for every compilation, the modules that have
+//! changed are invalidated in the [Workspace], so that only they and the
+//! modules that depend on them are compiled again. The modules of the
+//! workspace after a compilation tell the watcher which files to watch for
+//! the next change. If the compilation fails before any of the modules are
+//! added, only the entry point is watched.
+//!
+//! [Workspace]: hash_pipeline::workspace::Workspace
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use hash_pipeline::{interface::CompilerInterface, settings::CompilerSettings};
+use hash_utils::{fxhash::FxHashMap, log};
+
+use crate::CompilerBuilder;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Compile the entry point of the given settings, and compile it again
+/// whenever any of the modules that the compilation used change. This never
+/// returns.
+pub fn watch(settings: CompilerSettings) -> ! {
+    let entry_point = settings.entry_point().ok();
+    let mut compiler = CompilerBuilder::build_with_settings(settings);
+
+    // The entry point is watched before the first compilation, so that a
+    // change that is made whilst it is running is noticed afterwards.
+    let mut watcher = Watcher::default();
+    watcher.watch(entry_point.as_slice());
+
+    loop {
+        compiler.run_on_entry_point();
+        compiler.maybe_run_executable();
+
+        let mut files = compiler.workspace().module_paths();
+        if files.is_empty() {
+            files.extend(entry_point.clone());
+        }
+
+        log::info!("watching {} modules for changes", files.len());
+        watcher.watch(&files);
+
+        for file in watcher.wait_for_change() {
+            compiler.workspace_mut().invalidate_module(&file);
+        }
+
+        compiler.diagnostics_mut().clear();
+    }
+}
+
+/// Get the time when the file was last modified, or [None] if the file
+/// doesn't exist, e.g. a module that is imported but hasn't been created.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// The files that are watched for changes.
+#[derive(Default)]
+struct Watcher {
+    /// The time when each file was last seen to be modified, or [None] if the
+    /// file didn't exist.
+    files: FxHashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl Watcher {
+    /// Watch the given files, and stop watching any other files. A file that
+    /// is already watched keeps the time when it was last seen to be modified,
+    /// so a change that is made whilst compiling is still noticed.
+    fn watch(&mut self, files: &[PathBuf]) {
+        self.files.retain(|file, _| files.contains(file));
+
+        for file in files {
+            self.files.entry(file.clone()).or_insert_with(|| modified(file));
+        }
+    }
+
+    /// Get the files that have been modified, created or removed since they
+    /// were last seen, and remember their new state. Any change to the time
+    /// when a file was modified is reported, even if the time moves backwards.
+    fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed: Vec<_> = self
+            .files
+            .iter_mut()
+            .filter_map(|(file, last_modified)| {
+                let time = modified(file);
+                (time != *last_modified).then(|| {
+                    *last_modified = time;
+                    file.clone()
+                })
+            })
+            .collect();
+
+        changed.sort();
+        changed
+    }
+
+    /// Block until any of the watched files change, and return the files that
+    /// changed.
+    fn wait_for_change(&mut self) -> Vec<PathBuf> {
+        loop {
+            let changed = self.changed();
+
+            if !changed.is_empty() {
+                return changed;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process, time::SystemTime};
+
+    use super::Watcher;
+
+    #[test]
+    fn test_changed_files() {
+        let dir = env::temp_dir().join(format!("hash-watch-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let (existing, missing) = (dir.join("existing.hash"), dir.join("missing.hash"));
+        fs::write(&existing, "").unwrap();
+
+        let mut watcher = Watcher::default();
+        watcher.watch(&[existing.clone(), missing.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // A file whose modification time moves backwards is still reported,
+        // but only once.
+        let file = fs::File::options().write(true).open(&existing).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        assert_eq!(watcher.changed(), [existing.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        // Files that are created or removed are reported.
+        fs::write(&missing, "").unwrap();
+        fs::remove_file(&existing).unwrap();
+        assert_eq!(watcher.changed(), [existing.clone(), missing.clone()]);
+
+        // Files that are no longer watched are not reported.
+        watcher.watch(&[missing.clone()]);
+        fs::write(&existing, "").unwrap();
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
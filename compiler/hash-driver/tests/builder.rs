@@ -0,0 +1,568 @@
+//! Hash Compiler driver tests.
+use std::{
+    fmt::Debug,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use hash_driver::{listener::listen, Compilation, CompilerBuilder};
+use hash_messaging::{CompilerMessagingFormat, CompilerOutputMessage};
+use hash_pipeline::{
+    error::PipelineError,
+    interface::CancellationToken,
+    settings::{
+        ColourMode, CompilerCommand, CompilerSettings, CompilerStageKind, EmitKind, ProgressMode,
+    },
+};
+use hash_reporting::{
+    hash_error_codes::{
+        error_codes::HashErrorCode, warning_codes::HashWarningCode, DiagnosticCode,
+    },
+    report::{Report, ReportElement},
+    reporter::{deduplicate_reports, limit_errors},
+};
+use hash_utils::{clap::Parser, json::JsonValue};
+use rusty_fork::rusty_fork_test;
+
+/// Get the fingerprint of the definition that the body with the given name
+/// was lowered from.
+fn fingerprint_of(compilation: &Compilation, name: &str) -> Option<impl PartialEq + Debug> {
+    let ir = compilation.ir();
+    let index = ir.bodies.iter().position(|body| body.meta.name().as_str() == name)?;
+    ir.fingerprints.iter().find_map(|(fingerprint, body)| (body == index).then_some(fingerprint))
+}
+
+/// Get the hash of the optimised body with the given name.
+fn body_hash_of(compilation: &Compilation, name: &str) -> Option<impl PartialEq + Debug> {
+    let ir = compilation.ir();
+    let index = ir.bodies.iter().position(|body| body.meta.name().as_str() == name)?;
+    ir.body_hash(index)
+}
+
+/// An output stream that can still be read once it has been moved into the
+/// listener.
+#[derive(Clone, Default)]
+struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Each of the tests is run in a separate process, since the stores of the
+// compiler are shared by all of the compilations in a process.
+rusty_fork_test! {
+    #[test]
+    fn compile_in_memory_sources() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Analysis);
+
+        let source = r#"utils := import("utils");
+main := () => {
+    x: str = utils::double(2);
+};
+"#;
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", source)
+            .with_source("utils", "double := (x: i32) -> i32 => x * 2;\n")
+            .compile()
+            .unwrap();
+
+        // The import is resolved to the other source, so the only error is the
+        // mismatch between the types.
+        assert!(compilation.source("utils").is_some());
+        assert!(compilation.has_errors());
+        assert!(compilation.diagnostics().iter().all(|report| !report.title.contains("import")));
+    }
+
+    #[test]
+    fn only_one_compilation_exists_at_a_time() {
+        let compile = || {
+            let mut settings = CompilerSettings::new();
+            settings.set_stage(CompilerStageKind::Analysis);
+
+            CompilerBuilder::new().with_settings(settings).with_source("main", "x := 1;\n").compile()
+        };
+
+        // Another compilation can't be started whilst the first one is alive,
+        // but it can be once the first one has been dropped.
+        let compilation = compile().unwrap();
+        assert!(matches!(compile(), Err(PipelineError::CompilationInProgress)));
+
+        drop(compilation);
+        assert!(compile().is_ok());
+    }
+
+    #[test]
+    fn cancelled_compilation_stops_early() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let compilation = CompilerBuilder::new()
+            .with_cancellation_token(token)
+            .with_source("main", "main := () => {\n    x: str = 1;\n};\n")
+            .compile()
+            .unwrap();
+
+        // None of the stages run, so the type error isn't reported.
+        assert!(compilation.is_cancelled());
+        assert_eq!(compilation.diagnostics().len(), 1);
+        assert!(compilation.diagnostics()[0].title.contains("cancelled"));
+    }
+
+    #[test]
+    fn report_progress_as_json() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+        settings.progress = Some(ProgressMode::Json);
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", "foo := (x: i32) -> i32 => x + 1;\n")
+            .compile()
+            .unwrap();
+
+        let output = compilation.output();
+        assert!(!compilation.has_errors());
+
+        for stage in ["parse", "analysis", "lower"] {
+            let message = format!("\"stage\": \"{stage}\"");
+            assert!(
+                output.lines().any(|line| line.contains(&message)),
+                "no progress for `{stage}`"
+            );
+        }
+    }
+
+    #[test]
+    fn exceeding_the_memory_limit_stops_the_compilation() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Analysis);
+        settings.max_memory = Some(0);
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", "foo := (x: i32) -> i32 => x + 1;\n")
+            .compile()
+            .unwrap();
+
+        // The limit is checked once the parser has finished, which stops the
+        // compilation before any of the other stages.
+        let [report] = compilation.diagnostics() else { panic!("expected a single diagnostic") };
+        assert!(report.title.contains("memory limit of 0MiB"));
+        assert!(report.title.contains("`parse` stage"));
+    }
+
+    #[test]
+    fn emit_the_selected_artefacts() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+        settings.prelude_is_quiet = true;
+        settings.emit = vec![EmitKind::Ir, EmitKind::Layout];
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source(
+                "main",
+                "Point := struct(x: i32, y: i32);\nfoo := (x: i32) -> i32 => x + 1;\n",
+            )
+            .compile()
+            .unwrap();
+
+        let output = compilation.output();
+        assert!(!compilation.has_errors());
+        assert!(output.contains("IR dump for function `foo`"));
+        assert!(output.contains("Point"));
+    }
+
+    #[test]
+    fn print_the_global_allocations() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+        settings.prelude_is_quiet = true;
+        settings.lowering_settings.print_globals = true;
+
+        // The strings of each module are interned separately, so the `"hello"`
+        // literals of the two modules are different allocations.
+        let source = r#"greetings := import("greetings");
+    foo := () -> str => "hello";
+    baz := () -> str => "world!";
+    "#;
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", source)
+            .with_source("greetings", "bar := () -> str => \"hello\";\n")
+            .compile()
+            .unwrap();
+
+        // Both of the `"hello"` literals share the same data.
+        let output = compilation.output();
+        assert!(!compilation.has_errors());
+        assert!(output.contains(".rodata ("));
+        assert!(output.contains("\"world!\""));
+        assert!(output.contains("pooled with #"));
+    }
+
+    #[test]
+    fn write_a_trace_of_the_stages() {
+        let path = std::env::temp_dir().join(format!("hash-trace-{}.json", std::process::id()));
+
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+        settings.timings_trace = Some(path.clone());
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", "foo := (x: i32) -> i32 => x + 1;\n")
+            .compile()
+            .unwrap();
+
+        assert!(!compilation.has_errors());
+
+        let trace = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(trace.contains("\"traceEvents\""));
+
+        for stage in ["parse", "analysis", "lower"] {
+            assert!(trace.contains(&format!("\"name\": \"{stage}\"")), "no event for `{stage}`");
+        }
+    }
+
+    #[test]
+    fn lower_the_bodies_that_depend_on_a_changed_source_again() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+
+        let mut compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source(
+                "shapes_main",
+                "shapes := import(\"shapes\");\ncolours := import(\"colours\");\n\
+                 main := () => {\n    area := shapes::area(2);\n    red := colours::red();\n};\n",
+            )
+            .with_source("shapes", "area := (x: i32) -> i32 => x * 2;\n")
+            .with_source("colours", "red := () -> u8 => 255;\n")
+            .compile()
+            .unwrap();
+
+        assert!(!compilation.has_errors());
+        let (area, red) =
+            (fingerprint_of(&compilation, "area"), fingerprint_of(&compilation, "red"));
+        assert!(area.is_some() && red.is_some());
+
+        compilation.update_source("shapes", "area := (x: i32) -> i32 => x * 3;\n");
+        assert!(!compilation.has_errors());
+
+        // The changed module is lowered again, and the stale bodies are removed,
+        // whilst the body of the module that didn't change is re-used.
+        assert_ne!(fingerprint_of(&compilation, "area"), area);
+        assert_eq!(fingerprint_of(&compilation, "red"), red);
+
+        for name in ["main", "area", "red"] {
+            let bodies =
+                compilation.ir().bodies.iter().filter(|body| body.meta.name().as_str() == name);
+            assert_eq!(bodies.count(), 1, "expected a single body for `{name}`");
+        }
+    }
+
+    #[test]
+    fn reuse_the_bodies_of_unchanged_modules() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+        settings.progress = Some(ProgressMode::Json);
+
+        let mut compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", "shapes := import(\"shapes\");\nmain := () => { shapes::area(2); };\n")
+            .with_source("shapes", "area := (x: i32) -> i32 => x * 2;\n")
+            .compile()
+            .unwrap();
+
+        assert!(!compilation.has_errors());
+        let area = fingerprint_of(&compilation, "area");
+        let previous = compilation.output().len();
+
+        compilation.update_source("main", "shapes := import(\"shapes\");\nmain := () => { shapes::area(3); };\n");
+        assert!(!compilation.has_errors());
+
+        // The body of `area` is re-used, so only the body of `main` is
+        // optimised again.
+        assert_eq!(fingerprint_of(&compilation, "area"), area);
+
+        let output = compilation.output();
+        let optimised = output[previous..]
+            .lines()
+            .filter_map(|line| JsonValue::parse(line).ok())
+            .filter(|message| message.get("stage").and_then(JsonValue::as_str) == Some("optimise"))
+            .filter_map(|message| message.get("total").and_then(JsonValue::as_usize))
+            .last();
+        assert_eq!(optimised, Some(1));
+    }
+
+    #[test]
+    fn body_hashes_only_change_with_the_ir_of_the_body() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Lower);
+
+        let mut compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", "shapes := import(\"shapes\");\nmain := () => { shapes::area(2); };\n")
+            .with_source("shapes", "area := (x: i32) -> i32 => x * 2;\n")
+            .compile()
+            .unwrap();
+
+        let area = body_hash_of(&compilation, "area");
+        assert!(area.is_some());
+
+        // Moving the definition doesn't change its body, and so the hash of
+        // the body that is lowered again is the same.
+        compilation.update_source("shapes", "// The area of a square.\n\narea := (x: i32) -> i32 => x * 2;\n");
+        assert!(!compilation.has_errors());
+        assert_eq!(body_hash_of(&compilation, "area"), area);
+
+        compilation.update_source("shapes", "area := (x: i32) -> i32 => x * 3;\n");
+        assert!(!compilation.has_errors());
+        assert_ne!(body_hash_of(&compilation, "area"), area);
+    }
+
+    #[test]
+    fn write_diagnostics_as_json_messages() {
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Analysis);
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", "main := () => {\n    x: str = 1;\n};\n")
+            .compile()
+            .unwrap();
+
+        let report = compilation.diagnostics().iter().find(|report| report.is_error()).unwrap();
+        let mut output = vec![];
+        CompilerOutputMessage::Report(report.clone())
+            .write(CompilerMessagingFormat::Json, &mut output)
+            .unwrap();
+
+        // Each message is written on its own line, and the span of the mismatch
+        // is reported with the path of the source.
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.starts_with(r#"{ "type": "report", "kind": "error", "code": "#));
+        assert!(output.contains(r#""start": { "line": 2, "#));
+        assert!(output.contains("main.hash"));
+    }
+
+    #[test]
+    fn suggest_replacements_for_reports() {
+        let first_error = |source: &str| {
+            let mut settings = CompilerSettings::new();
+            settings.set_stage(CompilerStageKind::Analysis);
+
+            let compilation = CompilerBuilder::new()
+                .with_settings(settings)
+                .with_source("main", source)
+                .compile();
+            let report =
+                compilation.unwrap().diagnostics().iter().find(|report| report.is_error()).cloned();
+            report.unwrap()
+        };
+
+        // The assignment at the top-level was likely meant to be a declaration.
+        let report = first_error("a = 2;\n");
+        let Some(ReportElement::Suggestion(suggestion)) =
+            report.contents.iter().find(|element| matches!(element, ReportElement::Suggestion(_)))
+        else {
+            panic!("expected a suggestion")
+        };
+        assert_eq!(suggestion.replacement, ":=");
+        assert!(report.to_string().contains("a := 2;"));
+
+        let mut output = vec![];
+        CompilerOutputMessage::Report(report)
+            .write(CompilerMessagingFormat::Json, &mut output)
+            .unwrap();
+        assert!(String::from_utf8(output).unwrap().contains(r#""replacement": ":=""#));
+
+        // The parser suggests the missing `;` of an array type, and the `=` of a
+        // declaration with a type.
+        let report = first_error("main := () => {\n    x: [i32 2] = [1, 2];\n};\n");
+        assert!(report.to_string().contains("[i32; 2]"));
+
+        let report = first_error("main := () => {\n    x: i32 := 1;\n};\n");
+        assert!(report.to_string().contains("x: i32 = 1;"));
+    }
+
+    #[test]
+    fn change_the_level_of_warnings() {
+        let trailing_semis = Some(DiagnosticCode::Warning(HashWarningCode::TrailingSemicolons));
+        let compile = |args: &[&str]| {
+            let mut settings =
+                CompilerSettings::try_parse_from(["hashc"].iter().chain(args).copied()).unwrap();
+            settings.set_stage(CompilerStageKind::Analysis);
+
+            let compilation = CompilerBuilder::new()
+                .with_settings(settings)
+                .with_source("main", "main := () => {\n    x := 1;;\n};\n")
+                .compile()
+                .unwrap();
+            compilation
+                .diagnostics()
+                .iter()
+                .find(|report| report.code == trailing_semis)
+                .map(|report| (report.is_error(), report.to_string()))
+        };
+
+        // The warning is reported with its code, unless it is allowed.
+        let (is_error, rendered) = compile(&[]).unwrap();
+        assert!(!is_error);
+        assert!(rendered.contains("[W0003]"));
+        assert!(compile(&["-A", "W0003"]).is_none());
+        assert!(compile(&["-A", "warnings"]).is_none());
+
+        // A denied warning is reported as an error, and the level of the code takes
+        // precedence over the level of all warnings.
+        assert_eq!(compile(&["-D", "warnings"]).map(|(is_error, _)| is_error), Some(true));
+        assert_eq!(
+            compile(&["-A", "warnings", "-W", "w3"]).map(|(is_error, _)| is_error),
+            Some(false)
+        );
+        assert!(CompilerSettings::try_parse_from(["hashc", "-D", "E0020"]).is_err());
+    }
+
+    #[test]
+    fn deduplicate_and_limit_reports() {
+        let mut settings =
+            CompilerSettings::try_parse_from(["hashc", "--error-limit", "1"]).unwrap();
+        settings.set_stage(CompilerStageKind::Analysis);
+        assert_eq!(settings.error_limit, Some(1));
+
+        // The mismatches are in separate functions, since the checking of a block
+        // stops at its first error.
+        let source = r#"x := () => {
+        a: str = 1;
+    };
+    y := () => {
+        b: str = 2;
+    };
+    z := () => {
+        c: str = 3;
+    };
+    "#;
+
+        let compilation = CompilerBuilder::new()
+            .with_settings(settings)
+            .with_source("main", source)
+            .compile()
+            .unwrap();
+        let reports = compilation.diagnostics();
+        let is_mismatch = |report: &Report| {
+            report.code == Some(DiagnosticCode::Error(HashErrorCode::TypeMismatch))
+        };
+
+        // Each of the mismatches is at a different span, and reporting all of the
+        // diagnostics twice has no effect.
+        let mut deduplicated = deduplicate_reports(reports);
+        assert_eq!(deduplicated.iter().filter(|report| is_mismatch(report)).count(), 3);
+        assert_eq!(deduplicate_reports(reports.iter().chain(reports)).len(), deduplicated.len());
+
+        // Only the first error is kept when the errors are limited.
+        let errors = deduplicated.iter().filter(|report| report.is_error()).count();
+        assert_eq!(limit_errors(&mut deduplicated, 1), errors - 1);
+        assert_eq!(deduplicated.iter().filter(|report| report.is_error()).count(), 1);
+    }
+
+    #[test]
+    fn choose_when_to_use_colours() {
+        let settings = CompilerSettings::try_parse_from(["hashc", "--color=never"]).unwrap();
+        assert_eq!(settings.colour, ColourMode::Never);
+        assert_eq!(CompilerSettings::new().colour, ColourMode::Auto);
+
+        // The explicit modes don't depend on the terminal or the environment.
+        assert!(ColourMode::Always.use_colours());
+        assert!(!ColourMode::Never.use_colours());
+    }
+
+    #[test]
+    fn explain_diagnostic_codes() {
+        let settings = CompilerSettings::try_parse_from(["hashc", "explain", "E0020"]).unwrap();
+        let Some(CompilerCommand::Explain { code }) = settings.command else {
+            panic!("expected the `explain` command")
+        };
+        assert_eq!(code, DiagnosticCode::Error(HashErrorCode::TypeMismatch));
+        assert_eq!(code.to_string(), "E0020");
+
+        // Every code has an explanation.
+        assert!(HashErrorCode::ALL.iter().all(|code| !code.explanation().is_empty()));
+        assert!(HashWarningCode::ALL.iter().all(|code| !code.explanation().is_empty()));
+    }
+
+    #[test]
+    fn drive_sessions_through_messages() {
+        let path = std::env::temp_dir().join(format!("hash-listen-{}.hash", std::process::id()));
+        std::fs::write(&path, "main := () => {\n    x: str = 1;\n};\n").unwrap();
+
+        let input = [
+            format!(r#"{{ "type": "check_source", "path": "{}" }}"#, path.display()),
+            r#"{ "type": "evaluate_expr", "text": "1 + 2" }"#.to_string(),
+            r#"{ "type": "compile" }"#.to_string(),
+            r#"{ "type": "shutdown" }"#.to_string(),
+        ]
+        .join("\n");
+
+        let output = SharedOutput::default();
+        listen(CompilerSettings::new(), input.as_bytes(), output.clone());
+        std::fs::remove_file(&path).unwrap();
+
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let messages: Vec<_> = output.lines().map(|line| JsonValue::parse(line).unwrap()).collect();
+        let kind = |message: &JsonValue| message.get("type").and_then(JsonValue::as_str).map(str::to_string);
+        let id = |message: &JsonValue| message.get("id").and_then(JsonValue::as_usize);
+
+        // Both sessions are started and finished, in order.
+        let sessions: Vec<_> = messages
+            .iter()
+            .filter(|message| kind(message).is_some_and(|kind| kind.starts_with("session_")))
+            .map(|message| (kind(message).unwrap(), id(message).unwrap()))
+            .collect();
+        assert_eq!(sessions.iter().filter(|(kind, _)| kind == "session_started").count(), 2);
+        assert_eq!(sessions.last(), Some(&("session_finished".to_string(), 1)));
+
+        // The type error of the checked source is reported within the first
+        // session, and the expression of the second session is evaluated.
+        let finished = |session| {
+            messages.iter().position(|message| {
+                kind(message).as_deref() == Some("session_finished") && id(message) == Some(session)
+            })
+        };
+        let mismatch = messages.iter().position(|message| {
+            kind(message).as_deref() == Some("report")
+                && message
+                    .get("title")
+                    .and_then(JsonValue::as_str)
+                    .is_some_and(|title| title.contains("str"))
+        });
+        assert!(mismatch.unwrap() < finished(0).unwrap());
+
+        let evaluation =
+            messages.iter().find(|message| kind(message).as_deref() == Some("evaluation"));
+        assert_eq!(evaluation.and_then(id), Some(1));
+        assert_eq!(evaluation.and_then(|message| message.get("result")?.as_str()), Some("3_i32"));
+
+        // The message with an unknown type is reported, and doesn't start a
+        // session.
+        assert!(messages.iter().any(|message| {
+            message
+                .get("title")
+                .and_then(JsonValue::as_str)
+                .is_some_and(|title| title.contains("`compile`"))
+        }));
+    }
+}
@@ -1,62 +1,423 @@
-//! Hash Error code definitions.
-
-error_codes! {
-    // Semantic errors
-    ExpectingBooleanInCondition = 1,
-    UsingBreakOutsideLoop = 2,
-    UsingContinueOutsideLoop = 3,
-    UsingReturnOutsideFn = 4,
-    ItemIsImmutable = 5,
-    ItemMustBeImmutable = 6,
-    MultipleEntryPoints = 7,
-    InvalidEntryPointSignature = 8,
-
-    // Name spacing and symbol errors
-    UnresolvedSymbol = 10,
-    UnsupportedAccess = 11,
-    UnsupportedNamespaceAccess = 12,
-    UnsupportedPropertyAccess = 13,
-    AmbiguousAccess = 14,
-    UnresolvedNameInValue = 15,
-    InvalidPropertyAccess = 16,
-    MissingStructField = 17,
-    UninitialisedMember = 18,
-    InvalidAssignSubject = 19,
-
-    // Type errors
-    TypeMismatch = 20,
-    DisallowedType = 21,
-    UnresolvedType = 22,
-    ValueCannotBeUsedAsType = 24,
-    NonRuntimeInstantiable = 25,
-    UnsupportedImplicitFnApplication = 26,
-    InvalidUnionElement = 28,
-    InvalidIndexSubject = 29,
-    InvalidCallSubject = 30,
-
-    // Errors in regard to parameters and arguments
-    ParameterLengthMismatch = 35,
-    ParameterNameMismatch = 36,
-    ParameterInUse = 37,
-    AmbiguousFieldOrder = 38,
-
-    // traits
-    InvalidMergeElement = 50,
-    MultipleNominals = 51,
-    InvalidPropertyAccessOfNonMethod = 54,
-
-    // Miscellaneous typechecking and semantic errors
-    EnumDiscriminantOverflowed = 60,
-    DuplicateEnumDiscriminant = 61,
-
-    // Pattern errors
-    MismatchingPatBind = 79,
-    DuplicateBindInPat = 80,
-    MissingPatBind = 81,
-    RefutablePat = 82,
-    NonExhaustiveMatch = 83,
-    InvalidRangePatBoundaries = 84,
-
-    // Lexing/Parsing errors
-    InvalidLiteral = 100,
+//! Hash Error code definitions. The documentation of each code is its
+//! extended explanation, which is printed by `hashc explain E0020`.
+
+diagnostic_codes! {
+    /// The code of an error, which is written as `E` followed by the number of
+    /// the code, i.e. `E0020`.
+    HashErrorCode('E') {
+        // Semantic errors
+        /// The condition of an `if` or `while` expression must be of type `bool`,
+        /// there is no implicit conversion from other types to `bool`.
+        ExpectingBooleanInCondition = 1,
+
+        /// A `break` expression was used outside of a loop. A `break` can only be
+        /// used within the body of a `loop`, `while` or `for` loop, and it exits the
+        /// closest loop that it is in.
+        UsingBreakOutsideLoop = 2,
+
+        /// A `continue` expression was used outside of a loop. A `continue` can only
+        /// be used within the body of a `loop`, `while` or `for` loop, and it skips
+        /// to the next iteration of the closest loop that it is in.
+        UsingContinueOutsideLoop = 3,
+
+        /// A `return` expression was used outside of a function. A `return` can only
+        /// be used within the body of a function, and it returns from the closest
+        /// function that it is in.
+        UsingReturnOutsideFn = 4,
+
+        /// An item that was declared as immutable was modified. Declare the item
+        /// with `mut` if it should be modified, i.e. `mut x := 1`.
+        ItemIsImmutable = 5,
+
+        /// An item must be immutable in the position that it was declared in, i.e.
+        /// a declaration at the top-level of a module cannot be `mut`.
+        ItemMustBeImmutable = 6,
+
+        /// A program can only have a single entry point, but more than one function
+        /// was marked as the entry point.
+        MultipleEntryPoints = 7,
+
+        /// The entry point of a program must have the signature `() -> ()` or
+        /// `() -> i32`.
+        InvalidEntryPointSignature = 8,
+
+        /// An operation that may cause undefined behaviour, i.e. dereferencing a
+        /// raw pointer, was used outside of an `unsafe` block. Wrap the operation in
+        /// an `unsafe { ... }` block once it is certain that the operation is sound.
+        UnsafeOperationOutsideUnsafe = 9,
+
+        // Name spacing and symbol errors
+        /// A name was used which could not be found in any of the scopes that are
+        /// visible from where it is used. Check the spelling of the name, and that
+        /// the module that defines it has been imported.
+        UnresolvedSymbol = 10,
+
+        /// An access was made on a subject which does not support accesses, i.e.
+        /// `::` was used on a value which is not a module or a type.
+        UnsupportedAccess = 11,
+
+        /// A namespace access `a::b` was made on a subject which is not a
+        /// namespace, such as a module, `struct` or `enum`.
+        UnsupportedNamespaceAccess = 12,
+
+        /// A property access `a.b` was made on a subject which does not have any
+        /// properties.
+        UnsupportedPropertyAccess = 13,
+
+        /// An access refers to more than one item, and so it is unclear which item
+        /// was meant. Use a more specific path to the item.
+        AmbiguousAccess = 14,
+
+        /// A name was used in a value position, but the name could not be resolved
+        /// to a value in any of the scopes that are visible from there.
+        UnresolvedNameInValue = 15,
+
+        /// A property was accessed on a value whose type does not have a field or
+        /// a method with that name, or a numeric field was too large.
+        InvalidPropertyAccess = 16,
+
+        /// A field that the `struct` declares was not given when the `struct` was
+        /// constructed. Every field without a default value must be given.
+        MissingStructField = 17,
+
+        /// A member of a type was used before it was given a value.
+        UninitialisedMember = 18,
+
+        /// The left-hand side of an assignment is not something that can be
+        /// assigned to. Only variables, fields and indices can be assigned to.
+        InvalidAssignSubject = 19,
+
+        // Type errors
+        /// The type of an expression does not match the type that is expected at
+        /// its position. The expected type usually comes from an annotation, the
+        /// parameters of a function, or from another branch of the same expression.
+        TypeMismatch = 20,
+
+        /// A type was used in a position where it is not allowed, i.e. a type that
+        /// refers to the locals of a block.
+        DisallowedType = 21,
+
+        /// The type of a term could not be inferred from how it is used. Add a type
+        /// annotation to the term so that its type is known.
+        UnresolvedType = 22,
+
+        /// A hole `_` was found in a term. The compiler reports the type that the
+        /// hole is expected to have, so that it can be replaced with a term of that
+        /// type.
+        TypedHole = 23,
+
+        /// A value was used in a position where a type is expected.
+        ValueCannotBeUsedAsType = 24,
+
+        /// A term cannot exist whilst the program is running, i.e. a type or a
+        /// function that is only available at compile-time, but it was used in a
+        /// position that is evaluated at runtime.
+        NonRuntimeInstantiable = 25,
+
+        /// A function was called with the wrong kind of arguments, i.e. with
+        /// implicit `<...>` arguments when the function only accepts explicit
+        /// `(...)` arguments, or the other way around.
+        UnsupportedImplicitFnApplication = 26,
+
+        /// The typechecker could not make any progress whilst checking a term,
+        /// which usually means the term depends on itself. This is likely a bug in
+        /// the compiler.
+        BlockedTypechecking = 27,
+
+        /// A member of a union type is not a type.
+        InvalidUnionElement = 28,
+
+        /// A value was indexed with `[...]`, but the type of the value does not
+        /// support indexing. Only arrays, lists and strings can be indexed.
+        InvalidIndexSubject = 29,
+
+        /// A value was used as a function or dereferenced, but the type of the
+        /// value does not support that operation.
+        InvalidCallSubject = 30,
+
+        // Errors in regard to parameters and arguments
+        /// The number of arguments that were given does not match the number of
+        /// parameters that are expected, i.e. a function with two parameters was
+        /// called with a single argument.
+        ParameterLengthMismatch = 35,
+
+        /// A named argument does not match the name of the parameter in the same
+        /// position.
+        ParameterNameMismatch = 36,
+
+        /// A parameter was given more than one argument, or the same name was used
+        /// for more than one parameter.
+        ParameterInUse = 37,
+
+        /// The order of the fields is ambiguous since named and positional
+        /// arguments are mixed in a way that can't be matched with the parameters.
+        AmbiguousFieldOrder = 38,
+
+        /// A field of a `struct`, tuple or `enum` variant has neither a type
+        /// annotation nor a default value, so its type can't be determined. Give
+        /// the field a type annotation, i.e. `x: i32`.
+        MissingFieldAnnotation = 39,
+
+        /// The fields of a `struct` or `enum` variant must either all be named, or
+        /// all be un-named, i.e. `(x: i32, y: i32)` or `(i32, i32)`.
+        InconsistentFieldNaming = 40,
+
+        // traits
+        /// A merge declaration `~` contained an item which can't be merged.
+        InvalidMergeElement = 50,
+
+        /// A merge declaration `~` contained more than one nominal type, only a
+        /// single nominal type can be merged with other items.
+        MultipleNominals = 51,
+
+        /// A method call `a.b()` was made, but `b` is a field of `a` rather than a
+        /// method.
+        InvalidPropertyAccessOfNonMethod = 54,
+
+        // Miscellaneous typechecking and semantic errors
+        /// The discriminant of an `enum` variant is too large to be represented by
+        /// the type of the discriminant. Use a larger `#repr(...)` integer type, or
+        /// give the variants smaller discriminants.
+        EnumDiscriminantOverflowed = 60,
+
+        /// Two variants of an `enum` were given the same discriminant. Every
+        /// variant must have a distinct discriminant so that they can be told apart.
+        DuplicateEnumDiscriminant = 61,
+
+        /// A place was borrowed whilst another borrow of the same place is still in
+        /// use, and at least one of the borrows is mutable. A place can either have
+        /// a single mutable borrow, or any number of immutable borrows at a time.
+        ConflictingBorrow = 62,
+
+        /// A variable was read on some path of the program before it was given a
+        /// value. Assign a value to the variable on every path before it is used.
+        UninitialisedVariable = 63,
+
+        /// The program does not have an entry point. An executable must declare a
+        /// `main` function, or mark a function with `#entry_point`.
+        MissingEntryPoint = 64,
+
+        /// A `#derive(...)` item could not be derived for a type since one of the
+        /// fields of the type does not support the item. The fields must either be
+        /// primitive types, or types which also derive the item.
+        UnsupportedDerivedField = 65,
+
+        /// The layout of a type could not be computed, i.e. the type is too large
+        /// for the current target, or it depends on a type which doesn't have a
+        /// known layout.
+        UnknownLayout = 66,
+
+        /// A `for` loop iterates over a value which can't be iterated over. A `for`
+        /// loop can only iterate over arrays, slices and ranges of integers.
+        UnsupportedForLoopIterable = 67,
+
+        // Pattern errors
+        /// The alternatives of an `|` pattern must all bind the same names, with
+        /// the same mutability.
+        MismatchingPatBind = 79,
+
+        /// The same name was bound more than once in a single pattern.
+        DuplicateBindInPat = 80,
+
+        /// A name that is bound in one alternative of a pattern is missing from
+        /// another alternative. Each alternative must bind the same names.
+        MissingPatBind = 81,
+
+        /// A pattern that might not match was used in a position where the pattern
+        /// must always match, i.e. in a declaration or a `for` loop. Use a `match`
+        /// to handle the values that aren't covered, or add an `else` block to the
+        /// declaration.
+        RefutablePat = 82,
+
+        /// The cases of a `match` expression don't cover every possible value of the
+        /// subject. Add cases for the missing values, or add a wildcard `_` case.
+        NonExhaustiveMatch = 83,
+
+        /// The lower bound of a range pattern is greater than the upper bound, so
+        /// the pattern can never match.
+        InvalidRangePatBoundaries = 84,
+
+        /// Float literals can't be used in patterns, since floats can't be compared
+        /// exactly.
+        DisallowedFloatPat = 85,
+
+        /// An exclusive range `a..<` must have an end, since there is no value to
+        /// stop before otherwise.
+        MissingRangeEnd = 86,
+
+        // Lexing/Parsing errors
+        /// A numeric literal is malformed, i.e. an integer literal which has a base
+        /// prefix but no digits, or a float literal which has an exponent without
+        /// any digits.
+        InvalidLiteral = 100,
+
+        /// A numeric literal has a suffix which is not a numeric type. Integer
+        /// literals can be suffixed with an integer type, i.e. `1u8`, and float
+        /// literals with `f32` or `f64`.
+        InvalidLiteralSuffix = 101,
+
+        /// A string or character literal was not closed before the end of the line
+        /// or the file.
+        UnclosedLiteral = 102,
+
+        /// A character was found which can't begin any token.
+        UnexpectedCharacter = 103,
+
+        /// An opening delimiter `(`, `[` or `{` does not have a matching closing
+        /// delimiter.
+        UnclosedDelimiter = 104,
+
+        /// A character literal must contain exactly one codepoint. Use a string
+        /// literal `"..."` for text which has more than one character.
+        InvalidCharLiteral = 105,
+
+        /// An escape sequence within a string or a character literal is invalid.
+        /// The supported escape sequences are `\n`, `\r`, `\t`, `\\`, `\0`, `\'`,
+        /// `\"`, the byte escape `\x7F`, and the unicode escape `\u{1F600}`.
+        InvalidEscapeSequence = 106,
+
+        /// A byte literal contained a character which is not ASCII, or a unicode
+        /// escape. Use a `\xHH` escape for bytes which are not ASCII.
+        InvalidByteLiteral = 107,
+
+        /// A token was found where it was not expected, or the input ended early.
+        /// The report lists the tokens that were expected instead.
+        UnexpectedToken = 110,
+
+        /// A `struct` or `enum` definition must be followed by its entries, which
+        /// begin with a `(` or with type parameters `<`.
+        ExpectedTyParams = 111,
+
+        /// A block that begins with a `{` was expected.
+        ExpectedBlock = 112,
+
+        /// A name was expected.
+        ExpectedName = 113,
+
+        /// A macro invocation was expected after a `#`, either a name or a
+        /// bracketed list of invocations, i.e. `#foo` or `#[foo, bar]`.
+        ExpectedMacroInvocation = 114,
+
+        /// An expression was expected.
+        ExpectedExpr = 115,
+
+        /// A function must be followed by its body, which begins with a `=>`.
+        ExpectedFnBody = 116,
+
+        /// A type was expected.
+        ExpectedTy = 117,
+
+        /// A `.` must be followed by the name of a field, or by a method call.
+        ExpectedPropertyAccess = 118,
+
+        /// A pattern was expected.
+        ExpectedPat = 119,
+
+        /// The argument of an `import(...)` must be a string literal which is the
+        /// path of the module, i.e. `import("lib/io")`.
+        InvalidImportPath = 120,
+
+        /// The path of an `import(...)` could not be resolved to a module. The path
+        /// is relative to the file that contains the import.
+        UnresolvedImport = 121,
+
+        /// A spread pattern `...` was malformed, or used in a position where it
+        /// isn't allowed. Spread patterns can be used once in a list, tuple or
+        /// constructor pattern.
+        InvalidSpreadPat = 122,
+
+        /// A suffix was given on a numeric property access, i.e. `x.0u8`. The
+        /// fields of a tuple are accessed without a suffix, i.e. `x.0`.
+        DisallowedSuffix = 123,
+
+        /// A negative literal in a pattern must be written without a space between
+        /// the `-` and the literal, i.e. `-1` rather than `- 1`.
+        UnsupportedExprInPat = 124,
+
+        /// The `...` of a function that accepts a variable number of arguments must
+        /// be the last item in the parameters of the function.
+        MisplacedVariadic = 125,
+
+        // Attribute and expansion errors
+        /// The `#intrinsics` attribute can only be used within the prelude.
+        NonPreludeIntrinsics = 130,
+
+        /// An attribute was applied more than once to the same item with arguments
+        /// that conflict, i.e. `#repr("u8")` and `#repr("u16")`.
+        ConflictingAttributeArgs = 131,
+
+        /// The argument of an attribute is not one of the options of the attribute.
+        /// The report lists the arguments which are valid.
+        UnknownAttributeArg = 132,
+
+        /// The `#repr(...)` of an item is not valid for that item, i.e. an integer
+        /// representation on a `struct`, or an alignment which is not a non-zero
+        /// power of two.
+        InvalidRepr = 133,
+
+        /// `#derive(...)` cannot be applied to a `struct` or `enum` which has
+        /// generic parameters.
+        DeriveOnGenericItem = 134,
+
+        /// The calling convention of a function can't be used on the current
+        /// target, or with a function that accepts a variable number of arguments.
+        UnsupportedConvention = 135,
+
+        /// An attribute was applied which is not one of the built-in attribute
+        /// macros.
+        UnknownAttribute = 136,
+
+        /// The same attribute was applied more than once to an item.
+        DuplicateAttribute = 137,
+
+        /// An attribute was applied to an item that it can't be applied to, i.e. a
+        /// function attribute on a `struct`. The report lists the items that the
+        /// attribute can be applied to.
+        InvalidAttributeSubject = 138,
+
+        /// The arguments of an attribute must be integer, float, character or
+        /// string literals.
+        InvalidAttributeArg = 139,
+
+        // Declaration errors
+        /// A block which only contains declarations, such as a module or a `mod`
+        /// or `impl` block, contained an expression which isn't a declaration. If
+        /// the expression is an assignment `x = 1`, then it was likely meant to be
+        /// a declaration `x := 1`.
+        NonDeclarativeExpression = 140,
+
+        /// A declaration within a function body can't have a visibility modifier,
+        /// only declarations in a module or a `mod` or `impl` block can be `pub`
+        /// or `priv`.
+        IllegalVisibilityModifier = 141,
+
+        /// A declaration in a module or a `mod` or `impl` block can't have an
+        /// `else` block, since the pattern of the declaration must always match.
+        IllegalDeclarationElse = 142,
+
+        /// A `self` parameter can only be used in a function that is declared
+        /// within an `impl` block.
+        SelfInFreeStandingFn = 143,
+
+        /// Only `#foreign` functions can accept a variable number of arguments with
+        /// `...`.
+        NonForeignVariadicFn = 144,
+
+        /// Only `#foreign` functions can be declared without a body. Give the
+        /// function a body with `=> ...`, or declare it within an `#extern("C")`
+        /// module.
+        MissingFnBody = 145,
+
+        /// An `#extern(...)` module can only contain function declarations.
+        NonFnExternMember = 146,
+
+        /// A declaration in a module or a `mod` or `impl` block must have a
+        /// value, only the declarations within a function body can be
+        /// initialised later by an assignment.
+        MissingDeclarationValue = 147,
+
+        /// Only `#foreign` functions can specify the library that they are
+        /// linked against with `#link(...)`.
+        NonForeignLinkedFn = 148,
+    }
 }
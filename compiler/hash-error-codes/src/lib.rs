@@ -1,29 +1,133 @@
 //! Hash Error code library file.
+use std::{fmt, str::FromStr};
 
-macro_rules! error_codes {
-    ($($name:ident = $code:expr),* $(,)?) => (
-        /// Error code macro is used to generate the [HashErrorCode] macro.
-        #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-        #[allow(dead_code)]
-        pub enum HashErrorCode {
-            $($name, )*
-        }
+use error_codes::HashErrorCode;
+use warning_codes::HashWarningCode;
 
-        // This is used to verify that error codes cannot be re-used for error variants.
-        #[allow(dead_code)]
-        enum Dummy {
-            $($name = $code, )*
+macro_rules! diagnostic_codes {
+    ($(#[$attr:meta])* $ty:ident($prefix:literal) {
+        $($(#[doc = $doc:literal])* $name:ident = $code:expr),* $(,)?
+    }) => (
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+        pub enum $ty {
+            $($(#[doc = $doc])* $name, )*
         }
 
-        impl HashErrorCode {
-            #[allow(unused)]
+        // This is used to verify that codes cannot be re-used for variants.
+        const _: () = {
+            #[allow(dead_code)]
+            enum Dummy {
+                $($name = $code, )*
+            }
+        };
+
+        impl $ty {
+            /// All of the codes, in the order that they are defined.
+            pub const ALL: &'static [Self] = &[$(Self::$name, )*];
+
+            /// The prefix that the number of the code is written with.
+            pub const PREFIX: char = $prefix;
+
             pub fn to_num(&self) -> u32 {
                 match self {
                     $(Self::$name => $code, )*
                 }
             }
+
+            /// Get the code with the given number, if there is one.
+            pub fn from_num(num: u32) -> Option<Self> {
+                Self::ALL.iter().copied().find(|code| code.to_num() == num)
+            }
+
+            /// Get the extended explanation of the code, which is printed by
+            /// `hashc explain`.
+            pub fn explanation(&self) -> String {
+                let lines: &[&str] = match self {
+                    $(Self::$name => &[$($doc, )*], )*
+                };
+
+                lines
+                    .iter()
+                    .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+
+        impl ::std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, "{}{:0>4}", Self::PREFIX, self.to_num())
+            }
         }
     )
 }
 
 pub mod error_codes;
+pub mod warning_codes;
+
+/// The code of a diagnostic, which is written as the prefix of the kind of
+/// code and the number of the code, i.e. `E0020` or `W0001`.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum DiagnosticCode {
+    /// The code of an error.
+    Error(HashErrorCode),
+
+    /// The code of a warning.
+    Warning(HashWarningCode),
+}
+
+impl DiagnosticCode {
+    /// Get the extended explanation of the code.
+    pub fn explanation(&self) -> String {
+        match self {
+            DiagnosticCode::Error(code) => code.explanation(),
+            DiagnosticCode::Warning(code) => code.explanation(),
+        }
+    }
+}
+
+impl From<HashErrorCode> for DiagnosticCode {
+    fn from(code: HashErrorCode) -> Self {
+        DiagnosticCode::Error(code)
+    }
+}
+
+impl From<HashWarningCode> for DiagnosticCode {
+    fn from(code: HashWarningCode) -> Self {
+        DiagnosticCode::Warning(code)
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticCode::Error(code) => write!(f, "{code}"),
+            DiagnosticCode::Warning(code) => write!(f, "{code}"),
+        }
+    }
+}
+
+impl FromStr for DiagnosticCode {
+    type Err = String;
+
+    /// Read a code such as `E0020`, the prefix is case insensitive and the
+    /// leading zeroes of the number may be omitted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let prefix = chars.next().map(|prefix| prefix.to_ascii_uppercase());
+        let num = chars.as_str().parse::<u32>().ok();
+
+        let code = match (prefix, num) {
+            (Some(HashErrorCode::PREFIX), Some(num)) => {
+                HashErrorCode::from_num(num).map(DiagnosticCode::Error)
+            }
+            (Some(HashWarningCode::PREFIX), Some(num)) => {
+                HashWarningCode::from_num(num).map(DiagnosticCode::Warning)
+            }
+            _ => None,
+        };
+
+        code.ok_or_else(|| format!("unknown diagnostic code `{s}`"))
+    }
+}
@@ -0,0 +1,66 @@
+//! Hash warning code definitions. The documentation of each code is its
+//! extended explanation, which is printed by `hashc explain W0001`. The level
+//! of each warning can be changed with `-A`, `-W` and `-D`.
+
+diagnostic_codes! {
+    /// The code of a warning, which is written as `W` followed by the number of
+    /// the code, i.e. `W0001`.
+    HashWarningCode('W') {
+        // Parsing warnings
+        /// An expression is wrapped in parentheses which have no effect, i.e.
+        /// `if (x) { ... }`. Remove the parentheses.
+        RedundantParenthesis = 1,
+
+        /// The unary `+` operator has no effect on the expression that it is
+        /// applied to, and can be removed.
+        UselessUnaryOperator = 2,
+
+        /// A statement is followed by more than one semicolon. Only a single
+        /// semicolon is needed.
+        TrailingSemicolons = 3,
+
+        /// A definition has type parameters `<>` without any parameters. Remove
+        /// the `<>`.
+        UselessTyParams = 4,
+
+        /// An ordering comparison is compared for equality without parentheses,
+        /// i.e. `a < b == c`. Unlike a chain of comparisons, this compares the
+        /// result of `a < b` with `c`, add parentheses to make this explicit.
+        UnchainedComparison = 5,
+
+        // Semantic warnings
+        /// An expression in a body block has no effect, since it is a constant with
+        /// no side-effects and its value is not used.
+        UselessExpression = 6,
+
+        /// An attribute has no effect since it was already applied to the item.
+        UnusedAttribute = 7,
+
+        /// A `match` case can never match the subject of the `match`, since the
+        /// type of the pattern does not overlap with the subject.
+        UselessMatchCase = 8,
+
+        /// A pattern can never be reached, since every value that it matches is
+        /// already matched by a pattern before it. Remove the pattern, or move it
+        /// before the patterns that cover it.
+        UnreachablePat = 9,
+
+        /// The end of a range pattern overlaps with the start of another range
+        /// pattern, so the overlapping value is only matched by the first pattern.
+        /// This is likely an off-by-one mistake.
+        OverlappingRangeEnd = 10,
+
+        /// A declaration has an `else` block, but its pattern always matches, so
+        /// the `else` block is never run.
+        IrrefutableDeclarationElse = 11,
+
+        // Lowering warnings
+        /// An `unsafe` block does not contain any unsafe operations, and can be
+        /// removed.
+        UnusedUnsafe = 12,
+
+        /// An operation is known to always panic when the program is run, i.e. an
+        /// assertion whose condition is always false, or a division by zero.
+        UnconditionalPanic = 13,
+    }
+}
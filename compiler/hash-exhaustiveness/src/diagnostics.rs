@@ -3,7 +3,8 @@
 
 use hash_ast::ast::{MatchOrigin, RangeEnd};
 use hash_reporting::{
-    diagnostic::DiagnosticCellStore, hash_error_codes::error_codes::HashErrorCode,
+    diagnostic::DiagnosticCellStore,
+    hash_error_codes::{error_codes::HashErrorCode, warning_codes::HashWarningCode},
     reporter::Reporter,
 };
 use hash_source::location::Span;
@@ -161,6 +162,13 @@ pub enum ExhaustivenessWarning {
         /// The specific term that is overlapping between the two ranges.
         overlapping_term: LitPat,
     },
+
+    /// When a declaration with an `else` block has a pattern that is
+    /// irrefutable, meaning that the `else` block is never run.
+    IrrefutableDeclarationElse {
+        /// The pattern of the declaration.
+        pat: PatId,
+    },
 }
 
 impl ExhaustivenessWarning {
@@ -169,6 +177,7 @@ impl ExhaustivenessWarning {
             ExhaustivenessWarning::UselessMatchCase { pat, location } => {
                 reporter
                     .warning()
+                    .code(HashWarningCode::UselessMatchCase)
                     .title(format!("match case `{pat}` is redundant when matching on subject"))
                     .add_labelled_span(*location, "the match subject is given here...")
                     .add_labelled_span(
@@ -179,12 +188,14 @@ impl ExhaustivenessWarning {
             ExhaustivenessWarning::UnreachablePat { pat } => {
                 reporter
                     .warning()
+                    .code(HashWarningCode::UnreachablePat)
                     .title("pattern is unreachable")
                     .add_labelled_span(pat.span().unwrap(), "");
             }
             ExhaustivenessWarning::OverlappingRangeEnd { range, overlaps, overlapping_term } => {
                 reporter
                     .warning()
+                    .code(HashWarningCode::OverlappingRangeEnd)
                     .title("range pattern has an overlap with another pattern")
                     .add_labelled_span(
                         range.span().unwrap(),
@@ -192,6 +203,16 @@ impl ExhaustivenessWarning {
                     )
                     .add_labelled_span(overlaps.span().unwrap(), "...with this range");
             }
+            ExhaustivenessWarning::IrrefutableDeclarationElse { pat } => {
+                reporter
+                    .warning()
+                    .code(HashWarningCode::IrrefutableDeclarationElse)
+                    .title("irrefutable pattern in declaration with an `else` block")
+                    .add_labelled_span(
+                        pat.span().unwrap(),
+                        "this pattern always matches, so the `else` block is never run",
+                    );
+            }
         }
     }
 }
@@ -7,10 +7,14 @@
 //! [Fields] with the typechecker context available for reading and creating
 //! [DeconstructedPat](super::deconstruct::DeconstructedPat)s.
 
-use hash_storage::store::statics::StoreId;
+use std::ops::ControlFlow;
+
+use hash_storage::store::{statics::StoreId, TrivialSequenceStoreKey};
 use hash_tir::{
     intrinsics::utils::try_use_ty_as_array_ty,
-    tir::{CtorDefId, DataDefCtors, DataTy, NodesId, TupleTy, Ty, TyId},
+    sub::Sub,
+    tir::{CtorDefId, DataDefCtors, DataTy, NodesId, ParamsId, Term, TupleTy, Ty, TyId, VarTerm},
+    visitor::{Atom, Map, Visitor},
 };
 use hash_utils::{
     itertools::Itertools,
@@ -23,6 +27,43 @@ use crate::{
     ExhaustivenessChecker, ExhaustivenessEnv, PatCtx,
 };
 
+/// Get the types of the fields of a constructor of the given [DataTy]. The
+/// parameters of the data definition are substituted with the arguments of
+/// the type, so that the fields of generic data types have concrete types.
+pub(crate) fn ctor_field_tys(data_ty: DataTy, params: ParamsId) -> Vec<TyId> {
+    let DataTy { data_def, args } = data_ty;
+    let sub = Sub::from_pairs(
+        data_def
+            .borrow()
+            .params
+            .iter()
+            .zip(args.iter())
+            .map(|(param, arg)| (param.borrow().name, arg.borrow().value)),
+    );
+
+    params
+        .elements()
+        .borrow()
+        .iter()
+        .map(|param| {
+            if sub.is_empty() {
+                return param.ty;
+            }
+
+            Visitor::new().map(param.ty, |atom| match atom {
+                Atom::Term(term) => match *term.value() {
+                    Term::Var(VarTerm { symbol }) => match sub.get_sub_for(symbol) {
+                        Some(arg) => ControlFlow::Break(Atom::Term(arg)),
+                        None => ControlFlow::Continue(()),
+                    },
+                    _ => ControlFlow::Continue(()),
+                },
+                _ => ControlFlow::Continue(()),
+            })
+        })
+        .collect()
+}
+
 /// Representation of the `fields` that are stored by
 /// [DeconstructedPat](super::deconstruct::DeconstructedPat) which are nested
 /// patterns.
@@ -82,7 +123,7 @@ impl<E: ExhaustivenessEnv> ExhaustivenessChecker<'_, E> {
                             data.elements().borrow().iter().map(|member| member.ty).collect_vec();
                         self.wildcards_from_tys(tys)
                     }
-                    Ty::DataTy(DataTy { data_def, .. }) => {
+                    Ty::DataTy(DataTy { data_def, args }) => {
                         // get the variant index from the deconstructed ctor
                         let variant_idx =
                             if let DeconstructedCtor::Variant(idx) = ctor { *idx } else { 0 };
@@ -94,13 +135,8 @@ impl<E: ExhaustivenessEnv> ExhaustivenessChecker<'_, E> {
                         };
 
                         let ctor = CtorDefId::new(variants_id.elements(), variant_idx).borrow();
-                        let tys = ctor
-                            .params
-                            .elements()
-                            .borrow()
-                            .iter()
-                            .map(|member| member.ty)
-                            .collect_vec();
+                        let data_ty = DataTy { data_def, args };
+                        let tys = ctor_field_tys(data_ty, ctor.params);
 
                         self.wildcards_from_tys(tys)
                     }
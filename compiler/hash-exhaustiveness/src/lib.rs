@@ -238,6 +238,19 @@ impl<'env, E: ExhaustivenessEnv> ExhaustivenessChecker<'env, E> {
             })
         }
     }
+
+    /// Checks whether the given [PatId] of a declaration with an `else` block
+    /// is refutable in terms of the provided [TyId]. If the pattern is
+    /// irrefutable, then the `else` block is never run, which is reported as
+    /// a warning.
+    pub fn is_declaration_pat_refutable(&mut self, pat: PatId, ty: TyId) {
+        let arms = self.lower_pats_to_arms(&[pat], ty);
+        let report = self.compute_match_usefulness(ty, &arms);
+
+        if report.non_exhaustiveness_witnesses.is_empty() {
+            self.diagnostics.add_warning(ExhaustivenessWarning::IrrefutableDeclarationElse { pat })
+        }
+    }
 }
 
 /// Wraps a type `T` to provide access to the [ExhaustivenessChecker] that
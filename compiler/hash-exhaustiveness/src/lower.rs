@@ -24,7 +24,7 @@ use hash_utils::{itertools::Itertools, smallvec::SmallVec};
 use super::{
     construct::DeconstructedCtor,
     deconstruct::DeconstructedPat,
-    fields::Fields,
+    fields::{ctor_field_tys, Fields},
     list::{Array, ArrayKind},
     range::IntRange,
 };
@@ -134,13 +134,14 @@ impl<E: ExhaustivenessEnv> ExhaustivenessChecker<'_, E> {
                 // constructor's type...
                 let fields = self.deconstruct_pat_fields(args, params);
 
-                // Create wild-cards for all of the tuple inner members
-                let mut wilds: SmallVec<[_; 2]> = params
-                    .elements()
-                    .borrow()
-                    .iter()
-                    .map(|param| self.wildcard_from_ty(param.ty))
-                    .collect();
+                // Create wild-cards for all of the constructor fields, the types
+                // of the fields are substituted with the arguments of the type.
+                let field_tys = match *ty_id.value() {
+                    Ty::DataTy(data_ty) => ctor_field_tys(data_ty, params),
+                    _ => params.elements().borrow().iter().map(|param| param.ty).collect(),
+                };
+                let mut wilds: SmallVec<[_; 2]> =
+                    field_tys.into_iter().map(|ty| self.wildcard_from_ty(ty)).collect();
 
                 // For each provided field, we want to recurse and lower
                 // the pattern further
@@ -5,7 +5,7 @@
 //! to the constructor.
 use hash_ast::ast::RangeEnd;
 use hash_storage::store::{statics::StoreId, SequenceStoreKey, TrivialSequenceStoreKey};
-use hash_target::size::Size;
+use hash_target::{size::Size, HasTarget};
 use hash_tir::{
     intrinsics::utils::try_use_ty_as_int_ty,
     tir::{DataDefCtors, DataTy, NumericCtorBits, PrimitiveCtorInfo, Ty},
@@ -93,6 +93,14 @@ impl<E: ExhaustivenessEnv> ExhaustivenessChecker<'_, E> {
                             if let NumericCtorBits::Bounded(bits) = ctor_info.bits
                                 && !ctor_info.flags.is_float()
                             {
+                                // Platform dependent integers are as wide as a
+                                // pointer on the target.
+                                let bits = if ctor_info.flags.is_platform() {
+                                    self.target().ptr_size().bits() as u8
+                                } else {
+                                    bits
+                                };
+
                                 if ctor_info.flags.is_signed() {
                                     let min = 1u128 << (bits - 1);
                                     let max = min - 1;
@@ -0,0 +1,214 @@
+//! A generic dataflow analysis framework over Hash IR [Body]s.
+//!
+//! An analysis is described by implementing the [Analysis] trait, which
+//! specifies the direction of the analysis, the domain of the state that is
+//! tracked at each point of the [Body], and the transfer functions of
+//! statements and terminators. The state of the analysis must form a join
+//! semi-lattice, described by the [JoinSemiLattice] trait, so that the
+//! states of multiple control flow edges can be merged.
+//!
+//! The analysis is then run to a fixed-point with [Results::compute], which
+//! uses a worklist over the [BasicBlock]s of the body. The resulting state is
+//! stored at the granularity of [BasicBlock]s, consumers of the analysis can
+//! compute the state at a particular statement by applying the transfer
+//! functions from the boundary of the block.
+
+pub mod liveness;
+pub mod ranges;
+pub mod stack;
+
+use std::collections::VecDeque;
+
+use fixedbitset::FixedBitSet;
+use hash_ir::{
+    ir::{BasicBlock, BasicBlockData, Body, BodyInfo, IrRef, Statement, Terminator, START_BLOCK},
+    visitor::IrVisitorCtx,
+};
+use hash_utils::index_vec::{index_vec, IndexVec};
+
+/// The direction in which an [Analysis] propagates information through the
+/// control flow graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Information flows from the start of the body towards the exits, i.e.
+    /// the state on entry to a block is the join of the states on exit of
+    /// its predecessors.
+    Forward,
+
+    /// Information flows from the exits of the body towards the start, i.e.
+    /// the state on exit of a block is the join of the states on entry to
+    /// its successors.
+    Backward,
+}
+
+/// A domain that forms a join semi-lattice, the state of an [Analysis] must
+/// implement this so that states from different control flow edges can be
+/// merged.
+pub trait JoinSemiLattice: Clone + Eq {
+    /// Compute the least upper bound of `self` and `other`, storing it in
+    /// `self`. Returns whether `self` changed.
+    fn join(&mut self, other: &Self) -> bool;
+}
+
+impl JoinSemiLattice for FixedBitSet {
+    fn join(&mut self, other: &Self) -> bool {
+        let before = self.count_ones(..);
+        self.union_with(other);
+        self.count_ones(..) != before
+    }
+}
+
+/// A dataflow analysis over a [Body].
+pub trait Analysis {
+    /// The state that is tracked at each point of the body.
+    type Domain: JoinSemiLattice;
+
+    /// The direction of the analysis.
+    const DIRECTION: Direction;
+
+    /// Create the initial state of each block, this is the bottom value of
+    /// the lattice.
+    fn bottom(&self, body: &Body) -> Self::Domain;
+
+    /// Initialise the state on entry to the body. This is only used by
+    /// [Direction::Forward] analyses, and by default the entry state is the
+    /// bottom value.
+    fn initialise_entry(&self, _body: &Body, _state: &mut Self::Domain) {}
+
+    /// Apply the effect of a statement onto the given state.
+    fn apply_statement(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        ctx: &IrVisitorCtx<'_>,
+    );
+
+    /// Apply the effect of a terminator onto the given state.
+    fn apply_terminator(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        ctx: &IrVisitorCtx<'_>,
+    );
+
+    /// Apply the effect of taking the edge from the `terminator` of a block
+    /// to the `target` block, after the effect of the terminator has been
+    /// applied. This is only used by [Direction::Forward] analyses, and by
+    /// default the edges have no effect.
+    fn apply_edge(&self, _state: &mut Self::Domain, _terminator: &Terminator, _target: BasicBlock) {
+    }
+}
+
+/// The results of running an [Analysis] on a [Body] to a fixed-point.
+pub struct Results<A: Analysis> {
+    /// The analysis that produced the results.
+    pub analysis: A,
+
+    /// The state at the boundary of each [BasicBlock] that the analysis
+    /// starts from, i.e. the state on entry to the block for a forward
+    /// analysis, and the state on exit of the block for a backward analysis.
+    entry_sets: IndexVec<BasicBlock, A::Domain>,
+}
+
+impl<A: Analysis> Results<A> {
+    /// Run the given [Analysis] on the [Body] until a fixed-point is reached.
+    pub fn compute(analysis: A, body: &Body) -> Self {
+        let blocks = body.blocks();
+        let info = body.aux();
+
+        let mut entry_sets = index_vec![analysis.bottom(body); blocks.len()];
+
+        if A::DIRECTION == Direction::Forward && !blocks.is_empty() {
+            analysis.initialise_entry(body, &mut entry_sets[START_BLOCK]);
+        }
+
+        // Every block is visited at least once, a backward analysis converges
+        // quicker when the blocks are visited in reverse.
+        let mut worklist: VecDeque<_> = match A::DIRECTION {
+            Direction::Forward => blocks.indices().collect(),
+            Direction::Backward => blocks.indices().rev().collect(),
+        };
+        let mut queued = FixedBitSet::with_capacity(blocks.len());
+        queued.insert_range(..);
+
+        while let Some(block) = worklist.pop_front() {
+            queued.set(block.index(), false);
+
+            let mut state = entry_sets[block].clone();
+            apply_block(&analysis, &mut state, block, &blocks[block], info);
+
+            let mut propagate = |target: BasicBlock, state: &A::Domain| {
+                if entry_sets[target].join(state) && !queued.put(target.index()) {
+                    worklist.push_back(target);
+                }
+            };
+
+            match A::DIRECTION {
+                Direction::Forward => {
+                    let terminator = blocks[block].terminator.as_ref();
+
+                    for target in blocks[block].successors() {
+                        let mut edge_state = state.clone();
+
+                        if let Some(terminator) = terminator {
+                            analysis.apply_edge(&mut edge_state, terminator, target);
+                        }
+
+                        propagate(target, &edge_state);
+                    }
+                }
+                Direction::Backward => {
+                    for &target in body.basic_blocks.predecessors()[block].iter() {
+                        propagate(target, &state);
+                    }
+                }
+            }
+        }
+
+        Self { analysis, entry_sets }
+    }
+
+    /// Get the state at the boundary of the given block that the analysis
+    /// starts from, i.e. the state on entry to the block for a forward
+    /// analysis, and the state on exit of the block for a backward analysis.
+    pub fn entry_set(&self, block: BasicBlock) -> &A::Domain {
+        &self.entry_sets[block]
+    }
+}
+
+/// Apply the effect of the whole [BasicBlock] onto the given state, in
+/// the direction of the analysis.
+pub fn apply_block<A: Analysis>(
+    analysis: &A,
+    state: &mut A::Domain,
+    block: BasicBlock,
+    data: &BasicBlockData,
+    info: BodyInfo<'_>,
+) {
+    let statements = data.statements.len();
+    let terminator = |state: &mut A::Domain| {
+        if let Some(terminator) = &data.terminator {
+            let ctx = IrVisitorCtx::new(IrRef::new(block, statements), info);
+            analysis.apply_terminator(state, terminator, &ctx);
+        }
+    };
+
+    match A::DIRECTION {
+        Direction::Forward => {
+            for (index, statement) in data.statements.iter().enumerate() {
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                analysis.apply_statement(state, statement, &ctx);
+            }
+
+            terminator(state);
+        }
+        Direction::Backward => {
+            terminator(state);
+
+            for (index, statement) in data.statements.iter().enumerate().rev() {
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                analysis.apply_statement(state, statement, &ctx);
+            }
+        }
+    }
+}
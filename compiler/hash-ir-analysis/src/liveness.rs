@@ -0,0 +1,133 @@
+//! Implements a backwards liveness analysis for [Local]s within a [Body].
+//! A [Local] is considered to be "live" at a particular point in the
+//! [Body] if the value that is stored in the local may be read at some
+//! later point in the control flow graph before it is overwritten.
+//!
+//! The analysis is computed at the granularity of [BasicBlock]s, i.e. the
+//! set of live locals on exit of each block is stored. Consumers of the
+//! analysis can then compute the liveness at a particular statement by
+//! applying the [LivenessTransfer] function backwards from the exit
+//! of the block.
+
+use fixedbitset::FixedBitSet;
+use hash_ir::{
+    ir::{
+        BasicBlock, Body, IrRef, Local, Operand, Place, RValue, Statement, Terminator, RETURN_PLACE,
+    },
+    visitor::{IrVisitorCtx, IrVisitorMut, MutablePlaceCtx, PlaceCtx},
+};
+
+use crate::{Analysis, Direction, Results};
+
+/// The liveness analysis of the [Local]s within a [Body].
+pub struct Liveness;
+
+impl Liveness {
+    /// Compute the liveness of all [Local]s within the given [Body].
+    pub fn compute(body: &Body) -> Results<Self> {
+        Results::compute(Self, body)
+    }
+}
+
+impl Analysis for Liveness {
+    type Domain = FixedBitSet;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn bottom(&self, body: &Body) -> Self::Domain {
+        FixedBitSet::with_capacity(body.locals.len())
+    }
+
+    fn apply_statement(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        LivenessTransfer::new(state).visit_statement(statement, ctx);
+    }
+
+    fn apply_terminator(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        LivenessTransfer::new(state).visit_terminator(terminator, ctx);
+    }
+}
+
+impl Results<Liveness> {
+    /// Get the set of [Local]s that are live on the exit of the given block.
+    pub fn live_out(&self, block: BasicBlock) -> FixedBitSet {
+        self.entry_set(block).clone()
+    }
+}
+
+/// The transfer function of the liveness analysis. This will update the
+/// provided set of live locals by applying the effect of a statement or
+/// terminator in reverse, i.e. any locals that are defined are removed
+/// from the set, and any locals that are read are added to the set.
+pub struct LivenessTransfer<'a> {
+    /// The set of live locals that is being updated.
+    live: &'a mut FixedBitSet,
+}
+
+impl<'a> LivenessTransfer<'a> {
+    /// Create a new [LivenessTransfer] function for the given set of live
+    /// locals.
+    pub fn new(live: &'a mut FixedBitSet) -> Self {
+        Self { live }
+    }
+
+    /// Record that the given [Place] is being defined. If the place refers
+    /// to the whole local, then the local is no longer live, otherwise it
+    /// is only a partial definition, and the local remains live.
+    fn define(&mut self, place: &Place, ctx: &IrVisitorCtx<'_>) {
+        match place.as_local() {
+            Some(local) => self.live.set(local.index(), false),
+            None => self.visit_place(place, PlaceCtx::Mutable(MutablePlaceCtx::Store), ctx),
+        }
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for LivenessTransfer<'_> {
+    fn visit_assign_statement(&mut self, place: &Place, value: &RValue, ctx: &IrVisitorCtx<'_>) {
+        // The definition has to be applied before the uses since we are
+        // going backwards.
+        self.define(place, ctx);
+        self.visit_rvalue(value, ctx);
+    }
+
+    fn visit_return_terminator(&mut self, _: &IrVisitorCtx<'_>) {
+        self.live.insert(RETURN_PLACE.index());
+    }
+
+    fn visit_call_terminator(
+        &mut self,
+        op: &Operand,
+        args: &[Operand],
+        destination: &Place,
+        _: Option<BasicBlock>,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        self.define(destination, ctx);
+
+        self.visit_operand(op, ctx);
+        args.iter().for_each(|arg| self.visit_operand(arg, ctx));
+    }
+
+    fn visit_local(&mut self, local: Local, ctx: PlaceCtx, _: IrRef) {
+        match ctx {
+            // Liveness markers don't affect whether the value is read.
+            PlaceCtx::Meta(_) => {}
+
+            // Any other use of the local, a read, a borrow, an index, or a
+            // partial write through a projection is considered to make the
+            // local live.
+            PlaceCtx::Immutable(_) | PlaceCtx::Mutable(_) => {
+                self.live.insert(local.index());
+            }
+        }
+    }
+}
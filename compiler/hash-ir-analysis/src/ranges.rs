@@ -0,0 +1,612 @@
+//! Implements a forward value-range analysis for integral [Local]s within a
+//! [Body]. The analysis computes an [Interval] of the values that each local
+//! may hold at each point of the [Body].
+//!
+//! The ranges are seeded from the [ValidScalarRange] that the layout of the
+//! type of each local has, i.e. a `u8` is within `0..=255`, a `bool` within
+//! `0..=1` and a `char` within `0..=0x10FFFF`. The ranges are then refined by
+//! the constants and operations that are assigned to the locals, and by any
+//! `assume(..)` statements of comparisons, e.g. the `index < len` assumption
+//! that follows a bounds check.
+//!
+//! In order to guarantee that the analysis terminates on loops, the join of
+//! two states widens any bound that grows to be unbounded. Locals that have
+//! their address taken, or that aren't of an integral type are not tracked.
+//!
+//! @@Future: the results of checked operations are stored within a tuple, and
+//! hence they are not tracked.
+
+use hash_ir::{
+    ir::{
+        BinOp, Body, BodyInfo, ConstKind, Local, Operand, Place, RValue, Statement, StatementKind,
+        Terminator, TerminatorKind, UnOp,
+    },
+    ty::{ReprTy, ReprTyId, COMMON_REPR_TYS},
+    visitor::IrVisitorCtx,
+};
+use hash_storage::store::statics::StoreId;
+use hash_target::{abi::ValidScalarRange, size::Size};
+use hash_utils::index_vec::IndexVec;
+
+use crate::{Analysis, Direction, JoinSemiLattice, Results};
+
+/// An inclusive range of integral values, the values of signed types are
+/// stored with their sign, and hence an [Interval] can be compared
+/// irrespective of the signedness of the type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    /// The smallest value within the interval.
+    pub start: i128,
+
+    /// The largest value within the interval.
+    pub end: i128,
+}
+
+impl Interval {
+    /// An interval that is unbounded in both directions.
+    pub const FULL: Interval = Interval { start: i128::MIN, end: i128::MAX };
+
+    /// Create a new [Interval] from the given bounds.
+    pub fn new(start: i128, end: i128) -> Self {
+        debug_assert!(start <= end);
+        Self { start, end }
+    }
+
+    /// Create an [Interval] which only contains the given value.
+    pub fn singleton(value: i128) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Get the value of the [Interval] if it only contains a single value.
+    pub fn as_singleton(&self) -> Option<i128> {
+        (self.start == self.end).then_some(self.start)
+    }
+
+    /// Check if the given value is contained within the [Interval].
+    pub fn contains(&self, value: i128) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Check if the [Interval] is entirely contained within `other`.
+    pub fn is_subset_of(&self, other: Interval) -> bool {
+        other.start <= self.start && self.end <= other.end
+    }
+
+    /// Compute the intersection of two [Interval]s, if the intersection is
+    /// empty then [None] is returned.
+    pub fn intersect(&self, other: Interval) -> Option<Interval> {
+        let (start, end) = (self.start.max(other.start), self.end.min(other.end));
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// Compute the smallest [Interval] that contains both intervals.
+    pub fn hull(&self, other: Interval) -> Interval {
+        Interval { start: self.start.min(other.start), end: self.end.max(other.end) }
+    }
+
+    /// Widen the [Interval] with `other`, any bound that would grow by
+    /// computing the [Interval::hull] is made unbounded instead.
+    fn widen(&self, other: Interval) -> Interval {
+        Interval {
+            start: if other.start < self.start { i128::MIN } else { self.start },
+            end: if other.end > self.end { i128::MAX } else { self.end },
+        }
+    }
+
+    /// Convert a [ValidScalarRange] of a scalar with the given [Size] and
+    /// signedness into an [Interval]. If the range can't be represented as
+    /// an [Interval], i.e. it wraps around the bounds of the type, or the
+    /// values don't fit within an [i128], then [None] is returned.
+    pub fn from_valid_range(range: ValidScalarRange, size: Size, signed: bool) -> Option<Self> {
+        if signed {
+            if range.is_full_for(size) {
+                return Some(Interval::new(size.signed_int_min(), size.signed_int_max()));
+            }
+
+            let start = size.sign_extend(range.start) as i128;
+            let end = size.sign_extend(range.end) as i128;
+            (start <= end).then_some(Interval { start, end })
+        } else {
+            let start = i128::try_from(range.start).ok()?;
+            let end = i128::try_from(range.end).ok()?;
+            (range.start <= range.end).then_some(Interval { start, end })
+        }
+    }
+
+    /// Compute the [Interval] of all the valid values for the given type,
+    /// this is only defined for integral types, `bool` and `char`.
+    pub fn of_ty(ty: ReprTyId, ptr_size: Size) -> Option<Self> {
+        let (size, signed) = scalar_info(ty, ptr_size)?;
+
+        let range = ty.map(|ty| match ty {
+            ReprTy::Bool => ValidScalarRange { start: 0, end: 1 },
+            ReprTy::Char => ValidScalarRange { start: 0, end: char::MAX as u128 },
+            _ => ValidScalarRange::full(size),
+        });
+
+        Self::from_valid_range(range, size, signed)
+    }
+
+    /// Compute the [Interval] of the result of the given binary operation,
+    /// the result is [None] if nothing is known about the result.
+    fn binary_op(op: BinOp, lhs: Interval, rhs: Interval) -> Option<Interval> {
+        let bounds = |f: fn(i128, i128) -> Option<i128>| {
+            let values = [
+                f(lhs.start, rhs.start)?,
+                f(lhs.start, rhs.end)?,
+                f(lhs.end, rhs.start)?,
+                f(lhs.end, rhs.end)?,
+            ];
+
+            Some(Interval::new(*values.iter().min()?, *values.iter().max()?))
+        };
+
+        match op {
+            BinOp::Add => Some(Interval::new(
+                lhs.start.checked_add(rhs.start)?,
+                lhs.end.checked_add(rhs.end)?,
+            )),
+            BinOp::Sub => Some(Interval::new(
+                lhs.start.checked_sub(rhs.end)?,
+                lhs.end.checked_sub(rhs.start)?,
+            )),
+            BinOp::Mul => bounds(i128::checked_mul),
+            BinOp::BitAnd if lhs.start >= 0 && rhs.start >= 0 => {
+                Some(Interval::new(0, lhs.end.min(rhs.end)))
+            }
+            BinOp::Mod if lhs.start >= 0 && rhs.start > 0 => {
+                Some(Interval::new(0, lhs.end.min(rhs.end - 1)))
+            }
+            BinOp::Div if lhs.start >= 0 && rhs.start > 0 => {
+                Some(Interval::new(lhs.start / rhs.end, lhs.end / rhs.start))
+            }
+            op if op.is_comparator() => Some(match compare(op, lhs, rhs) {
+                Some(value) => Interval::singleton(value as i128),
+                None => Interval::new(0, 1),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Compute the result of the comparison `lhs op rhs` if the result is the
+/// same for all of the values within the [Interval]s.
+pub fn compare(op: BinOp, lhs: Interval, rhs: Interval) -> Option<bool> {
+    match op {
+        BinOp::Lt if lhs.end < rhs.start => Some(true),
+        BinOp::Lt if lhs.start >= rhs.end => Some(false),
+        BinOp::LtEq if lhs.end <= rhs.start => Some(true),
+        BinOp::LtEq if lhs.start > rhs.end => Some(false),
+        BinOp::Gt => compare(BinOp::Lt, rhs, lhs),
+        BinOp::GtEq => compare(BinOp::LtEq, rhs, lhs),
+        BinOp::Eq | BinOp::Neq => {
+            let equal = match (lhs.as_singleton(), rhs.as_singleton()) {
+                (Some(lhs), Some(rhs)) if lhs == rhs => Some(true),
+                _ if lhs.intersect(rhs).is_none() => Some(false),
+                _ => None,
+            }?;
+
+            Some(if op == BinOp::Eq { equal } else { !equal })
+        }
+        _ => None,
+    }
+}
+
+/// Get the [Size] and signedness of the scalar that represents the given
+/// type, if the type is integral, a `bool` or a `char`.
+fn scalar_info(ty: ReprTyId, ptr_size: Size) -> Option<(Size, bool)> {
+    ty.map(|ty| match ty {
+        ReprTy::Bool => Some((Size::from_bytes(1), false)),
+        ReprTy::Char => Some((Size::from_bytes(4), false)),
+        ReprTy::Int(ty) => Some((ty.size(ptr_size), true)),
+        ReprTy::UInt(ty) => Some((ty.size(ptr_size), false)),
+        _ => None,
+    })
+}
+
+/// A value that is used within a recorded comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Value {
+    /// A tracked [Local].
+    Local(Local),
+
+    /// A known constant value.
+    Const(i128),
+}
+
+/// A comparison that is stored within a boolean [Local], this is used to
+/// refine the ranges of the operands when the [Local] is assumed to be true.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Condition {
+    op: BinOp,
+    lhs: Value,
+    rhs: Value,
+}
+
+impl Condition {
+    /// Check if the [Condition] refers to the given [Local].
+    fn mentions(&self, local: Local) -> bool {
+        self.lhs == Value::Local(local) || self.rhs == Value::Local(local)
+    }
+}
+
+/// The state of the [ValueRangeAnalysis] at a point of the [Body].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeState {
+    /// Whether the point is reachable, the bottom value of the lattice is an
+    /// unreachable state.
+    reachable: bool,
+
+    /// The known ranges of each [Local], the range must be intersected with
+    /// the range of the type of the [Local] before it is used.
+    ranges: IndexVec<Local, Interval>,
+
+    /// The comparisons that are stored within each boolean [Local].
+    conditions: IndexVec<Local, Option<Condition>>,
+}
+
+impl RangeState {
+    /// Check whether the point of the state is reachable.
+    pub fn is_reachable(&self) -> bool {
+        self.reachable
+    }
+
+    /// Forget everything that is known about the given [Local].
+    fn kill(&mut self, local: Local) {
+        self.ranges[local] = Interval::FULL;
+
+        for condition in self.conditions.iter_mut() {
+            if condition.is_some_and(|condition| condition.mentions(local)) {
+                *condition = None;
+            }
+        }
+
+        self.conditions[local] = None;
+    }
+}
+
+impl JoinSemiLattice for RangeState {
+    fn join(&mut self, other: &Self) -> bool {
+        if !other.reachable {
+            return false;
+        }
+
+        if !self.reachable {
+            *self = other.clone();
+            return true;
+        }
+
+        let mut changed = false;
+
+        for (range, other) in self.ranges.iter_mut().zip(other.ranges.iter()) {
+            let widened = range.widen(*other);
+            changed |= widened != *range;
+            *range = widened;
+        }
+
+        for (condition, other) in self.conditions.iter_mut().zip(other.conditions.iter()) {
+            if condition.is_some() && condition != other {
+                *condition = None;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+/// The value-range analysis of the integral [Local]s within a [Body].
+pub struct ValueRangeAnalysis {
+    /// The size of a pointer on the target, this is needed to compute the
+    /// ranges of `usize` and `isize`.
+    ptr_size: Size,
+
+    /// The range of the type of each [Local], this is [None] if the [Local]
+    /// isn't tracked by the analysis.
+    ty_ranges: IndexVec<Local, Option<Interval>>,
+}
+
+impl ValueRangeAnalysis {
+    /// Create a new [ValueRangeAnalysis] for the given [Body].
+    pub fn new(body: &Body, ptr_size: Size) -> Self {
+        let mut ty_ranges: IndexVec<Local, _> =
+            body.locals.iter().map(|decl| Interval::of_ty(decl.ty(), ptr_size)).collect();
+
+        // Any local that has its address taken may be modified through the
+        // reference, so it can't be tracked.
+        for data in body.blocks().iter() {
+            for statement in data.statements.iter() {
+                if let StatementKind::Assign(_, RValue::Ref(_, place, _)) = statement.kind {
+                    ty_ranges[place.local] = None;
+                }
+            }
+        }
+
+        Self { ptr_size, ty_ranges }
+    }
+
+    /// Compute the value ranges of all of the tracked [Local]s within the
+    /// given [Body].
+    pub fn compute(body: &Body, ptr_size: Size) -> Results<Self> {
+        Results::compute(Self::new(body, ptr_size), body)
+    }
+
+    /// Get the range of the given [Local] in the given state, this is [None]
+    /// if the [Local] isn't tracked.
+    pub fn range_of(&self, state: &RangeState, local: Local) -> Option<Interval> {
+        let ty_range = self.ty_ranges[local]?;
+        Some(state.ranges[local].intersect(ty_range).unwrap_or(ty_range))
+    }
+
+    /// Get the range of the given [Operand] in the given state, this is [None]
+    /// if nothing is known about the value of the operand.
+    pub fn range_of_operand(
+        &self,
+        state: &RangeState,
+        operand: &Operand,
+        info: &BodyInfo<'_>,
+    ) -> Option<Interval> {
+        match operand {
+            Operand::Const(constant) => {
+                let ConstKind::Scalar(scalar) = constant.kind() else {
+                    return None;
+                };
+
+                let (size, signed) = scalar_info(constant.ty(), self.ptr_size)?;
+                let bits = scalar.to_bits(size).ok()?;
+                Self::value_from_bits(bits, size, signed).map(Interval::singleton)
+            }
+            Operand::Place(place) => match place.as_local() {
+                Some(local) if self.ty_ranges[local].is_some() => self.range_of(state, local),
+                _ => Interval::of_ty(place.ty(info), self.ptr_size),
+            },
+        }
+    }
+
+    /// Compute the result of the comparison `lhs op rhs` in the given state,
+    /// if the result is known.
+    pub fn evaluate_comparison(
+        &self,
+        state: &RangeState,
+        op: BinOp,
+        lhs: &Operand,
+        rhs: &Operand,
+        info: &BodyInfo<'_>,
+    ) -> Option<bool> {
+        let lhs = self.range_of_operand(state, lhs, info)?;
+        let rhs = self.range_of_operand(state, rhs, info)?;
+        compare(op, lhs, rhs)
+    }
+
+    /// Convert the raw value of a `switch` target into the value that is used
+    /// by the [Interval]s of the given type.
+    pub fn switch_value(&self, value: u128, ty: ReprTyId) -> Option<i128> {
+        let (size, signed) = scalar_info(ty, self.ptr_size)?;
+        Self::value_from_bits(value, size, signed)
+    }
+
+    fn value_from_bits(bits: u128, size: Size, signed: bool) -> Option<i128> {
+        if signed {
+            Some(size.sign_extend(bits) as i128)
+        } else {
+            i128::try_from(bits).ok()
+        }
+    }
+
+    /// Compute the range of the given [RValue], if the result is [None] then
+    /// the range of the type of the destination is used.
+    fn range_of_rvalue(
+        &self,
+        state: &RangeState,
+        rvalue: &RValue,
+        info: &BodyInfo<'_>,
+    ) -> Option<Interval> {
+        match rvalue {
+            RValue::Use(operand) => self.range_of_operand(state, operand, info),
+            RValue::BinaryOp(op, operands) => {
+                let (lhs, rhs) = operands.as_ref();
+                let lhs = self.range_of_operand(state, lhs, info)?;
+                let rhs = self.range_of_operand(state, rhs, info)?;
+                Interval::binary_op(*op, lhs, rhs)
+            }
+            RValue::UnaryOp(op, operand) => {
+                let range = self.range_of_operand(state, operand, info)?;
+
+                match op {
+                    UnOp::Not if operand.ty(info) == COMMON_REPR_TYS.bool => {
+                        Some(Interval::new(1 - range.end, 1 - range.start))
+                    }
+                    UnOp::Neg => {
+                        Some(Interval::new(range.end.checked_neg()?, range.start.checked_neg()?))
+                    }
+                    _ => None,
+                }
+            }
+            RValue::Cast(_, operand, _) => self.range_of_operand(state, operand, info),
+            RValue::Len(place) => place.ty(info).map(|ty| match ty {
+                ReprTy::Array { length, .. } => Some(Interval::singleton(*length as i128)),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Record that the given [Local] has been assigned the value of `rvalue`.
+    fn assign(&self, state: &mut RangeState, local: Local, rvalue: &RValue, info: &BodyInfo<'_>) {
+        let Some(ty_range) = self.ty_ranges[local] else {
+            return;
+        };
+
+        // The range must be computed before the assignment takes effect since
+        // the value may refer to the local itself.
+        let range = self.range_of_rvalue(state, rvalue, info);
+        let condition = match rvalue {
+            RValue::BinaryOp(op, operands) if op.is_comparator() => {
+                let (lhs, rhs) = operands.as_ref();
+                self.value_of(lhs).zip(self.value_of(rhs)).map(|(lhs, rhs)| Condition {
+                    op: *op,
+                    lhs,
+                    rhs,
+                })
+            }
+            _ => None,
+        };
+
+        state.kill(local);
+
+        // If the value may not fit within the type, e.g. the operation of
+        // the value might have wrapped around, then nothing is known.
+        state.ranges[local] = match range {
+            Some(range) if range.is_subset_of(ty_range) => range,
+            _ => ty_range,
+        };
+
+        // A condition that refers to the local itself is no longer valid
+        // after the assignment.
+        state.conditions[local] = condition.filter(|condition| !condition.mentions(local));
+    }
+
+    /// Convert an [Operand] into a [Value] which can be recorded within a
+    /// [Condition].
+    fn value_of(&self, operand: &Operand) -> Option<Value> {
+        match operand {
+            Operand::Place(place) => {
+                let local = place.as_local()?;
+                self.ty_ranges[local].map(|_| Value::Local(local))
+            }
+            Operand::Const(constant) => {
+                let ConstKind::Scalar(scalar) = constant.kind() else {
+                    return None;
+                };
+
+                let (size, signed) = scalar_info(constant.ty(), self.ptr_size)?;
+                Self::value_from_bits(scalar.to_bits(size).ok()?, size, signed).map(Value::Const)
+            }
+        }
+    }
+
+    /// Refine the state with the knowledge that the given [Condition] holds.
+    fn assume(&self, state: &mut RangeState, condition: Condition) {
+        let range = |state: &RangeState, value: Value| match value {
+            Value::Local(local) => self.range_of(state, local),
+            Value::Const(value) => Some(Interval::singleton(value)),
+        };
+
+        let (Some(lhs), Some(rhs)) = (range(state, condition.lhs), range(state, condition.rhs))
+        else {
+            return;
+        };
+
+        // Compute the refined ranges of the operands, `lhs` and `rhs` are
+        // swapped for `>` and `>=` so that only `<` and `<=` are handled.
+        let refined = match condition.op {
+            BinOp::Lt | BinOp::LtEq | BinOp::Gt | BinOp::GtEq => {
+                let strict = i128::from(matches!(condition.op, BinOp::Lt | BinOp::Gt));
+                let swapped = matches!(condition.op, BinOp::Gt | BinOp::GtEq);
+                let (lhs, rhs) = if swapped { (rhs, lhs) } else { (lhs, rhs) };
+
+                let new_lhs = rhs
+                    .end
+                    .checked_sub(strict)
+                    .and_then(|end| lhs.intersect(Interval { start: i128::MIN, end }));
+                let new_rhs = lhs
+                    .start
+                    .checked_add(strict)
+                    .and_then(|start| rhs.intersect(Interval { start, end: i128::MAX }));
+
+                new_lhs.zip(new_rhs).map(|(l, r)| if swapped { (r, l) } else { (l, r) })
+            }
+            BinOp::Eq => lhs.intersect(rhs).map(|range| (range, range)),
+            BinOp::Neq => {
+                // Only the bounds of an interval can be removed when the other
+                // side is a known value.
+                let exclude = |range: Interval, other: Interval| match other.as_singleton() {
+                    Some(value) if range.as_singleton() == Some(value) => None,
+                    Some(value) if range.start == value => {
+                        Some(Interval::new(value + 1, range.end))
+                    }
+                    Some(value) if range.end == value => {
+                        Some(Interval::new(range.start, value - 1))
+                    }
+                    _ => Some(range),
+                };
+
+                exclude(lhs, rhs).zip(exclude(rhs, lhs))
+            }
+            _ => return,
+        };
+
+        // If the assumption can never hold, then the point is unreachable.
+        let Some((lhs, rhs)) = refined else {
+            state.reachable = false;
+            return;
+        };
+
+        for (value, range) in [(condition.lhs, lhs), (condition.rhs, rhs)] {
+            if let Value::Local(local) = value {
+                state.ranges[local] = range;
+            }
+        }
+    }
+}
+
+impl Analysis for ValueRangeAnalysis {
+    type Domain = RangeState;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom(&self, body: &Body) -> Self::Domain {
+        RangeState {
+            reachable: false,
+            ranges: body.locals.iter().map(|_| Interval::FULL).collect(),
+            conditions: body.locals.iter().map(|_| None).collect(),
+        }
+    }
+
+    fn initialise_entry(&self, _: &Body, state: &mut Self::Domain) {
+        state.reachable = true;
+    }
+
+    fn apply_statement(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        if !state.reachable {
+            return;
+        }
+
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => match place.as_local() {
+                Some(local) => self.assign(state, local, rvalue, &ctx.info),
+                None => state.kill(place.local),
+            },
+            StatementKind::Live(local) | StatementKind::Dead(local) => state.kill(*local),
+            StatementKind::Assume(Operand::Place(place)) => {
+                if let Some(local) = place.as_local() {
+                    if let Some(condition) = state.conditions[local] {
+                        self.assume(state, condition);
+                    }
+
+                    // The assumed value is known to be `true`.
+                    if state.reachable && self.ty_ranges[local].is_some() {
+                        state.ranges[local] = Interval::singleton(1);
+                    }
+                }
+            }
+            StatementKind::Nop | StatementKind::Discriminate(..) | StatementKind::Assume(_) => {}
+        }
+    }
+
+    fn apply_terminator(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        _: &IrVisitorCtx<'_>,
+    ) {
+        if let TerminatorKind::Call { destination: Place { local, .. }, .. } = terminator.kind {
+            state.kill(local);
+        }
+    }
+}
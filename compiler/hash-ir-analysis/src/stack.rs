@@ -0,0 +1,146 @@
+//! Implements an estimate of the stack usage of the functions within a
+//! [CallGraph]. The maximum depth of the stack whilst a function is executing
+//! is its own frame, plus the deepest stack of any function that it calls.
+//!
+//! The estimate is a lower bound, the frames of functions that don't have a
+//! lowered body (i.e. foreign functions) and the callees of calls through
+//! function pointers are not known, and calls within a recursive cycle are
+//! not followed. The depths of functions that might reach any of these are
+//! marked as being incomplete, or recursive respectively.
+
+use hash_ir::call_graph::{CallGraph, CallGraphEdgeKind, CallGraphKey};
+use hash_storage::store::statics::StoreId;
+use hash_target::size::Size;
+
+/// The estimated stack usage of a node within the [CallGraph].
+#[derive(Debug, Clone, Default)]
+pub struct StackDepth {
+    /// The size of the frame of the node itself, this is [None] if the size
+    /// of the frame is not known.
+    pub frame: Option<Size>,
+
+    /// The maximum depth of the stack whilst the node is executing, including
+    /// the frame of the node.
+    pub depth: Size,
+
+    /// The chain of calls that reaches the maximum depth, starting with the
+    /// node itself.
+    pub path: Vec<usize>,
+
+    /// Whether the node might reach a recursive cycle, in which case the depth
+    /// of the stack is unbounded.
+    pub recursive: bool,
+
+    /// Whether the node might reach a function whose frame is not known.
+    pub incomplete: bool,
+}
+
+/// The estimated stack usage of all of the nodes within a [CallGraph].
+pub struct StackUsage {
+    /// The depth of each node within the graph.
+    depths: Vec<StackDepth>,
+
+    /// All of the recursive cycles within the graph.
+    cycles: Vec<Vec<usize>>,
+}
+
+impl StackUsage {
+    /// Estimate the stack usage of all of the nodes within the given graph,
+    /// `frame_of` computes the size of the frame of a lowered body from the
+    /// index of the body, i.e. the [`CallGraphNode::body`].
+    ///
+    /// [`CallGraphNode::body`]: hash_ir::call_graph::CallGraphNode::body
+    pub fn compute(graph: &CallGraph, frame_of: impl Fn(usize) -> Size) -> Self {
+        let node_count = graph.nodes().count();
+        let components = graph.components();
+
+        let mut component_of = vec![0; node_count];
+        for (index, component) in components.iter().enumerate() {
+            for &node in component {
+                component_of[node] = index;
+            }
+        }
+
+        // Only direct calls add to the depth of the stack, references are
+        // accounted for by the calls through function pointers.
+        let mut calls = vec![vec![]; node_count];
+        for edge in graph.edges() {
+            if edge.kind == CallGraphEdgeKind::Call {
+                calls[edge.caller].push(edge.callee);
+            }
+        }
+
+        let mut depths = vec![StackDepth::default(); node_count];
+        let mut cycles = vec![];
+
+        // The components are in reverse topological order, so the depths of
+        // all of the callees outside of a component are known before it.
+        for (index, component) in components.iter().enumerate() {
+            let recursive = graph.is_recursive(component);
+
+            if recursive {
+                cycles.push(component.clone());
+            }
+
+            for &node in component {
+                let frame = match (graph.node(node).body, graph.node(node).key) {
+                    (Some(body), _) => Some(frame_of(body)),
+                    // Intrinsics are expanded at the call site, and so they
+                    // don't have their own frame.
+                    (None, CallGraphKey::Instance(instance))
+                        if instance.borrow().is_intrinsic() =>
+                    {
+                        Some(Size::ZERO)
+                    }
+                    (None, _) => None,
+                };
+
+                let mut depth = StackDepth {
+                    frame,
+                    depth: frame.unwrap_or(Size::ZERO),
+                    path: vec![node],
+                    recursive,
+                    incomplete: frame.is_none(),
+                };
+
+                let mut deepest: Option<&StackDepth> = None;
+
+                for &callee in &calls[node] {
+                    // Calls within the same component are recursive, and so
+                    // they aren't followed.
+                    if component_of[callee] == index {
+                        continue;
+                    }
+
+                    let callee = &depths[callee];
+                    depth.recursive |= callee.recursive;
+                    depth.incomplete |= callee.incomplete;
+
+                    if deepest.map_or(true, |deepest| callee.depth > deepest.depth) {
+                        deepest = Some(callee);
+                    }
+                }
+
+                if let Some(deepest) = deepest {
+                    depth.depth = depth.depth + deepest.depth;
+                    depth.path.extend_from_slice(&deepest.path);
+                }
+
+                depths[node] = depth;
+            }
+        }
+
+        Self { depths, cycles }
+    }
+
+    /// Get the estimated stack usage of the node at the given index.
+    pub fn depth(&self, index: usize) -> &StackDepth {
+        &self.depths[index]
+    }
+
+    /// Get all of the recursive cycles within the graph, each cycle is made of
+    /// nodes that can all (transitively) call each other.
+    pub fn cycles(&self) -> &[Vec<usize>] {
+        &self.cycles
+    }
+}
@@ -0,0 +1,75 @@
+//! Pre-evaluation of calls whose arguments are all constants. If the
+//! [Interpreter] can evaluate the callee with the arguments into a constant,
+//! then the call is replaced by an assignment of the constant to the
+//! destination of the call.
+
+use hash_ir::{
+    ir::{Body, Operand, Statement, StatementKind, TerminatorKind},
+    ty::ReprTy,
+};
+use hash_repr::compute::LayoutComputer;
+use hash_storage::store::statics::StoreId;
+use hash_target::Target;
+
+use crate::{Interpreter, Value};
+
+/// Pre-evaluate all of the calls with constant arguments within the `bodies`
+/// that are accepted by `should_evaluate`. Calls that can't be evaluated, i.e.
+/// ones that fail an assertion, or that reach an intrinsic, are left as they
+/// are so that they still happen at runtime.
+pub fn pre_evaluate_calls(
+    bodies: &mut [Body],
+    lc: LayoutComputer<'_>,
+    target: &Target,
+    should_evaluate: impl Fn(&Body) -> bool,
+) {
+    let mut replacements = vec![];
+    let mut interpreter = Interpreter::new(bodies, lc, target);
+
+    for (index, body) in bodies.iter().enumerate() {
+        if !should_evaluate(body) {
+            continue;
+        }
+
+        let info = body.aux();
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            let TerminatorKind::Call { op, args, destination, target: Some(target), .. } =
+                &data.terminator().kind
+            else {
+                continue;
+            };
+
+            let ReprTy::FnDef { instance } = *op.ty(&info).borrow() else {
+                continue;
+            };
+
+            let Some(args) = args
+                .iter()
+                .map(|arg| match arg {
+                    Operand::Const(value) => Some(Value::Const(*value)),
+                    Operand::Place(_) => None,
+                })
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+
+            if let Ok(value) = interpreter.call(instance, args)
+                && let Some(value) = value.as_const()
+            {
+                replacements.push((index, block, *destination, value, *target));
+            }
+        }
+    }
+
+    for (index, block, destination, value, target) in replacements {
+        let data = &mut bodies[index].basic_blocks.blocks_mut()[block];
+        let origin = data.terminator().origin;
+
+        data.statements
+            .push(Statement { kind: StatementKind::Assign(destination, value.into()), origin });
+
+        data.terminator_mut().kind = TerminatorKind::Goto(target);
+    }
+}
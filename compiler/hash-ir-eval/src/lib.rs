@@ -0,0 +1,795 @@
+//! A compile-time interpreter for Hash IR. The [Interpreter] executes the
+//! lowered [Body] of a function with constant arguments, interpreting each
+//! statement and terminator of the body, and following calls into the bodies
+//! of other functions. This is the IR counterpart of the [ConstFolder], which
+//! can only fold a single operation on constant operands.
+//!
+//! Arithmetic follows the semantics that are described in
+//! [hash_target::overflow], checked operations produce an overflow flag that
+//! is verified by the `assert` terminators of the body, and a failed assertion
+//! stops the evaluation. Memory is modelled by the locals of the frames on the
+//! call stack, references point to a location within a local, and constants
+//! that are backed by an allocation are kept as they are.
+//!
+//! Anything that can't be evaluated at compile time, i.e. calls to intrinsics
+//! and foreign functions, stops the evaluation with an
+//! [`EvalError::Unsupported`] error. The evaluation is also stopped when it
+//! runs out of fuel, so that evaluating a function that doesn't terminate
+//! doesn't stall the compiler.
+//!
+//! @@Future: constant initialisers and array lengths are evaluated by the
+//! typechecker, and so they don't have a lowered body for the interpreter to
+//! evaluate. Once they are lowered, they should be evaluated here.
+#![feature(let_chains)]
+
+mod calls;
+mod value;
+
+use std::fmt;
+
+pub use calls::pre_evaluate_calls;
+use hash_const_eval::eval::ConstFolder;
+use hash_ir::{
+    cast::CastKind,
+    ir::{
+        AggregateKind, AssertKind, BasicBlock, BinOp, Body, Const, ConstKind, ConstOp, Local,
+        Operand, Place, PlaceProjection, RValue, Scalar, Statement, StatementKind, Terminator,
+        TerminatorKind, RETURN_PLACE, START_BLOCK,
+    },
+};
+use hash_repr::{
+    compute::LayoutComputer,
+    ty::{InstanceId, ReprTy, ReprTyId},
+};
+use hash_source::FloatTy;
+use hash_storage::store::statics::StoreId;
+use hash_target::{overflow::ShiftAmountSemantics, size::Size, Target};
+use hash_utils::{
+    fxhash::FxHashMap,
+    index_vec::{index_vec, IndexVec},
+};
+use value::{Aggregate, Pointer};
+pub use value::{Step, Value};
+
+/// The number of statements and terminators that a single evaluation may
+/// execute before it is abandoned.
+const FUEL: usize = 1_000_000;
+
+/// The maximum depth of the call stack of a single evaluation.
+const MAX_FRAMES: usize = 256;
+
+/// The reason why an evaluation was stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The evaluation reached an operation that can't be performed at
+    /// compile time.
+    Unsupported(&'static str),
+
+    /// The evaluation reached an operation whose behaviour is undefined.
+    Undefined(&'static str),
+
+    /// An `assert` terminator failed, i.e. an operation overflowed.
+    AssertFailed(AssertKind),
+
+    /// The evaluation executed more than [FUEL] statements and terminators.
+    OutOfFuel,
+
+    /// The call stack grew deeper than [MAX_FRAMES].
+    StackOverflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Unsupported(reason) => write!(f, "unsupported operation: {reason}"),
+            EvalError::Undefined(reason) => write!(f, "undefined behaviour: {reason}"),
+            EvalError::AssertFailed(kind) => {
+                write!(f, "assertion failed: {}", kind.message().trim_end())
+            }
+            EvalError::OutOfFuel => write!(f, "evaluation took too long"),
+            EvalError::StackOverflow => write!(f, "evaluation overflowed the stack"),
+        }
+    }
+}
+
+pub type EvalResult<T> = Result<T, EvalError>;
+
+/// The state of a function call that is being evaluated.
+struct Frame {
+    /// A unique identifier of the frame, this is used by [Pointer]s to refer
+    /// to the frame.
+    id: usize,
+
+    /// The index of the [Body] that is being evaluated.
+    body: usize,
+
+    /// The values of the locals of the body.
+    locals: IndexVec<Local, Value>,
+
+    /// The block that is being evaluated.
+    block: BasicBlock,
+
+    /// The index of the next statement within the block.
+    statement: usize,
+
+    /// Where the returned value is written to within the caller, and the
+    /// block that the caller continues from. This is [None] for the frame
+    /// that the evaluation started from.
+    ret: Option<(Pointer, Option<BasicBlock>)>,
+}
+
+/// The interpreter, it can evaluate calls to all of the functions that have
+/// a lowered [Body].
+pub struct Interpreter<'ir> {
+    /// All of the lowered bodies.
+    bodies: &'ir [Body],
+
+    /// A map from a function to the index of its [Body].
+    instances: FxHashMap<InstanceId, usize>,
+
+    /// Used to compute the sizes of types.
+    lc: LayoutComputer<'ir>,
+
+    /// The target that the code is being compiled for, the semantics of some
+    /// operations depend on it.
+    target: &'ir Target,
+
+    /// The call stack of the current evaluation.
+    frames: Vec<Frame>,
+
+    /// The identifier of the next frame that is pushed.
+    next_frame: usize,
+
+    /// The remaining fuel of the current evaluation.
+    fuel: usize,
+}
+
+impl<'ir> Interpreter<'ir> {
+    pub fn new(bodies: &'ir [Body], lc: LayoutComputer<'ir>, target: &'ir Target) -> Self {
+        let instances = bodies
+            .iter()
+            .enumerate()
+            .filter_map(|(index, body)| match *body.metadata().ty().borrow() {
+                ReprTy::FnDef { instance } => Some((instance, index)),
+                _ => None,
+            })
+            .collect();
+
+        Self { bodies, instances, lc, target, frames: vec![], next_frame: 0, fuel: 0 }
+    }
+
+    /// Evaluate a call to the function `instance` with the given arguments,
+    /// and return the value that the function returns.
+    pub fn call(&mut self, instance: InstanceId, args: Vec<Value>) -> EvalResult<Value> {
+        let body = self.body_of(instance)?;
+
+        self.frames.clear();
+        self.fuel = FUEL;
+
+        self.push_frame(body, args, None)?;
+        self.run()
+    }
+
+    /// Evaluate an [RValue] of the body at the given index, where the locals
+    /// of the body hold the given values. Reading a local that is
+    /// [Value::Uninit] stops the evaluation, and so this can be used to
+    /// evaluate the parts of a body whose locals are only partially known.
+    pub fn eval_rvalue_in(
+        &mut self,
+        body: usize,
+        locals: &mut IndexVec<Local, Value>,
+        rvalue: &RValue,
+    ) -> EvalResult<Value> {
+        let id = self.next_frame;
+        self.next_frame += 1;
+
+        self.frames.clear();
+        self.frames.push(Frame {
+            id,
+            body,
+            locals: std::mem::take(locals),
+            block: START_BLOCK,
+            statement: 0,
+            ret: None,
+        });
+
+        let result = self.eval_rvalue(&self.bodies[body], rvalue);
+        *locals = self.frames.pop().expect("no frame is being evaluated").locals;
+        result
+    }
+
+    /// Get the index of the [Body] of the given function.
+    fn body_of(&self, instance: InstanceId) -> EvalResult<usize> {
+        self.instances
+            .get(&instance)
+            .copied()
+            .ok_or(EvalError::Unsupported("call to a function without a body"))
+    }
+
+    fn frame(&self) -> &Frame {
+        self.frames.last().expect("no frame is being evaluated")
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("no frame is being evaluated")
+    }
+
+    /// Push a new frame for a call to the body at the given index.
+    fn push_frame(
+        &mut self,
+        body: usize,
+        args: Vec<Value>,
+        ret: Option<(Pointer, Option<BasicBlock>)>,
+    ) -> EvalResult<()> {
+        if self.frames.len() >= MAX_FRAMES {
+            return Err(EvalError::StackOverflow);
+        }
+
+        let data = &self.bodies[body];
+
+        if args.len() != data.arg_count {
+            return Err(EvalError::Undefined("call with the wrong number of arguments"));
+        }
+
+        let mut locals = index_vec![Value::Uninit; data.locals.len()];
+
+        for (local, arg) in data.args_iter().zip(args) {
+            locals[local] = arg;
+        }
+
+        let id = self.next_frame;
+        self.next_frame += 1;
+
+        self.frames.push(Frame { id, body, locals, block: START_BLOCK, statement: 0, ret });
+        Ok(())
+    }
+
+    /// Evaluate until the frame that the evaluation started from returns.
+    fn run(&mut self) -> EvalResult<Value> {
+        loop {
+            self.fuel = self.fuel.checked_sub(1).ok_or(EvalError::OutOfFuel)?;
+
+            let bodies = self.bodies;
+            let frame = self.frame();
+            let body = &bodies[frame.body];
+            let data = &body.blocks()[frame.block];
+
+            if let Some(statement) = data.statements.get(frame.statement) {
+                self.frame_mut().statement += 1;
+                self.statement(body, statement)?;
+            } else if let Some(value) = self.terminator(body, data.terminator())? {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Continue the evaluation of the current frame from the given block.
+    fn jump(&mut self, block: BasicBlock) {
+        let frame = self.frame_mut();
+        frame.block = block;
+        frame.statement = 0;
+    }
+
+    fn statement(&mut self, body: &Body, statement: &Statement) -> EvalResult<()> {
+        match &statement.kind {
+            StatementKind::Nop | StatementKind::Live(_) => {}
+            StatementKind::Dead(local) => self.frame_mut().locals[*local] = Value::Uninit,
+            StatementKind::Assume(operand) => {
+                if !self.eval_bool(body, operand)? {
+                    return Err(EvalError::Undefined("assumption does not hold"));
+                }
+            }
+            StatementKind::Assign(place, rvalue) => {
+                let value = self.eval_rvalue(body, rvalue)?;
+                let pointer = self.resolve(body, *place)?;
+                *self.location_mut(&pointer)? = value;
+            }
+            StatementKind::Discriminate(place, variant) => {
+                let pointer = self.resolve(body, *place)?;
+                self.location_mut(&pointer)?.set_variant(*variant);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate a terminator, returning the value that is returned by the
+    /// frame that the evaluation started from once it returns.
+    fn terminator(&mut self, body: &Body, terminator: &Terminator) -> EvalResult<Option<Value>> {
+        let target = match &terminator.kind {
+            TerminatorKind::Goto(target) | TerminatorKind::Drop { target, .. } => *target,
+            TerminatorKind::Return => return self.return_from_frame(),
+            TerminatorKind::Call { op, args, destination, target, .. } => {
+                let callee = self.body_of(self.eval_callee(body, op)?)?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval_operand(body, arg))
+                    .collect::<EvalResult<_>>()?;
+                let destination = self.resolve(body, *destination)?;
+
+                self.push_frame(callee, args, Some((destination, *target)))?;
+                return Ok(None);
+            }
+            TerminatorKind::Switch { value, targets } => {
+                let value = self.eval_scalar(body, value)?;
+                targets.corresponding_target(value.assert_bits(value.size()))
+            }
+            TerminatorKind::Assert { condition, expected, kind, target, .. } => {
+                if self.eval_bool(body, condition)? != *expected {
+                    return Err(EvalError::AssertFailed(**kind));
+                }
+
+                *target
+            }
+            TerminatorKind::Unreachable => {
+                return Err(EvalError::Undefined("entered unreachable code"));
+            }
+            // The unwind edges are never followed, since a failed assertion
+            // stops the evaluation.
+            TerminatorKind::Resume => return Err(EvalError::Unsupported("unwinding")),
+        };
+
+        self.jump(target);
+        Ok(None)
+    }
+
+    /// Pop the current frame, and write the returned value into the
+    /// destination of the call within the caller.
+    fn return_from_frame(&mut self) -> EvalResult<Option<Value>> {
+        let mut frame = self.frames.pop().expect("no frame is being evaluated");
+        let value = std::mem::replace(&mut frame.locals[RETURN_PLACE], Value::Uninit);
+
+        let Some((destination, target)) = frame.ret else {
+            return Ok(Some(value));
+        };
+
+        *self.location_mut(&destination)? = value;
+        self.jump(target.ok_or(EvalError::Undefined("returned from a diverging call"))?);
+        Ok(None)
+    }
+
+    /// Evaluate the function that is being called by a call terminator.
+    fn eval_callee(&self, body: &Body, op: &Operand) -> EvalResult<InstanceId> {
+        if let ReprTy::FnDef { instance } = *op.ty(&body.aux()).borrow() {
+            return Ok(instance);
+        }
+
+        match self.eval_const(body, op)?.kind() {
+            ConstKind::FnAddr(instance) => Ok(instance),
+            _ => Err(EvalError::Unsupported("call through an unknown function pointer")),
+        }
+    }
+
+    fn eval_operand(&self, body: &Body, operand: &Operand) -> EvalResult<Value> {
+        match operand {
+            Operand::Const(value) => Ok(Value::Const(*value)),
+            Operand::Place(place) => match self.read(body, *place)? {
+                Value::Uninit => Err(EvalError::Undefined("read of an uninitialised value")),
+                value => Ok(value.clone()),
+            },
+        }
+    }
+
+    fn eval_const(&self, body: &Body, operand: &Operand) -> EvalResult<Const> {
+        self.eval_operand(body, operand)?
+            .as_const()
+            .ok_or(EvalError::Unsupported("operation on a non-constant value"))
+    }
+
+    fn eval_scalar(&self, body: &Body, operand: &Operand) -> EvalResult<Scalar> {
+        match self.eval_const(body, operand)?.kind() {
+            ConstKind::Scalar(scalar) => Ok(scalar),
+            _ => Err(EvalError::Unsupported("operation on a non-scalar value")),
+        }
+    }
+
+    fn eval_bool(&self, body: &Body, operand: &Operand) -> EvalResult<bool> {
+        bool::try_from(self.eval_scalar(body, operand)?)
+            .map_err(|_| EvalError::Undefined("invalid boolean value"))
+    }
+
+    fn eval_rvalue(&self, body: &Body, rvalue: &RValue) -> EvalResult<Value> {
+        let value = match rvalue {
+            RValue::Use(operand) => return self.eval_operand(body, operand),
+            RValue::ConstOp(op, ty) => self.const_op(*op, *ty)?,
+            RValue::UnaryOp(op, operand) => {
+                let operand = self.eval_const(body, operand)?;
+
+                ConstFolder::new(self.lc)
+                    .try_fold_un_op(*op, &operand)
+                    .ok_or(EvalError::Unsupported("unary operation on a non-scalar value"))?
+            }
+            RValue::BinaryOp(op, operands) => {
+                let (lhs, rhs) = operands.as_ref();
+                let (lhs, rhs) = (self.eval_const(body, lhs)?, self.eval_const(body, rhs)?);
+                self.binary_op(*op, lhs, rhs)?.0
+            }
+            RValue::CheckedBinaryOp(op, operands) => {
+                let (lhs, rhs) = operands.as_ref();
+                let (lhs, rhs) = (self.eval_const(body, lhs)?, self.eval_const(body, rhs)?);
+                let (value, overflow) = self.binary_op(*op, lhs, rhs)?;
+
+                let fields = vec![Value::Const(value), Value::Const(Const::bool(overflow))];
+                return Ok(Value::aggregate(None, fields));
+            }
+            RValue::Cast(kind, operand, ty) => {
+                self.cast(*kind, self.eval_const(body, operand)?, *ty)?
+            }
+            RValue::Len(place) => {
+                let length = match *place.ty(&body.aux()).borrow() {
+                    ReprTy::Array { length, .. } => length,
+                    _ => self.elements(&self.resolve(body, *place)?)?,
+                };
+
+                Const::usize(length as u64, &self.lc)
+            }
+            RValue::Ref(_, place, _) => return Ok(Value::Ref(self.resolve(body, *place)?)),
+            RValue::Aggregate(kind, operands) => {
+                let variant = match kind {
+                    AggregateKind::Enum(_, variant) => Some(*variant),
+                    _ => None,
+                };
+
+                let fields = operands
+                    .iter()
+                    .map(|operand| self.eval_operand(body, operand))
+                    .collect::<EvalResult<_>>()?;
+
+                return Ok(Value::aggregate(variant, fields));
+            }
+            RValue::Repeat(operand, length) => {
+                let element = self.eval_operand(body, operand)?;
+                return Ok(Value::aggregate(None, vec![element; *length]));
+            }
+            RValue::Discriminant(place) => self.discriminant(body, *place)?,
+        };
+
+        Ok(Value::Const(value))
+    }
+
+    /// Resolve a [Place] within the current frame into a [Pointer], by
+    /// following all of the dereferences within the place.
+    fn resolve(&self, body: &Body, place: Place) -> EvalResult<Pointer> {
+        let mut pointer = Pointer { frame: self.frame().id, local: place.local, path: vec![] };
+
+        for projection in body.projections().borrow(place.projections).iter() {
+            let step = match *projection {
+                PlaceProjection::Deref => {
+                    pointer = match self.location(&pointer)? {
+                        Value::Ref(target) => target.clone(),
+                        Value::Const(_) => {
+                            return Err(EvalError::Unsupported("dereference of a constant"))
+                        }
+                        _ => return Err(EvalError::Undefined("dereference of a non-reference")),
+                    };
+
+                    continue;
+                }
+                PlaceProjection::Field(field) => Step::Field(field),
+                PlaceProjection::Downcast(variant) => Step::Downcast(variant),
+                PlaceProjection::Index(local) => {
+                    let index = self
+                        .eval_const(body, &Operand::Place(Place::from_local(local)))?
+                        .try_to_target_usize(&self.lc)
+                        .ok_or(EvalError::Undefined("index is not a `usize`"))?;
+
+                    Step::Field(self.check_index(&pointer, Some(index))?)
+                }
+                PlaceProjection::ConstantIndex { offset, from_end: false, .. } => {
+                    Step::Field(self.check_index(&pointer, Some(offset))?)
+                }
+                PlaceProjection::ConstantIndex { offset, from_end: true, .. } => {
+                    let index = self.elements(&pointer)?.checked_sub(offset);
+                    Step::Field(self.check_index(&pointer, index)?)
+                }
+                PlaceProjection::SubSlice { .. } => {
+                    return Err(EvalError::Unsupported("sub-slice projection"))
+                }
+            };
+
+            pointer.path.push(step);
+        }
+
+        Ok(pointer)
+    }
+
+    /// Check that the given index is within the bounds of the array that the
+    /// [Pointer] points to.
+    fn check_index(&self, pointer: &Pointer, index: Option<usize>) -> EvalResult<usize> {
+        let elements = self.elements(pointer)?;
+
+        index.filter(|index| *index < elements).ok_or(EvalError::Undefined("index out of bounds"))
+    }
+
+    /// Get the number of elements of the array that the [Pointer] points to.
+    fn elements(&self, pointer: &Pointer) -> EvalResult<usize> {
+        match self.location(pointer)? {
+            Value::Aggregate(aggregate) => Ok(aggregate.fields.len()),
+            Value::Const(_) => Err(EvalError::Unsupported("projection into a constant")),
+            _ => Err(EvalError::Undefined("read of an uninitialised value")),
+        }
+    }
+
+    /// Read the value at the given [Place] within the current frame.
+    fn read(&self, body: &Body, place: Place) -> EvalResult<&Value> {
+        let pointer = self.resolve(body, place)?;
+        self.location(&pointer)
+    }
+
+    fn location(&self, pointer: &Pointer) -> EvalResult<&Value> {
+        let frame = self
+            .frames
+            .iter()
+            .rev()
+            .find(|frame| frame.id == pointer.frame)
+            .ok_or(EvalError::Undefined("use of a dangling reference"))?;
+
+        pointer
+            .path
+            .iter()
+            .try_fold(&frame.locals[pointer.local], |value, step| value.project(*step))
+    }
+
+    fn location_mut(&mut self, pointer: &Pointer) -> EvalResult<&mut Value> {
+        let frame = self
+            .frames
+            .iter_mut()
+            .rev()
+            .find(|frame| frame.id == pointer.frame)
+            .ok_or(EvalError::Undefined("use of a dangling reference"))?;
+
+        pointer
+            .path
+            .iter()
+            .try_fold(&mut frame.locals[pointer.local], |value, step| value.project_mut(*step))
+    }
+
+    fn const_op(&self, op: ConstOp, ty: ReprTyId) -> EvalResult<Const> {
+        let layout = self
+            .lc
+            .layout_of_ty(ty)
+            .map_err(|_| EvalError::Unsupported("type without a layout"))?;
+
+        let value = layout.map(|layout| match op {
+            ConstOp::SizeOf => layout.size.bytes(),
+            ConstOp::AlignOf => layout.alignment.abi.bytes(),
+        });
+
+        Ok(Const::usize(value, &self.lc))
+    }
+
+    /// Compute the discriminant of the value at the given [Place], values
+    /// of types that aren't enums always have a discriminant of zero.
+    fn discriminant(&self, body: &Body, place: Place) -> EvalResult<Const> {
+        let ty = place.ty(&body.aux());
+
+        let value = match self.read(body, place)? {
+            Value::Aggregate(Aggregate { variant: Some(variant), .. }) => {
+                ty.borrow().discriminant_for_variant(*variant).map_or(0, |(_, value)| value)
+            }
+            Value::Uninit => return Err(EvalError::Undefined("read of an uninitialised value")),
+            Value::Const(_) => return Err(EvalError::Unsupported("discriminant of a constant")),
+            _ => 0,
+        };
+
+        Ok(Const::from_scalar_like(value, ty.borrow().discriminant_ty(), &self.lc))
+    }
+
+    /// Evaluate a binary operation, returning the result and whether the
+    /// operation overflowed. The result of an operation that overflows wraps
+    /// around.
+    fn binary_op(&self, op: BinOp, lhs: Const, rhs: Const) -> EvalResult<(Const, bool)> {
+        let ty = lhs.ty();
+
+        // Comparisons and bitwise operations can't overflow, and the folder
+        // already implements all of the operations on floats, booleans and
+        // characters.
+        let is_arithmetic = matches!(
+            op,
+            BinOp::Add
+                | BinOp::Sub
+                | BinOp::Mul
+                | BinOp::Div
+                | BinOp::Mod
+                | BinOp::Exp
+                | BinOp::Shl
+                | BinOp::Shr
+        );
+
+        if !ty.is_integral() || !is_arithmetic {
+            let value = ConstFolder::new(self.lc)
+                .try_fold_bin_op(op, &lhs, &rhs)
+                .ok_or(EvalError::Unsupported("binary operation on non-scalar values"))?;
+
+            return Ok((value, false));
+        }
+
+        let (ConstKind::Scalar(lhs), ConstKind::Scalar(rhs)) = (lhs.kind(), rhs.kind()) else {
+            return Err(EvalError::Unsupported("binary operation on non-scalar values"));
+        };
+
+        let size = self.lc.size_of_ty(ty).map_err(|_| EvalError::Unsupported("unsized integer"))?;
+        let signed = ty.is_signed();
+        let lhs = lhs.assert_bits(size);
+        let rhs = rhs.assert_bits(rhs.size());
+
+        let (value, overflow) = match op {
+            BinOp::Shl | BinOp::Shr => self.shift(op, size, signed, lhs, rhs),
+            BinOp::Div | BinOp::Mod => (divide(op, size, signed, lhs, rhs)?, false),
+            BinOp::Exp => exp(size, signed, lhs, rhs),
+            _ => arithmetic(op, size, signed, lhs, rhs),
+        };
+
+        Ok((Const::new(ty, ConstKind::Scalar(Scalar::from_uint(value, size))), overflow))
+    }
+
+    /// Compute `value << amount` or `value >> amount`, the shift overflows if
+    /// the amount is not smaller than the bit-width of the value, in which
+    /// case the result depends on the [ShiftAmountSemantics] of the target.
+    fn shift(
+        &self,
+        op: BinOp,
+        size: Size,
+        signed: bool,
+        value: u128,
+        amount: u128,
+    ) -> (u128, bool) {
+        let bits = u128::from(size.bits());
+        let too_large = amount >= bits;
+
+        if too_large && self.target.int_overflow.shift_amount == ShiftAmountSemantics::Saturate {
+            let negative = signed && (size.sign_extend(value) as i128) < 0;
+            let saturated = if op == BinOp::Shr && negative { size.truncate(u128::MAX) } else { 0 };
+
+            return (saturated, true);
+        }
+
+        let amount = (amount % bits) as u32;
+        let result = match op {
+            BinOp::Shl => value << amount,
+            _ if signed => (size.sign_extend(value) as i128 >> amount) as u128,
+            _ => value >> amount,
+        };
+
+        (size.truncate(result), too_large)
+    }
+
+    /// Cast a constant into the given type.
+    fn cast(&self, kind: CastKind, value: Const, ty: ReprTyId) -> EvalResult<Const> {
+        let ConstKind::Scalar(scalar) = value.kind() else {
+            return Err(EvalError::Unsupported("cast of a non-scalar value"));
+        };
+
+        let src = value.ty();
+        let bits = || {
+            let bits = scalar.assert_bits(scalar.size());
+            if src.is_signed() {
+                scalar.size().sign_extend(bits)
+            } else {
+                bits
+            }
+        };
+
+        Ok(match kind {
+            CastKind::IntToInt => {
+                let size = self.size_of(ty)?;
+                Const::new(ty, ConstKind::Scalar(Scalar::from_uint(size.truncate(bits()), size)))
+            }
+            CastKind::IntToFloat => match (float_ty(ty)?, src.is_signed()) {
+                (FloatTy::F32, true) => Const::from(bits() as i128 as f32),
+                (FloatTy::F32, false) => Const::from(bits() as f32),
+                (FloatTy::F64, true) => Const::from(bits() as i128 as f64),
+                (FloatTy::F64, false) => Const::from(bits() as f64),
+            },
+            // Like `as` casts, the value saturates at the bounds of the
+            // integer type, and `NaN` is converted into zero.
+            CastKind::FloatToInt => {
+                let value = match float_ty(src)? {
+                    FloatTy::F32 => scalar.to_f32() as f64,
+                    FloatTy::F64 => scalar.to_f64(),
+                };
+
+                let size = self.size_of(ty)?;
+                let shift = 128 - size.bits();
+
+                let bits = if ty.is_signed() {
+                    (value as i128).clamp(i128::MIN >> shift, i128::MAX >> shift) as u128
+                } else {
+                    (value as u128).min(u128::MAX >> shift)
+                };
+
+                Const::new(ty, ConstKind::Scalar(Scalar::from_uint(size.truncate(bits), size)))
+            }
+            CastKind::FloatToFloat => match (float_ty(src)?, float_ty(ty)?) {
+                (FloatTy::F32, FloatTy::F64) => Const::from(scalar.to_f32() as f64),
+                (FloatTy::F64, FloatTy::F32) => Const::from(scalar.to_f64() as f32),
+                _ => value,
+            },
+        })
+    }
+
+    fn size_of(&self, ty: ReprTyId) -> EvalResult<Size> {
+        self.lc.size_of_ty(ty).map_err(|_| EvalError::Unsupported("type without a layout"))
+    }
+}
+
+/// Get the [FloatTy] of a floating point type.
+fn float_ty(ty: ReprTyId) -> EvalResult<FloatTy> {
+    match *ty.borrow() {
+        ReprTy::Float(float) => Ok(float),
+        _ => Err(EvalError::Unsupported("float operation on a non-float value")),
+    }
+}
+
+/// Compute `lhs + rhs`, `lhs - rhs` or `lhs * rhs` on the raw bits of two
+/// integers.
+fn arithmetic(op: BinOp, size: Size, signed: bool, lhs: u128, rhs: u128) -> (u128, bool) {
+    if signed {
+        let (lhs, rhs) = (size.sign_extend(lhs) as i128, size.sign_extend(rhs) as i128);
+        let (result, overflow) = match op {
+            BinOp::Add => lhs.overflowing_add(rhs),
+            BinOp::Sub => lhs.overflowing_sub(rhs),
+            BinOp::Mul => lhs.overflowing_mul(rhs),
+            _ => unreachable!("`{op}` is not an arithmetic operator"),
+        };
+
+        let truncated = size.truncate(result as u128);
+        (truncated, overflow || size.sign_extend(truncated) as i128 != result)
+    } else {
+        let (result, overflow) = match op {
+            BinOp::Add => lhs.overflowing_add(rhs),
+            BinOp::Sub => lhs.overflowing_sub(rhs),
+            BinOp::Mul => lhs.overflowing_mul(rhs),
+            _ => unreachable!("`{op}` is not an arithmetic operator"),
+        };
+
+        let truncated = size.truncate(result);
+        (truncated, overflow || truncated != result)
+    }
+}
+
+/// Compute `lhs / rhs` or `lhs % rhs` on the raw bits of two integers.
+fn divide(op: BinOp, size: Size, signed: bool, lhs: u128, rhs: u128) -> EvalResult<u128> {
+    if rhs == 0 {
+        return Err(EvalError::Undefined("division by zero"));
+    }
+
+    if signed {
+        let (lhs, rhs) = (size.sign_extend(lhs) as i128, size.sign_extend(rhs) as i128);
+
+        if lhs == size.signed_int_min() && rhs == -1 {
+            return Err(EvalError::Undefined("division with overflow"));
+        }
+
+        let result = if op == BinOp::Div { lhs / rhs } else { lhs % rhs };
+        Ok(size.truncate(result as u128))
+    } else {
+        Ok(if op == BinOp::Div { lhs / rhs } else { lhs % rhs })
+    }
+}
+
+/// Compute `lhs ^^ rhs` on the raw bits of two integers by repeated squaring,
+/// a negative exponent yields `1`.
+fn exp(size: Size, signed: bool, lhs: u128, rhs: u128) -> (u128, bool) {
+    if signed && (size.sign_extend(rhs) as i128) < 0 {
+        return (1, false);
+    }
+
+    let (mut result, mut base, mut exponent, mut overflow) = (1, lhs, rhs, false);
+
+    while exponent > 0 {
+        if exponent & 1 != 0 {
+            let (value, overflowed) = arithmetic(BinOp::Mul, size, signed, result, base);
+            (result, overflow) = (value, overflow || overflowed);
+        }
+
+        exponent >>= 1;
+
+        if exponent > 0 {
+            let (value, overflowed) = arithmetic(BinOp::Mul, size, signed, base, base);
+            (base, overflow) = (value, overflow || overflowed);
+        }
+    }
+
+    (result, overflow)
+}
@@ -0,0 +1,137 @@
+//! Defines the values that the [Interpreter] operates on. Unlike a [Const],
+//! which is a flat representation of a value, the values of the interpreter
+//! are structured so that the fields of aggregates can be read and written
+//! independently, and so that references can point into the locals of any
+//! frame on the stack.
+//!
+//! [Interpreter]: crate::Interpreter
+
+use hash_ir::ir::{Const, Local};
+use hash_repr::ty::VariantIdx;
+
+use crate::{EvalError, EvalResult};
+
+/// A value that is stored within a local of the [Interpreter].
+///
+/// [Interpreter]: crate::Interpreter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// The value hasn't been initialised yet, reading it is an error.
+    Uninit,
+
+    /// A constant value, this is used for all scalars, zero-sized values,
+    /// function addresses and allocated constants.
+    Const(Const),
+
+    /// A tuple, struct, enum or array value.
+    Aggregate(Aggregate),
+
+    /// A reference to a place within one of the frames of the interpreter.
+    Ref(Pointer),
+}
+
+/// A value that is made up of fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    /// The variant of an enum value, this is [None] for all other values.
+    pub variant: Option<VariantIdx>,
+
+    /// The fields of the aggregate, i.e. the elements of an array.
+    pub fields: Vec<Value>,
+}
+
+/// A step within the path of a [Pointer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Select the field (or element) at the given index.
+    Field(usize),
+
+    /// Narrow the value down to the given variant.
+    Downcast(VariantIdx),
+}
+
+/// Points to a location within a local of a frame of the interpreter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pointer {
+    /// The identifier of the frame that the local belongs to.
+    pub frame: usize,
+
+    /// The local that is being pointed to.
+    pub local: Local,
+
+    /// The steps that are taken from the local to reach the location.
+    pub path: Vec<Step>,
+}
+
+impl Value {
+    /// Create an aggregate value from the given fields.
+    pub fn aggregate(variant: Option<VariantIdx>, fields: Vec<Value>) -> Self {
+        Self::Aggregate(Aggregate { variant, fields })
+    }
+
+    /// Convert the value into a [Const], if it is one.
+    pub fn as_const(&self) -> Option<Const> {
+        match self {
+            Self::Const(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Apply a [Step] onto the value for reading.
+    pub fn project(&self, step: Step) -> EvalResult<&Value> {
+        match (self, step) {
+            (Self::Aggregate(aggregate), Step::Field(index)) => aggregate
+                .fields
+                .get(index)
+                .ok_or(EvalError::Undefined("read of an uninitialised field")),
+            (Self::Aggregate(aggregate), Step::Downcast(variant)) => {
+                if aggregate.variant == Some(variant) {
+                    Ok(self)
+                } else {
+                    Err(EvalError::Undefined("read of an inactive enum variant"))
+                }
+            }
+            (Self::Uninit, _) => Err(EvalError::Undefined("read of an uninitialised value")),
+            (Self::Const(_), _) => Err(EvalError::Unsupported("projection into a constant")),
+            (Self::Ref(_), _) => Err(EvalError::Undefined("projection into a reference")),
+        }
+    }
+
+    /// Apply a [Step] onto the value for writing. Uninitialised values are
+    /// initialised as aggregates, and missing fields are added so that the
+    /// fields of an aggregate can be written one by one. Writing to a variant
+    /// that isn't active makes it the active variant.
+    pub fn project_mut(&mut self, step: Step) -> EvalResult<&mut Value> {
+        if matches!(self, Self::Uninit) {
+            *self = Self::aggregate(None, vec![]);
+        }
+
+        match (self, step) {
+            (Self::Aggregate(aggregate), Step::Field(index)) => {
+                if aggregate.fields.len() <= index {
+                    aggregate.fields.resize(index + 1, Value::Uninit);
+                }
+
+                Ok(&mut aggregate.fields[index])
+            }
+            (this @ Self::Aggregate(_), Step::Downcast(variant)) => {
+                this.set_variant(variant);
+                Ok(this)
+            }
+            (Self::Const(_), _) => Err(EvalError::Unsupported("projection into a constant")),
+            (_, _) => Err(EvalError::Undefined("projection into a reference")),
+        }
+    }
+
+    /// Set the active variant of the value, the fields of the value are
+    /// discarded if the variant changes.
+    pub fn set_variant(&mut self, variant: VariantIdx) {
+        match self {
+            Self::Aggregate(aggregate) if aggregate.variant == Some(variant) => {}
+            Self::Aggregate(aggregate) if aggregate.variant.is_none() => {
+                aggregate.variant = Some(variant);
+            }
+            _ => *self = Self::aggregate(Some(variant), vec![]),
+        }
+    }
+}
@@ -0,0 +1,1624 @@
+//! Implementation of a compact binary format for the IR. This format is used
+//! to write the lowered [Body]s of a workspace to disk, so that they can be
+//! consumed by out-of-process tools, or by later invocations of the pipeline
+//! without needing to lower the workspace again.
+//!
+//! The format begins with a header, followed by a table of all the interned
+//! values (identifiers, types, ADTs, instances and allocations) that are
+//! referenced by the bodies, and then the bodies themselves. All integers
+//! are written in the variable length LEB128 encoding, and interned values
+//! are referenced by their index within the table.
+//!
+//! ##Note: source locations are not preserved, since the spans of the AST
+//! nodes that the IR originated from are meaningless outside of the session
+//! in which the IR was lowered. Any decoded bodies, statements and terminators
+//! are given a null [AstNodeId].
+
+use std::{fmt, io};
+
+use hash_ast::ast::AstNodeId;
+use hash_ir::{
+    cast::CastKind,
+    ir::{
+        AggregateKind, AllocId, AssertKind, BasicBlock, BasicBlockData, BinOp, Body, BodyMetadata,
+        BodySource, Const, ConstKind, ConstOp, Local, LocalDecl, Operand, Place, PlaceProjection,
+        Projections, RValue, Scalar, Statement, StatementKind, SwitchTargets, Terminator,
+        TerminatorKind, UnOp, UnsafeOp, UnsafeOpKind,
+    },
+    ty::{
+        Adt, AdtField, AdtFlags, AdtId, AdtRepresentation, AdtVariant, Instance, InstanceId,
+        Mutability, RefKind, ReprTy, ReprTyId, ReprTyListId, RepresentationFlags, VariantIdx,
+        COMMON_REPR_TYS,
+    },
+};
+use hash_source::{
+    constant::{Alloc, AllocRange, Mutability as AllocMutability},
+    identifier::Identifier,
+};
+use hash_storage::store::statics::{SingleStoreValue, StoreId};
+use hash_target::{
+    abi::Abi,
+    alignment::Alignment,
+    discriminant::{Discriminant, DiscriminantKind},
+    primitives::{BigIntTy, IntTy, SIntTy, UIntTy},
+    size::Size,
+};
+use hash_utils::{fxhash::FxHashMap, index_vec::IndexVec};
+
+/// The magic bytes that every encoded IR file begins with.
+const MAGIC: &[u8; 4] = b"HIR\0";
+
+/// The version of the binary format, this should be bumped whenever the
+/// format changes.
+const VERSION: u64 = 3;
+
+/// The tags of the entries in the table of interned values.
+mod entry {
+    pub const IDENT: u8 = 0;
+    pub const TY: u8 = 1;
+    pub const ADT_DECL: u8 = 2;
+    pub const ADT_DEF: u8 = 3;
+    pub const INSTANCE: u8 = 4;
+    pub const ALLOC: u8 = 5;
+}
+
+/// An error that occurred when decoding IR from the binary format.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The data does not begin with the expected magic bytes.
+    InvalidMagic,
+
+    /// The data was encoded with a different version of the format.
+    UnsupportedVersion(u64),
+
+    /// The data ended before the decoder expected it to.
+    UnexpectedEof,
+
+    /// An unknown tag was read for the specified kind of item.
+    InvalidTag { kind: &'static str, tag: u64 },
+
+    /// An index referred to an interned value that does not exist.
+    InvalidIndex { kind: &'static str, index: usize },
+
+    /// A string was not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidMagic => write!(f, "data is not in the IR binary format"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported IR binary format version `{version}`, expected `{VERSION}`")
+            }
+            DecodeError::UnexpectedEof => write!(f, "unexpected end of data"),
+            DecodeError::InvalidTag { kind, tag } => write!(f, "invalid {kind} tag `{tag}`"),
+            DecodeError::InvalidIndex { kind, index } => {
+                write!(f, "invalid {kind} index `{index}`")
+            }
+            DecodeError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Write an unsigned integer in the LEB128 encoding.
+fn write_uint(buf: &mut Vec<u8>, mut value: u128) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            buf.push(byte);
+            break;
+        }
+
+        buf.push(byte | 0x80);
+    }
+}
+
+/// Write a length-prefixed sequence of bytes.
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_uint(buf, bytes.len() as u128);
+    buf.extend_from_slice(bytes);
+}
+
+/// Tag of a [SIntTy], [UIntTy], or [BigIntTy] within an [IntTy].
+fn int_ty_tag(ty: IntTy) -> u8 {
+    match ty {
+        IntTy::Int(SIntTy::I8) => 0,
+        IntTy::Int(SIntTy::I16) => 1,
+        IntTy::Int(SIntTy::I32) => 2,
+        IntTy::Int(SIntTy::I64) => 3,
+        IntTy::Int(SIntTy::I128) => 4,
+        IntTy::Int(SIntTy::ISize) => 5,
+        IntTy::UInt(UIntTy::U8) => 6,
+        IntTy::UInt(UIntTy::U16) => 7,
+        IntTy::UInt(UIntTy::U32) => 8,
+        IntTy::UInt(UIntTy::U64) => 9,
+        IntTy::UInt(UIntTy::U128) => 10,
+        IntTy::UInt(UIntTy::USize) => 11,
+        IntTy::Big(BigIntTy::IBig) => 12,
+        IntTy::Big(BigIntTy::UBig) => 13,
+    }
+}
+
+/// Tag of an [Abi].
+fn abi_tag(abi: Abi) -> u8 {
+    match abi {
+        Abi::C => 0,
+        Abi::Cold => 1,
+        Abi::Hash => 2,
+        Abi::Fast => 3,
+        Abi::System => 4,
+    }
+}
+
+/// [IrBinaryEncoder] is used to convert a collection of [Body]s into the
+/// binary format.
+#[derive(Default)]
+pub struct IrBinaryEncoder {
+    /// The table of interned values that the bodies reference.
+    entries: Vec<u8>,
+
+    /// The number of entries within the table.
+    entry_count: usize,
+
+    /// The encoded bodies.
+    bodies: Vec<u8>,
+
+    /// The number of encoded bodies.
+    body_count: usize,
+
+    /// The indices of all of the encoded identifiers.
+    idents: FxHashMap<Identifier, usize>,
+
+    /// The indices of all of the encoded types.
+    tys: FxHashMap<ReprTyId, usize>,
+
+    /// The indices of all of the encoded ADTs.
+    adts: FxHashMap<AdtId, usize>,
+
+    /// The indices of all of the encoded instances.
+    instances: FxHashMap<InstanceId, usize>,
+
+    /// The indices of all of the encoded allocations.
+    allocs: FxHashMap<AllocId, usize>,
+
+    /// ADTs that have been declared, but whose definitions have not been
+    /// written yet. The definitions are written after all of the bodies,
+    /// since ADTs may refer to themselves.
+    pending_adts: Vec<AdtId>,
+
+    /// The position of each of the common types, these types are not
+    /// written to the table since they always exist.
+    common_tys: FxHashMap<ReprTyId, usize>,
+}
+
+impl IrBinaryEncoder {
+    /// Create a new [IrBinaryEncoder].
+    pub fn new() -> Self {
+        let common_tys = COMMON_REPR_TYS.iter().enumerate().map(|(i, ty)| (ty, i)).collect();
+        Self { common_tys, ..Default::default() }
+    }
+
+    /// Add a [Body] to the encoded data.
+    pub fn encode_body(&mut self, body: &Body) {
+        let mut buf = Vec::new();
+        let metadata = body.metadata();
+
+        let name = self.ident(metadata.name());
+        write_uint(&mut buf, name as u128);
+        buf.push(match metadata.source() {
+            BodySource::Const => 0,
+            BodySource::Item => 1,
+            BodySource::Derived => 2,
+        });
+
+        let ty = self.ty(metadata.ty());
+        write_uint(&mut buf, ty as u128);
+        write_uint(&mut buf, body.arg_count as u128);
+        buf.push(body.needs_dumping() as u8);
+
+        write_uint(&mut buf, metadata.unsafe_ops.len() as u128);
+        for op in &metadata.unsafe_ops {
+            buf.push(match op.kind {
+                UnsafeOpKind::PtrOffset => 0,
+                UnsafeOpKind::PtrRead => 1,
+                UnsafeOpKind::PtrWrite => 2,
+                UnsafeOpKind::RawDeref => 3,
+                UnsafeOpKind::Transmute => 4,
+                UnsafeOpKind::ExternCall => 5,
+                UnsafeOpKind::UnsafeCall => 6,
+            });
+            buf.push(op.in_unsafe_block as u8);
+        }
+
+        write_uint(&mut buf, body.locals.len() as u128);
+        for decl in body.locals.iter() {
+            let flags = (decl.mutability().is_mutable() as u8)
+                | ((decl.auxiliary() as u8) << 1)
+                | ((decl.name().is_some() as u8) << 2);
+            buf.push(flags);
+
+            if let Some(name) = decl.name() {
+                let name = self.ident(name);
+                write_uint(&mut buf, name as u128);
+            }
+
+            let ty = self.ty(decl.ty());
+            write_uint(&mut buf, ty as u128);
+        }
+
+        write_uint(&mut buf, body.blocks().len() as u128);
+        for data in body.blocks().iter() {
+            write_uint(&mut buf, data.statements.len() as u128);
+            for statement in &data.statements {
+                self.statement(&mut buf, body, statement);
+            }
+
+            match &data.terminator {
+                Some(terminator) => {
+                    buf.push(1);
+                    self.terminator(&mut buf, body, terminator);
+                }
+                None => buf.push(0),
+            }
+        }
+
+        self.bodies.append(&mut buf);
+        self.body_count += 1;
+    }
+
+    /// Finish encoding, and produce the binary data.
+    pub fn finish(mut self) -> Vec<u8> {
+        // Write all of the pending ADT definitions, writing a definition
+        // might declare more ADTs.
+        while let Some(adt) = self.pending_adts.pop() {
+            self.adt_def(adt);
+        }
+
+        let mut data = Vec::with_capacity(self.entries.len() + self.bodies.len() + 16);
+        data.extend_from_slice(MAGIC);
+        write_uint(&mut data, VERSION as u128);
+        write_uint(&mut data, self.entry_count as u128);
+        data.append(&mut self.entries);
+        write_uint(&mut data, self.body_count as u128);
+        data.append(&mut self.bodies);
+        data
+    }
+
+    /// Begin a new entry in the table of interned values.
+    fn begin_entry(&mut self, tag: u8) {
+        self.entries.push(tag);
+        self.entry_count += 1;
+    }
+
+    /// Get the index of an [Identifier], adding it to the table if needed.
+    fn ident(&mut self, ident: Identifier) -> usize {
+        if let Some(index) = self.idents.get(&ident) {
+            return *index;
+        }
+
+        self.begin_entry(entry::IDENT);
+        write_bytes(&mut self.entries, ident.as_str().as_bytes());
+
+        let index = self.idents.len();
+        self.idents.insert(ident, index);
+        index
+    }
+
+    /// Get the index of a [ReprTyId], adding it to the table if needed.
+    fn ty(&mut self, ty: ReprTyId) -> usize {
+        if let Some(index) = self.tys.get(&ty) {
+            return *index;
+        }
+
+        // Firstly, write all of the types that this type depends on.
+        let mut buf = Vec::new();
+
+        if let Some(common) = self.common_tys.get(&ty) {
+            buf.push(0);
+            write_uint(&mut buf, *common as u128);
+        } else {
+            match ty.value() {
+                ReprTy::Int(_)
+                | ReprTy::UInt(_)
+                | ReprTy::Float(_)
+                | ReprTy::Str
+                | ReprTy::Bool
+                | ReprTy::Char
+                | ReprTy::Never => {
+                    let primitive = ty.value();
+                    let common = COMMON_REPR_TYS.iter().position(|ty| ty.value() == primitive);
+                    buf.push(0);
+                    write_uint(&mut buf, common.unwrap() as u128);
+                }
+                ReprTy::Ref(inner, mutability, kind) => {
+                    let inner = self.ty(inner);
+                    buf.push(1);
+                    write_uint(&mut buf, inner as u128);
+                    buf.push(mutability.is_mutable() as u8);
+                    buf.push(match kind {
+                        RefKind::Normal => 0,
+                        RefKind::Raw => 1,
+                        RefKind::Rc => 2,
+                    });
+                }
+                ReprTy::Slice(element) => {
+                    let element = self.ty(element);
+                    buf.push(2);
+                    write_uint(&mut buf, element as u128);
+                }
+                ReprTy::Array { ty, length } => {
+                    let element = self.ty(ty);
+                    buf.push(3);
+                    write_uint(&mut buf, element as u128);
+                    write_uint(&mut buf, length as u128);
+                }
+                ReprTy::Adt(adt) => {
+                    let adt = self.adt(adt);
+                    buf.push(4);
+                    write_uint(&mut buf, adt as u128);
+                }
+                ReprTy::Fn { params, return_ty, abi } => {
+                    let params = self.ty_list(params);
+                    let return_ty = self.ty(return_ty);
+                    buf.push(5);
+                    for index in params {
+                        write_uint(&mut buf, index as u128);
+                    }
+                    write_uint(&mut buf, return_ty as u128);
+                    buf.push(abi_tag(abi));
+                }
+                ReprTy::FnDef { instance } => {
+                    let instance = self.instance(instance);
+                    buf.push(6);
+                    write_uint(&mut buf, instance as u128);
+                }
+            }
+        }
+
+        self.begin_entry(entry::TY);
+        self.entries.append(&mut buf);
+
+        let index = self.tys.len();
+        self.tys.insert(ty, index);
+        index
+    }
+
+    /// Get the indices of all of the types within a [ReprTyListId], the
+    /// returned list begins with the number of types.
+    fn ty_list(&mut self, tys: ReprTyListId) -> Vec<usize> {
+        let tys = tys.value();
+        let mut indices = vec![tys.len()];
+        indices.extend(tys.iter().map(|ty| self.ty(*ty)));
+        indices
+    }
+
+    /// Get the index of an [AdtId], declaring it in the table if needed.
+    fn adt(&mut self, adt: AdtId) -> usize {
+        if let Some(index) = self.adts.get(&adt) {
+            return *index;
+        }
+
+        // ADTs that are part of the common types always exist, and so we
+        // refer to them by the common type instead.
+        let common = COMMON_REPR_TYS.iter().position(|ty| *ty.borrow() == ReprTy::Adt(adt));
+
+        self.begin_entry(entry::ADT_DECL);
+        match common {
+            Some(common) => {
+                self.entries.push(1);
+                write_uint(&mut self.entries, common as u128);
+            }
+            None => {
+                self.entries.push(0);
+                self.pending_adts.push(adt);
+            }
+        }
+
+        let index = self.adts.len();
+        self.adts.insert(adt, index);
+        index
+    }
+
+    /// Write the definition of a declared ADT.
+    fn adt_def(&mut self, id: AdtId) {
+        let adt = id.value();
+        let mut buf = Vec::new();
+
+        write_uint(&mut buf, self.adts[&id] as u128);
+        let name = self.ident(adt.name);
+        write_uint(&mut buf, name as u128);
+        write_uint(&mut buf, adt.flags.bits() as u128);
+        write_uint(&mut buf, adt.metadata.flags().bits() as u128);
+
+        match adt.metadata.discriminant {
+            Some(ty) => buf.extend([1, int_ty_tag(ty)]),
+            None => buf.push(0),
+        }
+
+        match adt.metadata.alignment {
+            Some(alignment) => {
+                buf.push(1);
+                write_uint(&mut buf, alignment.bytes() as u128);
+            }
+            None => buf.push(0),
+        }
+
+        match adt.substitutions {
+            Some(substitutions) => {
+                buf.push(1);
+                for index in self.ty_list(substitutions) {
+                    write_uint(&mut buf, index as u128);
+                }
+            }
+            None => buf.push(0),
+        }
+
+        write_uint(&mut buf, adt.variants.len() as u128);
+        for variant in adt.variants.iter() {
+            let name = self.ident(variant.name);
+            write_uint(&mut buf, name as u128);
+
+            let Discriminant { value, ty, kind } = variant.discriminant;
+            write_uint(&mut buf, value);
+            buf.push(int_ty_tag(ty));
+            match kind {
+                DiscriminantKind::Relative(offset) => {
+                    buf.push(0);
+                    write_uint(&mut buf, offset as u128);
+                }
+                DiscriminantKind::Explicit => buf.push(1),
+            }
+
+            write_uint(&mut buf, variant.fields.len() as u128);
+            for field in &variant.fields {
+                let name = self.ident(field.name);
+                let ty = self.ty(field.ty);
+                write_uint(&mut buf, name as u128);
+                write_uint(&mut buf, ty as u128);
+            }
+        }
+
+        self.begin_entry(entry::ADT_DEF);
+        self.entries.append(&mut buf);
+    }
+
+    /// Get the index of an [InstanceId], adding it to the table if needed.
+    fn instance(&mut self, id: InstanceId) -> usize {
+        if let Some(index) = self.instances.get(&id) {
+            return *index;
+        }
+
+        let instance = id.value();
+        let mut buf = Vec::new();
+
+        let name = self.ident(instance.name());
+        write_uint(&mut buf, name as u128);
+        for index in self.ty_list(instance.params) {
+            write_uint(&mut buf, index as u128);
+        }
+
+        let ret_ty = self.ty(instance.ret_ty);
+        write_uint(&mut buf, ret_ty as u128);
+        buf.push(abi_tag(instance.abi));
+        buf.push(instance.is_intrinsic() as u8);
+        buf.push(instance.is_c_variadic() as u8);
+
+        self.begin_entry(entry::INSTANCE);
+        self.entries.append(&mut buf);
+
+        let index = self.instances.len();
+        self.instances.insert(id, index);
+        index
+    }
+
+    /// Get the index of an [AllocId], adding it to the table if needed.
+    fn alloc(&mut self, id: AllocId) -> usize {
+        if let Some(index) = self.allocs.get(&id) {
+            return *index;
+        }
+
+        self.begin_entry(entry::ALLOC);
+        id.map(|alloc| {
+            write_bytes(
+                &mut self.entries,
+                alloc.read_bytes(AllocRange::new(Size::ZERO, alloc.size())),
+            );
+            write_uint(&mut self.entries, alloc.align().bytes() as u128);
+            self.entries.push(matches!(alloc.mutability(), AllocMutability::Mutable) as u8);
+        });
+
+        let index = self.allocs.len();
+        self.allocs.insert(id, index);
+        index
+    }
+
+    /// Write a [Scalar] into the given buffer.
+    fn scalar(buf: &mut Vec<u8>, scalar: Scalar) {
+        let size = scalar.size();
+        buf.push(size.bytes() as u8);
+        write_uint(buf, scalar.assert_bits(size));
+    }
+
+    /// Write a [Const] into the given buffer.
+    fn constant(&mut self, buf: &mut Vec<u8>, constant: &Const) {
+        let ty = self.ty(constant.ty());
+        write_uint(buf, ty as u128);
+
+        match constant.kind {
+            ConstKind::Zero => buf.push(0),
+            ConstKind::Scalar(scalar) => {
+                buf.push(1);
+                Self::scalar(buf, scalar);
+            }
+            ConstKind::Pair { data, len } => {
+                let data = self.alloc(data);
+                buf.push(2);
+                write_uint(buf, data as u128);
+                Self::scalar(buf, len);
+            }
+            ConstKind::Alloc { offset, alloc } => {
+                let alloc = self.alloc(alloc);
+                buf.push(3);
+                write_uint(buf, alloc as u128);
+                write_uint(buf, offset.bytes() as u128);
+            }
+            ConstKind::FnAddr(instance) => {
+                let instance = self.instance(instance);
+                buf.push(4);
+                write_uint(buf, instance as u128);
+            }
+        }
+    }
+
+    /// Write a [Place] into the given buffer.
+    fn place(buf: &mut Vec<u8>, body: &Body, place: &Place) {
+        write_uint(buf, place.local.index() as u128);
+
+        let projections = body.projections.borrow(place.projections);
+        write_uint(buf, projections.len() as u128);
+
+        for projection in projections.iter() {
+            match *projection {
+                PlaceProjection::Downcast(variant) => {
+                    buf.push(0);
+                    write_uint(buf, variant.index() as u128);
+                }
+                PlaceProjection::Field(field) => {
+                    buf.push(1);
+                    write_uint(buf, field as u128);
+                }
+                PlaceProjection::Index(local) => {
+                    buf.push(2);
+                    write_uint(buf, local.index() as u128);
+                }
+                PlaceProjection::ConstantIndex { offset, from_end, min_length } => {
+                    buf.push(3);
+                    write_uint(buf, offset as u128);
+                    buf.push(from_end as u8);
+                    write_uint(buf, min_length as u128);
+                }
+                PlaceProjection::SubSlice { from, to, from_end } => {
+                    buf.push(4);
+                    write_uint(buf, from as u128);
+                    write_uint(buf, to as u128);
+                    buf.push(from_end as u8);
+                }
+                PlaceProjection::Deref => buf.push(5),
+            }
+        }
+    }
+
+    /// Write an [Operand] into the given buffer.
+    fn operand(&mut self, buf: &mut Vec<u8>, body: &Body, operand: &Operand) {
+        match operand {
+            Operand::Const(constant) => {
+                buf.push(0);
+                self.constant(buf, constant);
+            }
+            Operand::Place(place) => {
+                buf.push(1);
+                Self::place(buf, body, place);
+            }
+        }
+    }
+
+    /// Write an [RValue] into the given buffer.
+    fn rvalue(&mut self, buf: &mut Vec<u8>, body: &Body, rvalue: &RValue) {
+        match rvalue {
+            RValue::Use(operand) => {
+                buf.push(0);
+                self.operand(buf, body, operand);
+            }
+            RValue::ConstOp(op, ty) => {
+                let ty = self.ty(*ty);
+                buf.push(1);
+                buf.push(match op {
+                    ConstOp::SizeOf => 0,
+                    ConstOp::AlignOf => 1,
+                });
+                write_uint(buf, ty as u128);
+            }
+            RValue::UnaryOp(op, operand) => {
+                buf.extend([2, u8::from(*op)]);
+                self.operand(buf, body, operand);
+            }
+            RValue::BinaryOp(op, operands) | RValue::CheckedBinaryOp(op, operands) => {
+                let checked = matches!(rvalue, RValue::CheckedBinaryOp(..));
+                buf.extend([if checked { 4 } else { 3 }, u8::from(*op)]);
+                self.operand(buf, body, &operands.0);
+                self.operand(buf, body, &operands.1);
+            }
+            RValue::Cast(kind, operand, ty) => {
+                let ty = self.ty(*ty);
+                buf.push(5);
+                buf.push(match kind {
+                    CastKind::FloatToInt => 0,
+                    CastKind::IntToFloat => 1,
+                    CastKind::IntToInt => 2,
+                    CastKind::FloatToFloat => 3,
+                });
+                self.operand(buf, body, operand);
+                write_uint(buf, ty as u128);
+            }
+            RValue::Len(place) => {
+                buf.push(6);
+                Self::place(buf, body, place);
+            }
+            RValue::Ref(mutability, place, kind) => {
+                buf.push(7);
+                buf.push(mutability.is_mutable() as u8);
+                buf.push(match kind {
+                    RefKind::Normal => 0,
+                    RefKind::Raw => 1,
+                    RefKind::Rc => 2,
+                });
+                Self::place(buf, body, place);
+            }
+            RValue::Aggregate(kind, operands) => {
+                let mut kind_buf = Vec::new();
+                match *kind {
+                    AggregateKind::Tuple(adt) => {
+                        kind_buf.push(0);
+                        write_uint(&mut kind_buf, self.adt(adt) as u128);
+                    }
+                    AggregateKind::Array(ty) => {
+                        kind_buf.push(1);
+                        write_uint(&mut kind_buf, self.ty(ty) as u128);
+                    }
+                    AggregateKind::Enum(adt, variant) => {
+                        kind_buf.push(2);
+                        write_uint(&mut kind_buf, self.adt(adt) as u128);
+                        write_uint(&mut kind_buf, variant.index() as u128);
+                    }
+                    AggregateKind::Struct(adt) => {
+                        kind_buf.push(3);
+                        write_uint(&mut kind_buf, self.adt(adt) as u128);
+                    }
+                }
+
+                buf.push(8);
+                buf.append(&mut kind_buf);
+                write_uint(buf, operands.len() as u128);
+                for operand in operands {
+                    self.operand(buf, body, operand);
+                }
+            }
+            RValue::Repeat(operand, count) => {
+                buf.push(9);
+                self.operand(buf, body, operand);
+                write_uint(buf, *count as u128);
+            }
+            RValue::Discriminant(place) => {
+                buf.push(10);
+                Self::place(buf, body, place);
+            }
+        }
+    }
+
+    /// Write a [Statement] into the given buffer.
+    fn statement(&mut self, buf: &mut Vec<u8>, body: &Body, statement: &Statement) {
+        match &statement.kind {
+            StatementKind::Nop => buf.push(0),
+            StatementKind::Assign(place, rvalue) => {
+                buf.push(1);
+                Self::place(buf, body, place);
+                self.rvalue(buf, body, rvalue);
+            }
+            StatementKind::Discriminate(place, variant) => {
+                buf.push(2);
+                Self::place(buf, body, place);
+                write_uint(buf, variant.index() as u128);
+            }
+            StatementKind::Live(local) => {
+                buf.push(3);
+                write_uint(buf, local.index() as u128);
+            }
+            StatementKind::Dead(local) => {
+                buf.push(4);
+                write_uint(buf, local.index() as u128);
+            }
+            StatementKind::Assume(operand) => {
+                buf.push(5);
+                self.operand(buf, body, operand);
+            }
+        }
+    }
+
+    /// Write an optional [BasicBlock] into the given buffer.
+    fn block(buf: &mut Vec<u8>, block: Option<BasicBlock>) {
+        // Blocks are offset by one so that `0` can denote the absence of
+        // a block.
+        write_uint(buf, block.map_or(0, |block| block.index() + 1) as u128);
+    }
+
+    /// Write a [Terminator] into the given buffer.
+    fn terminator(&mut self, buf: &mut Vec<u8>, body: &Body, terminator: &Terminator) {
+        match &terminator.kind {
+            TerminatorKind::Goto(target) => {
+                buf.push(0);
+                Self::block(buf, Some(*target));
+            }
+            TerminatorKind::Return => buf.push(1),
+            TerminatorKind::Call { op, args, destination, target, unwind } => {
+                buf.push(2);
+                self.operand(buf, body, op);
+                write_uint(buf, args.len() as u128);
+                for arg in args {
+                    self.operand(buf, body, arg);
+                }
+
+                Self::place(buf, body, destination);
+                Self::block(buf, *target);
+                Self::block(buf, *unwind);
+            }
+            TerminatorKind::Unreachable => buf.push(3),
+            TerminatorKind::Resume => buf.push(4),
+            TerminatorKind::Switch { value, targets } => {
+                buf.push(5);
+                self.operand(buf, body, value);
+                write_uint(buf, targets.values.len() as u128);
+                for (value, target) in targets.iter() {
+                    write_uint(buf, value);
+                    Self::block(buf, Some(target));
+                }
+
+                Self::block(buf, targets.otherwise);
+            }
+            TerminatorKind::Assert { condition, expected, kind, target, unwind } => {
+                buf.push(6);
+                self.operand(buf, body, condition);
+                buf.push(*expected as u8);
+
+                match kind.as_ref() {
+                    AssertKind::DivisionByZero { operand } => {
+                        buf.push(0);
+                        self.operand(buf, body, operand);
+                    }
+                    AssertKind::RemainderByZero { operand } => {
+                        buf.push(1);
+                        self.operand(buf, body, operand);
+                    }
+                    AssertKind::Overflow { op, lhs, rhs } => {
+                        buf.extend([2, u8::from(*op)]);
+                        self.operand(buf, body, lhs);
+                        self.operand(buf, body, rhs);
+                    }
+                    AssertKind::NegativeOverflow { operand } => {
+                        buf.push(3);
+                        self.operand(buf, body, operand);
+                    }
+                    AssertKind::BoundsCheck { len, index } => {
+                        buf.push(4);
+                        self.operand(buf, body, len);
+                        self.operand(buf, body, index);
+                    }
+                    AssertKind::ShiftAmountTooLarge { op, lhs, amount } => {
+                        buf.extend([5, u8::from(*op)]);
+                        self.operand(buf, body, lhs);
+                        self.operand(buf, body, amount);
+                    }
+                    AssertKind::NegativeExponent { base, exponent } => {
+                        buf.push(6);
+                        self.operand(buf, body, base);
+                        self.operand(buf, body, exponent);
+                    }
+                    AssertKind::InvalidDiscriminant { discriminant } => {
+                        buf.push(7);
+                        self.operand(buf, body, discriminant);
+                    }
+                }
+
+                Self::block(buf, Some(*target));
+                Self::block(buf, *unwind);
+            }
+            TerminatorKind::Drop { place, target } => {
+                buf.push(7);
+                Self::place(buf, body, place);
+                Self::block(buf, Some(*target));
+            }
+        }
+    }
+}
+
+/// [IrBinaryDecoder] is used to read [Body]s that were written with the
+/// [IrBinaryEncoder]. All of the types, ADTs, instances and allocations that
+/// the bodies reference are interned into the current session.
+pub struct IrBinaryDecoder<'data> {
+    /// The data that is being decoded.
+    data: &'data [u8],
+
+    /// The current position within the data.
+    position: usize,
+
+    /// All of the decoded identifiers.
+    idents: Vec<Identifier>,
+
+    /// All of the decoded types.
+    tys: Vec<ReprTyId>,
+
+    /// All of the decoded ADTs.
+    adts: Vec<AdtId>,
+
+    /// All of the decoded instances.
+    instances: Vec<InstanceId>,
+
+    /// All of the decoded allocations.
+    allocs: Vec<AllocId>,
+
+    /// A copy of the common types, in the order that they were encoded.
+    common_tys: Vec<ReprTyId>,
+}
+
+impl<'data> IrBinaryDecoder<'data> {
+    /// Create a new [IrBinaryDecoder] for the given data.
+    pub fn new(data: &'data [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            idents: Vec::new(),
+            tys: Vec::new(),
+            adts: Vec::new(),
+            instances: Vec::new(),
+            allocs: Vec::new(),
+            common_tys: COMMON_REPR_TYS.iter().collect(),
+        }
+    }
+
+    /// Decode all of the [Body]s within the data.
+    pub fn decode(mut self) -> Result<Vec<Body>, DecodeError> {
+        if self.data.get(..MAGIC.len()) != Some(MAGIC.as_slice()) {
+            return Err(DecodeError::InvalidMagic);
+        }
+
+        self.position = MAGIC.len();
+
+        let version = self.read_uint()? as u64;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        for _ in 0..self.read_usize()? {
+            self.read_entry()?;
+        }
+
+        (0..self.read_usize()?).map(|_| self.read_body()).collect()
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        let byte = *self.data.get(self.position).ok_or(DecodeError::UnexpectedEof)?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, DecodeError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    fn read_uint(&mut self) -> Result<u128, DecodeError> {
+        let mut value = 0u128;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.read_u8()?;
+
+            if shift >= 128 {
+                return Err(DecodeError::InvalidTag { kind: "integer", tag: byte as u64 });
+            }
+
+            value |= ((byte & 0x7f) as u128) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn read_usize(&mut self) -> Result<usize, DecodeError> {
+        Ok(self.read_uint()? as usize)
+    }
+
+    fn read_bytes(&mut self) -> Result<&'data [u8], DecodeError> {
+        let len = self.read_usize()?;
+        let bytes = self
+            .data
+            .get(self.position..self.position.saturating_add(len))
+            .ok_or(DecodeError::UnexpectedEof)?;
+
+        self.position += len;
+        Ok(bytes)
+    }
+
+    /// Look up an interned value by the index that is read next.
+    fn read_index<T: Copy>(
+        &mut self,
+        kind: &'static str,
+        table: fn(&Self) -> &[T],
+    ) -> Result<T, DecodeError> {
+        let index = self.read_usize()?;
+        table(self).get(index).copied().ok_or(DecodeError::InvalidIndex { kind, index })
+    }
+
+    fn read_ident(&mut self) -> Result<Identifier, DecodeError> {
+        self.read_index("identifier", |this| this.idents.as_slice())
+    }
+
+    fn read_ty(&mut self) -> Result<ReprTyId, DecodeError> {
+        self.read_index("type", |this| this.tys.as_slice())
+    }
+
+    fn read_adt(&mut self) -> Result<AdtId, DecodeError> {
+        self.read_index("ADT", |this| this.adts.as_slice())
+    }
+
+    fn read_instance(&mut self) -> Result<InstanceId, DecodeError> {
+        self.read_index("instance", |this| this.instances.as_slice())
+    }
+
+    fn read_alloc(&mut self) -> Result<AllocId, DecodeError> {
+        self.read_index("allocation", |this| this.allocs.as_slice())
+    }
+
+    fn read_common_ty(&mut self) -> Result<ReprTyId, DecodeError> {
+        self.read_index("common type", |this| this.common_tys.as_slice())
+    }
+
+    fn read_ty_list(&mut self) -> Result<ReprTyListId, DecodeError> {
+        let tys = (0..self.read_usize()?).map(|_| self.read_ty()).collect::<Result<Vec<_>, _>>()?;
+        Ok(ReprTyListId::seq(tys))
+    }
+
+    fn read_int_ty(&mut self) -> Result<IntTy, DecodeError> {
+        Ok(match self.read_u8()? {
+            0 => IntTy::Int(SIntTy::I8),
+            1 => IntTy::Int(SIntTy::I16),
+            2 => IntTy::Int(SIntTy::I32),
+            3 => IntTy::Int(SIntTy::I64),
+            4 => IntTy::Int(SIntTy::I128),
+            5 => IntTy::Int(SIntTy::ISize),
+            6 => IntTy::UInt(UIntTy::U8),
+            7 => IntTy::UInt(UIntTy::U16),
+            8 => IntTy::UInt(UIntTy::U32),
+            9 => IntTy::UInt(UIntTy::U64),
+            10 => IntTy::UInt(UIntTy::U128),
+            11 => IntTy::UInt(UIntTy::USize),
+            12 => IntTy::Big(BigIntTy::IBig),
+            13 => IntTy::Big(BigIntTy::UBig),
+            tag => return Err(DecodeError::InvalidTag { kind: "integer type", tag: tag as u64 }),
+        })
+    }
+
+    fn read_mutability(&mut self) -> Result<Mutability, DecodeError> {
+        Ok(if self.read_bool()? { Mutability::Mutable } else { Mutability::Immutable })
+    }
+
+    fn read_ref_kind(&mut self) -> Result<RefKind, DecodeError> {
+        Ok(match self.read_u8()? {
+            0 => RefKind::Normal,
+            1 => RefKind::Raw,
+            2 => RefKind::Rc,
+            tag => return Err(DecodeError::InvalidTag { kind: "reference kind", tag: tag as u64 }),
+        })
+    }
+
+    fn read_abi(&mut self) -> Result<Abi, DecodeError> {
+        Ok(match self.read_u8()? {
+            0 => Abi::C,
+            1 => Abi::Cold,
+            2 => Abi::Hash,
+            3 => Abi::Fast,
+            4 => Abi::System,
+            tag => return Err(DecodeError::InvalidTag { kind: "ABI", tag: tag as u64 }),
+        })
+    }
+
+    fn read_alignment(&mut self) -> Result<Alignment, DecodeError> {
+        let bytes = self.read_uint()? as u64;
+        Alignment::from_bytes(bytes)
+            .map_err(|_| DecodeError::InvalidTag { kind: "alignment", tag: bytes })
+    }
+
+    /// Read an entry of the table of interned values.
+    fn read_entry(&mut self) -> Result<(), DecodeError> {
+        match self.read_u8()? {
+            entry::IDENT => {
+                let name = std::str::from_utf8(self.read_bytes()?)
+                    .map_err(|_| DecodeError::InvalidUtf8)?;
+                self.idents.push(name.into());
+            }
+            entry::TY => {
+                let ty = match self.read_u8()? {
+                    0 => self.read_common_ty()?,
+                    1 => {
+                        let inner = self.read_ty()?;
+                        let mutability = self.read_mutability()?;
+                        let kind = self.read_ref_kind()?;
+                        ReprTy::create(ReprTy::Ref(inner, mutability, kind))
+                    }
+                    2 => ReprTy::create(ReprTy::Slice(self.read_ty()?)),
+                    3 => {
+                        let ty = self.read_ty()?;
+                        let length = self.read_usize()?;
+                        ReprTy::create(ReprTy::Array { ty, length })
+                    }
+                    4 => ReprTy::create(ReprTy::Adt(self.read_adt()?)),
+                    5 => {
+                        let params = self.read_ty_list()?;
+                        let return_ty = self.read_ty()?;
+                        let abi = self.read_abi()?;
+                        ReprTy::create(ReprTy::Fn { params, return_ty, abi })
+                    }
+                    6 => ReprTy::create(ReprTy::FnDef { instance: self.read_instance()? }),
+                    tag => return Err(DecodeError::InvalidTag { kind: "type", tag: tag as u64 }),
+                };
+
+                self.tys.push(ty);
+            }
+            entry::ADT_DECL => {
+                let adt = if self.read_bool()? {
+                    self.read_common_ty()?.borrow().as_adt()
+                } else {
+                    // The definition of the ADT is filled in when the definition
+                    // entry is read.
+                    Adt::create(Adt::new(Identifier::from(""), IndexVec::new()))
+                };
+
+                self.adts.push(adt);
+            }
+            entry::ADT_DEF => {
+                let id = self.read_adt()?;
+                let name = self.read_ident()?;
+                let flags = AdtFlags::from_bits_truncate(self.read_uint()? as u32);
+                let representation =
+                    RepresentationFlags::from_bits_truncate(self.read_uint()? as u32);
+
+                let mut metadata = AdtRepresentation::default();
+                metadata.add_flags(representation);
+                metadata.discriminant =
+                    if self.read_bool()? { Some(self.read_int_ty()?) } else { None };
+                metadata.alignment =
+                    if self.read_bool()? { Some(self.read_alignment()?) } else { None };
+
+                let substitutions =
+                    if self.read_bool()? { Some(self.read_ty_list()?) } else { None };
+
+                let mut variants = IndexVec::new();
+                for _ in 0..self.read_usize()? {
+                    let name = self.read_ident()?;
+                    let value = self.read_uint()?;
+                    let ty = self.read_int_ty()?;
+                    let kind = match self.read_u8()? {
+                        0 => DiscriminantKind::Relative(self.read_uint()? as u32),
+                        1 => DiscriminantKind::Explicit,
+                        tag => {
+                            return Err(DecodeError::InvalidTag {
+                                kind: "discriminant",
+                                tag: tag as u64,
+                            })
+                        }
+                    };
+
+                    let fields = (0..self.read_usize()?)
+                        .map(|_| Ok(AdtField { name: self.read_ident()?, ty: self.read_ty()? }))
+                        .collect::<Result<_, DecodeError>>()?;
+
+                    variants.push(AdtVariant {
+                        name,
+                        fields,
+                        discriminant: Discriminant { value, ty, kind },
+                    });
+                }
+
+                let mut adt = Adt::new_with_flags(name, variants, flags);
+                adt.metadata = metadata;
+                adt.substitutions = substitutions;
+                id.set(adt);
+            }
+            entry::INSTANCE => {
+                let name = self.read_ident()?;
+                let params = self.read_ty_list()?;
+                let ret_ty = self.read_ty()?;
+
+                let mut instance = Instance::new(name, None, params, ret_ty, AstNodeId::null());
+                instance.abi = self.read_abi()?;
+                instance.is_intrinsic = self.read_bool()?;
+                instance.is_c_variadic = self.read_bool()?;
+
+                self.instances.push(Instance::create(instance));
+            }
+            entry::ALLOC => {
+                let bytes = self.read_bytes()?;
+                let align = self.read_alignment()?;
+                let mutability = if self.read_bool()? {
+                    AllocMutability::Mutable
+                } else {
+                    AllocMutability::Immutable
+                };
+
+                self.allocs.push(Alloc::create(Alloc::from_bytes(bytes, align, mutability)));
+            }
+            tag => return Err(DecodeError::InvalidTag { kind: "entry", tag: tag as u64 }),
+        }
+
+        Ok(())
+    }
+
+    fn read_scalar(&mut self) -> Result<Scalar, DecodeError> {
+        let size = Size::from_bytes(self.read_u8()?);
+        let value = self.read_uint()?;
+
+        // Scalars cannot be zero sized, and must be able to hold the value.
+        if size.bytes() == 0 {
+            return Err(DecodeError::InvalidTag { kind: "scalar", tag: 0 });
+        }
+
+        Scalar::try_from_uint(value, size)
+            .ok_or(DecodeError::InvalidTag { kind: "scalar", tag: size.bytes() })
+    }
+
+    fn read_const(&mut self) -> Result<Const, DecodeError> {
+        let ty = self.read_ty()?;
+        let kind = match self.read_u8()? {
+            0 => ConstKind::Zero,
+            1 => ConstKind::Scalar(self.read_scalar()?),
+            2 => ConstKind::Pair { data: self.read_alloc()?, len: self.read_scalar()? },
+            3 => {
+                let alloc = self.read_alloc()?;
+                ConstKind::Alloc { alloc, offset: Size::from_bytes(self.read_uint()? as u64) }
+            }
+            4 => ConstKind::FnAddr(self.read_instance()?),
+            tag => return Err(DecodeError::InvalidTag { kind: "constant", tag: tag as u64 }),
+        };
+
+        Ok(Const::new(ty, kind))
+    }
+
+    fn read_local(&mut self) -> Result<Local, DecodeError> {
+        Ok(Local::new(self.read_usize()?))
+    }
+
+    fn read_block(&mut self) -> Result<Option<BasicBlock>, DecodeError> {
+        Ok(self.read_usize()?.checked_sub(1).map(BasicBlock::new))
+    }
+
+    fn read_target(&mut self) -> Result<BasicBlock, DecodeError> {
+        self.read_block()?.ok_or(DecodeError::InvalidIndex { kind: "block", index: 0 })
+    }
+
+    fn read_place(&mut self, projections: &mut Projections) -> Result<Place, DecodeError> {
+        let local = self.read_local()?;
+        let items = (0..self.read_usize()?)
+            .map(|_| {
+                Ok(match self.read_u8()? {
+                    0 => PlaceProjection::Downcast(VariantIdx::new(self.read_usize()?)),
+                    1 => PlaceProjection::Field(self.read_usize()?),
+                    2 => PlaceProjection::Index(self.read_local()?),
+                    3 => PlaceProjection::ConstantIndex {
+                        offset: self.read_usize()?,
+                        from_end: self.read_bool()?,
+                        min_length: self.read_usize()?,
+                    },
+                    4 => PlaceProjection::SubSlice {
+                        from: self.read_usize()?,
+                        to: self.read_usize()?,
+                        from_end: self.read_bool()?,
+                    },
+                    5 => PlaceProjection::Deref,
+                    tag => {
+                        return Err(DecodeError::InvalidTag { kind: "projection", tag: tag as u64 })
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Place { local, projections: projections.create_from_slice(&items) })
+    }
+
+    fn read_operand(&mut self, projections: &mut Projections) -> Result<Operand, DecodeError> {
+        match self.read_u8()? {
+            0 => Ok(Operand::Const(self.read_const()?)),
+            1 => Ok(Operand::Place(self.read_place(projections)?)),
+            tag => Err(DecodeError::InvalidTag { kind: "operand", tag: tag as u64 }),
+        }
+    }
+
+    fn read_bin_op(&mut self) -> Result<BinOp, DecodeError> {
+        let tag = self.read_u8()?;
+        BinOp::try_from(tag)
+            .map_err(|_| DecodeError::InvalidTag { kind: "binary operator", tag: tag as u64 })
+    }
+
+    fn read_rvalue(&mut self, projections: &mut Projections) -> Result<RValue, DecodeError> {
+        Ok(match self.read_u8()? {
+            0 => RValue::Use(self.read_operand(projections)?),
+            1 => {
+                let op = match self.read_u8()? {
+                    0 => ConstOp::SizeOf,
+                    1 => ConstOp::AlignOf,
+                    tag => {
+                        return Err(DecodeError::InvalidTag { kind: "const op", tag: tag as u64 })
+                    }
+                };
+
+                RValue::ConstOp(op, self.read_ty()?)
+            }
+            2 => {
+                let tag = self.read_u8()?;
+                let op = UnOp::try_from(tag).map_err(|_| DecodeError::InvalidTag {
+                    kind: "unary operator",
+                    tag: tag as u64,
+                })?;
+
+                RValue::UnaryOp(op, self.read_operand(projections)?)
+            }
+            tag @ (3 | 4) => {
+                let op = self.read_bin_op()?;
+                let operands =
+                    Box::new((self.read_operand(projections)?, self.read_operand(projections)?));
+
+                if tag == 3 {
+                    RValue::BinaryOp(op, operands)
+                } else {
+                    RValue::CheckedBinaryOp(op, operands)
+                }
+            }
+            5 => {
+                let kind = match self.read_u8()? {
+                    0 => CastKind::FloatToInt,
+                    1 => CastKind::IntToFloat,
+                    2 => CastKind::IntToInt,
+                    3 => CastKind::FloatToFloat,
+                    tag => return Err(DecodeError::InvalidTag { kind: "cast", tag: tag as u64 }),
+                };
+
+                RValue::Cast(kind, self.read_operand(projections)?, self.read_ty()?)
+            }
+            6 => RValue::Len(self.read_place(projections)?),
+            7 => {
+                let mutability = self.read_mutability()?;
+                let kind = self.read_ref_kind()?;
+                RValue::Ref(mutability, self.read_place(projections)?, kind)
+            }
+            8 => {
+                let kind = match self.read_u8()? {
+                    0 => AggregateKind::Tuple(self.read_adt()?),
+                    1 => AggregateKind::Array(self.read_ty()?),
+                    2 => AggregateKind::Enum(self.read_adt()?, VariantIdx::new(self.read_usize()?)),
+                    3 => AggregateKind::Struct(self.read_adt()?),
+                    tag => {
+                        return Err(DecodeError::InvalidTag { kind: "aggregate", tag: tag as u64 })
+                    }
+                };
+
+                let operands = (0..self.read_usize()?)
+                    .map(|_| self.read_operand(projections))
+                    .collect::<Result<_, _>>()?;
+
+                RValue::Aggregate(kind, operands)
+            }
+            9 => RValue::Repeat(self.read_operand(projections)?, self.read_usize()?),
+            10 => RValue::Discriminant(self.read_place(projections)?),
+            tag => return Err(DecodeError::InvalidTag { kind: "rvalue", tag: tag as u64 }),
+        })
+    }
+
+    fn read_statement(&mut self, projections: &mut Projections) -> Result<Statement, DecodeError> {
+        let kind = match self.read_u8()? {
+            0 => StatementKind::Nop,
+            1 => {
+                StatementKind::Assign(self.read_place(projections)?, self.read_rvalue(projections)?)
+            }
+            2 => StatementKind::Discriminate(
+                self.read_place(projections)?,
+                VariantIdx::new(self.read_usize()?),
+            ),
+            3 => StatementKind::Live(self.read_local()?),
+            4 => StatementKind::Dead(self.read_local()?),
+            5 => StatementKind::Assume(self.read_operand(projections)?),
+            tag => return Err(DecodeError::InvalidTag { kind: "statement", tag: tag as u64 }),
+        };
+
+        Ok(Statement { kind, origin: AstNodeId::null() })
+    }
+
+    fn read_terminator(
+        &mut self,
+        projections: &mut Projections,
+    ) -> Result<Terminator, DecodeError> {
+        let kind = match self.read_u8()? {
+            0 => TerminatorKind::Goto(self.read_target()?),
+            1 => TerminatorKind::Return,
+            2 => {
+                let op = self.read_operand(projections)?;
+                let args = (0..self.read_usize()?)
+                    .map(|_| self.read_operand(projections))
+                    .collect::<Result<_, _>>()?;
+
+                TerminatorKind::Call {
+                    op,
+                    args,
+                    destination: self.read_place(projections)?,
+                    target: self.read_block()?,
+                    unwind: self.read_block()?,
+                }
+            }
+            3 => TerminatorKind::Unreachable,
+            4 => TerminatorKind::Resume,
+            5 => {
+                let value = self.read_operand(projections)?;
+                let targets = (0..self.read_usize()?)
+                    .map(|_| Ok((self.read_uint()?, self.read_target()?)))
+                    .collect::<Result<Vec<_>, DecodeError>>()?;
+
+                let targets = SwitchTargets::new(targets.into_iter(), self.read_block()?);
+                TerminatorKind::Switch { value, targets }
+            }
+            6 => {
+                let condition = self.read_operand(projections)?;
+                let expected = self.read_bool()?;
+                let kind = match self.read_u8()? {
+                    0 => AssertKind::DivisionByZero { operand: self.read_operand(projections)? },
+                    1 => AssertKind::RemainderByZero { operand: self.read_operand(projections)? },
+                    2 => AssertKind::Overflow {
+                        op: self.read_bin_op()?,
+                        lhs: self.read_operand(projections)?,
+                        rhs: self.read_operand(projections)?,
+                    },
+                    3 => AssertKind::NegativeOverflow { operand: self.read_operand(projections)? },
+                    4 => AssertKind::BoundsCheck {
+                        len: self.read_operand(projections)?,
+                        index: self.read_operand(projections)?,
+                    },
+                    5 => AssertKind::ShiftAmountTooLarge {
+                        op: self.read_bin_op()?,
+                        lhs: self.read_operand(projections)?,
+                        amount: self.read_operand(projections)?,
+                    },
+                    6 => AssertKind::NegativeExponent {
+                        base: self.read_operand(projections)?,
+                        exponent: self.read_operand(projections)?,
+                    },
+                    7 => AssertKind::InvalidDiscriminant {
+                        discriminant: self.read_operand(projections)?,
+                    },
+                    tag => return Err(DecodeError::InvalidTag { kind: "assert", tag: tag as u64 }),
+                };
+
+                TerminatorKind::Assert {
+                    condition,
+                    expected,
+                    kind: Box::new(kind),
+                    target: self.read_target()?,
+                    unwind: self.read_block()?,
+                }
+            }
+            7 => TerminatorKind::Drop {
+                place: self.read_place(projections)?,
+                target: self.read_target()?,
+            },
+            tag => return Err(DecodeError::InvalidTag { kind: "terminator", tag: tag as u64 }),
+        };
+
+        Ok(Terminator { kind, origin: AstNodeId::null() })
+    }
+
+    fn read_body(&mut self) -> Result<Body, DecodeError> {
+        let name = self.read_ident()?;
+        let source = match self.read_u8()? {
+            0 => BodySource::Const,
+            1 => BodySource::Item,
+            2 => BodySource::Derived,
+            tag => return Err(DecodeError::InvalidTag { kind: "body source", tag: tag as u64 }),
+        };
+
+        let mut metadata = BodyMetadata::new(name, source);
+        metadata.set_ty(self.read_ty()?);
+
+        let arg_count = self.read_usize()?;
+        let dump = self.read_bool()?;
+
+        for _ in 0..self.read_usize()? {
+            let kind = match self.read_u8()? {
+                0 => UnsafeOpKind::PtrOffset,
+                1 => UnsafeOpKind::PtrRead,
+                2 => UnsafeOpKind::PtrWrite,
+                3 => UnsafeOpKind::RawDeref,
+                4 => UnsafeOpKind::Transmute,
+                5 => UnsafeOpKind::ExternCall,
+                6 => UnsafeOpKind::UnsafeCall,
+                tag => return Err(DecodeError::InvalidTag { kind: "unsafe op", tag: tag as u64 }),
+            };
+
+            let in_unsafe_block = self.read_bool()?;
+            metadata.unsafe_ops.push(UnsafeOp { kind, in_unsafe_block, origin: AstNodeId::null() });
+        }
+
+        let mut locals = IndexVec::new();
+        for _ in 0..self.read_usize()? {
+            let flags = self.read_u8()?;
+            let mutability =
+                if flags & 0b001 != 0 { Mutability::Mutable } else { Mutability::Immutable };
+            let name = if flags & 0b100 != 0 { Some(self.read_ident()?) } else { None };
+            let ty = self.read_ty()?;
+
+            let mut decl = if flags & 0b010 != 0 {
+                LocalDecl::new_auxiliary(ty, mutability)
+            } else {
+                LocalDecl::new(Identifier::from(""), mutability, ty)
+            };
+
+            decl.name = name;
+            locals.push(decl);
+        }
+
+        let mut projections = Projections::new();
+        let mut blocks = IndexVec::new();
+
+        for _ in 0..self.read_usize()? {
+            let statements = (0..self.read_usize()?)
+                .map(|_| self.read_statement(&mut projections))
+                .collect::<Result<_, _>>()?;
+
+            let terminator = if self.read_bool()? {
+                Some(self.read_terminator(&mut projections)?)
+            } else {
+                None
+            };
+
+            blocks.push(BasicBlockData { statements, terminator });
+        }
+
+        let mut body =
+            Body::new(blocks, locals, projections, metadata, arg_count, AstNodeId::null());
+
+        if dump {
+            body.mark_to_dump();
+        }
+
+        Ok(body)
+    }
+}
+
+/// Encode the given [Body]s into the binary format.
+pub fn encode_ir_bodies<'a>(bodies: impl IntoIterator<Item = &'a Body>) -> Vec<u8> {
+    let mut encoder = IrBinaryEncoder::new();
+
+    for body in bodies {
+        encoder.encode_body(body);
+    }
+
+    encoder.finish()
+}
+
+/// Decode all of the [Body]s from data that was produced with
+/// [encode_ir_bodies].
+pub fn decode_ir_bodies(data: &[u8]) -> Result<Vec<Body>, DecodeError> {
+    IrBinaryDecoder::new(data).decode()
+}
+
+/// Write all of the specified bodies in the binary format.
+pub fn dump_ir_bodies(
+    bodies: &[Body],
+    dump_all: bool,
+    prelude_is_quiet: bool,
+    writer: &mut impl io::Write,
+) -> io::Result<()> {
+    let bodies = bodies.iter().filter(|body| {
+        // Skip the prelude if we're in quiet mode, and skip any bodies
+        // which weren't requested to be dumped.
+        !(prelude_is_quiet && body.source().is_prelude()) && (dump_all || body.needs_dumping())
+    });
+
+    writer.write_all(&encode_ir_bodies(bodies))
+}
+
+#[cfg(test)]
+mod tests {
+    use hash_ast::ast::AstNodeId;
+    use hash_ir::{
+        ir::{
+            BasicBlockData, BinOp, Body, BodyMetadata, BodySource, Const, Local, LocalDecl,
+            Operand, Place, Projections, RValue, Scalar, Statement, StatementKind, Terminator,
+            TerminatorKind,
+        },
+        ty::{Mutability, COMMON_REPR_TYS},
+    };
+    use hash_repr::{compute::LayoutComputer, LayoutStorage};
+    use hash_target::{data_layout::TargetDataLayout, size::Size};
+    use hash_utils::index_vec::IndexVec;
+
+    use crate::{
+        binary::{decode_ir_bodies, encode_ir_bodies, DecodeError},
+        IrWriter, WriteIr,
+    };
+
+    /// Print all of the statements and terminators of the given [Body].
+    fn print_body(body: &Body, lc: LayoutComputer) -> Vec<String> {
+        body.blocks()
+            .iter()
+            .flat_map(|data| {
+                let statements = data
+                    .statements
+                    .iter()
+                    .map(|statement| format!("{}", IrWriter::new(statement, body.aux(), lc)));
+                let terminator = data
+                    .terminator
+                    .as_ref()
+                    .map(|terminator| format!("{}", terminator.with_edges(body.aux(), lc, true)));
+
+                statements.chain(terminator).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let i32_ty = COMMON_REPR_TYS.i32;
+        let mut locals = IndexVec::new();
+        locals.push(LocalDecl::new_auxiliary(i32_ty, Mutability::Mutable));
+        locals.push(LocalDecl::new_immutable("x".into(), i32_ty));
+
+        let one = Const::scalar(Scalar::from_uint(1u32, Size::from_bytes(4)), i32_ty);
+        let rvalue = RValue::BinaryOp(
+            BinOp::Add,
+            Box::new((Operand::Place(Place::from_local(Local::new(1))), Operand::Const(one))),
+        );
+
+        let mut data = BasicBlockData::new(Some(Terminator {
+            kind: TerminatorKind::Return,
+            origin: AstNodeId::null(),
+        }));
+        data.statements.push(Statement {
+            kind: StatementKind::Assign(Place::return_place(), rvalue),
+            origin: AstNodeId::null(),
+        });
+
+        let mut metadata = BodyMetadata::new("inc".into(), BodySource::Item);
+        metadata.set_ty(i32_ty);
+
+        let mut blocks = IndexVec::new();
+        blocks.push(data);
+        let body = Body::new(blocks, locals, Projections::new(), metadata, 1, AstNodeId::null());
+
+        let data = encode_ir_bodies([&body]);
+        let decoded = decode_ir_bodies(&data).unwrap();
+
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].metadata().name(), body.metadata().name());
+        assert_eq!(decoded[0].arg_count, body.arg_count);
+        assert_eq!(decoded[0].locals.raw, body.locals.raw);
+        assert_eq!(print_body(&decoded[0], lc), print_body(&body, lc));
+
+        // Truncated data should be rejected, rather than producing a partial body.
+        let result = decode_ir_bodies(&data[..data.len() - 1]);
+        assert!(matches!(result, Err(DecodeError::UnexpectedEof)));
+    }
+}
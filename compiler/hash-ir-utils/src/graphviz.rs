@@ -10,10 +10,14 @@
 use std::io;
 
 use hash_const_eval::print::pretty_print_const;
-use hash_ir::ir::{BasicBlock, BasicBlockData, Body, BodySource, TerminatorKind};
+use hash_ir::{
+    call_graph::{CallGraph, CallGraphEdge, CallGraphEdgeKind, Reachable},
+    ir::{BasicBlock, BasicBlockData, Body, BodySource, TerminatorKind},
+};
 use hash_repr::{compute::LayoutComputer, constant::Const};
+use hash_source::{SourceId, SourceMapUtils};
 use hash_target::data_layout::HasDataLayout;
-use hash_utils::derive_more::Constructor;
+use hash_utils::{derive_more::Constructor, indexmap::IndexMap};
 use html_escape::encode_text;
 
 use crate::WriteIr;
@@ -83,9 +87,15 @@ impl<'ir> IrGraphWriter<'ir> {
         // return_type declaration, this is always located at `0`
         let return_ty_decl = declarations.next().unwrap();
 
+        // The title of the graph is the name of the body, followed by the location
+        // of the definition that it was lowered from.
+        let title =
+            format!("{} ({})", self.body.metadata().name, self.body.source_span().fmt_location());
+        write!(w, "  label=<{}{}", encode_text(&title), LINE_SEPARATOR)?;
+
         match self.body.metadata().source() {
-            BodySource::Item => {
-                write!(w, "  label=<{}(", self.body.metadata().name,)?;
+            BodySource::Item | BodySource::Derived => {
+                write!(w, "{}(", self.body.metadata().name)?;
 
                 // Write the arguments of the function
                 for (i, param) in declarations.take(self.body.arg_count).enumerate() {
@@ -110,7 +120,7 @@ impl<'ir> IrGraphWriter<'ir> {
                 // @@Todo: maybe figure out a better format for this?
                 write!(
                     w,
-                    "  label=<{}{}{}",
+                    "{}{}{}",
                     self.body.metadata().name,
                     encode_text(&header),
                     LINE_SEPARATOR
@@ -154,12 +164,21 @@ impl<'ir> IrGraphWriter<'ir> {
                     TerminatorKind::Assert { target, .. } | TerminatorKind::Goto(target) => {
                         writeln!(w, r#"  {prefix}{id:?} -> {prefix}{target:?} [label=""];"#)?;
                     }
-                    TerminatorKind::Call { target, .. } if target.is_some() => {
+                    TerminatorKind::Drop { target, .. } => {
+                        writeln!(w, r#"  {prefix}{id:?} -> {prefix}{target:?} [label="drop"];"#)?;
+                    }
+                    TerminatorKind::Call { target: Some(target), .. } => {
+                        // Indirect calls are denoted with a dashed edge, since the
+                        // callee is only known at runtime.
+                        let style = if terminator.is_indirect_call(&self.body.aux()) {
+                            r#", style="dashed""#
+                        } else {
+                            ""
+                        };
+
                         writeln!(
                             w,
-                            r#"  {prefix}{:?} -> {prefix}{:?} [label="return"];"#,
-                            id,
-                            target.unwrap()
+                            r#"  {prefix}{id:?} -> {prefix}{target:?} [label="return"{style}];"#,
                         )?;
                     }
                     TerminatorKind::Switch { targets, value } => {
@@ -187,7 +206,17 @@ impl<'ir> IrGraphWriter<'ir> {
                     }
                     TerminatorKind::Call { .. }
                     | TerminatorKind::Unreachable
-                    | TerminatorKind::Return => {}
+                    | TerminatorKind::Return
+                    | TerminatorKind::Resume => {}
+                }
+
+                // Unwind edges are denoted with a dotted edge since they are
+                // only taken when the program panics.
+                if let Some(unwind) = terminator.unwind() {
+                    writeln!(
+                        w,
+                        r#"  {prefix}{id:?} -> {prefix}{unwind:?} [label="unwind", style="dotted"];"#
+                    )?;
                 }
             }
         }
@@ -304,3 +333,133 @@ pub fn dump_ir_bodies(
 
     writeln!(writer, "}}")
 }
+
+/// [IrCallGraphWriter] is used to emit a [CallGraph] as a single `.dot`
+/// graph. Each module is written as a separate cluster within the graph.
+///
+/// If the [Reachable] nodes of the graph are provided, then any nodes that
+/// aren't reachable are either omitted, or drawn in grey.
+pub struct IrCallGraphWriter<'g> {
+    /// The call graph that is being written.
+    graph: &'g CallGraph,
+
+    /// The nodes of the graph that are reachable from the roots of the
+    /// graph, if any roots were specified.
+    reachable: Option<&'g Reachable>,
+
+    /// Whether nodes that aren't reachable should be omitted from the graph.
+    only_reachable: bool,
+
+    /// Options for the style of the graph that is being emitted.
+    options: IrGraphOptions,
+}
+
+impl<'g> IrCallGraphWriter<'g> {
+    /// Create a new writer for the given [CallGraph].
+    pub fn new(
+        graph: &'g CallGraph,
+        reachable: Option<&'g Reachable>,
+        only_reachable: bool,
+    ) -> Self {
+        Self { graph, reachable, only_reachable, options: IrGraphOptions::default() }
+    }
+
+    /// Check whether a node of the graph is reachable, all nodes are
+    /// considered reachable if no roots were specified.
+    fn is_reachable(&self, index: usize) -> bool {
+        self.reachable.map_or(true, |reachable| reachable.contains(index))
+    }
+
+    /// Check whether a node of the graph should be written.
+    fn is_written(&self, index: usize) -> bool {
+        !self.only_reachable || self.is_reachable(index)
+    }
+
+    /// Write a node of the graph.
+    fn write_node(&self, w: &mut impl io::Write, index: usize, indent: &str) -> io::Result<()> {
+        let node = self.graph.node(index);
+        let style = if node.has_body() {
+            "solid"
+        } else if node.is_unknown() {
+            "dotted"
+        } else {
+            "dashed"
+        };
+
+        let label = match node.span {
+            Some(span) => format!("{} ({})", node.name, span.fmt_location()),
+            None => format!("{}", node.name),
+        };
+
+        let colour =
+            if self.is_reachable(index) { "" } else { ", color=\"grey\", fontcolor=\"grey\"" };
+
+        writeln!(
+            w,
+            "{indent}f{index} [label=<{}>, style=\"{style}\"{colour}];",
+            encode_text(&label)
+        )
+    }
+
+    /// Function that writes the call graph to the appropriate writer.
+    pub fn write_graph(&self, w: &mut impl io::Write) -> io::Result<()> {
+        writeln!(w, "digraph call_graph {{")?;
+        writeln!(w, "  graph [fontname=\"{}\"];", self.options.font)?;
+        writeln!(w, "  node [fontname=\"{}\", shape=\"box\"];", self.options.font)?;
+        writeln!(w, "  edge [fontname=\"{}\"];", self.options.font)?;
+
+        // Group all of the nodes by the module that they are defined in.
+        let mut modules: IndexMap<SourceId, Vec<usize>> = IndexMap::new();
+
+        for (index, node) in self.graph.nodes() {
+            if !self.is_written(index) {
+                continue;
+            }
+
+            match node.source {
+                Some(source) => modules.entry(source).or_default().push(index),
+                None => self.write_node(w, index, "  ")?,
+            }
+        }
+
+        for (cluster, (source, nodes)) in modules.iter().enumerate() {
+            let name = SourceMapUtils::map(*source, |source| source.name().to_string());
+
+            writeln!(w, "  subgraph cluster_{cluster} {{")?;
+            writeln!(w, "    label=<{}>;", encode_text(&name))?;
+            writeln!(w, "    style=\"rounded\";")?;
+
+            for index in nodes {
+                self.write_node(w, *index, "    ")?;
+            }
+
+            writeln!(w, "  }}")?;
+        }
+
+        for CallGraphEdge { caller, callee, kind, count } in self.graph.edges() {
+            if !self.is_written(caller) || !self.is_written(callee) {
+                continue;
+            }
+
+            let mut attrs = vec![];
+
+            if count > 1 {
+                attrs.push(format!("label=\"{count}\""));
+            }
+
+            // References to functions are drawn as dashed edges, since they
+            // don't necessarily result in a call.
+            if kind == CallGraphEdgeKind::Reference {
+                attrs.push("style=\"dashed\"".to_string());
+            }
+
+            if attrs.is_empty() {
+                writeln!(w, "  f{caller} -> f{callee};")?;
+            } else {
+                writeln!(w, "  f{caller} -> f{callee} [{}];", attrs.join(", "))?;
+            }
+        }
+
+        writeln!(w, "}}")
+    }
+}
@@ -0,0 +1,267 @@
+//! Implementation of a JSON writer for the IR. This writing backend emits
+//! each [Body] into a structured JSON document, which can be consumed by
+//! external tooling without needing to parse the pretty-printed format of
+//! the IR. Any IR components that are not broken down any further, i.e.
+//! places, operands and rvalues are emitted in their pretty-printed form.
+
+use std::io;
+
+use hash_ir::{
+    call_graph::{CallGraph, Reachable},
+    ir::{BasicBlockData, Body, Statement, StatementKind, Terminator, TerminatorKind},
+    ty::Mutability,
+};
+use hash_repr::compute::LayoutComputer;
+use hash_source::SourceMapUtils;
+use hash_utils::json::JsonValue;
+
+use crate::WriteIr;
+
+/// [IrJsonWriter] is used to convert a [Body] into a [JsonValue].
+pub struct IrJsonWriter<'ir> {
+    /// The body that is being converted.
+    body: &'ir Body,
+
+    /// The layout computer is used to compute the layout of the data
+    /// under constants.
+    lc: LayoutComputer<'ir>,
+}
+
+impl<'ir> IrJsonWriter<'ir> {
+    /// Create a new JSON writer for the given body.
+    pub fn new(body: &'ir Body, lc: LayoutComputer<'ir>) -> Self {
+        Self { body, lc }
+    }
+
+    /// Convert the [Body] into a [JsonValue].
+    pub fn to_json(&self) -> JsonValue {
+        let metadata = self.body.metadata();
+
+        let locals = self
+            .body
+            .locals
+            .iter_enumerated()
+            .map(|(local, decl)| {
+                JsonValue::object([
+                    ("local", JsonValue::string(format!("{local:?}"))),
+                    ("ty", JsonValue::string(decl.ty())),
+                    ("mutable", JsonValue::Bool(decl.mutability() == Mutability::Mutable)),
+                    (
+                        "name",
+                        decl.name()
+                            .filter(|_| !decl.auxiliary())
+                            .map_or(JsonValue::Null, JsonValue::string),
+                    ),
+                ])
+            })
+            .collect();
+
+        let blocks = self
+            .body
+            .blocks()
+            .iter_enumerated()
+            .map(|(id, data)| {
+                JsonValue::object([
+                    ("id", JsonValue::string(format!("{id:?}"))),
+                    ("statements", self.block_statements(data)),
+                    (
+                        "terminator",
+                        data.terminator.as_ref().map_or(JsonValue::Null, |t| self.terminator(t)),
+                    ),
+                ])
+            })
+            .collect();
+
+        let unsafe_ops = metadata
+            .unsafe_ops
+            .iter()
+            .map(|op| {
+                JsonValue::object([
+                    ("kind", JsonValue::string(op.kind)),
+                    ("in_unsafe_block", JsonValue::Bool(op.in_unsafe_block)),
+                ])
+            })
+            .collect();
+
+        JsonValue::object([
+            ("name", JsonValue::string(metadata.name())),
+            ("source", JsonValue::string(metadata.source())),
+            ("span", JsonValue::string(self.body.source_span().fmt_path())),
+            ("ty", JsonValue::string(metadata.ty())),
+            ("arg_count", JsonValue::from(self.body.arg_count)),
+            ("locals", JsonValue::Array(locals)),
+            ("blocks", JsonValue::Array(blocks)),
+            ("unsafe_ops", JsonValue::Array(unsafe_ops)),
+        ])
+    }
+
+    /// Convert all of the [Statement]s of a block into a [JsonValue::Array].
+    fn block_statements(&self, data: &BasicBlockData) -> JsonValue {
+        JsonValue::Array(data.statements.iter().map(|s| self.statement(s)).collect())
+    }
+
+    /// Convert a [Statement] into a [JsonValue].
+    fn statement(&self, statement: &Statement) -> JsonValue {
+        let info = self.body.aux();
+
+        let mut entries = match &statement.kind {
+            StatementKind::Nop => vec![("kind", JsonValue::string("nop"))],
+            StatementKind::Assign(place, value) => vec![
+                ("kind", JsonValue::string("assign")),
+                ("place", JsonValue::string(place.with_edges(info, self.lc, false))),
+                ("value", JsonValue::string(value.with_edges(info, self.lc, false))),
+            ],
+            StatementKind::Discriminate(place, variant) => vec![
+                ("kind", JsonValue::string("discriminate")),
+                ("place", JsonValue::string(place.with_edges(info, self.lc, false))),
+                ("variant", JsonValue::from(variant.index())),
+            ],
+            StatementKind::Live(local) => vec![
+                ("kind", JsonValue::string("live")),
+                ("local", JsonValue::string(format!("{local:?}"))),
+            ],
+            StatementKind::Dead(local) => vec![
+                ("kind", JsonValue::string("dead")),
+                ("local", JsonValue::string(format!("{local:?}"))),
+            ],
+            StatementKind::Assume(operand) => vec![
+                ("kind", JsonValue::string("assume")),
+                ("operand", JsonValue::string(operand.with_edges(info, self.lc, false))),
+            ],
+        };
+
+        entries.push(("text", JsonValue::string(statement.with_edges(info, self.lc, false))));
+        JsonValue::object(entries)
+    }
+
+    /// Convert a [Terminator] into a [JsonValue].
+    fn terminator(&self, terminator: &Terminator) -> JsonValue {
+        let info = self.body.aux();
+
+        let kind = match &terminator.kind {
+            TerminatorKind::Goto(_) => "goto",
+            TerminatorKind::Unreachable => "unreachable",
+            TerminatorKind::Return => "return",
+            TerminatorKind::Resume => "resume",
+            TerminatorKind::Call { .. } => "call",
+            TerminatorKind::Switch { .. } => "switch",
+            TerminatorKind::Assert { .. } => "assert",
+            TerminatorKind::Drop { .. } => "drop",
+        };
+
+        let successors = terminator
+            .successors()
+            .map(|successor| JsonValue::string(format!("{successor:?}")))
+            .collect();
+
+        JsonValue::object([
+            ("kind", JsonValue::string(kind)),
+            ("successors", JsonValue::Array(successors)),
+            ("text", JsonValue::string(terminator.with_edges(info, self.lc, true))),
+        ])
+    }
+}
+
+/// [IrCallGraphJsonWriter] is used to convert a [CallGraph] into a
+/// [JsonValue]. If the [Reachable] nodes of the graph are provided, then
+/// each node records whether it is reachable, and any nodes that aren't
+/// reachable can be omitted.
+pub struct IrCallGraphJsonWriter<'g> {
+    /// The call graph that is being converted.
+    graph: &'g CallGraph,
+
+    /// The nodes of the graph that are reachable from the roots of the
+    /// graph, if any roots were specified.
+    reachable: Option<&'g Reachable>,
+
+    /// Whether nodes that aren't reachable should be omitted.
+    only_reachable: bool,
+}
+
+impl<'g> IrCallGraphJsonWriter<'g> {
+    /// Create a new JSON writer for the given [CallGraph].
+    pub fn new(
+        graph: &'g CallGraph,
+        reachable: Option<&'g Reachable>,
+        only_reachable: bool,
+    ) -> Self {
+        Self { graph, reachable, only_reachable }
+    }
+
+    /// Check whether a node of the graph should be emitted.
+    fn is_written(&self, index: usize) -> bool {
+        !self.only_reachable || self.reachable.map_or(true, |reachable| reachable.contains(index))
+    }
+
+    /// Convert the [CallGraph] into a [JsonValue].
+    pub fn to_json(&self) -> JsonValue {
+        let nodes = self
+            .graph
+            .nodes()
+            .filter(|(index, _)| self.is_written(*index))
+            .map(|(index, node)| {
+                let module = node.source.map_or(JsonValue::Null, |source| {
+                    JsonValue::string(SourceMapUtils::map(source, |source| {
+                        source.name().to_string()
+                    }))
+                });
+
+                let mut entries = vec![
+                    ("id", JsonValue::from(index)),
+                    ("name", JsonValue::string(node.name)),
+                    ("module", module),
+                    (
+                        "span",
+                        node.span
+                            .map_or(JsonValue::Null, |span| JsonValue::string(span.fmt_path())),
+                    ),
+                    ("has_body", JsonValue::Bool(node.has_body())),
+                    ("unknown", JsonValue::Bool(node.is_unknown())),
+                ];
+
+                if let Some(reachable) = self.reachable {
+                    entries.push(("reachable", JsonValue::Bool(reachable.contains(index))));
+                }
+
+                JsonValue::object(entries)
+            })
+            .collect();
+
+        let edges = self
+            .graph
+            .edges()
+            .filter(|edge| self.is_written(edge.caller) && self.is_written(edge.callee))
+            .map(|edge| {
+                JsonValue::object([
+                    ("caller", JsonValue::from(edge.caller)),
+                    ("callee", JsonValue::from(edge.callee)),
+                    ("kind", JsonValue::string(edge.kind.as_str())),
+                    ("count", JsonValue::from(edge.count)),
+                ])
+            })
+            .collect();
+
+        JsonValue::object([("nodes", JsonValue::Array(nodes)), ("edges", JsonValue::Array(edges))])
+    }
+}
+
+/// Dump all of the provided [Body]s to standard output as a JSON document.
+pub fn dump_ir_bodies(
+    bodies: &[Body],
+    dump_all: bool,
+    prelude_is_quiet: bool,
+    lc: LayoutComputer<'_>,
+    writer: &mut impl io::Write,
+) -> io::Result<()> {
+    let bodies = bodies
+        .iter()
+        .filter(|body| {
+            // Skip the prelude if we're in quiet mode, and skip any bodies
+            // which weren't requested to be dumped.
+            !(prelude_is_quiet && body.source().is_prelude()) && (dump_all || body.needs_dumping())
+        })
+        .map(|body| IrJsonWriter::new(body, lc).to_json())
+        .collect();
+
+    writeln!(writer, "{}", JsonValue::object([("bodies", JsonValue::Array(bodies))]))
+}
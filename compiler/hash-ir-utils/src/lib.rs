@@ -7,12 +7,14 @@
 //! information about data representation when constructing and destructing
 //! Hash IR constants into various representations.
 #![feature(let_chains)]
+pub mod binary;
 pub mod graphviz;
+pub mod json;
 pub mod pretty;
 
 use std::{fmt, ops::Deref};
 
-use hash_const_eval::print::pretty_print_const;
+use hash_const_eval::print::{pretty_print_const_with_options, ConstPrintOptions};
 use hash_ir::{
     ir::{
         AggregateKind, AssertKind, BodyInfo, Operand, Place, PlaceProjection, RValue, Statement,
@@ -40,12 +42,21 @@ pub struct IrWriter<'ctx, T> {
     /// Whether the formatting implementations should write
     /// edges for IR items, this mostly applies to [Terminator]s.
     pub with_edges: bool,
+
+    /// How the constants within the IR items are written.
+    pub const_options: ConstPrintOptions,
 }
 
 impl<'ctx, T> IrWriter<'ctx, T> {
     /// Create a new IR writer for the given body.
     pub fn new(item: T, info: BodyInfo<'ctx>, lc: LayoutComputer<'ctx>) -> Self {
-        Self { item, lc, info, with_edges: false }
+        Self { item, lc, info, with_edges: false, const_options: ConstPrintOptions::default() }
+    }
+
+    /// Write the constants within the item with the given [ConstPrintOptions].
+    pub fn with_const_options(mut self, const_options: ConstPrintOptions) -> Self {
+        self.const_options = const_options;
+        self
     }
 }
 
@@ -71,11 +82,11 @@ pub trait WriteIr<'ctx>: Sized {
         lc: LayoutComputer<'ctx>,
         with_edges: bool,
     ) -> IrWriter<'ctx, Self> {
-        IrWriter { item: self, info, lc, with_edges }
+        IrWriter { item: self, info, lc, with_edges, const_options: ConstPrintOptions::default() }
     }
 
     fn with<U>(self, other: &IrWriter<'ctx, U>) -> IrWriter<'ctx, Self> {
-        IrWriter::new(self, other.info, other.lc)
+        IrWriter::new(self, other.info, other.lc).with_const_options(other.const_options)
     }
 }
 
@@ -140,7 +151,8 @@ impl fmt::Display for IrWriter<'_, &Operand> {
                 }
 
                 let mut buf = TempWriter::default();
-                pretty_print_const(&mut buf, constant, self.lc).unwrap();
+                pretty_print_const_with_options(&mut buf, constant, self.lc, self.const_options)
+                    .unwrap();
                 write!(f, "{}", buf.into_string())
             }
         }
@@ -230,6 +242,9 @@ impl fmt::Display for IrWriter<'_, &Statement> {
             StatementKind::Dead(local) => {
                 write!(f, "dead({local:?})")
             }
+            StatementKind::Assume(operand) => {
+                write!(f, "assume({})", operand.with(self))
+            }
         }
     }
 }
@@ -241,8 +256,21 @@ impl fmt::Display for IrWriter<'_, &Terminator> {
             TerminatorKind::Goto(place) if self.with_edges => write!(f, "goto -> {place:?}"),
             TerminatorKind::Goto(_) => write!(f, "goto"),
             TerminatorKind::Return => write!(f, "return"),
-            TerminatorKind::Call { op, args, target, destination } => {
-                write!(f, "{} = {}(", destination.with(self), op.with(self))?;
+            TerminatorKind::Resume => write!(f, "resume"),
+            TerminatorKind::Call { op, args, target, destination, unwind } => {
+                // Calls through function pointers are written with the type of
+                // the operand, since the callee isn't statically known.
+                if self.item.is_indirect_call(&self.info) {
+                    write!(
+                        f,
+                        "{} = call *({}: {})(",
+                        destination.with(self),
+                        op.with(self),
+                        op.ty(&self.info)
+                    )?;
+                } else {
+                    write!(f, "{} = {}(", destination.with(self), op.with(self))?;
+                }
 
                 // write all of the arguments
                 for (i, arg) in args.iter().enumerate() {
@@ -253,15 +281,21 @@ impl fmt::Display for IrWriter<'_, &Terminator> {
                     write!(f, "{}", arg.with(self))?;
                 }
 
+                write!(f, ")")?;
+
                 // Only print the target if there is a target, and if the formatting
                 // specifies that edges should be printed.
-                if let Some(target) = target
-                    && self.with_edges
-                {
-                    write!(f, ") -> {target:?}")
-                } else {
-                    write!(f, ")")
+                if self.with_edges {
+                    if let Some(target) = target {
+                        write!(f, " -> {target:?}")?;
+                    }
+
+                    if let Some(unwind) = unwind {
+                        write!(f, ", unwind -> {unwind:?}")?;
+                    }
                 }
+
+                Ok(())
             }
             TerminatorKind::Unreachable => write!(f, "unreachable"),
             TerminatorKind::Switch { value, targets } => {
@@ -286,7 +320,13 @@ impl fmt::Display for IrWriter<'_, &Terminator> {
                             Const::from_scalar_like(value, target_ty, self.lc.data_layout());
 
                         let mut buf = TempWriter::default();
-                        pretty_print_const(&mut buf, &value, self.lc).unwrap();
+                        pretty_print_const_with_options(
+                            &mut buf,
+                            &value,
+                            self.lc,
+                            self.const_options,
+                        )
+                        .unwrap();
                         write!(f, "{}", buf.into_string())?;
 
                         write!(f, " -> {target:?}")?;
@@ -300,7 +340,7 @@ impl fmt::Display for IrWriter<'_, &Terminator> {
 
                 Ok(())
             }
-            TerminatorKind::Assert { condition, expected, kind, target } => {
+            TerminatorKind::Assert { condition, expected, kind, target, unwind } => {
                 write!(
                     f,
                     "assert({}, {expected:?}, \"{}\")",
@@ -310,10 +350,18 @@ impl fmt::Display for IrWriter<'_, &Terminator> {
 
                 if self.with_edges {
                     write!(f, " -> {target:?}")?;
+
+                    if let Some(unwind) = unwind {
+                        write!(f, ", unwind -> {unwind:?}")?;
+                    }
                 }
 
                 Ok(())
             }
+            TerminatorKind::Drop { place, target } if self.with_edges => {
+                write!(f, "drop({}) -> {target:?}", place.with(self))
+            }
+            TerminatorKind::Drop { place, .. } => write!(f, "drop({})", place.with(self)),
         }
     }
 }
@@ -339,6 +387,22 @@ impl fmt::Display for IrWriter<'_, &AssertKind> {
             AssertKind::NegativeOverflow { operand } => {
                 write!(f, "attempt to negate `{}`, which would overflow", operand.with(self))
             }
+            AssertKind::ShiftAmountTooLarge { op, lhs, amount } => {
+                write!(
+                    f,
+                    "attempt to compute `{} {op} {}`, which would shift by too much",
+                    lhs.with(self),
+                    amount.with(self)
+                )
+            }
+            AssertKind::NegativeExponent { base, exponent } => {
+                write!(
+                    f,
+                    "attempt to compute `{} ** {}`, which has a negative exponent",
+                    base.with(self),
+                    exponent.with(self)
+                )
+            }
             AssertKind::BoundsCheck { len, index } => {
                 write!(
                     f,
@@ -347,18 +411,29 @@ impl fmt::Display for IrWriter<'_, &AssertKind> {
                     index.with(self)
                 )
             }
+            AssertKind::InvalidDiscriminant { discriminant } => {
+                write!(
+                    f,
+                    "`{}` is not the discriminant of any variant of the enum",
+                    discriminant.with(self)
+                )
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use hash_const_eval::print::ConstPrintOptions;
     use hash_ir::{
-        ir::{BodyInfo, Local, LocalDecls, Place, PlaceProjection, Projections},
-        ty::VariantIdx,
+        ir::{BodyInfo, Local, LocalDecls, Operand, Place, PlaceProjection, Projections},
+        ty::{Adt, AdtField, AdtFlags, AdtVariant, ReprTy, VariantIdx, COMMON_REPR_TYS},
     };
-    use hash_repr::{compute::LayoutComputer, LayoutStorage};
+    use hash_repr::{compute::LayoutComputer, constant::Const, LayoutStorage};
+    use hash_source::constant::Alloc;
+    use hash_storage::store::statics::SingleStoreValue;
     use hash_target::data_layout::TargetDataLayout;
+    use hash_utils::index_vec::index_vec;
 
     use crate::IrWriter;
 
@@ -396,4 +471,51 @@ mod tests {
         let item = IrWriter::new(&place, info, lc);
         assert_eq!(format!("{}", item), "(*(*(*_0)))");
     }
+
+    #[test]
+    fn test_array_const_display() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+        let projections = Projections::new();
+        let locals = LocalDecls::new();
+
+        let bytes: Vec<u8> = (1..=5i32).flat_map(i32::to_le_bytes).collect();
+        let alloc = Alloc::create(Alloc::from_bytes_immutable(bytes));
+        let ty = ReprTy::create(ReprTy::Array { ty: COMMON_REPR_TYS.i32, length: 5 });
+        let operand = Operand::Const(Const::alloc(alloc, ty));
+
+        let info = BodyInfo { locals: &locals, projections: &projections };
+        let item = IrWriter::new(&operand, info, lc);
+        assert_eq!(format!("{}", item), "const [1_i32, 2_i32, 3_i32, 4_i32, 5_i32]");
+
+        // The elements after the limit are elided.
+        let options = ConstPrintOptions { array_element_limit: 2 };
+        let item = IrWriter::new(&operand, info, lc).with_const_options(options);
+        assert_eq!(format!("{}", item), "const [1_i32, 2_i32, ..]");
+    }
+
+    #[test]
+    fn test_union_const_display() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+        let projections = Projections::new();
+        let locals = LocalDecls::new();
+
+        // Unions can't be written in source, so we construct one directly.
+        let fields = vec![
+            AdtField { name: "small".into(), ty: COMMON_REPR_TYS.u8 },
+            AdtField { name: "large".into(), ty: COMMON_REPR_TYS.u32 },
+        ];
+        let variants = index_vec![AdtVariant::singleton("Number".into(), fields)];
+        let adt = Adt::new_with_flags("Number".into(), variants, AdtFlags::UNION);
+        let ty = ReprTy::create(ReprTy::Adt(Adt::create(adt)));
+
+        let alloc = Alloc::create(Alloc::from_bytes_immutable(258u32.to_le_bytes().to_vec()));
+        let operand = Operand::Const(Const::alloc(alloc, ty));
+
+        // The widest field of the union is printed.
+        let info = BodyInfo { locals: &locals, projections: &projections };
+        let item = IrWriter::new(&operand, info, lc);
+        assert_eq!(format!("{}", item), "const Number { large: 258_u32 }");
+    }
 }
@@ -2,12 +2,50 @@
 
 use std::fmt;
 
-use hash_ir::ir::{BasicBlock, Body, BodySource};
+use hash_attrs::{builtin::attrs, ty::AttrId};
+use hash_const_eval::print::ConstPrintOptions;
+use hash_ir::{
+    ir::{BasicBlock, Body, BodySource},
+    ty::{InstanceHelpers, InstanceId},
+};
 use hash_repr::compute::LayoutComputer;
+use hash_storage::store::statics::StoreId;
+use hash_target::abi::Abi;
 use hash_utils::itertools::Itertools;
 
 use crate::WriteIr;
 
+/// Options that control what an [IrBodyWriter] writes for each [Body].
+#[derive(Debug, Clone, Copy)]
+pub struct BodyWriterOptions {
+    /// Whether each statement and terminator should be annotated with the
+    /// source location that it originated from.
+    pub with_spans: bool,
+
+    /// Whether the terminators should show the blocks that they jump to, i.e.
+    /// `goto -> bb1` rather than `goto`.
+    pub with_edges: bool,
+
+    /// Whether to only write the blocks of the body and the edges between
+    /// them, omitting the declarations and the statements. This makes the
+    /// shape of very large bodies easier to grasp.
+    pub summary: bool,
+
+    /// How the constants within the statements and terminators are written.
+    pub const_options: ConstPrintOptions,
+}
+
+impl Default for BodyWriterOptions {
+    fn default() -> Self {
+        Self {
+            with_spans: false,
+            with_edges: true,
+            summary: false,
+            const_options: ConstPrintOptions::default(),
+        }
+    }
+}
+
 /// [IrBodyWriter] is used to encapsulate the logic of pretty-printing a
 /// [Body] to a [fmt::Formatter]. The [IrBodyWriter] is uses the standalone
 /// implementations for displaying each IR component with the addition of adding
@@ -20,12 +58,21 @@ pub struct IrBodyWriter<'ir> {
     /// The layout computer is used to compute the layout of the data
     /// under the constant.
     lc: LayoutComputer<'ir>,
+
+    /// What should be written for the body.
+    options: BodyWriterOptions,
 }
 
 impl<'ir> IrBodyWriter<'ir> {
     /// Create a new IR writer for the given body.
     pub fn new(body: &'ir Body, lc: LayoutComputer<'ir>) -> Self {
-        Self { body, lc }
+        Self { body, lc, options: BodyWriterOptions::default() }
+    }
+
+    /// Specify the [BodyWriterOptions] of the writer.
+    pub fn with_options(mut self, options: BodyWriterOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Function to deal with a [Body] header which is formatted depending on
@@ -49,7 +96,7 @@ impl<'ir> IrBodyWriter<'ir> {
         let return_ty_decl = declarations.next().unwrap();
 
         match self.body.metadata().source() {
-            BodySource::Item => {
+            BodySource::Item | BodySource::Derived => {
                 write!(f, "{} := (", self.body.metadata().name)?;
 
                 for (i, param) in declarations.take(self.body.arg_count).enumerate() {
@@ -76,6 +123,10 @@ impl<'ir> IrBodyWriter<'ir> {
     fn write_body(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.write_header(f)?;
 
+        if self.options.summary {
+            return self.write_summary(f);
+        }
+
         let declarations = self.body.locals.iter_enumerated().take(self.body.arg_count + 1);
 
         // We write debug information about the parameters of the function in
@@ -136,32 +187,76 @@ impl<'ir> IrBodyWriter<'ir> {
         writeln!(f, "}}")
     }
 
+    /// Write each block of the body on a single line, with the number of
+    /// statements in the block and the terminator of the block:
+    /// ```ignore
+    /// bb0 (2 statements): switch(_1) [1_i32 -> bb1, otherwise -> bb2]
+    /// ```
+    fn write_summary(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (block, data) in self.body.basic_blocks.blocks.iter_enumerated() {
+            let count = data.statements.len();
+            let plural = if count == 1 { "" } else { "s" };
+            write!(f, "{: <1$}{block:?} ({count} statement{plural})", "", 4)?;
+
+            if let Some(terminator) = &data.terminator {
+                let terminator = terminator
+                    .with_edges(self.body.aux(), self.lc, true)
+                    .with_const_options(self.options.const_options);
+                write!(f, ": {terminator}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        writeln!(f, "}}")
+    }
+
     fn write_block(&self, block: BasicBlock, f: &mut fmt::Formatter) -> fmt::Result {
         // Print the label for the block
         writeln!(f, "{: <1$}{block:?} {{", "", 4)?;
         let block_data = &self.body.blocks()[block];
 
-        // Write all of the statements within the block
-        for statement in &block_data.statements {
-            writeln!(
-                f,
-                "{: <2$}{};",
-                "",
-                statement.with_edges(self.body.aux(), self.lc, false),
-                8
-            )?;
-        }
+        // Render all of the statements within the block, followed by the
+        // terminator of the block. If the terminator is not present, this is an
+        // invariant but we don't care here.
+        let lines = block_data
+            .statements
+            .iter()
+            .map(|statement| {
+                (
+                    format!(
+                        "{};",
+                        statement
+                            .with_edges(self.body.aux(), self.lc, false)
+                            .with_const_options(self.options.const_options)
+                    ),
+                    statement.origin,
+                )
+            })
+            .chain(block_data.terminator.as_ref().map(|terminator| {
+                (
+                    format!(
+                        "{};",
+                        terminator
+                            .with_edges(self.body.aux(), self.lc, self.options.with_edges)
+                            .with_const_options(self.options.const_options)
+                    ),
+                    terminator.origin,
+                )
+            }))
+            .collect_vec();
+
+        let longest_line = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+
+        for (line, origin) in lines {
+            write!(f, "{: <1$}{line}", "", 8)?;
+
+            if self.options.with_spans {
+                let span = origin.span().fmt_start_path();
+                write!(f, "{: <1$}\t// at {span}", "", longest_line - line.len())?;
+            }
 
-        // Write the terminator of the block. If the terminator is
-        // not present, this is an invariant but we don't care here.
-        if let Some(terminator) = &block_data.terminator {
-            writeln!(
-                f,
-                "{: <2$}{};",
-                "",
-                terminator.with_edges(self.body.aux(), self.lc, true),
-                8
-            )?;
+            writeln!(f)?;
         }
 
         writeln!(f, "{: <1$}}}", "", 4)
@@ -174,11 +269,102 @@ impl fmt::Display for IrBodyWriter<'_> {
     }
 }
 
+/// [BodyHeader] is used to print the line that introduces a [Body] within
+/// an IR dump. For function items, any additional information about the
+/// function, such as the ABI, the function attributes, and whether the
+/// function is the entry point, is written as a comment on the
+/// following line:
+/// ```ignore
+/// IR dump for function `main` defined at $DIR/main.hash:1:9-3:2
+/// // entry point, abi: C, #no_mangle
+/// ```
+/// The comment is omitted if the function has no additional information.
+pub struct BodyHeader<'ir> {
+    /// The body that is being introduced.
+    body: &'ir Body,
+
+    /// The entry point of the program, if there is one.
+    entry_point: Option<InstanceId>,
+}
+
+impl<'ir> BodyHeader<'ir> {
+    /// The attributes that are shown in the header of a function, if the
+    /// function has them.
+    const ATTRS: [AttrId; 7] = [
+        attrs::LANG,
+        attrs::ENTRY_POINT,
+        attrs::PURE,
+        attrs::FOREIGN,
+        attrs::NO_MANGLE,
+        attrs::LINK_NAME,
+        attrs::LINK,
+    ];
+
+    /// Create a new [BodyHeader] for the given body.
+    pub fn new(body: &'ir Body, entry_point: Option<InstanceId>) -> Self {
+        Self { body, entry_point }
+    }
+
+    /// Collect all of the additional information about the function that
+    /// the [Body] was lowered from.
+    fn function_info(&self) -> Vec<String> {
+        let instance_id = self.body.metadata().ty().borrow().as_instance();
+        let mut info = vec![];
+
+        if self.entry_point == Some(instance_id) {
+            info.push("entry point".to_string());
+        }
+
+        instance_id.map(|instance| {
+            if instance.is_origin_polymorphic() {
+                info.push("monomorphised instance".to_string());
+            }
+
+            if instance.abi != Abi::Hash {
+                info.push(format!("abi: {}", instance.abi));
+            }
+
+            for attr in Self::ATTRS {
+                if instance.has_attr(attr) {
+                    info.push(format!("#{}", attr.name()));
+                }
+            }
+        });
+
+        info
+    }
+}
+
+impl fmt::Display for BodyHeader<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let metadata = self.body.metadata();
+        write!(
+            f,
+            "IR dump for {} `{}` defined at {}",
+            metadata.source(),
+            metadata.name(),
+            self.body.source_span().fmt_path()
+        )?;
+
+        if matches!(metadata.source(), BodySource::Item | BodySource::Derived) {
+            let info = self.function_info();
+
+            if !info.is_empty() {
+                write!(f, "\n// {}", info.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Dump all of the provided [Body]s to standard output using the `dot` format.
 pub fn dump_ir_bodies(
     bodies: &[Body],
     dump_all: bool,
     prelude_is_quiet: bool,
+    options: BodyWriterOptions,
+    entry_point: Option<InstanceId>,
     lc: LayoutComputer<'_>,
     writer: &mut impl std::io::Write,
 ) -> std::io::Result<()> {
@@ -201,11 +387,9 @@ pub fn dump_ir_bodies(
 
         writeln!(
             writer,
-            "IR dump for {} `{}` defined at {}\n{}",
-            body.metadata().source(),
-            body.metadata().name(),
-            body.span().fmt_path(),
-            IrBodyWriter::new(body, lc)
+            "{}\n{}",
+            BodyHeader::new(body, entry_point),
+            IrBodyWriter::new(body, lc).with_options(options)
         )?;
     }
 
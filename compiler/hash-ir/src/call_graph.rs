@@ -0,0 +1,452 @@
+//! Defines the [CallGraph] of a collection of lowered [Body]s. The graph
+//! records which functions each body calls, and which functions each body
+//! references as a value (and so might call indirectly). The graph can then
+//! be queried for all of the functions that are reachable from a particular
+//! function, i.e. the entry point of the program.
+
+use fixedbitset::FixedBitSet;
+use hash_repr::constant::{Const, ConstKind};
+use hash_source::{identifier::Identifier, location::Span, SourceId};
+use hash_storage::store::statics::StoreId;
+use hash_utils::indexmap::IndexMap;
+
+use crate::{
+    ir::{BasicBlock, Body, BodyInfo, IrRef, Operand, Place, TerminatorKind},
+    ty::{InstanceId, ReprTy},
+    visitor::{IrVisitorCtx, IrVisitorMut},
+};
+
+/// A key that uniquely identifies a node within the [CallGraph].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallGraphKey {
+    /// A function instance, this is either a function that has a lowered
+    /// body, or a function that is only referenced by a call.
+    Instance(InstanceId),
+
+    /// A lowered body that isn't a function, i.e. a constant.
+    Body(usize),
+
+    /// The unknown callee of all calls through function pointers.
+    Unknown,
+}
+
+/// A node within the [CallGraph].
+#[derive(Debug, Clone)]
+pub struct CallGraphNode {
+    /// The key of the node.
+    pub key: CallGraphKey,
+
+    /// The name of the function, or constant.
+    pub name: Identifier,
+
+    /// The module that the node was defined in, if known.
+    pub source: Option<SourceId>,
+
+    /// The [Span] of the definition of the node, this is only known for nodes
+    /// that have a lowered body.
+    pub span: Option<Span>,
+
+    /// The index of the lowered body of the node, nodes without bodies are
+    /// intrinsics, foreign functions, functions whose bodies were not
+    /// provided to the graph, or the [`CallGraphKey::Unknown`] node.
+    pub body: Option<usize>,
+}
+
+impl CallGraphNode {
+    /// Whether the node has a lowered body.
+    pub fn has_body(&self) -> bool {
+        self.body.is_some()
+    }
+
+    /// Whether this is the node that represents the callee of indirect calls.
+    pub fn is_unknown(&self) -> bool {
+        self.key == CallGraphKey::Unknown
+    }
+}
+
+/// The kind of an edge within the [CallGraph].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallGraphEdgeKind {
+    /// The caller directly calls the callee.
+    Call,
+
+    /// The caller uses the callee as a value, i.e. by storing it in a
+    /// function pointer, so the callee might be called indirectly.
+    Reference,
+}
+
+impl CallGraphEdgeKind {
+    /// Get the name of the edge kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Call => "call",
+            Self::Reference => "reference",
+        }
+    }
+}
+
+/// An edge within the [CallGraph].
+#[derive(Debug, Clone, Copy)]
+pub struct CallGraphEdge {
+    /// The index of the node that makes the call.
+    pub caller: usize,
+
+    /// The index of the node that is called.
+    pub callee: usize,
+
+    /// Whether this is a call, or a reference.
+    pub kind: CallGraphEdgeKind,
+
+    /// The number of call sites (or references) that the edge represents.
+    pub count: usize,
+}
+
+/// The [CallGraph] is built from the `call` terminators within the provided
+/// [Body]s. Calls to function definitions are recorded as edges to the
+/// function, and calls through function pointers are recorded as edges to
+/// the [`CallGraphKey::Unknown`] node. Any function definitions that are
+/// used as values are recorded as [`CallGraphEdgeKind::Reference`] edges,
+/// so that functions which are only called indirectly are still reachable.
+///
+/// The index of each node is used as its identifier within the graph.
+pub struct CallGraph {
+    /// All of the nodes within the graph.
+    nodes: IndexMap<CallGraphKey, CallGraphNode>,
+
+    /// All of the edges between the nodes, and the number of call sites that
+    /// the edge represents.
+    edges: IndexMap<(usize, usize, CallGraphEdgeKind), usize>,
+
+    /// The nodes that each node has an edge to.
+    successors: Vec<Vec<usize>>,
+}
+
+impl CallGraph {
+    /// Build the call graph from the given [Body]s, the index that is paired
+    /// with each body is recorded as the [`CallGraphNode::body`].
+    pub fn new<'ir>(bodies: impl IntoIterator<Item = (usize, &'ir Body)> + Clone) -> Self {
+        let mut graph =
+            Self { nodes: IndexMap::new(), edges: IndexMap::new(), successors: Vec::new() };
+
+        // Firstly, we add all of the bodies as nodes, so that bodies are always
+        // attributed to the module they are defined in.
+        for (id, body) in bodies.clone() {
+            let key = Self::body_key(id, body);
+            let node = CallGraphNode {
+                key,
+                name: body.metadata().name(),
+                source: Some(body.source()),
+                span: Some(body.source_span()),
+                body: Some(id),
+            };
+
+            graph.nodes.insert(key, node);
+        }
+
+        for (id, body) in bodies {
+            let caller = graph.nodes.get_index_of(&Self::body_key(id, body)).unwrap();
+            let info = body.aux();
+
+            for data in body.blocks().iter() {
+                let Some(TerminatorKind::Call { op, .. }) =
+                    data.terminator.as_ref().map(|t| &t.kind)
+                else {
+                    continue;
+                };
+
+                let callee = match *op.ty(&info).borrow() {
+                    ReprTy::FnDef { instance } => CallGraphKey::Instance(instance),
+                    _ => CallGraphKey::Unknown,
+                };
+
+                graph.add_edge(caller, callee, CallGraphEdgeKind::Call);
+            }
+
+            let mut references = FnReferences::default();
+            references.visit(body);
+
+            for instance in references.instances {
+                graph.add_edge(
+                    caller,
+                    CallGraphKey::Instance(instance),
+                    CallGraphEdgeKind::Reference,
+                );
+            }
+        }
+
+        graph.successors = vec![vec![]; graph.nodes.len()];
+
+        for (caller, callee, _) in graph.edges.keys() {
+            graph.successors[*caller].push(*callee);
+        }
+
+        graph
+    }
+
+    /// Compute the [CallGraphKey] of a particular [Body].
+    fn body_key(id: usize, body: &Body) -> CallGraphKey {
+        match *body.metadata().ty().borrow() {
+            ReprTy::FnDef { instance } => CallGraphKey::Instance(instance),
+            _ => CallGraphKey::Body(id),
+        }
+    }
+
+    /// Record an edge from the `caller` to the `callee`, adding the callee
+    /// as a node if it hasn't been seen before.
+    fn add_edge(&mut self, caller: usize, callee: CallGraphKey, kind: CallGraphEdgeKind) {
+        let entry = self.nodes.entry(callee);
+        let index = entry.index();
+
+        entry.or_insert_with(|| match callee {
+            CallGraphKey::Instance(instance) => {
+                let instance = instance.borrow();
+                CallGraphNode {
+                    key: callee,
+                    name: instance.name(),
+                    source: instance.source,
+                    span: None,
+                    body: None,
+                }
+            }
+            _ => CallGraphNode {
+                key: callee,
+                name: "?".into(),
+                source: None,
+                span: None,
+                body: None,
+            },
+        });
+
+        *self.edges.entry((caller, index, kind)).or_insert(0) += 1;
+    }
+
+    /// Get all of the nodes within the graph, paired with their index.
+    pub fn nodes(&self) -> impl Iterator<Item = (usize, &CallGraphNode)> + '_ {
+        self.nodes.values().enumerate()
+    }
+
+    /// Get the node at the given index.
+    pub fn node(&self, index: usize) -> &CallGraphNode {
+        &self.nodes[index]
+    }
+
+    /// Get all of the edges within the graph.
+    pub fn edges(&self) -> impl Iterator<Item = CallGraphEdge> + '_ {
+        self.edges.iter().map(|(&(caller, callee, kind), &count)| CallGraphEdge {
+            caller,
+            callee,
+            kind,
+            count,
+        })
+    }
+
+    /// Get the index of the node which represents the given instance.
+    pub fn instance_node(&self, instance: InstanceId) -> Option<usize> {
+        self.nodes.get_index_of(&CallGraphKey::Instance(instance))
+    }
+
+    /// Find the node with the given name, nodes with a lowered body are
+    /// preferred over nodes that are only referenced by a call.
+    pub fn find_node(&self, name: &str) -> Option<usize> {
+        self.nodes()
+            .filter(|(_, node)| node.name.as_str() == name)
+            .min_by_key(|(_, node)| !node.has_body())
+            .map(|(index, _)| index)
+    }
+
+    /// Get all of the nodes that the given node has an edge to.
+    pub fn successors(&self, index: usize) -> &[usize] {
+        &self.successors[index]
+    }
+
+    /// Compute all of the nodes that are reachable from the given roots, the
+    /// roots are always reachable.
+    pub fn reachable_from(&self, roots: impl IntoIterator<Item = usize>) -> Reachable {
+        let mut visited = FixedBitSet::with_capacity(self.nodes.len());
+        let mut work_list: Vec<_> = roots.into_iter().collect();
+
+        while let Some(next) = work_list.pop() {
+            if visited.put(next) {
+                continue;
+            }
+
+            work_list.extend(self.successors(next).iter().copied());
+        }
+
+        Reachable { visited }
+    }
+
+    /// Compute the strongly connected components of the graph, i.e. the
+    /// groups of nodes that can all (transitively) reach each other. The
+    /// components are returned in reverse topological order, so any component
+    /// that a node has an edge to appears before the component of the node.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components = Components {
+            graph: self,
+            next_index: 0,
+            indices: vec![None; self.nodes.len()],
+            low_links: vec![0; self.nodes.len()],
+            on_stack: FixedBitSet::with_capacity(self.nodes.len()),
+            stack: vec![],
+            components: vec![],
+        };
+
+        for node in 0..self.nodes.len() {
+            if components.indices[node].is_none() {
+                components.visit(node);
+            }
+        }
+
+        components.components
+    }
+
+    /// Check whether the given component of the graph is a recursive cycle,
+    /// i.e. it is made of mutually recursive functions, or of a single function
+    /// which calls (or references) itself.
+    pub fn is_recursive(&self, component: &[usize]) -> bool {
+        match component {
+            [] => false,
+            [node] => self.successors(*node).contains(node),
+            _ => true,
+        }
+    }
+}
+
+/// Computes the strongly connected components of a [CallGraph] using
+/// Tarjan's algorithm.
+struct Components<'g> {
+    /// The graph that is being traversed.
+    graph: &'g CallGraph,
+
+    /// The index that will be given to the next node that is visited.
+    next_index: usize,
+
+    /// The order in which each node was visited, if it has been visited.
+    indices: Vec<Option<usize>>,
+
+    /// The smallest index of any node that is reachable from each node, and
+    /// is still on the stack.
+    low_links: Vec<usize>,
+
+    /// The nodes that are currently on the stack.
+    on_stack: FixedBitSet,
+
+    /// The nodes that haven't been assigned to a component yet.
+    stack: Vec<usize>,
+
+    /// The components that have been found so far.
+    components: Vec<Vec<usize>>,
+}
+
+impl Components<'_> {
+    /// Visit the given node, and all of the nodes that it has an edge to
+    /// which haven't been visited yet.
+    fn visit(&mut self, node: usize) {
+        let index = self.next_index;
+        self.next_index += 1;
+
+        self.indices[node] = Some(index);
+        self.low_links[node] = index;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        for &successor in self.graph.successors(node) {
+            match self.indices[successor] {
+                None => {
+                    self.visit(successor);
+                    self.low_links[node] = self.low_links[node].min(self.low_links[successor]);
+                }
+                Some(successor_index) if self.on_stack.contains(successor) => {
+                    self.low_links[node] = self.low_links[node].min(successor_index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        // If this node is the root of a component, then all of the nodes that
+        // are above it on the stack are part of the component.
+        if self.low_links[node] == index {
+            let mut component = vec![];
+
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.set(member, false);
+                component.push(member);
+
+                if member == node {
+                    break;
+                }
+            }
+
+            component.sort_unstable();
+            self.components.push(component);
+        }
+    }
+}
+
+/// The set of nodes of a [CallGraph] that are reachable from some root
+/// nodes, computed by [`CallGraph::reachable_from`].
+#[derive(Debug, Clone)]
+pub struct Reachable {
+    /// The nodes that were visited.
+    visited: FixedBitSet,
+}
+
+impl Reachable {
+    /// Check whether the node at the given index is reachable.
+    pub fn contains(&self, index: usize) -> bool {
+        self.visited.contains(index)
+    }
+
+    /// Get the number of reachable nodes.
+    pub fn count(&self) -> usize {
+        self.visited.count_ones(..)
+    }
+
+    /// Iterate over the indices of all of the reachable nodes.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.visited.ones()
+    }
+}
+
+/// Collects all of the function definitions that are used as values within
+/// a [Body], ignoring the functions that are being directly called.
+#[derive(Default)]
+struct FnReferences {
+    /// The referenced instances, in the order that they were found.
+    instances: Vec<InstanceId>,
+}
+
+impl FnReferences {
+    /// Record the constant if it refers to a function definition, either
+    /// directly or by its address.
+    fn record(&mut self, constant: &Const) {
+        if let ConstKind::FnAddr(instance) = constant.kind() {
+            self.instances.push(instance);
+        } else if let ReprTy::FnDef { instance } = *constant.ty().borrow() {
+            self.instances.push(instance);
+        }
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for FnReferences {
+    fn visit_call_terminator(
+        &mut self,
+        _: &Operand,
+        args: &[Operand],
+        _: &Place,
+        _: Option<BasicBlock>,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        // The callee of a direct call is already recorded as a call edge,
+        // and so only the arguments are checked.
+        args.iter().for_each(|arg| self.visit_operand(arg, ctx));
+    }
+
+    fn visit_const_value(&mut self, constant: &Const, _: &IrVisitorCtx<'_>) {
+        self.record(constant);
+    }
+
+    fn visit_const_rvalue(&mut self, constant: &Const, _: IrRef, _: &BodyInfo<'_>) {
+        self.record(constant);
+    }
+}
@@ -0,0 +1,49 @@
+//! Defines a map of all of the items that have been synthesised by
+//! the compiler from `#derive(...)` attributes on type definitions.
+
+use hash_attrs::attr::DeriveAttr;
+use hash_utils::fxhash::FxHashMap;
+
+use crate::ty::{InstanceId, ReprTyId};
+
+/// Stored information about a derived item, i.e. the synthesised
+/// `eq` function of some type.
+#[derive(Debug, Clone, Copy)]
+pub struct DerivedItemData {
+    /// The defined instance that corresponds to the derived item.
+    instance: InstanceId,
+
+    /// The type of the derived item.
+    ty: ReprTyId,
+}
+
+/// This struct is used to map a type and a [DeriveAttr] to the
+/// associated synthesised item.
+#[derive(Default)]
+pub struct DerivedItems {
+    /// The derived item map, keyed by the type that the item was
+    /// derived for.
+    items: FxHashMap<(ReprTyId, DeriveAttr), DerivedItemData>,
+}
+
+impl DerivedItems {
+    /// Create a new empty [DerivedItems] map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the derived item for the specified type.
+    pub fn set(&mut self, subject: ReprTyId, item: DeriveAttr, instance: InstanceId, ty: ReprTyId) {
+        self.items.insert((subject, item), DerivedItemData { instance, ty });
+    }
+
+    /// Get the [InstanceId] of the derived item for the specified type.
+    pub fn get(&self, subject: ReprTyId, item: DeriveAttr) -> Option<InstanceId> {
+        self.items.get(&(subject, item)).map(|item| item.instance)
+    }
+
+    /// Get the [ReprTyId] of the derived item for the specified type.
+    pub fn get_ty(&self, subject: ReprTyId, item: DeriveAttr) -> Option<ReprTyId> {
+        self.items.get(&(subject, item)).map(|item| item.ty)
+    }
+}
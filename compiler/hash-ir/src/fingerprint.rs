@@ -0,0 +1,83 @@
+//! Defines a map of fingerprints of the definitions that [Body]s were
+//! lowered from. When the compiler is running in an interactive or watch
+//! mode, the lowering stage is invoked on sources that have previously been
+//! lowered. The fingerprints are used to determine whether an existing
+//! [Body] can be reused, or whether the definition it was lowered from
+//! has changed, and so the [Body] must be rebuilt.
+//!
+//! [Body]: crate::ir::Body
+
+use std::hash::{Hash, Hasher};
+
+use hash_utils::fxhash::{FxHashMap, FxHasher};
+
+/// A [Fingerprint] is a hash of the definition that a [Body] was lowered
+/// from.
+///
+/// [Body]: crate::ir::Body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Compute the [Fingerprint] of the given value.
+    pub fn new(value: impl Hash) -> Self {
+        let mut hasher = FxHasher::default();
+        value.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// This struct is used to map [Fingerprint]s onto the index of the
+/// [Body] within [`crate::IrStorage::bodies`] that was lowered from the
+/// definition.
+///
+/// [Body]: crate::ir::Body
+#[derive(Default)]
+pub struct BodyFingerprints {
+    /// The fingerprint map.
+    bodies: FxHashMap<Fingerprint, usize>,
+}
+
+impl BodyFingerprints {
+    /// Create a new empty [BodyFingerprints] map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the body at `index` was lowered from a definition with
+    /// the given [Fingerprint].
+    pub fn set(&mut self, fingerprint: Fingerprint, index: usize) {
+        self.bodies.insert(fingerprint, index);
+    }
+
+    /// Get the index of the body that was lowered from a definition with
+    /// the given [Fingerprint].
+    pub fn get(&self, fingerprint: Fingerprint) -> Option<usize> {
+        self.bodies.get(&fingerprint).copied()
+    }
+
+    /// Check whether a body has been lowered from a definition with the
+    /// given [Fingerprint].
+    pub fn contains(&self, fingerprint: Fingerprint) -> bool {
+        self.bodies.contains_key(&fingerprint)
+    }
+
+    /// Iterate over all of the fingerprints and the index of the body that
+    /// they belong to.
+    pub fn iter(&self) -> impl Iterator<Item = (Fingerprint, usize)> + '_ {
+        self.bodies.iter().map(|(fingerprint, index)| (*fingerprint, *index))
+    }
+
+    /// Remap all of the recorded indices after bodies have been removed.
+    /// The `remap` function returns the new index of a body, or [None] if
+    /// the body was removed.
+    pub(crate) fn remap(&mut self, mut remap: impl FnMut(usize) -> Option<usize>) {
+        self.bodies.retain(|_, index| match remap(*index) {
+            Some(new) => {
+                *index = new;
+                true
+            }
+            None => false,
+        });
+    }
+}
@@ -0,0 +1,290 @@
+//! Planning of the global allocations of a program. Every constant allocation
+//! that is referred to by a lowered [Body] is assigned a [GlobalSection] and
+//! an offset within the section that respects the alignment of the
+//! allocation. Immutable allocations that contain the same bytes are pooled,
+//! i.e. two occurrences of the string `"hello"` share the same data, so that
+//! backends only emit the data once.
+//!
+//! The plan is computed once the bodies have been optimised, and can be
+//! printed with `--print-globals`.
+
+use std::fmt;
+
+use hash_repr::constant::{Const, ConstKind};
+use hash_source::constant::{AllocId, AllocRange, Mutability};
+use hash_storage::store::statics::StoreId;
+use hash_target::{alignment::Alignment, size::Size};
+use hash_utils::fxhash::{FxHashMap, FxHashSet};
+
+use crate::{
+    ir::{Body, BodyInfo, IrRef},
+    visitor::{IrVisitorCtx, IrVisitorMut},
+};
+
+/// The section of the program that a global allocation is placed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalSection {
+    /// Read-only data, i.e. string literals and constant aggregates.
+    ReadOnly,
+
+    /// Data which may be written to whilst the program is running.
+    Data,
+}
+
+impl GlobalSection {
+    /// All of the sections, in the order that they are printed.
+    const ALL: [GlobalSection; 2] = [GlobalSection::ReadOnly, GlobalSection::Data];
+}
+
+impl fmt::Display for GlobalSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobalSection::ReadOnly => write!(f, ".rodata"),
+            GlobalSection::Data => write!(f, ".data"),
+        }
+    }
+}
+
+/// The placement of a single allocation within the [GlobalAllocPlan].
+#[derive(Debug, Clone, Copy)]
+pub struct GlobalAlloc {
+    /// The allocation that is placed.
+    pub alloc: AllocId,
+
+    /// The section that the allocation is placed in.
+    pub section: GlobalSection,
+
+    /// The offset of the allocation from the start of the section.
+    pub offset: Size,
+
+    /// The size of the allocation.
+    pub size: Size,
+
+    /// The alignment of the allocation.
+    pub align: Alignment,
+
+    /// The allocation that holds the data of this allocation, if the data
+    /// was pooled with an allocation that was placed before it.
+    pub pooled_with: Option<AllocId>,
+}
+
+/// The placement of all of the global allocations that are referred to by
+/// the lowered bodies, see the [module documentation](self).
+#[derive(Debug, Default)]
+pub struct GlobalAllocPlan {
+    /// The placed allocations, in the order that they were first referred to.
+    allocs: Vec<GlobalAlloc>,
+
+    /// The index of each allocation within `allocs`.
+    indices: FxHashMap<AllocId, usize>,
+}
+
+impl GlobalAllocPlan {
+    /// Create an empty [GlobalAllocPlan].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute the [GlobalAllocPlan] of all of the allocations that are
+    /// referred to by the given bodies.
+    pub fn compute<'a>(bodies: impl IntoIterator<Item = &'a Body>) -> Self {
+        let mut collector = AllocCollector::default();
+
+        for body in bodies {
+            collector.visit(body);
+        }
+
+        let mut plan = Self::new();
+        let mut ends = [Size::ZERO; GlobalSection::ALL.len()];
+
+        // The allocation that first held each of the distinct immutable
+        // contents, this is used to pool allocations with the same bytes.
+        let mut pool: FxHashMap<Vec<u8>, usize> = FxHashMap::default();
+
+        for alloc in collector.allocs {
+            let (bytes, align, mutable) = alloc.map(|data| {
+                let bytes = data.read_bytes(AllocRange::new(Size::ZERO, data.size())).to_vec();
+                (bytes, data.align(), matches!(data.mutability(), Mutability::Mutable))
+            });
+
+            let size = Size::from_bytes(bytes.len() as u64);
+            let section = if mutable { GlobalSection::Data } else { GlobalSection::ReadOnly };
+
+            // The data can only be shared if the pooled allocation is placed
+            // at an offset that satisfies the alignment of this allocation.
+            let pooled = match section {
+                GlobalSection::ReadOnly => pool
+                    .get(&bytes)
+                    .map(|&index| &plan.allocs[index])
+                    .filter(|pooled| pooled.offset.align_to(align) == pooled.offset),
+                GlobalSection::Data => None,
+            };
+
+            let placed = match pooled {
+                Some(pooled) => GlobalAlloc {
+                    alloc,
+                    section,
+                    offset: pooled.offset,
+                    size,
+                    align,
+                    pooled_with: Some(pooled.pooled_with.unwrap_or(pooled.alloc)),
+                },
+                None => {
+                    let end = &mut ends[section as usize];
+                    let offset = end.align_to(align);
+                    *end = offset + size;
+
+                    if section == GlobalSection::ReadOnly {
+                        pool.entry(bytes).or_insert(plan.allocs.len());
+                    }
+
+                    GlobalAlloc { alloc, section, offset, size, align, pooled_with: None }
+                }
+            };
+
+            plan.indices.insert(alloc, plan.allocs.len());
+            plan.allocs.push(placed);
+        }
+
+        plan
+    }
+
+    /// Get the placement of the given allocation, if it is referred to by
+    /// any of the bodies.
+    pub fn get(&self, alloc: AllocId) -> Option<&GlobalAlloc> {
+        self.indices.get(&alloc).map(|&index| &self.allocs[index])
+    }
+
+    /// Get the allocation that holds the data of the given allocation. This
+    /// is the allocation itself, unless its data was pooled.
+    pub fn data_of(&self, alloc: AllocId) -> AllocId {
+        self.get(alloc).and_then(|placed| placed.pooled_with).unwrap_or(alloc)
+    }
+
+    /// Iterate over all of the placed allocations, in the order that they
+    /// were first referred to.
+    pub fn iter(&self) -> impl Iterator<Item = &GlobalAlloc> {
+        self.allocs.iter()
+    }
+
+    /// Compute the size of the given [GlobalSection].
+    pub fn section_size(&self, section: GlobalSection) -> Size {
+        self.allocs
+            .iter()
+            .filter(|placed| placed.section == section)
+            .map(|placed| placed.offset + placed.size)
+            .max()
+            .unwrap_or(Size::ZERO)
+    }
+
+    /// Get the number of placed allocations.
+    pub fn len(&self) -> usize {
+        self.allocs.len()
+    }
+
+    /// Check whether there are no placed allocations.
+    pub fn is_empty(&self) -> bool {
+        self.allocs.is_empty()
+    }
+}
+
+/// Write a short preview of the contents of an allocation, allocations that
+/// contain text are written as strings, and anything else as bytes.
+fn write_contents(f: &mut fmt::Formatter<'_>, alloc: AllocId) -> fmt::Result {
+    const LIMIT: usize = 16;
+
+    alloc.map(|data| {
+        let bytes = data.read_bytes(AllocRange::new(Size::ZERO, data.size()));
+
+        match std::str::from_utf8(bytes) {
+            Ok(text) if !text.chars().any(char::is_control) => write!(f, "{text:?}"),
+            _ => {
+                for byte in bytes.iter().take(LIMIT) {
+                    write!(f, "{byte:02x} ")?;
+                }
+
+                if bytes.len() > LIMIT {
+                    write!(f, "..")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    })
+}
+
+impl fmt::Display for GlobalAllocPlan {
+    /// Write the plan as a listing of each section, i.e.
+    /// ```notrust
+    /// .rodata (11 bytes)
+    ///   0x0000  #0  size 5  align 1  "hello"
+    ///   0x0005  #1  size 6  align 1  "world!"
+    ///   0x0000  #2  size 5  align 1  pooled with #0
+    /// .data (0 bytes)
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for section in GlobalSection::ALL {
+            writeln!(f, "{section} ({} bytes)", self.section_size(section).bytes())?;
+
+            for (index, placed) in self.allocs.iter().enumerate() {
+                if placed.section != section {
+                    continue;
+                }
+
+                write!(
+                    f,
+                    "  {:#06x}  #{index}  size {}  align {}  ",
+                    placed.offset.bytes(),
+                    placed.size.bytes(),
+                    placed.align.bytes()
+                )?;
+
+                match placed.pooled_with {
+                    Some(pooled) => writeln!(f, "pooled with #{}", self.indices[&pooled])?,
+                    None => {
+                        write_contents(f, placed.alloc)?;
+                        writeln!(f)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects all of the allocations that are referred to by the constants of
+/// a [Body].
+#[derive(Default)]
+struct AllocCollector {
+    /// The referenced allocations, in the order that they were found.
+    allocs: Vec<AllocId>,
+
+    /// The allocations that have already been found.
+    seen: FxHashSet<AllocId>,
+}
+
+impl AllocCollector {
+    /// Record the allocation of the constant, if it has one.
+    fn record(&mut self, constant: &Const) {
+        let alloc = match constant.kind() {
+            ConstKind::Pair { data, .. } => data,
+            ConstKind::Alloc { alloc, .. } => alloc,
+            ConstKind::Zero | ConstKind::Scalar(_) | ConstKind::FnAddr(_) => return,
+        };
+
+        if self.seen.insert(alloc) {
+            self.allocs.push(alloc);
+        }
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for AllocCollector {
+    fn visit_const_value(&mut self, constant: &Const, _: &IrVisitorCtx<'_>) {
+        self.record(constant);
+    }
+
+    fn visit_const_rvalue(&mut self, constant: &Const, _: IrRef, _: &BodyInfo<'_>) {
+        self.record(constant);
+    }
+}
@@ -139,7 +139,7 @@ impl LocalDecl {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum PlaceProjection {
     /// When we want to narrow down the union type to some specific
     /// variant.
@@ -472,6 +472,12 @@ pub enum StatementKind {
     /// A statement which is used to denote that a [Local] is now "dead"
     /// in terms of live interval.
     Dead(Local),
+
+    /// A statement which denotes that the given boolean [Operand] is known
+    /// to be `true` at this point, e.g. `index < len` after a bounds check
+    /// has passed. The statement has no runtime effect, but analyses and
+    /// backends may exploit the assumption.
+    Assume(Operand),
 }
 
 /// A [Statement] is a intermediate transformation step within a [BasicBlock].
@@ -511,6 +517,28 @@ pub enum AssertKind {
     /// whilst subtracting or terms that are signed
     NegativeOverflow { operand: Operand },
 
+    /// Occurs when a value is shifted by an amount that is not smaller than
+    /// the bit-width of the value.
+    ShiftAmountTooLarge {
+        /// The shift that is being performed.
+        op: BinOp,
+
+        /// The value that is being shifted.
+        lhs: Operand,
+
+        /// The amount that the value is being shifted by.
+        amount: Operand,
+    },
+
+    /// Occurs when an integer is raised to a negative power.
+    NegativeExponent {
+        /// The base of the exponentiation.
+        base: Operand,
+
+        /// The exponent of the exponentiation.
+        exponent: Operand,
+    },
+
     /// Bounds check assertion.
     BoundsCheck {
         /// The length of the array that is being checked.
@@ -519,6 +547,13 @@ pub enum AssertKind {
         /// The index that is being checked.
         index: Operand,
     },
+
+    /// Occurs when the discriminant of an enum doesn't belong to any of the
+    /// variants of the enum.
+    InvalidDiscriminant {
+        /// The discriminant that was read from the enum.
+        discriminant: Operand,
+    },
 }
 
 impl AssertKind {
@@ -534,15 +569,26 @@ impl AssertKind {
             AssertKind::Overflow { op: BinOp::Mod, .. } => {
                 "attempt to calculate the remainder with overflow"
             }
-            AssertKind::Overflow { op: BinOp::Shl, .. } => "attempt to shift left with overflow\n",
-            AssertKind::Overflow { op: BinOp::Shr, .. } => "attempt to shift right with overflow\n",
+            AssertKind::Overflow { op: BinOp::Exp, .. } => {
+                "attempt to raise to a power with overflow\n"
+            }
             AssertKind::Overflow { op, .. } => panic!("unexpected overflow operator `{op}`\n"),
             AssertKind::DivisionByZero { .. } => "attempt to divide by zero\n",
             AssertKind::RemainderByZero { .. } => {
                 "attempt to take remainder with a divisor of zero\n"
             }
             AssertKind::NegativeOverflow { .. } => "attempt to negate with overflow\n",
+            AssertKind::ShiftAmountTooLarge { op: BinOp::Shl, .. } => {
+                "attempt to shift left by an amount that is too large\n"
+            }
+            AssertKind::ShiftAmountTooLarge { .. } => {
+                "attempt to shift right by an amount that is too large\n"
+            }
+            AssertKind::NegativeExponent { .. } => "attempt to raise to a negative power\n",
             AssertKind::BoundsCheck { .. } => "attempt to index array out of bounds\n",
+            AssertKind::InvalidDiscriminant { .. } => {
+                "attempt to match on an invalid enum discriminant\n"
+            }
         }
     }
 }
@@ -571,11 +617,15 @@ impl Terminator {
     /// Get all of the successors of a [Terminator].
     pub fn successors(&self) -> Successors<'_> {
         match self.kind {
-            TerminatorKind::Goto(target)
-            | TerminatorKind::Call { target: Some(target), .. }
-            | TerminatorKind::Assert { target, .. } => {
+            TerminatorKind::Goto(target) | TerminatorKind::Drop { target, .. } => {
                 Some(target).into_iter().chain([].iter().copied())
             }
+            TerminatorKind::Call { target, ref unwind, .. } => {
+                target.into_iter().chain(unwind.as_slice().iter().copied())
+            }
+            TerminatorKind::Assert { target, ref unwind, .. } => {
+                Some(target).into_iter().chain(unwind.as_slice().iter().copied())
+            }
             TerminatorKind::Switch { ref targets, .. } => {
                 targets.otherwise.into_iter().chain(targets.targets.iter().copied())
             }
@@ -586,11 +636,15 @@ impl Terminator {
     /// Get all of the successors of a [Terminator] as mutable references.
     pub fn successors_mut(&mut self) -> SuccessorsMut<'_> {
         match self.kind {
-            TerminatorKind::Goto(ref mut target)
-            | TerminatorKind::Call { target: Some(ref mut target), .. }
-            | TerminatorKind::Assert { ref mut target, .. } => {
+            TerminatorKind::Goto(ref mut target) | TerminatorKind::Drop { ref mut target, .. } => {
                 Some(target).into_iter().chain(&mut [])
             }
+            TerminatorKind::Call { ref mut target, ref mut unwind, .. } => {
+                target.as_mut().into_iter().chain(unwind.as_mut_slice())
+            }
+            TerminatorKind::Assert { ref mut target, ref mut unwind, .. } => {
+                Some(target).into_iter().chain(unwind.as_mut_slice())
+            }
             TerminatorKind::Switch { ref mut targets, .. } => {
                 targets.otherwise.as_mut().into_iter().chain(targets.targets.iter_mut())
             }
@@ -598,6 +652,25 @@ impl Terminator {
         }
     }
 
+    /// Get the `unwind` edge of the [Terminator], if it has one.
+    pub fn unwind(&self) -> Option<BasicBlock> {
+        match self.kind {
+            TerminatorKind::Call { unwind, .. } | TerminatorKind::Assert { unwind, .. } => unwind,
+            _ => None,
+        }
+    }
+
+    /// Check whether the [Terminator] is a call through a function pointer,
+    /// i.e. the callee isn't statically known.
+    pub fn is_indirect_call(&self, info: &BodyInfo) -> bool {
+        match self.kind {
+            TerminatorKind::Call { ref op, .. } => {
+                !matches!(*op.ty(info).borrow(), ReprTy::FnDef { .. })
+            }
+            _ => false,
+        }
+    }
+
     /// Function that replaces a specified successor with another
     /// [BasicBlock].
     pub fn replace_edge(&mut self, successor: BasicBlock, replacement: BasicBlock) {
@@ -608,10 +681,21 @@ impl Terminator {
             TerminatorKind::Switch { ref mut targets, .. } => {
                 targets.replace_edge(successor, replacement)
             }
-            TerminatorKind::Call { target: Some(ref mut target), .. } if *target == successor => {
-                *target = replacement;
+            TerminatorKind::Call { ref mut target, ref mut unwind, .. } => {
+                for edge in target.iter_mut().chain(unwind.iter_mut()) {
+                    if *edge == successor {
+                        *edge = replacement;
+                    }
+                }
             }
-            TerminatorKind::Assert { ref mut target, .. } => {
+            TerminatorKind::Assert { ref mut target, ref mut unwind, .. } => {
+                for edge in iter::once(target).chain(unwind.iter_mut()) {
+                    if *edge == successor {
+                        *edge = replacement;
+                    }
+                }
+            }
+            TerminatorKind::Drop { ref mut target, .. } if *target == successor => {
                 *target = replacement;
             }
             // All other edges cannot be replaced
@@ -749,12 +833,21 @@ pub enum TerminatorKind {
 
         /// Where to return after completing the call
         target: Option<BasicBlock>,
+
+        /// Where to continue if the call panics, this is a cleanup block
+        /// which drops any locals that are in scope and then resumes
+        /// unwinding. If this is [None], then no cleanup is required.
+        unwind: Option<BasicBlock>,
     },
 
     /// Denotes that this terminator should never be reached, doing so will
     /// break IR control flow invariants.
     Unreachable,
 
+    /// Continue unwinding the stack after a panic. This terminates the
+    /// cleanup blocks that are reached via an `unwind` edge.
+    Resume,
+
     /// Essentially a `jump if <0> to <1> else go to <2>`. The last argument is
     /// the `otherwise` condition.
     Switch {
@@ -779,6 +872,21 @@ pub enum TerminatorKind {
         /// If the `condition` was verified, this is where the program should
         /// continue to.
         target: BasicBlock,
+        /// If the `condition` was not verified, the program panics and
+        /// continues unwinding from this cleanup block, if any.
+        unwind: Option<BasicBlock>,
+    },
+
+    /// Drop the value that is stored at the given [Place], i.e. run any
+    /// cleanup that the value requires when it goes out of scope. Drops are
+    /// inserted by the lowering stage whenever a scope is exited, including
+    /// early exits via `return`, `break` and `continue`.
+    Drop {
+        /// The place that is being dropped.
+        place: Place,
+        /// Where the program should continue to after the value has been
+        /// dropped.
+        target: BasicBlock,
     },
 }
 
@@ -884,13 +992,16 @@ pub enum BodySource {
     Const,
     /// The item is a normal function.
     Item,
+    /// The item is a function that is synthesised from a `#derive(...)`
+    /// attribute.
+    Derived,
 }
 
 impl fmt::Display for BodySource {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             BodySource::Const => write!(f, "constant block"),
-            BodySource::Item => write!(f, "function"),
+            BodySource::Item | BodySource::Derived => write!(f, "function"),
         }
     }
 }
@@ -934,6 +1045,10 @@ pub struct Body {
     /// Whether the IR Body that is generated should be printed
     /// when the generation process is finalised.
     dump: bool,
+
+    /// Whether the optimisation passes have already been applied to the
+    /// body.
+    optimised: bool,
 }
 
 impl Body {
@@ -955,6 +1070,7 @@ impl Body {
             arg_count,
             origin,
             dump: false,
+            optimised: false,
         }
     }
 
@@ -1011,6 +1127,18 @@ impl Body {
         self.dump
     }
 
+    /// Set the `optimised` flag to `true`, so that the optimisation passes
+    /// aren't applied to the body again.
+    pub fn mark_optimised(&mut self) {
+        self.optimised = true;
+    }
+
+    /// Check if the optimisation passes have already been applied to the
+    /// [Body].
+    pub fn is_optimised(&self) -> bool {
+        self.optimised
+    }
+
     /// Get the [BodyMetadata] for the [Body].
     pub fn metadata(&self) -> &BodyMetadata {
         &self.meta
@@ -1026,8 +1154,14 @@ impl Body {
         BodyInfoMut { locals: &mut self.locals, projections: &mut self.projections }
     }
 
-    /// Get the [Span] of the [Body].
-    pub fn span(&self) -> Span {
+    /// Get the [AstNodeId] of the item that the [Body] was lowered from.
+    pub fn origin(&self) -> AstNodeId {
+        self.origin
+    }
+
+    /// Get the [Span] of the definition that the [Body] was lowered from,
+    /// i.e. the function definition or the constant declaration.
+    pub fn source_span(&self) -> Span {
         self.origin.span()
     }
 
@@ -1056,12 +1190,16 @@ pub struct BodyMetadata {
 
     /// The type of the body that was lowered
     ty: Option<ReprTyId>,
+
+    /// All of the operations within the body that violate the memory
+    /// rules of the language, and hence should be audited.
+    pub unsafe_ops: Vec<UnsafeOp>,
 }
 
 impl BodyMetadata {
     /// Create a new [BodyMetadata] with the given `name`.
     pub fn new(name: Identifier, source: BodySource) -> Self {
-        Self { name, ty: None, source }
+        Self { name, ty: None, source, unsafe_ops: Vec::new() }
     }
 
     /// Set the type of the body that was lowered.
@@ -1085,6 +1223,60 @@ impl BodyMetadata {
     }
 }
 
+/// The kind of operation that was recorded as an [UnsafeOp].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsafeOpKind {
+    /// A pointer offset was computed, i.e. `ptr_offset(ptr, len)`.
+    PtrOffset,
+
+    /// A value was read from a raw pointer, i.e. `read(ptr)`.
+    PtrRead,
+
+    /// A value was written to a raw pointer, i.e. `write(ptr, value)`.
+    PtrWrite,
+
+    /// A raw pointer was dereferenced, i.e. `*ptr`.
+    RawDeref,
+
+    /// A value was transmuted into another type.
+    Transmute,
+
+    /// A call to a function that is defined outside of the language, i.e. a
+    /// `#foreign` function.
+    ExternCall,
+
+    /// A call to a function that is marked as `#unsafe_fn`.
+    UnsafeCall,
+}
+
+impl fmt::Display for UnsafeOpKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsafeOpKind::PtrOffset => write!(f, "pointer offset"),
+            UnsafeOpKind::PtrRead => write!(f, "pointer read"),
+            UnsafeOpKind::PtrWrite => write!(f, "pointer write"),
+            UnsafeOpKind::RawDeref => write!(f, "raw pointer dereference"),
+            UnsafeOpKind::Transmute => write!(f, "transmute"),
+            UnsafeOpKind::ExternCall => write!(f, "call to foreign function"),
+            UnsafeOpKind::UnsafeCall => write!(f, "call to unsafe function"),
+        }
+    }
+}
+
+/// An operation that was lowered within a [Body] that can violate the
+/// memory rules of the language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsafeOp {
+    /// The kind of operation that was performed.
+    pub kind: UnsafeOpKind,
+
+    /// Whether the operation occurred within an `unsafe` block.
+    pub in_unsafe_block: bool,
+
+    /// Where the operation originates from.
+    pub origin: AstNodeId,
+}
+
 /// All of the auxiliary stores that are used within a [Body]. This is useful
 /// for other functions that might need access to this information when reading
 /// items within the [Body].
@@ -13,6 +13,42 @@ pub enum LangItem {
     /// The `str_eq` intrinsic function. This will compare two strings
     /// and return a boolean value.
     StrEq,
+
+    /// The `print` function. This will write a string to the standard
+    /// output.
+    Print,
+
+    /// The `debug_int` function. This will write a signed integer to the
+    /// standard output.
+    DebugInt,
+
+    /// The `debug_uint` function. This will write an unsigned integer to
+    /// the standard output.
+    DebugUInt,
+
+    /// The `debug_float` function. This will write a float to the standard
+    /// output.
+    DebugFloat,
+
+    /// The `debug_char` function. This will write a character to the
+    /// standard output.
+    DebugChar,
+
+    /// The `serialize_bytes` function. This will write the lower bytes of an
+    /// integer into a buffer in little-endian order.
+    SerializeBytes,
+
+    /// The `deserialize_bytes` function. This will read a little-endian
+    /// integer from a buffer.
+    DeserializeBytes,
+
+    /// The `serialize_float` function. This will write the bits of a float
+    /// into a buffer in little-endian order.
+    SerializeFloat,
+
+    /// The `deserialize_float` function. This will read the bits of a float
+    /// from a buffer.
+    DeserializeFloat,
 }
 
 impl LangItem {
@@ -21,6 +57,15 @@ impl LangItem {
         match name {
             "panic" => Self::Panic,
             "str_eq" => Self::StrEq,
+            "print" => Self::Print,
+            "debug_int" => Self::DebugInt,
+            "debug_uint" => Self::DebugUInt,
+            "debug_float" => Self::DebugFloat,
+            "debug_char" => Self::DebugChar,
+            "serialize_bytes" => Self::SerializeBytes,
+            "deserialize_bytes" => Self::DeserializeBytes,
+            "serialize_float" => Self::SerializeFloat,
+            "deserialize_float" => Self::DeserializeFloat,
             _ => panic!("unknown language item: {name}"),
         }
     }
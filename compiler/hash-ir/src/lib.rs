@@ -10,10 +10,15 @@
 )]
 
 pub mod basic_blocks;
+pub mod call_graph;
 pub mod cast;
+pub mod derived;
+pub mod fingerprint;
+pub mod globals;
 pub mod intrinsics;
 pub mod ir;
 pub mod lang_items;
+pub mod link;
 pub mod traversal;
 pub mod ty;
 pub mod visitor;
@@ -23,11 +28,15 @@ use std::{
     sync::OnceLock,
 };
 
+use derived::DerivedItems;
+use fingerprint::{BodyFingerprints, Fingerprint};
+use globals::GlobalAllocPlan;
 use hash_source::entry_point::EntryPointState;
 use hash_storage::stores;
 use intrinsics::Intrinsics;
 use ir::Body;
 use lang_items::LangItems;
+use link::LinkItems;
 use ty::{AdtStore, InstanceId, InstanceStore, ReprTyListStore, ReprTyStore};
 
 /// Storage that is used by the lowering stage. This stores all of the
@@ -45,6 +54,17 @@ pub struct IrStorage {
 
     /// Holds information about the program entry point.
     pub entry_point: EntryPointState<InstanceId>,
+
+    /// The fingerprints of the definitions that the [Body]s were lowered
+    /// from, this is used to avoid re-lowering definitions that haven't
+    /// changed between pipeline runs.
+    pub fingerprints: BodyFingerprints,
+
+    /// The hashes of the optimised [Body]s, the hash at each index is the
+    /// hash of the body at the same index in [`Self::bodies`]. The hashes
+    /// are computed once the bodies have been optimised, and are cleared
+    /// whenever the bodies are modified.
+    pub body_hashes: Vec<Fingerprint>,
 }
 
 impl Default for IrStorage {
@@ -55,13 +75,75 @@ impl Default for IrStorage {
 
 impl IrStorage {
     pub fn new() -> Self {
-        Self { bodies: Vec::new(), ctx: IrCtx::new(), entry_point: EntryPointState::new() }
+        Self {
+            bodies: Vec::new(),
+            ctx: IrCtx::new(),
+            entry_point: EntryPointState::new(),
+            fingerprints: BodyFingerprints::new(),
+            body_hashes: Vec::new(),
+        }
     }
 
     /// Extend the the [IrStorage] with the generated bodies.
     pub fn add_bodies(&mut self, bodies: impl IntoIterator<Item = Body>) {
+        self.body_hashes.clear();
         self.bodies.extend(bodies)
     }
+
+    /// Extend the [IrStorage] with the generated bodies, recording the
+    /// [Fingerprint] of the definition that each body was lowered from.
+    pub fn add_fingerprinted_bodies(
+        &mut self,
+        bodies: impl IntoIterator<Item = (Option<Fingerprint>, Body)>,
+    ) {
+        self.body_hashes.clear();
+
+        for (fingerprint, body) in bodies {
+            if let Some(fingerprint) = fingerprint {
+                self.fingerprints.set(fingerprint, self.bodies.len());
+            }
+
+            self.bodies.push(body);
+        }
+    }
+
+    /// Remove all of the bodies that don't satisfy the given predicate. The
+    /// predicate is given the body, and the [Fingerprint] that it was
+    /// lowered with (if any).
+    pub fn retain_bodies(&mut self, mut f: impl FnMut(&Body, Option<Fingerprint>) -> bool) {
+        self.body_hashes.clear();
+
+        let mut fingerprints = vec![None; self.bodies.len()];
+        for (fingerprint, index) in self.fingerprints.iter() {
+            fingerprints[index] = Some(fingerprint);
+        }
+
+        let mut indices = Vec::with_capacity(self.bodies.len());
+        let mut index = 0;
+
+        for (body, fingerprint) in self.bodies.iter().zip(fingerprints) {
+            if f(body, fingerprint) {
+                indices.push(Some(index));
+                index += 1;
+            } else {
+                indices.push(None);
+            }
+        }
+
+        let mut current = 0;
+        self.bodies.retain(|_| {
+            current += 1;
+            indices[current - 1].is_some()
+        });
+
+        self.fingerprints.remap(|index| indices[index]);
+    }
+
+    /// Get the hash of the optimised body at the given index, if the hashes
+    /// have been computed.
+    pub fn body_hash(&self, index: usize) -> Option<Fingerprint> {
+        self.body_hashes.get(index).copied()
+    }
 }
 
 /// The [IrCtx] is used to store all interned information that
@@ -76,6 +158,18 @@ pub struct IrCtx {
     /// A map of intrinsics that will be filled ion at the code generation
     /// stage based on the selected backend for the compiler.
     intrinsics: RefCell<Intrinsics>,
+
+    /// A map of all of the items that were synthesised from `#derive(...)`
+    /// attributes during the lowering process.
+    derived_items: RefCell<DerivedItems>,
+
+    /// A map of all of the foreign items that are linked against a
+    /// library with a `#link(...)` attribute.
+    link_items: RefCell<LinkItems>,
+
+    /// The placement of all of the global allocations that are referred to
+    /// by the bodies, this is computed once the bodies have been optimised.
+    globals: RefCell<GlobalAllocPlan>,
 }
 
 stores!(
@@ -100,7 +194,13 @@ impl IrCtx {
         let lang_items = LangItems::new();
         let intrinsics = Intrinsics::new();
 
-        Self { lang_items: RefCell::new(lang_items), intrinsics: RefCell::new(intrinsics) }
+        Self {
+            lang_items: RefCell::new(lang_items),
+            intrinsics: RefCell::new(intrinsics),
+            derived_items: RefCell::new(DerivedItems::new()),
+            link_items: RefCell::new(LinkItems::new()),
+            globals: RefCell::new(GlobalAllocPlan::new()),
+        }
     }
 
     /// Get a reference to the [Intrinsics] map.
@@ -122,6 +222,36 @@ impl IrCtx {
     pub fn lang_items_mut(&self) -> RefMut<LangItems> {
         self.lang_items.borrow_mut()
     }
+
+    /// Get a reference to the [DerivedItems] map.
+    pub fn derived_items(&self) -> Ref<DerivedItems> {
+        self.derived_items.borrow()
+    }
+
+    /// Get a mutable reference to the [DerivedItems] map.
+    pub fn derived_items_mut(&self) -> RefMut<DerivedItems> {
+        self.derived_items.borrow_mut()
+    }
+
+    /// Get a reference to the [LinkItems] map.
+    pub fn link_items(&self) -> Ref<LinkItems> {
+        self.link_items.borrow()
+    }
+
+    /// Get a mutable reference to the [LinkItems] map.
+    pub fn link_items_mut(&self) -> RefMut<LinkItems> {
+        self.link_items.borrow_mut()
+    }
+
+    /// Get a reference to the [GlobalAllocPlan].
+    pub fn globals(&self) -> Ref<GlobalAllocPlan> {
+        self.globals.borrow()
+    }
+
+    /// Get a mutable reference to the [GlobalAllocPlan].
+    pub fn globals_mut(&self) -> RefMut<GlobalAllocPlan> {
+        self.globals.borrow_mut()
+    }
 }
 
 /// Interface to access information about the representations and layout.
@@ -0,0 +1,56 @@
+//! Defines a map of all of the foreign items that are linked against
+//! a library using `#link(...)` attributes.
+
+use hash_source::identifier::Identifier;
+use hash_utils::indexmap::{IndexMap, IndexSet};
+
+use crate::ty::InstanceId;
+
+/// Stored information about a foreign item, i.e. which library it should be
+/// resolved from, and the name of the symbol within the library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkItem {
+    /// The name of the library that the item is linked against, i.e. `c` for
+    /// `libc`.
+    pub library: Identifier,
+
+    /// The name of the symbol that the item resolves to.
+    pub symbol: Identifier,
+}
+
+/// This struct is used to map foreign [InstanceId]s to the [LinkItem]
+/// that they should be resolved with.
+#[derive(Default)]
+pub struct LinkItems {
+    /// The link item map, stored in the order that items were added so that
+    /// libraries are always linked in a deterministic order.
+    items: IndexMap<InstanceId, LinkItem>,
+}
+
+impl LinkItems {
+    /// Create a new empty [LinkItems] map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the [LinkItem] for the specified instance.
+    pub fn set(&mut self, instance: InstanceId, item: LinkItem) {
+        self.items.insert(instance, item);
+    }
+
+    /// Get the [LinkItem] for the specified instance.
+    pub fn get(&self, instance: InstanceId) -> Option<LinkItem> {
+        self.items.get(&instance).copied()
+    }
+
+    /// Iterate over all of the instances and their [LinkItem]s.
+    pub fn iter(&self) -> impl Iterator<Item = (InstanceId, LinkItem)> + '_ {
+        self.items.iter().map(|(instance, item)| (*instance, *item))
+    }
+
+    /// Get all of the libraries that are linked against, in the order that
+    /// they were first referenced.
+    pub fn libraries(&self) -> IndexSet<Identifier> {
+        self.items.values().map(|item| item.library).collect()
+    }
+}
@@ -18,6 +18,9 @@ use crate::ir::{BodyInfo, Place, PlaceProjection};
 pub trait InstanceHelpers {
     /// Check if the instance has the specified attribute.
     fn has_attr(&self, attr: impl Into<AttrId>) -> bool;
+
+    /// Get the specified attribute from the instance, if it is present.
+    fn get_attr(&self, attr: impl Into<AttrId>) -> Option<Attr>;
 }
 
 impl InstanceHelpers for Instance {
@@ -25,6 +28,10 @@ impl InstanceHelpers for Instance {
         let attr = attr.into();
         attr_store().node_has_attr(self.attr_id, attr)
     }
+
+    fn get_attr(&self, attr: impl Into<AttrId>) -> Option<Attr> {
+        attr_store().get_attr(self.attr_id, attr.into())
+    }
 }
 
 pub trait AdtHelpers {
@@ -63,6 +70,15 @@ fn adt_representation_from_attr(attr: &Attr) -> AdtRepresentation {
             debug_assert!(!value.is_big()); // Discriminant cannot be a big int.
             representation.discriminant = Some(value);
         }
+        ReprAttr::Packed => {
+            representation.add_flags(RepresentationFlags::PACKED);
+        }
+        ReprAttr::Align(alignment) => {
+            representation.alignment = Some(alignment);
+        }
+        ReprAttr::Simd => {
+            representation.add_flags(RepresentationFlags::SIMD);
+        }
     }
 
     representation
@@ -90,47 +106,61 @@ impl PlaceTy {
 
     /// Apply a projection to the current [PlaceTy].
     fn apply_projection(self, projection: PlaceProjection) -> Self {
+        self.try_apply_projection(projection)
+            .unwrap_or_else(|| panic!("cannot apply projection `{projection:?}` on {self:?}"))
+    }
+
+    /// Attempt to apply a projection to the current [PlaceTy]. If the
+    /// projection is not valid for the type of the place, i.e. a field access
+    /// on a non-ADT type, or a dereference of a non-reference type, then
+    /// this will return [None].
+    pub fn try_apply_projection(self, projection: PlaceProjection) -> Option<Self> {
         match projection {
-            PlaceProjection::Downcast(index) => PlaceTy { ty: self.ty, index: Some(index) },
-            PlaceProjection::Field(index) => {
-                let ty = self
-                    .ty
-                    .borrow()
-                    .on_field_access(index, self.index)
-                    .unwrap_or_else(|| panic!("expected an ADT, got {self:?}"));
+            PlaceProjection::Downcast(index) => {
+                let has_variant = self.ty.map(|ty| match ty {
+                    ReprTy::Adt(id) => id.borrow().variants.get(index).is_some(),
+                    _ => false,
+                });
 
-                PlaceTy { ty, index: None }
+                has_variant.then_some(PlaceTy { ty: self.ty, index: Some(index) })
             }
-            PlaceProjection::Deref => {
-                let ty = self
-                    .ty
-                    .borrow()
-                    .on_deref()
-                    .unwrap_or_else(|| panic!("expected a reference, got {self:?}"));
-
-                PlaceTy { ty, index: None }
+            PlaceProjection::Field(index) => {
+                let ty = self.ty.map(|ty| match ty {
+                    ReprTy::Adt(id) => id.map(|adt| {
+                        let variant = match self.index {
+                            Some(variant) => adt.variants.get(variant)?,
+                            None if adt.flags.is_struct() || adt.flags.is_tuple() => {
+                                adt.univariant()
+                            }
+                            None => return None,
+                        };
+
+                        variant.fields.get(index).map(|field| field.ty)
+                    }),
+                    _ => None,
+                })?;
+
+                Some(PlaceTy::from_ty(ty))
             }
+            PlaceProjection::Deref => self.ty.borrow().on_deref().map(PlaceTy::from_ty),
             PlaceProjection::Index(_) | PlaceProjection::ConstantIndex { .. } => {
-                let ty = self
-                    .ty
-                    .map(|ty| ty.on_index())
-                    .unwrap_or_else(|| panic!("expected an array or slice, got `{:?}`", self.ty));
-
-                PlaceTy { ty, index: None }
+                self.ty.map(|ty| ty.on_index()).map(PlaceTy::from_ty)
             }
             PlaceProjection::SubSlice { from, to, from_end } => {
                 let ty = self.ty.map(|base| match base {
-                    ReprTy::Slice(_) => self.ty,
+                    ReprTy::Slice(_) => Some(self.ty),
                     ReprTy::Array { ty, .. } if !from_end => {
-                        ReprTy::create(ReprTy::Array { ty: *ty, length: to - from })
+                        let length = to.checked_sub(from)?;
+                        Some(ReprTy::create(ReprTy::Array { ty: *ty, length }))
                     }
-                    ReprTy::Array { ty, length: size } if from_end => {
-                        ReprTy::create(ReprTy::Array { ty: *ty, length: size - from - to })
+                    ReprTy::Array { ty, length: size } => {
+                        let length = size.checked_sub(from + to)?;
+                        Some(ReprTy::create(ReprTy::Array { ty: *ty, length }))
                     }
-                    _ => panic!("expected an array or slice, got {self:?}"),
-                });
+                    _ => None,
+                })?;
 
-                PlaceTy { ty, index: None }
+                Some(PlaceTy::from_ty(ty))
             }
         }
     }
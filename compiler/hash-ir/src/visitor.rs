@@ -49,6 +49,15 @@ impl PlaceCtx {
     pub fn is_operand(self) -> bool {
         matches!(self, PlaceCtx::Immutable(ImmutablePlaceCtx::Operand))
     }
+
+    /// Check whether the [PlaceContext] is referring to the [Place]
+    /// being borrowed.
+    pub fn is_borrow(self) -> bool {
+        matches!(
+            self,
+            PlaceCtx::Immutable(ImmutablePlaceCtx::Ref) | PlaceCtx::Mutable(MutablePlaceCtx::Ref)
+        )
+    }
 }
 
 /// [ImmutablePlaceCtx] is a reference of where a a particular [Place] is
@@ -88,6 +97,9 @@ pub enum MutablePlaceCtx {
 
     /// The place is being used as a mutable reference.
     Ref,
+
+    /// This [Place] is being dropped by a [`TerminatorKind::Drop`].
+    Drop,
 }
 
 /// [MetaPlaceCtx] is a reference of where a a particular [Place] is
@@ -225,6 +237,8 @@ pub trait IrVisitorMut<'ir>: Sized {
 
     fn visit_return_terminator(&mut self, _: &IrVisitorCtx<'_>) {}
 
+    fn visit_resume_terminator(&mut self, _: &IrVisitorCtx<'_>) {}
+
     fn visit_call_terminator(
         &mut self,
         op: &Operand,
@@ -256,6 +270,10 @@ pub trait IrVisitorMut<'ir>: Sized {
         walk_mut::walk_assert_terminator(self, condition, expected, kind, target, ctx);
     }
 
+    fn visit_drop_terminator(&mut self, place: &Place, target: BasicBlock, ctx: &IrVisitorCtx<'_>) {
+        walk_mut::walk_drop_terminator(self, place, target, ctx);
+    }
+
     fn visit_assert_kind(&mut self, kind: &AssertKind, ctx: &IrVisitorCtx<'_>) {
         match kind {
             AssertKind::DivisionByZero { operand } => {
@@ -271,10 +289,21 @@ pub trait IrVisitorMut<'ir>: Sized {
             AssertKind::NegativeOverflow { operand } => {
                 walk_mut::walk_operand(self, operand, ctx);
             }
+            AssertKind::ShiftAmountTooLarge { lhs, amount, .. } => {
+                walk_mut::walk_operand(self, lhs, ctx);
+                walk_mut::walk_operand(self, amount, ctx);
+            }
+            AssertKind::NegativeExponent { base, exponent } => {
+                walk_mut::walk_operand(self, base, ctx);
+                walk_mut::walk_operand(self, exponent, ctx);
+            }
             AssertKind::BoundsCheck { len, index } => {
                 walk_mut::walk_operand(self, len, ctx);
                 walk_mut::walk_operand(self, index, ctx);
             }
+            AssertKind::InvalidDiscriminant { discriminant } => {
+                walk_mut::walk_operand(self, discriminant, ctx);
+            }
         }
     }
 
@@ -354,6 +383,7 @@ pub mod walk_mut {
             StatementKind::Live(local) | StatementKind::Dead(local) => {
                 visitor.visit_local(*local, PlaceCtx::Meta(MetaPlaceCtx::Liveness), ctx.location)
             }
+            StatementKind::Assume(operand) => visitor.visit_operand(operand, ctx),
         }
     }
 
@@ -385,15 +415,19 @@ pub mod walk_mut {
             TerminatorKind::Goto(target) => visitor.visit_goto_terminator(*target, ctx),
             TerminatorKind::Unreachable => visitor.visit_unreachable_terminator(ctx),
             TerminatorKind::Return => visitor.visit_return_terminator(ctx),
-            TerminatorKind::Call { op, args, destination, target } => {
+            TerminatorKind::Resume => visitor.visit_resume_terminator(ctx),
+            TerminatorKind::Call { op, args, destination, target, .. } => {
                 visitor.visit_call_terminator(op, args, destination, *target, ctx)
             }
             TerminatorKind::Switch { value, targets } => {
                 visitor.visit_switch_terminator(value, targets, ctx)
             }
-            TerminatorKind::Assert { condition, expected, kind, target } => {
+            TerminatorKind::Assert { condition, expected, kind, target, .. } => {
                 visitor.visit_assert_terminator(condition, *expected, kind, *target, ctx)
             }
+            TerminatorKind::Drop { place, target } => {
+                visitor.visit_drop_terminator(place, *target, ctx)
+            }
         }
     }
 
@@ -432,6 +466,15 @@ pub mod walk_mut {
         visitor.visit_assert_kind(kind, ctx);
     }
 
+    pub fn walk_drop_terminator<'ir, V: IrVisitorMut<'ir>>(
+        visitor: &mut V,
+        place: &Place,
+        _: BasicBlock,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        visitor.visit_place(place, PlaceCtx::Mutable(MutablePlaceCtx::Drop), ctx);
+    }
+
     pub fn walk_rvalue<'ir, V: IrVisitorMut<'ir>>(
         visitor: &mut V,
         value: &RValue,
@@ -697,6 +740,8 @@ pub trait ModifyingIrVisitor<'ir>: Sized {
 
     fn visit_return_terminator(&self, _: &mut IrVisitorCtxMut<'_>) {}
 
+    fn visit_resume_terminator(&self, _: &mut IrVisitorCtxMut<'_>) {}
+
     fn visit_call_terminator(
         &self,
         op: &mut Operand,
@@ -728,6 +773,15 @@ pub trait ModifyingIrVisitor<'ir>: Sized {
         walk_modifying::walk_assert_terminator(self, condition, expected, kind, target, ctx);
     }
 
+    fn visit_drop_terminator(
+        &self,
+        place: &mut Place,
+        target: &mut BasicBlock,
+        ctx: &mut IrVisitorCtxMut<'_>,
+    ) {
+        walk_modifying::walk_drop_terminator(self, place, target, ctx);
+    }
+
     fn visit_assert_kind(&self, kind: &mut AssertKind, ctx: &mut IrVisitorCtxMut<'_>) {
         match kind {
             AssertKind::DivisionByZero { operand } => {
@@ -743,10 +797,21 @@ pub trait ModifyingIrVisitor<'ir>: Sized {
             AssertKind::NegativeOverflow { operand } => {
                 walk_modifying::walk_operand(self, operand, ctx);
             }
+            AssertKind::ShiftAmountTooLarge { lhs, amount, .. } => {
+                walk_modifying::walk_operand(self, lhs, ctx);
+                walk_modifying::walk_operand(self, amount, ctx);
+            }
+            AssertKind::NegativeExponent { base, exponent } => {
+                walk_modifying::walk_operand(self, base, ctx);
+                walk_modifying::walk_operand(self, exponent, ctx);
+            }
             AssertKind::BoundsCheck { len, index } => {
                 walk_modifying::walk_operand(self, len, ctx);
                 walk_modifying::walk_operand(self, index, ctx);
             }
+            AssertKind::InvalidDiscriminant { discriminant } => {
+                walk_modifying::walk_operand(self, discriminant, ctx);
+            }
         }
     }
 
@@ -829,6 +894,7 @@ pub mod walk_modifying {
             StatementKind::Live(local) | StatementKind::Dead(local) => {
                 visitor.visit_local(local, PlaceCtx::Meta(MetaPlaceCtx::Liveness), ctx.location)
             }
+            StatementKind::Assume(operand) => visitor.visit_operand(operand, ctx),
         }
     }
 
@@ -860,15 +926,19 @@ pub mod walk_modifying {
             TerminatorKind::Goto(target) => visitor.visit_goto_terminator(target, ctx),
             TerminatorKind::Unreachable => visitor.visit_unreachable_terminator(ctx),
             TerminatorKind::Return => visitor.visit_return_terminator(ctx),
-            TerminatorKind::Call { op, args, destination, target } => {
+            TerminatorKind::Resume => visitor.visit_resume_terminator(ctx),
+            TerminatorKind::Call { op, args, destination, target, .. } => {
                 visitor.visit_call_terminator(op, args, destination, target, ctx)
             }
             TerminatorKind::Switch { value, targets } => {
                 visitor.visit_switch_terminator(value, targets, ctx)
             }
-            TerminatorKind::Assert { condition, expected, kind, target } => {
+            TerminatorKind::Assert { condition, expected, kind, target, .. } => {
                 visitor.visit_assert_terminator(condition, expected, kind, target, ctx)
             }
+            TerminatorKind::Drop { place, target } => {
+                visitor.visit_drop_terminator(place, target, ctx)
+            }
         }
     }
 
@@ -909,6 +979,15 @@ pub mod walk_modifying {
         visitor.visit_assert_kind(assert, ctx);
     }
 
+    pub fn walk_drop_terminator<'ir, V: ModifyingIrVisitor<'ir>>(
+        visitor: &V,
+        place: &mut Place,
+        _: &mut BasicBlock,
+        ctx: &mut IrVisitorCtxMut<'_>,
+    ) {
+        visitor.visit_place(place, PlaceCtx::Mutable(MutablePlaceCtx::Drop), ctx);
+    }
+
     pub fn walk_rvalue<'ir, V: ModifyingIrVisitor<'ir>>(
         visitor: &V,
         value: &mut RValue,
@@ -4,6 +4,7 @@ use std::{cell::Cell, fmt::Display, iter};
 
 use hash_reporting::{
     diagnostic::{DiagnosticStore, HasDiagnosticsMut},
+    hash_error_codes::error_codes::HashErrorCode,
     report::{help, info, note, Report},
     reporter::{Reporter, Reports},
     unicode_normalization::UnicodeNormalization,
@@ -197,12 +198,49 @@ pub enum LexerErrorKind {
     NonAsciiByteLit(char),
 }
 
+impl LexerErrorKind {
+    /// Get the [HashErrorCode] that is associated with the kind of error.
+    fn code(&self) -> HashErrorCode {
+        match self {
+            LexerErrorKind::MalformedNumericalLit
+            | LexerErrorKind::MissingExponentDigits
+            | LexerErrorKind::MissingDigits
+            | LexerErrorKind::UnsupportedFloatBaseLiteral(_)
+            | LexerErrorKind::InvalidFloatExponent => HashErrorCode::InvalidLiteral,
+            LexerErrorKind::InvalidLitSuffix(..) => HashErrorCode::InvalidLiteralSuffix,
+            LexerErrorKind::UnclosedStringLit | LexerErrorKind::UnclosedCharLit => {
+                HashErrorCode::UnclosedLiteral
+            }
+            LexerErrorKind::Unexpected(_) | LexerErrorKind::Expected(_) => {
+                HashErrorCode::UnexpectedCharacter
+            }
+            LexerErrorKind::Unclosed(_) => HashErrorCode::UnclosedDelimiter,
+            LexerErrorKind::EmptyCharLit | LexerErrorKind::MultipleCharCodePoints { .. } => {
+                HashErrorCode::InvalidCharLiteral
+            }
+            LexerErrorKind::UnknownEscapeSequence(_)
+            | LexerErrorKind::UnclosedUnicodeLit
+            | LexerErrorKind::MalformedUnicodeLit
+            | LexerErrorKind::InvalidUnicodeEscape(_)
+            | LexerErrorKind::UnicodeLitTooLong
+            | LexerErrorKind::NumericEscapeSequenceTooShort
+            | LexerErrorKind::NumericEscapeSequenceTooLarge
+            | LexerErrorKind::InvalidNumericEscapeSequence(_)
+            | LexerErrorKind::UnicodeLitTooLarge => HashErrorCode::InvalidEscapeSequence,
+            LexerErrorKind::UnicodeEscapeInByteLit | LexerErrorKind::NonAsciiByteLit(_) => {
+                HashErrorCode::InvalidByteLiteral
+            }
+        }
+    }
+}
+
 impl From<LexerError> for Reports {
     fn from(err: LexerError) -> Self {
         let mut reporter = Reporter::new();
 
         let mut span_label = None;
         let mut help_notes = vec![];
+        let code = err.kind.code();
 
         let message = match err.kind {
             LexerErrorKind::UnknownEscapeSequence(ch) => {
@@ -356,6 +394,7 @@ impl From<LexerError> for Reports {
 
         let report = reporter
             .error()
+            .code(code)
             .title(message)
             .add_labelled_span(err.location, span_label.unwrap_or("here".to_string()));
 
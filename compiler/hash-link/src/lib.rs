@@ -16,6 +16,7 @@ use std::{
 
 use command::{EscapeArg, LinkCommand};
 use error::{escape_returned_error, AdditionalFailureInfo, LinkerError};
+use hash_ir::IrStorage;
 use hash_pipeline::{
     interface::{
         CompilerInterface, CompilerOutputStream, CompilerResult, CompilerStage, StageMetrics,
@@ -39,6 +40,10 @@ pub struct LinkerCtx<'ctx> {
     /// Reference to the current compiler workspace.
     pub workspace: &'ctx Workspace,
 
+    /// Reference to the IR storage, this is used to find all of the
+    /// libraries that foreign items are linked against.
+    pub icx: &'ctx IrStorage,
+
     /// A reference to the backend settings in the current session.
     pub settings: &'ctx CompilerSettings,
 
@@ -82,7 +87,7 @@ impl<Ctx: LinkerCtxQuery> CompilerStage<Ctx> for CompilerLinker {
     }
 
     fn run(&mut self, _: SourceId, ctx: &mut Ctx) -> CompilerResult<()> {
-        let LinkerCtx { workspace, settings, mut stdout } = ctx.data();
+        let LinkerCtx { workspace, icx, settings, mut stdout } = ctx.data();
 
         // If we are not emitting an executable, then we can
         if !workspace.yields_executable(settings) || workspace.code_map.objects().next().is_none() {
@@ -100,7 +105,7 @@ impl<Ctx: LinkerCtxQuery> CompilerStage<Ctx> for CompilerLinker {
         let linker = &mut *self.record("find", |_| get_linker(&linker_path, flavour, settings));
 
         let linker_command =
-            build_linker_args(linker, flavour, settings, workspace, output_path.as_path())
+            build_linker_args(linker, flavour, settings, workspace, icx, output_path.as_path())
                 .map_err(|err| vec![err.into()])?;
 
         // print out link-line if specified via `-Cdump=link-line`
@@ -10,6 +10,7 @@ use std::{
     path::Path,
 };
 
+use hash_ir::IrStorage;
 use hash_pipeline::{settings::CompilerSettings, workspace::Workspace};
 use hash_target::{
     link::{Cc, LinkerFlavour, Lld, RelocationModel},
@@ -141,7 +142,7 @@ pub(super) fn get_linker<'a>(
                         TargetArch::X86_64 => Some("x86"),
                         TargetArch::Aarch64 => Some("arm64"),
                         TargetArch::Arm => Some("arm"),
-                        TargetArch::Unknown => None,
+                        TargetArch::Msp430 | TargetArch::Unknown => None,
                     };
 
                     if let Some(arch_name) = arch {
@@ -174,13 +175,14 @@ pub(super) fn get_linker<'a>(
     }
 }
 
-/// Given the [CompilerSettings], the [Workspace], and the specified file
-/// path, we create a new linker instance.
+/// Given the [CompilerSettings], the [Workspace], the [IrStorage], and the
+/// specified file path, we create a new linker instance.
 pub(crate) fn build_linker_args<'a>(
     linker: &mut dyn Linker,
     flavour: LinkerFlavour,
     settings: &'a CompilerSettings,
     workspace: &Workspace,
+    icx: &IrStorage,
     output_filename: &Path,
 ) -> LinkerResult<'a, LinkCommand> {
     // Compute the output kind from the session
@@ -206,8 +208,17 @@ pub(crate) fn build_linker_args<'a>(
         linker.add_object(object);
     }
 
-    // @@Todo: we need to record what libraries are being used by the workspace, and
-    // then add them here.
+    // The libraries are searched for in the directories that were specified
+    // via `--includes`, before the default search paths of the linker.
+    for path in &settings.codegen_settings.library_paths {
+        linker.include_path(path);
+    }
+
+    // Then, we link against all of the libraries that foreign items were declared
+    // to come from using `#link(...)`.
+    for library in icx.ctx.link_items().libraries() {
+        linker.link_dylib(library.as_str(), false, true);
+    }
 
     // ------------ Late order independent options ------------
 
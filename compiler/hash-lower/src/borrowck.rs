@@ -0,0 +1,235 @@
+//! Implements a conservative aliasing analysis on lowered IR [Body]s. This is
+//! a first step towards a real borrow checker, and it only reports borrows
+//! that obviously conflict with one another, namely a reference to a place
+//! being created whilst an overlapping reference is still in use, and at
+//! least one of the references is mutable.
+//!
+//! A borrow is created by an assignment of a [`RValue::Ref`] to a [Local], the
+//! borrow is then considered to be "in scope" at all of the points that are
+//! reachable from the assignment without the [Local] being overwritten. A
+//! borrow that is in scope conflicts with a new borrow if the [Local] that
+//! holds it is still live after the new borrow is created.
+//!
+//! @@Future: references that are copied into other locals, or that are stored
+//! within aggregates are not tracked, and so conflicts through them are not
+//! reported. Raw and reference counted references are not considered at all.
+
+use fixedbitset::FixedBitSet;
+use hash_ast::ast::AstNodeId;
+use hash_ir::{
+    ir::{
+        Body, IrRef, Local, Place, PlaceProjection, RValue, Statement, StatementKind, Terminator,
+        TerminatorKind,
+    },
+    ty::{Mutability, RefKind},
+    visitor::{IrVisitorCtx, IrVisitorMut},
+};
+use hash_ir_analysis::{
+    liveness::{Liveness, LivenessTransfer},
+    Analysis, Direction, Results,
+};
+use hash_utils::fxhash::FxHashMap;
+
+use crate::diagnostics::LoweringDiagnostic;
+
+/// A reference that is created within a [Body].
+#[derive(Debug, Clone, Copy)]
+struct Borrow {
+    /// The [Local] that the reference is stored in.
+    local: Local,
+
+    /// The [Place] that is being borrowed.
+    place: Place,
+
+    /// Whether the borrow is mutable or not.
+    mutability: Mutability,
+
+    /// Where the borrow is created.
+    origin: AstNodeId,
+}
+
+/// The [BorrowChecker] walks over a [Body] and reports any conflicting
+/// borrows that it finds.
+pub(crate) struct BorrowChecker<'ir> {
+    /// The body that is being checked.
+    body: &'ir Body,
+
+    /// All of the borrows that are created within the body.
+    borrows: Vec<Borrow>,
+
+    /// A map from the location of an assignment to the borrow that the
+    /// assignment creates.
+    locations: FxHashMap<IrRef, usize>,
+}
+
+impl<'ir> BorrowChecker<'ir> {
+    /// Create a new [BorrowChecker] for the given [Body].
+    pub(crate) fn new(body: &'ir Body) -> Self {
+        let mut borrows = Vec::new();
+        let mut locations = FxHashMap::default();
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            for (index, statement) in data.statements.iter().enumerate() {
+                if let StatementKind::Assign(dest, RValue::Ref(mutability, place, kind)) =
+                    statement.kind
+                    && kind == RefKind::Normal
+                    && let Some(local) = dest.as_local()
+                {
+                    locations.insert(IrRef::new(block, index), borrows.len());
+                    borrows.push(Borrow { local, place, mutability, origin: statement.origin });
+                }
+            }
+        }
+
+        Self { body, borrows, locations }
+    }
+
+    /// Check the [Body] for any conflicting borrows, at most one conflict is
+    /// reported for each borrow.
+    pub(crate) fn check(&self) -> Vec<LoweringDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if self.borrows.is_empty() {
+            return diagnostics;
+        }
+
+        let in_scope = Results::compute(BorrowsInScope { checker: self }, self.body);
+        let liveness = Liveness::compute(self.body);
+        let info = self.body.aux();
+
+        for (block, data) in self.body.blocks().iter_enumerated() {
+            // Compute the live locals after each statement by walking
+            // backwards from the exit of the block.
+            let mut live = liveness.live_out(block);
+            let mut live_after = vec![live.clone(); data.statements.len()];
+
+            if let Some(terminator) = &data.terminator {
+                let ctx = IrVisitorCtx::new(IrRef::new(block, data.statements.len()), info);
+                LivenessTransfer::new(&mut live).visit_terminator(terminator, &ctx);
+            }
+
+            for (index, statement) in data.statements.iter().enumerate().rev() {
+                live_after[index] = live.clone();
+
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                LivenessTransfer::new(&mut live).visit_statement(statement, &ctx);
+            }
+
+            let mut state = in_scope.entry_set(block).clone();
+
+            for (index, statement) in data.statements.iter().enumerate() {
+                if let Some(&new) = self.locations.get(&IrRef::new(block, index))
+                    && let Some(conflict) = self.find_conflict(new, &state, &live_after[index])
+                {
+                    let (borrow, previous) = (self.borrows[new], self.borrows[conflict]);
+
+                    diagnostics.push(LoweringDiagnostic::ConflictingBorrow {
+                        mutability: borrow.mutability,
+                        previous_mutability: previous.mutability,
+                        origin: borrow.origin,
+                        previous: previous.origin,
+                    });
+                }
+
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                in_scope.analysis.apply_statement(&mut state, statement, &ctx);
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Find a borrow within `state` that conflicts with the `new` borrow, a
+    /// borrow conflicts if it is still used after the new borrow is created.
+    fn find_conflict(&self, new: usize, state: &FixedBitSet, live: &FixedBitSet) -> Option<usize> {
+        let borrow = self.borrows[new];
+
+        state.ones().find(|&index| {
+            let other = self.borrows[index];
+
+            other.local != borrow.local
+                && live.contains(other.local.index())
+                && (borrow.mutability == Mutability::Mutable
+                    || other.mutability == Mutability::Mutable)
+                && self.places_overlap(borrow.place, other.place)
+        })
+    }
+
+    /// Check whether the two places obviously overlap, i.e. one of the places
+    /// is contained within the other. If the projections of the places can't
+    /// be compared, for example when indexing with a non-constant index, then
+    /// they are assumed to not overlap.
+    fn places_overlap(&self, a: Place, b: Place) -> bool {
+        if a.local != b.local {
+            return false;
+        }
+
+        let projections = &self.body.projections;
+        let (a, b) = (projections.borrow(a.projections), projections.borrow(b.projections));
+
+        a.iter().zip(b.iter()).all(|(a, b)| a == b && !matches!(a, PlaceProjection::Index(_)))
+    }
+
+    /// Remove all of the borrows that are stored in the given [Local] from
+    /// the set, since the local has been overwritten.
+    fn kill_borrows_in(&self, state: &mut FixedBitSet, local: Local) {
+        for (index, borrow) in self.borrows.iter().enumerate() {
+            if borrow.local == local {
+                state.set(index, false);
+            }
+        }
+    }
+}
+
+/// The forward analysis that computes the set of borrows that are in scope at
+/// each point of the [Body]. No borrows are in scope when entering the body.
+struct BorrowsInScope<'a, 'ir> {
+    checker: &'a BorrowChecker<'ir>,
+}
+
+impl Analysis for BorrowsInScope<'_, '_> {
+    type Domain = FixedBitSet;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom(&self, _: &Body) -> Self::Domain {
+        FixedBitSet::with_capacity(self.checker.borrows.len())
+    }
+
+    fn apply_statement(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        match statement.kind {
+            StatementKind::Assign(place, _) => {
+                if let Some(local) = place.as_local() {
+                    self.checker.kill_borrows_in(state, local);
+                }
+
+                if let Some(&borrow) = self.checker.locations.get(&ctx.location) {
+                    state.insert(borrow);
+                }
+            }
+            StatementKind::Dead(local) => self.checker.kill_borrows_in(state, local),
+            StatementKind::Nop
+            | StatementKind::Discriminate(..)
+            | StatementKind::Live(_)
+            | StatementKind::Assume(_) => {}
+        }
+    }
+
+    fn apply_terminator(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        _: &IrVisitorCtx<'_>,
+    ) {
+        if let TerminatorKind::Call { destination, .. } = terminator.kind
+            && let Some(local) = destination.as_local()
+        {
+            self.checker.kill_borrows_in(state, local);
+        }
+    }
+}
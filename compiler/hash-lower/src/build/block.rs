@@ -4,18 +4,24 @@
 //! is located in `matches.rs`.
 use std::mem;
 
+use hash_ast::ast::{AstNodeId, MatchOrigin};
+use hash_const_eval::op::BinOp;
 use hash_ir::{
-    ir::{BasicBlock, Place},
-    ty::Mutability,
+    ir::{BasicBlock, Const, LocalDecl, Operand, Place, RValue, TerminatorKind},
+    ty::{Mutability, COMMON_REPR_TYS},
 };
-use hash_storage::store::{statics::StoreId, TrivialSequenceStoreKey};
+use hash_storage::store::{statics::StoreId, SequenceStoreKey, TrivialSequenceStoreKey};
 use hash_tir::{
     context::{Context, ScopeKind},
-    tir::{BlockStatement, BlockTerm, LoopTerm, MatchTerm, Term, TermId},
+    intrinsics::{definitions::Intrinsic as TirIntrinsic, utils::try_use_term_as_bool},
+    tir::{
+        BlockStatement, BlockTerm, CallTerm, CtorPat, LoopTerm, MatchCase, MatchTerm, NodesId, Pat,
+        PatId, Term, TermId,
+    },
 };
 
-use super::{BlockAnd, BlockAndExtend, BodyBuilder, LoopBlockInfo};
-use crate::build::unpack;
+use super::{place::PlaceBuilder, BlockAnd, BlockAndExtend, BodyBuilder, LoopBlockInfo};
+use crate::{build::unpack, diagnostics::LoweringDiagnostic};
 
 impl<'tcx> BodyBuilder<'tcx> {
     pub(crate) fn block_into_dest(
@@ -27,8 +33,12 @@ impl<'tcx> BodyBuilder<'tcx> {
         let span = self.span_of_term(block_term);
 
         match *block_term.value() {
-            Term::Block(ref body) => self.body_block_into_dest(place, block, body),
+            Term::Block(ref body) => self.body_block_into_dest(place, block, span, body),
             Term::Loop(LoopTerm { inner }) => {
+                if let Some(for_loop) = self.classify_indexed_for_loop(inner) {
+                    return self.lower_indexed_for_loop(block, span, for_loop);
+                }
+
                 // Begin the loop block by connecting the previous block
                 // and terminating it with a `goto` instruction to this block
                 let loop_body = self.control_flow_graph.start_new_block();
@@ -64,6 +74,7 @@ impl<'tcx> BodyBuilder<'tcx> {
         &mut self,
         place: Place,
         mut block: BasicBlock,
+        span: AstNodeId,
         body: &BlockTerm,
     ) -> BlockAnd<()> {
         let BlockTerm { stack_id, statements, expr } = body;
@@ -73,6 +84,8 @@ impl<'tcx> BodyBuilder<'tcx> {
         }
 
         Context::enter_resolved_scope_mut(self, ScopeKind::Stack(*stack_id), |this| {
+            this.control_flow_graph.push_drop_scope();
+
             // Essentially walk all of the statement in the block, and then set
             // the return type of this block as the last expression, or an empty
             // unit if there is no expression.
@@ -99,6 +112,18 @@ impl<'tcx> BodyBuilder<'tcx> {
             if !this.reached_terminator {
                 unpack!(block = this.term_into_dest(place, block, *expr));
             }
+
+            // If the block was exited early, then the locals of the block
+            // have already been dropped on the path that exits the block.
+            //
+            // @@Future: we don't track whether a local has been moved out
+            // of, so a local that is moved into the `place` of the block is
+            // still dropped here.
+            if this.reached_terminator {
+                this.control_flow_graph.exit_drop_scope();
+            } else {
+                block = this.control_flow_graph.pop_drop_scope(block, span);
+            }
         });
 
         // we finally reset the reached terminator flag so that we can
@@ -107,6 +132,261 @@ impl<'tcx> BodyBuilder<'tcx> {
         block.unit()
     }
 
+    /// Check whether the `inner` term of a loop is a de-sugared `for` loop,
+    /// i.e. it is of the form:
+    /// ```text
+    /// loop {
+    ///     match for_items(<iterable>) {
+    ///         Option::Some(<pat>) => <body>,
+    ///         Option::None => break,
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Or, `for_range(<start>, <end>, <inclusive>)` for a range. The subject
+    /// of the match is a call to one of the `for_items` and `for_range`
+    /// intrinsics, which name resolution creates from the iterator of the
+    /// loop. Such loops are always lowered into an index-based loop, the
+    /// intrinsics themselves are never called.
+    fn classify_indexed_for_loop(&mut self, inner: TermId) -> Option<IndexedForLoop> {
+        let term = match *inner.value() {
+            Term::Block(BlockTerm { statements, expr, .. }) if statements.is_empty() => expr,
+            _ => inner,
+        };
+
+        let Term::Match(MatchTerm { subject, cases, origin: MatchOrigin::For }) = *term.value()
+        else {
+            return None;
+        };
+
+        let Term::Call(CallTerm { subject: callee, args, .. }) = *subject.value() else {
+            return None;
+        };
+
+        let Term::Intrinsic(intrinsic) = *callee.value() else {
+            return None;
+        };
+
+        let arg = |index: usize| args.at(index).map(|arg| arg.borrow().value);
+
+        // The leading type arguments of the intrinsics are skipped.
+        let iterable = match intrinsic {
+            TirIntrinsic::ForItems => ForLoopIterable::Items(arg(2)?),
+            TirIntrinsic::ForRange => {
+                let (start, end) = (arg(1)?, arg(2)?);
+                let inclusive = try_use_term_as_bool(arg(3)?)?;
+                ForLoopIterable::Range { start, end, inclusive }
+            }
+            _ => return None,
+        };
+
+        // The first case is always the `Some(<pat>)` case of the loop.
+        let case = cases.elements().borrow().first().map(|case| **case)?;
+        let Pat::Ctor(CtorPat { ctor_pat_args, .. }) = *case.bind_pat.value() else {
+            return None;
+        };
+
+        if ctor_pat_args.len() != 1 {
+            return None;
+        }
+
+        let pat = ctor_pat_args.at(0)?.borrow().pat.assert_pat();
+        let origin = self.span_of_term(subject);
+        Some(IndexedForLoop { iterable, case, pat, origin })
+    }
+
+    /// Lower a `for` loop that was classified by
+    /// [`Self::classify_indexed_for_loop`]. A loop over an array or a slice
+    /// is lowered as follows:
+    /// ```text
+    /// bb0 {
+    ///     _i = const 0_usize;
+    ///     goto -> bb1;
+    /// }
+    ///
+    /// bb1 {
+    ///     _len = len(<iterable>);
+    ///     _cond = Lt(_i, _len);
+    ///     switch(_cond) [false -> bb4, otherwise -> bb2];
+    /// }
+    ///
+    /// bb2 {
+    ///     <pat> = <iterable>[_i];
+    ///     <body>
+    ///     goto -> bb3;
+    /// }
+    ///
+    /// bb3 {
+    ///     _i = Add(_i, const 1_usize);
+    ///     goto -> bb1;
+    /// }
+    /// ```
+    ///
+    /// Since the index is always checked against the length of the iterable
+    /// in the loop header, the element access does not need a bounds check,
+    /// and the increment of the index can never overflow.
+    ///
+    /// A loop over a range starts the index at `start`, compares it against
+    /// `end` which is only evaluated once, and binds `<pat>` to the index
+    /// itself. If the range includes `end`, the header compares the index
+    /// with `LtEq`, and the latch exits the loop once the index is equal to
+    /// `end` rather than incrementing it past the largest value of the type.
+    ///
+    /// The iterable of the loop must be an array or a slice, or a range of
+    /// integers, otherwise a diagnostic is emitted and the loop is not
+    /// lowered.
+    fn lower_indexed_for_loop(
+        &mut self,
+        mut block: BasicBlock,
+        span: AstNodeId,
+        for_loop: IndexedForLoop,
+    ) -> BlockAnd<()> {
+        let IndexedForLoop { iterable, case, pat, origin } = for_loop;
+
+        let (items, index_ty, start, end, inclusive) = match iterable {
+            ForLoopIterable::Items(items) => {
+                // The iterable must be an array or a slice place, the element
+                // is then read directly from the iterable.
+                let ty = self.ty_id_from_tir_term(items);
+                if ty.borrow().element_ty().is_none() {
+                    let diagnostic = LoweringDiagnostic::UnsupportedForLoopIterable {
+                        ty,
+                        is_range: false,
+                        origin,
+                    };
+                    self.diagnostics.push(diagnostic);
+                    return block.unit();
+                }
+
+                let mut items_place =
+                    unpack!(block = self.as_place_builder(block, items, Mutability::Immutable));
+
+                // ##AutoDeref: if the iterable is behind a reference, then we dereference it.
+                if self.ty_id_from_tir_term(items).borrow().is_ref() {
+                    items_place = items_place.deref()
+                }
+
+                let start = Operand::Const(Const::usize(0, &self.ctx));
+                (Some(items_place), COMMON_REPR_TYS.usize, start, None, false)
+            }
+            ForLoopIterable::Range { start, end, inclusive } => {
+                let ty = self.ty_id_from_tir_term(start);
+                if !ty.borrow().is_integral() {
+                    let diagnostic = LoweringDiagnostic::UnsupportedForLoopIterable {
+                        ty,
+                        is_range: true,
+                        origin,
+                    };
+                    self.diagnostics.push(diagnostic);
+                    return block.unit();
+                }
+
+                let start = unpack!(block = self.as_operand(block, start, Mutability::Immutable));
+
+                // The end of the range is evaluated before the loop, so that
+                // changing it within the body doesn't affect the loop.
+                let end_operand =
+                    unpack!(block = self.as_operand(block, end, Mutability::Immutable));
+                let end = self.temp_place(ty);
+                self.control_flow_graph.push_assign(block, end, RValue::Use(end_operand), span);
+
+                (None, ty, start, Some(end), inclusive)
+            }
+        };
+
+        let index = self.locals.push(LocalDecl::new_auxiliary(index_ty, Mutability::Mutable));
+        let index_place = Place::from_local(index);
+        self.control_flow_graph.push_assign(block, index_place, RValue::Use(start), span);
+
+        // The loop header checks whether the index is still within the
+        // bounds of the iterable.
+        let header = self.control_flow_graph.start_new_block();
+        self.control_flow_graph.goto(block, header, span);
+
+        let end = match (end, &items) {
+            (Some(end), _) => end,
+            (None, Some(items)) => {
+                let subject = items.clone().into_place(&mut self.projections);
+                let len = self.temp_place(COMMON_REPR_TYS.usize);
+                self.control_flow_graph.push_assign(header, len, RValue::Len(subject), span);
+                len
+            }
+            (None, None) => unreachable!(),
+        };
+
+        let in_bounds = self.temp_place(COMMON_REPR_TYS.bool);
+        let op = if inclusive { BinOp::LtEq } else { BinOp::Lt };
+        let operands = Box::new((index_place.into(), end.into()));
+        self.control_flow_graph.push_assign(
+            header,
+            in_bounds,
+            RValue::BinaryOp(op, operands),
+            span,
+        );
+
+        let loop_body = self.control_flow_graph.start_new_block();
+        let latch = self.control_flow_graph.start_new_block();
+        let next_block = self.control_flow_graph.start_new_block();
+
+        let terminator = TerminatorKind::make_if(in_bounds.into(), loop_body, next_block);
+        self.control_flow_graph.terminate(header, span, terminator);
+
+        // A `continue` within the body jumps to the latch so that the index
+        // is incremented before the next iteration.
+        let next_block = unpack!(self.enter_breakable_block(latch, next_block, move |this| {
+            Context::enter_resolved_scope_mut(this, ScopeKind::Stack(case.stack_id), |this| {
+                this.declare_bindings(pat);
+
+                let element = match items {
+                    Some(items) => items.index(index),
+                    None => PlaceBuilder::from(index),
+                };
+                let body_block = unpack!(this.place_into_pat(loop_body, pat, element));
+
+                let tmp_place = this.make_tmp_unit();
+                let body_block_end =
+                    unpack!(this.term_into_dest(tmp_place, body_block, case.value));
+
+                if !this.control_flow_graph.is_terminated(body_block_end) {
+                    this.control_flow_graph.goto(body_block_end, latch, span);
+                }
+            });
+
+            this.reached_terminator = false;
+            next_block.unit()
+        }));
+
+        let mut latch = latch;
+
+        if inclusive {
+            let is_last = self.temp_place(COMMON_REPR_TYS.bool);
+            let operands = Box::new((index_place.into(), end.into()));
+            self.control_flow_graph.push_assign(
+                latch,
+                is_last,
+                RValue::BinaryOp(BinOp::Eq, operands),
+                span,
+            );
+
+            let increment = self.control_flow_graph.start_new_block();
+            let terminator = TerminatorKind::make_if(is_last.into(), next_block, increment);
+            self.control_flow_graph.terminate(latch, span, terminator);
+            latch = increment;
+        }
+
+        let one = Operand::Const(Const::from_scalar_like(1, index_ty, &self.ctx));
+        let operands = Box::new((index_place.into(), one));
+        self.control_flow_graph.push_assign(
+            latch,
+            index_place,
+            RValue::BinaryOp(BinOp::Add, operands),
+            span,
+        );
+        self.control_flow_graph.goto(latch, header, span);
+
+        next_block.unit()
+    }
+
     /// Function that handles the lowering of loop expressions. This function
     /// takes in an inner closure which runs the operation of lowering the
     /// inner body loop,
@@ -119,7 +399,8 @@ impl<'tcx> BodyBuilder<'tcx> {
     where
         F: FnOnce(&mut BodyBuilder<'tcx>) -> BlockAnd<()>,
     {
-        let block_info = LoopBlockInfo { loop_body, next_block };
+        let drop_scope_depth = self.control_flow_graph.drop_scope_depth();
+        let block_info = LoopBlockInfo { loop_body, next_block, drop_scope_depth };
         let old_block_info = mem::replace(&mut self.loop_block_info, Some(block_info));
 
         let normal_exit_block = f(self);
@@ -128,3 +409,35 @@ impl<'tcx> BodyBuilder<'tcx> {
         normal_exit_block
     }
 }
+
+/// A `for` loop over an array, a slice or a range that can be lowered into
+/// an index-based loop.
+struct IndexedForLoop {
+    /// The iterable of the loop.
+    iterable: ForLoopIterable,
+
+    /// The `Some(<pat>)` case of the de-sugared loop, this contains the
+    /// body of the loop and the scope of the bindings of the pattern.
+    case: MatchCase,
+
+    /// The pattern that each element of the iterable is bound to.
+    pat: PatId,
+
+    /// The origin of the iterable, which is used for diagnostics.
+    origin: AstNodeId,
+}
+
+/// The iterable of an [IndexedForLoop].
+enum ForLoopIterable {
+    /// An array or a slice, the elements are read from it.
+    Items(TermId),
+
+    /// A range of integers from `start` to `end`.
+    Range {
+        start: TermId,
+        end: TermId,
+
+        /// Whether `end` is included in the range.
+        inclusive: bool,
+    },
+}
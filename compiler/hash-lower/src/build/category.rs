@@ -70,7 +70,8 @@ impl Category {
             | Ty::TupleTy(_)
             | Ty::RefTy(_)
             | Ty::Universe(_)
-            | Term::Hole(_) => Category::RValue(RValueKind::As),
+            | Term::Hole(_)
+            | Term::TypedHole(_) => Category::RValue(RValueKind::As),
         }
     }
 }
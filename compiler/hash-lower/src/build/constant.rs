@@ -2,10 +2,14 @@
 //! This module also includes logic that can perform constant folding on
 //! various constants.
 
+use hash_const_eval::utils::ConstUtils;
 use hash_ir::ir;
 use hash_reporting::macros::panic_on_span;
 use hash_storage::store::statics::StoreId;
-use hash_tir::tir::{self, HasAstNodeId};
+use hash_tir::{
+    intrinsics::utils::try_use_term_as_machine_integer,
+    tir::{self, HasAstNodeId, NodesId},
+};
 
 use super::BodyBuilder;
 
@@ -42,4 +46,127 @@ impl<'tcx> BodyBuilder<'tcx> {
             ),
         }
     }
+
+    /// Attempt to fold a term into a constant, if the term is a literal, or
+    /// a field access or index on a term that can be folded into a constant.
+    /// For example, `(1, 2).0` can be folded into `1` without needing to
+    /// create a temporary for the tuple. If the term cannot be folded, then
+    /// this returns `None`.
+    pub(crate) fn fold_const_projection(&mut self, term: tir::TermId) -> Option<ir::Const> {
+        // Projections on constant tuples, constructors and arrays are folded
+        // from the term that initialises the projected element.
+        if let Some(element) = self.project_const_aggregate(term) {
+            return self.fold_const_projection(element);
+        }
+
+        match *term.value() {
+            tir::Term::Lit(lit) => Some(self.lit_as_const(lit)),
+            tir::Term::Access(tir::AccessTerm { subject, field }) => {
+                let constant = self.fold_const_projection(subject)?;
+
+                // We only deal with direct accesses on ADTs, if the constant is
+                // behind a reference then we can't read it here.
+                if !constant.ty().borrow().is_adt() {
+                    return None;
+                }
+
+                let index = self.lookup_field_index(constant.ty(), field);
+                ConstUtils::new(self.ctx.layout_computer(), &constant).project_field(index)
+            }
+            tir::Term::Index(tir::IndexTerm { subject, index }) => {
+                let constant = self.fold_const_projection(subject)?;
+                let index = self.fold_const_projection(index)?;
+
+                if !constant.ty().borrow().is_array() {
+                    return None;
+                }
+
+                let lc = self.ctx.layout_computer();
+                let index = ConstUtils::new(lc, &index).eval_bits().try_into().ok()?;
+                ConstUtils::new(lc, &constant).project_field(index)
+            }
+            _ => None,
+        }
+    }
+
+    /// If the term is a field access or an index on a constant tuple,
+    /// constructor or array (or on a projection of one), get the term that
+    /// initialises the projected element.
+    fn project_const_aggregate(&mut self, term: tir::TermId) -> Option<tir::TermId> {
+        let (subject, element) = match *term.value() {
+            tir::Term::Access(tir::AccessTerm { subject, field }) => (subject, Ok(field)),
+            tir::Term::Index(tir::IndexTerm { subject, index }) => (subject, Err(index)),
+            _ => return None,
+        };
+
+        let subject = self.project_const_aggregate(subject).unwrap_or(subject);
+
+        // All of the elements of the aggregate must be constant, otherwise
+        // the other elements would still have to be evaluated.
+        if !Self::is_const_aggregate(subject) {
+            return None;
+        }
+
+        let ty = self.ty_id_from_tir_term(subject);
+
+        match (*subject.value(), element) {
+            (
+                tir::Term::Tuple(tir::TupleTerm { data: args })
+                | tir::Term::Ctor(tir::CtorTerm { ctor_args: args, .. }),
+                Ok(field),
+            ) => {
+                let is_struct =
+                    ty.borrow().as_adt().map(|adt| adt.flags.is_struct() || adt.flags.is_tuple());
+                if !is_struct {
+                    return None;
+                }
+
+                let index = self.lookup_field_index(ty, field);
+                args.elements()
+                    .value()
+                    .into_iter()
+                    .find(|arg| self.lookup_field_index(ty, arg.target) == index)
+                    .map(|arg| arg.value)
+            }
+            (tir::Term::Array(array), Err(index)) => {
+                if !ty.borrow().is_array() {
+                    return None;
+                }
+
+                let index = self.fold_const_projection(index)?;
+                let index: usize = ConstUtils::new(self.ctx.layout_computer(), &index)
+                    .eval_bits()
+                    .try_into()
+                    .ok()?;
+
+                // An index that is out of bounds is left to the bounds check.
+                if let tir::ArrayTerm::Repeated(_, length) = array
+                    && try_use_term_as_machine_integer(self, length)? <= index
+                {
+                    return None;
+                }
+
+                array.element_at(index)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check whether the term is a tuple, constructor or array that only
+    /// contains literals, or other such aggregates.
+    fn is_const_aggregate(term: tir::TermId) -> bool {
+        let elements = match *term.value() {
+            tir::Term::Tuple(tir::TupleTerm { data: args })
+            | tir::Term::Ctor(tir::CtorTerm { ctor_args: args, .. }) => {
+                args.elements().value().into_iter().map(|arg| arg.value).collect()
+            }
+            tir::Term::Array(tir::ArrayTerm::Normal(elements)) => elements.elements().value(),
+            tir::Term::Array(tir::ArrayTerm::Repeated(element, _)) => vec![element],
+            _ => return false,
+        };
+
+        elements.into_iter().all(|element| {
+            matches!(*element.value(), tir::Term::Lit(_)) || Self::is_const_aggregate(element)
+        })
+    }
 }
@@ -0,0 +1,1040 @@
+//! Logic for synthesising the bodies of items that are requested by
+//! `#derive(...)` attributes on type definitions. The synthesised items
+//! operate on references to the type, and visit the fields of the type in
+//! the order that they appear within the layout of the type:
+//!
+//! - `eq` has the signature `(&T, &T) -> bool`. Each field of both values is
+//!   compared, and the comparison short-circuits on the first field that
+//!   differs. For enums, the discriminants are compared first, and then the
+//!   fields of the active variant are compared.
+//!
+//! - `hash` has the signature `(&T) -> u64`. The hash of each field is combined
+//!   into the result using the FNV-1a scheme. For enums, the discriminant is
+//!   combined first, and then the fields of the active variant.
+//!
+//! - `debug` has the signature `(&T) -> ()`. The value is printed to the
+//!   standard output in the same form as the constructor of the type, i.e.
+//!   `Point(x = 1, y = 2)` or `Shape::Circle(radius = 3)`. Unlike the other
+//!   items, fields are visited in the order that they are declared, and unnamed
+//!   fields are printed without their name.
+//!
+//! - `serialize` synthesises an encoder with the signature `(&T, &raw u8,
+//!   usize) -> usize`, and a decoder with the signature `(&raw u8, usize, &mut
+//!   T) -> usize`. Both take an offset into the buffer, and return the offset
+//!   after the encoded value. The format is derived from the structure of the
+//!   type rather than its layout: fields are encoded in declaration order,
+//!   integers are encoded in little-endian order using their width (pointer
+//!   sized integers always use 8 bytes), floats are encoded as 8 byte IEEE-754
+//!   values, and enums are prefixed by a 4 byte index of the active variant.
+//!   The capacity of the buffer isn't checked, so calls to either of them are
+//!   unsafe.
+//!
+//! Fields are only supported if they are primitive, a type which also derives
+//! the same item, or a `str` when deriving `eq` or `debug`. 128-bit integers
+//! are only supported when deriving `eq` or `hash`.
+
+use hash_ast::ast::AstNodeId;
+use hash_attrs::attr::DeriveAttr;
+use hash_ir::{
+    cast::CastKind,
+    intrinsics::Intrinsic,
+    ir::{
+        BasicBlock, BinOp, Const, Local, LocalDecl, Operand, Place, PlaceProjection, RValue,
+        Statement, StatementKind, SwitchTargets, TerminatorKind, UnsafeOpKind, START_BLOCK,
+    },
+    lang_items::LangItem,
+    ty::{Mutability, RefKind, ReprTy, ReprTyId, VariantIdx, COMMON_REPR_TYS},
+};
+use hash_repr::TyInfo;
+use hash_source::{constant::AllocId, identifier::Identifier};
+use hash_storage::store::statics::{SingleStoreValue, StoreId};
+use hash_target::{
+    primitives::{FloatTy, SIntTy, UIntTy},
+    size::Size,
+};
+
+use super::{place::PlaceBuilder, unpack, BlockAnd, BlockAndExtend, BodyBuilder};
+use crate::diagnostics::LoweringDiagnostic;
+
+/// The initial value of the FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+/// The prime that the FNV-1a hash is multiplied with after each
+/// value is combined.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// The number of bytes that are used to encode the variant of an enum when
+/// deriving `serialize`.
+const VARIANT_TAG_SIZE: u64 = 4;
+
+/// An item that is synthesised from a `#derive(...)` attribute.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DerivedItem {
+    /// The type that the item is derived for.
+    pub subject: ReprTyId,
+
+    /// The kind of item that is derived.
+    pub item: DeriveAttr,
+
+    /// The function type of the synthesised item.
+    pub ty: ReprTyId,
+
+    /// The type definition that the item is derived from.
+    pub origin: AstNodeId,
+}
+
+impl DerivedItem {
+    /// The names of the arguments that the synthesised item accepts.
+    fn arg_names(&self) -> &'static [&'static str] {
+        match self.item {
+            DeriveAttr::Eq => &["lhs", "rhs"],
+            DeriveAttr::Hash | DeriveAttr::Debug => &["value"],
+            DeriveAttr::Serialize => &["value", "buf", "offset"],
+            DeriveAttr::Deserialize => &["buf", "offset", "out"],
+        }
+    }
+
+    /// The number of arguments that the synthesised item accepts.
+    pub(crate) fn arg_count(&self) -> usize {
+        self.arg_names().len()
+    }
+
+    /// The number of leading value arguments of an `Intrinsics::derived_*`
+    /// call which are of the subject type, these are passed to the item by
+    /// reference.
+    pub(crate) fn subject_args(item: DeriveAttr) -> usize {
+        match item {
+            DeriveAttr::Eq => 2,
+            DeriveAttr::Hash | DeriveAttr::Debug | DeriveAttr::Serialize => 1,
+            DeriveAttr::Deserialize => 0,
+        }
+    }
+
+    /// The kind of unsafe operation that a call to the synthesised item
+    /// performs, if any. The encoder and decoder of `serialize` access the
+    /// buffer without checking its capacity, so they can only be called
+    /// within an `unsafe` block.
+    pub(crate) fn unsafe_op(item: DeriveAttr) -> Option<UnsafeOpKind> {
+        match item {
+            DeriveAttr::Serialize => Some(UnsafeOpKind::PtrWrite),
+            DeriveAttr::Deserialize => Some(UnsafeOpKind::PtrRead),
+            DeriveAttr::Eq | DeriveAttr::Hash | DeriveAttr::Debug => None,
+        }
+    }
+}
+
+impl<'tcx> BodyBuilder<'tcx> {
+    /// Build the body of a [DerivedItem].
+    pub(super) fn build_derived(&mut self) {
+        let derived = self.item.as_derived();
+        let start = self.control_flow_graph.start_new_block();
+        debug_assert!(start == START_BLOCK);
+
+        let params = derived.ty.borrow().as_instance().borrow().params;
+
+        for (name, ty) in derived.arg_names().iter().zip(params.borrow().iter()) {
+            self.locals.push(LocalDecl::new_immutable(Identifier::from(*name), *ty));
+        }
+
+        let block = match derived.item {
+            DeriveAttr::Eq => self.build_derived_eq(start, derived),
+            DeriveAttr::Hash => self.build_derived_hash(start, derived),
+            DeriveAttr::Debug => self.build_derived_debug(start, derived),
+            DeriveAttr::Serialize => self.build_derived_serialize(start, derived),
+            DeriveAttr::Deserialize => self.build_derived_deserialize(start, derived),
+        };
+
+        self.control_flow_graph.terminate(block, derived.origin, TerminatorKind::Return);
+    }
+
+    /// Build the body of a derived `eq` item.
+    fn build_derived_eq(&mut self, mut block: BasicBlock, derived: DerivedItem) -> BasicBlock {
+        let origin = derived.origin;
+        let lhs = PlaceBuilder::from(Local::new(1)).deref();
+        let rhs = PlaceBuilder::from(Local::new(2)).deref();
+
+        // If any of the comparisons fail, then we jump to the `fail` block
+        // which immediately returns `false`.
+        let fail = self.control_flow_graph.start_new_block();
+        self.control_flow_graph.push_assign(
+            fail,
+            Place::return_place(),
+            Const::bool(false).into(),
+            origin,
+        );
+        self.control_flow_graph.terminate(fail, origin, TerminatorKind::Return);
+
+        block = if derived.subject.borrow().as_adt().borrow().flags.is_enum() {
+            let lhs_discriminant = self.derived_discriminant(block, derived, lhs.clone());
+            let rhs_discriminant = self.derived_discriminant(block, derived, rhs.clone());
+
+            // Compare the discriminants first, since the fields can only be
+            // compared if both values are the same variant.
+            let same = self.temp_place(COMMON_REPR_TYS.bool);
+            let value = RValue::BinaryOp(
+                BinOp::Eq,
+                Box::new((lhs_discriminant.into(), rhs_discriminant.into())),
+            );
+            self.control_flow_graph.push_assign(block, same, value, origin);
+
+            let switch_block = self.control_flow_graph.start_new_block();
+            self.control_flow_graph.terminate(
+                block,
+                origin,
+                TerminatorKind::make_if(same.into(), switch_block, fail),
+            );
+
+            self.switch_on_variants(
+                switch_block,
+                lhs_discriminant,
+                derived,
+                |this, block, variant| {
+                    let lhs = lhs.clone().downcast(variant);
+                    let rhs = rhs.clone().downcast(variant);
+                    this.compare_fields(block, derived, variant, lhs, rhs, fail)
+                },
+            )
+        } else {
+            self.compare_fields(block, derived, VariantIdx::new(0), lhs, rhs, fail)
+        };
+
+        self.control_flow_graph.push_assign(
+            block,
+            Place::return_place(),
+            Const::bool(true).into(),
+            origin,
+        );
+        block
+    }
+
+    /// Compare all of the fields of a variant of `lhs` and `rhs`. If any
+    /// of the fields differ, then control flow jumps to the `fail` block.
+    fn compare_fields(
+        &mut self,
+        mut block: BasicBlock,
+        derived: DerivedItem,
+        variant: VariantIdx,
+        lhs: PlaceBuilder,
+        rhs: PlaceBuilder,
+        fail: BasicBlock,
+    ) -> BasicBlock {
+        for (index, ty) in self.fields_in_layout_order(derived, variant) {
+            let lhs =
+                lhs.clone_project(PlaceProjection::Field(index)).into_place(&mut self.projections);
+            let rhs =
+                rhs.clone_project(PlaceProjection::Field(index)).into_place(&mut self.projections);
+
+            let Some(result) = unpack!(block = self.compare_field(block, derived, ty, lhs, rhs))
+            else {
+                continue;
+            };
+
+            let next = self.control_flow_graph.start_new_block();
+            self.control_flow_graph.terminate(
+                block,
+                derived.origin,
+                TerminatorKind::make_if(result.into(), next, fail),
+            );
+            block = next;
+        }
+
+        block
+    }
+
+    /// Compare a single field of type `ty`, returning the [Place] which
+    /// stores whether the fields are equal. If the field cannot be compared,
+    /// then a diagnostic is emitted, and [None] is returned.
+    fn compare_field(
+        &mut self,
+        block: BasicBlock,
+        derived: DerivedItem,
+        ty: ReprTyId,
+        lhs: Place,
+        rhs: Place,
+    ) -> BlockAnd<Option<Place>> {
+        let origin = derived.origin;
+        let result = self.temp_place(COMMON_REPR_TYS.bool);
+
+        if ty.borrow().is_switchable() || ty.borrow().is_float() {
+            let value = RValue::BinaryOp(BinOp::Eq, Box::new((lhs.into(), rhs.into())));
+            self.control_flow_graph.push_assign(block, result, value, origin);
+
+            return block.and(Some(result));
+        }
+
+        // Strings are compared using the `str_eq` lang item, and any types that
+        // derive `eq` themselves use the derived item.
+        let derived_eq = self.ctx.lcx.derived_items().get_ty(ty, DeriveAttr::Eq);
+
+        let (item, args) = if ty == COMMON_REPR_TYS.str {
+            (self.get_lang_item(LangItem::StrEq), vec![lhs.into(), rhs.into()])
+        } else if let Some(item) = derived_eq {
+            let lhs = self.derived_ref(block, lhs, ty, origin);
+            let rhs = self.derived_ref(block, rhs, ty, origin);
+            (item, vec![lhs, rhs])
+        } else {
+            self.diagnostics.push(LoweringDiagnostic::UnsupportedDerivedField {
+                item: derived.item,
+                subject: derived.subject,
+                ty,
+                origin,
+            });
+
+            return block.and(None);
+        };
+
+        let block =
+            unpack!(self.build_fn_call(result, block, Const::zst(item).into(), args, origin));
+        block.and(Some(result))
+    }
+
+    /// Build the body of a derived `hash` item.
+    fn build_derived_hash(&mut self, block: BasicBlock, derived: DerivedItem) -> BasicBlock {
+        let origin = derived.origin;
+        let value = PlaceBuilder::from(Local::new(1)).deref();
+
+        let basis = self.derived_u64(FNV_OFFSET_BASIS);
+        self.control_flow_graph.push_assign(block, Place::return_place(), basis.into(), origin);
+
+        if derived.subject.borrow().as_adt().borrow().flags.is_enum() {
+            let discriminant = self.derived_discriminant(block, derived, value.clone());
+            let discriminant_ty = derived.subject.borrow().discriminant_ty();
+
+            let hash = self.temp_place(COMMON_REPR_TYS.u64);
+            let cast_kind = CastKind::classify(discriminant_ty, COMMON_REPR_TYS.u64);
+            let cast = RValue::Cast(cast_kind, discriminant.into(), COMMON_REPR_TYS.u64);
+            self.control_flow_graph.push_assign(block, hash, cast, origin);
+            self.combine_hash(block, hash.into(), origin);
+
+            self.switch_on_variants(block, discriminant, derived, |this, block, variant| {
+                this.hash_fields(block, derived, variant, value.clone().downcast(variant))
+            })
+        } else {
+            self.hash_fields(block, derived, VariantIdx::new(0), value)
+        }
+    }
+
+    /// Combine the hashes of all of the fields of a variant of `value`
+    /// into the result.
+    fn hash_fields(
+        &mut self,
+        mut block: BasicBlock,
+        derived: DerivedItem,
+        variant: VariantIdx,
+        value: PlaceBuilder,
+    ) -> BasicBlock {
+        for (index, ty) in self.fields_in_layout_order(derived, variant) {
+            let field = value
+                .clone_project(PlaceProjection::Field(index))
+                .into_place(&mut self.projections);
+
+            if let Some(hash) = unpack!(block = self.hash_field(block, derived, ty, field)) {
+                self.combine_hash(block, hash, derived.origin);
+            }
+        }
+
+        block
+    }
+
+    /// Compute the hash of a single field of type `ty`. If the field cannot
+    /// be hashed, then a diagnostic is emitted, and [None] is returned.
+    fn hash_field(
+        &mut self,
+        block: BasicBlock,
+        derived: DerivedItem,
+        ty: ReprTyId,
+        field: Place,
+    ) -> BlockAnd<Option<Operand>> {
+        let origin = derived.origin;
+
+        // Floats are hashed by their bits, so they are first transmuted into
+        // an integer of the same width.
+        if let ReprTy::Float(float_ty) = ty.value() {
+            let bits_ty = match float_ty {
+                FloatTy::F32 => COMMON_REPR_TYS.u32,
+                FloatTy::F64 => COMMON_REPR_TYS.u64,
+            };
+
+            let bits = self.temp_place(bits_ty);
+            let transmute = self.ctx().intrinsics().get_ty(Intrinsic::Transmute).unwrap();
+            let args = vec![Const::zero().into(), Const::zero().into(), field.into()];
+            let block = unpack!(self.build_fn_call(
+                bits,
+                block,
+                Const::zst(transmute).into(),
+                args,
+                origin
+            ));
+
+            if bits_ty == COMMON_REPR_TYS.u64 {
+                return block.and(Some(bits.into()));
+            }
+
+            let hash = self.temp_place(COMMON_REPR_TYS.u64);
+            let cast = RValue::Cast(CastKind::IntToInt, bits.into(), COMMON_REPR_TYS.u64);
+            self.control_flow_graph.push_assign(block, hash, cast, origin);
+            return block.and(Some(hash.into()));
+        }
+
+        let hash = self.temp_place(COMMON_REPR_TYS.u64);
+
+        // Primitive values are hashed by their value.
+        if ty.borrow().is_switchable() {
+            let cast_kind = CastKind::classify(ty, COMMON_REPR_TYS.u64);
+            let cast = RValue::Cast(cast_kind, field.into(), COMMON_REPR_TYS.u64);
+            self.control_flow_graph.push_assign(block, hash, cast, origin);
+
+            return block.and(Some(hash.into()));
+        }
+
+        let Some(item) = self.ctx.lcx.derived_items().get_ty(ty, DeriveAttr::Hash) else {
+            self.diagnostics.push(LoweringDiagnostic::UnsupportedDerivedField {
+                item: derived.item,
+                subject: derived.subject,
+                ty,
+                origin,
+            });
+
+            return block.and(None);
+        };
+
+        let arg = self.derived_ref(block, field, ty, origin);
+        let block =
+            unpack!(self.build_fn_call(hash, block, Const::zst(item).into(), vec![arg], origin));
+        block.and(Some(hash.into()))
+    }
+
+    /// Combine the given `hash` into the running hash that is stored in the
+    /// return place, i.e. `_0 = (_0 ^ hash) * FNV_PRIME`.
+    fn combine_hash(&mut self, block: BasicBlock, hash: Operand, origin: AstNodeId) {
+        let mixed = self.temp_place(COMMON_REPR_TYS.u64);
+        let value = RValue::BinaryOp(BinOp::BitXor, Box::new((Place::return_place().into(), hash)));
+        self.control_flow_graph.push_assign(block, mixed, value, origin);
+
+        let prime = self.derived_u64(FNV_PRIME);
+        let value = RValue::BinaryOp(BinOp::Mul, Box::new((mixed.into(), prime.into())));
+        self.control_flow_graph.push_assign(block, Place::return_place(), value, origin);
+    }
+
+    /// Build the body of a derived `debug` item.
+    fn build_derived_debug(&mut self, block: BasicBlock, derived: DerivedItem) -> BasicBlock {
+        let origin = derived.origin;
+        let value = PlaceBuilder::from(Local::new(1)).deref();
+        let adt = derived.subject.borrow().as_adt();
+        let name = adt.borrow().name;
+
+        let block = if adt.borrow().flags.is_enum() {
+            let discriminant = self.derived_discriminant(block, derived, value.clone());
+
+            self.switch_on_variants(block, discriminant, derived, |this, block, variant| {
+                let variant_name = adt.borrow().variant(variant).name;
+                let block = this.debug_str(block, format!("{name}::{variant_name}"), origin);
+                this.debug_fields(block, derived, variant, value.clone().downcast(variant))
+            })
+        } else {
+            let block = self.debug_str(block, name.to_string(), origin);
+            self.debug_fields(block, derived, VariantIdx::new(0), value)
+        };
+
+        self.control_flow_graph.push_assign(
+            block,
+            Place::return_place(),
+            Const::zero().into(),
+            origin,
+        );
+        block
+    }
+
+    /// Print all of the fields of a variant of `value` within parentheses.
+    /// Variants of enums which have no fields are printed without the
+    /// parentheses, i.e. `Shape::Empty`.
+    fn debug_fields(
+        &mut self,
+        mut block: BasicBlock,
+        derived: DerivedItem,
+        variant: VariantIdx,
+        value: PlaceBuilder,
+    ) -> BasicBlock {
+        let origin = derived.origin;
+        let (is_enum, fields) = derived.subject.borrow().as_adt().map(|adt| {
+            let fields = &adt.variant(variant).fields;
+            (
+                adt.flags.is_enum(),
+                fields.iter().map(|field| (field.name, field.ty)).collect::<Vec<_>>(),
+            )
+        });
+
+        if is_enum && fields.is_empty() {
+            return block;
+        }
+
+        block = self.debug_str(block, "(".to_string(), origin);
+
+        for (index, (name, ty)) in fields.into_iter().enumerate() {
+            let mut prefix = if index > 0 { ", ".to_string() } else { String::new() };
+
+            // Positional fields are named after their index, so we skip
+            // printing the name for them.
+            if name.as_str().parse::<usize>().is_err() {
+                prefix.push_str(&format!("{name} = "));
+            }
+
+            if !prefix.is_empty() {
+                block = self.debug_str(block, prefix, origin);
+            }
+
+            let field = value
+                .clone_project(PlaceProjection::Field(index))
+                .into_place(&mut self.projections);
+            block = self.debug_field(block, derived, ty, field);
+        }
+
+        self.debug_str(block, ")".to_string(), origin)
+    }
+
+    /// Print a single field of type `ty`. If the field cannot be printed,
+    /// then a diagnostic is emitted, and nothing is printed for the field.
+    fn debug_field(
+        &mut self,
+        block: BasicBlock,
+        derived: DerivedItem,
+        ty: ReprTyId,
+        field: Place,
+    ) -> BasicBlock {
+        let origin = derived.origin;
+
+        // Integers are widened to 64 bits before they are printed, since the
+        // printing functions in the prelude only accept 64 bit values.
+        let (item, target) = match ty.value() {
+            ReprTy::Bool => {
+                let on_true = self.control_flow_graph.start_new_block();
+                let on_false = self.control_flow_graph.start_new_block();
+                let join = self.control_flow_graph.start_new_block();
+
+                self.control_flow_graph.terminate(
+                    block,
+                    origin,
+                    TerminatorKind::make_if(field.into(), on_true, on_false),
+                );
+
+                let on_true = self.debug_str(on_true, "true".to_string(), origin);
+                self.control_flow_graph.goto(on_true, join, origin);
+                let on_false = self.debug_str(on_false, "false".to_string(), origin);
+                self.control_flow_graph.goto(on_false, join, origin);
+
+                return join;
+            }
+            ReprTy::Char => {
+                let block = self.debug_str(block, "'".to_string(), origin);
+                let block = self.debug_call(block, LangItem::DebugChar, field.into(), origin);
+                return self.debug_str(block, "'".to_string(), origin);
+            }
+            // Strings are represented as a reference to the unsized `str`.
+            ReprTy::Ref(inner, ..) if inner.is_str() => {
+                let block = self.debug_str(block, "\"".to_string(), origin);
+                let block = self.debug_call(block, LangItem::Print, field.into(), origin);
+                return self.debug_str(block, "\"".to_string(), origin);
+            }
+            ReprTy::Int(int_ty) if int_ty != SIntTy::I128 => {
+                (LangItem::DebugInt, COMMON_REPR_TYS.i64)
+            }
+            ReprTy::UInt(int_ty) if int_ty != UIntTy::U128 => {
+                (LangItem::DebugUInt, COMMON_REPR_TYS.u64)
+            }
+            ReprTy::Float(_) => (LangItem::DebugFloat, COMMON_REPR_TYS.f64),
+            _ => {
+                let Some(item) = self.ctx.lcx.derived_items().get_ty(ty, DeriveAttr::Debug) else {
+                    self.diagnostics.push(LoweringDiagnostic::UnsupportedDerivedField {
+                        item: derived.item,
+                        subject: derived.subject,
+                        ty,
+                        origin,
+                    });
+
+                    return block;
+                };
+
+                let arg = self.derived_ref(block, field, ty, origin);
+                let result = self.temp_place(COMMON_REPR_TYS.unit);
+                return unpack!(self.build_fn_call(
+                    result,
+                    block,
+                    Const::zst(item).into(),
+                    vec![arg],
+                    origin
+                ));
+            }
+        };
+
+        let widened = self.temp_place(target);
+        let cast = RValue::Cast(CastKind::classify(ty, target), field.into(), target);
+        self.control_flow_graph.push_assign(block, widened, cast, origin);
+        self.debug_call(block, item, widened.into(), origin)
+    }
+
+    /// Print the given string literal to the standard output.
+    fn debug_str(&mut self, block: BasicBlock, value: String, origin: AstNodeId) -> BasicBlock {
+        let value = Const::str(AllocId::str(value), &self.ctx);
+        self.debug_call(block, LangItem::Print, value.into(), origin)
+    }
+
+    /// Call one of the printing lang items with the given argument.
+    fn debug_call(
+        &mut self,
+        block: BasicBlock,
+        item: LangItem,
+        arg: Operand,
+        origin: AstNodeId,
+    ) -> BasicBlock {
+        let item = self.get_lang_item(item);
+        let result = self.temp_place(COMMON_REPR_TYS.unit);
+        unpack!(self.build_fn_call(result, block, Const::zst(item).into(), vec![arg], origin))
+    }
+
+    /// Build the body of a derived `serialize` item, the running offset
+    /// into the buffer is stored in the return place.
+    fn build_derived_serialize(&mut self, block: BasicBlock, derived: DerivedItem) -> BasicBlock {
+        let origin = derived.origin;
+        let value = PlaceBuilder::from(Local::new(1)).deref();
+
+        let offset = Place::from_local(Local::new(3));
+        self.control_flow_graph.push_assign(block, Place::return_place(), offset.into(), origin);
+
+        if derived.subject.borrow().as_adt().borrow().flags.is_enum() {
+            let discriminant = self.derived_discriminant(block, derived, value.clone());
+
+            self.switch_on_variants(block, discriminant, derived, |this, block, variant| {
+                // The variant is encoded by its index rather than its
+                // discriminant, so that the format doesn't depend on the
+                // representation of the enum.
+                let tag = this.derived_u64(variant.index() as u64);
+                let block = this.serialize_bytes(block, tag.into(), VARIANT_TAG_SIZE, origin);
+                this.serialize_fields(block, derived, variant, value.clone().downcast(variant))
+            })
+        } else {
+            self.serialize_fields(block, derived, VariantIdx::new(0), value)
+        }
+    }
+
+    /// Encode all of the fields of a variant of `value` in the order that
+    /// they are declared.
+    fn serialize_fields(
+        &mut self,
+        mut block: BasicBlock,
+        derived: DerivedItem,
+        variant: VariantIdx,
+        value: PlaceBuilder,
+    ) -> BasicBlock {
+        for (index, ty) in self.fields_in_declaration_order(derived.subject, variant) {
+            let field = value
+                .clone_project(PlaceProjection::Field(index))
+                .into_place(&mut self.projections);
+            block = self.serialize_field(block, derived, ty, field);
+        }
+
+        block
+    }
+
+    /// Encode a single field of type `ty`. If the field cannot be encoded,
+    /// then a diagnostic is emitted, and nothing is encoded for the field.
+    fn serialize_field(
+        &mut self,
+        block: BasicBlock,
+        derived: DerivedItem,
+        ty: ReprTyId,
+        field: Place,
+    ) -> BasicBlock {
+        let origin = derived.origin;
+        let buf = Place::from_local(Local::new(2));
+
+        if let Some(size) = encoded_size(ty) {
+            let widened = self.temp_place(COMMON_REPR_TYS.u64);
+            let cast = RValue::Cast(
+                CastKind::classify(ty, COMMON_REPR_TYS.u64),
+                field.into(),
+                COMMON_REPR_TYS.u64,
+            );
+            self.control_flow_graph.push_assign(block, widened, cast, origin);
+
+            return self.serialize_bytes(block, widened.into(), size, origin);
+        }
+
+        let (item, args) = if ty.borrow().is_float() {
+            let widened = self.temp_place(COMMON_REPR_TYS.f64);
+            let cast = RValue::Cast(
+                CastKind::classify(ty, COMMON_REPR_TYS.f64),
+                field.into(),
+                COMMON_REPR_TYS.f64,
+            );
+            self.control_flow_graph.push_assign(block, widened, cast, origin);
+
+            let item = self.get_lang_item(LangItem::SerializeFloat);
+            (item, vec![buf.into(), Place::return_place().into(), widened.into()])
+        } else if let Some(item) = self.ctx.lcx.derived_items().get_ty(ty, DeriveAttr::Serialize) {
+            let arg = self.derived_ref(block, field, ty, origin);
+            (item, vec![arg, buf.into(), Place::return_place().into()])
+        } else {
+            self.diagnostics.push(LoweringDiagnostic::UnsupportedDerivedField {
+                item: derived.item,
+                subject: derived.subject,
+                ty,
+                origin,
+            });
+
+            return block;
+        };
+
+        unpack!(self.build_fn_call(
+            Place::return_place(),
+            block,
+            Const::zst(item).into(),
+            args,
+            origin
+        ))
+    }
+
+    /// Encode the lower `size` bytes of the given `u64` value at the running
+    /// offset.
+    fn serialize_bytes(
+        &mut self,
+        block: BasicBlock,
+        value: Operand,
+        size: u64,
+        origin: AstNodeId,
+    ) -> BasicBlock {
+        let item = self.get_lang_item(LangItem::SerializeBytes);
+        let args = vec![
+            Place::from_local(Local::new(2)).into(),
+            Place::return_place().into(),
+            value,
+            self.derived_usize(size).into(),
+        ];
+
+        unpack!(self.build_fn_call(
+            Place::return_place(),
+            block,
+            Const::zst(item).into(),
+            args,
+            origin
+        ))
+    }
+
+    /// Build the body of a derived `deserialize` item, the running offset
+    /// into the buffer is stored in the return place.
+    fn build_derived_deserialize(&mut self, block: BasicBlock, derived: DerivedItem) -> BasicBlock {
+        let origin = derived.origin;
+        let out = PlaceBuilder::from(Local::new(3)).deref();
+
+        let offset = Place::from_local(Local::new(2));
+        self.control_flow_graph.push_assign(block, Place::return_place(), offset.into(), origin);
+
+        if !derived.subject.borrow().as_adt().borrow().flags.is_enum() {
+            return self.deserialize_fields(block, derived, VariantIdx::new(0), out);
+        }
+
+        let tag = self.temp_place(COMMON_REPR_TYS.u64);
+        let block = self.deserialize_bytes(block, tag, VARIANT_TAG_SIZE, origin);
+        let join = self.control_flow_graph.start_new_block();
+
+        // If the tag doesn't correspond to any of the variants, then the buffer
+        // wasn't produced by the encoder of this type.
+        let invalid = self.control_flow_graph.start_new_block();
+        let message = format!("invalid variant when deserializing `{}`", derived.subject);
+        self.derived_panic(invalid, message, origin);
+
+        let variants =
+            derived.subject.borrow().as_adt().map(|adt| adt.variants.indices().collect::<Vec<_>>());
+        let mut targets = Vec::with_capacity(variants.len());
+
+        for variant in variants {
+            let variant_block = self.control_flow_graph.start_new_block();
+            let end = self.deserialize_fields(
+                variant_block,
+                derived,
+                variant,
+                out.clone().downcast(variant),
+            );
+
+            // Set the discriminant once all of the fields have been written.
+            let place = out.clone().into_place(&mut self.projections);
+            self.control_flow_graph
+                .push(end, Statement { kind: StatementKind::Discriminate(place, variant), origin });
+
+            self.control_flow_graph.goto(end, join, origin);
+            targets.push((variant.index() as u128, variant_block));
+        }
+
+        let targets = SwitchTargets::new(targets.into_iter(), Some(invalid));
+        self.control_flow_graph.terminate(
+            block,
+            origin,
+            TerminatorKind::Switch { value: tag.into(), targets },
+        );
+
+        join
+    }
+
+    /// Decode all of the fields of a variant into `out` in the order that
+    /// they are declared.
+    fn deserialize_fields(
+        &mut self,
+        mut block: BasicBlock,
+        derived: DerivedItem,
+        variant: VariantIdx,
+        out: PlaceBuilder,
+    ) -> BasicBlock {
+        for (index, ty) in self.fields_in_declaration_order(derived.subject, variant) {
+            let field =
+                out.clone_project(PlaceProjection::Field(index)).into_place(&mut self.projections);
+            block = self.deserialize_field(block, derived, ty, field);
+        }
+
+        block
+    }
+
+    /// Decode a single field of type `ty` into `field`. If the field cannot
+    /// be decoded, then the field is left as it is.
+    fn deserialize_field(
+        &mut self,
+        mut block: BasicBlock,
+        derived: DerivedItem,
+        ty: ReprTyId,
+        field: Place,
+    ) -> BasicBlock {
+        let origin = derived.origin;
+        let buf = Place::from_local(Local::new(1));
+
+        if let Some(size) = encoded_size(ty) {
+            let raw = self.temp_place(COMMON_REPR_TYS.u64);
+            block = self.deserialize_bytes(block, raw, size, origin);
+
+            // Any non-zero value is treated as `true` for booleans.
+            let value = if ty == COMMON_REPR_TYS.bool {
+                let zero = self.derived_u64(0);
+                RValue::BinaryOp(BinOp::Neq, Box::new((raw.into(), zero.into())))
+            } else {
+                RValue::Cast(CastKind::classify(COMMON_REPR_TYS.u64, ty), raw.into(), ty)
+            };
+
+            self.control_flow_graph.push_assign(block, field, value, origin);
+            return block;
+        }
+
+        if ty.borrow().is_float() {
+            let raw = self.temp_place(COMMON_REPR_TYS.f64);
+            let item = self.get_lang_item(LangItem::DeserializeFloat);
+            let args = vec![buf.into(), Place::return_place().into()];
+            block = unpack!(self.build_fn_call(raw, block, Const::zst(item).into(), args, origin));
+            self.advance_offset(block, 8, origin);
+
+            let cast = RValue::Cast(CastKind::classify(COMMON_REPR_TYS.f64, ty), raw.into(), ty);
+            self.control_flow_graph.push_assign(block, field, cast, origin);
+            return block;
+        }
+
+        // The encoder is synthesised for the same fields, and already reports
+        // any fields that are unsupported.
+        let Some(item) = self.ctx.lcx.derived_items().get_ty(ty, DeriveAttr::Deserialize) else {
+            return block;
+        };
+
+        let ref_ty = ReprTy::create(ReprTy::Ref(ty, Mutability::Mutable, RefKind::Normal));
+        let out = self.temp_place(ref_ty);
+        let value = RValue::Ref(Mutability::Mutable, field, RefKind::Normal);
+        self.control_flow_graph.push_assign(block, out, value, origin);
+
+        let args = vec![buf.into(), Place::return_place().into(), out.into()];
+        unpack!(self.build_fn_call(
+            Place::return_place(),
+            block,
+            Const::zst(item).into(),
+            args,
+            origin
+        ))
+    }
+
+    /// Decode a little-endian integer of `size` bytes at the running offset
+    /// into `destination`, and advance the offset past it.
+    fn deserialize_bytes(
+        &mut self,
+        block: BasicBlock,
+        destination: Place,
+        size: u64,
+        origin: AstNodeId,
+    ) -> BasicBlock {
+        let item = self.get_lang_item(LangItem::DeserializeBytes);
+        let args = vec![
+            Place::from_local(Local::new(1)).into(),
+            Place::return_place().into(),
+            self.derived_usize(size).into(),
+        ];
+
+        let block =
+            unpack!(self.build_fn_call(destination, block, Const::zst(item).into(), args, origin));
+        self.advance_offset(block, size, origin);
+        block
+    }
+
+    /// Advance the running offset that is stored in the return place by
+    /// `size` bytes.
+    fn advance_offset(&mut self, block: BasicBlock, size: u64, origin: AstNodeId) {
+        let size = self.derived_usize(size);
+        let value =
+            RValue::BinaryOp(BinOp::Add, Box::new((Place::return_place().into(), size.into())));
+        self.control_flow_graph.push_assign(block, Place::return_place(), value, origin);
+    }
+
+    /// Terminate the given block with a call to the `panic` lang item.
+    fn derived_panic(&mut self, block: BasicBlock, message: String, origin: AstNodeId) {
+        let item = self.get_lang_item(LangItem::Panic);
+        let message = Const::str(AllocId::str(message), &self.ctx);
+        let destination = self.temp_place(COMMON_REPR_TYS.never);
+
+        self.control_flow_graph.terminate(
+            block,
+            origin,
+            TerminatorKind::Call {
+                op: Const::zst(item).into(),
+                args: vec![message.into()],
+                destination,
+                target: None,
+                // Derived bodies don't declare any locals that need to be
+                // dropped, so there is nothing to clean up.
+                unwind: None,
+            },
+        );
+    }
+
+    /// Emit a switch on the `discriminant` of the subject, and build each of
+    /// the variant arms using `build_variant`. All of the arms jump to the
+    /// returned block once they complete.
+    fn switch_on_variants(
+        &mut self,
+        block: BasicBlock,
+        discriminant: Place,
+        derived: DerivedItem,
+        mut build_variant: impl FnMut(&mut Self, BasicBlock, VariantIdx) -> BasicBlock,
+    ) -> BasicBlock {
+        let origin = derived.origin;
+        let join = self.control_flow_graph.start_new_block();
+
+        // The discriminant is always one of the variants.
+        let otherwise = self.control_flow_graph.start_new_block();
+        self.control_flow_graph.terminate(otherwise, origin, TerminatorKind::Unreachable);
+
+        let adt = derived.subject.borrow().as_adt();
+        let discriminants = adt.map(|adt| adt.discriminants().collect::<Vec<_>>());
+        let mut targets = Vec::with_capacity(discriminants.len());
+
+        for (variant, value) in discriminants {
+            let variant_block = self.control_flow_graph.start_new_block();
+            let end = build_variant(self, variant_block, variant);
+
+            self.control_flow_graph.goto(end, join, origin);
+            targets.push((value, variant_block));
+        }
+
+        let targets = SwitchTargets::new(targets.into_iter(), Some(otherwise));
+        self.control_flow_graph.terminate(
+            block,
+            origin,
+            TerminatorKind::Switch { value: discriminant.into(), targets },
+        );
+
+        join
+    }
+
+    /// Compute the fields of a variant of the `subject` along with their
+    /// types, in the order that they appear within the layout. If the layout
+    /// of the subject cannot be computed, then the fields are returned in
+    /// declaration order (the error is reported on the derived item).
+    fn fields_in_layout_order(
+        &mut self,
+        derived: DerivedItem,
+        variant: VariantIdx,
+    ) -> Vec<(usize, ReprTyId)> {
+        let subject = derived.subject;
+        let Some(layout) = self.layout_of(subject, derived.origin) else {
+            return self.fields_in_declaration_order(subject, variant);
+        };
+
+        let info = TyInfo { ty: subject, layout }.for_variant(self.ctx.layout_computer(), variant);
+        let order = info.layout.borrow().shape.iter_increasing_offsets().collect::<Vec<_>>();
+
+        subject.borrow().as_adt().map(|adt| {
+            let fields = &adt.variant(variant).fields;
+            order.into_iter().map(|index| (index, fields[index].ty)).collect()
+        })
+    }
+
+    /// Compute the fields of a variant of the `subject` along with their
+    /// types, in the order that they are declared.
+    fn fields_in_declaration_order(
+        &self,
+        subject: ReprTyId,
+        variant: VariantIdx,
+    ) -> Vec<(usize, ReprTyId)> {
+        subject.borrow().as_adt().map(|adt| {
+            adt.variant(variant).fields.iter().map(|field| field.ty).enumerate().collect()
+        })
+    }
+
+    /// Read the discriminant of the given `value` into a temporary.
+    fn derived_discriminant(
+        &mut self,
+        block: BasicBlock,
+        derived: DerivedItem,
+        value: PlaceBuilder,
+    ) -> Place {
+        let discriminant_ty = derived.subject.borrow().discriminant_ty();
+        let discriminant = self.temp_place(discriminant_ty);
+        let place = value.into_place(&mut self.projections);
+
+        let value = self.discriminant_of(place, derived.subject);
+        self.control_flow_graph.push_assign(block, discriminant, value, derived.origin);
+
+        discriminant
+    }
+
+    /// Create a reference to the given `place` of type `ty` in a temporary.
+    fn derived_ref(
+        &mut self,
+        block: BasicBlock,
+        place: Place,
+        ty: ReprTyId,
+        origin: AstNodeId,
+    ) -> Operand {
+        let ref_ty = ReprTy::create(ReprTy::Ref(ty, Mutability::Immutable, RefKind::Normal));
+        let temp = self.temp_place(ref_ty);
+        let value = RValue::Ref(Mutability::Immutable, place, RefKind::Normal);
+
+        self.control_flow_graph.push_assign(block, temp, value, origin);
+        temp.into()
+    }
+
+    /// Create a `u64` constant.
+    fn derived_u64(&self, value: u64) -> Const {
+        Const::from_scalar_like(value as u128, COMMON_REPR_TYS.u64, &self.ctx)
+    }
+
+    /// Create a `usize` constant.
+    fn derived_usize(&self, value: u64) -> Const {
+        Const::from_scalar_like(value as u128, COMMON_REPR_TYS.usize, &self.ctx)
+    }
+}
+
+/// Compute the number of bytes that are used to encode a value of type `ty`
+/// when deriving `serialize`, if the value is encoded as an integer. Pointer
+/// sized integers always use 8 bytes so that the format doesn't depend on
+/// the target.
+fn encoded_size(ty: ReprTyId) -> Option<u64> {
+    let ptr_size = Size::from_bytes(8);
+
+    match ty.value() {
+        ReprTy::Bool => Some(1),
+        ReprTy::Char => Some(4),
+        ReprTy::Int(int_ty) if int_ty != SIntTy::I128 => Some(int_ty.size(ptr_size).bytes()),
+        ReprTy::UInt(int_ty) if int_ty != UIntTy::U128 => Some(int_ty.size(ptr_size).bytes()),
+        _ => None,
+    }
+}
@@ -4,31 +4,42 @@
 //! [crate::build::temp].
 
 use hash_ast::ast::AstNodeId;
+use hash_attrs::{attr::attr_store, builtin::attrs};
 use hash_ir::{
+    cast::CastKind,
     intrinsics::Intrinsic,
     ir::{
         AggregateKind, BasicBlock, Const, LogicalBinOp, Operand, Place, RValue, Statement,
-        StatementKind, TerminatorKind,
+        StatementKind, SwitchTargets, TerminatorKind, UnsafeOpKind,
     },
     ty::{AdtId, Mutability, RefKind, ReprTy, ReprTyId, VariantIdx, COMMON_REPR_TYS},
 };
 use hash_reporting::macros::panic_on_span;
-use hash_source::identifier::Identifier;
-use hash_storage::store::{statics::StoreId, SequenceStoreKey};
+use hash_source::{
+    constant::{FloatTy, SIntTy, UIntTy},
+    identifier::Identifier,
+};
+use hash_storage::store::{
+    statics::{SingleStoreValue, StoreId},
+    SequenceStoreKey,
+};
+use hash_target::HasTarget;
 use hash_tir::{
     atom_info::ItemInAtomInfo,
     context::Context,
-    intrinsics::utils::try_use_term_as_machine_integer,
+    intrinsics::{definitions::Intrinsic as TirIntrinsic, utils::try_use_term_as_machine_integer},
     term_as_variant,
     tir::{
-        self, commands::AssignTerm, ArgsId, ArrayTerm, CallTerm, CtorTerm, HasAstNodeId,
+        self, commands::AssignTerm, ArgsId, ArrayTerm, CallTerm, CtorTerm, FnTy, HasAstNodeId,
         LoopControlTerm, NodesId, ParamIndex, RefTerm, ReturnTerm, Term, TermId, TupleTerm, Ty,
         UnsafeTerm,
     },
 };
 use hash_utils::itertools::Itertools;
 
-use super::{ty::FnCallTermKind, unpack, BlockAnd, BlockAndExtend, BodyBuilder, LoopBlockInfo};
+use super::{
+    ty::FnCallTermKind, unpack, BlockAnd, BlockAndExtend, BodyBuilder, DerivedItem, LoopBlockInfo,
+};
 
 impl<'tcx> BodyBuilder<'tcx> {
     /// Compile the given [Term] and place the value of the [Term]
@@ -146,11 +157,30 @@ impl<'tcx> BodyBuilder<'tcx> {
             Term::Call(ref fn_term @ CallTerm { subject, args, .. }) => {
                 match self.classify_fn_call_term(fn_term) {
                     FnCallTermKind::Call => {
+                        // Check whether the call is to an intrinsic or function that
+                        // is considered to be unsafe.
+                        match *subject.value() {
+                            Term::Intrinsic(TirIntrinsic::Transmute) => {
+                                self.record_unsafe_op(UnsafeOpKind::Transmute, span);
+                            }
+                            Term::Fn(def)
+                                if attr_store()
+                                    .node_has_attr(def.node_id_ensured(), attrs::FOREIGN) =>
+                            {
+                                self.record_unsafe_op(UnsafeOpKind::ExternCall, span);
+                            }
+                            _ => {}
+                        }
+
                         // Get the type of the function into or to to get the
                         // fn-type so that we can enter the scope.
                         let ty = self.ctx.get_inferred_ty(subject);
                         let fn_ty = term_as_variant!(self, ty.value(), FnTy);
 
+                        if fn_ty.is_unsafe {
+                            self.record_unsafe_op(UnsafeOpKind::UnsafeCall, span);
+                        }
+
                         // Try and create the ir_type from a function definition, otherwise
                         // if it is just a function, then we make the the type from the function.
 
@@ -160,11 +190,105 @@ impl<'tcx> BodyBuilder<'tcx> {
                     }
                     FnCallTermKind::Cast(..)
                     | FnCallTermKind::UnaryOp(_, _)
-                    | FnCallTermKind::BinaryOp(_, _, _) => {
+                    | FnCallTermKind::BinaryOp(_, _, _)
+                    | FnCallTermKind::VariantCount(_)
+                    | FnCallTermKind::ConstOp(_, _)
+                    | FnCallTermKind::DiscriminantValue(_) => {
                         let rvalue = unpack!(block = self.as_rvalue(block, term));
                         self.control_flow_graph.push_assign(block, destination, rvalue, span);
                         block.unit()
                     }
+                    FnCallTermKind::Derived(item, subject, args) => {
+                        let func = self.ctx.lcx.derived_items().get_ty(subject, item).unwrap();
+
+                        if let Some(kind) = DerivedItem::unsafe_op(item) {
+                            self.record_unsafe_op(kind, span);
+                        }
+
+                        // The first argument is the type, the values of the subject type
+                        // are passed to the derived item by reference, and any remaining
+                        // arguments are passed as they are.
+                        let by_ref = DerivedItem::subject_args(item);
+                        let args = args
+                            .elements()
+                            .borrow()
+                            .iter()
+                            .skip(1)
+                            .enumerate()
+                            .map(|(index, arg)| {
+                                if index >= by_ref {
+                                    return unpack!(
+                                        block = self.as_operand(
+                                            block,
+                                            arg.value,
+                                            Mutability::Immutable
+                                        )
+                                    );
+                                }
+
+                                let place = unpack!(
+                                    block = self.as_place(block, arg.value, Mutability::Immutable)
+                                );
+                                let ty = self.ty_id_from_tir_term(arg.value);
+                                let ref_ty = ReprTy::create(ReprTy::Ref(
+                                    ty,
+                                    Mutability::Immutable,
+                                    RefKind::Normal,
+                                ));
+
+                                let temp = self.temp_place(ref_ty);
+                                let value =
+                                    RValue::Ref(Mutability::Immutable, place, RefKind::Normal);
+                                self.control_flow_graph.push_assign(block, temp, value, span);
+                                temp.into()
+                            })
+                            .collect::<Vec<_>>();
+
+                        self.build_fn_call(destination, block, Const::zst(func).into(), args, span)
+                    }
+                    FnCallTermKind::EnumFromDiscriminant(subject, value) => {
+                        let ty = self.ty_id_from_tir_term(term);
+                        self.enum_from_discriminant_into_dest(
+                            destination,
+                            block,
+                            subject,
+                            ty,
+                            value,
+                            span,
+                        )
+                    }
+                    FnCallTermKind::PtrRead(_) => {
+                        // The read is represented as a place which dereferences the
+                        // pointer, see `as_place_builder`.
+                        let place =
+                            unpack!(block = self.as_place(block, term, Mutability::Immutable));
+                        self.control_flow_graph.push_assign(block, destination, place.into(), span);
+                        block.unit()
+                    }
+                    FnCallTermKind::PtrWrite(ptr, value) => {
+                        self.record_unsafe_op(UnsafeOpKind::PtrWrite, span);
+
+                        // Lower the value and the pointer in RTL, and then assign the
+                        // value into the dereferenced pointer.
+                        let value = unpack!(block = self.as_rvalue(block, value));
+                        let place =
+                            unpack!(block = self.as_place_builder(block, ptr, Mutability::Mutable))
+                                .deref()
+                                .into_place(&mut self.projections);
+
+                        self.control_flow_graph.push_assign(block, place, value, span);
+
+                        // The result of a write is always the unit value.
+                        let const_value = Const::zero();
+                        self.control_flow_graph.push_assign(
+                            block,
+                            destination,
+                            const_value.into(),
+                            span,
+                        );
+
+                        block.unit()
+                    }
 
                     // We deal with logical
                     // binary expressions differently than other binary operators.
@@ -263,10 +387,16 @@ impl<'tcx> BodyBuilder<'tcx> {
                 // When this is a continue, we need to **jump** back to the
                 // start of the loop block, and when this is a break, we need to
                 // **jump** to the proceeding block of the loop block
-                let Some(LoopBlockInfo { loop_body, next_block }) = self.loop_block_info else {
+                let Some(LoopBlockInfo { loop_body, next_block, drop_scope_depth }) =
+                    self.loop_block_info
+                else {
                     panic_on_span!(span.span(), "`continue` or `break` outside of loop");
                 };
 
+                // Drop all of the locals that were declared within the loop body
+                // before leaving it.
+                block = self.control_flow_graph.drop_scopes_until(drop_scope_depth, block, span);
+
                 // Add terminators to this block to specify where this block will jump...
                 match control {
                     LoopControlTerm::Continue => {
@@ -282,6 +412,9 @@ impl<'tcx> BodyBuilder<'tcx> {
             Term::Return(ReturnTerm { expression }) => {
                 unpack!(block = self.term_into_dest(Place::return_place(), block, expression));
 
+                // All of the scopes of the body are exited by the `return`.
+                block = self.control_flow_graph.drop_scopes_until(0, block, span);
+
                 // In either case, we want to mark that the function has reached the
                 // **terminating** statement of this block and we needn't continue looking
                 // for more statements beyond this point.
@@ -306,7 +439,14 @@ impl<'tcx> BodyBuilder<'tcx> {
 
                 block.unit()
             }
-            Term::Unsafe(UnsafeTerm { inner }) => self.term_into_dest(destination, block, inner),
+            Term::Unsafe(UnsafeTerm { inner }) => {
+                // Any operations that are recorded whilst lowering the inner term
+                // are marked as being within an `unsafe` block.
+                let in_unsafe_block = std::mem::replace(&mut self.in_unsafe_block, true);
+                let block_and = self.term_into_dest(destination, block, inner);
+                self.in_unsafe_block = in_unsafe_block;
+                block_and
+            }
             Term::Ref(RefTerm { kind, mutable, subject }) => {
                 let mutability = if mutable { Mutability::Mutable } else { Mutability::Immutable };
 
@@ -328,9 +468,18 @@ impl<'tcx> BodyBuilder<'tcx> {
                 block.unit()
             }
             Term::Index(_) | Term::Deref(_) | Term::Access(_) => {
-                let place = unpack!(block = self.as_place(block, term, Mutability::Immutable));
-                self.control_flow_graph.push_assign(block, destination, place.into(), span);
+                // Projections on constants are folded directly into a new
+                // constant, rather than reading them from a temporary.
+                let value = match self.fold_const_projection(term) {
+                    Some(constant) => constant.into(),
+                    None => {
+                        let place =
+                            unpack!(block = self.as_place(block, term, Mutability::Immutable));
+                        place.into()
+                    }
+                };
 
+                self.control_flow_graph.push_assign(block, destination, value, span);
                 block.unit()
             }
 
@@ -342,8 +491,15 @@ impl<'tcx> BodyBuilder<'tcx> {
             | Ty::RefTy(_)
             | Ty::Universe(_)
             | Term::Hole(_)
-            | Term::Intrinsic(_)
-            | Term::Fn(_) => block.unit(),
+            | Term::TypedHole(_)
+            | Term::Intrinsic(_) => block.unit(),
+            Term::Fn(def) => {
+                // A reference to a function that is used as a value, so we take
+                // the address of the function.
+                let constant = self.fn_addr_from_tir_fn_def(def);
+                self.control_flow_graph.push_assign(block, destination, constant.into(), span);
+                block.unit()
+            }
         };
 
         block_and
@@ -376,8 +532,14 @@ impl<'tcx> BodyBuilder<'tcx> {
         args: ArgsId,
         origin: AstNodeId,
     ) -> BlockAnd<()> {
-        // First we want to lower the subject of the function call
-        let func = unpack!(block = self.as_operand(block, subject, Mutability::Immutable));
+        // First we want to lower the subject of the function call. If the subject
+        // directly refers to a function, then the function is called directly,
+        // otherwise the subject is a function pointer which is called indirectly.
+        let func = if let Term::Fn(def) = *subject.value() {
+            Const::zst(self.ty_id_from_tir_fn_def(def)).into()
+        } else {
+            unpack!(block = self.as_operand(block, subject, Mutability::Immutable))
+        };
 
         // lower the arguments of the function...
         //
@@ -393,16 +555,63 @@ impl<'tcx> BodyBuilder<'tcx> {
         //     }
         // }
 
+        // If the type of the function is C-variadic, then any arguments after its
+        // parameters are passed in the variadic part of the call. The type is used
+        // rather than the subject, so that calls through a function pointer are
+        // promoted in the same way as direct calls.
+        let fixed_count = match *self.ctx.get_inferred_ty(subject).value() {
+            Ty::FnTy(FnTy { is_c_variadic: true, params, .. }) => Some(params.len()),
+            _ => None,
+        };
+
         let args = args
             .elements()
             .borrow()
             .iter()
-            .map(|arg| unpack!(block = self.as_operand(block, arg.value, Mutability::Immutable)))
+            .enumerate()
+            .map(|(index, arg)| {
+                let operand =
+                    unpack!(block = self.as_operand(block, arg.value, Mutability::Immutable));
+
+                match fixed_count {
+                    Some(count) if index >= count => {
+                        unpack!(block = self.promote_variadic_arg(block, arg.value, operand))
+                    }
+                    _ => operand,
+                }
+            })
             .collect::<Vec<_>>();
 
         self.build_fn_call(destination, block, func, args, origin)
     }
 
+    /// Apply the C "default argument promotions" to an argument that is passed
+    /// in the variadic part of a call to a C-variadic function. This means
+    /// that `f32`s are promoted to `f64`s, and that `bool`s and integers that
+    /// are smaller than 32 bits are promoted to 32-bit integers.
+    fn promote_variadic_arg(
+        &mut self,
+        block: BasicBlock,
+        term: TermId,
+        operand: Operand,
+    ) -> BlockAnd<Operand> {
+        let ty = self.ty_id_from_tir_term(term);
+
+        let promoted_ty = match *ty.borrow() {
+            ReprTy::Float(FloatTy::F32) => COMMON_REPR_TYS.f64,
+            ReprTy::Bool | ReprTy::Int(SIntTy::I8 | SIntTy::I16) => COMMON_REPR_TYS.i32,
+            ReprTy::UInt(UIntTy::U8 | UIntTy::U16) => COMMON_REPR_TYS.u32,
+            _ => return block.and(operand),
+        };
+
+        let span = self.span_of_term(term);
+        let promoted = self.temp_place(promoted_ty);
+        let cast = RValue::Cast(CastKind::classify(ty, promoted_ty), operand, promoted_ty);
+
+        self.control_flow_graph.push_assign(block, promoted, cast, span);
+        block.and(promoted.into())
+    }
+
     /// Build a function call from the provided subject and arguments. This
     /// function simply terminates the current [BasicBlock] with a
     /// [`TerminatorKind::Call`] and returns the block that is used for the
@@ -418,11 +627,15 @@ impl<'tcx> BodyBuilder<'tcx> {
         // This is the block that is used when resuming from the function..
         let success = self.control_flow_graph.start_new_block();
 
+        // If the function panics, then any locals that are in scope need to be
+        // cleaned up whilst unwinding.
+        let unwind = self.control_flow_graph.unwind_block(origin);
+
         // Terminate the current block with a `Call` terminator
         self.control_flow_graph.terminate(
             block,
             origin,
-            TerminatorKind::Call { op: subject, args, destination, target: Some(success) },
+            TerminatorKind::Call { op: subject, args, destination, target: Some(success), unwind },
         );
 
         success.unit()
@@ -570,6 +783,85 @@ impl<'tcx> BodyBuilder<'tcx> {
         block.unit()
     }
 
+    /// Place the variant of the enum `subject` that has the `value` as its
+    /// discriminant into the destination, which is an `Option` of the enum
+    /// with the type `option`. If none of the variants have the discriminant,
+    /// then the destination is `None`.
+    ///
+    /// The `value` is a `usize`, and so the discriminants are compared with it
+    /// after they have been converted into a `usize` in the same way as the
+    /// `discriminant_value` intrinsic converts them, i.e. signed discriminants
+    /// are sign-extended. This means that converting the discriminant of a
+    /// variant back into the enum always yields the same variant.
+    fn enum_from_discriminant_into_dest(
+        &mut self,
+        destination: Place,
+        mut block: BasicBlock,
+        subject: ReprTyId,
+        option: ReprTyId,
+        value: TermId,
+        origin: AstNodeId,
+    ) -> BlockAnd<()> {
+        let value = unpack!(block = self.as_operand(block, value, Mutability::Immutable));
+
+        let option_adt = option.borrow().as_adt();
+        let (none, some) = option_adt.map(|adt| {
+            (adt.variant_idx(&"None".into()).unwrap(), adt.variant_idx(&"Some".into()).unwrap())
+        });
+
+        let (discriminant_ty, discriminants) = subject
+            .borrow()
+            .as_adt()
+            .map(|adt| (adt.discriminant_ty(), adt.discriminants().collect::<Vec<_>>()));
+
+        let ptr_size = self.target().ptr_size();
+        let discriminant_size = discriminant_ty.size(ptr_size);
+
+        let join = self.control_flow_graph.start_new_block();
+        let mut targets = Vec::with_capacity(discriminants.len());
+
+        for (variant, discriminant) in discriminants {
+            let variant_block = self.control_flow_graph.start_new_block();
+
+            // Create the variant, and then wrap it in `Some(..)`.
+            let enum_value = self.temp_place(subject);
+            self.control_flow_graph.push(
+                variant_block,
+                Statement { kind: StatementKind::Discriminate(enum_value, variant), origin },
+            );
+            self.control_flow_graph.push(
+                variant_block,
+                Statement { kind: StatementKind::Discriminate(destination, some), origin },
+            );
+
+            let aggregate =
+                RValue::Aggregate(AggregateKind::Enum(option_adt, some), vec![enum_value.into()]);
+            self.control_flow_graph.push_assign(variant_block, destination, aggregate, origin);
+            self.control_flow_graph.goto(variant_block, join, origin);
+
+            let discriminant = if discriminant_ty.is_signed() {
+                ptr_size.truncate(discriminant_size.sign_extend(discriminant))
+            } else {
+                discriminant
+            };
+
+            targets.push((discriminant, variant_block));
+        }
+
+        // Any other value isn't the discriminant of a variant.
+        let otherwise = self.control_flow_graph.start_new_block();
+        self.control_flow_graph.push(
+            otherwise,
+            Statement { kind: StatementKind::Discriminate(destination, none), origin },
+        );
+        self.control_flow_graph.goto(otherwise, join, origin);
+
+        let targets = SwitchTargets::new(targets.into_iter(), Some(otherwise));
+        self.control_flow_graph.terminate(block, origin, TerminatorKind::Switch { value, targets });
+
+        join.unit()
+    }
+
     /// This function will generate the necessary code to initialise a slice on
     /// the stack. A slice differs from an array by the fact that it is an
     /// aggregate value that contains the length of the slice, and the
@@ -624,7 +916,11 @@ impl<'tcx> BodyBuilder<'tcx> {
         origin: AstNodeId,
     ) -> BlockAnd<()> {
         let element_ty = ty.borrow().element_ty().unwrap();
-        let size = self.ctx.size_of(element_ty).unwrap() * args.len();
+        let Some(element_size) = self.size_of(element_ty, origin) else {
+            return block.unit();
+        };
+
+        let size = element_size * args.len();
         let size_op = Operand::Const(Const::usize(size as u64, &self.ctx));
 
         // find the `malloc` function which is defined in the prelude
@@ -54,7 +54,7 @@ pub(super) struct Candidate {
     pub pairs: SmallVec<[MatchPair; 1]>,
 
     /// Block before all of the bindings have been established within
-    /// the arm.
+    /// the arm. This is [None] if the candidate can never be reached.
     pub pre_binding_block: Option<BasicBlock>,
 
     /// In the event that the guard is evaluated, this is the block that
@@ -106,6 +106,32 @@ impl Candidate {
         }
     }
 
+    /// Split the candidate into `count` copies of itself, which become the
+    /// sub-candidates of the candidate. This is used when a test doesn't
+    /// decide whether the candidate matches, and so the candidate must be
+    /// specialised for each of the outcomes of the test. The bindings
+    /// that have already been made remain on the candidate, and are shared
+    /// by all of the copies.
+    pub(super) fn split_into_copies(&mut self, count: usize) -> &mut [Candidate] {
+        debug_assert!(self.sub_candidates.is_empty());
+        let pairs = mem::take(&mut self.pairs);
+
+        self.sub_candidates = (0..count)
+            .map(|_| Candidate {
+                origin: self.origin,
+                has_guard: self.has_guard,
+                otherwise_block: None,
+                pre_binding_block: None,
+                next_candidate_pre_bind_block: None,
+                pairs: pairs.clone(),
+                bindings: Vec::new(),
+                sub_candidates: Vec::new(),
+            })
+            .collect();
+
+        &mut self.sub_candidates
+    }
+
     /// Visit all of the leaves of a candidate and apply some operation on
     /// each one that is contained in the current candidate.
     pub(super) fn visit_leaves<'a>(&'a mut self, mut visit_leaf: impl FnMut(&'a mut Candidate)) {
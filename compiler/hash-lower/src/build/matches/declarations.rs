@@ -3,7 +3,7 @@
 
 use hash_ast::ast::AstNodeId;
 use hash_ir::{
-    ir::{BasicBlock, Local, LocalDecl, Place},
+    ir::{BasicBlock, Local, LocalDecl, Place, TerminatorKind},
     ty::{Mutability, ReprTyId},
 };
 use hash_storage::store::{statics::StoreId, TrivialSequenceStoreKey};
@@ -36,13 +36,65 @@ impl<'tcx> BodyBuilder<'tcx> {
     /// This function handles the lowering of an declaration term.
     pub(crate) fn lower_declaration(&mut self, mut block: BasicBlock, decl: &Decl) -> BlockAnd<()> {
         // First, we declare all of the bindings that are present
-        // in the pattern, and then we place the expression into
-        // the pattern using `expr_into_pat`.
+        // in the pattern. If the declaration has no initialiser, then the
+        // bindings are left uninitialised until they are assigned to.
+        //
+        // @@Future: the bindings of a declaration without an initialiser
+        // are never dropped, since there are no drop flags to record
+        // whether they have been initialised.
+        let Some(value) = decl.value else {
+            self.declare_bindings(decl.bind_pat);
+            return block.unit();
+        };
+
+        if let Some(otherwise) = decl.otherwise {
+            return self.lower_declaration_with_else(block, decl.bind_pat, value, otherwise);
+        }
+
+        // Then we place the expression into the pattern using
+        // `expr_into_pat`. The drops of the bindings are only scheduled once
+        // they are initialised, so that an unwind from the initialiser
+        // doesn't drop them.
         self.declare_bindings(decl.bind_pat);
-        unpack!(block = self.tir_term_into_pat(block, decl.bind_pat, decl.value));
+        unpack!(block = self.tir_term_into_pat(block, decl.bind_pat, value));
+        self.schedule_binding_drops(decl.bind_pat);
+
+        block.unit()
+    }
+
+    /// Lower a declaration with a refutable pattern, i.e. `Some(x) := y else
+    /// { ... }`. The decision tree for the pattern is built like a `match`
+    /// with a single arm, and the `otherwise` block of the tree runs the
+    /// `else` block, which must diverge.
+    fn lower_declaration_with_else(
+        &mut self,
+        mut block: BasicBlock,
+        pat: PatId,
+        value: TermId,
+        otherwise: TermId,
+    ) -> BlockAnd<()> {
+        let pat_span = self.span_of_pat(pat);
+        let place = unpack!(block = self.as_place_builder(block, value, Mutability::Mutable));
+
+        let has_guard = pat.borrow().is_if();
+        let mut candidate = Candidate::new(pat_span, pat, &place, has_guard);
 
-        // if the declaration has an initialiser, then we need to deal with
-        // the initialisation block.
+        // The `else` block is lowered before the bindings are declared, so
+        // that the bindings aren't dropped when exiting from it since they
+        // are never initialised on that path.
+        if let Some(else_block) = self.build_match_tree(block, pat_span, &mut [&mut candidate]) {
+            let BlockAnd(else_end, _) =
+                self.term_into_temp(else_block, otherwise, Mutability::Immutable);
+
+            if !self.control_flow_graph.is_terminated(else_end) {
+                let span = self.span_of_term(otherwise);
+                self.control_flow_graph.terminate(else_end, span, TerminatorKind::Unreachable);
+            }
+        }
+
+        self.declare_bindings(pat);
+        let block = self.bind_pat(pat_span, pat, candidate);
+        self.schedule_binding_drops(pat);
         block.unit()
     }
 
@@ -51,13 +103,29 @@ impl<'tcx> BodyBuilder<'tcx> {
     /// bound within the pattern since we have already checked that all pattern
     /// variants declare the same binds of the same type, on the same
     /// pattern level.
-    pub(super) fn declare_bindings(&mut self, pat: PatId) {
+    ///
+    /// The bindings aren't dropped until [Self::schedule_binding_drops] is
+    /// called, which should happen once they have been initialised.
+    pub(crate) fn declare_bindings(&mut self, pat: PatId) {
         self.visit_primary_pattern_bindings(pat, &mut |this, mutability, name, _span, ty| {
             let local = LocalDecl::new(name.ident(), mutability, ty);
             this.push_local(name, local);
         })
     }
 
+    /// Schedule the drops of all of the bindings that were declared by
+    /// [Self::declare_bindings] for the given pattern, in the innermost
+    /// scope.
+    pub(crate) fn schedule_binding_drops(&mut self, pat: PatId) {
+        self.visit_primary_pattern_bindings(pat, &mut |this, _, name, _span, ty| {
+            if let Some(local) = this.lookup_local(name)
+                && ty.needs_drop()
+            {
+                this.control_flow_graph.schedule_drop(local);
+            }
+        })
+    }
+
     fn visit_primary_pattern_bindings(
         &mut self,
         pat: PatId,
@@ -194,7 +262,7 @@ impl<'tcx> BodyBuilder<'tcx> {
     /// Construct a [Candidate] for a given [ast::Pat], and then lower
     /// the pattern with a single candidate, and the expression that
     /// is the initialising value of the patterns.
-    fn place_into_pat(
+    pub(crate) fn place_into_pat(
         &mut self,
         block: BasicBlock,
         pat: PatId,
@@ -138,22 +138,33 @@ impl<'tcx> BodyBuilder<'tcx> {
         origin: AstNodeId,
         candidates: &mut [&mut Candidate],
     ) {
-        // This is the basic block that is derived for using when the
-        // matching fails on the pattern, and that it should jump to
-        // in the `otherwise` situation.
-        let mut otherwise = None;
-
-        self.match_candidates(origin, block, &mut otherwise, candidates);
-
         // We need to terminate the otherwise block with an `unreachable` since
         // this branch should never be reached since the `match` is exhaustive.
-        if let Some(otherwise_block) = otherwise {
+        if let Some(otherwise_block) = self.build_match_tree(block, origin, candidates) {
             self.control_flow_graph.terminate(
                 otherwise_block,
                 subject_origin,
                 TerminatorKind::Unreachable,
             );
         }
+    }
+
+    /// Build the decision tree for the given [Candidate]s like
+    /// [`Self::lower_match_tree`], but leave the `otherwise` block that is
+    /// reached when none of the candidates match for the caller to deal with.
+    /// This returns [None] if the candidates are exhaustive.
+    fn build_match_tree(
+        &mut self,
+        block: BasicBlock,
+        origin: AstNodeId,
+        candidates: &mut [&mut Candidate],
+    ) -> Option<BasicBlock> {
+        // This is the basic block that is derived for using when the
+        // matching fails on the pattern, and that it should jump to
+        // in the `otherwise` situation.
+        let mut otherwise = None;
+
+        self.match_candidates(origin, block, &mut otherwise, candidates);
 
         // Link each leaf candidate to the `pre_binding_block` of the next candidate.
         let mut previous_candidate: Option<&mut Candidate> = None;
@@ -166,6 +177,8 @@ impl<'tcx> BodyBuilder<'tcx> {
                 previous_candidate = Some(leaf);
             });
         }
+
+        otherwise
     }
 
     /// This function will lower the bindings, guards and the bodies of the arms
@@ -184,9 +197,13 @@ impl<'tcx> BodyBuilder<'tcx> {
         for (arm, candidate) in arm_candidates {
             // Each match-case creates its own scope, so we need to enter it here...
             Context::enter_resolved_scope_mut(self, ScopeKind::Stack(arm.stack_id), |this| {
+                this.control_flow_graph.push_drop_scope();
                 this.declare_bindings(arm.bind_pat);
                 let arm_block = this.bind_pat(subject_origin, arm.bind_pat, candidate);
-                lowered_arms_edges.push(this.term_into_dest(destination, arm_block, arm.value));
+                this.schedule_binding_drops(arm.bind_pat);
+                let arm_end = unpack!(this.term_into_dest(destination, arm_block, arm.value));
+                let arm_end = this.control_flow_graph.pop_drop_scope(arm_end, subject_origin);
+                lowered_arms_edges.push(arm_end.unit());
             })
         }
 
@@ -272,13 +289,10 @@ impl<'tcx> BodyBuilder<'tcx> {
             if let Some(otherwise) = otherwise_block {
                 otherwise
             } else {
-                // All other candidates after this point are unreachable, so we can
-                // just remove
-                if unmatched_candidates.is_empty() {
-                    return;
-                }
-
-                self.control_flow_graph.start_new_block()
+                // All other candidates after this point are unreachable, so
+                // we don't test them, and they are left without a
+                // `pre_binding_block`.
+                return;
             }
         } else {
             start_block
@@ -352,12 +366,9 @@ impl<'tcx> BodyBuilder<'tcx> {
             }
         }
 
-        // Add blocks for all of the unreachable candidates...
-        for candidate in unreachable_candidates {
-            debug_assert!(candidate.pre_binding_block.is_none());
-
-            candidate.pre_binding_block = Some(self.control_flow_graph.start_new_block());
-        }
+        // All of the candidates after the first candidate without a guard are
+        // unreachable, so they are left without a `pre_binding_block`.
+        debug_assert!(unreachable_candidates.iter().all(|c| c.pre_binding_block.is_none()));
 
         reachable_candidates.last_mut().unwrap().otherwise_block
     }
@@ -416,15 +427,17 @@ impl<'tcx> BodyBuilder<'tcx> {
 
     /// This is the point where we begin to "test" candidates since we have
     /// simplified all of the candidates as much as possible. We take the
-    /// first candidate from the provided list, and take the first pattern
-    /// within it's list that it must satisfy. Then we decide what kind of
-    /// test to perform based on the type of the pattern.
+    /// first candidate from the provided list, and select one of the patterns
+    /// within it's list that it must satisfy (see [`Self::select_test_pair`]).
+    /// Then we decide what kind of test to perform based on the type of the
+    /// pattern.
     ///
-    /// After we know what test is going to be performed on the candidate, we
-    /// can cut down the number of candidates (from high to low priority)
-    /// and check.
-    ///
-    /// There are situations where this does not apply, for example:
+    /// After we know what test is going to be performed, we sort each of the
+    /// candidates (from high to low priority) into the outcomes of the test
+    /// that they still apply to. This builds a decision tree in the style of
+    /// Maranget's "Compiling Pattern Matching to Good Decision Trees", in
+    /// which each outcome of a test is specialised with the candidates that
+    /// are compatible with it. For example:
     /// ```ignore
     /// (x, y, z) := (true, true, true)
     ///
@@ -441,68 +454,50 @@ impl<'tcx> BodyBuilder<'tcx> {
     /// - If `x` is `true`, then we have candidates #1, #2, and #4
     /// - If `x` is `false`, then we have candidates #2, #3
     ///
-    /// This would generate separate code-paths for these two cases. In some
-    /// cases, this would produce exponential behaviour (and thus exponential
-    /// amount of code). This might not actually show up in real scenarios,
-    /// but a simple scenario which is realistic might be:
-    /// ```ignore
-    /// match x {
-    ///    "a" if a => { ... }
-    ///    "b" if b => { ... }
-    ///    "c" if c => { ... }
-    /// }
-    /// ```
+    /// Candidate #2 doesn't test `x`, so it is split into a copy for each of
+    /// the outcomes (see [`Candidate::split_into_copies`]). This means that
+    /// each outcome of the test leads to a set of candidates that is complete,
+    /// and so if none of the candidates of an outcome match, then none of the
+    /// candidates of the whole `match` can match, and we can branch straight
+    /// to the `otherwise` block. As a result, a particular place is never
+    /// tested again once its value is known on some path, unlike with a linear
+    /// `if-else-if` chain of tests.
     ///
-    /// Here, we use a [TestKind::Eq] since we need to perform an equality test
-    /// between the string literal and `x`. Using the standard approach, we
-    /// wouldn't generate two distinct sets of two values, since `"a"` might
-    /// be matched later by some other branch, which would then lead to an
-    /// exponential number of tests occurring.
+    /// The cost of the copies is that the size of the tree might grow
+    /// exponentially in the number of places that are tested, however the
+    /// heuristic that is used to select the tested place keeps the number of
+    /// copies low in practise, and candidates that are unreachable after
+    /// an irrefutable candidate are never tested (see
+    /// [`Self::match_simplified_candidates`]).
     ///
-    /// To avoid this, we try to ensure that the amount of generated tests is
-    /// linear. We do this by doing a k-way test, which returns an additional
-    /// "unmatched" set alongside the matches `k` set. When we encounter a
-    /// candidate that would present in more than one of the sets, we put it
-    /// and all candidates below it in an "unmatched" set. This ensures that
-    /// these sets are disjoint, which means we don't need to perform more
-    /// than k + 1 tests.
+    /// Ref: <http://moscova.inria.fr/~maranget/papers/ml05e-maranget.pdf>
     ///
-    /// Once a test is performed, we branch into the appropriate candidate set,
-    /// and then recursively call `match_candidates`. These sub-matches are
-    /// non-exhaustive (since we discarded the `otherwise` set) - so we
-    /// continue with the "unmatched" set with `match_candidates` Using this
-    /// approach, we essentially generate an `if-else-if` chain.
+    /// [Candidate]: candidate::Candidate
     fn test_candidates(
         &mut self,
         origin: AstNodeId,
-        mut candidates: &mut [&mut Candidate],
+        candidates: &mut [&mut Candidate],
         block: BasicBlock,
         otherwise: &mut Option<BasicBlock>,
     ) {
-        // take the first pattern
-        let pair = &candidates.first().unwrap().pairs[0];
+        // Select which of the patterns of the first candidate to test.
+        let pair_index = self.select_test_pair(candidates);
+        let pair = &candidates.first().unwrap().pairs[pair_index];
         let mut test = self.test_match_pair(pair);
         let match_place = pair.place.clone();
 
-        // For switch tests, we may want to add additional cases to the
-        // test from the available candidates
+        // For switch tests, we add the cases of all of the candidates that
+        // test the same place to the test, so that each of them is sorted
+        // into its own outcome.
         match test.kind {
             TestKind::Switch { options: ref mut variants, .. } => {
                 for candidate in candidates.iter() {
-                    // If we couldn't add a particular candidate, then short-circuit
-                    // since we won't be able to add any candidates after.
-                    if !self.add_variants_to_switch(&match_place, candidate, variants) {
-                        break;
-                    }
+                    self.add_variants_to_switch(&match_place, candidate, variants);
                 }
             }
             TestKind::SwitchInt { ref mut options, .. } => {
                 for candidate in candidates.iter() {
-                    // If we couldn't add a particular candidate, then short-circuit
-                    // since we won't be able to add any candidates after.
-                    if !self.add_cases_to_switch(&match_place, candidate, options) {
-                        break;
-                    }
+                    self.add_cases_to_switch(&match_place, candidate, options);
                 }
             }
             _ => {}
@@ -517,67 +512,99 @@ impl<'tcx> BodyBuilder<'tcx> {
         // the size we need
         target_table.resize_with(test.targets(), Default::default);
 
-        let candidate_count = candidates.len();
-
         // Sort the candidates into each appropriate vector within the `target_map`.
-        // If we encounter a candidate that the test does not apply to, then we
-        // stop sorting since there will be no further candidates that the test applies
-        // to.
-        while let Some(candidate) = candidates.first_mut() {
-            let Some(index) = self.sort_candidate(&match_place, &test, candidate) else {
-                break;
-            };
+        // If the test doesn't decide whether a candidate matches, because the
+        // candidate doesn't test the place, or the outcome of the test doesn't
+        // imply anything about the pattern, then the candidate still applies
+        // after all of the outcomes that can be reached.
+        let reachable_targets = test.reachable_targets();
+
+        for (index, candidate) in candidates.iter_mut().enumerate() {
+            if let Some(target) = self.sort_candidate(&match_place, &test, candidate) {
+                target_table[target].push(candidate);
+                continue;
+            }
 
-            let (candidate, rest) = candidates.split_first_mut().unwrap();
-            target_table[index].push(candidate);
-            candidates = rest;
-        }
+            // The first candidate determined the test, and so it must be
+            // sorted into one of the outcomes.
+            debug_assert!(index > 0, "the first candidate must be sorted by its test");
 
-        // We should have at-least removed the first candidate.
-        debug_assert!(candidate_count > candidates.len());
+            if let [target] = reachable_targets[..] {
+                target_table[target].push(candidate);
+            } else {
+                let copies = candidate.split_into_copies(reachable_targets.len());
 
-        let make_target_blocks = move |this: &mut Self| -> Vec<BasicBlock> {
-            // If none of the `target_table` entries match, then we use this
-            // as the place to branch to. This is either the block where we
-            // started matching the untested candidates in `match_candidates`,
-            // or it's the `otherwise` block.
-            let remainder_start = &mut None;
-            let remainder_start =
-                if candidates.is_empty() { &mut *otherwise } else { remainder_start };
+                for (copy, target) in copies.iter_mut().zip(&reachable_targets) {
+                    target_table[*target].push(copy);
+                }
+            }
+        }
 
+        let make_target_blocks = move |this: &mut Self| -> Vec<BasicBlock> {
             // For every outcome of the test, process the candidates that still
-            // apply, collect a list of block where the control flow will branch if
-            // one of the `target_table` entries sets is not exhaustive.
-            let target_blocks: Vec<_> = target_table
+            // apply. Since these are all of the candidates that can match after
+            // the outcome, then if none of them match (or there are none), we
+            // branch to the `otherwise` block.
+            target_table
                 .into_iter()
                 .map(|mut candidates| {
                     if !candidates.is_empty() {
                         let candidate_start = this.control_flow_graph.start_new_block();
 
-                        this.match_candidates(
-                            origin,
-                            candidate_start,
-                            remainder_start,
-                            &mut candidates,
-                        );
+                        this.match_candidates(origin, candidate_start, otherwise, &mut candidates);
                         candidate_start
                     } else {
-                        *remainder_start
-                            .get_or_insert_with(|| this.control_flow_graph.start_new_block())
+                        *otherwise.get_or_insert_with(|| this.control_flow_graph.start_new_block())
                     }
                 })
-                .collect();
+                .collect()
+        };
 
-            if !candidates.is_empty() {
-                let remainder_start =
-                    remainder_start.unwrap_or_else(|| this.control_flow_graph.start_new_block());
-                this.match_candidates(origin, remainder_start, otherwise, candidates);
-            };
+        self.perform_test(origin, block, &match_place, &test, make_target_blocks);
+    }
 
-            target_blocks
+    /// Select which [MatchPair] of the first candidate should be tested. Any
+    /// of the pairs of the first candidate must be tested before it can be
+    /// matched, so we are free to pick any of them. This follows the heuristics
+    /// of Maranget's decision tree construction to pick the place that leads
+    /// to the smallest tree:
+    ///
+    /// 1. "needed prefix": pick the place that is tested by the longest run of
+    ///    candidates, since a single test on that place can then sort the most
+    ///    candidates before any of them have to be copied.
+    ///
+    /// 2. "small default": of those, pick the place that is not tested by the
+    ///    fewest candidates, since each of these candidates is copied into all
+    ///    of the outcomes of the test.
+    ///
+    /// If multiple places are still tied, the earliest pair is picked so that
+    /// the tests remain in source order.
+    ///
+    /// Ref: <http://moscova.inria.fr/~maranget/papers/ml05e-maranget.pdf>
+    ///
+    /// [MatchPair]: candidate::MatchPair
+    fn select_test_pair(&self, candidates: &[&mut Candidate]) -> usize {
+        let (first, rest) = candidates.split_first().unwrap();
+        let tests_place = |candidate: &Candidate, place: &PlaceBuilder| {
+            candidate.pairs.iter().any(|pair| pair.place == *place)
         };
 
-        self.perform_test(origin, block, &match_place, &test, make_target_blocks);
+        // `or` patterns are dealt with separately, and they are always
+        // sorted to the end of the pairs.
+        first
+            .pairs
+            .iter()
+            .enumerate()
+            .take_while(|(_, pair)| !pair.pat.borrow().is_or())
+            .map(|(index, pair)| {
+                let prefix = rest.iter().take_while(|c| tests_place(c, &pair.place)).count();
+                let defaults = rest.iter().filter(|c| !tests_place(c, &pair.place)).count();
+                (index, prefix, defaults)
+            })
+            .max_by(|(a, a_prefix, a_defaults), (b, b_prefix, b_defaults)| {
+                a_prefix.cmp(b_prefix).then(b_defaults.cmp(a_defaults)).then(b.cmp(a))
+            })
+            .map_or(0, |(index, ..)| index)
     }
 
     /// This function is responsible for putting all of the declared bindings
@@ -588,20 +615,47 @@ impl<'tcx> BodyBuilder<'tcx> {
             _ => None,
         };
 
-        if candidate.sub_candidates.is_empty() {
+        // Leaves that are never reached don't have a `pre_binding_block`,
+        // and they are skipped entirely.
+        let mut reachable_leaves = 0;
+        traverse_candidate(
+            &candidate,
+            &mut (),
+            &mut |leaf, _| reachable_leaves += leaf.pre_binding_block.is_some() as usize,
+            |inner, _| inner.sub_candidates.iter(),
+            |_| {},
+        );
+
+        if reachable_leaves == 0 {
+            // The arm is unreachable, but it is still lowered.
+            self.control_flow_graph.start_new_block()
+        } else if candidate.sub_candidates.is_empty() {
             // We don't need generate another `BasicBlock` when we only have
             // this candidate.
             self.bind_and_guard_matched_candidate(candidate, guard, &[], origin)
         } else {
-            let target_block = self.control_flow_graph.start_new_block();
+            // If only one of the leaves can be reached, then the arm can
+            // continue directly from its bindings.
+            let mut target_block =
+                (reachable_leaves > 1).then(|| self.control_flow_graph.start_new_block());
 
             traverse_candidate(
                 candidate,
                 &mut Vec::new(),
                 &mut |leaf, parent_bindings| {
+                    if leaf.pre_binding_block.is_none() {
+                        return;
+                    }
+
                     let binding_end =
                         self.bind_and_guard_matched_candidate(leaf, guard, parent_bindings, origin);
-                    self.control_flow_graph.goto(binding_end, target_block, origin);
+
+                    match target_block {
+                        Some(target_block) => {
+                            self.control_flow_graph.goto(binding_end, target_block, origin)
+                        }
+                        None => target_block = Some(binding_end),
+                    }
                 },
                 |inner, parent_bindings| {
                     parent_bindings.push(inner.bindings);
@@ -612,7 +666,7 @@ impl<'tcx> BodyBuilder<'tcx> {
                 },
             );
 
-            target_block
+            target_block.unwrap()
         }
     }
 
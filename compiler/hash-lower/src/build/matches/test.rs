@@ -9,7 +9,7 @@ use fixedbitset::FixedBitSet;
 use hash_ast::ast::{self, AstNodeId};
 use hash_ir::{
     ir::{
-        BasicBlock, BinOp, Const, ConstKind, Operand, Place, PlaceProjection, RValue,
+        AssertKind, BasicBlock, BinOp, Const, ConstKind, Operand, Place, PlaceProjection, RValue,
         SwitchTargets, TerminatorKind,
     },
     lang_items::LangItem,
@@ -21,7 +21,7 @@ use hash_tir::{
     atom_info::ItemInAtomInfo,
     tir::{CtorPat, IfPat, NodesId, ParamIndex, Pat, PatArgsId, PatId, RangePat, Spread},
 };
-use hash_utils::indexmap::IndexMap;
+use hash_utils::{indexmap::IndexMap, itertools::Itertools};
 
 use super::{
     candidate::{Candidate, MatchPair},
@@ -36,6 +36,9 @@ pub(super) enum TestKind {
         /// The id of the ADT that the switch is being performed on.
         adt: AdtId,
 
+        /// The type of the subject that the switch is being performed on.
+        ty: ReprTyId,
+
         /// All of the allowed variants for this particular switch.
         options: FixedBitSet,
     },
@@ -117,6 +120,27 @@ impl Test {
             TestKind::Eq { .. } | TestKind::Range { .. } | TestKind::Len { .. } => 2,
         }
     }
+
+    /// Get the indices of the targets that the test can branch to. A
+    /// [TestKind::Switch] only branches to the variants within its
+    /// `options`, and to the `otherwise` target if the options don't cover
+    /// all of the variants. All of the targets of any other test can be
+    /// reached.
+    pub(super) fn reachable_targets(&self) -> Vec<usize> {
+        match self.kind {
+            TestKind::Switch { adt, ref options, .. } => {
+                let variant_count = adt.borrow().variants.len();
+                let mut targets = options.ones().collect_vec();
+
+                if targets.len() < variant_count {
+                    targets.push(variant_count);
+                }
+
+                targets
+            }
+            _ => (0..self.targets()).collect(),
+        }
+    }
 }
 
 impl<'tcx> BodyBuilder<'tcx> {
@@ -206,6 +230,7 @@ impl<'tcx> BodyBuilder<'tcx> {
                         Test {
                             kind: TestKind::Switch {
                                 adt,
+                                ty: ty_id,
                                 options: FixedBitSet::with_capacity(variant_count),
                             },
                             origin,
@@ -531,6 +556,21 @@ impl<'tcx> BodyBuilder<'tcx> {
         }
     }
 
+    /// Create a block which fails an [`AssertKind::InvalidDiscriminant`]
+    /// assertion on the given `discriminant`.
+    fn invalid_discriminant_block(&mut self, discriminant: Place, origin: AstNodeId) -> BasicBlock {
+        let block = self.control_flow_graph.start_new_block();
+        let kind = AssertKind::InvalidDiscriminant { discriminant: Operand::Place(discriminant) };
+
+        // The assertion always fails, so the block that it continues to is
+        // never reached.
+        let condition = Operand::Const(Const::bool(false));
+        let unreachable = self.assert(block, condition, true, kind, origin);
+        self.control_flow_graph.terminate(unreachable, origin, TerminatorKind::Unreachable);
+
+        block
+    }
+
     /// This function is responsible for generating the code for the specified
     /// [Test].
     pub(super) fn perform_test(
@@ -546,20 +586,68 @@ impl<'tcx> BodyBuilder<'tcx> {
         let span = test.origin;
 
         match test.kind {
-            TestKind::Switch { adt, options: ref variants } => {
-                let target_blocks = make_target_blocks(self);
+            TestKind::Switch { adt, ty, options: ref variants } => {
                 let (variant_count, discriminant_ty) =
                     adt.map(|adt| (adt.variants.len(), adt.discriminant_ty()));
 
+                // If the discriminant of the place has already been read by a test
+                // that dominates this one, then we re-use the read. Otherwise, we
+                // read the discriminant into a temporary so that we can compare it
+                // to the specified values within the switch statement, and record
+                // the read whilst the sub-tree of this test is built.
+                //
+                // @@Safety: this relies on `if-guards` not being able to mutate
+                // the subject of the match.
+                let discriminant_ty = discriminant_ty.to_repr_ty();
+                let cached = self
+                    .discriminant_reads
+                    .iter()
+                    .rev()
+                    .find(|(read, _)| read == place_builder)
+                    .map(|(_, discriminant)| *discriminant);
+
+                let discriminant_tmp = match cached {
+                    Some(discriminant) => discriminant,
+                    None => {
+                        let discriminant_tmp = self.temp_place(discriminant_ty);
+                        let value = self.discriminant_of(place, ty);
+                        self.control_flow_graph.push_assign(
+                            block,
+                            discriminant_tmp,
+                            value,
+                            subject_origin,
+                        );
+
+                        self.discriminant_reads.push((place_builder.clone(), discriminant_tmp));
+                        discriminant_tmp
+                    }
+                };
+
+                let target_blocks = make_target_blocks(self);
+
+                if cached.is_none() {
+                    self.discriminant_reads.pop();
+                }
+
                 // Assert that the number of variants is the same as the number of
                 // target blocks.
                 debug_assert_eq!(variant_count + 1, target_blocks.len());
 
                 let otherwise_block = target_blocks.last().copied();
 
+                // If discriminants are checked, then the variants that aren't
+                // tested explicitly branch to the `otherwise` block, so that the
+                // `otherwise` of the switch is only reached by a discriminant that
+                // doesn't belong to any variant, which then traps.
+                let checked = self.ctx.settings.lowering_settings.checked_discriminants;
+                let switch_otherwise = if checked {
+                    Some(self.invalid_discriminant_block(discriminant_tmp, subject_origin))
+                } else {
+                    otherwise_block
+                };
+
                 // Here we want to create a switch statement that will match on all of the
                 // specified discriminants of the ADT.
-                let discriminant_ty = discriminant_ty.to_repr_ty();
                 let targets = adt.map(|adt| {
                     // Map over all of the discriminants of the ADT, and filter out those that
                     // are not in the `options` set.
@@ -568,21 +656,16 @@ impl<'tcx> BodyBuilder<'tcx> {
                             let idx = var_idx.index();
                             if variants.contains(idx) {
                                 Some((discriminant, target_blocks[idx]))
+                            } else if checked {
+                                otherwise_block.map(|block| (discriminant, block))
                             } else {
                                 None
                             }
                         }),
-                        otherwise_block,
+                        switch_otherwise,
                     )
                 });
 
-                // Then we push an assignment to a the passed in `place` so that we
-                // can compare the discriminant to the specified value within the
-                // switch statement.
-                let discriminant_tmp = self.temp_place(discriminant_ty);
-                let value = RValue::Discriminant(place);
-                self.control_flow_graph.push_assign(block, discriminant_tmp, value, subject_origin);
-
                 // then terminate this block with the `switch` terminator
                 self.control_flow_graph.terminate(
                     block,
@@ -778,6 +861,7 @@ impl<'tcx> BodyBuilder<'tcx> {
         let str_eq = self.get_lang_item(LangItem::StrEq);
         let eq_result = self.temp_place(COMMON_REPR_TYS.bool);
         let eq_block = self.control_flow_graph.start_new_block();
+        let unwind = self.control_flow_graph.unwind_block(span);
         self.control_flow_graph.terminate(
             block,
             span,
@@ -786,6 +870,7 @@ impl<'tcx> BodyBuilder<'tcx> {
                 args: vec![Operand::Place(value), expected],
                 destination: eq_result,
                 target: Some(eq_block),
+                unwind,
             },
         );
 
@@ -801,43 +886,40 @@ impl<'tcx> BodyBuilder<'tcx> {
         );
     }
 
-    /// Try to add any match pairs of the [Candidate] to the particular
-    /// `options` of a [TestKind::SwitchInt].
+    /// Add the variant that the [Candidate] tests at the `test_place` to the
+    /// `variants` of a [TestKind::Switch], if the candidate tests the place
+    /// with a constructor pattern.
     pub(super) fn add_variants_to_switch(
         &mut self,
         test_place: &PlaceBuilder,
         candidate: &Candidate,
         variants: &mut FixedBitSet,
-    ) -> bool {
+    ) {
         // Find the common matching place between the candidate and this
-        // value, if there is none then we cannot merge these together.
+        // value, if there is none then there is nothing to add.
         let Some(match_pair) = candidate.pairs.iter().find(|pair| pair.place == *test_place) else {
-            return false;
+            return;
         };
 
         // See if the underlying pattern is a variant, and if so add it to
         // the variants...
-        match *match_pair.pat.value() {
-            Pat::Ctor(CtorPat { ctor, .. }) => {
-                variants.insert(ctor.1 as usize);
-                true
-            }
-
-            // We don't know how to map anything else.
-            _ => false,
+        if let Pat::Ctor(CtorPat { ctor, .. }) = *match_pair.pat.value() {
+            variants.insert(ctor.1 as usize);
         }
     }
 
-    /// Try to add any [Candidate]s that are also matching on a particular
-    /// variant.
+    /// Add the value that the [Candidate] tests at the `test_place` to the
+    /// `options` of a [TestKind::SwitchInt], if the candidate tests the place
+    /// with a literal (or a `bool` constructor). Ranges don't add any
+    /// options, they are sorted by whether they contain any of the options.
     pub(super) fn add_cases_to_switch(
         &mut self,
         test_place: &PlaceBuilder,
         candidate: &Candidate,
         options: &mut IndexMap<Const, u128>,
-    ) -> bool {
+    ) {
         let Some(match_pair) = candidate.pairs.iter().find(|mp| mp.place == *test_place) else {
-            return false;
+            return;
         };
 
         match *match_pair.pat.value() {
@@ -847,13 +929,6 @@ impl<'tcx> BodyBuilder<'tcx> {
                     panic!("expected scalar constant in `add_cases_to_switch`")
                 };
                 options.entry(constant).or_insert(scalar.to_bits(scalar.size()).unwrap());
-                true
-            }
-            Pat::Range(ref pat) => {
-                // Check if there is at least one value that is not
-                // contained within the range.
-                let ty = self.ty_id_from_tir_pat(match_pair.pat);
-                self.values_not_contained_in_range(pat, ty, options).unwrap_or(false)
             }
 
             // Boolean type...
@@ -867,12 +942,16 @@ impl<'tcx> BodyBuilder<'tcx> {
                 };
 
                 options.entry(constant).or_insert(value);
-                true
             }
 
             // We either don't know how to map these, or they should of been mapped
             // by `add_variants_to_switch`.
-            Pat::Binding(_) | Pat::Tuple(_) | Pat::Array(_) | Pat::Or(_) | Pat::If(_) => false,
+            Pat::Range(_)
+            | Pat::Binding(_)
+            | Pat::Tuple(_)
+            | Pat::Array(_)
+            | Pat::Or(_)
+            | Pat::If(_) => {}
         }
     }
 
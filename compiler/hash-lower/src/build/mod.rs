@@ -4,6 +4,7 @@
 mod block;
 mod category;
 mod constant;
+mod derive;
 mod into;
 mod matches;
 mod place;
@@ -29,11 +30,14 @@ use hash_tir::{
 };
 use hash_utils::{fxhash::FxHashMap, index_vec::IndexVec};
 
-use crate::{cfg::ControlFlowGraph, ctx::BuilderCtx};
+pub(crate) use self::derive::DerivedItem;
+use self::place::PlaceBuilder;
+use crate::{cfg::ControlFlowGraph, ctx::BuilderCtx, diagnostics::LoweringDiagnostic};
 
 /// A wrapper type for the kind of TIR term that is being lowered, the [Builder]
 /// accepts either a [FnDefId] or a [TermId]. The [TermId] case is used when a
-/// constant block is being lowered.
+/// constant block is being lowered. Additionally, the [Builder] can synthesise
+/// the body of a [DerivedItem] which has no corresponding TIR term.
 #[derive(Clone, Copy)]
 pub(crate) enum BuildItem {
     /// A function body is being lowered.
@@ -41,6 +45,8 @@ pub(crate) enum BuildItem {
     /// An arbitrary expression is being lowered, this is done
     /// for constant expressions.
     Const(TermId),
+    /// An item that is synthesised from a `#derive(...)` attribute.
+    Derived(DerivedItem),
 }
 
 impl BuildItem {
@@ -48,7 +54,7 @@ impl BuildItem {
     /// an expression variant, then this will panic.
     pub fn as_const(&self) -> TermId {
         match self {
-            BuildItem::FnDef(_) => unreachable!(),
+            BuildItem::FnDef(_) | BuildItem::Derived(_) => unreachable!(),
             BuildItem::Const(term) => *term,
         }
     }
@@ -58,7 +64,16 @@ impl BuildItem {
     pub fn as_fn_def(&self) -> FnDefId {
         match self {
             BuildItem::FnDef(fn_def) => *fn_def,
-            BuildItem::Const(_) => unreachable!(),
+            BuildItem::Const(_) | BuildItem::Derived(_) => unreachable!(),
+        }
+    }
+
+    /// Convert the build item into the derived item variant, if this is
+    /// not a derived item variant, then this will panic.
+    pub fn as_derived(&self) -> DerivedItem {
+        match self {
+            BuildItem::Derived(derived) => *derived,
+            BuildItem::FnDef(_) | BuildItem::Const(_) => unreachable!(),
         }
     }
 }
@@ -75,6 +90,12 @@ impl From<TermId> for BuildItem {
     }
 }
 
+impl From<DerivedItem> for BuildItem {
+    fn from(derived: DerivedItem) -> Self {
+        BuildItem::Derived(derived)
+    }
+}
+
 /// A monadic representation of a lowering context. Functions often need to
 /// operate on the current block, potentially package the block, and return
 /// some additional information that is outside of the block.
@@ -128,6 +149,12 @@ pub(crate) struct LoopBlockInfo {
     /// Denotes where the index of the next block that is being used
     /// for `break` statements should jump to...
     next_block: BasicBlock,
+
+    /// The number of drop scopes that were entered when the loop was
+    /// entered. Any scopes that are deeper than this are exited by a
+    /// `break` or `continue`, and so their locals must be dropped before
+    /// jumping out of the loop body.
+    drop_scope_depth: usize,
 }
 
 /// The builder is responsible for lowering a body into the associated IR.
@@ -178,6 +205,22 @@ pub(crate) struct BodyBuilder<'tcx> {
     /// `tmp_place` is [None], then we create a new temporary place and store
     /// it in the field for later use.
     tmp_place: Option<Place>,
+
+    /// The discriminant reads that have been performed whilst lowering the
+    /// decision tree of a `match`. Each entry is only present whilst the
+    /// sub-tree of the test that performed the read is being built, so
+    /// the read always dominates any test that re-uses it.
+    discriminant_reads: Vec<(PlaceBuilder, Place)>,
+
+    /// Whether the builder is currently lowering the contents of an
+    /// `unsafe` block. This is used to annotate any [UnsafeOp]s that
+    /// are recorded in the [BodyMetadata].
+    ///
+    /// [UnsafeOp]: hash_ir::ir::UnsafeOp
+    in_unsafe_block: bool,
+
+    /// Any diagnostics that were emitted whilst lowering the body.
+    diagnostics: Vec<LoweringDiagnostic>,
 }
 
 impl HasTarget for BodyBuilder<'_> {
@@ -202,6 +245,7 @@ impl<'ctx> BodyBuilder<'ctx> {
                 (fn_def.borrow().ty.params.len(), BodySource::Item)
             }
             BuildItem::Const(_) => (0, BodySource::Const),
+            BuildItem::Derived(derived) => (derived.arg_count(), BodySource::Derived),
         };
 
         Self {
@@ -216,9 +260,18 @@ impl<'ctx> BodyBuilder<'ctx> {
             reached_terminator: false,
             loop_block_info: None,
             tmp_place: None,
+            discriminant_reads: Vec::new(),
+            in_unsafe_block: false,
+            diagnostics: Vec::new(),
         }
     }
 
+    /// Take all of the [LoweringDiagnostic]s that were emitted whilst
+    /// lowering the body.
+    pub(crate) fn take_diagnostics(&mut self) -> Vec<LoweringDiagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
     /// Convert the [Builder] into the [Body].
     pub(crate) fn finish(self) -> Body {
         // Verify that all basic blocks have a terminator
@@ -232,6 +285,7 @@ impl<'ctx> BodyBuilder<'ctx> {
         let span = match self.item {
             BuildItem::FnDef(def) => self.span_of_def(def),
             BuildItem::Const(term) => self.span_of_term(term),
+            BuildItem::Derived(derived) => derived.origin,
         };
 
         let mut body = Body::new(
@@ -256,6 +310,7 @@ impl<'ctx> BodyBuilder<'ctx> {
         let ty = match self.item {
             BuildItem::FnDef(fn_def) => self.ty_id_from_tir_fn_def(fn_def),
             BuildItem::Const(item) => self.ty_id_from_tir_term(item),
+            BuildItem::Derived(derived) => derived.ty,
         };
         self.info.set_ty(ty);
 
@@ -276,6 +331,7 @@ impl<'ctx> BodyBuilder<'ctx> {
         match self.item {
             BuildItem::FnDef(_) => self.build_fn(),
             BuildItem::Const(_) => self.build_const(),
+            BuildItem::Derived(_) => self.build_derived(),
         }
     }
 
@@ -321,10 +377,22 @@ impl<'ctx> BodyBuilder<'ctx> {
         let start = self.control_flow_graph.start_new_block();
         debug_assert!(start == START_BLOCK);
 
+        // The arguments of the body are owned by the body, and are dropped
+        // when the body returns.
+        self.control_flow_graph.push_drop_scope();
+
+        for (local, decl) in self.locals.iter_enumerated().skip(1).take(self.arg_count) {
+            if decl.ty.needs_drop() {
+                self.control_flow_graph.schedule_drop(local);
+            }
+        }
+
         // Now that we have built the inner body block, we then need to terminate
         // the current basis block with a return terminator.
         let return_block = unpack!(self.term_into_dest(Place::return_place(), start, body));
         let span = self.span_of_term(body);
+        let return_block = self.control_flow_graph.pop_drop_scope(return_block, span);
+        debug_assert!(self.control_flow_graph.drop_scope_depth() == 0);
 
         self.control_flow_graph.terminate(return_block, span, TerminatorKind::Return);
     }
@@ -1,13 +1,17 @@
 //! Utilities for dealing with [Place]s when building up Hash IR.
 
+use hash_const_eval::op::BinOp;
 use hash_ir::{
-    ir::{BasicBlock, Local, Place, PlaceProjection, Projections},
-    ty::{Mutability, ReprTyId, VariantIdx},
+    ir::{
+        AssertKind, BasicBlock, Local, Place, PlaceProjection, Projections, RValue, Statement,
+        StatementKind, UnsafeOpKind,
+    },
+    ty::{Mutability, RefKind, ReprTy, ReprTyId, VariantIdx, COMMON_REPR_TYS},
 };
 use hash_storage::store::statics::StoreId;
 use hash_tir::tir::{AccessTerm, DerefTerm, IndexTerm, ParamIndex, Term, TermId, Ty};
 
-use super::{unpack, BlockAnd, BlockAndExtend, BodyBuilder};
+use super::{ty::FnCallTermKind, unpack, BlockAnd, BlockAndExtend, BodyBuilder};
 
 /// A builder interface for building a [Place] with a base [Local]
 /// and a collection of projections that are applied as the
@@ -114,8 +118,29 @@ impl<'tcx> BodyBuilder<'tcx> {
             Term::Deref(DerefTerm { subject }) => {
                 let place_builder =
                     unpack!(block = self.as_place_builder(block, subject, mutability));
+
+                // Dereferencing a raw pointer is an unsafe operation.
+                let ty = self.ty_id_from_tir_term(subject);
+                if ty.map(|ty| matches!(ty, ReprTy::Ref(_, _, RefKind::Raw))) {
+                    self.record_unsafe_op(UnsafeOpKind::RawDeref, self.span_of_term(term));
+                }
+
                 block.and(place_builder.deref())
             }
+            Term::Call(call) => {
+                // A read from a raw pointer is just a dereference of the pointer, so
+                // we can represent it as a place.
+                if let FnCallTermKind::PtrRead(ptr) = self.classify_fn_call_term(&call) {
+                    self.record_unsafe_op(UnsafeOpKind::PtrRead, self.span_of_term(term));
+
+                    let place_builder =
+                        unpack!(block = self.as_place_builder(block, ptr, mutability));
+                    return block.and(place_builder.deref());
+                }
+
+                let temp = unpack!(block = self.term_into_temp(block, term, mutability));
+                block.and(PlaceBuilder::from(temp))
+            }
             Term::Index(IndexTerm { subject, index }) => {
                 let mut base_place =
                     unpack!(block = self.as_place_builder(block, subject, mutability));
@@ -133,14 +158,43 @@ impl<'tcx> BodyBuilder<'tcx> {
                     base_place = base_place.deref()
                 }
 
-                // @@Todo: depending on the configuration, we may need to insert a bounds check
-                // here.
+                // When the `checks` sanitiser is enabled, we insert a bounds check
+                // before the index is used.
+                //
+                // @@Todo: bounds checks should also be inserted for normal builds.
+                if self.ctx.settings.sanitize.keeps_checks() {
+                    let origin = self.span_of_term(term);
+                    let subject = base_place.clone().into_place(&mut self.projections);
+
+                    let len = self.temp_place(COMMON_REPR_TYS.usize);
+                    self.control_flow_graph.push_assign(block, len, RValue::Len(subject), origin);
+
+                    let in_bounds = self.temp_place(COMMON_REPR_TYS.bool);
+                    let operands = Box::new((Place::from_local(index).into(), len.into()));
+                    self.control_flow_graph.push_assign(
+                        block,
+                        in_bounds,
+                        RValue::BinaryOp(BinOp::Lt, operands),
+                        origin,
+                    );
+
+                    let kind = AssertKind::BoundsCheck {
+                        len: len.into(),
+                        index: Place::from_local(index).into(),
+                    };
+                    block = self.assert(block, in_bounds.into(), true, kind, origin);
+
+                    // Once the bounds check has passed, the index is known
+                    // to be within the bounds of the subject.
+                    let assume = StatementKind::Assume(in_bounds.into());
+                    self.control_flow_graph.push(block, Statement { kind: assume, origin });
+                }
+
                 block.and(base_place.index(index))
             }
             Term::Tuple(_)
             | Term::Lit(_)
             | Term::Array(_)
-            | Term::Call(_)
             | Term::Ctor(_)
             | Term::Fn(_)
             | Term::Intrinsic(_)
@@ -159,7 +213,8 @@ impl<'tcx> BodyBuilder<'tcx> {
             | Ty::RefTy(_)
             | Ty::Universe(_)
             | Term::Ref(_)
-            | Term::Hole(_) => {
+            | Term::Hole(_)
+            | Term::TypedHole(_) => {
                 // These expressions are not places, so we need to create a temporary
                 // and then deal with it.
                 let temp = unpack!(block = self.term_into_temp(block, term, mutability));
@@ -171,7 +226,7 @@ impl<'tcx> BodyBuilder<'tcx> {
     /// Function to lookup the index of a particular field within a [ReprTyId]
     /// using a [ParamIndex]. This function assumes that the underlying type
     /// is a [ReprTy::Adt].
-    fn lookup_field_index(&mut self, ty: ReprTyId, field: ParamIndex) -> usize {
+    pub(crate) fn lookup_field_index(&mut self, ty: ReprTyId, field: ParamIndex) -> usize {
         ty.borrow().as_adt().map(|adt| {
             // @@Todo: deal with unions here.
             if adt.flags.is_struct() || adt.flags.is_tuple() {
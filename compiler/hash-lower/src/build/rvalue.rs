@@ -8,7 +8,10 @@ use hash_const_eval::{
 };
 use hash_ir::{
     cast::CastKind,
-    ir::{AssertKind, BasicBlock, Const, ConstKind, Operand, RValue, Scalar},
+    ir::{
+        AssertKind, BasicBlock, Const, ConstKind, ConstOp, LocalDecl, Operand, Place, RValue,
+        Scalar, TerminatorKind, UnsafeOpKind,
+    },
     ty::{Mutability, ReprTy, ReprTyId, COMMON_REPR_TYS},
 };
 use hash_source::constant::IntTy;
@@ -45,6 +48,10 @@ impl<'tcx> BodyBuilder<'tcx> {
             ref fn_call_term @ Term::Call(fn_call) => {
                 match self.classify_fn_call_term(&fn_call) {
                     FnCallTermKind::BinaryOp(op, lhs, rhs) => {
+                        if op == BinOp::Offset {
+                            self.record_unsafe_op(UnsafeOpKind::PtrOffset, span);
+                        }
+
                         let lhs = unpack!(block = self.as_operand(block, lhs, Mutability::Mutable));
                         let rhs = unpack!(block = self.as_operand(block, rhs, Mutability::Mutable));
 
@@ -62,7 +69,7 @@ impl<'tcx> BodyBuilder<'tcx> {
                         // the type, and a negation occurs. This causes the value to overflow. We
                         // check for this case here, and emit an assertion check for this (assuming
                         // checked operations are enabled).
-                        if self.ctx.settings.lowering_settings().checked_operations
+                        if self.ctx.settings.checked_operations()
                             && matches!(op, UnOp::Neg)
                             && ty.borrow().is_signed()
                         {
@@ -110,6 +117,37 @@ impl<'tcx> BodyBuilder<'tcx> {
                         let cast_kind = CastKind::classify(source_ty, ty);
                         block.and(RValue::Cast(cast_kind, source, ty))
                     }
+                    FnCallTermKind::VariantCount(ty) => {
+                        let count = ty.borrow().as_adt().borrow().variants.len();
+                        let value = Const::from_scalar_like(
+                            count as u128,
+                            COMMON_REPR_TYS.usize,
+                            &self.ctx,
+                        );
+
+                        block.and(value.into())
+                    }
+                    FnCallTermKind::ConstOp(op, ty) => block.and(self.build_const_op(op, ty)),
+                    FnCallTermKind::DiscriminantValue(subject) => {
+                        let place =
+                            unpack!(block = self.as_place(block, subject, Mutability::Immutable));
+
+                        // Read the discriminant into a temporary, and then cast it into
+                        // a `usize` since the discriminant type depends on the enum.
+                        let subject_ty = self.ty_id_from_tir_term(subject);
+                        let discriminant_ty = subject_ty.borrow().discriminant_ty();
+                        let discriminant = self.temp_place(discriminant_ty);
+
+                        let value = self.discriminant_of(place, subject_ty);
+                        self.control_flow_graph.push_assign(block, discriminant, value, span);
+
+                        let cast_kind = CastKind::classify(discriminant_ty, COMMON_REPR_TYS.usize);
+                        block.and(RValue::Cast(
+                            cast_kind,
+                            discriminant.into(),
+                            COMMON_REPR_TYS.usize,
+                        ))
+                    }
                     _ => as_operand(fn_call_term, self),
                 }
             }
@@ -138,7 +176,8 @@ impl<'tcx> BodyBuilder<'tcx> {
             | Ty::Universe(_)
             | Term::Ref(_)
             | Term::Deref(_)
-            | Term::Hole(_)) => as_operand(term, self),
+            | Term::Hole(_)
+            | Term::TypedHole(_)) => as_operand(term, self),
         }
     }
 
@@ -171,22 +210,30 @@ impl<'tcx> BodyBuilder<'tcx> {
     ) -> BlockAnd<Operand> {
         let term = operand.value();
 
-        // If the item is a reference to a function or intrinsic, i.e. the subject of a
+        // If the item is a reference to a function that is being used as a value,
+        // then we take the address of the function.
+        if let Term::Fn(def_id) = *term {
+            return block.and(self.fn_addr_from_tir_fn_def(def_id).into());
+        }
+
+        // If the item is a reference to an intrinsic, i.e. the subject of a
         // call, create a function type for the operand.
-        let ty_id = if let Term::Fn(def_id) = *term {
-            Some(self.ty_id_from_tir_fn_def(def_id))
-        } else if let Term::Intrinsic(intrinsic) = *term {
-            Some(self.ty_id_from_tir_intrinsic(intrinsic, operand.node_id_or_default()))
-        } else {
-            None
-        };
+        if let Term::Intrinsic(intrinsic) = *term {
+            let ty_id = self.ty_id_from_tir_intrinsic(intrinsic, operand.node_id_or_default());
+
+            // If this is indeed a function type, we emit a ZST to represent the operand
+            // of the function.
+            if ty_id.map(|ty| matches!(ty, ReprTy::FnDef { .. })) {
+                return block.and(Operand::Const(Const::zst(ty_id)));
+            }
+        }
 
-        // If this is indeed a function type, we emit a ZST to represent the operand
-        // of the function.
-        if let Some(ty_id) = ty_id
-            && ty_id.map(|ty| matches!(ty, ReprTy::FnDef { .. }))
+        // Projections on constants can be folded directly into a new constant,
+        // which avoids materialising the constant into a temporary.
+        if matches!(*term, Term::Access(_) | Term::Index(_))
+            && let Some(constant) = self.fold_const_projection(operand)
         {
-            return block.and(Operand::Const(Const::zst(ty_id)));
+            return block.and(constant.into());
         }
 
         match Category::of(&term) {
@@ -227,11 +274,17 @@ impl<'tcx> BodyBuilder<'tcx> {
             }
         }
 
+        // Integer exponentiation has no equivalent instruction, so it is
+        // computed with a loop instead.
+        if op == BinOp::Exp && ty.borrow().is_integral() {
+            return self.build_int_exp(block, ty, origin, lhs, rhs);
+        }
+
         let operands = Box::new((lhs, rhs));
 
         // If we need have been instructed to insert overflow checks, and the
         // operator is checkable, then use `CheckedBinaryOp` instead of `BinaryOp`.
-        if self.ctx.settings.lowering_settings().checked_operations {
+        if self.ctx.settings.checked_operations() {
             let is_integral = ty.borrow().is_integral();
 
             if op.is_checkable() && is_integral {
@@ -247,14 +300,14 @@ impl<'tcx> BodyBuilder<'tcx> {
                 // Push an assignment to the tuple on the operation
                 self.control_flow_graph.push_assign(block, temp, rvalue, origin);
 
-                block = self.assert(
-                    block,
-                    Operand::Place(overflow),
-                    false,
-                    AssertKind::Overflow { op, lhs, rhs },
-                    origin,
-                );
+                // Shifts can only "overflow" by shifting by too much.
+                let kind = if matches!(op, BinOp::Shl | BinOp::Shr) {
+                    AssertKind::ShiftAmountTooLarge { op, lhs, amount: rhs }
+                } else {
+                    AssertKind::Overflow { op, lhs, rhs }
+                };
 
+                block = self.assert(block, Operand::Place(overflow), false, kind, origin);
                 return block.and(result.into());
             } else if is_integral && (op == BinOp::Div || op == BinOp::Mod) {
                 // Check for division or a remainder by zero, and if so emit
@@ -338,4 +391,186 @@ impl<'tcx> BodyBuilder<'tcx> {
 
         block.and(RValue::BinaryOp(op, operands))
     }
+
+    /// Build a [ConstOp] on the given type. If the layout of the type can be
+    /// computed, then the operation is evaluated into a constant, otherwise
+    /// the [`RValue::ConstOp`] is kept so that it is evaluated once the
+    /// layout is known.
+    pub(crate) fn build_const_op(&self, op: ConstOp, ty: ReprTyId) -> RValue {
+        let Ok(layout) = self.ctx.layout_of(ty) else {
+            return RValue::ConstOp(op, ty);
+        };
+
+        let value = layout.map(|layout| match op {
+            ConstOp::SizeOf => layout.size.bytes(),
+            ConstOp::AlignOf => layout.alignment.abi.bytes(),
+        });
+
+        Const::from_scalar_like(value as u128, COMMON_REPR_TYS.usize, &self.ctx).into()
+    }
+
+    /// Build the exponentiation `lhs ^^ rhs` of two integers. There is no
+    /// instruction for this, so the result is computed by repeated squaring:
+    /// ```ignore
+    /// result := 1; base := lhs; exponent := rhs;
+    ///
+    /// while exponent > 0 {
+    ///     if exponent & 1 != 0 { result = result * base; }
+    ///     exponent = exponent >> 1;
+    ///     if exponent > 0 { base = base * base; }
+    /// }
+    /// ```
+    ///
+    /// The semantics of the operation when it overflows, or when the exponent
+    /// is negative, are defined within [`hash_target::overflow`].
+    fn build_int_exp(
+        &mut self,
+        mut block: BasicBlock,
+        ty: ReprTyId,
+        origin: AstNodeId,
+        lhs: Operand,
+        rhs: Operand,
+    ) -> BlockAnd<RValue> {
+        let zero = Operand::Const(Const::from_scalar_like(0, ty, &self.ctx));
+        let one = Operand::Const(Const::from_scalar_like(1, ty, &self.ctx));
+
+        if self.ctx.settings.checked_operations() && ty.borrow().is_signed() {
+            let is_negative = self.temp_place(COMMON_REPR_TYS.bool);
+
+            self.control_flow_graph.push_assign(
+                block,
+                is_negative,
+                RValue::BinaryOp(BinOp::Lt, Box::new((rhs, zero))),
+                origin,
+            );
+
+            block = self.assert(
+                block,
+                Operand::Place(is_negative),
+                false,
+                AssertKind::NegativeExponent { base: lhs, exponent: rhs },
+                origin,
+            );
+        }
+
+        let mutable_temp = |this: &mut Self, value: Operand| {
+            let place = Place::from_local(
+                this.locals.push(LocalDecl::new_auxiliary(ty, Mutability::Mutable)),
+            );
+
+            this.control_flow_graph.push_assign(block, place, RValue::Use(value), origin);
+            place
+        };
+
+        let result = mutable_temp(self, one);
+        let base = mutable_temp(self, lhs);
+        let exponent = mutable_temp(self, rhs);
+
+        let head = self.control_flow_graph.start_new_block();
+        let step = self.control_flow_graph.start_new_block();
+        let multiply = self.control_flow_graph.start_new_block();
+        let shift = self.control_flow_graph.start_new_block();
+        let square = self.control_flow_graph.start_new_block();
+        let done = self.control_flow_graph.start_new_block();
+
+        self.control_flow_graph.goto(block, head, origin);
+
+        // while exponent > 0
+        let is_positive = self.temp_place(COMMON_REPR_TYS.bool);
+        self.control_flow_graph.push_assign(
+            head,
+            is_positive,
+            RValue::BinaryOp(BinOp::Gt, Box::new((exponent.into(), zero))),
+            origin,
+        );
+        self.control_flow_graph.terminate(
+            head,
+            origin,
+            TerminatorKind::make_if(is_positive.into(), step, done),
+        );
+
+        // if exponent & 1 != 0
+        let bit = self.temp_place(ty);
+        let is_odd = self.temp_place(COMMON_REPR_TYS.bool);
+        self.control_flow_graph.push_assign(
+            step,
+            bit,
+            RValue::BinaryOp(BinOp::BitAnd, Box::new((exponent.into(), one))),
+            origin,
+        );
+        self.control_flow_graph.push_assign(
+            step,
+            is_odd,
+            RValue::BinaryOp(BinOp::Neq, Box::new((bit.into(), zero))),
+            origin,
+        );
+        self.control_flow_graph.terminate(
+            step,
+            origin,
+            TerminatorKind::make_if(is_odd.into(), multiply, shift),
+        );
+
+        // result = result * base
+        let kind = AssertKind::Overflow { op: BinOp::Exp, lhs, rhs };
+        let multiply = self.build_int_exp_mul(multiply, ty, origin, result, base, kind);
+        self.control_flow_graph.goto(multiply, shift, origin);
+
+        // exponent = exponent >> 1, and if exponent > 0
+        let has_more = self.temp_place(COMMON_REPR_TYS.bool);
+        self.control_flow_graph.push_assign(
+            shift,
+            exponent,
+            RValue::BinaryOp(BinOp::Shr, Box::new((exponent.into(), one))),
+            origin,
+        );
+        self.control_flow_graph.push_assign(
+            shift,
+            has_more,
+            RValue::BinaryOp(BinOp::Gt, Box::new((exponent.into(), zero))),
+            origin,
+        );
+        self.control_flow_graph.terminate(
+            shift,
+            origin,
+            TerminatorKind::make_if(has_more.into(), square, done),
+        );
+
+        // base = base * base
+        let square = self.build_int_exp_mul(square, ty, origin, base, base, kind);
+        self.control_flow_graph.goto(square, head, origin);
+
+        done.and(RValue::Use(result.into()))
+    }
+
+    /// Build `target = target * factor` as a step of an integer
+    /// exponentiation. If operations are checked, then the multiplication
+    /// is checked for overflow, which is reported as the given [AssertKind].
+    fn build_int_exp_mul(
+        &mut self,
+        block: BasicBlock,
+        ty: ReprTyId,
+        origin: AstNodeId,
+        target: Place,
+        factor: Place,
+        kind: AssertKind,
+    ) -> BasicBlock {
+        let operands = Box::new((target.into(), factor.into()));
+
+        if !self.ctx.settings.checked_operations() {
+            let rvalue = RValue::BinaryOp(BinOp::Mul, operands);
+            self.control_flow_graph.push_assign(block, target, rvalue, origin);
+            return block;
+        }
+
+        let temp = self.temp_place(ReprTy::make_tuple(&[ty, COMMON_REPR_TYS.bool]));
+        let rvalue = RValue::CheckedBinaryOp(BinOp::Mul, operands);
+        self.control_flow_graph.push_assign(block, temp, rvalue, origin);
+
+        let result = temp.field(0, &mut self.projections);
+        let overflow = temp.field(1, &mut self.projections);
+
+        let block = self.assert(block, Operand::Place(overflow), false, kind, origin);
+        self.control_flow_graph.push_assign(block, target, RValue::Use(result.into()), origin);
+        block
+    }
 }
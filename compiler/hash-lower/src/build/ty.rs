@@ -6,12 +6,13 @@
 //! types into the [ReprTy] which is then used for the lowering process.
 
 use hash_ast::ast::AstNodeId;
+use hash_attrs::attr::DeriveAttr;
 use hash_const_eval::{
     op::{BinOp, LogicalBinOp, UnOp},
     Const, ConstKind,
 };
 use hash_ir::{
-    ir::Scalar,
+    ir::{ConstOp, Scalar},
     ty::{ReprTy, ReprTyId},
 };
 use hash_storage::store::{statics::StoreId, TrivialSequenceStoreKey};
@@ -19,7 +20,7 @@ use hash_target::{size::Size, HasTarget};
 use hash_tir::{
     atom_info::ItemInAtomInfo,
     intrinsics::{definitions::Intrinsic as TirIntrinsic, utils::try_use_term_as_integer_lit},
-    tir::{CallTerm, DataTy, FnDefId, LitPat, PatId, Term, TermId, TyId},
+    tir::{ArgsId, CallTerm, DataTy, FnDefId, LitPat, PatId, Term, TermId, TyId},
 };
 
 use super::BodyBuilder;
@@ -50,6 +51,33 @@ pub enum FnCallTermKind {
     /// An "unary" operation, the term should be lowered into the equivalent
     /// unary operation.
     UnaryOp(UnOp, TermId),
+
+    /// A read from a raw pointer, the term should be lowered into a
+    /// dereference of the pointer, i.e. `(*ptr)`.
+    PtrRead(TermId),
+
+    /// A write to a raw pointer, the term should be lowered into an
+    /// assignment to the dereferenced pointer, i.e. `(*ptr) = value`.
+    PtrWrite(TermId, TermId),
+
+    /// The number of variants of the given enum type, this is lowered
+    /// into a constant.
+    VariantCount(ReprTyId),
+
+    /// The size or alignment of the given type, this is lowered into a
+    /// constant if the layout of the type is known.
+    ConstOp(ConstOp, ReprTyId),
+
+    /// Read the discriminant of the given term, which is an enum value.
+    DiscriminantValue(TermId),
+
+    /// Convert the given term into the variant of the given enum type which
+    /// has the term as its discriminant, this yields an `Option` of the enum.
+    EnumFromDiscriminant(ReprTyId, TermId),
+
+    /// A call to an item that is derived for the given type, the arguments
+    /// are passed by reference to the derived item.
+    Derived(DeriveAttr, ReprTyId, ArgsId),
 }
 
 impl<'tcx> BodyBuilder<'tcx> {
@@ -93,6 +121,13 @@ impl<'tcx> BodyBuilder<'tcx> {
         self.ctx.repr_ty_from_tir_fn_def(fn_def)
     }
 
+    /// Create a function pointer constant that refers to the address of
+    /// the given [FnDefId].
+    pub(super) fn fn_addr_from_tir_fn_def(&mut self, fn_def: FnDefId) -> Const {
+        let instance = self.ty_id_from_tir_fn_def(fn_def).borrow().as_instance();
+        Const::fn_addr(instance)
+    }
+
     /// Function which is used to classify a [FnCallTerm] into a
     /// [FnCallTermKind].
     pub(crate) fn classify_fn_call_term(&self, term: &CallTerm) -> FnCallTermKind {
@@ -167,10 +202,68 @@ impl<'tcx> BodyBuilder<'tcx> {
 
                         FnCallTermKind::UnaryOp(parsed_op, subject)
                     }
-                    TirIntrinsic::SizeOf
-                    | TirIntrinsic::AlignOf
-                    | TirIntrinsic::PtrOffset
-                    | TirIntrinsic::Transmute
+                    TirIntrinsic::PtrOffset => {
+                        let (ptr, offset) = (
+                            args.at(0).unwrap().borrow().value,
+                            args.at(1).unwrap().borrow().value,
+                        );
+
+                        FnCallTermKind::BinaryOp(BinOp::Offset, ptr, offset)
+                    }
+                    TirIntrinsic::Read => {
+                        let ptr = args.at(1).unwrap().borrow().value;
+                        FnCallTermKind::PtrRead(ptr)
+                    }
+                    TirIntrinsic::Write => {
+                        let (ptr, value) = (
+                            args.at(1).unwrap().borrow().value,
+                            args.at(2).unwrap().borrow().value,
+                        );
+
+                        FnCallTermKind::PtrWrite(ptr, value)
+                    }
+                    TirIntrinsic::VariantCount => {
+                        let ty = args.at(0).unwrap().borrow().value;
+                        FnCallTermKind::VariantCount(self.ty_id_from_tir_ty(ty))
+                    }
+                    TirIntrinsic::DiscriminantValue => {
+                        let value = args.at(1).unwrap().borrow().value;
+                        FnCallTermKind::DiscriminantValue(value)
+                    }
+                    TirIntrinsic::EnumFromDiscriminant => {
+                        let (ty, value) = (
+                            args.at(0).unwrap().borrow().value,
+                            args.at(1).unwrap().borrow().value,
+                        );
+
+                        FnCallTermKind::EnumFromDiscriminant(self.ty_id_from_tir_ty(ty), value)
+                    }
+                    TirIntrinsic::SizeOf | TirIntrinsic::AlignOf => {
+                        let op = match intrinsic {
+                            TirIntrinsic::SizeOf => ConstOp::SizeOf,
+                            _ => ConstOp::AlignOf,
+                        };
+
+                        let ty = args.at(0).unwrap().borrow().value;
+                        FnCallTermKind::ConstOp(op, self.ty_id_from_tir_ty(ty))
+                    }
+                    TirIntrinsic::DerivedEq
+                    | TirIntrinsic::DerivedHash
+                    | TirIntrinsic::DerivedDebug
+                    | TirIntrinsic::DerivedSerialize
+                    | TirIntrinsic::DerivedDeserialize => {
+                        let item = match intrinsic {
+                            TirIntrinsic::DerivedEq => DeriveAttr::Eq,
+                            TirIntrinsic::DerivedHash => DeriveAttr::Hash,
+                            TirIntrinsic::DerivedDebug => DeriveAttr::Debug,
+                            TirIntrinsic::DerivedSerialize => DeriveAttr::Serialize,
+                            _ => DeriveAttr::Deserialize,
+                        };
+
+                        let ty = args.at(0).unwrap().borrow().value;
+                        FnCallTermKind::Derived(item, self.ty_id_from_tir_ty(ty), *args)
+                    }
+                    TirIntrinsic::Transmute
                     | TirIntrinsic::Memcmp
                     | TirIntrinsic::Memcpy
                     | TirIntrinsic::Abort
@@ -178,6 +271,9 @@ impl<'tcx> BodyBuilder<'tcx> {
                     TirIntrinsic::Eval | TirIntrinsic::UserError | TirIntrinsic::DebugPrint => {
                         panic!("Found unexpected intrinsic {} which should have been evaluated during TC", intrinsic)
                     }
+                    TirIntrinsic::ForItems | TirIntrinsic::ForRange => {
+                        panic!("Found unexpected intrinsic {} outside of a `for` loop", intrinsic)
+                    }
                 }
             }
             _ => FnCallTermKind::Call,
@@ -232,7 +328,7 @@ impl<'tcx> BodyBuilder<'tcx> {
                     let size = ty.size(ptr_size);
 
                     let value = if at_end { size.unsigned_int_max() } else { 0 };
-                    Scalar::from_uint(value, ptr_size)
+                    Scalar::from_uint(value, size)
                 }
                 _ => unreachable!(),
             }),
@@ -8,12 +8,13 @@ use hash_const_eval::Const;
 use hash_ir::{
     ir::{
         AggregateKind, AssertKind, BasicBlock, Local, LocalDecl, Operand, Place, RValue,
-        TerminatorKind,
+        TerminatorKind, UnsafeOp, UnsafeOpKind,
     },
     lang_items::LangItem,
     ty::{Mutability, ReprTyId, COMMON_REPR_TYS},
     IrCtx,
 };
+use hash_repr::{DiscriminantReadInfo, LayoutId};
 use hash_storage::store::statics::{SequenceStoreValue, StoreId};
 use hash_tir::tir::{
     Arg, DataTy, FnDefId, HasAstNodeId, ModMember, ModMemberValue, Node, NodeId, PatId, SymbolId,
@@ -21,6 +22,7 @@ use hash_tir::tir::{
 };
 
 use super::BodyBuilder;
+use crate::diagnostics::LoweringDiagnostic;
 
 impl<'tcx> BodyBuilder<'tcx> {
     /// Get a reference to a [IrCtx].
@@ -104,6 +106,41 @@ impl<'tcx> BodyBuilder<'tcx> {
         RValue::Aggregate(AggregateKind::Struct(adt), vec![ptr, metadata])
     }
 
+    /// Compute the layout of the given type. If the layout cannot be computed,
+    /// then a diagnostic is emitted for the body that is being lowered, and
+    /// `None` is returned so that the caller can continue lowering the body.
+    pub(crate) fn layout_of(&mut self, ty: ReprTyId, origin: AstNodeId) -> Option<LayoutId> {
+        match self.ctx.layout_of(ty) {
+            Ok(layout) => Some(layout),
+            Err(error) => {
+                self.diagnostics.push(LoweringDiagnostic::LayoutComputation { ty, error, origin });
+                None
+            }
+        }
+    }
+
+    /// Compute the size of the given type in bytes, if the layout of the type
+    /// cannot be computed then a diagnostic is emitted, see
+    /// [`Self::layout_of`].
+    pub(crate) fn size_of(&mut self, ty: ReprTyId, origin: AstNodeId) -> Option<usize> {
+        let layout = self.layout_of(ty, origin)?;
+        Some(layout.size().bytes().try_into().unwrap())
+    }
+
+    /// Create an [RValue] which reads the discriminant of the given `place`
+    /// of type `ty`. If the [DiscriminantReadInfo] of the type specifies that
+    /// the discriminant is a known constant, then the constant is used rather
+    /// than reading the discriminant from the value.
+    pub(crate) fn discriminant_of(&self, place: Place, ty: ReprTyId) -> RValue {
+        match self.ctx.layout_computer().discriminant_read_info(ty) {
+            Ok(DiscriminantReadInfo::Constant { value }) => {
+                let discriminant_ty = ty.borrow().discriminant_ty();
+                Const::from_scalar_like(value, discriminant_ty, &self.ctx).into()
+            }
+            _ => RValue::Discriminant(place),
+        }
+    }
+
     /// Function to create a new [Place] that is used to ignore
     /// the results of expressions, i.e. blocks.
     pub(crate) fn make_tmp_unit(&mut self) -> Place {
@@ -120,6 +157,14 @@ impl<'tcx> BodyBuilder<'tcx> {
         }
     }
 
+    /// Record that an [UnsafeOp] of the given kind was lowered at the
+    /// specified `origin`. Whether the operation is allowed to occur here
+    /// has already been checked by the typechecker.
+    pub(crate) fn record_unsafe_op(&mut self, kind: UnsafeOpKind, origin: AstNodeId) {
+        let op = UnsafeOp { kind, in_unsafe_block: self.in_unsafe_block, origin };
+        self.info.unsafe_ops.push(op);
+    }
+
     /// Create an assertion on a particular block
     pub(crate) fn assert(
         &mut self,
@@ -130,6 +175,7 @@ impl<'tcx> BodyBuilder<'tcx> {
         origin: AstNodeId,
     ) -> BasicBlock {
         let success_block = self.control_flow_graph.start_new_block();
+        let unwind = self.control_flow_graph.unwind_block(origin);
 
         self.control_flow_graph.terminate(
             block,
@@ -139,6 +185,7 @@ impl<'tcx> BodyBuilder<'tcx> {
                 expected,
                 kind: Box::new(kind),
                 target: success_block,
+                unwind,
             },
         );
 
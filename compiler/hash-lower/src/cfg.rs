@@ -5,13 +5,36 @@ use std::fmt;
 
 use hash_ast::ast::AstNodeId;
 use hash_ir::ir::{
-    BasicBlock, BasicBlockData, Place, RValue, Statement, StatementKind, Terminator, TerminatorKind,
+    BasicBlock, BasicBlockData, Local, Place, RValue, Statement, StatementKind, Terminator,
+    TerminatorKind,
 };
 use hash_utils::index_vec::IndexVec;
 
+/// A [DropScope] records all of the locals that were declared within a
+/// particular lexical scope, and require to be dropped when the scope is
+/// exited.
+#[derive(Debug, Default)]
+struct DropScope {
+    /// The locals that should be dropped when the scope is exited, in the
+    /// order that they were declared. The locals are dropped in the reverse
+    /// order.
+    locals: Vec<Local>,
+
+    /// The cleanup block that drops all of the locals of this scope when
+    /// unwinding, and then continues to the cleanup of the enclosing
+    /// scope. This is reset whenever a new drop is scheduled in this scope,
+    /// since the cleanup would then be missing the local.
+    cached_unwind: Option<BasicBlock>,
+}
+
 pub struct ControlFlowGraph {
     /// The basic blocks that this control flow graph contains.
     pub(crate) basic_blocks: IndexVec<BasicBlock, BasicBlockData>,
+
+    /// The stack of scopes that are currently entered whilst building the
+    /// control flow graph. Each scope records the locals that need to be
+    /// dropped when control flow leaves the scope.
+    drop_scopes: Vec<DropScope>,
 }
 
 impl fmt::Debug for ControlFlowGraph {
@@ -33,7 +56,7 @@ impl fmt::Debug for ControlFlowGraph {
 impl ControlFlowGraph {
     /// Create a new empty control-flow graph
     pub fn new() -> Self {
-        Self { basic_blocks: IndexVec::new() }
+        Self { basic_blocks: IndexVec::new(), drop_scopes: Vec::new() }
     }
 
     /// Get a reference to a [BasicBlock] inner [BasicBlockData].
@@ -98,4 +121,138 @@ impl ControlFlowGraph {
     pub(crate) fn goto(&mut self, source: BasicBlock, target: BasicBlock, origin: AstNodeId) {
         self.terminate(source, origin, TerminatorKind::Goto(target));
     }
+
+    /// Terminate a [BasicBlock] by adding a [TerminatorKind::Drop] of the
+    /// given [Place], and return the block that the drop continues to.
+    pub(crate) fn drop(
+        &mut self,
+        block: BasicBlock,
+        place: Place,
+        origin: AstNodeId,
+    ) -> BasicBlock {
+        let target = self.start_new_block();
+        self.terminate(block, origin, TerminatorKind::Drop { place, target });
+        target
+    }
+
+    /// Enter a new scope, any drops that are scheduled after this point
+    /// are performed when the scope is popped via [Self::pop_drop_scope].
+    pub(crate) fn push_drop_scope(&mut self) {
+        self.drop_scopes.push(DropScope::default());
+    }
+
+    /// Get the number of scopes that are currently entered. This is used
+    /// to record the depth of a scope that may be exited early, i.e. the
+    /// scope of a loop when lowering a `break` or `continue`.
+    pub(crate) fn drop_scope_depth(&self) -> usize {
+        self.drop_scopes.len()
+    }
+
+    /// Schedule the given [Local] to be dropped when the innermost scope
+    /// is exited. If no scope has been entered, the local is never dropped,
+    /// this is the case for constant bodies.
+    pub(crate) fn schedule_drop(&mut self, local: Local) {
+        if let Some(scope) = self.drop_scopes.last_mut() {
+            scope.locals.push(local);
+            scope.cached_unwind = None;
+        }
+    }
+
+    /// Exit the innermost scope, and drop all of the locals that were
+    /// scheduled within it. The returned [BasicBlock] is the block that
+    /// control flow continues in after all of the drops. If `block` has
+    /// already been terminated, i.e. the scope was exited early, then no
+    /// drops are emitted since they have already been performed on the
+    /// path that exited the scope.
+    pub(crate) fn pop_drop_scope(
+        &mut self,
+        mut block: BasicBlock,
+        origin: AstNodeId,
+    ) -> BasicBlock {
+        let scope = self.drop_scopes.pop().expect("no drop scope to pop");
+
+        if self.is_terminated(block) {
+            return block;
+        }
+
+        for local in scope.locals.into_iter().rev() {
+            block = self.drop(block, Place::from_local(local), origin);
+        }
+
+        block
+    }
+
+    /// Exit the innermost scope without dropping any of its locals. This is
+    /// used when the scope has been exited early, and so the drops have
+    /// already been emitted via [Self::drop_scopes_until].
+    pub(crate) fn exit_drop_scope(&mut self) {
+        self.drop_scopes.pop().expect("no drop scope to pop");
+    }
+
+    /// Build the cleanup path that is taken if the program panics at the
+    /// current point, the cleanup path drops all of the locals that have
+    /// been scheduled so far, and then resumes unwinding. If no locals need
+    /// to be dropped, then no cleanup path is needed and [None] is returned.
+    ///
+    /// Each scope has a single cleanup block which is re-used by all of the
+    /// unwind edges within the scope, until another drop is scheduled in it.
+    pub(crate) fn unwind_block(&mut self, origin: AstNodeId) -> Option<BasicBlock> {
+        let mut target = None;
+
+        for index in 0..self.drop_scopes.len() {
+            let scope = &self.drop_scopes[index];
+
+            // An empty scope continues directly to the cleanup of the
+            // enclosing scope.
+            if scope.locals.is_empty() {
+                continue;
+            }
+
+            if let Some(cached) = scope.cached_unwind {
+                target = Some(cached);
+                continue;
+            }
+
+            let locals = scope.locals.clone();
+            let cleanup = self.start_new_block();
+            let mut block = cleanup;
+
+            for local in locals.into_iter().rev() {
+                block = self.drop(block, Place::from_local(local), origin);
+            }
+
+            match target {
+                Some(outer) => self.goto(block, outer, origin),
+                None => self.terminate(block, origin, TerminatorKind::Resume),
+            }
+
+            self.drop_scopes[index].cached_unwind = Some(cleanup);
+            target = Some(cleanup);
+        }
+
+        target
+    }
+
+    /// Emit drops for all of the scopes that are deeper than `depth`
+    /// without exiting them. This is used when lowering early exits from
+    /// a scope, for example `return` exits all of the scopes in the body,
+    /// and `break` exits all of the scopes within the loop body.
+    pub(crate) fn drop_scopes_until(
+        &mut self,
+        depth: usize,
+        mut block: BasicBlock,
+        origin: AstNodeId,
+    ) -> BasicBlock {
+        let locals = self.drop_scopes[depth..]
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.locals.iter().rev().copied())
+            .collect::<Vec<_>>();
+
+        for local in locals {
+            block = self.drop(block, Place::from_local(local), origin);
+        }
+
+        block
+    }
 }
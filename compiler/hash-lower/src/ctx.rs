@@ -4,13 +4,22 @@
 use std::io::Write;
 
 use hash_ir::{ty::ReprTyId, HasIrCtx, IrCtx};
-use hash_pipeline::{interface::CompilerOutputStream, settings::CompilerSettings};
+use hash_pipeline::{
+    interface::CompilerOutputStream,
+    settings::{CompilerSettings, LayoutDumpMode},
+};
 use hash_repr::{
     compute::{LayoutComputer, LayoutError},
-    write::{LayoutWriter, LayoutWriterConfig},
+    write::{
+        LayoutDiagramWriter, LayoutJsonWriter, LayoutLlvmWriter, LayoutTableWriter, LayoutWriter,
+        LayoutWriterConfig,
+    },
     LayoutId, LayoutStorage, TyInfo,
 };
-use hash_storage::store::statics::SequenceStoreValue;
+use hash_storage::store::{
+    statics::{SequenceStoreValue, StoreId},
+    SequenceStoreKey,
+};
 use hash_target::{HasTarget, Target};
 use hash_tir::{
     atom_info::{AtomInfoStore, HasAtomInfo},
@@ -18,8 +27,8 @@ use hash_tir::{
     stores::tir_stores,
     tir::{Arg, DataDefId, DataTy, ModDefId, Node, NodeId},
 };
-use hash_tir_utils::lower::{HasTyCache, TyCache, TyLowerEnv};
-use hash_utils::stream_writeln;
+use hash_tir_utils::lower::{HasTyCache, TyCache, TyCacheEntry, TyLowerEnv};
+use hash_utils::{itertools::Itertools, stream_writeln};
 
 use crate::LoweringCtx;
 
@@ -118,30 +127,72 @@ impl<'ir> BuilderCtx<'ir> {
         self.layout_computer().layout_of_ty(ty)
     }
 
-    /// Compute the size of a given [ReprTyId].
-    pub fn size_of(&self, ty: ReprTyId) -> Result<usize, LayoutError> {
-        Ok(self.layout_of(ty)?.size().bytes().try_into().unwrap())
+    /// Dump the layout of a given data definition. If the data definition is
+    /// generic, then the layout of each instantiation of the definition that
+    /// was created whilst lowering is dumped instead.
+    pub(crate) fn dump_ty_layout(&self, data_def: DataDefId, mut out: CompilerOutputStream) {
+        if data_def.borrow().params.len() == 0 {
+            let ty = self.repr_ty_from_tir_data_ty(DataTy {
+                args: Node::create_at(Node::<Arg>::empty_seq(), data_def.origin()),
+                data_def,
+            });
+
+            return self.write_ty_layout(ty, out);
+        }
+
+        // The same instantiation may have been lowered multiple times with
+        // different argument nodes, so instantiations are de-duplicated by
+        // their applied type arguments.
+        let instantiations = self
+            .ty_cache
+            .borrow()
+            .iter()
+            .filter_map(|(entry, ty)| match entry {
+                TyCacheEntry::Data(data_ty) if data_ty.data_def == data_def => {
+                    Some((ty.to_string(), *ty))
+                }
+                _ => None,
+            })
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .dedup_by(|(a, _), (b, _)| a == b)
+            .collect_vec();
+
+        if instantiations.is_empty() {
+            let name = data_def.borrow().name;
+            stream_writeln!(out, "No instantiations of `{name}` were found whilst lowering");
+            return;
+        }
+
+        for (_, ty) in instantiations {
+            self.write_ty_layout(ty, out.clone());
+        }
     }
 
-    /// Dump the layout of a given type.
-    pub(crate) fn dump_ty_layout(&self, data_def: DataDefId, mut out: CompilerOutputStream) {
-        let ty = self.repr_ty_from_tir_data_ty(DataTy {
-            args: Node::create_at(Node::<Arg>::empty_seq(), data_def.origin()),
-            data_def,
-        });
-        let layout = self.layout_of(ty).unwrap();
+    /// Write the layout of the given type. If the layout of the type cannot be
+    /// computed, then a message is written instead so that the layouts of
+    /// the remaining types can still be dumped.
+    fn write_ty_layout(&self, ty: ReprTyId, mut out: CompilerOutputStream) {
+        let Ok(layout) = self.layout_of(ty) else {
+            stream_writeln!(out, "Cannot compute the layout of `{ty}`");
+            return;
+        };
 
-        let writer_config = LayoutWriterConfig::from_character_set(self.settings.character_set);
+        let info = TyInfo { ty, layout };
+        let lc = self.layout_computer();
 
         // Print the layout
-        stream_writeln!(
-            out,
-            "{}",
-            LayoutWriter::new_with_config(
-                TyInfo { ty, layout },
-                self.layout_computer(),
-                writer_config
-            )
-        );
+        match self.settings.lowering_settings.layout_dump_mode {
+            LayoutDumpMode::Pretty => {
+                let config = LayoutWriterConfig::from_character_set(self.settings.character_set);
+                stream_writeln!(out, "{}", LayoutWriter::new_with_config(info, lc, config));
+            }
+            LayoutDumpMode::Json => stream_writeln!(out, "{}", LayoutJsonWriter::new(info, lc)),
+            LayoutDumpMode::Table => stream_writeln!(out, "{}", LayoutTableWriter::new(info, lc)),
+            LayoutDumpMode::Diagram => {
+                let config = LayoutWriterConfig::from_character_set(self.settings.character_set);
+                stream_writeln!(out, "{}", LayoutDiagramWriter::new_with_config(info, lc, config));
+            }
+            LayoutDumpMode::Llvm => stream_writeln!(out, "{}", LayoutLlvmWriter::new(info, lc)),
+        }
     }
 }
@@ -0,0 +1,202 @@
+//! Diagnostics that can be emitted whilst lowering TIR into Hash IR.
+
+use hash_ast::ast::AstNodeId;
+use hash_attrs::attr::DeriveAttr;
+use hash_ir::ty::{Mutability, ReprTyId};
+use hash_reporting::{
+    hash_error_codes::{error_codes::HashErrorCode, warning_codes::HashWarningCode},
+    report::{ReportCodeBlock, ReportElement, ReportNote, ReportNoteKind},
+    reporter::{Reporter, Reports},
+};
+use hash_repr::compute::LayoutError;
+use hash_source::identifier::Identifier;
+
+/// A diagnostic that is emitted by the lowering stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoweringDiagnostic {
+    /// A `#derive(...)` item could not be synthesised for a type since
+    /// one of its fields does not support the derived item.
+    UnsupportedDerivedField { item: DeriveAttr, subject: ReprTyId, ty: ReprTyId, origin: AstNodeId },
+
+    /// A `for` loop iterates over a value which isn't an array or a slice,
+    /// or over a range whose bounds aren't integers.
+    UnsupportedForLoopIterable { ty: ReprTyId, is_range: bool, origin: AstNodeId },
+
+    /// A place is borrowed whilst an overlapping borrow of the place is
+    /// still in use, and at least one of the borrows is mutable.
+    ConflictingBorrow {
+        mutability: Mutability,
+        previous_mutability: Mutability,
+        origin: AstNodeId,
+        previous: AstNodeId,
+    },
+
+    /// A variable is read on some path before it has been initialised.
+    UninitialisedVariable { name: Identifier, origin: AstNodeId },
+
+    /// The layout of a type that is required whilst lowering a body could
+    /// not be computed.
+    LayoutComputation { ty: ReprTyId, error: LayoutError, origin: AstNodeId },
+
+    /// An `assert` whose condition is known to always fail, i.e. the
+    /// operation will always panic at runtime.
+    FailingAssertion { message: &'static str, origin: AstNodeId },
+}
+
+impl LoweringDiagnostic {
+    /// Get the associated [AstNodeId] with the [LoweringDiagnostic], this
+    /// is used to sort the order that diagnostics are emitted.
+    pub(crate) fn id(&self) -> AstNodeId {
+        match self {
+            LoweringDiagnostic::UnsupportedDerivedField { origin, .. }
+            | LoweringDiagnostic::UnsupportedForLoopIterable { origin, .. }
+            | LoweringDiagnostic::ConflictingBorrow { origin, .. }
+            | LoweringDiagnostic::UninitialisedVariable { origin, .. }
+            | LoweringDiagnostic::LayoutComputation { origin, .. }
+            | LoweringDiagnostic::FailingAssertion { origin, .. } => *origin,
+        }
+    }
+}
+
+impl From<LoweringDiagnostic> for Reports {
+    fn from(diagnostic: LoweringDiagnostic) -> Self {
+        let mut reporter = Reporter::new();
+
+        match diagnostic {
+            LoweringDiagnostic::UnsupportedDerivedField { item, subject, ty, origin } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnsupportedDerivedField)
+                    .title(format!("cannot derive `{item}` for `{subject}`"))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        origin.span(),
+                        format!("a field of type `{ty}` does not support `{item}`"),
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        format!("fields must be primitive, or a type which also derives `{item}`"),
+                    )));
+            }
+            LoweringDiagnostic::UnsupportedForLoopIterable { ty, is_range, origin } => {
+                let (title, label) = if is_range {
+                    (
+                        format!("cannot iterate over a range of `{ty}`"),
+                        format!("the bounds of this range are of type `{ty}`"),
+                    )
+                } else {
+                    (
+                        format!("cannot iterate over a value of type `{ty}`"),
+                        format!("this is of type `{ty}`"),
+                    )
+                };
+
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnsupportedForLoopIterable)
+                    .title(title)
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        origin.span(),
+                        label,
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "a `for` loop can only iterate over arrays, slices and ranges of integers",
+                    )));
+            }
+            LoweringDiagnostic::ConflictingBorrow {
+                mutability,
+                previous_mutability,
+                origin,
+                previous,
+            } => {
+                let describe = |mutability| match mutability {
+                    Mutability::Mutable => "mutable",
+                    Mutability::Immutable => "immutable",
+                };
+
+                let title = if mutability == previous_mutability {
+                    "cannot borrow this as mutable more than once at a time".to_string()
+                } else {
+                    format!(
+                        "cannot borrow this as {} because it is also borrowed as {}",
+                        describe(mutability),
+                        describe(previous_mutability)
+                    )
+                };
+
+                reporter
+                    .error()
+                    .code(HashErrorCode::ConflictingBorrow)
+                    .title(title)
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        previous.span(),
+                        format!("{} borrow occurs here", describe(previous_mutability)),
+                    )))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        origin.span(),
+                        format!("{} borrow occurs here", describe(mutability)),
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Info,
+                        "the first borrow is still used after the second borrow is created",
+                    )));
+            }
+            LoweringDiagnostic::UninitialisedVariable { name, origin } => {
+                reporter
+                    .error()
+                    .code(HashErrorCode::UninitialisedVariable)
+                    .title(format!("use of possibly uninitialised variable `{name}`"))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        origin.span(),
+                        format!("`{name}` is used here, but it may not have been initialised"),
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        format!("assign a value to `{name}` on every path before it is used"),
+                    )));
+            }
+            LoweringDiagnostic::LayoutComputation { ty, error, origin } => {
+                let reason = match error {
+                    LayoutError::Overflow => {
+                        "the type is too large for the current target".to_string()
+                    }
+                    LayoutError::Unknown(inner) if inner == ty => {
+                        "the type does not have a known layout".to_string()
+                    }
+                    LayoutError::Unknown(inner) => {
+                        format!("the type `{inner}` does not have a known layout")
+                    }
+                    LayoutError::InvalidSimd(inner) => format!(
+                        "the SIMD vector `{inner}` must have one or more fields of the same \
+                         integer or float type"
+                    ),
+                };
+
+                reporter
+                    .error()
+                    .code(HashErrorCode::UnknownLayout)
+                    .title(format!("cannot compute the layout of `{ty}`"))
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        origin.span(),
+                        format!("the layout of `{ty}` is required here"),
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Note,
+                        reason,
+                    )));
+            }
+            LoweringDiagnostic::FailingAssertion { message, origin } => {
+                reporter
+                    .warning()
+                    .code(HashWarningCode::UnconditionalPanic)
+                    .title("this operation will panic at runtime")
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        origin.span(),
+                        message.trim_end(),
+                    )));
+            }
+        }
+
+        reporter.into_reports()
+    }
+}
@@ -6,11 +6,12 @@ use std::ops::ControlFlow;
 
 use hash_attrs::{attr::attr_store, builtin::attrs};
 use hash_pipeline::workspace::StageInfo;
+use hash_source::{entry_point::EntryPointState, SourceId};
 use hash_storage::store::{statics::StoreId, Store, TrivialSequenceStoreKey};
 use hash_tir::{
     atom_info::ItemInAtomInfo,
     stores::tir_stores,
-    tir::{FnDefId, HasAstNodeId, ModKind, ModMemberValue, TermId},
+    tir::{DataDefId, FnDefId, HasAstNodeId, ModKind, ModMemberValue, TermId},
     visitor::{Atom, Visit, Visitor},
 };
 use hash_utils::{derive_more::Constructor, indexmap::IndexSet};
@@ -22,12 +23,33 @@ pub(crate) struct FnDiscoverer<'a> {
     /// status of each source is. This is used to avoid re-queuing modules
     /// that may of been queued in a previous run.
     stage_info: &'a StageInfo,
+
+    /// The entry point of the program that was resolved during semantic
+    /// analysis, if any.
+    entry_point: &'a EntryPointState<FnDefId>,
 }
 
 /// Stores a set of discovered functions.
 #[derive(Debug, Clone, Default)]
 pub struct DiscoveredFns {
     pub fns: IndexSet<FnDefId>,
+
+    /// Any data definitions that have a `#derive(...)` attribute, the
+    /// derived items of these definitions need to be synthesised.
+    pub derives: IndexSet<DataDefId>,
+
+    /// All of the sources that were visited whilst discovering functions.
+    /// Any bodies that were previously lowered from these sources may be
+    /// stale and need to be checked.
+    pub sources: IndexSet<SourceId>,
+
+    /// Functions that are nested within a function that is marked with
+    /// `#dump_ir`, these inherit the directive from the parent function.
+    pub dumped: IndexSet<FnDefId>,
+
+    /// The entry point of the program, if it was discovered within the
+    /// sources that were visited.
+    pub entry_point: Option<FnDefId>,
 }
 
 impl DiscoveredFns {
@@ -98,7 +120,9 @@ impl FnDiscoverer<'_> {
             // @@Incomplete: mod-blocks that are already lowered won't be caught by
             // the queue-deduplication.
             match def.borrow().kind {
-                ModKind::Source(id) if !self.stage_info.get(id).is_lowered() => {}
+                ModKind::Source(id) if !self.stage_info.get(id).is_lowered() => {
+                    fns.sources.insert(id);
+                }
                 _ => return,
             };
 
@@ -107,16 +131,28 @@ impl FnDiscoverer<'_> {
                     ModMemberValue::Mod(_) => {
                         // Will be handled later in the loop
                     }
-                    ModMemberValue::Data(_) => {
-                        // We don't need to discover anything in data types
+                    ModMemberValue::Data(def) => {
+                        // We only need to discover data types which derive items.
+                        if let Some(id) = def.node_id()
+                            && attr_store().node_has_attr(id, attrs::DERIVE)
+                        {
+                            fns.derives.insert(def);
+                        }
                     }
                     ModMemberValue::Fn(def) if !fns.contains(def) => {
                         if let Some(body) = self.queue_fn_and_body(def) {
                             fns.add_fn(def);
 
-                            // Add all nested functions too
+                            if self.entry_point.def() == Some(def) {
+                                fns.entry_point = Some(def);
+                            }
+
+                            // Add all nested functions too, if the function is to be
+                            // dumped, then so are all of the nested functions.
+                            let dump =
+                                attr_store().node_has_attr(def.node_id_ensured(), attrs::DUMP_IR);
                             let inferred_body = tir_stores().atom_info().get_inferred_value(body);
-                            self.add_all_child_fns(inferred_body, &mut fns);
+                            self.add_all_child_fns(inferred_body, dump, &mut fns);
                         }
                     }
                     ModMemberValue::Fn(_) => {
@@ -130,21 +166,35 @@ impl FnDiscoverer<'_> {
             }
         });
 
+        // The order of the stores depends on the order in which modules
+        // were analysed, so we order the discovered definitions by their
+        // position in the source in order for the lowered bodies to be
+        // deterministic.
+        fns.fns.sort_by(|a, b| a.cmp_position(b));
+        fns.derives.sort_by(|a, b| a.cmp_position(b));
+
         fns
     }
 
     /// Add all the child functions of the given term to the given set of
     /// discovered functions.
     ///
+    /// If `dump` is set, then all of the child functions are marked to
+    /// be dumped.
+    ///
     /// *Invariant*: The term must be inferred, i.e.
     /// `self.get_inferred_value(term) = term`
-    fn add_all_child_fns(&self, term: TermId, fns: &mut DiscoveredFns) {
+    fn add_all_child_fns(&self, term: TermId, dump: bool, fns: &mut DiscoveredFns) {
         Visitor::new().visit(term, &mut |atom: Atom| match atom {
             Atom::FnDef(fn_def) => {
                 // @@Todo: this doesn't deal with captures.
                 if !fns.contains(fn_def) && self.queue_fn_and_body(fn_def).is_some() {
                     fns.add_fn(fn_def);
 
+                    if dump {
+                        fns.dumped.insert(fn_def);
+                    }
+
                     ControlFlow::Continue(())
                 } else {
                     ControlFlow::Break(())
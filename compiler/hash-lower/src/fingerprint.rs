@@ -0,0 +1,85 @@
+//! Computes the [Fingerprint]s of the function definitions that are lowered.
+//! The fingerprint of a definition is derived from the identity of the
+//! definition itself, and the fingerprints of all of the functions that it
+//! refers to. A definition that is re-created (i.e. because its source was
+//! changed) has a new identity, and this change is propagated to all of the
+//! definitions that depend on it, so that their bodies are rebuilt too.
+use std::ops::ControlFlow;
+
+use hash_ir::fingerprint::Fingerprint;
+use hash_storage::store::statics::StoreId;
+use hash_tir::{
+    atom_info::ItemInAtomInfo,
+    stores::tir_stores,
+    tir::FnDefId,
+    visitor::{Atom, Visit, Visitor},
+};
+use hash_utils::{
+    fxhash::{FxHashMap, FxHashSet},
+    indexmap::IndexSet,
+};
+
+/// Computes and caches the [Fingerprint]s of function definitions.
+#[derive(Default)]
+pub(crate) struct FingerprintComputer {
+    /// The fingerprints that have already been computed.
+    fingerprints: FxHashMap<FnDefId, Fingerprint>,
+
+    /// The definitions whose fingerprint is currently being computed, this
+    /// is used to break cycles between (mutually) recursive functions.
+    in_progress: FxHashSet<FnDefId>,
+}
+
+impl FingerprintComputer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute the [Fingerprint] of the given definition, which covers the
+    /// definition and all of the functions that it depends on.
+    ///
+    /// If a dependency is part of a cycle that is currently being computed,
+    /// then only the identity of the dependency contributes to the
+    /// fingerprint.
+    pub fn fingerprint(&mut self, def: FnDefId) -> Fingerprint {
+        if let Some(fingerprint) = self.fingerprints.get(&def) {
+            return *fingerprint;
+        }
+
+        if !self.in_progress.insert(def) {
+            return Fingerprint::new(def);
+        }
+
+        let dependencies = dependencies_of(def)
+            .into_iter()
+            .map(|dependency| self.fingerprint(dependency))
+            .collect::<Vec<_>>();
+        let fingerprint = Fingerprint::new((def, dependencies));
+
+        self.in_progress.remove(&def);
+        self.fingerprints.insert(def, fingerprint);
+        fingerprint
+    }
+}
+
+/// Collect all of the function definitions that the body of the given
+/// definition refers to in the order that they appear. Nested definitions
+/// are included, but their own dependencies are only reached through their
+/// fingerprint.
+fn dependencies_of(def: FnDefId) -> IndexSet<FnDefId> {
+    let body = tir_stores().atom_info().get_inferred_value(def.borrow().body);
+    let mut dependencies = IndexSet::new();
+
+    Visitor::new().visit(body, &mut |atom: Atom| match atom {
+        Atom::FnDef(dependency) => {
+            if dependency != def {
+                dependencies.insert(dependency);
+            }
+
+            ControlFlow::Break(())
+        }
+        Atom::Term(_) | Atom::Lit(_) | Atom::Pat(_) => ControlFlow::Continue(()),
+    });
+
+    dependencies
+}
@@ -0,0 +1,330 @@
+//! Computes a stable hash of each optimised [Body], this covers all of the
+//! statements, terminators, constants and types of the body. The hashes are
+//! stored in [`hash_ir::IrStorage::body_hashes`] so that the backend stages,
+//! and the VM bytecode emitter, can avoid re-generating the artifact of a
+//! body if the hash matches an artifact that was generated earlier.
+//!
+//! The hash is computed from the structure of the IR rather than from the
+//! interned identifiers, since the identifiers depend on the order in which
+//! types and constants were interned. Types are hashed by their definition,
+//! and constants by their value. The source locations of the statements are
+//! not included, so moving a definition within a module doesn't change the
+//! hash of its body.
+
+use std::{
+    hash::{Hash, Hasher},
+    mem,
+};
+
+use hash_ir::{
+    fingerprint::Fingerprint,
+    ir::{
+        AggregateKind, AssertKind, Body, Const, ConstKind, Operand, Place, RValue, StatementKind,
+        TerminatorKind,
+    },
+    ty::{AdtId, InstanceId, ReprTy, ReprTyId},
+};
+use hash_source::{
+    constant::{AllocId, AllocRange},
+    SourceMapUtils,
+};
+use hash_storage::store::statics::StoreId;
+use hash_target::size::Size;
+use hash_utils::fxhash::{FxHashMap, FxHasher};
+
+/// Compute the hash of the given [Body].
+pub(crate) fn compute_body_hash(body: &Body) -> Fingerprint {
+    let mut hasher = BodyHasher { body, state: FxHasher::default(), adts: FxHashMap::default() };
+    hasher.body();
+    Fingerprint::new(hasher.state.finish())
+}
+
+/// Walks over a [Body] and feeds its structure into the `state`.
+struct BodyHasher<'b> {
+    body: &'b Body,
+
+    state: FxHasher,
+
+    /// The ADTs that have been hashed, and the order in which they were
+    /// first hashed. An ADT that is referenced again, e.g. by a recursive
+    /// type, is only hashed by its position in this order.
+    adts: FxHashMap<AdtId, usize>,
+}
+
+impl BodyHasher<'_> {
+    fn write(&mut self, value: impl Hash) {
+        value.hash(&mut self.state);
+    }
+
+    fn body(&mut self) {
+        let body = self.body;
+
+        self.ty(body.metadata().ty());
+        self.write(body.arg_count);
+        self.write(body.locals.len());
+
+        for local in body.locals.iter() {
+            self.write(mem::discriminant(&local.mutability));
+            self.ty(local.ty);
+        }
+
+        self.write(body.blocks().len());
+
+        for block in body.blocks().iter() {
+            self.write(block.statements.len());
+
+            for statement in &block.statements {
+                self.statement(&statement.kind);
+            }
+
+            self.terminator(&block.terminator().kind);
+        }
+    }
+
+    fn statement(&mut self, statement: &StatementKind) {
+        self.write(mem::discriminant(statement));
+
+        match statement {
+            StatementKind::Nop => {}
+            StatementKind::Assign(place, value) => {
+                self.place(place);
+                self.rvalue(value);
+            }
+            StatementKind::Discriminate(place, variant) => {
+                self.place(place);
+                self.write(variant);
+            }
+            StatementKind::Live(local) | StatementKind::Dead(local) => self.write(local),
+            StatementKind::Assume(operand) => self.operand(operand),
+        }
+    }
+
+    fn rvalue(&mut self, value: &RValue) {
+        self.write(mem::discriminant(value));
+
+        match value {
+            RValue::Use(operand) => self.operand(operand),
+            RValue::ConstOp(op, ty) => {
+                self.write(mem::discriminant(op));
+                self.ty(*ty);
+            }
+            RValue::UnaryOp(op, operand) => {
+                self.write(mem::discriminant(op));
+                self.operand(operand);
+            }
+            RValue::BinaryOp(op, operands) | RValue::CheckedBinaryOp(op, operands) => {
+                self.write(mem::discriminant(op));
+                self.operand(&operands.0);
+                self.operand(&operands.1);
+            }
+            RValue::Cast(kind, operand, ty) => {
+                self.write(mem::discriminant(kind));
+                self.operand(operand);
+                self.ty(*ty);
+            }
+            RValue::Len(place) | RValue::Discriminant(place) => self.place(place),
+            RValue::Ref(mutability, place, kind) => {
+                self.write((mem::discriminant(mutability), mem::discriminant(kind)));
+                self.place(place);
+            }
+            RValue::Aggregate(kind, operands) => {
+                self.write(mem::discriminant(kind));
+
+                match kind {
+                    AggregateKind::Tuple(adt) | AggregateKind::Struct(adt) => self.adt(*adt),
+                    AggregateKind::Enum(adt, variant) => {
+                        self.adt(*adt);
+                        self.write(variant);
+                    }
+                    AggregateKind::Array(ty) => self.ty(*ty),
+                }
+
+                self.write(operands.len());
+                operands.iter().for_each(|operand| self.operand(operand));
+            }
+            RValue::Repeat(operand, count) => {
+                self.operand(operand);
+                self.write(count);
+            }
+        }
+    }
+
+    fn terminator(&mut self, terminator: &TerminatorKind) {
+        self.write(mem::discriminant(terminator));
+
+        match terminator {
+            TerminatorKind::Goto(target) => self.write(target),
+            TerminatorKind::Return | TerminatorKind::Unreachable | TerminatorKind::Resume => {}
+            TerminatorKind::Call { op, args, destination, target, unwind } => {
+                self.operand(op);
+                self.write(args.len());
+                args.iter().for_each(|arg| self.operand(arg));
+                self.place(destination);
+                self.write((target, unwind));
+            }
+            TerminatorKind::Switch { value, targets } => {
+                self.operand(value);
+                self.write((&targets.values, &targets.targets, targets.otherwise));
+            }
+            TerminatorKind::Assert { condition, expected, kind, target, unwind } => {
+                self.operand(condition);
+                self.write((expected, target, unwind));
+                self.assert_kind(kind);
+            }
+            TerminatorKind::Drop { place, target } => {
+                self.place(place);
+                self.write(target);
+            }
+        }
+    }
+
+    fn assert_kind(&mut self, kind: &AssertKind) {
+        self.write(mem::discriminant(kind));
+
+        match kind {
+            AssertKind::DivisionByZero { operand }
+            | AssertKind::RemainderByZero { operand }
+            | AssertKind::NegativeOverflow { operand }
+            | AssertKind::InvalidDiscriminant { discriminant: operand } => self.operand(operand),
+            AssertKind::Overflow { op, lhs, rhs: other }
+            | AssertKind::ShiftAmountTooLarge { op, lhs, amount: other } => {
+                self.write(mem::discriminant(op));
+                self.operand(lhs);
+                self.operand(other);
+            }
+            AssertKind::NegativeExponent { base: lhs, exponent: other }
+            | AssertKind::BoundsCheck { len: lhs, index: other } => {
+                self.operand(lhs);
+                self.operand(other);
+            }
+        }
+    }
+
+    fn operand(&mut self, operand: &Operand) {
+        self.write(mem::discriminant(operand));
+
+        match operand {
+            Operand::Const(constant) => self.constant(constant),
+            Operand::Place(place) => self.place(place),
+        }
+    }
+
+    fn place(&mut self, place: &Place) {
+        let body = self.body;
+        self.write(place.local);
+        self.write(body.projections.borrow(place.projections));
+    }
+
+    fn constant(&mut self, constant: &Const) {
+        self.ty(constant.ty());
+        self.write(mem::discriminant(&constant.kind));
+
+        match constant.kind {
+            ConstKind::Zero => {}
+            ConstKind::Scalar(scalar) => self.write(scalar),
+            ConstKind::Pair { data, len } => {
+                self.alloc(data);
+                self.write(len);
+            }
+            ConstKind::Alloc { offset, alloc } => {
+                self.write(offset.bytes());
+                self.alloc(alloc);
+            }
+            ConstKind::FnAddr(instance) => self.instance(instance),
+        }
+    }
+
+    fn alloc(&mut self, alloc: AllocId) {
+        alloc.map(|alloc| {
+            let bytes = alloc.read_bytes(AllocRange::new(Size::ZERO, alloc.size()));
+            bytes.hash(&mut self.state);
+            alloc.align().bytes().hash(&mut self.state);
+        })
+    }
+
+    fn instance(&mut self, instance: InstanceId) {
+        let instance = instance.value();
+
+        self.write(instance.name().as_str());
+        self.write(mem::discriminant(&instance.abi));
+        self.write((instance.is_intrinsic, instance.is_c_variadic));
+
+        // Functions with the same name from different modules must not be
+        // confused.
+        if let Some(source) = instance.source {
+            SourceMapUtils::map(source, |source| source.canonicalised_path().hash(&mut self.state));
+        }
+
+        for param in instance.params.value() {
+            self.ty(param);
+        }
+
+        self.ty(instance.ret_ty);
+    }
+
+    fn ty(&mut self, ty: ReprTyId) {
+        let ty = ty.value();
+        self.write(mem::discriminant(&ty));
+
+        match ty {
+            ReprTy::Int(ty) => self.write(mem::discriminant(&ty)),
+            ReprTy::UInt(ty) => self.write(mem::discriminant(&ty)),
+            ReprTy::Float(ty) => self.write(mem::discriminant(&ty)),
+            ReprTy::Str | ReprTy::Bool | ReprTy::Char | ReprTy::Never => {}
+            ReprTy::Ref(pointee, mutability, kind) => {
+                self.write((mem::discriminant(&mutability), mem::discriminant(&kind)));
+                self.ty(pointee);
+            }
+            ReprTy::Slice(element) => self.ty(element),
+            ReprTy::Array { ty, length } => {
+                self.write(length);
+                self.ty(ty);
+            }
+            ReprTy::Adt(adt) => self.adt(adt),
+            ReprTy::Fn { params, return_ty, abi } => {
+                self.write(mem::discriminant(&abi));
+
+                let params = params.value();
+                self.write(params.len());
+
+                for param in params {
+                    self.ty(param);
+                }
+
+                self.ty(return_ty);
+            }
+            ReprTy::FnDef { instance } => self.instance(instance),
+        }
+    }
+
+    fn adt(&mut self, id: AdtId) {
+        if let Some(&order) = self.adts.get(&id) {
+            self.write(order);
+            return;
+        }
+
+        self.adts.insert(id, self.adts.len());
+
+        let adt = id.value();
+        self.write(adt.name.as_str());
+        self.write(adt.flags.bits());
+
+        let metadata = &adt.metadata;
+        self.write(metadata.discriminant);
+        self.write(metadata.alignment.map(|alignment| alignment.bytes()));
+        self.write(metadata.flags().bits());
+
+        self.write(adt.variants.len());
+
+        for variant in adt.variants.iter() {
+            self.write(variant.name.as_str());
+            self.write((variant.discriminant.value, variant.discriminant.ty));
+            self.write(variant.fields.len());
+
+            for field in &variant.fields {
+                self.write(field.name.as_str());
+                self.ty(field.ty);
+            }
+        }
+    }
+}
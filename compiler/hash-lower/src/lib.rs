@@ -5,34 +5,75 @@
 //! elimination.
 #![feature(decl_macro, let_chains, never_type, unwrap_infallible)]
 
+mod borrowck;
 mod build;
 mod cfg;
 mod ctx;
+mod diagnostics;
 
 mod discover;
+mod fingerprint;
+mod hashing;
 mod optimise;
 mod ty;
+mod uninit;
+mod validate;
+
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
 
+use borrowck::BorrowChecker;
 use build::BodyBuilder;
 use ctx::BuilderCtx;
 use discover::FnDiscoverer;
+use fingerprint::FingerprintComputer;
 use hash_attrs::{attr::attr_store, builtin::attrs};
-use hash_ir::IrStorage;
-use hash_ir_utils::{graphviz, pretty};
+use hash_const_eval::print::ConstPrintOptions;
+use hash_ir::{
+    call_graph::CallGraph,
+    globals::GlobalAllocPlan,
+    ir::{Body, BodySource, LocalDecl},
+    IrStorage,
+};
+use hash_ir_analysis::stack::{StackDepth, StackUsage};
+use hash_ir_eval::pre_evaluate_calls;
+use hash_ir_utils::{
+    binary, graphviz, json,
+    pretty::{self, BodyWriterOptions},
+};
 use hash_pipeline::{
     interface::{
         CompilerInterface, CompilerOutputStream, CompilerResult, CompilerStage, StageMetrics,
     },
-    settings::{CompilerSettings, CompilerStageKind, IrDumpMode},
+    settings::{
+        CallGraphDumpMode, CompilerSettings, CompilerStageKind, EmitKind, IrDumpMode,
+        OptimisationLevel,
+    },
     workspace::{SourceStageInfo, Workspace},
 };
+use hash_reporting::reporter::Reports;
 use hash_repr::{compute::LayoutComputer, LayoutStorage};
 use hash_semantics::storage::SemanticStorage;
 use hash_source::SourceId;
 use hash_storage::store::{statics::StoreId, Store};
+use hash_target::{size::Size, HasTarget};
 use hash_tir::{stores::tir_stores, tir::HasAstNodeId};
-use hash_utils::{profiling::HasMutMetrics, rayon};
-use optimise::Optimiser;
+use hash_utils::{
+    fxhash::FxHashSet,
+    itertools::Itertools,
+    profiling::HasMutMetrics,
+    rayon::{self, prelude::*},
+    stream_writeln,
+};
+use hashing::compute_body_hash;
+use optimise::{propagate_constants, Optimiser};
+use uninit::UninitChecker;
+use validate::{IrValidator, ValidationStage};
 
 /// The Hash IR builder compiler stage.
 #[derive(Default)]
@@ -73,7 +114,7 @@ pub struct LoweringCtx<'ir> {
     pub stdout: CompilerOutputStream,
 
     /// Reference to the rayon thread pool.
-    pub _pool: &'ir rayon::ThreadPool,
+    pub pool: &'ir rayon::ThreadPool,
 }
 
 pub trait LoweringCtxQuery: CompilerInterface {
@@ -106,44 +147,195 @@ impl<Ctx: LoweringCtxQuery> CompilerStage<Ctx> for IrGen {
         let entry_point = &data.semantic_storage.distinguished_items.entry_point;
 
         // Discover all of the bodies that need to be lowered
-        let items = self.record("discover", |_| {
-            let discoverer = FnDiscoverer::new(&data.workspace.source_stage_info);
+        let mut items = self.record("discover", |_| {
+            let discoverer = FnDiscoverer::new(&data.workspace.source_stage_info, entry_point);
             discoverer.discover_fns()
         });
 
         // Pre-allocate the vector of lowered bodies.
         let mut lowered_bodies = Vec::with_capacity(items.fns.len());
+        let mut diagnostics = Vec::new();
+
+        // The fingerprints of all the definitions that were discovered in this
+        // run, bodies that were lowered from definitions in the discovered
+        // sources, but are no longer present are removed.
+        let mut fingerprints = FxHashSet::default();
+        let sources = std::mem::take(&mut items.sources);
+        let dumped = std::mem::take(&mut items.dumped);
+        let entry_point_def = items.entry_point;
+
+        // Create all of the items that are derived from `#derive(...)` before
+        // building any bodies, so that they can be referenced by both user
+        // defined bodies and other derived bodies.
+        let derived = self.record("derive", |_| {
+            let ctx = BuilderCtx::new(&data);
+            items.derives.iter().flat_map(|def| ctx.create_derived_items(*def)).collect::<Vec<_>>()
+        });
+
+        let (first_derived, total) = (items.fns.len(), items.fns.len() + derived.len());
+        let progress = &data.workspace.progress;
+        let memory_limit = data.settings.memory_limit();
+
+        // The bodies that are re-used from a previous compilation, these are
+        // still checked since the diagnostics of the previous compilation
+        // are not kept.
+        let mut reused = Vec::new();
 
         self.record("build", |_| {
-            for func in items.into_iter() {
+            let mut fingerprint_computer = FingerprintComputer::new();
+
+            for (index, func) in items.into_iter().enumerate() {
+                // Stop lowering bodies once the compilation has been cancelled,
+                // the cancellation is reported after all of the bodies.
+                if data.workspace.cancellation.is_cancelled() {
+                    break;
+                }
+
+                progress.report("lower", index, total);
+
                 let name = func.borrow().name.ident();
 
+                // If neither the definition nor any of the functions that it
+                // depends on have changed since it was last lowered, then we
+                // can re-use the previously generated body.
+                let fingerprint = fingerprint_computer.fingerprint(func);
+                fingerprints.insert(fingerprint);
+
+                if let Some(index) = data.icx.fingerprints.get(fingerprint) {
+                    reused.push(index);
+                    let body = &data.icx.bodies[index];
+
+                    if entry_point_def == Some(func) {
+                        let instance = body.meta.ty().borrow().as_instance();
+                        data.icx.entry_point.replace(instance, entry_point.kind().unwrap());
+                    }
+
+                    continue;
+                }
+
                 let ctx = BuilderCtx::new(&data);
                 let mut builder = BodyBuilder::new(name, func.into(), ctx);
                 builder.build();
+                diagnostics.extend(builder.take_diagnostics());
+
+                let mut body = builder.finish();
+                memory_limit.check(CompilerStageKind::Lower, Some(name.as_str()))?;
 
-                let body = builder.finish();
+                // Nested functions inherit the `#dump_ir` directive from
+                // the function that they are defined in.
+                if dumped.contains(&func) {
+                    body.mark_to_dump();
+                }
 
                 // This is the entry point, so we need to record that this
-                // is the entry point.
-                if let Some(def) = entry_point.def()
-                    && def == func
-                {
+                // is the entry point. If the entry point was previously lowered,
+                // then the instance is replaced since the old body is stale.
+                if entry_point_def == Some(func) {
                     let instance = body.meta.ty().borrow().as_instance();
-                    data.icx.entry_point.set(instance, entry_point.kind().unwrap());
+                    data.icx.entry_point.replace(instance, entry_point.kind().unwrap());
                 }
 
                 // add the body to the lowered bodies
-                lowered_bodies.push(body);
+                lowered_bodies.push((Some(fingerprint), body));
+            }
+
+            // Derived bodies are built last since they may rely on lang items
+            // that are only registered whilst lowering the user defined bodies.
+            //
+            // Derived items are re-created on each run, so they are never
+            // re-used.
+            for (index, item) in derived.into_iter().enumerate() {
+                if data.workspace.cancellation.is_cancelled() {
+                    break;
+                }
+
+                progress.report("lower", first_derived + index, total);
+
+                let name = item.ty.borrow().as_instance().borrow().name();
+
+                let ctx = BuilderCtx::new(&data);
+                let mut builder = BodyBuilder::new(name, item.into(), ctx);
+                builder.build();
+                diagnostics.extend(builder.take_diagnostics());
+
+                lowered_bodies.push((None, builder.finish()));
+                memory_limit.check(CompilerStageKind::Lower, Some(name.as_str()))?;
             }
+
+            progress.report("lower", total, total);
+            CompilerResult::Ok(())
+        })?;
+
+        // None of the lowered bodies are stored if the compilation was
+        // cancelled, so the bodies are lowered again by the next compilation.
+        data.workspace.cancellation.check()?;
+
+        // Check all of the newly lowered bodies for any obviously conflicting
+        // borrows. Derived bodies are synthesised by the compiler, and so they
+        // are not checked.
+        self.record("borrowck", |_| {
+            for (_, body) in lowered_bodies.iter() {
+                if body.metadata().source() != BodySource::Derived {
+                    diagnostics.extend(BorrowChecker::new(body).check());
+                }
+            }
+        });
+
+        // Check that none of the bodies read a variable before it has been
+        // initialised.
+        self.record("uninit", |_| {
+            let reused = reused.iter().map(|index| &data.icx.bodies[*index]);
+
+            for body in lowered_bodies.iter().map(|(_, body)| body).chain(reused) {
+                diagnostics.extend(UninitChecker::new(body).check());
+            }
+        });
+
+        // If requested, check that all of the lowered bodies are well-formed
+        // before they are passed onto any further stages.
+        let mut validation_errors = Vec::new();
+
+        if data.settings.lowering_settings.validate {
+            self.record("validate", |_| {
+                for (_, body) in lowered_bodies.iter() {
+                    if let Err(errors) =
+                        IrValidator::new(body, ValidationStage::Lowering).validate()
+                    {
+                        validation_errors.extend(errors);
+                    }
+                }
+            });
+        }
+
+        // Remove any stale bodies from the discovered sources, i.e. bodies
+        // whose definition has changed or has been removed, and all of the
+        // bodies of the modules that have been invalidated.
+        let stale_sources = std::mem::take(&mut data.workspace.stale_sources);
+        data.icx.retain_bodies(|body, fingerprint| {
+            let source = body.source();
+
+            !stale_sources.contains(&source)
+                && (!sources.contains(&source)
+                    || fingerprint.is_some_and(|fingerprint| fingerprints.contains(&fingerprint)))
         });
 
         // Mark all modules now as lowered, and all generated
         // bodies to the store.
         data.workspace.source_stage_info.set_all(SourceStageInfo::LOWERED);
-        data.icx.add_bodies(lowered_bodies);
+        data.icx.add_fingerprinted_bodies(lowered_bodies);
 
-        Ok(())
+        if diagnostics.is_empty() && validation_errors.is_empty() {
+            Ok(())
+        } else {
+            // Sort the diagnostics by source order so that they are always emitted
+            // in a stable order. Polymorphic functions are lowered once per
+            // instance, so we also remove any duplicate diagnostics.
+            diagnostics.sort_by_key(|diagnostic| diagnostic.id());
+            diagnostics.dedup();
+
+            let diagnostics = diagnostics.into_iter().flat_map(Reports::from);
+            Err(diagnostics.chain(validation_errors.into_iter().flat_map(Reports::from)).collect())
+        }
     }
 
     fn cleanup(&mut self, _entry: SourceId, ctx: &mut Ctx) {
@@ -152,19 +344,45 @@ impl<Ctx: LoweringCtxQuery> CompilerStage<Ctx> for IrGen {
 
         // Iterate over all of the ADTs that have a registered `AstNodeId`
         // in the `AstInfo`. If the ADT contains a `#layout_of` attribute,
-        // then we try to lower the type, and then print the layout of
-        // the type.
+        // or it is the type that was requested via `explain-layout`, then
+        // we try to lower the type, and then print the layout of the type.
+        // If `--emit=layout` was specified, the layouts of all of the ADTs are
+        // printed. The layouts are printed in source order.
         //
         // @@Todo: instead of looping through all the data defs, we should
         // instead look at a queue of data defs which should have been constructed
         // earlier.
+        let explain_layout = data.settings.lowering_settings.explain_layout.as_deref();
+        let emit_all = data.settings.emits(EmitKind::Layout);
+        let quiet_prelude = data.settings.prelude_is_quiet;
+        let mut data_defs = vec![];
+
         tir_stores().data_def().for_each_entry(|data_def| {
-            if let Some(id) = data_def.node_id()
-                && attr_store().node_has_attr(id, attrs::LAYOUT_OF)
-            {
-                builder.dump_ty_layout(data_def, data.stdout.clone())
+            let Some(id) = data_def.node_id() else {
+                return;
+            };
+
+            let is_explained =
+                explain_layout.is_some_and(|name| data_def.borrow().name.ident().as_str() == name);
+
+            let is_emitted = emit_all && !(quiet_prelude && id.source().is_prelude());
+
+            if is_explained || is_emitted || attr_store().node_has_attr(id, attrs::LAYOUT_OF) {
+                data_defs.push(data_def);
             }
-        })
+        });
+
+        data_defs.sort_by(|a, b| a.cmp_position(b));
+
+        if let Some(name) = explain_layout
+            && !data_defs.iter().any(|data_def| data_def.borrow().name.ident().as_str() == name)
+        {
+            stream_writeln!(data.stdout.clone(), "No type named `{name}` was found");
+        }
+
+        for data_def in data_defs {
+            builder.dump_ty_layout(data_def, data.stdout.clone())
+        }
     }
 }
 
@@ -199,33 +417,120 @@ impl<Ctx: LoweringCtxQuery> CompilerStage<Ctx> for IrOptimiser {
     }
 
     fn run(&mut self, _: SourceId, ctx: &mut Ctx) -> CompilerResult<()> {
-        let LoweringCtx { icx, settings, .. } = ctx.data();
+        let LoweringCtx { workspace, icx, settings, pool, lcx, .. } = ctx.data();
+        let Workspace { cancellation, progress, .. } = &*workspace;
+        let memory_limit = settings.memory_limit();
+
+        let optimiser = Optimiser::new(settings);
+        let mut validation_errors = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        // Bodies that were re-used from a previous compilation have already
+        // been optimised, since a body is only re-used if neither its
+        // definition nor any of its dependencies have changed. Optimising
+        // them again would only repeat the same work, so they are skipped.
+        let lc = LayoutComputer::new(lcx);
+        let should_evaluate = |body: &Body| {
+            !body.is_optimised() && optimiser.optimisation_level_of(body) > OptimisationLevel::Debug
+        };
+
+        // Calls with constant arguments are evaluated before the bodies are
+        // optimised, so that the optimisations can make use of the results.
+        self.record("const-eval", |_| {
+            pre_evaluate_calls(&mut icx.bodies, lc, settings.target(), should_evaluate);
+        });
 
-        let bodies = &mut icx.bodies;
-        let body_data = &icx.ctx;
+        // The values of the calls are then propagated through the bodies.
+        self.record("const-propagation", |_| {
+            for body in icx.bodies.iter_mut().filter(|body| should_evaluate(body)) {
+                if cancellation.is_cancelled() {
+                    break;
+                }
+
+                diagnostics.extend(propagate_constants(body, lc, settings.target()));
+            }
+        });
 
         self.record("optimise", |this| {
-            // @@Todo: think about making optimisation passes in parallel...
-            // pool.scope(|scope| {
-            //     for body in &mut icx.generated_bodies {
-            //         scope.spawn(|_| {
-            //             let optimiser = Optimiser::new(body_data, settings);
-            //             optimiser.optimise(body);
-            //         });
-            //     }
-            // });
-
-            for body in bodies.iter_mut() {
-                let optimiser = Optimiser::new(body_data, settings);
-                optimiser.optimise(body);
+            // Each body is optimised independently of the others, and the
+            // results are collected in the same order as the bodies. This
+            // ensures that the reported errors, and any bodies that are later
+            // dumped appear in a deterministic order regardless of how the
+            // work is scheduled.
+            //
+            // Any bodies that haven't been started when the compilation is
+            // cancelled, or once the memory limit has been exceeded are skipped.
+            //
+            // A body is only marked as optimised if it remains valid, so that
+            // the validation errors are reported again by the next compilation.
+            let total = icx.bodies.iter().filter(|body| !body.is_optimised()).count();
+            let completed = AtomicUsize::new(0);
+            let exceeded = Mutex::new(None);
+            let results: Vec<_> = pool.install(|| {
+                icx.bodies
+                    .par_iter_mut()
+                    .filter(|body| !body.is_optimised())
+                    .filter(|_| !cancellation.is_cancelled() && exceeded.lock().unwrap().is_none())
+                    .map(|body| {
+                        let optimisation = optimiser.optimise(body);
+                        if optimisation.errors.is_empty() {
+                            body.mark_optimised();
+                        }
+
+                        let completed = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                        progress.report("optimise", completed, total);
+
+                        let name = body.meta.name();
+                        if let Err(reports) =
+                            memory_limit.check(CompilerStageKind::Lower, Some(name.as_str()))
+                        {
+                            exceeded.lock().unwrap().get_or_insert(reports);
+                        }
+
+                        optimisation
+                    })
+                    .collect()
+            });
+
+            for mut optimisation in results {
+                validation_errors.append(&mut optimisation.errors);
 
                 // Collect metrics on the stages.
-                let metrics = optimiser.into_metrics().into();
+                let metrics = optimisation.into_metrics().into();
                 this.metrics().merge(&metrics);
             }
+
+            exceeded.into_inner().unwrap().map_or(Ok(()), Err)
+        })?;
+
+        // The bodies aren't in their final form if the compilation was
+        // cancelled, so no hashes are computed for them.
+        cancellation.check()?;
+
+        // Now that the bodies are in their final form, we can compute the
+        // hashes that the backends use to determine whether any artifacts
+        // that were generated for the body can be re-used.
+        self.record("hash", |_| {
+            icx.body_hashes = icx.bodies.iter().map(compute_body_hash).collect();
+        });
+
+        // The global allocations are planned once the bodies are final, so
+        // that allocations which were removed by the optimiser aren't placed.
+        self.record("globals", |_| {
+            *icx.ctx.globals_mut() = GlobalAllocPlan::compute(&icx.bodies);
         });
 
-        Ok(())
+        if diagnostics.is_empty() && validation_errors.is_empty() {
+            Ok(())
+        } else {
+            // Polymorphic functions are optimised once per instance, so any
+            // duplicate diagnostics are removed.
+            diagnostics.sort_by_key(|diagnostic| diagnostic.id());
+            diagnostics.dedup();
+
+            let diagnostics = diagnostics.into_iter().flat_map(Reports::from);
+            Err(diagnostics.chain(validation_errors.into_iter().flat_map(Reports::from)).collect())
+        }
     }
 
     fn cleanup(&mut self, _entry_point: SourceId, ctx: &mut Ctx) {
@@ -233,15 +538,201 @@ impl<Ctx: LoweringCtxQuery> CompilerStage<Ctx> for IrOptimiser {
 
         // we need to check if any of the bodies have been marked for `dumping`
         // and emit the IR that they have generated.
-        let dump = settings.lowering_settings.dump;
+        let dump = settings.emits(EmitKind::Ir);
         let quiet_prelude = settings.prelude_is_quiet;
 
         let lc = LayoutComputer::new(lcx);
 
-        if settings.lowering_settings.dump_mode == IrDumpMode::Graph {
-            graphviz::dump_ir_bodies(&icx.bodies, dump, quiet_prelude, lc, &mut stdout).unwrap();
-        } else {
-            pretty::dump_ir_bodies(&icx.bodies, dump, quiet_prelude, lc, &mut stdout).unwrap();
+        match settings.lowering_settings.dump_mode {
+            IrDumpMode::Graph => {
+                graphviz::dump_ir_bodies(&icx.bodies, dump, quiet_prelude, lc, &mut stdout)
+            }
+            IrDumpMode::CallGraph => dump_call_graph(icx, settings, &mut stdout),
+            IrDumpMode::StackUsage => dump_stack_usage(icx, settings, lc, &mut stdout),
+            mode @ (IrDumpMode::Pretty | IrDumpMode::Summary) => {
+                let options = BodyWriterOptions {
+                    with_spans: settings.lowering_settings.dump_spans,
+                    with_edges: settings.lowering_settings.dump_edges,
+                    summary: mode == IrDumpMode::Summary,
+                    const_options: ConstPrintOptions {
+                        array_element_limit: settings.lowering_settings.dump_array_limit,
+                    },
+                };
+
+                pretty::dump_ir_bodies(
+                    &icx.bodies,
+                    dump,
+                    quiet_prelude,
+                    options,
+                    icx.entry_point.def(),
+                    lc,
+                    &mut stdout,
+                )
+            }
+            IrDumpMode::Json => {
+                json::dump_ir_bodies(&icx.bodies, dump, quiet_prelude, lc, &mut stdout)
+            }
+            IrDumpMode::Binary => {
+                binary::dump_ir_bodies(&icx.bodies, dump, quiet_prelude, &mut stdout)
+            }
+        }
+        .unwrap();
+
+        if settings.lowering_settings.print_globals {
+            write!(stdout, "{}", icx.ctx.globals()).unwrap();
         }
     }
 }
+
+/// Dump the call graph of the lowered bodies in the mode that is specified
+/// by the [CompilerSettings]. If a function was specified, then the graph is
+/// restricted to the functions that are reachable from it, otherwise the
+/// functions that are reachable from the entry point are marked.
+fn dump_call_graph(
+    icx: &IrStorage,
+    settings: &CompilerSettings,
+    stdout: &mut impl Write,
+) -> io::Result<()> {
+    let graph = build_call_graph(icx, settings);
+    let from = settings.lowering_settings.call_graph_from.as_deref();
+
+    let root = match call_graph_root(icx, settings, &graph) {
+        Ok(root) => root,
+        Err(name) => return writeln!(stdout, "No function named `{name}` was found"),
+    };
+
+    let reachable = root.map(|root| graph.reachable_from([root]));
+    let only_reachable = from.is_some();
+
+    match settings.lowering_settings.call_graph_mode {
+        CallGraphDumpMode::Dot => {
+            graphviz::IrCallGraphWriter::new(&graph, reachable.as_ref(), only_reachable)
+                .write_graph(stdout)
+        }
+        CallGraphDumpMode::Json => {
+            let writer =
+                json::IrCallGraphJsonWriter::new(&graph, reachable.as_ref(), only_reachable);
+            writeln!(stdout, "{}", writer.to_json())
+        }
+    }
+}
+
+/// Dump an estimate of the stack usage of each of the lowered bodies, and
+/// any recursive cycles within the call graph. If a function was specified,
+/// then the report is restricted to the functions that are reachable from
+/// it, otherwise the deepest stack from the entry point is summarised.
+fn dump_stack_usage(
+    icx: &IrStorage,
+    settings: &CompilerSettings,
+    lc: LayoutComputer,
+    stdout: &mut impl Write,
+) -> io::Result<()> {
+    let graph = build_call_graph(icx, settings);
+    let from = settings.lowering_settings.call_graph_from.as_deref();
+
+    let root = match call_graph_root(icx, settings, &graph) {
+        Ok(root) => root,
+        Err(name) => return writeln!(stdout, "No function named `{name}` was found"),
+    };
+
+    let usage = StackUsage::compute(&graph, |body| frame_size(&icx.bodies[body], lc));
+    let reachable = root.map(|root| graph.reachable_from([root]));
+    let is_shown = |index| from.is_none() || reachable.as_ref().is_some_and(|r| r.contains(index));
+
+    let flags = |depth: &StackDepth| match (depth.recursive, depth.incomplete) {
+        (true, true) => " (recursive, incomplete)",
+        (true, false) => " (recursive)",
+        (false, true) => " (incomplete)",
+        (false, false) => "",
+    };
+
+    if let Some(root) = root {
+        let depth = usage.depth(root);
+        let path = depth.path.iter().map(|node| graph.node(*node).name).join(" -> ");
+
+        writeln!(
+            stdout,
+            "maximum stack depth from `{}`: {} bytes{}\n  {path}\n",
+            graph.node(root).name,
+            depth.depth.bytes(),
+            flags(depth)
+        )?;
+    }
+
+    for (index, node) in graph.nodes() {
+        if !node.has_body() || !is_shown(index) {
+            continue;
+        }
+
+        let depth = usage.depth(index);
+        let frame = depth.frame.unwrap_or(Size::ZERO);
+
+        writeln!(
+            stdout,
+            "{}: frame {} bytes, depth {} bytes{}",
+            node.name,
+            frame.bytes(),
+            depth.depth.bytes(),
+            flags(depth)
+        )?;
+    }
+
+    let cycles = usage
+        .cycles()
+        .iter()
+        .filter(|cycle| cycle.iter().any(|node| is_shown(*node)))
+        .collect_vec();
+
+    if !cycles.is_empty() {
+        writeln!(stdout, "\nrecursive cycles:")?;
+
+        for cycle in cycles {
+            writeln!(stdout, "  {}", cycle.iter().map(|node| graph.node(*node).name).join(", "))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the [CallGraph] of the lowered bodies that were requested to be
+/// dumped.
+fn build_call_graph(icx: &IrStorage, settings: &CompilerSettings) -> CallGraph {
+    let dump = settings.emits(EmitKind::Ir);
+    let quiet_prelude = settings.prelude_is_quiet;
+
+    let bodies = icx.bodies.iter().enumerate().filter(|(_, body)| {
+        // Skip the prelude if we're in quiet mode, and skip any bodies
+        // which weren't requested to be dumped.
+        !(quiet_prelude && body.source().is_prelude()) && (dump || body.needs_dumping())
+    });
+
+    CallGraph::new(bodies)
+}
+
+/// Find the root of the given [CallGraph], which is either the function that
+/// was specified by the [CompilerSettings], or the entry point. If the
+/// specified function doesn't exist within the graph, then its name is
+/// returned as the error.
+fn call_graph_root<'s>(
+    icx: &IrStorage,
+    settings: &'s CompilerSettings,
+    graph: &CallGraph,
+) -> Result<Option<usize>, &'s str> {
+    match settings.lowering_settings.call_graph_from.as_deref() {
+        Some(name) => graph.find_node(name).map(Some).ok_or(name),
+        None => Ok(icx.entry_point.def().and_then(|entry| graph.instance_node(entry))),
+    }
+}
+
+/// Estimate the size of the stack frame of the given [Body], i.e. the size of
+/// all of its locals when they are laid out one after another. Locals whose
+/// layout can't be computed are ignored.
+fn frame_size(body: &Body, lc: LayoutComputer) -> Size {
+    body.locals.iter().fold(Size::ZERO, |size, LocalDecl { ty, .. }| match lc.layout_of_ty(*ty) {
+        Ok(layout) => {
+            let layout = layout.borrow();
+            size.align_to(layout.alignment.abi) + layout.size
+        }
+        Err(_) => size,
+    })
+}
@@ -14,7 +14,6 @@ use hash_ir::{
         StatementKind, RETURN_PLACE,
     },
     visitor::{walk_mut, IrVisitorCtx, IrVisitorMut, ModifyingIrVisitor, PlaceCtx},
-    IrCtx,
 };
 use hash_pipeline::settings::{CompilerSettings, OptimisationLevel};
 use hash_utils::index_vec::{index_vec, IndexVec};
@@ -34,11 +33,11 @@ impl IrOptimisationPass for CleanupLocalPass {
 
     /// Pass [CleanupLocalPass] is always enabled since it performs
     /// necessary cleanup of the initially generated IR.
-    fn enabled(&self, settings: &CompilerSettings) -> bool {
-        settings.optimisation_level >= OptimisationLevel::Debug
+    fn enabled(&self, _: &CompilerSettings, level: OptimisationLevel) -> bool {
+        level >= OptimisationLevel::Debug
     }
 
-    fn optimise(&self, body: &mut Body, _: &IrCtx) {
+    fn optimise(&self, body: &mut Body) {
         let mut local_map = LocalUseMap::new(body);
         self.simplify_locals(body, &mut local_map);
 
@@ -77,27 +76,21 @@ impl CleanupLocalPass {
 
             // iterate all blocks and remove any dead local references
             for block in basic_blocks.blocks_mut() {
+                // Any `nop`s are left in place, they are removed by the
+                // `compact_statements` pass unless the markers are retained.
                 block.statements.retain(|statement| {
-                    let (keep, is_nop) = match statement.kind {
-                        StatementKind::Nop => (false, true),
-                        StatementKind::Assign(place, _) | StatementKind::Discriminate(place, _)
-                            if !local_map.is_used(place.local) =>
-                        {
-                            (false, false)
+                    let keep = match statement.kind {
+                        StatementKind::Assign(place, _) | StatementKind::Discriminate(place, _) => {
+                            local_map.is_used(place.local)
                         }
-                        _ => (true, false),
+                        _ => true,
                     };
 
                     if !keep {
-                        // If we removed a `nop`, then we don't have to re-run the
-                        // whole graph since this does not affect the counts of any
-                        // other locals.
-                        if !is_nop {
-                            // we also need to perform an update to the local count
-                            // since we just removed the assignment to this local.
-                            local_map.statement_removed(info, statement);
-                            changed = true;
-                        }
+                        // we also need to perform an update to the local count
+                        // since we just removed the assignment to this local.
+                        local_map.statement_removed(info, statement);
+                        changed = true;
                     }
 
                     keep
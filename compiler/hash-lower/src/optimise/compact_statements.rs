@@ -0,0 +1,58 @@
+//! IR Optimisation pass that compacts the [Statement]s of each block
+//! within a [Body] before it is emitted. This removes any `nop` statements
+//! that were left behind by previous passes, and any `live(_n)` marker that
+//! is immediately followed by a `dead(_n)` marker for the same [Local],
+//! since the local is never used within the range.
+//!
+//! This pass can be disabled using the `--ir-retain-markers` setting, which
+//! is useful when debugging other optimisation passes.
+
+use hash_ir::ir::{Body, Statement, StatementKind};
+use hash_pipeline::settings::{CompilerSettings, OptimisationLevel};
+
+use super::IrOptimisationPass;
+
+/// The [CompactStatementsPass] is responsible for removing redundant
+/// marker statements from the [Body].
+pub struct CompactStatementsPass;
+
+impl IrOptimisationPass for CompactStatementsPass {
+    fn name(&self) -> &'static str {
+        "optimise::compact_statements"
+    }
+
+    /// Pass [CompactStatementsPass] is always enabled unless the markers
+    /// were explicitly requested to be retained.
+    fn enabled(&self, settings: &CompilerSettings, _: OptimisationLevel) -> bool {
+        !settings.lowering_settings.retain_markers
+    }
+
+    fn optimise(&self, body: &mut Body) {
+        for block in body.basic_blocks.blocks_mut() {
+            let statements = std::mem::take(&mut block.statements);
+            block.statements = compact_statements(statements);
+        }
+    }
+}
+
+/// Compact the given list of [Statement]s. Since the statements are
+/// collected onto a stack, removing a `live(_n)` and `dead(_n)` pair
+/// may cause an outer pair to become adjacent, which is then removed
+/// as well.
+fn compact_statements(statements: Vec<Statement>) -> Vec<Statement> {
+    let mut compacted: Vec<Statement> = Vec::with_capacity(statements.len());
+
+    for statement in statements {
+        match statement.kind {
+            StatementKind::Nop => continue,
+            StatementKind::Dead(local)
+                if compacted.last().is_some_and(|last| last.kind == StatementKind::Live(local)) =>
+            {
+                compacted.pop();
+            }
+            _ => compacted.push(statement),
+        }
+    }
+
+    compacted
+}
@@ -0,0 +1,373 @@
+//! IR Optimisation pass that propagates the constant values of [Local]s
+//! through a [Body]. The values are computed by the [ConstPropagation]
+//! analysis, which evaluates each assignment with the [Interpreter] using the
+//! values of the locals that are known at that point. The values are then
+//! used to:
+//!
+//! 1. Replace assignments whose value is known with a constant, and replace the
+//!    operands of all other assignments and terminators with constants.
+//!
+//! 2. Replace a `switch` on a known value with a `goto` to the corresponding
+//!    target.
+//!
+//! 3. Remove `assert`s whose condition is known to always hold, and report a
+//!    warning for `assert`s whose condition is known to always fail, since the
+//!    operation will panic at runtime.
+//!
+//! Any blocks that become unreachable are later removed by the
+//! [super::simplify_graph::SimplifyGraphPass].
+//!
+//! Unlike the other passes, this is not an [super::IrOptimisationPass], since
+//! the [Interpreter] needs a [LayoutComputer] which can't be shared between
+//! the threads that optimise the bodies. Instead, it is run on each body
+//! before the bodies are optimised.
+
+use std::cell::RefCell;
+
+use fixedbitset::FixedBitSet;
+use hash_ir::{
+    ir::{
+        Body, BodyInfo, Const, ConstKind, IrRef, Local, Operand, Place, PlaceProjection, RValue,
+        Statement, StatementKind, Terminator, TerminatorKind,
+    },
+    visitor::IrVisitorCtx,
+};
+use hash_ir_analysis::{Analysis, Direction, JoinSemiLattice, Results};
+use hash_ir_eval::{Interpreter, Step, Value};
+use hash_repr::compute::LayoutComputer;
+use hash_target::Target;
+use hash_utils::index_vec::{index_vec, IndexVec};
+
+use crate::diagnostics::LoweringDiagnostic;
+
+/// Propagate the constant values of the [Local]s within the given [Body],
+/// and return a diagnostic for each `assert` that always fails.
+pub(crate) fn propagate_constants(
+    body: &mut Body,
+    lc: LayoutComputer<'_>,
+    target: &Target,
+) -> Vec<LoweringDiagnostic> {
+    let mut diagnostics = vec![];
+    let mut statements = vec![];
+    let mut terminators = vec![];
+
+    let results = Results::compute(ConstPropagation::new(body, lc, target), body);
+    let analysis = &results.analysis;
+    let info = body.aux();
+
+    for (block, data) in body.blocks().iter_enumerated() {
+        let mut state = results.entry_set(block).clone();
+
+        for (index, statement) in data.statements.iter().enumerate() {
+            if let StatementKind::Assign(place, rvalue) = &statement.kind
+                && let Some(rvalue) = analysis.propagate_rvalue(&mut state, rvalue)
+            {
+                statements.push((IrRef::new(block, index), StatementKind::Assign(*place, rvalue)));
+            }
+
+            let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+            analysis.apply_statement(&mut state, statement, &ctx);
+        }
+
+        let Some(terminator) = &data.terminator else { continue };
+
+        let kind = match &terminator.kind {
+            TerminatorKind::Switch { value, targets } => {
+                analysis.eval_const(&mut state, value).and_then(|value| match value.kind() {
+                    ConstKind::Scalar(scalar) => Some(TerminatorKind::Goto(
+                        targets.corresponding_target(scalar.assert_bits(scalar.size())),
+                    )),
+                    _ => None,
+                })
+            }
+            TerminatorKind::Assert { condition, expected, kind, target, unwind } => {
+                match analysis.eval_bool(&mut state, condition) {
+                    Some(value) if value == *expected => Some(TerminatorKind::Goto(*target)),
+                    known => {
+                        if known.is_some() {
+                            diagnostics.push(LoweringDiagnostic::FailingAssertion {
+                                message: kind.message(),
+                                origin: terminator.origin,
+                            });
+                        }
+
+                        let mut condition = *condition;
+                        analysis.propagate_operand(&mut state, &mut condition).then(|| {
+                            TerminatorKind::Assert {
+                                condition,
+                                expected: *expected,
+                                kind: kind.clone(),
+                                target: *target,
+                                unwind: *unwind,
+                            }
+                        })
+                    }
+                }
+            }
+            TerminatorKind::Call { op, args, destination, target, unwind } => {
+                let mut args = args.clone();
+                let changed = args.iter_mut().fold(false, |changed, arg| {
+                    analysis.propagate_operand(&mut state, arg) | changed
+                });
+
+                changed.then(|| TerminatorKind::Call {
+                    op: *op,
+                    args,
+                    destination: *destination,
+                    target: *target,
+                    unwind: *unwind,
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            terminators.push((block, kind));
+        }
+    }
+
+    drop(results);
+
+    let blocks = body.basic_blocks.blocks_mut();
+
+    for (location, kind) in statements {
+        blocks[location.block].statements[location.index].kind = kind;
+    }
+
+    for (block, kind) in terminators {
+        blocks[block].terminator_mut().kind = kind;
+    }
+
+    diagnostics
+}
+
+/// The values of the [Local]s that are known at a point of a [Body]. The
+/// locals whose values aren't known are [Value::Uninit], since the
+/// [Interpreter] refuses to read them. The state is [None] if the point is
+/// unreachable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KnownValues(Option<IndexVec<Local, Value>>);
+
+impl JoinSemiLattice for KnownValues {
+    fn join(&mut self, other: &Self) -> bool {
+        let Some(other) = &other.0 else {
+            return false;
+        };
+
+        match &mut self.0 {
+            Some(values) => {
+                let mut changed = false;
+
+                // A local is only known if it has the same value on both
+                // of the edges.
+                for (value, other) in values.iter_mut().zip(other.iter()) {
+                    if value != other && *value != Value::Uninit {
+                        *value = Value::Uninit;
+                        changed = true;
+                    }
+                }
+
+                changed
+            }
+            None => {
+                self.0 = Some(other.clone());
+                true
+            }
+        }
+    }
+}
+
+/// A forward analysis which computes the [KnownValues] at each point of a
+/// [Body].
+struct ConstPropagation<'ir> {
+    /// The interpreter that is used to evaluate the assignments.
+    interpreter: RefCell<Interpreter<'ir>>,
+
+    /// The locals whose values are tracked. Locals that are borrowed may be
+    /// modified through a reference, and so their values are never known.
+    tracked: FixedBitSet,
+}
+
+impl<'ir> ConstPropagation<'ir> {
+    fn new(body: &'ir Body, lc: LayoutComputer<'ir>, target: &'ir Target) -> Self {
+        let mut tracked = FixedBitSet::with_capacity(body.locals.len());
+        tracked.insert_range(..);
+
+        for data in body.blocks().iter() {
+            for statement in data.statements.iter() {
+                if let StatementKind::Assign(_, RValue::Ref(_, place, _)) = &statement.kind {
+                    tracked.set(place.local.index(), false);
+                }
+            }
+        }
+
+        let interpreter = Interpreter::new(std::slice::from_ref(body), lc, target);
+        Self { interpreter: RefCell::new(interpreter), tracked }
+    }
+
+    /// Evaluate an [RValue] with the known values of the locals. Values that
+    /// contain a reference refer to the frame of the evaluation, and so they
+    /// are never known.
+    fn eval(&self, state: &mut KnownValues, rvalue: &RValue) -> Option<Value> {
+        let values = state.0.as_mut()?;
+        let value = self.interpreter.borrow_mut().eval_rvalue_in(0, values, rvalue).ok()?;
+
+        (!contains_ref(&value)).then_some(value)
+    }
+
+    /// Evaluate an [Operand] into a [Const], if its value is known.
+    fn eval_const(&self, state: &mut KnownValues, operand: &Operand) -> Option<Const> {
+        self.eval(state, &RValue::Use(*operand))?.as_const()
+    }
+
+    /// Evaluate a boolean [Operand], if its value is known.
+    fn eval_bool(&self, state: &mut KnownValues, operand: &Operand) -> Option<bool> {
+        match self.eval_const(state, operand)?.kind() {
+            ConstKind::Scalar(scalar) => bool::try_from(scalar).ok(),
+            _ => None,
+        }
+    }
+
+    /// Replace the given [Operand] with a constant if its value is known,
+    /// returning whether the operand was replaced.
+    fn propagate_operand(&self, state: &mut KnownValues, operand: &mut Operand) -> bool {
+        if let Operand::Place(_) = operand
+            && let Some(value) = self.eval_const(state, operand)
+        {
+            *operand = Operand::Const(value);
+            return true;
+        }
+
+        false
+    }
+
+    /// Compute the [RValue] that the given one can be replaced with. If the
+    /// value is known, then it is replaced by the constant, otherwise any
+    /// known operands of the value are replaced.
+    fn propagate_rvalue(&self, state: &mut KnownValues, rvalue: &RValue) -> Option<RValue> {
+        // A reference must refer to the place itself, and a constant can't
+        // be simplified any further.
+        if matches!(rvalue, RValue::Ref(..) | RValue::Use(Operand::Const(_))) {
+            return None;
+        }
+
+        if let Some(Value::Const(value)) = self.eval(state, rvalue) {
+            return Some(value.into());
+        }
+
+        let mut rvalue = rvalue.clone();
+
+        let changed = match &mut rvalue {
+            RValue::Use(operand)
+            | RValue::UnaryOp(_, operand)
+            | RValue::Cast(_, operand, _)
+            | RValue::Repeat(operand, _) => self.propagate_operand(state, operand),
+            RValue::BinaryOp(_, operands) | RValue::CheckedBinaryOp(_, operands) => {
+                let (lhs, rhs) = operands.as_mut();
+                self.propagate_operand(state, lhs) | self.propagate_operand(state, rhs)
+            }
+            RValue::Aggregate(_, operands) => operands
+                .iter_mut()
+                .fold(false, |changed, operand| self.propagate_operand(state, operand) | changed),
+            RValue::ConstOp(..) | RValue::Len(_) | RValue::Ref(..) | RValue::Discriminant(_) => {
+                false
+            }
+        };
+
+        changed.then_some(rvalue)
+    }
+
+    /// Modify the value at the given [Place] with `f`. If the location of the
+    /// place within the local isn't known, i.e. it is indexed, then the value
+    /// of the whole local is forgotten.
+    fn write(
+        &self,
+        state: &mut KnownValues,
+        place: Place,
+        info: &BodyInfo,
+        f: impl FnOnce(&mut Value),
+    ) {
+        let Some(values) = &mut state.0 else { return };
+        let projections = info.projections.borrow(place.projections);
+
+        // A write through a reference can only modify a local that is
+        // borrowed, and these are never tracked.
+        if projections.contains(&PlaceProjection::Deref)
+            || !self.tracked.contains(place.local.index())
+        {
+            return;
+        }
+
+        let location =
+            projections.iter().try_fold(&mut values[place.local], |value, projection| {
+                match *projection {
+                    PlaceProjection::Field(field) => value.project_mut(Step::Field(field)).ok(),
+                    PlaceProjection::Downcast(variant) => {
+                        value.project_mut(Step::Downcast(variant)).ok()
+                    }
+                    _ => None,
+                }
+            });
+
+        match location {
+            Some(location) => f(location),
+            None => values[place.local] = Value::Uninit,
+        }
+    }
+}
+
+impl Analysis for ConstPropagation<'_> {
+    type Domain = KnownValues;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom(&self, _: &Body) -> Self::Domain {
+        KnownValues(None)
+    }
+
+    fn initialise_entry(&self, body: &Body, state: &mut Self::Domain) {
+        state.0 = Some(index_vec![Value::Uninit; body.locals.len()]);
+    }
+
+    fn apply_statement(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        match &statement.kind {
+            StatementKind::Assign(place, rvalue) => {
+                let value = self.eval(state, rvalue).unwrap_or(Value::Uninit);
+                self.write(state, *place, &ctx.info, |location| *location = value);
+            }
+            StatementKind::Discriminate(place, variant) => {
+                self.write(state, *place, &ctx.info, |location| location.set_variant(*variant))
+            }
+            StatementKind::Live(local) | StatementKind::Dead(local) => {
+                let place = Place::from_local(*local);
+                self.write(state, place, &ctx.info, |location| *location = Value::Uninit)
+            }
+            StatementKind::Nop | StatementKind::Assume(_) => {}
+        }
+    }
+
+    fn apply_terminator(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &Terminator,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        if let TerminatorKind::Call { destination, .. } = &terminator.kind {
+            self.write(state, *destination, &ctx.info, |location| *location = Value::Uninit);
+        }
+    }
+}
+
+/// Check if the given [Value] contains a reference.
+fn contains_ref(value: &Value) -> bool {
+    match value {
+        Value::Ref(_) => true,
+        Value::Aggregate(aggregate) => aggregate.fields.iter().any(contains_ref),
+        Value::Uninit | Value::Const(_) => false,
+    }
+}
@@ -0,0 +1,293 @@
+//! IR Optimisation pass that performs copy propagation on [Local]s
+//! within a particular [Body]. The lowering process emits many
+//! intermediate temporaries of the form `_n = _m`, this pass finds
+//! all such copies where both [Local]s are in a "single-assignment"
+//! form, and rewrites all uses of `_n` to directly refer to `_m`. This
+//! pass relies on the [super::cleanup_locals::CleanupLocalPass] to
+//! remove the now un-used temporaries from the body.
+//!
+//! A [Local] is considered to be in single-assignment form when:
+//!
+//! 1. It is assigned exactly once (arguments are assigned on entry).
+//!
+//! 2. It is never borrowed, never assigned through a projection, and never
+//!    discriminated.
+//!
+//! 3. The single assignment dominates all of the uses of the [Local].
+
+use fixedbitset::FixedBitSet;
+use hash_ir::{
+    ir::{
+        BasicBlock, Body, IrRef, Local, LocalKind, Operand, Place, PlaceProjection, RValue,
+        StatementKind, RETURN_PLACE, START_BLOCK,
+    },
+    traversal,
+    visitor::{
+        ImmutablePlaceCtx, IrVisitorCtx, IrVisitorMut, ModifyingIrVisitor, MutablePlaceCtx,
+        PlaceCtx,
+    },
+};
+use hash_storage::store::SequenceStoreKey;
+use hash_utils::{
+    graph::dominators::Dominators,
+    index_vec::{index_vec, IndexVec},
+};
+
+use super::IrOptimisationPass;
+
+/// The [CopyPropagationPass] is responsible for eliminating trivial
+/// copies between temporaries, and re-writing uses of the copied
+/// [Local] to the source of the copy.
+pub struct CopyPropagationPass;
+
+impl IrOptimisationPass for CopyPropagationPass {
+    fn name(&self) -> &'static str {
+        "optimise::copy_propagation"
+    }
+
+    fn optimise(&self, body: &mut Body) {
+        let assignments = SsaLocals::new(body);
+        let copies = assignments.compute_copies(body);
+
+        // If there are no copies, then there is nothing to do.
+        if copies.iter_enumerated().all(|(local, head)| local == *head) {
+            return;
+        }
+
+        // The liveness markers of all of the locals that take part in a copy
+        // are removed, including those of the source of the copy. The uses of
+        // a copy are re-written to the source, and so the source could
+        // otherwise be marked as dead whilst the copy is still being used.
+        let mut storage_to_remove = FixedBitSet::with_capacity(body.locals.len());
+        for (local, head) in copies.iter_enumerated() {
+            if local != *head {
+                storage_to_remove.insert(local.index());
+                storage_to_remove.insert(head.index());
+            }
+        }
+
+        // Remove all of the copy statements themselves, we turn them into
+        // `nop`s which are then cleaned up by the `compact_statements` pass.
+        for block in body.basic_blocks.blocks_mut() {
+            block.statements.retain(|statement| match statement.kind {
+                StatementKind::Live(local) | StatementKind::Dead(local) => {
+                    !storage_to_remove.contains(local.index())
+                }
+                _ => true,
+            });
+
+            for statement in block.statements.iter_mut() {
+                if let StatementKind::Assign(place, _) = statement.kind
+                    && let Some(local) = place.as_local()
+                    && copies[local] != local
+                {
+                    statement.kind = StatementKind::Nop;
+                }
+            }
+        }
+
+        let replacer = CopyReplacer { copies };
+        replacer.visit(body);
+    }
+}
+
+/// Records how a particular [Local] is assigned within the body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LocalAssignment {
+    /// The local has not been assigned (yet).
+    Unassigned,
+
+    /// The local is assigned exactly once at the given location, and
+    /// this location dominates all of the uses of the local.
+    Single(IrRef),
+
+    /// The local is assigned multiple times, or is used in a way which
+    /// means that it cannot be considered to be in single-assignment form.
+    Multiple,
+}
+
+/// A map of all of the [Local]s in a [Body], and whether they are
+/// in single-assignment form.
+struct SsaLocals {
+    /// The [Dominators] of the body, used to check whether the single
+    /// assignment of a [Local] dominates its uses.
+    dominators: Dominators<BasicBlock>,
+
+    /// The assignments of each [Local].
+    assignments: IndexVec<Local, LocalAssignment>,
+
+    /// The reverse post-order of the [BasicBlock]s that were visited.
+    order: Vec<BasicBlock>,
+}
+
+impl SsaLocals {
+    /// Compute the [SsaLocals] for the given [Body].
+    fn new(body: &Body) -> Self {
+        let mut this = Self {
+            dominators: body.basic_blocks.dominators(),
+            assignments: index_vec![LocalAssignment::Unassigned; body.locals.len()],
+            order: Vec::with_capacity(body.basic_blocks.len()),
+        };
+
+        // The return place is implicitly read by the `return` terminator, so
+        // we never consider it.
+        this.assignments[RETURN_PLACE] = LocalAssignment::Multiple;
+
+        // Arguments are assigned on the entry of the function.
+        for arg in body.args_iter() {
+            this.assign(arg, START_BLOCK.ref_to_start());
+        }
+
+        // Traverse the blocks in reverse post-order, which means that if an
+        // assignment dominates a use, the assignment is visited first.
+        let info = body.aux();
+        for (block, data) in traversal::ReversePostOrder::new_from_start(body) {
+            this.order.push(block);
+            this.visit_basic_block(block, data, &info);
+        }
+
+        this
+    }
+
+    /// Check whether the given [Local] is in single-assignment form.
+    fn is_ssa(&self, local: Local) -> bool {
+        matches!(self.assignments[local], LocalAssignment::Single(_))
+    }
+
+    /// Record an assignment to the given [Local].
+    fn assign(&mut self, local: Local, reference: IrRef) {
+        let assignment = &mut self.assignments[local];
+
+        *assignment = match assignment {
+            LocalAssignment::Unassigned => LocalAssignment::Single(reference),
+            _ => LocalAssignment::Multiple,
+        };
+    }
+
+    /// Record a use of the given [Local], if the assignment of the local
+    /// does not dominate the use, then the local is no longer considered
+    /// to be in single-assignment form.
+    fn use_local(&mut self, local: Local, reference: IrRef) {
+        let assignment = &mut self.assignments[local];
+
+        match *assignment {
+            LocalAssignment::Single(def) => {
+                let dominates = if def.block == reference.block {
+                    def.index <= reference.index
+                } else {
+                    self.dominators.is_dominated_by(reference.block, def.block)
+                };
+
+                if !dominates {
+                    *assignment = LocalAssignment::Multiple;
+                }
+            }
+            LocalAssignment::Unassigned => *assignment = LocalAssignment::Multiple,
+            LocalAssignment::Multiple => {}
+        }
+    }
+
+    /// Compute a map of each [Local] to the [Local] that it is a copy of. If
+    /// the [Local] is not a copy, then it maps to itself. Chains of copies
+    /// are resolved to the first [Local] in the chain.
+    fn compute_copies(&self, body: &Body) -> IndexVec<Local, Local> {
+        let mut copies: IndexVec<Local, Local> = body.locals.indices().collect();
+
+        // Since we visit blocks in reverse post-order, and the assignment of
+        // the source of a copy must dominate the copy, the source of the copy
+        // is always resolved before the copy itself.
+        for block in self.order.iter().copied() {
+            for statement in body.basic_blocks.blocks[block].statements.iter() {
+                let StatementKind::Assign(place, RValue::Use(Operand::Place(source))) =
+                    statement.kind
+                else {
+                    continue;
+                };
+
+                let (Some(dest), Some(source)) = (place.as_local(), source.as_local()) else {
+                    continue;
+                };
+
+                // We only eliminate temporaries, since user variables should be
+                // preserved for debugging purposes.
+                if dest == source
+                    || body.local_kind(dest) != LocalKind::Temp
+                    || !self.is_ssa(dest)
+                    || !self.is_ssa(source)
+                    || body.locals[dest].ty() != body.locals[source].ty()
+                {
+                    continue;
+                }
+
+                copies[dest] = copies[source];
+            }
+        }
+
+        copies
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for SsaLocals {
+    fn visit_assign_statement(&mut self, place: &Place, value: &RValue, ctx: &IrVisitorCtx<'_>) {
+        // We visit the value first, since any uses of the local within the value
+        // occur before the assignment.
+        self.visit_rvalue(value, ctx);
+
+        match place.as_local() {
+            Some(local) => self.assign(local, ctx.location),
+            None => self.visit_place(place, PlaceCtx::Mutable(MutablePlaceCtx::Store), ctx),
+        }
+    }
+
+    fn visit_place(&mut self, place: &Place, place_ctx: PlaceCtx, ctx: &IrVisitorCtx<'_>) {
+        for projection in ctx.info.projections.borrow(place.projections) {
+            if let PlaceProjection::Index(local) = projection {
+                self.use_local(*local, ctx.location);
+            }
+        }
+
+        // Mutating a local through a projection means that it is
+        // no longer in single-assignment form.
+        if place_ctx.is_mutating() && !place.projections.is_empty() {
+            self.assignments[place.local] = LocalAssignment::Multiple;
+            return;
+        }
+
+        self.visit_local(place.local, place_ctx, ctx.location);
+    }
+
+    fn visit_local(&mut self, local: Local, ctx: PlaceCtx, reference: IrRef) {
+        match ctx {
+            PlaceCtx::Immutable(
+                ImmutablePlaceCtx::Operand
+                | ImmutablePlaceCtx::Inspect
+                | ImmutablePlaceCtx::Projection,
+            ) => self.use_local(local, reference),
+
+            // Call destinations are assignments to the local.
+            PlaceCtx::Mutable(MutablePlaceCtx::Call) => self.assign(local, reference),
+
+            // Liveness does not affect the value of the local.
+            PlaceCtx::Meta(_) => {}
+
+            // Borrowing the local, or any other kind of mutation means that
+            // the local can't be considered to be in single-assignment form.
+            PlaceCtx::Immutable(ImmutablePlaceCtx::Ref) | PlaceCtx::Mutable(_) => {
+                self.assignments[local] = LocalAssignment::Multiple;
+            }
+        }
+    }
+}
+
+/// A visitor that re-writes all [Local]s that are copies to the source
+/// of the copy.
+struct CopyReplacer {
+    /// The map of each [Local] to the [Local] that it is a copy of.
+    copies: IndexVec<Local, Local>,
+}
+
+impl<'ir> ModifyingIrVisitor<'ir> for CopyReplacer {
+    fn visit_local(&self, local: &mut Local, _: PlaceCtx, _: IrRef) {
+        *local = self.copies[*local];
+    }
+}
@@ -0,0 +1,128 @@
+//! IR Optimisation pass that removes assignments to [Local]s which are
+//! never subsequently read. The lowering process often produces such
+//! assignments, for example when the result of a checked operation is
+//! stored into a tuple, but only the overflow flag of the tuple is ever
+//! read.
+//!
+//! The pass uses the results of the [Liveness] analysis in order to
+//! determine whether a [Local] is read after it is assigned to. Any
+//! [Local] that is borrowed is never considered since the value may
+//! be read through the reference.
+
+use fixedbitset::FixedBitSet;
+use hash_ir::{
+    ir::{Body, IrRef, Local, Place, RValue, StatementKind, RETURN_PLACE},
+    visitor::{IrVisitorCtx, IrVisitorMut, PlaceCtx},
+};
+use hash_ir_analysis::liveness::{Liveness, LivenessTransfer};
+
+use super::IrOptimisationPass;
+
+/// The [DeadStoreEliminationPass] is responsible for removing assignments
+/// to [Local]s that are never read.
+pub struct DeadStoreEliminationPass;
+
+impl IrOptimisationPass for DeadStoreEliminationPass {
+    fn name(&self) -> &'static str {
+        "optimise::dead_store_elimination"
+    }
+
+    fn optimise(&self, body: &mut Body) {
+        let borrowed = BorrowedLocals::compute(body);
+        let liveness = Liveness::compute(body);
+        let info = body.aux();
+
+        let mut dead_stores = vec![];
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            let mut live = liveness.live_out(block);
+
+            if let Some(terminator) = &data.terminator {
+                let ctx = IrVisitorCtx::new(IrRef::new(block, data.statements.len()), info);
+                LivenessTransfer::new(&mut live).visit_terminator(terminator, &ctx);
+            }
+
+            for (index, statement) in data.statements.iter().enumerate().rev() {
+                // If the assignment is to a local that is not live after the
+                // statement, then the store is dead. We don't apply the effects
+                // of the statement since it will be removed.
+                if let StatementKind::Assign(place, ref value) = statement.kind
+                    && let Some(local) = place.as_local()
+                    && local != RETURN_PLACE
+                    && !live.contains(local.index())
+                    && !borrowed.contains(local.index())
+                    && rvalue_is_removable(value)
+                {
+                    dead_stores.push(IrRef::new(block, index));
+                    continue;
+                }
+
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                LivenessTransfer::new(&mut live).visit_statement(statement, &ctx);
+            }
+        }
+
+        // Replace all of the dead stores with `nop`s, they will be removed
+        // by the `compact_statements` pass.
+        let blocks = body.basic_blocks.blocks_mut();
+
+        for IrRef { block, index } in dead_stores {
+            blocks[block].statements[index].kind = StatementKind::Nop;
+        }
+    }
+}
+
+/// Check whether an [RValue] can be removed without affecting the
+/// behaviour of the program.
+///
+/// All of the current [RValue]s are removable since computing them has no
+/// side-effects. Operations which can fail at runtime, i.e. an overflow or a
+/// division by zero, are checked by a separate `assert` terminator. The
+/// `assert` reads the condition of the check, so the store of the condition
+/// is always live, and removing the operation never removes the check. If
+/// the checks are disabled, then the failure is undefined behaviour, and so
+/// the operation can be removed. Calls and drops are terminators, and so they
+/// are never considered by this pass.
+fn rvalue_is_removable(value: &RValue) -> bool {
+    // @@Safety: the match is exhaustive so that any new RValue has to be
+    // considered here, if it has side-effects then it must be excluded.
+    match value {
+        RValue::Use(_)
+        | RValue::ConstOp(_, _)
+        | RValue::UnaryOp(_, _)
+        | RValue::BinaryOp(_, _)
+        | RValue::CheckedBinaryOp(_, _)
+        | RValue::Len(_)
+        | RValue::Ref(_, _, _)
+        | RValue::Aggregate(_, _)
+        | RValue::Discriminant(_)
+        | RValue::Cast(_, _, _)
+        | RValue::Repeat(_, _) => true,
+    }
+}
+
+/// A visitor that computes all of the [Local]s that are borrowed
+/// within a [Body].
+struct BorrowedLocals {
+    /// The set of locals that are borrowed.
+    borrowed: FixedBitSet,
+}
+
+impl BorrowedLocals {
+    /// Compute the set of borrowed [Local]s in the given [Body].
+    fn compute(body: &Body) -> FixedBitSet {
+        let mut this = Self { borrowed: FixedBitSet::with_capacity(body.locals.len()) };
+        this.visit(body);
+        this.borrowed
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for BorrowedLocals {
+    fn visit_place(&mut self, place: &Place, place_ctx: PlaceCtx, _: &IrVisitorCtx<'_>) {
+        if place_ctx.is_borrow() {
+            self.borrowed.insert(place.local.index());
+        }
+    }
+
+    fn visit_local(&mut self, _: Local, _: PlaceCtx, _: IrRef) {}
+}
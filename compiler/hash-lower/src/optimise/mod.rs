@@ -3,81 +3,251 @@
 //! pass is implemented as a function that takes a mutable reference to a
 //! `Body` and may modify the body by removing, or adding instructions and
 //! or basic blocks.
-//!
-//! @@Todo: write a constant value propagation pass.
 
-use hash_ir::{ir::Body, IrCtx};
+use hash_attrs::attr::OptimizeAttr;
+use hash_ir::ir::Body;
 use hash_pipeline::settings::{CompilerSettings, OptimisationLevel};
+use hash_target::data_layout::HasDataLayout;
 use hash_utils::profiling::{CellStageMetrics, HasMetrics};
 
+pub(crate) use self::const_propagation::propagate_constants;
+use crate::validate::{IrValidator, ValidationError, ValidationStage};
+
 // Various passes that are used to optimise the generated IR bodies.
 mod cleanup_locals;
+mod compact_statements;
+mod const_propagation;
+mod copy_propagation;
+mod dead_store_elimination;
 mod simplify_graph;
+mod strip_assumptions;
+mod value_ranges;
 
-pub trait IrOptimisationPass {
+/// An optimisation pass which operates on a single [Body]. Passes may only
+/// modify the body that they are given, since bodies are optimised in
+/// parallel.
+pub trait IrOptimisationPass: Send + Sync {
     /// Get the name of the particular optimisation pass.
     fn name(&self) -> &'static str;
 
     /// Check if this optimisation pas is enabled with accordance to
-    /// the current [LoweringSettings].
-    fn enabled(&self, settings: &CompilerSettings) -> bool {
-        settings.optimisation_level > OptimisationLevel::Debug
+    /// the current [LoweringSettings], and the [OptimisationLevel] of the
+    /// body that is being optimised. The level of the body is the level of
+    /// the compilation unless it is overridden by an `#optimize` attribute.
+    ///
+    /// Passes that are enabled at [OptimisationLevel::Debug] are mandatory,
+    /// they also run on bodies that are marked with `#optimize("none")`.
+    fn enabled(&self, _settings: &CompilerSettings, level: OptimisationLevel) -> bool {
+        level > OptimisationLevel::Debug
     }
 
     /// Perform the optimisation pass on the body.
-    fn optimise(&self, body: &mut Body, store: &IrCtx);
+    fn optimise(&self, body: &mut Body);
 }
 
 /// The optimiser is responsible for running all of the optimisation passes.
 /// Since all bodies are already lowered, and they have no interdependencies,
-/// we can run all of the optimisation passes on each body in parallel.
+/// we can run all of the optimisation passes on each body in parallel. The
+/// optimiser itself holds no mutable state, so it can be shared between all
+/// of the threads that are optimising bodies.
 pub struct Optimiser<'ir> {
-    store: &'ir IrCtx,
-
     /// Stores all of the lowering settings that are used to
     /// determine which passes are enabled.
     settings: &'ir CompilerSettings,
 
     /// The various passes that have been added to the optimisation
     /// pipeline.
-    passes: Vec<Box<dyn IrOptimisationPass + Send>>,
-
-    /// Metrics for each of the passes.
-    metrics: CellStageMetrics,
+    passes: Vec<Box<dyn IrOptimisationPass>>,
 }
 
 impl<'ir> Optimiser<'ir> {
-    pub fn new(store: &'ir IrCtx, settings: &'ir CompilerSettings) -> Self {
+    pub fn new(settings: &'ir CompilerSettings) -> Self {
         Self {
-            store,
             settings,
             passes: vec![
+                Box::new(strip_assumptions::StripAssumptionsPass),
+                Box::new(value_ranges::ValueRangePass::new(settings.data_layout().pointer_size)),
                 Box::new(simplify_graph::SimplifyGraphPass),
+                Box::new(copy_propagation::CopyPropagationPass),
+                Box::new(dead_store_elimination::DeadStoreEliminationPass),
                 Box::new(cleanup_locals::CleanupLocalPass),
+                Box::new(compact_statements::CompactStatementsPass),
             ],
-            metrics: CellStageMetrics::default(),
         }
     }
 
     /// Optimise a specific body. This will run all of the optimisation passes
     /// on the body.
-    pub fn optimise(&self, body: &mut Body) {
+    ///
+    /// If IR validation is enabled, the body is validated after each pass. If
+    /// a pass produces an invalid body, then no further passes are run on
+    /// the body, and the validation errors are recorded in the returned
+    /// [BodyOptimisation].
+    pub(crate) fn optimise(&self, body: &mut Body) -> BodyOptimisation {
+        let mut optimisation = BodyOptimisation::default();
+        let level = self.optimisation_level_of(body);
+
         for pass in self.passes.iter() {
-            if pass.enabled(self.settings) {
-                self.record(pass.name(), |this| {
-                    pass.optimise(body, this.store);
-                })
+            if pass.enabled(self.settings, level) {
+                optimisation.record(pass.name(), |_| pass.optimise(body));
+
+                if self.settings.lowering_settings.validate
+                    && let Err(errors) =
+                        IrValidator::new(body, ValidationStage::Pass(pass.name())).validate()
+                {
+                    optimisation.errors = errors;
+                    break;
+                }
             }
         }
+
+        optimisation
+    }
+
+    /// Compute the [OptimisationLevel] that should be used for the given
+    /// [Body]. If the item that the body was lowered from specifies an
+    /// `#optimize` attribute, then it overrides the level of the compilation.
+    /// Bodies that shouldn't be optimised at all are given the
+    /// [OptimisationLevel::Debug] level, so that only the mandatory passes
+    /// are run on them.
+    pub(crate) fn optimisation_level_of(&self, body: &Body) -> OptimisationLevel {
+        match OptimizeAttr::on_node(body.origin()) {
+            Some(OptimizeAttr::None) => OptimisationLevel::Debug,
+            Some(OptimizeAttr::Speed) => OptimisationLevel::Release,
+            Some(OptimizeAttr::Size) => OptimisationLevel::Size,
+            None => self.settings.optimisation_level,
+        }
     }
+}
+
+/// The outcome of optimising a single [Body].
+#[derive(Default)]
+pub(crate) struct BodyOptimisation {
+    /// Metrics for each of the passes that were run on the body.
+    metrics: CellStageMetrics,
+
+    /// Any errors that were found when validating the body after a pass.
+    pub errors: Vec<ValidationError>,
+}
 
+impl BodyOptimisation {
     pub fn into_metrics(self) -> CellStageMetrics {
         self.metrics
     }
 }
 
-impl HasMetrics for Optimiser<'_> {
+impl HasMetrics for BodyOptimisation {
     fn metrics(&self) -> &CellStageMetrics {
         &self.metrics
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use hash_ast::ast::AstNodeId;
+    use hash_ir::{
+        ir::{
+            BasicBlock, BasicBlockData, Body, BodyMetadata, BodySource, LocalDecl, LocalDecls,
+            Projections, Statement, StatementKind, Terminator, TerminatorKind,
+        },
+        ty::{Mutability, COMMON_REPR_TYS},
+    };
+    use hash_pipeline::settings::{CompilerSettings, OptimisationLevel};
+    use hash_utils::index_vec::index_vec;
+
+    use super::{IrOptimisationPass, Optimiser};
+    use crate::validate::{ValidationErrorKind, ValidationStage};
+
+    /// A pass that makes the body invalid by jumping to a block that
+    /// doesn't exist.
+    struct BreakTargetPass;
+
+    impl IrOptimisationPass for BreakTargetPass {
+        fn name(&self) -> &'static str {
+            "break_target"
+        }
+
+        fn enabled(&self, _: &CompilerSettings, _: OptimisationLevel) -> bool {
+            true
+        }
+
+        fn optimise(&self, body: &mut Body) {
+            let block = &mut body.basic_blocks.blocks_mut()[BasicBlock::new(0)];
+            block.terminator.as_mut().unwrap().kind = TerminatorKind::Goto(BasicBlock::new(1));
+        }
+    }
+
+    /// A pass that leaves a `nop` in the body, so that we can check whether
+    /// it was run.
+    struct MarkerPass;
+
+    impl IrOptimisationPass for MarkerPass {
+        fn name(&self) -> &'static str {
+            "marker"
+        }
+
+        fn enabled(&self, _: &CompilerSettings, _: OptimisationLevel) -> bool {
+            true
+        }
+
+        fn optimise(&self, body: &mut Body) {
+            let block = &mut body.basic_blocks.blocks_mut()[BasicBlock::new(0)];
+            block
+                .statements
+                .push(Statement { kind: StatementKind::Nop, origin: AstNodeId::null() });
+        }
+    }
+
+    fn empty_body() -> Body {
+        let terminator = Terminator { kind: TerminatorKind::Return, origin: AstNodeId::null() };
+        let blocks = index_vec![BasicBlockData::new(Some(terminator))];
+
+        let mut locals = LocalDecls::new();
+        locals.push(LocalDecl::new_auxiliary(COMMON_REPR_TYS.unit, Mutability::Mutable));
+
+        let info = BodyMetadata::new("broken".into(), BodySource::Item);
+        Body::new(blocks, locals, Projections::new(), info, 0, AstNodeId::null())
+    }
+
+    #[test]
+    fn test_invalid_pass_stops_optimisation() {
+        let mut settings = CompilerSettings::default();
+        settings.lowering_settings.validate = true;
+
+        let optimiser = Optimiser {
+            settings: &settings,
+            passes: vec![Box::new(MarkerPass), Box::new(BreakTargetPass), Box::new(MarkerPass)],
+        };
+
+        let mut body = empty_body();
+        let optimisation = optimiser.optimise(&mut body);
+
+        // The error is attributed to the pass that broke the body...
+        assert_eq!(optimisation.errors.len(), 1);
+        let error = optimisation.errors[0];
+        assert_eq!(error.stage, ValidationStage::Pass("break_target"));
+        assert_eq!(
+            error.kind,
+            ValidationErrorKind::UnknownTarget { target: BasicBlock::new(1), count: 1 }
+        );
+
+        // ...and the passes after it are skipped.
+        assert_eq!(body.blocks()[BasicBlock::new(0)].statements.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_pass_without_validation() {
+        let settings = CompilerSettings::default();
+        let optimiser = Optimiser {
+            settings: &settings,
+            passes: vec![Box::new(BreakTargetPass), Box::new(MarkerPass)],
+        };
+
+        // Without validation, all of the passes are run and nothing is reported.
+        let mut body = empty_body();
+        let optimisation = optimiser.optimise(&mut body);
+
+        assert!(optimisation.errors.is_empty());
+        assert_eq!(body.blocks()[BasicBlock::new(0)].statements.len(), 1);
+    }
+}
@@ -13,7 +13,7 @@
 
 use hash_ir::{
     ir::{BasicBlock, BasicBlockData, Body, Terminator, TerminatorKind, START_BLOCK},
-    traversal, IrCtx,
+    traversal,
 };
 use hash_pipeline::settings::{CompilerSettings, OptimisationLevel};
 use hash_utils::{
@@ -97,11 +97,11 @@ impl IrOptimisationPass for SimplifyGraphPass {
         "optimise::simplify_graph"
     }
 
-    fn enabled(&self, settings: &CompilerSettings) -> bool {
-        settings.optimisation_level >= OptimisationLevel::Debug
+    fn enabled(&self, _: &CompilerSettings, level: OptimisationLevel) -> bool {
+        level >= OptimisationLevel::Debug
     }
 
-    fn optimise(&self, body: &mut Body, _: &IrCtx) {
+    fn optimise(&self, body: &mut Body) {
         GraphSimplifier::new(body).simplify();
 
         // Now we can remove the blocks that we no longer need.
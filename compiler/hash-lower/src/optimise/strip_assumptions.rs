@@ -0,0 +1,39 @@
+//! IR Optimisation pass that removes all of the `assume(..)` statements
+//! within a [Body]. The assumptions are replaced with `nop` statements which
+//! are later removed by the statement compaction pass, and any locals that
+//! were only used by the assumptions are then cleaned up.
+//!
+//! This pass is only enabled with the `--ir-strip-assumptions` setting, which
+//! is useful when the assumptions that are derived from checked operations
+//! should not be exploited by the code generation backend.
+
+use hash_ir::ir::{Body, StatementKind};
+use hash_pipeline::settings::{CompilerSettings, OptimisationLevel};
+
+use super::IrOptimisationPass;
+
+/// The [StripAssumptionsPass] is responsible for removing all of the
+/// `assume(..)` statements from the [Body].
+pub struct StripAssumptionsPass;
+
+impl IrOptimisationPass for StripAssumptionsPass {
+    fn name(&self) -> &'static str {
+        "optimise::strip_assumptions"
+    }
+
+    /// Pass [StripAssumptionsPass] is only enabled if the assumptions were
+    /// explicitly requested to be stripped.
+    fn enabled(&self, settings: &CompilerSettings, _: OptimisationLevel) -> bool {
+        settings.lowering_settings.strip_assumptions
+    }
+
+    fn optimise(&self, body: &mut Body) {
+        for block in body.basic_blocks.blocks_mut() {
+            for statement in block.statements.iter_mut() {
+                if let StatementKind::Assume(_) = statement.kind {
+                    statement.kind = StatementKind::Nop;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! IR Optimisation pass that uses the value ranges of [Local]s to simplify
+//! the [Body]. The ranges are computed by the [ValueRangeAnalysis], and are
+//! then used to:
+//!
+//! 1. Replace comparisons that always yield the same result with a constant.
+//!
+//! 2. Remove the targets of a `switch` that can never be taken, if only a
+//!    single target remains then the `switch` is replaced with a `goto`.
+//!
+//! 3. Remove `assert`s, for example bounds checks, whose condition is known to
+//!    always hold.
+//!
+//! Any blocks that become unreachable are later removed by the
+//! [super::simplify_graph::SimplifyGraphPass].
+
+use hash_ir::{
+    ir::{BasicBlock, Body, Const, IrRef, RValue, StatementKind, SwitchTargets, TerminatorKind},
+    visitor::IrVisitorCtx,
+};
+use hash_ir_analysis::{ranges::ValueRangeAnalysis, Analysis};
+use hash_target::size::Size;
+
+use super::IrOptimisationPass;
+
+/// The [ValueRangePass] is responsible for simplifying the [Body] using the
+/// results of the [ValueRangeAnalysis].
+pub struct ValueRangePass {
+    /// The size of a pointer on the target.
+    ptr_size: Size,
+}
+
+impl ValueRangePass {
+    pub fn new(ptr_size: Size) -> Self {
+        Self { ptr_size }
+    }
+}
+
+impl IrOptimisationPass for ValueRangePass {
+    fn name(&self) -> &'static str {
+        "optimise::value_ranges"
+    }
+
+    fn optimise(&self, body: &mut Body) {
+        let results = ValueRangeAnalysis::compute(body, self.ptr_size);
+        let analysis = &results.analysis;
+        let info = body.aux();
+
+        let mut comparisons: Vec<(IrRef, bool)> = Vec::new();
+        let mut terminators: Vec<(BasicBlock, TerminatorKind)> = Vec::new();
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            let mut state = results.entry_set(block).clone();
+
+            for (index, statement) in data.statements.iter().enumerate() {
+                if !state.is_reachable() {
+                    break;
+                }
+
+                if let StatementKind::Assign(_, RValue::BinaryOp(op, operands)) = &statement.kind
+                    && op.is_comparator()
+                    && let Some(value) =
+                        analysis.evaluate_comparison(&state, *op, &operands.0, &operands.1, &info)
+                {
+                    comparisons.push((IrRef::new(block, index), value));
+                }
+
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                analysis.apply_statement(&mut state, statement, &ctx);
+            }
+
+            let Some(terminator) = &data.terminator else { continue };
+
+            if !state.is_reachable() {
+                continue;
+            }
+
+            match &terminator.kind {
+                TerminatorKind::Switch { value, targets } => {
+                    let Some(range) = analysis.range_of_operand(&state, value, &info) else {
+                        continue;
+                    };
+
+                    let ty = value.ty(&info);
+                    let possible = |value: u128| {
+                        analysis.switch_value(value, ty).is_some_and(|value| range.contains(value))
+                    };
+
+                    // If the value is known, then we can jump straight to the
+                    // corresponding target.
+                    if let Some(value) = range.as_singleton()
+                        && let Some((_, target)) = targets
+                            .iter()
+                            .find(|(target, _)| analysis.switch_value(*target, ty) == Some(value))
+                    {
+                        terminators.push((block, TerminatorKind::Goto(target)));
+                        continue;
+                    }
+
+                    if targets.iter().all(|(value, _)| possible(value)) {
+                        continue;
+                    }
+
+                    let remaining: Vec<_> =
+                        targets.iter().filter(|(value, _)| possible(*value)).collect();
+
+                    // If none of the targets are possible and there is no
+                    // `otherwise` block, then the switch can never be reached.
+                    let kind = if remaining.is_empty() && targets.has_otherwise() {
+                        TerminatorKind::Goto(targets.otherwise())
+                    } else if remaining.is_empty() {
+                        TerminatorKind::Unreachable
+                    } else {
+                        TerminatorKind::Switch {
+                            value: *value,
+                            targets: SwitchTargets::new(remaining.into_iter(), targets.otherwise),
+                        }
+                    };
+
+                    terminators.push((block, kind));
+                }
+                TerminatorKind::Assert { condition, expected, target, .. } => {
+                    let known = analysis
+                        .range_of_operand(&state, condition, &info)
+                        .and_then(|range| range.as_singleton());
+
+                    if known == Some(*expected as i128) {
+                        terminators.push((block, TerminatorKind::Goto(*target)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if comparisons.is_empty() && terminators.is_empty() {
+            return;
+        }
+
+        let blocks = body.basic_blocks.blocks_mut();
+
+        for (location, value) in comparisons {
+            if let StatementKind::Assign(_, rvalue) =
+                &mut blocks[location.block].statements[location.index].kind
+            {
+                *rvalue = Const::bool(value).into();
+            }
+        }
+
+        for (block, kind) in terminators {
+            blocks[block].terminator_mut().kind = kind;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hash_ast::ast::AstNodeId;
+    use hash_ir::{
+        ir::{
+            BasicBlock, BasicBlockData, Body, BodyMetadata, BodySource, Const, LocalDecl,
+            LocalDecls, Operand, Projections, SwitchTargets, Terminator, TerminatorKind,
+        },
+        ty::{Mutability, COMMON_REPR_TYS},
+    };
+    use hash_target::{data_layout::TargetDataLayout, size::Size};
+    use hash_utils::index_vec::index_vec;
+
+    use super::ValueRangePass;
+    use crate::optimise::IrOptimisationPass;
+
+    /// Create a body which switches on the constant `3_u8`, with a single
+    /// target for the value `1`.
+    fn switch_body(otherwise: Option<BasicBlock>) -> Body {
+        let terminator = |kind| Some(Terminator { kind, origin: AstNodeId::null() });
+
+        let value = Const::from_scalar_like(3, COMMON_REPR_TYS.u8, &TargetDataLayout::default());
+        let targets = SwitchTargets::new([(1, BasicBlock::new(1))].into_iter(), otherwise);
+        let blocks = index_vec![
+            BasicBlockData::new(terminator(TerminatorKind::Switch {
+                value: Operand::Const(value),
+                targets,
+            })),
+            BasicBlockData::new(terminator(TerminatorKind::Return)),
+            BasicBlockData::new(terminator(TerminatorKind::Return)),
+        ];
+
+        let mut locals = LocalDecls::new();
+        locals.push(LocalDecl::new_auxiliary(COMMON_REPR_TYS.unit, Mutability::Mutable));
+
+        let info = BodyMetadata::new("switch".into(), BodySource::Item);
+        Body::new(blocks, locals, Projections::new(), info, 0, AstNodeId::null())
+    }
+
+    fn terminator_of_entry(body: &Body) -> &TerminatorKind {
+        &body.blocks()[BasicBlock::new(0)].terminator.as_ref().unwrap().kind
+    }
+
+    #[test]
+    fn test_impossible_switch_jumps_to_otherwise() {
+        let mut body = switch_body(Some(BasicBlock::new(2)));
+        ValueRangePass::new(Size::from_bytes(8)).optimise(&mut body);
+
+        assert_eq!(terminator_of_entry(&body), &TerminatorKind::Goto(BasicBlock::new(2)));
+    }
+
+    #[test]
+    fn test_impossible_switch_without_otherwise() {
+        let mut body = switch_body(None);
+        ValueRangePass::new(Size::from_bytes(8)).optimise(&mut body);
+
+        assert_eq!(terminator_of_entry(&body), &TerminatorKind::Unreachable);
+    }
+}
@@ -2,17 +2,20 @@
 //! only wraps the logic that is present in TIR lowering.
 
 use hash_ast::ast::AstNodeId;
-use hash_attrs::builtin::attrs;
+use hash_attrs::{attr::DeriveAttr, builtin::attrs};
 use hash_ir::{intrinsics::Intrinsic, lang_items::LangItem, ty::InstanceHelpers, HasIrCtx};
-use hash_repr::ty::{Instance, ReprTy, ReprTyId, ReprTyListId};
-use hash_storage::store::statics::{SingleStoreValue, StoreId};
+use hash_repr::ty::{
+    Instance, Mutability, RefKind, ReprTy, ReprTyId, ReprTyListId, COMMON_REPR_TYS,
+};
+use hash_source::identifier::Identifier;
+use hash_storage::store::statics::{SequenceStoreValue, SingleStoreValue, StoreId};
 use hash_tir::{
     intrinsics::{definitions::Intrinsic as TirIntrinsic, make::IsIntrinsic},
-    tir::{DataTy, FnDefId, FnTy, NodesId, TyId},
+    tir::{Arg, DataDefId, DataTy, FnDefId, FnTy, HasAstNodeId, Node, NodeId, NodesId, TyId},
 };
 use hash_tir_utils::lower::{ShouldCache, TyLower};
 
-use crate::ctx::BuilderCtx;
+use crate::{build::DerivedItem, ctx::BuilderCtx};
 
 impl<'ir> BuilderCtx<'ir> {
     pub(crate) fn repr_ty_from_tir_ty(&self, id: TyId) -> ReprTyId {
@@ -86,4 +89,54 @@ impl<'ir> BuilderCtx<'ir> {
 
         instance
     }
+
+    /// Create all of the items that are requested by the `#derive(...)`
+    /// attribute on the given [DataDefId]. This only creates the [Instance]s
+    /// of the items, and registers them so that they can be referenced by
+    /// other bodies. The bodies themselves are built later on.
+    pub(crate) fn create_derived_items(&self, data_def: DataDefId) -> Vec<DerivedItem> {
+        let origin = data_def.node_id_ensured();
+        let subject = self.repr_ty_from_tir_data_ty(DataTy {
+            args: Node::create_at(Node::<Arg>::empty_seq(), data_def.origin()),
+            data_def,
+        });
+
+        let subject_ref =
+            ReprTy::create(ReprTy::Ref(subject, Mutability::Immutable, RefKind::Normal));
+        let subject_mut_ref =
+            ReprTy::create(ReprTy::Ref(subject, Mutability::Mutable, RefKind::Normal));
+        let buf =
+            ReprTy::create(ReprTy::Ref(COMMON_REPR_TYS.u8, Mutability::Immutable, RefKind::Raw));
+        let name = data_def.borrow().name.ident();
+
+        DeriveAttr::on_node(origin)
+            .into_iter()
+            .map(|item| {
+                let (params, ret_ty) = match item {
+                    DeriveAttr::Eq => {
+                        (ReprTyListId::seq([subject_ref, subject_ref]), COMMON_REPR_TYS.bool)
+                    }
+                    DeriveAttr::Hash => (ReprTyListId::seq([subject_ref]), COMMON_REPR_TYS.u64),
+                    DeriveAttr::Debug => (ReprTyListId::seq([subject_ref]), COMMON_REPR_TYS.unit),
+                    DeriveAttr::Serialize => (
+                        ReprTyListId::seq([subject_ref, buf, COMMON_REPR_TYS.usize]),
+                        COMMON_REPR_TYS.usize,
+                    ),
+                    DeriveAttr::Deserialize => (
+                        ReprTyListId::seq([buf, COMMON_REPR_TYS.usize, subject_mut_ref]),
+                        COMMON_REPR_TYS.usize,
+                    ),
+                };
+
+                let ident = Identifier::from(format!("{name}::{item}"));
+                let instance = Instance::new(ident, Some(origin.source()), params, ret_ty, origin);
+
+                let instance = Instance::create(instance);
+                let ty = ReprTy::create(ReprTy::FnDef { instance });
+                self.ir_ctx().derived_items_mut().set(subject, item, instance, ty);
+
+                DerivedItem { subject, item, ty, origin }
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,331 @@
+//! Implements a definite initialisation analysis on lowered IR [Body]s, any
+//! variable that is read on some path through the [Body] before it has been
+//! assigned to is reported.
+//!
+//! The analysis tracks the initialisation of each [MovePath] of the
+//! variables, which is either a variable itself or a field of a variable
+//! that is accessed within the body. On entry to the body, all of the
+//! variables that are defined within the body are uninitialised. Assigning
+//! to a path initialises it and all of the paths within it, so initialising
+//! a field of a variable doesn't initialise the whole variable. The
+//! destination of a call is only initialised once the call returns, and
+//! not when it unwinds.
+//!
+//! Writing through a reference or an index of a path requires that the path
+//! is already initialised, and so does taking a reference to a path.
+//!
+//! @@Future: temporaries are not checked since they are created by the
+//! lowering process itself.
+
+use fixedbitset::FixedBitSet;
+use hash_ast::ast::AstNodeId;
+use hash_ir::{
+    ir::{
+        BasicBlock, Body, BodyInfo, IrRef, Local, LocalKind, Place, PlaceProjection, Statement,
+        StatementKind, Terminator, TerminatorKind,
+    },
+    visitor::{IrVisitorCtx, IrVisitorMut, MutablePlaceCtx, PlaceCtx},
+};
+use hash_ir_analysis::{Analysis, Direction, Results};
+use hash_utils::fxhash::FxHashMap;
+
+use crate::diagnostics::LoweringDiagnostic;
+
+/// The [UninitChecker] walks over a [Body] and reports any reads of
+/// variables that may not have been initialised.
+pub(crate) struct UninitChecker<'ir> {
+    /// The body that is being checked.
+    body: &'ir Body,
+}
+
+impl<'ir> UninitChecker<'ir> {
+    /// Create a new [UninitChecker] for the given [Body].
+    pub(crate) fn new(body: &'ir Body) -> Self {
+        Self { body }
+    }
+
+    /// Check the [Body] for any reads of uninitialised variables, at most
+    /// one read is reported for each variable.
+    pub(crate) fn check(&self) -> Vec<LoweringDiagnostic> {
+        let body = self.body;
+        let mut diagnostics = Vec::new();
+
+        let paths = MovePaths::new(body);
+
+        if paths.paths.is_empty() {
+            return diagnostics;
+        }
+
+        let results = Results::compute(MaybeUninit { body, paths: &paths }, body);
+        let info = body.aux();
+        let mut reported = FixedBitSet::with_capacity(body.locals.len());
+
+        let mut report = |state: &FixedBitSet, reads: Vec<Place>, origin: AstNodeId| {
+            for place in reads {
+                let Some((path, _)) = paths.lookup(&place, &info) else {
+                    continue;
+                };
+
+                if state.contains(path) && !reported.put(place.local.index()) {
+                    let name = body.locals[place.local].name.unwrap();
+                    diagnostics.push(LoweringDiagnostic::UninitialisedVariable { name, origin });
+                }
+            }
+        };
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            let mut state = results.entry_set(block).clone();
+
+            // The reads of a statement are checked before the effect of the
+            // statement is applied, i.e. `x = x + 1` reads `x` before it is
+            // initialised.
+            for (index, statement) in data.statements.iter().enumerate() {
+                let ctx = IrVisitorCtx::new(IrRef::new(block, index), info);
+                report(&state, PlaceReads::of_statement(statement, &paths, &ctx), statement.origin);
+                results.analysis.apply_statement(&mut state, statement, &ctx);
+            }
+
+            if let Some(terminator) = &data.terminator {
+                let ctx = IrVisitorCtx::new(IrRef::new(block, data.statements.len()), info);
+                report(
+                    &state,
+                    PlaceReads::of_terminator(terminator, &paths, &ctx),
+                    terminator.origin,
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// A part of a variable whose initialisation is tracked, this is either the
+/// variable itself, or a (nested) field of the variable.
+struct MovePath {
+    /// The field and downcast projections of the path.
+    projections: Vec<PlaceProjection>,
+
+    /// The paths that are directly contained within this path.
+    children: Vec<usize>,
+}
+
+/// All of the [MovePath]s of the variables that are accessed within a
+/// [Body].
+#[derive(Default)]
+struct MovePaths {
+    /// The paths that are tracked.
+    paths: Vec<MovePath>,
+
+    /// The paths of each of the variables.
+    locals: FxHashMap<Local, Vec<usize>>,
+}
+
+impl MovePaths {
+    /// Collect all of the [MovePath]s that are accessed within the [Body].
+    fn new(body: &Body) -> Self {
+        let mut collector = PathCollector { body, paths: Self::default() };
+        collector.visit(body);
+        collector.paths
+    }
+
+    /// Split the projections of a [Place] into the projections of the
+    /// [MovePath] that it accesses, and whether the path is accessed
+    /// through a reference or an index.
+    fn split(projections: &[PlaceProjection]) -> (&[PlaceProjection], bool) {
+        let tracked = projections
+            .iter()
+            .take_while(|projection| {
+                matches!(projection, PlaceProjection::Field(_) | PlaceProjection::Downcast(_))
+            })
+            .count();
+
+        (&projections[..tracked], tracked < projections.len())
+    }
+
+    /// Find the [MovePath] of the given projections of a [Local].
+    fn find(&self, local: Local, projections: &[PlaceProjection]) -> Option<usize> {
+        self.locals
+            .get(&local)?
+            .iter()
+            .copied()
+            .find(|path| self.paths[*path].projections == projections)
+    }
+
+    /// Add the [MovePath] of the given projections of a [Local], and all of
+    /// the paths that contain it.
+    fn insert(&mut self, local: Local, projections: &[PlaceProjection]) -> usize {
+        if let Some(path) = self.find(local, projections) {
+            return path;
+        }
+
+        let parent = projections.split_last().map(|(_, rest)| self.insert(local, rest));
+        let path = self.paths.len();
+
+        self.paths.push(MovePath { projections: projections.to_vec(), children: vec![] });
+        self.locals.entry(local).or_default().push(path);
+
+        if let Some(parent) = parent {
+            self.paths[parent].children.push(path);
+        }
+
+        path
+    }
+
+    /// Find the [MovePath] that is accessed by the given [Place], and
+    /// whether it is accessed through a reference or an index.
+    fn lookup(&self, place: &Place, info: &BodyInfo<'_>) -> Option<(usize, bool)> {
+        let projections = info.projections.borrow(place.projections);
+        let (tracked, indirect) = Self::split(projections);
+        self.find(place.local, tracked).map(|path| (path, indirect))
+    }
+
+    /// Mark the given [MovePath] and all of the paths within it as
+    /// initialised.
+    fn initialise(&self, state: &mut FixedBitSet, path: usize) {
+        state.set(path, false);
+
+        for child in &self.paths[path].children {
+            self.initialise(state, *child);
+        }
+    }
+}
+
+/// Collects the [MovePath]s of all of the places of variables within a
+/// [Body].
+struct PathCollector<'ir> {
+    body: &'ir Body,
+    paths: MovePaths,
+}
+
+impl<'ir> IrVisitorMut<'ir> for PathCollector<'ir> {
+    fn visit_place(&mut self, place: &Place, _: PlaceCtx, ctx: &IrVisitorCtx<'_>) {
+        let projections = ctx.info.projections.borrow(place.projections);
+
+        if self.body.local_kind(place.local) == LocalKind::Var {
+            let (tracked, _) = MovePaths::split(projections);
+            self.paths.insert(place.local, tracked);
+        }
+
+        for projection in projections {
+            if let PlaceProjection::Index(local) = projection {
+                if self.body.local_kind(*local) == LocalKind::Var {
+                    self.paths.insert(*local, &[]);
+                }
+            }
+        }
+    }
+}
+
+/// The forward analysis that computes the set of [MovePath]s that may be
+/// uninitialised at each point of the [Body].
+struct MaybeUninit<'a> {
+    body: &'a Body,
+    paths: &'a MovePaths,
+}
+
+impl MaybeUninit<'_> {
+    /// Initialise the [MovePath] that is written to by the given [Place],
+    /// writes through a reference or an index of a path don't initialise it.
+    fn initialise(&self, state: &mut FixedBitSet, place: &Place, info: &BodyInfo<'_>) {
+        if let Some((path, false)) = self.paths.lookup(place, info) {
+            self.paths.initialise(state, path);
+        }
+    }
+}
+
+impl Analysis for MaybeUninit<'_> {
+    type Domain = FixedBitSet;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn bottom(&self, _: &Body) -> Self::Domain {
+        FixedBitSet::with_capacity(self.paths.paths.len())
+    }
+
+    fn initialise_entry(&self, _: &Body, state: &mut Self::Domain) {
+        state.insert_range(..);
+    }
+
+    fn apply_statement(
+        &self,
+        state: &mut Self::Domain,
+        statement: &Statement,
+        ctx: &IrVisitorCtx<'_>,
+    ) {
+        match &statement.kind {
+            StatementKind::Assign(place, _) | StatementKind::Discriminate(place, _) => {
+                self.initialise(state, place, &ctx.info)
+            }
+            StatementKind::Nop
+            | StatementKind::Live(_)
+            | StatementKind::Dead(_)
+            | StatementKind::Assume(_) => {}
+        }
+    }
+
+    fn apply_terminator(&self, _: &mut Self::Domain, _: &Terminator, _: &IrVisitorCtx<'_>) {}
+
+    fn apply_edge(&self, state: &mut Self::Domain, terminator: &Terminator, target: BasicBlock) {
+        // The destination of a call is only written to once the call returns.
+        if let TerminatorKind::Call { destination, target: Some(returns), .. } = &terminator.kind {
+            if *returns == target {
+                self.initialise(state, destination, &self.body.aux());
+            }
+        }
+    }
+}
+
+/// Collects all of the [Place]s that must be initialised when a statement
+/// or a terminator is executed.
+struct PlaceReads<'a> {
+    paths: &'a MovePaths,
+    reads: Vec<Place>,
+}
+
+impl<'a> PlaceReads<'a> {
+    fn of_statement(
+        statement: &Statement,
+        paths: &'a MovePaths,
+        ctx: &IrVisitorCtx<'_>,
+    ) -> Vec<Place> {
+        let mut this = Self { paths, reads: vec![] };
+        this.visit_statement(statement, ctx);
+        this.reads
+    }
+
+    fn of_terminator(
+        terminator: &Terminator,
+        paths: &'a MovePaths,
+        ctx: &IrVisitorCtx<'_>,
+    ) -> Vec<Place> {
+        let mut this = Self { paths, reads: vec![] };
+        this.visit_terminator(terminator, ctx);
+        this.reads
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for PlaceReads<'_> {
+    fn visit_place(&mut self, place: &Place, place_ctx: PlaceCtx, ctx: &IrVisitorCtx<'_>) {
+        for projection in ctx.info.projections.borrow(place.projections) {
+            if let PlaceProjection::Index(local) = projection {
+                self.reads.push(Place::from_local(*local));
+            }
+        }
+
+        match place_ctx {
+            // Drops are inserted by the lowering, and only drop locals that
+            // have been initialised.
+            PlaceCtx::Meta(_) | PlaceCtx::Mutable(MutablePlaceCtx::Drop) => {}
+            // Writing to a place only reads it if the write goes through a
+            // reference or an index of the place.
+            PlaceCtx::Mutable(
+                MutablePlaceCtx::Store | MutablePlaceCtx::Discriminant | MutablePlaceCtx::Call,
+            ) => {
+                if let Some((_, true)) = self.paths.lookup(place, &ctx.info) {
+                    self.reads.push(*place);
+                }
+            }
+            _ => self.reads.push(*place),
+        }
+    }
+}
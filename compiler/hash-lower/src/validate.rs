@@ -0,0 +1,428 @@
+//! Implements a sanity checker for lowered IR [Body]s. The [IrValidator]
+//! walks over a [Body] and verifies that the structural invariants of the IR
+//! hold, namely:
+//!
+//! - All [Local]s that are referenced within the body are declared.
+//!
+//! - All [BasicBlock]s have a terminator, and all of the targets of a
+//!   terminator exist.
+//!
+//! - The type of an assignment [RValue] matches the type of the [Place] that is
+//!   being assigned to.
+//!
+//! - All [PlaceProjection]s of a [Place] are valid for the type that they are
+//!   applied to.
+//!
+//! - The operand of an `assume(..)` statement is a boolean.
+//!
+//! The validator is only run when the `--validate-ir` flag is specified. It
+//! is run after the body has been lowered, and after each optimisation pass
+//! has been applied to the body. This is useful for catching malformed IR
+//! before it causes a crash in the code generation stage.
+
+use std::fmt;
+
+use hash_ast::ast::AstNodeId;
+use hash_ir::{
+    ir::{
+        BasicBlock, Body, IrRef, Local, Place, PlaceProjection, RValue, Statement, StatementKind,
+        Terminator,
+    },
+    ty::{AdtId, Mutability, PlaceTy, ReprTy, ReprTyId, COMMON_REPR_TYS},
+    visitor::{IrVisitorCtx, IrVisitorMut, PlaceCtx},
+};
+use hash_reporting::{
+    report::{ReportCodeBlock, ReportElement, ReportNote, ReportNoteKind},
+    reporter::{Reporter, Reports},
+};
+use hash_source::{identifier::Identifier, location::Span};
+use hash_storage::store::{statics::StoreId, SequenceStoreKey};
+
+/// The point in the pipeline at which a [Body] was validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValidationStage {
+    /// The body was validated after it was lowered.
+    Lowering,
+
+    /// The body was validated after the given optimisation pass.
+    Pass(&'static str),
+}
+
+impl fmt::Display for ValidationStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationStage::Lowering => write!(f, "after lowering"),
+            ValidationStage::Pass(name) => write!(f, "after `{name}`"),
+        }
+    }
+}
+
+/// The kind of invariant that was violated within a [Body].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValidationErrorKind {
+    /// A [Local] was referenced that is not declared in the body.
+    UndeclaredLocal { local: Local, count: usize },
+
+    /// A [BasicBlock] does not have a terminator.
+    MissingTerminator,
+
+    /// A terminator jumps to a [BasicBlock] that does not exist.
+    UnknownTarget { target: BasicBlock, count: usize },
+
+    /// The type of the assigned value does not match the type of the
+    /// [Place] that it is assigned to.
+    MismatchedAssign { place: ReprTyId, value: ReprTyId },
+
+    /// A [PlaceProjection] is applied to a type that does not support it.
+    InvalidProjection { ty: ReprTyId, projection: PlaceProjection },
+
+    /// The operand of an `assume(..)` statement is not a boolean.
+    NonBooleanAssumption { ty: ReprTyId },
+}
+
+impl fmt::Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationErrorKind::UndeclaredLocal { local, count } => {
+                write!(f, "local `{local:?}` is used, but only {count} locals are declared")
+            }
+            ValidationErrorKind::MissingTerminator => write!(f, "block has no terminator"),
+            ValidationErrorKind::UnknownTarget { target, count } => {
+                write!(f, "terminator jumps to `{target:?}`, but only {count} blocks exist")
+            }
+            ValidationErrorKind::MismatchedAssign { place, value } => {
+                write!(f, "value of type `{value}` is assigned to a place of type `{place}`")
+            }
+            ValidationErrorKind::InvalidProjection { ty, projection } => {
+                write!(f, "projection `{projection:?}` cannot be applied to type `{ty}`")
+            }
+            ValidationErrorKind::NonBooleanAssumption { ty } => {
+                write!(f, "assumption of type `{ty}` is not a boolean")
+            }
+        }
+    }
+}
+
+/// An error that is emitted when a [Body] does not adhere to the
+/// invariants of the IR.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ValidationError {
+    /// The name of the body that is invalid.
+    pub body: Identifier,
+
+    /// When the body was validated.
+    pub stage: ValidationStage,
+
+    /// The location within the body where the invariant is violated.
+    pub location: IrRef,
+
+    /// The source location of the offending statement or terminator.
+    pub span: Span,
+
+    /// The invariant that was violated.
+    pub kind: ValidationErrorKind,
+}
+
+impl From<ValidationError> for Reports {
+    fn from(error: ValidationError) -> Self {
+        let ValidationError { body, stage, location: IrRef { block, index }, span, kind } = error;
+        let mut reporter = Reporter::new();
+
+        reporter
+            .internal()
+            .title(format!("invalid IR in body `{body}` {stage}"))
+            .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                span,
+                format!("{kind}, in `{block:?}[{index}]`"),
+            )))
+            .add_element(ReportElement::Note(ReportNote::new(
+                ReportNoteKind::Note,
+                "the IR for this body is malformed, this is a compiler bug",
+            )));
+
+        reporter.into_reports()
+    }
+}
+
+/// The [IrValidator] verifies the structural invariants of a [Body], see
+/// the module documentation for the invariants that are checked.
+pub(crate) struct IrValidator<'ir> {
+    /// The body that is being validated.
+    body: &'ir Body,
+
+    /// When the body is being validated.
+    stage: ValidationStage,
+
+    /// All of the errors that have been found within the body.
+    errors: Vec<ValidationError>,
+}
+
+impl<'ir> IrValidator<'ir> {
+    /// Create a new [IrValidator] for the given [Body].
+    pub(crate) fn new(body: &'ir Body, stage: ValidationStage) -> Self {
+        Self { body, stage, errors: Vec::new() }
+    }
+
+    /// Validate the [Body], returning all of the invariants that are
+    /// violated within the body.
+    pub(crate) fn validate(mut self) -> Result<(), Vec<ValidationError>> {
+        let body = self.body;
+
+        for (block, data) in body.blocks().iter_enumerated() {
+            for (index, statement) in data.statements.iter().enumerate() {
+                self.validate_statement(statement, IrRef::new(block, index));
+            }
+
+            let location = IrRef::new(block, data.statements.len());
+
+            match &data.terminator {
+                Some(terminator) => self.validate_terminator(terminator, location),
+                None => self.report(
+                    location,
+                    body.source_span(),
+                    ValidationErrorKind::MissingTerminator,
+                ),
+            }
+        }
+
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    /// Record that an invariant was violated at the given location.
+    fn report(&mut self, location: IrRef, span: Span, kind: ValidationErrorKind) {
+        self.errors.push(ValidationError {
+            body: self.body.meta.name(),
+            stage: self.stage,
+            location,
+            span,
+            kind,
+        });
+    }
+
+    /// Validate a [Statement], all of the places within the statement must
+    /// be valid, and an assignment must not change the type of the place.
+    fn validate_statement(&mut self, statement: &Statement, location: IrRef) {
+        let span = statement.origin.span();
+
+        match &statement.kind {
+            StatementKind::Nop => {}
+            StatementKind::Assign(place, value) => {
+                let place_ty = self.validate_place(*place, location, span);
+                let value_is_valid = self.validate_rvalue(value, location, span);
+
+                // We can only compute the type of the value if all of the
+                // places that it uses are valid.
+                if let Some(place_ty) = place_ty
+                    && value_is_valid
+                {
+                    let value_ty = value.ty(&self.body.aux());
+
+                    if !tys_are_compatible(place_ty, value_ty) {
+                        let kind = ValidationErrorKind::MismatchedAssign {
+                            place: place_ty,
+                            value: value_ty,
+                        };
+                        self.report(location, span, kind);
+                    }
+                }
+            }
+            StatementKind::Discriminate(place, _) => {
+                self.validate_place(*place, location, span);
+            }
+            StatementKind::Live(local) | StatementKind::Dead(local) => {
+                self.validate_local(*local, location, span);
+            }
+            StatementKind::Assume(operand) => {
+                if self.validate_rvalue(&(*operand).into(), location, span) {
+                    let ty = operand.ty(&self.body.aux());
+
+                    if !tys_are_compatible(COMMON_REPR_TYS.bool, ty) {
+                        let kind = ValidationErrorKind::NonBooleanAssumption { ty };
+                        self.report(location, span, kind);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validate all of the places that are used within an [RValue], returning
+    /// whether all of them are valid.
+    fn validate_rvalue(&mut self, value: &RValue, location: IrRef, span: Span) -> bool {
+        let mut places = PlaceCollector::default();
+        places.visit_rvalue(value, &IrVisitorCtx::new(location, self.body.aux()));
+
+        places.into_iter().fold(true, |valid, place| {
+            self.validate_place(place, location, span).is_some() && valid
+        })
+    }
+
+    /// Validate a [Terminator], all of the targets of the terminator must
+    /// exist, and all of the places within the terminator must be valid.
+    fn validate_terminator(&mut self, terminator: &Terminator, location: IrRef) {
+        let span = terminator.origin.span();
+        let count = self.body.blocks().len();
+
+        for target in terminator.successors() {
+            if target.index() >= count {
+                self.report(location, span, ValidationErrorKind::UnknownTarget { target, count });
+            }
+        }
+
+        let mut places = PlaceCollector::default();
+        places.visit_terminator(terminator, &IrVisitorCtx::new(location, self.body.aux()));
+
+        for place in places.into_iter() {
+            self.validate_place(place, location, span);
+        }
+    }
+
+    /// Validate that the [Local] is declared within the body.
+    fn validate_local(&mut self, local: Local, location: IrRef, span: Span) -> bool {
+        let count = self.body.locals.len();
+        let is_declared = local.index() < count;
+
+        if !is_declared {
+            self.report(location, span, ValidationErrorKind::UndeclaredLocal { local, count });
+        }
+
+        is_declared
+    }
+
+    /// Validate a [Place] by checking that the base local is declared, and
+    /// that each projection of the place can be applied to the type that it
+    /// projects. If the place is valid, the type of the place is returned.
+    fn validate_place(&mut self, place: Place, location: IrRef, span: Span) -> Option<ReprTyId> {
+        if !self.validate_local(place.local, location, span) {
+            return None;
+        }
+
+        let body = self.body;
+        let mut base = PlaceTy::from_ty(body.locals[place.local].ty());
+
+        for projection in body.projections.borrow(place.projections).iter().copied() {
+            if let PlaceProjection::Index(local) = projection
+                && !self.validate_local(local, location, span)
+            {
+                return None;
+            }
+
+            match base.try_apply_projection(projection) {
+                Some(projected) => base = projected,
+                None => {
+                    let kind = ValidationErrorKind::InvalidProjection { ty: base.ty, projection };
+                    self.report(location, span, kind);
+                    return None;
+                }
+            }
+        }
+
+        Some(base.ty)
+    }
+}
+
+/// Collects all of the [Place]s that are directly used within a statement,
+/// rvalue or terminator.
+#[derive(Default)]
+struct PlaceCollector {
+    places: Vec<Place>,
+}
+
+impl PlaceCollector {
+    fn into_iter(self) -> impl Iterator<Item = Place> {
+        self.places.into_iter()
+    }
+}
+
+impl<'ir> IrVisitorMut<'ir> for PlaceCollector {
+    fn visit_place(&mut self, place: &Place, _: PlaceCtx, _: &IrVisitorCtx<'_>) {
+        self.places.push(*place);
+    }
+}
+
+/// Check whether a value of type `value` can be stored in a place of type
+/// `place`. Since types are not interned, types are compared structurally
+/// rather than by their [ReprTyId].
+fn tys_are_compatible(place: ReprTyId, value: ReprTyId) -> bool {
+    if place == value {
+        return true;
+    }
+
+    let (place, value) = (place.value(), value.value());
+
+    match (place, value) {
+        // The never type can be coerced into any type.
+        (_, ReprTy::Never) | (ReprTy::Never, _) => true,
+        (ReprTy::Ref(place, place_mut, place_kind), ReprTy::Ref(value, value_mut, value_kind)) => {
+            // A mutable reference can be stored in an immutable one.
+            place_kind == value_kind
+                && (place_mut == value_mut || place_mut == Mutability::Immutable)
+                && tys_are_compatible(place, value)
+        }
+        (ReprTy::Slice(place), ReprTy::Slice(value)) => tys_are_compatible(place, value),
+        (
+            ReprTy::Array { ty: place, length: place_length },
+            ReprTy::Array { ty: value, length: value_length },
+        ) => place_length == value_length && tys_are_compatible(place, value),
+        (ReprTy::Adt(place), ReprTy::Adt(value)) => adts_are_compatible(place, value),
+        (
+            ReprTy::Fn { params: place_params, return_ty: place_return, abi: place_abi },
+            ReprTy::Fn { params: value_params, return_ty: value_return, abi: value_abi },
+        ) => {
+            place_abi == value_abi
+                && tys_are_compatible(place_return, value_return)
+                && place_params.len() == value_params.len()
+                && place_params
+                    .borrow()
+                    .iter()
+                    .zip(value_params.borrow().iter())
+                    .all(|(place, value)| tys_are_compatible(*place, *value))
+        }
+        (ReprTy::FnDef { instance: place }, ReprTy::FnDef { instance: value }) => {
+            place == value || {
+                let (place, value) = (place.borrow(), value.borrow());
+                place.name() == value.name() && place.attr_id == value.attr_id
+            }
+        }
+        (place, value) => place == value,
+    }
+}
+
+/// Check whether two ADTs are compatible. Tuples are compared structurally,
+/// whilst any other ADT must originate from the same definition.
+fn adts_are_compatible(place: AdtId, value: AdtId) -> bool {
+    if place == value {
+        return true;
+    }
+
+    let (place, value) = (place.borrow(), value.borrow());
+
+    if place.flags.is_tuple() && value.flags.is_tuple() {
+        let (place, value) = (place.univariant(), value.univariant());
+
+        return place.fields.len() == value.fields.len()
+            && place
+                .fields
+                .iter()
+                .zip(value.fields.iter())
+                .all(|(place, value)| tys_are_compatible(place.ty, value.ty));
+    }
+
+    let is_same_origin = |origin: Option<AstNodeId>| origin.is_some() && origin == value.origin;
+    is_same_origin(place.origin)
+        && place.name == value.name
+        && match (place.substitutions, value.substitutions) {
+            (Some(place), Some(value)) => {
+                place.len() == value.len()
+                    && place
+                        .borrow()
+                        .iter()
+                        .zip(value.borrow().iter())
+                        .all(|(place, value)| tys_are_compatible(*place, *value))
+            }
+            (place, value) => place.is_none() && value.is_none(),
+        }
+}
@@ -0,0 +1,8 @@
+//! The Hash language server. The server speaks the language server protocol
+//! over a pair of streams, and uses the compiler pipeline to publish the
+//! diagnostics of the open documents, to show the inferred types of terms
+//! on hover, and to find the definitions of variables.
+#![feature(let_chains)]
+
+pub mod protocol;
+pub mod server;
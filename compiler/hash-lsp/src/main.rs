@@ -0,0 +1,41 @@
+//! Hash language server entry point, the server communicates with the
+//! client over the standard input and output streams.
+use std::{io, ops::ControlFlow, process};
+
+use hash_lsp::{protocol::read_message, server::Server};
+use hash_utils::json::JsonValue;
+
+fn main() {
+    // The logger of the compiler writes to the standard output, which is
+    // reserved for the messages of the protocol, so no logger is installed.
+    let mut input = io::stdin().lock();
+    let mut server = Server::new(io::stdout());
+
+    loop {
+        let message = match read_message(&mut input) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(error) => {
+                eprintln!("couldn't read a message: {error}");
+                process::exit(1);
+            }
+        };
+
+        let message = match JsonValue::parse(&message) {
+            Ok(message) => message,
+            Err(error) => {
+                eprintln!("{error}");
+                continue;
+            }
+        };
+
+        match server.handle(&message) {
+            Ok(ControlFlow::Continue(())) => {}
+            Ok(ControlFlow::Break(())) => break,
+            Err(error) => {
+                eprintln!("couldn't write a message: {error}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! The framing of the messages of the language server protocol. Each message
+//! is preceded by a header which specifies the length of its content:
+//! ```notrust
+//! Content-Length: 44\r\n
+//! \r\n
+//! {"jsonrpc":"2.0","id":1,"method":"shutdown"}
+//! ```
+use std::io::{self, BufRead, Write};
+
+use hash_utils::json::JsonValue;
+
+/// Read the content of the next message, returns [None] once the stream has
+/// ended.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut length = None;
+    let mut line = String::new();
+
+    // Read the header, which ends with an empty line.
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "missing `Content-Length` header"));
+    };
+
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+
+    String::from_utf8(content)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Write the given message with its header.
+pub fn write_message(writer: &mut impl Write, message: &JsonValue) -> io::Result<()> {
+    let content = message.to_single_line();
+
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+}
@@ -0,0 +1,455 @@
+//! The state of the language server, and the handling of each of the
+//! requests and notifications of the client.
+//!
+//! Whenever a document is opened or changed, the document is compiled up to
+//! semantic analysis with a [CompilerBuilder], using the contents of all of
+//! the open documents in place of the files on the disk. The diagnostics of
+//! the compilation are published to the client, and a [ModuleIndex] of the
+//! document is kept in order to answer hover and go-to-definition requests.
+//!
+//! Each compilation currently creates a new compiler, and so the prelude is
+//! analysed again for every change. The stores of the compiler are global to
+//! the process and can't yet invalidate the items of a module that has
+//! changed, so the items of previous compilations are never freed.
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    ops::ControlFlow,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use hash_driver::CompilerBuilder;
+use hash_pipeline::settings::{CompilerSettings, CompilerStageKind};
+use hash_reporting::report::{Report, ReportElement, ReportKind};
+use hash_source::{location::Span, SourceMapUtils};
+use hash_tir::index::ModuleIndex;
+use hash_utils::{itertools::Itertools, json::JsonValue, path::normalise_path};
+
+use crate::protocol::write_message;
+
+/// The error code of a request for a method that isn't supported.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The error code of a request that is sent after the server has shut down.
+const INVALID_REQUEST: i64 = -32600;
+
+/// The error code of a request whose parameters are malformed.
+const INVALID_PARAMS: i64 = -32602;
+
+/// The error of a request, which is sent to the client in place of a result.
+type RequestError = (i64, String);
+
+/// A language server that writes its messages to the stream `W`.
+pub struct Server<W> {
+    /// The stream that responses and notifications are written to.
+    output: W,
+
+    /// The contents of each document that is open in the client.
+    documents: HashMap<PathBuf, String>,
+
+    /// The index of each open document from its last compilation, this is
+    /// missing if the compilation didn't reach semantic analysis.
+    indices: HashMap<PathBuf, ModuleIndex>,
+
+    /// Whether the client has asked the server to shut down, any further
+    /// requests are rejected.
+    shutting_down: bool,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(output: W) -> Self {
+        Self { output, documents: HashMap::new(), indices: HashMap::new(), shutting_down: false }
+    }
+
+    /// Get the stream that the messages of the server are written to.
+    pub fn output(&self) -> &W {
+        &self.output
+    }
+
+    /// Handle a message of the client, the server should stop once this
+    /// returns [`ControlFlow::Break`].
+    pub fn handle(&mut self, message: &JsonValue) -> io::Result<ControlFlow<()>> {
+        let method = message.get("method").and_then(JsonValue::as_str);
+        let params = message.get("params").unwrap_or(&JsonValue::Null);
+
+        match (method, message.get("id")) {
+            (Some("exit"), _) => return Ok(ControlFlow::Break(())),
+            (Some(method), Some(id)) => {
+                let outcome = match self.shutting_down {
+                    true => Err((INVALID_REQUEST, "the server is shutting down".to_string())),
+                    false => self.request(method, params),
+                };
+
+                let response = match outcome {
+                    Ok(result) => ("result", result),
+                    Err((code, message)) => (
+                        "error",
+                        JsonValue::object([
+                            ("code", code.into()),
+                            ("message", JsonValue::String(message)),
+                        ]),
+                    ),
+                };
+
+                let response = JsonValue::object([
+                    ("jsonrpc", JsonValue::string("2.0")),
+                    ("id", id.clone()),
+                    response,
+                ]);
+                write_message(&mut self.output, &response)?;
+            }
+            (Some(method), None) => self.notification(method, params)?,
+
+            // The server doesn't send any requests, so there are no responses
+            // to handle.
+            (None, _) => {}
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    fn request(&mut self, method: &str, params: &JsonValue) -> Result<JsonValue, RequestError> {
+        match method {
+            "initialize" => Ok(JsonValue::object([
+                (
+                    "capabilities",
+                    JsonValue::object([
+                        // The full contents of a document are sent on each change.
+                        ("textDocumentSync", JsonValue::from(1usize)),
+                        ("hoverProvider", JsonValue::from(true)),
+                        ("definitionProvider", JsonValue::from(true)),
+                    ]),
+                ),
+                ("serverInfo", JsonValue::object([("name", JsonValue::string("hash-lsp"))])),
+            ])),
+            "shutdown" => {
+                self.shutting_down = true;
+                Ok(JsonValue::Null)
+            }
+            "textDocument/hover" => self.hover(params),
+            "textDocument/definition" => self.definition(params),
+            _ => Err((METHOD_NOT_FOUND, format!("`{method}` isn't supported"))),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &JsonValue) -> io::Result<()> {
+        let path =
+            params.path(&["textDocument", "uri"]).and_then(JsonValue::as_str).and_then(uri_to_path);
+        let Some(path) = path else {
+            return Ok(());
+        };
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params.path(&["textDocument", "text"]).and_then(JsonValue::as_str);
+                self.documents.insert(path.clone(), text.unwrap_or_default().to_string());
+                self.analyse(&path)
+            }
+            "textDocument/didChange" => {
+                // Only full changes are requested, so the last change contains
+                // the whole document.
+                let changes = params.get("contentChanges").and_then(JsonValue::as_array);
+                let text = changes.and_then(|changes| changes.last()?.get("text")?.as_str());
+
+                if let Some(text) = text {
+                    self.documents.insert(path.clone(), text.to_string());
+                }
+
+                self.analyse(&path)
+            }
+            "textDocument/didSave" => {
+                // All of the open documents are analysed again, so that the
+                // diagnostics of the documents that import the saved document
+                // are brought up to date.
+                let paths = self.documents.keys().cloned().sorted().collect_vec();
+                paths.iter().try_for_each(|path| self.analyse(path))
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&path);
+                self.indices.remove(&path);
+                self.publish_diagnostics(&path, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Compile the open document at the given path, and publish the
+    /// diagnostics of the compilation.
+    fn analyse(&mut self, path: &Path) -> io::Result<()> {
+        let Some(text) = self.documents.get(path) else {
+            return Ok(());
+        };
+
+        let mut settings = CompilerSettings::new();
+        settings.set_stage(CompilerStageKind::Analysis);
+
+        // The document is the entry point, and all of the other open documents
+        // take precedence over the files on the disk if they are imported.
+        let mut builder =
+            CompilerBuilder::new().with_settings(settings).with_source(path_name(path), text);
+
+        for (other, contents) in &self.documents {
+            if other != path {
+                builder = builder.with_source(path_name(other), contents);
+            }
+        }
+
+        // A crash of the compiler shouldn't take down the server.
+        let diagnostics = match panic::catch_unwind(AssertUnwindSafe(|| builder.compile())) {
+            Ok(Ok(compilation)) => {
+                match SourceMapUtils::id_by_path(&normalise_path(path))
+                    .and_then(|source| compilation.index(source))
+                {
+                    Some(index) => self.indices.insert(path.to_path_buf(), index),
+                    None => self.indices.remove(path),
+                };
+
+                compilation
+                    .diagnostics()
+                    .iter()
+                    .filter_map(|report| diagnostic(report, path))
+                    .collect()
+            }
+            Ok(Err(error)) => {
+                self.indices.remove(path);
+
+                let report: Report = error.into();
+                diagnostic(&report, path).into_iter().collect()
+            }
+            Err(_) => {
+                self.indices.remove(path);
+
+                let mut report = Report::new();
+                report
+                    .kind(ReportKind::Internal)
+                    .title("the compiler crashed whilst analysing the document");
+                diagnostic(&report, path).into_iter().collect()
+            }
+        };
+
+        self.publish_diagnostics(path, diagnostics)
+    }
+
+    fn publish_diagnostics(&mut self, path: &Path, diagnostics: Vec<JsonValue>) -> io::Result<()> {
+        let params = JsonValue::object([
+            ("uri", JsonValue::String(path_to_uri(path))),
+            ("diagnostics", JsonValue::Array(diagnostics)),
+        ]);
+
+        let notification = JsonValue::object([
+            ("jsonrpc", JsonValue::string("2.0")),
+            ("method", JsonValue::string("textDocument/publishDiagnostics")),
+            ("params", params),
+        ]);
+
+        write_message(&mut self.output, &notification)
+    }
+
+    /// Find the index of the document and the byte offset within the document
+    /// that the `textDocument` and `position` of the request point to.
+    fn position(&self, params: &JsonValue) -> Result<(&ModuleIndex, usize), RequestError> {
+        let invalid = || (INVALID_PARAMS, "expected a document and a position".to_string());
+
+        let path = params
+            .path(&["textDocument", "uri"])
+            .and_then(JsonValue::as_str)
+            .and_then(uri_to_path)
+            .ok_or_else(invalid)?;
+        let line =
+            params.path(&["position", "line"]).and_then(JsonValue::as_usize).ok_or_else(invalid)?;
+        let character = params
+            .path(&["position", "character"])
+            .and_then(JsonValue::as_usize)
+            .ok_or_else(invalid)?;
+
+        // The document might not have been analysed successfully.
+        let (Some(index), Some(text)) = (self.indices.get(&path), self.documents.get(&path)) else {
+            return Err((INVALID_PARAMS, format!("`{}` hasn't been analysed", path.display())));
+        };
+
+        Ok((index, offset(text, line, character)))
+    }
+
+    /// Show the inferred type of the term under the cursor.
+    fn hover(&self, params: &JsonValue) -> Result<JsonValue, RequestError> {
+        let (index, offset) = self.position(params)?;
+
+        let Some((entry, ty)) = index.entry_at(offset).and_then(|entry| Some((entry, entry.ty?)))
+        else {
+            return Ok(JsonValue::Null);
+        };
+
+        Ok(JsonValue::object([
+            (
+                "contents",
+                JsonValue::object([
+                    ("kind", JsonValue::string("markdown")),
+                    ("value", JsonValue::String(format!("```hash\n{ty}\n```"))),
+                ]),
+            ),
+            ("range", range(entry.span)),
+        ]))
+    }
+
+    /// Find where the variable under the cursor is defined.
+    fn definition(&self, params: &JsonValue) -> Result<JsonValue, RequestError> {
+        let (index, offset) = self.position(params)?;
+
+        let Some(definition) = index.entry_at(offset).and_then(|entry| entry.definition) else {
+            return Ok(JsonValue::Null);
+        };
+
+        let path = SourceMapUtils::map(definition.id, |source| source.path().to_path_buf());
+        Ok(JsonValue::object([
+            ("uri", JsonValue::String(path_to_uri(&path))),
+            ("range", range(definition)),
+        ]))
+    }
+}
+
+/// The name that a document is added to the [CompilerBuilder] with, which is
+/// the path of the document.
+fn path_name(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// Convert a [Report] into an LSP diagnostic, the diagnostic is placed on the
+/// first span of the report. Reports that are located within another module
+/// are published when that module is analysed, and so [None] is returned.
+fn diagnostic(report: &Report, path: &Path) -> Option<JsonValue> {
+    let span = report.contents.iter().find_map(|element| match element {
+        ReportElement::CodeBlock(block) => Some(block.span),
+        ReportElement::Note(_) | ReportElement::Suggestion(_) => None,
+    });
+
+    let range = match span {
+        Some(span)
+            if SourceMapUtils::map(span.id, |source| source.path() == normalise_path(path)) =>
+        {
+            range(span)
+        }
+        Some(_) => return None,
+        None => JsonValue::object([("start", position(0, 0)), ("end", position(0, 0))]),
+    };
+
+    let severity: usize = match report.kind {
+        ReportKind::Error | ReportKind::Internal => 1,
+        ReportKind::Warning => 2,
+        ReportKind::Info => 3,
+    };
+
+    // The notes of the report are written on the lines after the title.
+    let notes = report.contents.iter().filter_map(|element| match element {
+        ReportElement::Note(note) if note.label.as_str().is_empty() => Some(note.message.clone()),
+        ReportElement::Note(note) => Some(format!("{}: {}", note.label.as_str(), note.message)),
+        ReportElement::Suggestion(suggestion) => {
+            Some(format!("help: replace with `{}`", suggestion.replacement))
+        }
+        ReportElement::CodeBlock(_) => None,
+    });
+    let message = std::iter::once(report.title.clone()).chain(notes).join("\n");
+
+    let mut entries = vec![
+        ("range", range),
+        ("severity", JsonValue::from(severity)),
+        ("source", JsonValue::string("hash")),
+        ("message", JsonValue::String(message)),
+    ];
+
+    if let Some(code) = report.code {
+        entries.push(("code", JsonValue::String(code.to_string())));
+    }
+
+    Some(JsonValue::object(entries))
+}
+
+fn position(line: usize, character: usize) -> JsonValue {
+    JsonValue::object([("line", line.into()), ("character", character.into())])
+}
+
+/// Convert a [Span] into an LSP range. The end of a [Span] is inclusive,
+/// whereas the end of a range is exclusive.
+fn range(span: Span) -> JsonValue {
+    SourceMapUtils::map(span.id, |source| {
+        let text = source.contents().0;
+        let (start, end) = (span.range.start(), span.range.end() + 1);
+
+        JsonValue::object([("start", position_of(text, start)), ("end", position_of(text, end))])
+    })
+}
+
+/// Get the position of the given byte offset within the text. The characters
+/// of a position are counted in UTF-16 code units.
+fn position_of(text: &str, offset: usize) -> JsonValue {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+    position(before.matches('\n').count(), before[line_start..].encode_utf16().count())
+}
+
+/// Get the byte offset of the given line and UTF-16 character within the
+/// text, positions past the end of a line are clamped to the end of the line.
+fn offset(text: &str, line: usize, character: usize) -> usize {
+    let line_start = text.split_inclusive('\n').take(line).map(str::len).sum::<usize>();
+    let mut units = 0;
+
+    for (index, c) in text[line_start..].char_indices() {
+        if units >= character || c == '\n' {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+
+    text.len()
+}
+
+/// Convert a `file://` URI into a path, any percent-encoded bytes of the URI
+/// are decoded.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut index = 0;
+
+    while index < encoded.len() {
+        let decoded = (encoded[index] == b'%')
+            .then(|| std::str::from_utf8(encoded.get(index + 1..index + 3)?).ok())
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match decoded {
+            Some(byte) => {
+                bytes.push(byte);
+                index += 3;
+            }
+            None => {
+                bytes.push(encoded[index]);
+                index += 1;
+            }
+        }
+    }
+
+    Some(PathBuf::from(String::from_utf8(bytes).ok()?))
+}
+
+/// Convert a path into a `file://` URI, percent-encoding any bytes that
+/// aren't allowed within the path of a URI.
+pub fn path_to_uri(path: &Path) -> String {
+    let mut uri = "file://".to_string();
+
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(byte as char)
+            }
+            byte => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    uri
+}
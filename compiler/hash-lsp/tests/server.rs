@@ -0,0 +1,109 @@
+//! Hash language server tests.
+use std::io::Cursor;
+
+use hash_lsp::{
+    protocol::read_message,
+    server::{path_to_uri, Server},
+};
+use hash_utils::json::JsonValue;
+
+/// Create a message from the given JSON document.
+fn message(document: &str) -> JsonValue {
+    JsonValue::parse(document).unwrap()
+}
+
+/// Read all of the messages that the server has written.
+fn messages(server: &Server<Vec<u8>>) -> Vec<JsonValue> {
+    let mut output = Cursor::new(server.output().as_slice());
+    let mut messages = vec![];
+
+    while let Some(message) = read_message(&mut output).unwrap() {
+        messages.push(JsonValue::parse(&message).unwrap());
+    }
+
+    messages
+}
+
+/// Find the response to the request with the given id.
+fn response(server: &Server<Vec<u8>>, id: usize) -> JsonValue {
+    messages(server)
+        .into_iter()
+        .find(|message| message.get("id").and_then(JsonValue::as_usize) == Some(id))
+        .unwrap()
+}
+
+#[test]
+fn read_and_write_json_documents() {
+    let document = r#"{ "name": "a\"bé😀", "items": [1, -2.5, true, null], "empty": {} }"#;
+    let value = JsonValue::parse(document).unwrap();
+
+    assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("a\"bé😀"));
+    assert_eq!(
+        value.path(&["items"]).and_then(JsonValue::as_array).map(|items| items.len()),
+        Some(4)
+    );
+    assert_eq!(JsonValue::parse(&value.to_string()).unwrap(), value);
+    assert!(JsonValue::parse("[1, 2").is_err());
+}
+
+#[test]
+fn publish_diagnostics_and_answer_requests() {
+    let path = std::env::temp_dir().join("hash-lsp").join("main.hash");
+    let uri = path_to_uri(&path);
+    let text = "foo := (x: i32) -> i32 => x;\\nbar := () => { y: str = foo(1); };\\n";
+
+    let mut server = Server::new(vec![]);
+    let _ = server.handle(&message(r#"{ "jsonrpc": "2.0", "id": 1, "method": "initialize" }"#));
+    let _ = server.handle(&message(&format!(
+        r#"{{ "jsonrpc": "2.0", "method": "textDocument/didOpen",
+             "params": {{ "textDocument": {{ "uri": "{uri}", "text": "{text}" }} }} }}"#
+    )));
+
+    // The mismatch between `str` and `i32` is published as an error.
+    let published = messages(&server)
+        .into_iter()
+        .find(|message| {
+            message.get("method").and_then(JsonValue::as_str)
+                == Some("textDocument/publishDiagnostics")
+        })
+        .unwrap();
+    let diagnostics =
+        published.path(&["params", "diagnostics"]).and_then(JsonValue::as_array).unwrap();
+    assert!(diagnostics.iter().any(|diagnostic| {
+        diagnostic.get("severity").and_then(JsonValue::as_usize) == Some(1)
+            && diagnostic.path(&["range", "start", "line"]).and_then(JsonValue::as_usize) == Some(1)
+    }));
+
+    // Hovering over the use of `x` within the body shows its type, and its
+    // definition is the parameter.
+    let position = format!(
+        r#"{{ "textDocument": {{ "uri": "{uri}" }}, "position": {{ "line": 0, "character": 26 }} }}"#
+    );
+    let _ = server.handle(&message(&format!(
+        r#"{{ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": {position} }}"#
+    )));
+    let _ = server.handle(&message(&format!(
+        r#"{{ "jsonrpc": "2.0", "id": 3, "method": "textDocument/definition",
+             "params": {position} }}"#
+    )));
+
+    let hover = response(&server, 2);
+    let contents =
+        hover.path(&["result", "contents", "value"]).and_then(JsonValue::as_str).unwrap();
+    assert!(contents.contains("i32"));
+
+    let definition = response(&server, 3);
+    assert_eq!(definition.path(&["result", "uri"]).and_then(JsonValue::as_str), Some(uri.as_str()));
+    assert_eq!(
+        definition.path(&["result", "range", "start", "character"]).and_then(JsonValue::as_usize),
+        Some(8)
+    );
+
+    // Unknown requests are rejected, and the server stops once it exits.
+    let _ = server.handle(&message(r#"{ "jsonrpc": "2.0", "id": 4, "method": "unknown" }"#));
+    assert!(response(&server, 4).path(&["error", "code"]).is_some());
+    assert!(server
+        .handle(&message(r#"{ "jsonrpc": "2.0", "method": "exit" }"#))
+        .unwrap()
+        .is_break());
+}
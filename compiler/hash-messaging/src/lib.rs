@@ -1 +1,253 @@
 //! Defines compiler messages that are passed in and out of the compiler.
+//!
+//! When the compiler is run with `--message-format=json`, each message is
+//! written as a JSON object on its own line, so that the output can be
+//! consumed by editors and CI tooling. A [CompilerOutputMessage::Report] is
+//! written as:
+//! ```json
+//! {
+//!   "type": "report",
+//!   "kind": "error",
+//!   "code": "E0020",
+//!   "title": "types mismatch, wanted `str`, but got `i32`",
+//!   "spans": [
+//!     {
+//!       "path": "/home/user/main.hash",
+//!       "start": { "line": 2, "column": 15 },
+//!       "end": { "line": 2, "column": 15 },
+//!       "message": ""
+//!     }
+//!   ],
+//!   "notes": [{ "kind": "help", "message": "..." }],
+//!   "suggestions": [
+//!     {
+//!       "path": "/home/user/main.hash",
+//!       "start": { "line": 1, "column": 3 },
+//!       "end": { "line": 1, "column": 3 },
+//!       "replacement": ":="
+//!     }
+//!   ]
+//! }
+//! ```
+//! The `kind` of a report is one of `error`, `warn`, `info` or `internal`,
+//! and the `code` is `null` if the report has no code. Lines and
+//! columns begin from `1`, and the `end` of a span is inclusive. Each of the
+//! `suggestions` can be applied by replacing the text of its span with the
+//! `replacement`.
+//!
+//! An external process can also drive the compiler by writing a
+//! [CompilerInputMessage] on each line, i.e. `{ "type": "shutdown" }`. Each
+//! message that starts a session is answered with the id of the session, the
+//! reports of the session, and then a message once the session is finished.
+
+use std::{fmt, path::PathBuf};
+
+use hash_reporting::report::{Report, ReportElement};
+use hash_source::{location::Span, SourceMapUtils};
+use hash_utils::{
+    clap,
+    json::{JsonError, JsonValue},
+};
+
+/// The format that the compiler uses to write its messages, i.e. the
+/// diagnostics that are produced by the stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum CompilerMessagingFormat {
+    /// Render messages for a person to read in the terminal.
+    #[default]
+    Normal,
+
+    /// Write each message as a JSON object on its own line.
+    Json,
+}
+
+impl fmt::Display for CompilerMessagingFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// A message that is read by the compiler, which is written as a JSON object
+/// with a `type` and the fields of the message, i.e.
+/// `{ "type": "check_source", "path": "main.hash" }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompilerInputMessage {
+    /// Typecheck the module at the given path, and all of its imports.
+    CheckSource { path: PathBuf },
+
+    /// Evaluate the given expression, as if it was entered interactively.
+    EvaluateExpr { text: String },
+
+    /// Cancel the session with the given id, the session stops before it runs
+    /// any further stages or bodies.
+    Cancel { id: usize },
+
+    /// Stop reading messages, the compiler exits once the sessions that have
+    /// already been started are finished.
+    Shutdown,
+}
+
+impl CompilerInputMessage {
+    /// Read a [CompilerInputMessage] from a JSON document.
+    pub fn parse(input: &str) -> Result<Self, JsonError> {
+        let message = JsonValue::parse(input)?;
+        let field = |name: &str| {
+            message.get(name).ok_or_else(|| JsonError { message: format!("missing `{name}`") })
+        };
+        let string = |name: &str| {
+            field(name)?
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| JsonError { message: format!("expected `{name}` to be a string") })
+        };
+
+        match field("type")?.as_str() {
+            Some("check_source") => Ok(Self::CheckSource { path: string("path")?.into() }),
+            Some("evaluate_expr") => Ok(Self::EvaluateExpr { text: string("text")? }),
+            Some("cancel") => match field("id")?.as_usize() {
+                Some(id) => Ok(Self::Cancel { id }),
+                None => Err(JsonError { message: "expected `id` to be a session id".to_string() }),
+            },
+            Some("shutdown") => Ok(Self::Shutdown),
+            Some(kind) => Err(JsonError { message: format!("unknown message type `{kind}`") }),
+            None => Err(JsonError { message: "expected `type` to be a string".to_string() }),
+        }
+    }
+}
+
+/// A message that is written by the compiler.
+#[derive(Debug, Clone)]
+pub enum CompilerOutputMessage {
+    /// A diagnostic that was produced by the compiler.
+    Report(Report),
+
+    /// A session has been started for a [CompilerInputMessage], the `id` can
+    /// be used to cancel the session.
+    SessionStarted { id: usize },
+
+    /// The result of evaluating the expression of a session.
+    Evaluation { id: usize, result: String },
+
+    /// A session has finished, all of its reports have been written.
+    SessionFinished { id: usize, cancelled: bool },
+}
+
+impl CompilerOutputMessage {
+    /// Convert the message into its JSON representation.
+    pub fn to_json(&self) -> JsonValue {
+        match self {
+            CompilerOutputMessage::Report(report) => report_to_json(report),
+            CompilerOutputMessage::SessionStarted { id } => JsonValue::object([
+                ("type", JsonValue::string("session_started")),
+                ("id", JsonValue::from(*id)),
+            ]),
+            CompilerOutputMessage::Evaluation { id, result } => JsonValue::object([
+                ("type", JsonValue::string("evaluation")),
+                ("id", JsonValue::from(*id)),
+                ("result", JsonValue::string(result)),
+            ]),
+            CompilerOutputMessage::SessionFinished { id, cancelled } => JsonValue::object([
+                ("type", JsonValue::string("session_finished")),
+                ("id", JsonValue::from(*id)),
+                ("cancelled", JsonValue::Bool(*cancelled)),
+            ]),
+        }
+    }
+
+    /// Write the message in the given [CompilerMessagingFormat], the message
+    /// is followed by a newline.
+    pub fn write(
+        &self,
+        format: CompilerMessagingFormat,
+        writer: &mut impl std::io::Write,
+    ) -> std::io::Result<()> {
+        match (format, self) {
+            (CompilerMessagingFormat::Normal, CompilerOutputMessage::Report(report)) => {
+                writeln!(writer, "{report}")
+            }
+            (CompilerMessagingFormat::Normal, CompilerOutputMessage::SessionStarted { id }) => {
+                writeln!(writer, "session {id} started")
+            }
+            (CompilerMessagingFormat::Normal, CompilerOutputMessage::Evaluation { result, .. }) => {
+                writeln!(writer, "{result}")
+            }
+            (
+                CompilerMessagingFormat::Normal,
+                CompilerOutputMessage::SessionFinished { id, cancelled },
+            ) => {
+                let status = if *cancelled { "was cancelled" } else { "finished" };
+                writeln!(writer, "session {id} {status}")
+            }
+            (CompilerMessagingFormat::Json, _) => {
+                writeln!(writer, "{}", self.to_json().to_single_line())
+            }
+        }
+    }
+}
+
+/// Convert a [Span] into the `path`, `start` and `end` entries of a JSON
+/// object.
+fn span_to_json(span: Span) -> Vec<(&'static str, JsonValue)> {
+    let (path, range) = SourceMapUtils::map(span.id, |source| {
+        (source.canonicalised_path().display().to_string(), source.row_cols(span.range))
+    });
+
+    let position = |row: usize, column: usize| {
+        JsonValue::object([
+            ("line", JsonValue::from(row + 1)),
+            ("column", JsonValue::from(column + 1)),
+        ])
+    };
+
+    vec![
+        ("path", JsonValue::String(path)),
+        ("start", position(range.start.row, range.start.column)),
+        ("end", position(range.end.row, range.end.column)),
+    ]
+}
+
+/// Convert a [Report] into its JSON representation, the code blocks of the
+/// report become `spans`, the suggestions become `suggestions`, and all of
+/// the other elements become `notes`.
+fn report_to_json(report: &Report) -> JsonValue {
+    let mut spans = vec![];
+    let mut notes = vec![];
+    let mut suggestions = vec![];
+
+    for element in &report.contents {
+        match element {
+            ReportElement::CodeBlock(block) => {
+                let mut entries = span_to_json(block.span);
+                entries.push(("message", JsonValue::string(&block.code_message)));
+                spans.push(JsonValue::object(entries));
+            }
+            ReportElement::Note(note) => notes.push(JsonValue::object([
+                ("kind", JsonValue::string(note.label.as_str())),
+                ("message", JsonValue::string(&note.message)),
+            ])),
+            ReportElement::Suggestion(suggestion) => {
+                let mut entries = span_to_json(suggestion.span);
+                entries.push(("replacement", JsonValue::string(&suggestion.replacement)));
+                suggestions.push(JsonValue::object(entries));
+            }
+        }
+    }
+
+    let code = match report.code {
+        Some(code) => JsonValue::String(code.to_string()),
+        None => JsonValue::Null,
+    };
+
+    JsonValue::object([
+        ("type", JsonValue::string("report")),
+        ("kind", JsonValue::string(report.kind.message())),
+        ("code", code),
+        ("title", JsonValue::string(&report.title)),
+        ("spans", JsonValue::Array(spans)),
+        ("notes", JsonValue::Array(notes)),
+        ("suggestions", JsonValue::Array(suggestions)),
+    ])
+}
@@ -2,6 +2,7 @@
 use hash_ast::{ast::TyParamOrigin, origin::PatOrigin};
 use hash_pipeline::fs::ImportError;
 use hash_reporting::{
+    hash_error_codes::error_codes::HashErrorCode,
     report::help,
     reporter::{Reporter, Reports},
 };
@@ -29,6 +30,19 @@ pub struct ParseError {
 
     /// An optional token in question that was received byt shouldn't of been
     received: Option<TokenKind>,
+
+    /// An optional fix for the error, which replaces the source at the span
+    /// with the given text.
+    suggestion: Option<(Span, String)>,
+}
+
+impl ParseError {
+    /// Add a suggestion to the [ParseError] which replaces the source at the
+    /// given span with the `replacement`.
+    pub(crate) fn with_suggestion(mut self, span: Span, replacement: impl ToString) -> Self {
+        self.suggestion = Some((span, replacement.to_string()));
+        self
+    }
 }
 
 /// Enum representation of the AST generation error variants.
@@ -108,6 +122,37 @@ pub enum ParseErrorKind {
     /// When an attempt is made to write an expression which would evaluate to a
     /// negative literal, i.e. `- 1`, `- /* boo! */ 2`, etc.
     UnsupportedExprInPat { value: String },
+
+    /// When the `...` of a variadic function is not the last item in the
+    /// parameters of the function.
+    MisplacedCVariadic,
+}
+
+impl ParseErrorKind {
+    /// Get the [HashErrorCode] that is associated with the kind of error.
+    fn code(&self) -> HashErrorCode {
+        match self {
+            ParseErrorKind::UnExpected => HashErrorCode::UnexpectedToken,
+            ParseErrorKind::TyDef(_) => HashErrorCode::ExpectedTyParams,
+            ParseErrorKind::ExpectedBlock => HashErrorCode::ExpectedBlock,
+            ParseErrorKind::ExpectedName => HashErrorCode::ExpectedName,
+            ParseErrorKind::ExpectedMacroInvocation => HashErrorCode::ExpectedMacroInvocation,
+            ParseErrorKind::ExpectedExpr => HashErrorCode::ExpectedExpr,
+            ParseErrorKind::ExpectedFnBody => HashErrorCode::ExpectedFnBody,
+            ParseErrorKind::ExpectedTy => HashErrorCode::ExpectedTy,
+            ParseErrorKind::ExpectedPropertyAccess => HashErrorCode::ExpectedPropertyAccess,
+            ParseErrorKind::ExpectedPat => HashErrorCode::ExpectedPat,
+            ParseErrorKind::ImportPath => HashErrorCode::InvalidImportPath,
+            ParseErrorKind::ErroneousImport(_) => HashErrorCode::UnresolvedImport,
+            ParseErrorKind::MalformedSpreadPat(_)
+            | ParseErrorKind::DisallowedSpreadPat { .. }
+            | ParseErrorKind::MultipleSpreadPats { .. } => HashErrorCode::InvalidSpreadPat,
+            ParseErrorKind::DisallowedSuffix(_) => HashErrorCode::DisallowedSuffix,
+            ParseErrorKind::InvalidPropertyAccess => HashErrorCode::InvalidPropertyAccess,
+            ParseErrorKind::UnsupportedExprInPat { .. } => HashErrorCode::UnsupportedExprInPat,
+            ParseErrorKind::MisplacedCVariadic => HashErrorCode::MisplacedVariadic,
+        }
+    }
 }
 
 /// Conversion implementation from an AST Generator Error into a Parser Error.
@@ -175,6 +220,11 @@ impl From<ParseError> for Reports {
                 "negative numerical literals must be written as a single numerical value"
                     .to_string()
             }
+            ParseErrorKind::MisplacedCVariadic => {
+                span_label = "the `...` is specified here".to_string();
+
+                "`...` must be the last item in the parameters of a function".to_string()
+            }
         };
 
         // `AstGenErrorKind::Expected` format the error message in their own way,
@@ -195,13 +245,20 @@ impl From<ParseError> for Reports {
         // Now actually build the report
         let mut reporter = Reporter::new();
         let report = reporter.error();
-        report.title(base_message).add_labelled_span(err.location, span_label);
+        report
+            .code(err.kind.code())
+            .title(base_message)
+            .add_labelled_span(err.location, span_label);
 
         // Add the `help` messages to the report
         for note in help_notes {
             report.add_element(note);
         }
 
+        if let Some((span, replacement)) = err.suggestion {
+            report.add_suggestion(span, replacement);
+        }
+
         reporter.into_reports()
     }
 }
@@ -90,6 +90,9 @@ bitflags! {
         /// An exclusive range `..<`
         const RangeExclusive = 1 << 26;
 
+        /// A semicolon `;`
+        const Semi = 1 << 27;
+
         /// A `pub` keyword
         const PubKw = 1 << 29;
 
@@ -169,6 +172,7 @@ impl fmt::Display for ExpectedItem {
                 ExpectedItem::Ellipsis => toks.push("..."),
                 ExpectedItem::Range => toks.push(".."),
                 ExpectedItem::RangeExclusive => toks.push("..<"),
+                ExpectedItem::Semi => toks.push(";"),
                 _ => unreachable!(),
             }
         }
@@ -199,6 +203,7 @@ impl From<TokenKind> for ExpectedItem {
             TokenKind::Ellipsis => ExpectedItem::Ellipsis,
             TokenKind::Range => ExpectedItem::Range,
             TokenKind::RangeExclusive => ExpectedItem::RangeExclusive,
+            TokenKind::Semi => ExpectedItem::Semi,
             TokenKind::Tree(delim, _) | TokenKind::RightDelim(delim) => delim.into(),
             _ => unreachable!("unexpected token kind when deriving expected item: {:?}", value),
         }
@@ -2,8 +2,11 @@
 
 use std::fmt::Display;
 
-use hash_ast::ast::{Expr, TyParamOrigin};
-use hash_reporting::reporter::{Reporter, Reports};
+use hash_ast::ast::{BinOp, Expr, TyParamOrigin};
+use hash_reporting::{
+    hash_error_codes::warning_codes::HashWarningCode,
+    reporter::{Reporter, Reports},
+};
 use hash_source::location::Span;
 use hash_utils::{derive_more::Constructor, pluralise};
 
@@ -68,11 +71,30 @@ pub enum WarningKind {
     /// When type parameters are provided with no specified parameters i.e.
     /// `<>`.
     UselessTyParams { origin: TyParamOrigin },
+
+    /// When the result of an ordering comparison is compared for equality
+    /// without parentheses, i.e. `a < b == c`. Unlike a chain of ordering
+    /// comparisons, this compares the result of `a < b` with `c`.
+    UnchainedComparison { ordering: BinOp, equality: BinOp },
+}
+
+impl WarningKind {
+    /// Get the [HashWarningCode] that is associated with the kind of warning.
+    fn code(&self) -> HashWarningCode {
+        match self {
+            WarningKind::RedundantParenthesis(_) => HashWarningCode::RedundantParenthesis,
+            WarningKind::UselessUnaryOperator(_) => HashWarningCode::UselessUnaryOperator,
+            WarningKind::TrailingSemis(_) => HashWarningCode::TrailingSemicolons,
+            WarningKind::UselessTyParams { .. } => HashWarningCode::UselessTyParams,
+            WarningKind::UnchainedComparison { .. } => HashWarningCode::UnchainedComparison,
+        }
+    }
 }
 
 impl From<ParseWarning> for Reports {
     fn from(warning: ParseWarning) -> Self {
         let mut span_label = "".to_string();
+        let code = warning.kind.code();
 
         let message = match warning.kind {
             WarningKind::RedundantParenthesis(subject) => {
@@ -98,10 +120,18 @@ impl From<ParseWarning> for Reports {
 
                 format!("useless type parameters on this `{}` {label}", origin.name())
             }
+            WarningKind::UnchainedComparison { ordering, equality } => {
+                span_label = format!(
+                    "the result of `{ordering}` is compared with `{equality}`, add parentheses \
+                     to make this explicit"
+                );
+
+                format!("comparison operators `{ordering}` and `{equality}` don't form a chain")
+            }
         };
 
         let mut reporter = Reporter::new();
-        reporter.warning().title(message).add_labelled_span(warning.span, span_label);
+        reporter.warning().code(code).title(message).add_labelled_span(warning.span, span_label);
 
         reporter.into_reports()
     }
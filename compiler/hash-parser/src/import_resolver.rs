@@ -2,11 +2,14 @@
 //! `hash-ast` which provides a general interface to write a parser.
 use std::path::PathBuf;
 
-use hash_pipeline::fs::{resolve_path, ImportError};
+use hash_pipeline::{
+    fs::{resolve_path, ImportError},
+    vfs::FileSystem,
+};
 use hash_source::{constant::AllocId, ModuleKind, SourceId, SourceMapUtils};
 use hash_utils::crossbeam_channel::Sender;
 
-use crate::ParserAction;
+use crate::{KnownModules, ParserAction};
 
 /// The [ImportResolver] contains internal logic for resolving the path
 /// and contents of a module import, and queueing discovered imports through
@@ -18,6 +21,12 @@ pub struct ImportResolver<'p> {
     /// Working directory from where the import path resolution occurs.
     root_dir: &'p PathBuf,
 
+    /// The [FileSystem] that imports are resolved within.
+    file_system: &'p dyn FileSystem,
+
+    /// The modules that belong to the workspace that is being parsed.
+    known_modules: &'p KnownModules,
+
     /// The parser message queue sender.
     sender: Sender<ParserAction>,
 }
@@ -28,9 +37,11 @@ impl<'p> ImportResolver<'p> {
     pub(crate) fn new(
         source_id: SourceId,
         root_dir: &'p PathBuf,
+        file_system: &'p dyn FileSystem,
+        known_modules: &'p KnownModules,
         sender: Sender<ParserAction>,
     ) -> Self {
-        Self { root_dir, sender, source_id }
+        Self { root_dir, file_system, known_modules, sender, source_id }
     }
 
     /// Get the [SourceId] associated with the current [ImportResolver].
@@ -43,19 +54,34 @@ impl<'p> ImportResolver<'p> {
     /// module, and then proceed to send a [ParserAction::ParseImport]
     /// through the message queue.
     pub(crate) fn resolve_import(&self, path: AllocId) -> Result<SourceId, ImportError> {
-        let resolved_path = resolve_path(path.to_str().as_str(), self.root_dir)?;
+        let resolved_path = resolve_path(self.file_system, path.to_str().as_str(), self.root_dir)?;
 
-        // Check if we have already parsed this file
-        if let Some(source) = SourceMapUtils::id_by_path(&resolved_path) {
-            return Ok(source);
-        }
+        // Check if we have already parsed this file. The source map is shared
+        // by all of the workspaces in the process, so a module that another
+        // workspace has parsed is not re-used.
+        let mut known_modules = self.known_modules.lock();
+        let source = match SourceMapUtils::id_by_path(&resolved_path)
+            .filter(|source| known_modules.contains(&(*source).into()))
+        {
+            Some(source) => source,
+            None => {
+                // Otherwise, we reserve a module id for the file.
+                //
+                // Send over the resolved path and the contents of the file.
+                let source = SourceMapUtils::reserve_module(resolved_path, ModuleKind::Normal);
+                known_modules.insert(source.into());
+                self.sender
+                    .send(ParserAction::ParseImport { source, sender: self.sender.clone() })
+                    .unwrap();
+                source
+            }
+        };
+        drop(known_modules);
 
-        // Otherwise, we reserve a module id for the file.
-        //
-        // Send over the resolved path and the contents of the file.
-        let source = SourceMapUtils::reserve_module(resolved_path, ModuleKind::Normal);
+        // The dependency is recorded so that this source is invalidated along
+        // with the imported module.
         self.sender
-            .send(ParserAction::ParseImport { source, sender: self.sender.clone() })
+            .send(ParserAction::AddDependency { source: self.source_id, dependency: source.into() })
             .unwrap();
         Ok(source)
     }
@@ -7,7 +7,7 @@ mod import_resolver;
 pub mod parser;
 mod source;
 
-use std::env;
+use std::{env, sync::Arc};
 
 use hash_ast::{
     ast::{self, LocalSpanMap, SpanMap},
@@ -17,8 +17,9 @@ use hash_lexer::{Lexer, LexerMetadata};
 use hash_pipeline::{
     fs::read_in_path,
     interface::{CompilerInterface, CompilerStage},
-    settings::CompilerStageKind,
-    workspace::Workspace,
+    settings::{CompilerStageKind, EmitKind},
+    vfs::FileSystem,
+    workspace::{SourceStageInfo, Workspace},
 };
 use hash_reporting::{diagnostic::DiagnosticsMut, report::Report, reporter::Reports};
 use hash_source::{
@@ -27,6 +28,8 @@ use hash_source::{
 };
 use hash_utils::{
     crossbeam_channel::{unbounded, Sender},
+    fxhash::FxHashSet,
+    parking_lot::Mutex,
     profiling::{HasMutMetrics, StageMetrics},
     rayon,
 };
@@ -36,6 +39,10 @@ use source::ParseSource;
 
 use crate::diagnostics::ParserDiagnostics;
 
+/// The modules that are known to the workspace that is being parsed. Imports
+/// of these modules re-use their existing [SourceId].
+type KnownModules = Arc<Mutex<FxHashSet<ModuleId>>>;
+
 /// The [Parser] stage is responsible for parsing the source code into an
 /// abstract syntax tree (AST). The parser will also perform some basic
 /// semantic analysis, such as resolving imports, and some other basic
@@ -92,9 +99,35 @@ impl<Ctx: ParserCtxQuery> CompilerStage<Ctx> for Parser {
         assert!(pool.current_num_threads() > 1, "Parser loop requires at least 2 workers");
 
         let node_map = &mut workspace.node_map;
+        let stage_info = &mut workspace.source_stage_info;
+        let file_system = &workspace.file_system;
+        let progress = &workspace.progress;
+
+        // The modules that each of the parsed sources import, these are added
+        // to the workspace once all of the sources have been parsed.
+        let mut dependencies = Vec::new();
+
+        // The modules that have been parsed into this workspace (by this or a
+        // previous run), and the entry point which is about to be parsed.
+        let known_modules: KnownModules = Arc::new(Mutex::new(
+            node_map
+                .iter_modules()
+                .map(|(id, _)| id)
+                .chain(entry_point.is_module().then(|| entry_point.into()))
+                .collect(),
+        ));
+
+        // The number of modules that have been parsed, and the number of
+        // modules that are known about, which grows with each import.
+        let (mut parsed, mut discovered) = (0, 1);
 
         // Parse the entry point
-        parse_source(ParseSource::from_source(entry_point, Some(current_dir)), sender);
+        parse_source(
+            ParseSource::from_source(entry_point, Some(current_dir)),
+            sender,
+            file_system.clone(),
+            known_modules.clone(),
+        );
 
         pool.scope(|scope| {
             while let Ok(message) = receiver.recv() {
@@ -103,13 +136,27 @@ impl<Ctx: ParserCtxQuery> CompilerStage<Ctx> for Parser {
                         collected_diagnostics.extend(diagnostics);
                         self.merge_metrics(timings);
                         node_map.get_interactive_block_mut(id).set_node(node);
+
+                        parsed += 1;
+                        progress.report("parse", parsed, discovered);
                     }
                     ParserAction::SetModuleNode { id, node, diagnostics, timings } => {
                         collected_diagnostics.extend(diagnostics);
                         self.merge_metrics(timings);
 
                         let path = SourceMapUtils::map(id, |source| source.path().to_path_buf());
-                        node_map.add_module(ModuleEntry::new(path, node));
+                        node_map.add_module(id, ModuleEntry::new(path, node));
+
+                        // Imported modules haven't been added to the workspace
+                        // yet, the stages record which modules they have
+                        // processed in the stage info.
+                        stage_info.add(id.into(), SourceStageInfo::empty());
+
+                        parsed += 1;
+                        progress.report("parse", parsed, discovered);
+                    }
+                    ParserAction::AddDependency { source, dependency } => {
+                        dependencies.push((source, dependency));
                     }
                     ParserAction::MergeSpans { spans } => scope.spawn(move |_| {
                         SpanMap::add_local_map(spans);
@@ -120,19 +167,35 @@ impl<Ctx: ParserCtxQuery> CompilerStage<Ctx> for Parser {
                         // up in the source map before sending this message. If an existing
                         // `SourceId` is already present, then the message is not sent.
 
+                        let file_system = file_system.clone();
+                        let known_modules = known_modules.clone();
+                        discovered += 1;
+
                         scope.spawn(move |_| {
                             // reserve a module id for the module we are about to parse.
-                            parse_source(ParseSource::from_source(source, None), sender)
+                            parse_source(
+                                ParseSource::from_source(source, None),
+                                sender,
+                                file_system,
+                                known_modules,
+                            )
                         });
                     }
                     ParserAction::Error { diagnostics, timings } => {
                         collected_diagnostics.extend(diagnostics);
                         self.merge_metrics(timings);
+
+                        parsed += 1;
+                        progress.report("parse", parsed, discovered);
                     }
                 }
             }
         });
 
+        for (source, dependency) in dependencies {
+            workspace.add_dependency(source, dependency);
+        }
+
         if collected_diagnostics.is_empty() {
             Ok(())
         } else {
@@ -140,13 +203,13 @@ impl<Ctx: ParserCtxQuery> CompilerStage<Ctx> for Parser {
         }
     }
 
-    /// Any other stage than `semantic_pass` is valid when `--dump-ast` is
+    /// Any other stage than `semantic_pass` is valid when `--emit=ast` is
     /// specified.
     fn cleanup(&mut self, entry_point: SourceId, ctx: &mut Ctx) {
         let settings = ctx.settings();
         let mut stdout = ctx.output_stream();
 
-        if settings.stage < CompilerStageKind::UntypedAnalysis && settings.ast_settings().dump {
+        if settings.stage < CompilerStageKind::UntypedAnalysis && settings.emits(EmitKind::Ast) {
             let set = settings.character_set;
             let mode = settings.ast_settings.dump_mode;
             ctx.workspace().print_sources(entry_point, mode, set, &mut stdout).unwrap();
@@ -169,6 +232,11 @@ pub enum ParserAction {
     /// lexing and parsing.
     ParseImport { source: SourceId, sender: Sender<ParserAction> },
 
+    /// A source imports the given module, this is sent for every resolved
+    /// import, including the ones that refer to modules that have already
+    /// been parsed.
+    AddDependency { source: SourceId, dependency: ModuleId },
+
     /// A unrecoverable error occurred during the parsing or lexing of a module.
     Error { diagnostics: Vec<Report>, timings: StageMetrics },
 
@@ -210,20 +278,26 @@ pub enum ParserAction {
     },
 }
 
-/// Parse a specific source specified by [ParseSource].
-fn parse_source(source: ParseSource, sender: Sender<ParserAction>) {
+/// Parse a specific source specified by [ParseSource], the source and any of
+/// its imports are read from the given [FileSystem].
+fn parse_source(
+    source: ParseSource,
+    sender: Sender<ParserAction>,
+    file_system: Arc<dyn FileSystem>,
+    known_modules: KnownModules,
+) {
     let mut timings = StageMetrics::default();
     let id = source.id();
 
-    // Read in the contents from disk if this is a module, otherwise copy
-    // from the already inserted interactive line.
+    // Read in the contents from the file system if this is a module, otherwise
+    // copy from the already inserted interactive line.
     let contents = timings.record("read", |_| {
         if id.is_interactive() {
             // @@Dumbness: We have to copy out the contents of the interactive line.
             Ok(SourceMapUtils::map(id, |source| source.owned_contents()))
         } else {
             let path = SourceMapUtils::map(id, |source| source.path().to_path_buf());
-            read_in_path(path.as_path())
+            read_in_path(file_system.as_ref(), path.as_path())
         }
     });
 
@@ -265,7 +339,13 @@ fn parse_source(source: ParseSource, sender: Sender<ParserAction>) {
 
     // Create a new import resolver in the event of more modules that
     // are encountered whilst parsing this module.
-    let resolver = ImportResolver::new(id, source.parent(), sender);
+    let resolver = ImportResolver::new(
+        id,
+        source.parent(),
+        file_system.as_ref(),
+        &known_modules,
+        sender,
+    );
     let mut diagnostics = ParserDiagnostics::new();
     let mut spans = LocalSpanMap::with_capacity(id, tokens.len() * 2);
     let mut gen = AstGen::new(spanned, &tokens, &resolver, &mut diagnostics, &mut spans);
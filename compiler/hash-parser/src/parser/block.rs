@@ -76,7 +76,7 @@ impl<'s> AstGen<'s> {
 
         self.parse_token(TokenKind::Keyword(Keyword::In))?;
 
-        let iterator = self.parse_expr_with_precedence(0)?;
+        let iterator = self.parse_for_loop_iterator()?;
         let body = self.parse_block()?;
 
         Ok(self.node_with_joined_span(
@@ -85,6 +85,28 @@ impl<'s> AstGen<'s> {
         ))
     }
 
+    /// Parse the iterable of a `for` loop, which is either an expression or a
+    /// range of values, i.e. `0..<len`.
+    fn parse_for_loop_iterator(&mut self) -> ParseResult<AstNode<Expr>> {
+        let start = self.current_pos();
+        let lo = self.parse_expr_with_precedence(0)?;
+
+        let end = match self.peek_kind() {
+            Some(TokenKind::Range) => {
+                self.skip_fast(TokenKind::Range);
+                RangeEnd::Included
+            }
+            Some(TokenKind::RangeExclusive) => {
+                self.skip_fast(TokenKind::RangeExclusive);
+                RangeEnd::Excluded
+            }
+            _ => return Ok(lo),
+        };
+
+        let hi = self.parse_expr_with_precedence(0)?;
+        Ok(self.node_with_joined_span(Expr::Range(RangeExpr { lo, hi, end }), start))
+    }
+
     /// Parse a `while` loop block.
     pub(crate) fn parse_while_loop(&mut self) -> ParseResult<AstNode<Block>> {
         let start = self.current_pos();
@@ -4,7 +4,10 @@ use hash_ast::ast::*;
 use hash_token::{delimiter::Delimiter, keyword::Keyword, TokenKind};
 
 use super::AstGen;
-use crate::diagnostics::error::{ParseErrorKind, ParseResult};
+use crate::diagnostics::{
+    error::{ParseErrorKind, ParseResult},
+    expected::ExpectedItem,
+};
 
 impl<'s> AstGen<'s> {
     /// Construct the [Params] from the parsed [`AstNodes<Param>`]. This is
@@ -80,6 +83,41 @@ impl<'s> AstGen<'s> {
         Ok(self.make_params(params, origin))
     }
 
+    /// Parse the parameters of a function definition. The parameters may end
+    /// with a `...` which specifies that the function accepts any number of
+    /// additional arguments, this is returned alongside the parameters.
+    pub(crate) fn parse_fn_def_params(&mut self) -> ParseResult<(AstNode<Params>, bool)> {
+        let mut c_variadic = None;
+
+        let params = self.in_tree(Delimiter::Paren, None, |gen| {
+            Ok(gen.parse_nodes_with_skips(
+                |g, _| {
+                    // The `...` can only appear as the last item of the parameters.
+                    if let Some(span) = c_variadic {
+                        return g.err_with_location(
+                            ParseErrorKind::MisplacedCVariadic,
+                            ExpectedItem::empty(),
+                            None,
+                            span,
+                        );
+                    }
+
+                    match g.peek_kind() {
+                        Some(TokenKind::Ellipsis) => {
+                            g.skip_fast(TokenKind::Ellipsis); // `...`
+                            c_variadic = Some(g.previous_pos());
+                            Ok(None)
+                        }
+                        _ => Ok(Some(g.parse_param(ParamOrigin::Fn)?)),
+                    }
+                },
+                |g| g.parse_token(TokenKind::Comma),
+            ))
+        })?;
+
+        Ok((self.make_params(params, ParamOrigin::Fn), c_variadic.is_some()))
+    }
+
     /// Parses an nominal definition type field, which could either be a named
     /// or un-named field. The un-named field is just a specified type,
     /// whilst a named variant, is a specified name and then an optional
@@ -2,7 +2,7 @@
 //! logic that transforms tokens into an AST.
 use hash_ast::ast::*;
 use hash_reporting::diagnostic::HasDiagnosticsMut;
-use hash_source::location::ByteRange;
+use hash_source::{identifier::IDENTS, location::ByteRange};
 use hash_token::{delimiter::Delimiter, keyword::Keyword, IntLitKind, Token, TokenKind};
 use hash_utils::thin_vec::thin_vec;
 
@@ -144,6 +144,15 @@ impl<'s> AstGen<'s> {
                 let data = self.parse_primitive_lit();
                 self.node_with_span(Expr::Lit(LitExpr { data }), token.span)
             }
+            // Typed holes, either `?` or `_`
+            TokenKind::Question => {
+                self.skip_fast(token.kind); // `?`
+                self.node_with_span(Expr::Hole(HoleExpr {}), token.span)
+            }
+            TokenKind::Ident(ident) if ident == IDENTS.underscore => {
+                self.skip_fast(token.kind); // `_`
+                self.node_with_span(Expr::Hole(HoleExpr {}), token.span)
+            }
             TokenKind::Ident(ident) => {
                 self.skip_fast(token.kind); // `ident`
                 let name = self.node_with_span(Name { ident }, token.span);
@@ -286,10 +295,26 @@ impl<'s> AstGen<'s> {
     /// precedence of each operator using Pratt parsing.
     pub(crate) fn parse_expr_with_precedence(
         &mut self,
-        mut min_prec: u8,
+        min_prec: u8,
     ) -> ParseResult<AstNode<Expr>> {
+        self.parse_binary_expr(min_prec).map(|(expr, _)| expr)
+    }
+
+    /// Parse the chain of binary expressions of
+    /// [`Self::parse_expr_with_precedence`], and also return the outermost
+    /// operator of the expression if the operator isn't wrapped within
+    /// parentheses.
+    ///
+    /// Ordering comparisons that directly follow one another, e.g. `a < b < c`,
+    /// are collected into a [ChainedComparisonExpr] rather than comparing the
+    /// result of `a < b` with `c`.
+    fn parse_binary_expr(
+        &mut self,
+        mut min_prec: u8,
+    ) -> ParseResult<(AstNode<Expr>, Option<BinOp>)> {
         // first of all, we want to get the lhs...
         let (mut lhs, lhs_span) = self.track_span(|this| this.parse_expr())?;
+        let mut lhs_op = None;
 
         loop {
             let op_start = self.current_pos();
@@ -336,18 +361,62 @@ impl<'s> AstGen<'s> {
                 // being `r_prec`.
                 min_prec = r_prec;
             } else {
-                let rhs = self.parse_expr_with_precedence(r_prec)?;
+                let (rhs, rhs_op) = self.parse_binary_expr(r_prec)?;
 
-                //v transform the operator into an `BinaryExpr`
+                // transform the operator into either a `BinaryExpr` or a link of a chain
                 let operator = self.node_with_span(op, op_span);
-                lhs = self.node_with_joined_span(
-                    Expr::BinaryExpr(BinaryExpr { lhs, rhs, operator }),
-                    lhs_span,
-                );
+
+                // Comparing the result of an ordering comparison for equality is
+                // likely meant to be a chain, i.e. `a < b == c` is `(a < b) == c`.
+                if matches!(op, BinOp::EqEq | BinOp::NotEq) {
+                    let ordering =
+                        [lhs_op, rhs_op].into_iter().flatten().find(BinOp::is_ordering_comparator);
+
+                    if let Some(ordering) = ordering {
+                        self.add_warning(ParseWarning::new(
+                            WarningKind::UnchainedComparison { ordering, equality: op },
+                            self.make_span(lhs_span.join(self.previous_pos())),
+                        ));
+                    }
+                }
+
+                let is_chained = op.is_ordering_comparator()
+                    && lhs_op.is_some_and(|lhs_op| lhs_op.is_ordering_comparator());
+
+                lhs = if is_chained {
+                    // An ordering comparison that directly follows another one extends
+                    // the chain, rather than comparing the result of the previous one.
+                    let (mut operands, mut operators) = match lhs.into_body() {
+                        Expr::BinaryExpr(BinaryExpr { lhs: first, rhs: second, operator }) => {
+                            (thin_vec![first, second], thin_vec![operator])
+                        }
+                        Expr::ChainedComparison(chain) => {
+                            (chain.operands.nodes, chain.operators.nodes)
+                        }
+                        _ => unreachable!("an ordering comparison is a binary expression"),
+                    };
+
+                    operands.push(rhs);
+                    operators.push(operator);
+
+                    let chain = ChainedComparisonExpr {
+                        operands: self.nodes_with_joined_span(operands, lhs_span),
+                        operators: self.nodes_with_joined_span(operators, lhs_span),
+                    };
+
+                    self.node_with_joined_span(Expr::ChainedComparison(chain), lhs_span)
+                } else {
+                    self.node_with_joined_span(
+                        Expr::BinaryExpr(BinaryExpr { lhs, rhs, operator }),
+                        lhs_span,
+                    )
+                };
             }
+
+            lhs_op = Some(op);
         }
 
-        Ok(lhs)
+        Ok((lhs, lhs_op))
     }
 
     /// Provided an initial subject expression that is parsed by the parent
@@ -486,21 +555,25 @@ impl<'s> AstGen<'s> {
             TokenKind::Amp => {
                 self.skip_fast(TokenKind::Amp); // `&`
 
-                // Check if this reference is raw...
+                // Check if this reference is raw or reference counted...
                 match self.peek().copied() {
-                    Some(token) if token.has_kind(TokenKind::Keyword(Keyword::Raw)) => {
-                        self.skip_fast(token.kind); // `raw`
+                    Some(Token {
+                        kind: kw @ TokenKind::Keyword(Keyword::Raw | Keyword::Rc),
+                        ..
+                    }) => {
+                        self.skip_fast(kw); // `raw` or `rc`
+
+                        let kind = match kw {
+                            TokenKind::Keyword(Keyword::Raw) => RefKind::Raw,
+                            _ => RefKind::Rc,
+                        };
 
                         // Parse a mutability modifier if any
                         let mutability = self
                             .parse_token_fast(TokenKind::Keyword(Keyword::Mut))
                             .map(|_| self.node_with_span(Mutability::Mutable, self.current_pos()));
 
-                        Expr::Ref(RefExpr {
-                            inner_expr: self.parse_expr()?,
-                            kind: RefKind::Raw,
-                            mutability,
-                        })
+                        Expr::Ref(RefExpr { inner_expr: self.parse_expr()?, kind, mutability })
                     }
                     Some(Token { kind: kw @ TokenKind::Keyword(Keyword::Mut), span }) => {
                         self.skip_fast(kw); // `mut`
@@ -509,7 +582,7 @@ impl<'s> AstGen<'s> {
 
                         Expr::Ref(RefExpr {
                             inner_expr,
-                            kind: RefKind::Raw,
+                            kind: RefKind::Normal,
                             mutability: Some(self.node_with_span(Mutability::Mutable, span)),
                         })
                     }
@@ -583,6 +656,10 @@ impl<'s> AstGen<'s> {
     /// ^^^^^^^^  ^^^   ^^^─────┐
     /// pattern   type    the right hand-side expr
     /// ```
+    ///
+    /// If the pattern is refutable, the declaration can be followed by an
+    /// `else` block that is run when the value doesn't match the pattern,
+    /// e.g. `Some(x) := y else { return };`.
     pub(crate) fn parse_declaration(&mut self, pat: AstNode<Pat>) -> ParseResult<Declaration> {
         // Attempt to parse an optional type...
         let ty = match self.peek_kind() {
@@ -590,10 +667,44 @@ impl<'s> AstGen<'s> {
             _ => Some(self.parse_ty()?),
         };
 
+        // A declaration with a type uses `=` rather than `:=`, i.e. `x: i32 = 1`,
+        // so if a `:=` follows the type then we suggest to use `=` instead.
+        if let (Some(colon @ Token { kind: TokenKind::Colon, .. }), Some(eq)) =
+            (self.peek(), self.peek_second())
+            && eq.has_kind(TokenKind::Eq)
+        {
+            let span = self.make_span(colon.span.join(eq.span));
+
+            return Err(self
+                .make_err(
+                    ParseErrorKind::UnExpected,
+                    ExpectedItem::Eq,
+                    Some(colon.kind),
+                    Some(colon.span),
+                )
+                .with_suggestion(span, "="));
+        }
+
+        // A declaration with a type can omit the initialiser, i.e. `mut x: i32;`,
+        // in which case the binding is initialised later.
+        if ty.is_some() && matches!(self.peek_kind(), None | Some(TokenKind::Semi)) {
+            return Ok(Declaration { pat, ty, value: None, otherwise: None });
+        }
+
         // Now parse the initialiser...
         self.parse_token(TokenKind::Eq)?;
-        let value = self.parse_expr_with_precedence(0)?;
-        Ok(Declaration { pat, ty, value })
+        let value = Some(self.parse_expr_with_precedence(0)?);
+
+        // ...and the optional `else` block.
+        let otherwise = match self.peek_kind() {
+            Some(kw @ TokenKind::Keyword(Keyword::Else)) => {
+                self.skip_fast(kw); // `else`
+                Some(self.parse_block()?)
+            }
+            _ => None,
+        };
+
+        Ok(Declaration { pat, ty, value, otherwise })
     }
 
     /// Given a initial left-hand side expression, attempt to parse a
@@ -752,7 +863,13 @@ impl<'s> AstGen<'s> {
 
                 // We want to emit a redundant parentheses warning if it is not a binary-like
                 // expression since it does not affect the precedence...
-                if !matches!(expr.body(), Expr::BinaryExpr(_) | Expr::Cast(_) | Expr::FnDef(_)) {
+                if !matches!(
+                    expr.body(),
+                    Expr::BinaryExpr(_)
+                        | Expr::ChainedComparison(_)
+                        | Expr::Cast(_)
+                        | Expr::FnDef(_)
+                ) {
                     gen.add_warning(ParseWarning::new(
                         WarningKind::RedundantParenthesis(expr.body().into()),
                         gen.make_span(gen.range()),
@@ -798,7 +915,7 @@ impl<'s> AstGen<'s> {
     pub(crate) fn parse_fn_def(&mut self) -> ParseResult<AstNode<Expr>> {
         // parse function definition parameters.
         let start = self.current_pos();
-        let params = self.parse_params(ParamOrigin::Fn)?;
+        let (params, c_variadic) = self.parse_fn_def_params()?;
 
         // check if there is a return type
         let return_ty = match self.peek_resultant_fn(|g| g.parse_token(TokenKind::ThinArrow)) {
@@ -806,14 +923,26 @@ impl<'s> AstGen<'s> {
             _ => None,
         };
 
-        self.parse_token(TokenKind::FatArrow)?;
+        // A function without a body is a declaration of a foreign function, i.e.
+        // `puts := (s: &raw u8) -> i32`, which must specify a return type.
+        let has_body = match return_ty {
+            Some(_) => self.peek_resultant_fn(|g| g.parse_token(TokenKind::FatArrow)).is_some(),
+            None => {
+                self.parse_token(TokenKind::FatArrow)?;
+                true
+            }
+        };
 
         let fn_body = match self.peek() {
-            Some(_) => self.parse_expr_with_precedence(0)?,
+            _ if !has_body => None,
+            Some(_) => Some(self.parse_expr_with_precedence(0)?),
             None => self.err(ParseErrorKind::ExpectedFnBody, ExpectedItem::empty(), None)?,
         };
 
-        Ok(self.node_with_joined_span(Expr::FnDef(FnDef { params, return_ty, fn_body }), start))
+        Ok(self.node_with_joined_span(
+            Expr::FnDef(FnDef { params, return_ty, fn_body, c_variadic }),
+            start,
+        ))
     }
 
     /// Function to parse a sequence of top-level [Expr]s from the current
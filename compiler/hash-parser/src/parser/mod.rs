@@ -252,6 +252,7 @@ impl<'s> AstGen<'s> {
             self.make_span(span.unwrap_or_else(|| self.eof_pos())),
             expected,
             received,
+            None,
         )
     }
 
@@ -81,10 +81,18 @@ impl<'s> AstGen<'s> {
             TokenKind::Amp => {
                 self.skip_fast(TokenKind::Amp);
 
-                // Check if this is a raw ref
-                let kind = self
-                    .parse_token_fast(TokenKind::Keyword(Keyword::Raw))
-                    .map(|_| self.node_with_span(RefKind::Raw, self.current_pos()));
+                // Check if this is a raw or a reference counted ref
+                let kind = match self.peek_kind() {
+                    Some(kw @ TokenKind::Keyword(Keyword::Raw)) => {
+                        self.skip_fast(kw);
+                        Some(self.node_with_span(RefKind::Raw, self.current_pos()))
+                    }
+                    Some(kw @ TokenKind::Keyword(Keyword::Rc)) => {
+                        self.skip_fast(kw);
+                        Some(self.node_with_span(RefKind::Rc, self.current_pos()))
+                    }
+                    _ => None,
+                };
 
                 // Parse a mutability modifier if any
                 let mutability = self
@@ -112,16 +120,32 @@ impl<'s> AstGen<'s> {
             TokenKind::Tree(Delimiter::Bracket, _) => {
                 let (inner, len) = self.in_tree(Delimiter::Bracket, None, |gen| {
                     // @@ErrorRecovery: Investigate introducing `Err` variant into types...
-                    let inner_type = gen.parse_ty()?;
+                    let (inner_type, range) = gen.track_span(|gen| gen.parse_ty())?;
 
                     // Optionally, the user may specify a size for the array type by
                     // using a `;` followed by an expression that evaluates to a]
                     // constant integer.
-                    let len = if gen.peek_kind() == Some(TokenKind::Semi) {
-                        gen.skip_fast(TokenKind::Semi);
-                        Some(gen.parse_expr()?)
-                    } else {
-                        None
+                    let len = match gen.peek() {
+                        Some(Token { kind: TokenKind::Semi, .. }) => {
+                            gen.skip_fast(TokenKind::Semi);
+                            Some(gen.parse_expr()?)
+                        }
+                        // If anything else follows the type, then the `;` before the
+                        // size is likely missing, i.e. `[i32 4]`.
+                        Some(token) => {
+                            let span = gen.make_span(range);
+                            let suggestion = format!("{};", gen.source.hunk(range));
+
+                            return Err(gen
+                                .make_err(
+                                    ParseErrorKind::UnExpected,
+                                    ExpectedItem::Semi,
+                                    Some(token.kind),
+                                    Some(token.span),
+                                )
+                                .with_suggestion(span, suggestion));
+                        }
+                        None => None,
                     };
 
                     Ok((inner_type, len))
@@ -6,7 +6,7 @@ use std::{io, path::PathBuf};
 
 use hash_reporting::report::{Report, ReportKind};
 
-use crate::fs::ImportError;
+use crate::{fs::ImportError, settings::CompilerStageKind};
 
 // Errors that might occur when attempting to compile and or interpret a
 /// program.
@@ -33,6 +33,35 @@ pub enum PipelineError {
     /// When a configuration key value is not a valid option
     /// for the specified key.
     InvalidValue(String, String),
+
+    /// A compilation was started whilst another compilation of the process
+    /// was still alive, the stores of the compiler are global to the process
+    /// and so only one compilation can exist at a time.
+    CompilationInProgress,
+    /// The compilation was cancelled through its [CancellationToken].
+    ///
+    /// [CancellationToken]: crate::interface::CancellationToken
+    Cancelled,
+
+    /// The compiler used more memory than the limit that was specified via
+    /// `--max-memory`.
+    MemoryLimitExceeded {
+        /// The limit in mebibytes.
+        limit: usize,
+
+        /// The number of mebibytes that were used.
+        used: usize,
+
+        /// The name of the store that uses the most memory, and the number of
+        /// mebibytes that it uses.
+        largest_store: Option<(String, usize)>,
+
+        /// The stage that was running when the limit was exceeded.
+        stage: CompilerStageKind,
+
+        /// The item, e.g. the body, that the stage was working on.
+        item: Option<String>,
+    },
 }
 
 impl From<PipelineError> for Report {
@@ -59,6 +88,22 @@ impl From<PipelineError> for Report {
             PipelineError::InvalidValue(key, value) => {
                 format!("invalid value `{value}` for configuration key `{key}`")
             }
+            PipelineError::CompilationInProgress => {
+                "another compilation is still in progress within this process".to_string()
+            }
+            PipelineError::Cancelled => "the compilation was cancelled".to_string(),
+            PipelineError::MemoryLimitExceeded { limit, used, largest_store, stage, item } => {
+                let item = item.map(|item| format!(" whilst working on `{item}`"));
+                let largest_store = largest_store
+                    .map(|(name, size)| format!(", of which {size}MiB by the `{name}` store"));
+
+                format!(
+                    "the compiler exceeded the memory limit of {limit}MiB ({used}MiB were used{}) \
+                    during the `{stage}` stage{}",
+                    largest_store.unwrap_or_default(),
+                    item.unwrap_or_default()
+                )
+            }
         };
 
         report.kind(ReportKind::Error).title(message);
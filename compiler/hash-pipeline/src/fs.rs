@@ -9,6 +9,8 @@ use std::{
 use hash_reporting::report::{Report, ReportKind};
 use hash_source::constant::StringId;
 
+use crate::vfs::FileSystem;
+
 /// The location of a build directory of this package, this used to resolve
 /// where the standard library is located at.
 const STDLIB: &str = env!("STDLIB_PATH");
@@ -132,18 +134,21 @@ fn get_stdlib_modules(dir: impl AsRef<Path>) -> Vec<PathBuf> {
     paths
 }
 
-/// Function to read in the contents of a file specified by a [Path]. If
-/// reading the file fails, an [ImportError] is returned.
-pub fn read_in_path(import_path: impl AsRef<Path>) -> Result<String, ImportError> {
+/// Function to read in the contents of a file specified by a [Path] from the
+/// [FileSystem]. If reading the file fails, an [ImportError] is returned.
+pub fn read_in_path(
+    file_system: &dyn FileSystem,
+    import_path: impl AsRef<Path>,
+) -> Result<String, ImportError> {
     // Create a interned string to represent the path
-    fs::read_to_string(import_path.as_ref()).map_err(|_| ImportError {
+    file_system.read(import_path.as_ref()).map_err(|_| ImportError {
         kind: ImportErrorKind::UnreadableFile,
         path: StringId::new(import_path.as_ref().to_str().unwrap()),
     })
 }
 
-/// Function used to resolve the path of a module according to the language
-/// resolution rules.
+/// Function used to resolve the path of a module within the [FileSystem]
+/// according to the language resolution rules.
 ///
 /// ## Rules
 ///
@@ -168,6 +173,7 @@ pub fn read_in_path(import_path: impl AsRef<Path>) -> Result<String, ImportError
 /// - If the path to the module couldn't be resolved, an [ImportError] is
 ///   raised.
 pub fn resolve_path<'p>(
+    file_system: &dyn FileSystem,
     path: impl Into<&'p str>,
     wd: impl AsRef<Path>,
 ) -> Result<PathBuf, ImportError> {
@@ -177,7 +183,8 @@ pub fn resolve_path<'p>(
 
     let modules = get_stdlib_modules(STDLIB);
 
-    let canonicalise = |path: &Path| path.canonicalize().map_err(|err| (path.to_path_buf(), err));
+    let canonicalise =
+        |path: &Path| file_system.canonicalise(path).map_err(|err| (path.to_path_buf(), err));
 
     // check if the given path is equal to any of the standard library paths, and
     // if so we prefix it with the standard library path.
@@ -195,16 +202,16 @@ pub fn resolve_path<'p>(
     // an index module that is located within the given directory. This takes
     // precedence over checking if a module is named that directory.
     // More info on this topic: https://hash-org.github.io/lang/modules.html#importing
-    if raw_path.is_dir() {
+    if file_system.is_dir(&raw_path) {
         let idx_path = raw_path.join("index.hash");
 
-        if idx_path.exists() {
+        if file_system.exists(&idx_path) {
             return Ok(canonicalise(&idx_path)?);
         }
 
         // ok now check if the user is referencing a module instead of the dir
         let raw_path_hash = raw_path.with_extension("hash");
-        if raw_path_hash.exists() {
+        if file_system.exists(&raw_path_hash) {
             return Ok(canonicalise(&raw_path_hash)?);
         }
 
@@ -230,7 +237,7 @@ pub fn resolve_path<'p>(
 
                 // Only try to check this route if the provided file did not already have an
                 // extension
-                if raw_path.extension().is_none() && raw_path_hash.exists() {
+                if raw_path.extension().is_none() && file_system.exists(&raw_path_hash) {
                     Ok(canonicalise(&raw_path_hash)?)
                 } else {
                     Err(ImportError { path: StringId::new(path), kind: ImportErrorKind::NotFound })
@@ -2,20 +2,122 @@
 //! that are used by the pipeline to run various stages that transform the
 //! provided sources into runnable/executable code.
 
-use std::sync::{Arc, Mutex};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
 
 use hash_ast::node_map::NodeMap;
 use hash_reporting::report::Report;
 use hash_source::SourceId;
+use hash_storage::store::store_sizes;
+use hash_utils::profiling::get_resident_set_size;
 pub use hash_utils::profiling::StageMetrics;
 
 use crate::{
+    error::PipelineError,
     settings::{CompilerSettings, CompilerStageKind},
     workspace::Workspace,
 };
 
 pub type CompilerResult<T> = Result<T, Vec<Report>>;
 
+/// A [CancellationToken] is used to abort a compilation that is no longer
+/// needed, e.g. when an editor has already requested a compilation of newer
+/// sources. Cancellation is cooperative, the pipeline checks the token before
+/// each stage, and long-running stages check it whilst they are running, i.e.
+/// once per module or once per body.
+///
+/// The token can be cloned and shared between threads, all of the clones
+/// refer to the same token.
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new [CancellationToken] that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request that the compilation is cancelled.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether the compilation has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Check whether the compilation has been cancelled, and if so, produce
+    /// the error that terminates the pipeline.
+    pub fn check(&self) -> CompilerResult<()> {
+        if self.is_cancelled() {
+            Err(vec![PipelineError::Cancelled.into()])
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A [MemoryLimit] bounds the amount of memory that the compiler may use,
+/// which is specified in mebibytes via `--max-memory`. The memory that is
+/// used is approximated by the resident set size of the compiler, or by the
+/// sizes of the registered stores if the resident set size isn't available.
+/// The memory is checked after each stage, and by the long-running stages at
+/// the same points as the [CancellationToken].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryLimit {
+    limit: Option<usize>,
+}
+
+/// The number of bytes in a mebibyte.
+const MEBIBYTE: usize = 1 << 20;
+
+impl MemoryLimit {
+    /// Create a new [MemoryLimit] of the given number of mebibytes, there is
+    /// no limit if it is [None].
+    pub fn new(limit: Option<usize>) -> Self {
+        Self { limit }
+    }
+
+    /// Get the number of bytes that are used, if it exceeds the limit.
+    fn exceeded(&self) -> Option<usize> {
+        let limit = self.limit?;
+        let used = get_resident_set_size()
+            .unwrap_or_else(|| store_sizes().into_iter().map(|(_, size)| size).sum());
+        (used > limit * MEBIBYTE).then_some(used)
+    }
+
+    /// Check whether the compiler uses more memory than the limit.
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded().is_some()
+    }
+
+    /// Check whether the compiler uses more memory than the limit, and if so,
+    /// produce the error that terminates the pipeline. The error names the
+    /// `stage` and the `item`, e.g. the body, that was being worked on, as
+    /// well as the store that uses the most memory.
+    pub fn check(&self, stage: CompilerStageKind, item: Option<&str>) -> CompilerResult<()> {
+        match self.exceeded() {
+            Some(used) => {
+                let largest_store =
+                    store_sizes().into_iter().next().map(|(name, size)| (name, size / MEBIBYTE));
+
+                Err(vec![PipelineError::MemoryLimitExceeded {
+                    limit: self.limit.unwrap(),
+                    used: used / MEBIBYTE,
+                    largest_store,
+                    stage,
+                    item: item.map(str::to_string),
+                }
+                .into()])
+            }
+            None => Ok(()),
+        }
+    }
+}
+
 /// [CompilerStage] represents an abstract stage within the compiler pipeline.
 /// Each stage has an associated [CompilerStageKind] which can be used by
 /// the pipeline which stages to run.
@@ -4,5 +4,7 @@
 pub mod error;
 pub mod fs;
 pub mod interface;
+pub mod progress;
 pub mod settings;
+pub mod vfs;
 pub mod workspace;
@@ -0,0 +1,126 @@
+//! Hash Compiler progress reporting. Long-running stages report how many of
+//! their items, i.e. modules or bodies, they have completed to a
+//! [ProgressReporter] which is stored within the [Workspace]. The reports are
+//! either drawn as a progress bar, or written as JSON messages which can be
+//! consumed by external tooling.
+//!
+//! [Workspace]: crate::workspace::Workspace
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use hash_utils::json::JsonValue;
+
+use crate::{interface::CompilerOutputStream, settings::ProgressMode};
+
+/// The progress of a stage at some point during its execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageProgress {
+    /// The name of the stage, or of the part of the stage, that is running.
+    pub stage: &'static str,
+
+    /// The number of items that have been completed.
+    pub completed: usize,
+
+    /// The number of items that the stage knows about, this may grow whilst
+    /// the stage is running, e.g. as the parser discovers more imports.
+    pub total: usize,
+}
+
+impl StageProgress {
+    /// Whether all of the items of the stage have been completed.
+    pub fn is_finished(&self) -> bool {
+        self.completed >= self.total
+    }
+}
+
+/// A [ProgressReporter] is notified whenever a stage makes progress, stages
+/// may report progress from several threads at once.
+pub trait ProgressReporter: Send + Sync {
+    fn report(&self, progress: StageProgress);
+}
+
+/// A handle to the [ProgressReporter] of the current compilation, reporting
+/// progress does nothing if there is no reporter.
+#[derive(Clone, Default)]
+pub struct Progress {
+    reporter: Option<Arc<dyn ProgressReporter>>,
+}
+
+impl Progress {
+    /// Create a [Progress] that notifies the given [ProgressReporter].
+    pub fn new(reporter: impl ProgressReporter + 'static) -> Self {
+        Self { reporter: Some(Arc::new(reporter)) }
+    }
+
+    /// Create a [Progress] that reports in the given [ProgressMode] to the
+    /// given stream.
+    pub fn with_mode(mode: ProgressMode, stream: CompilerOutputStream) -> Self {
+        match mode {
+            ProgressMode::Bar => Self::new(ProgressBar::new(stream)),
+            ProgressMode::Json => Self::new(JsonProgress::new(stream)),
+        }
+    }
+
+    /// Report that `completed` out of `total` items of the `stage` have been
+    /// completed.
+    pub fn report(&self, stage: &'static str, completed: usize, total: usize) {
+        if let Some(reporter) = &self.reporter {
+            reporter.report(StageProgress { stage, completed, total });
+        }
+    }
+}
+
+/// The number of characters that the bar of a [ProgressBar] is drawn with.
+const BAR_WIDTH: usize = 30;
+
+/// A [ProgressReporter] that redraws a progress bar on a single line of the
+/// terminal, the bar is moved onto a new line once the stage is finished.
+pub struct ProgressBar {
+    stream: Mutex<CompilerOutputStream>,
+}
+
+impl ProgressBar {
+    pub fn new(stream: CompilerOutputStream) -> Self {
+        Self { stream: Mutex::new(stream) }
+    }
+}
+
+impl ProgressReporter for ProgressBar {
+    fn report(&self, progress: StageProgress) {
+        let StageProgress { stage, completed, total } = progress;
+        let filled = (BAR_WIDTH * completed).checked_div(total).unwrap_or(BAR_WIDTH).min(BAR_WIDTH);
+        let bar = format!("{}{}", "=".repeat(filled), " ".repeat(BAR_WIDTH - filled));
+        let end = if progress.is_finished() { "\n" } else { "" };
+
+        // Progress is best effort, a failure to write shouldn't stop the stage.
+        let mut stream = self.stream.lock().unwrap();
+        let _ = write!(stream, "\r{stage:>12} [{bar}] {completed}/{total}{end}");
+        let _ = stream.flush();
+    }
+}
+
+/// A [ProgressReporter] that writes each report as a JSON object on its own
+/// line, e.g. `{ "stage": "lower", "completed": 3, "total": 10 }`.
+pub struct JsonProgress {
+    stream: Mutex<CompilerOutputStream>,
+}
+
+impl JsonProgress {
+    pub fn new(stream: CompilerOutputStream) -> Self {
+        Self { stream: Mutex::new(stream) }
+    }
+}
+
+impl ProgressReporter for JsonProgress {
+    fn report(&self, progress: StageProgress) {
+        let message = JsonValue::object([
+            ("stage", JsonValue::string(progress.stage)),
+            ("completed", JsonValue::from(progress.completed)),
+            ("total", JsonValue::from(progress.total)),
+        ]);
+
+        let _ = writeln!(self.stream.lock().unwrap(), "{}", message.to_single_line());
+    }
+}
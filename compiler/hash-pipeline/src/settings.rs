@@ -5,18 +5,21 @@ use core::fmt;
 use std::{
     env::{self, temp_dir},
     fmt::Display,
+    io::{self, IsTerminal},
     path::PathBuf,
     str::FromStr,
 };
 
 use hash_ast_utils::dump::AstDumpMode;
+use hash_messaging::CompilerMessagingFormat;
+use hash_reporting::hash_error_codes::{warning_codes::HashWarningCode, DiagnosticCode};
 use hash_target::{HasTarget, Target, HOST_TARGET_TRIPLE};
 use hash_utils::{
-    clap::{Args, Parser, ValueEnum},
+    clap::{ArgAction, Args, Parser, Subcommand, ValueEnum},
     tree_writing::CharacterSet,
 };
 
-use crate::{error::PipelineError, fs::resolve_path};
+use crate::{error::PipelineError, fs::resolve_path, interface::MemoryLimit, vfs::RealFileSystem};
 
 /// Various settings that are present on the compiler pipeline when initially
 /// launching.
@@ -56,6 +59,11 @@ pub struct CompilerSettings {
     #[arg(long = "timings", default_value_t = false)]
     pub show_timings: bool,
 
+    /// Write a trace of when each stage ran to the given path, in the Chrome
+    /// trace event format, e.g. `--timings-trace=trace.json`.
+    #[arg(long = "timings-trace")]
+    pub timings_trace: Option<PathBuf>,
+
     /// Whether to output of each stage result.
     #[arg(long, default_value_t = false)]
     pub output_stage_results: bool,
@@ -88,10 +96,63 @@ pub struct CompilerSettings {
     #[arg(long, value_parser = CharacterSet::parse, required=false, default_value = "unicode", default_value_t = CharacterSet::Unicode)]
     pub character_set: CharacterSet,
 
+    /// The format that diagnostics are written in, with `json` each
+    /// diagnostic is written to the output stream as a JSON object on its
+    /// own line, e.g. `--message-format=json`.
+    #[arg(long = "message-format", default_value_t = CompilerMessagingFormat::default())]
+    pub message_format: CompilerMessagingFormat,
+
+    /// Whether diagnostics are written with colours, e.g. `--color=never`.
+    #[arg(long = "color", default_value_t = ColourMode::default())]
+    pub colour: ColourMode,
+
     /// The optimisation level that is to be performed.
     #[arg(long, default_value_t = OptimisationLevel::default())]
     pub optimisation_level: OptimisationLevel,
 
+    /// The sanitiser instrumentation that should be emitted, this is
+    /// independent of the [OptimisationLevel].
+    #[arg(long = "sanitize", default_value_t = SanitizeMode::default())]
+    pub sanitize: SanitizeMode,
+
+    /// Which parts of the compiler should record a structured trace of their
+    /// internal operations, e.g. `--trace=tc`.
+    #[arg(long = "trace", value_delimiter = ',')]
+    pub trace: Vec<TraceTarget>,
+
+    /// Report the progress of the long-running stages, either as a progress
+    /// bar or as JSON messages, e.g. `--progress=bar`.
+    #[arg(long = "progress")]
+    pub progress: Option<ProgressMode>,
+
+    /// The maximum number of mebibytes of memory that the compiler may use,
+    /// the compilation is stopped with an error once more memory is used.
+    #[arg(long = "max-memory")]
+    pub max_memory: Option<usize>,
+
+    /// The artefacts that the compiler should produce, e.g. `--emit=ir,obj`.
+    /// If no artefacts are specified, an executable is produced.
+    #[arg(long = "emit", value_delimiter = ',')]
+    pub emit: Vec<EmitKind>,
+
+    /// Report the given warnings, which are either a warning code or
+    /// `warnings` for all of the warnings, e.g. `-W W0001`.
+    #[arg(short = 'W', long = "warn", value_name = "WARNING")]
+    pub warn: Vec<LintTarget>,
+
+    /// Report the given warnings as errors, e.g. `-D warnings`.
+    #[arg(short = 'D', long = "deny", value_name = "WARNING")]
+    pub deny: Vec<LintTarget>,
+
+    /// Don't report the given warnings, e.g. `-A W0006`.
+    #[arg(short = 'A', long = "allow", value_name = "WARNING")]
+    pub allow: Vec<LintTarget>,
+
+    /// The maximum number of errors that are reported, the remaining errors
+    /// are omitted. If no limit is specified, all errors are reported.
+    #[arg(long = "error-limit")]
+    pub error_limit: Option<usize>,
+
     /// All settings that relate to any AST traversing stages.
     #[command(flatten)]
     pub ast_settings: AstSettings,
@@ -113,6 +174,12 @@ pub struct CompilerSettings {
     /// code generation.
     #[arg(long, default_value_t = CompilerStageKind::default())]
     pub stage: CompilerStageKind,
+
+    /// An optional developer command that the compiler should run instead
+    /// of the normal pipeline. The command is applied onto the rest of the
+    /// settings via [`CompilerSettings::apply_command`].
+    #[command(subcommand)]
+    pub command: Option<CompilerCommand>,
 }
 
 impl CompilerSettings {
@@ -127,10 +194,16 @@ impl CompilerSettings {
     pub fn try_entry_point(&self) -> Option<Result<PathBuf, PipelineError>> {
         self.entry_point.as_ref().map(|path| {
             let current_dir = env::current_dir().unwrap();
-            resolve_path(path.to_str().unwrap(), current_dir).map_err(PipelineError::ImportPath)
+            resolve_path(&RealFileSystem, path.to_str().unwrap(), current_dir)
+                .map_err(PipelineError::ImportPath)
         })
     }
 
+    /// Get the [MemoryLimit] of the compilation.
+    pub fn memory_limit(&self) -> MemoryLimit {
+        MemoryLimit::new(self.max_memory)
+    }
+
     /// Get the entry point from the [CompilerSettings] whilst asserting that
     /// there must be a given entry point in this case. If the entrypoint is not
     /// specified, a [`PipelineError::MissingEntryPoint`] is then returned.
@@ -197,14 +270,52 @@ impl CompilerSettings {
     /// Configure the [CompilerSettings] to have a specified
     /// [OptimisationLevel].
     ///
-    /// This function will also disable the default options that a
-    /// [OptimisationLevel] implies, i.e. for "release",
-    /// `checked_operations` are disabled.
+    /// The options that an [OptimisationLevel] implies are derived from the
+    /// level when they are queried, i.e. [`Self::checked_operations`].
     pub fn set_optimisation_level(&mut self, level: OptimisationLevel) {
         self.optimisation_level = level;
+    }
+
+    /// Whether checked operations should be emitted when lowering. These are
+    /// disabled for "release" builds, and by the [LoweringSettings]. The
+    /// `checks` sanitiser always keeps the checks regardless of either.
+    pub fn checked_operations(&self) -> bool {
+        self.sanitize.keeps_checks()
+            || (self.lowering_settings.checked_operations
+                && self.optimisation_level != OptimisationLevel::Release)
+    }
 
-        if self.optimisation_level == OptimisationLevel::Release {
-            self.lowering_settings.checked_operations = false;
+    /// Apply the [CompilerCommand] that was specified onto the rest of the
+    /// settings, if any. This configures the entry point and the stage that
+    /// the compiler should run to for the command.
+    pub fn apply_command(&mut self) {
+        match self.command.take() {
+            Some(CompilerCommand::Scopes { file, mode }) => {
+                self.entry_point = Some(file);
+                self.stage = CompilerStageKind::Analysis;
+                self.semantic_settings.dump_scopes = Some(mode);
+            }
+            Some(CompilerCommand::ExplainLayout { file, ty }) => {
+                self.entry_point = Some(file);
+                self.stage = CompilerStageKind::Lower;
+                self.lowering_settings.explain_layout = Some(ty);
+                self.lowering_settings.layout_dump_mode = LayoutDumpMode::Diagram;
+            }
+            Some(CompilerCommand::Callgraph { file, mode, from }) => {
+                self.entry_point = Some(file);
+                self.stage = CompilerStageKind::Lower;
+                self.emit.push(EmitKind::Ir);
+                self.lowering_settings.dump_mode = IrDumpMode::CallGraph;
+                self.lowering_settings.call_graph_mode = mode;
+                self.lowering_settings.call_graph_from = from;
+            }
+            Some(CompilerCommand::Watch { file }) => {
+                self.entry_point = Some(file);
+            }
+            Some(CompilerCommand::Listen) => {
+                self.message_format = CompilerMessagingFormat::Json;
+            }
+            Some(CompilerCommand::Explain { .. }) | None => {}
         }
     }
 
@@ -226,6 +337,51 @@ impl CompilerSettings {
         self.stage = stage;
     }
 
+    /// Whether the given [EmitKind] artefact should be produced. The `--*-dump`
+    /// flags are shorthands for the corresponding artefacts.
+    pub fn emits(&self, kind: EmitKind) -> bool {
+        let flag = match kind {
+            EmitKind::Ast => self.ast_settings.dump,
+            EmitKind::Tir => self.semantic_settings.dump_tir,
+            EmitKind::Ir => self.lowering_settings.dump,
+            EmitKind::Bytecode => self.codegen_settings.dump_bytecode,
+            EmitKind::Asm => self.codegen_settings.dump_assembly,
+            EmitKind::Exe => self.emit.is_empty(),
+            EmitKind::Layout | EmitKind::Obj => false,
+        };
+
+        flag || self.emit.contains(&kind)
+    }
+
+    /// Get the [LintLevel] of a warning with the given code, or of a warning
+    /// that has no code. A level that is given for the code takes precedence
+    /// over a level that is given for all `warnings`, and if a warning is
+    /// given more than one level, then `-D` wins over `-W`, which wins over
+    /// `-A`.
+    pub fn lint_level(&self, code: Option<HashWarningCode>) -> LintLevel {
+        let level_of = |target: LintTarget| {
+            let levels = [
+                (&self.deny, LintLevel::Deny),
+                (&self.warn, LintLevel::Warn),
+                (&self.allow, LintLevel::Allow),
+            ];
+
+            levels
+                .into_iter()
+                .find(|(targets, _)| targets.contains(&target))
+                .map(|(_, level)| level)
+        };
+
+        code.and_then(|code| level_of(LintTarget::Code(code)))
+            .or_else(|| level_of(LintTarget::Warnings))
+            .unwrap_or(LintLevel::Warn)
+    }
+
+    /// Whether the given [TraceTarget] should be traced.
+    pub fn is_tracing(&self, target: TraceTarget) -> bool {
+        self.trace.contains(&target)
+    }
+
     /// Get a reference to the [AstSettings].
     pub fn ast_settings(&self) -> &AstSettings {
         &self.ast_settings
@@ -272,21 +428,185 @@ impl Default for CompilerSettings {
             output_directory: None,
             output_stage_results: false,
             show_timings: false,
+            timings_trace: None,
             skip_prelude: false,
             prelude_is_quiet: false,
             emit_errors: true,
             character_set: CharacterSet::Unicode,
+            message_format: CompilerMessagingFormat::default(),
+            colour: ColourMode::default(),
             worker_count: num_cpus::get(),
             stage: CompilerStageKind::default(),
             optimisation_level: OptimisationLevel::default(),
+            sanitize: SanitizeMode::default(),
+            trace: vec![],
+            progress: None,
+            max_memory: None,
+            emit: vec![],
+            warn: vec![],
+            deny: vec![],
+            allow: vec![],
+            error_limit: None,
             ast_settings: AstSettings::default(),
             lowering_settings: LoweringSettings::default(),
             codegen_settings: CodeGenSettings::default(),
             semantic_settings: SemanticSettings::default(),
+            command: None,
+        }
+    }
+}
+
+/// The parts of the compiler that can record a structured trace of their
+/// internal operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TraceTarget {
+    /// Trace the unifications that are performed by the typechecker, and the
+    /// substitutions that they produce.
+    Tc,
+}
+
+impl fmt::Display for TraceTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tc => write!(f, "tc"),
+        }
+    }
+}
+
+/// The warnings that a [LintLevel] is given to with `-W`, `-D` or `-A`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintTarget {
+    /// All of the warnings, which is written as `warnings`.
+    Warnings,
+
+    /// The warning with the given code, i.e. `W0001`.
+    Code(HashWarningCode),
+}
+
+impl FromStr for LintTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "warnings" {
+            return Ok(Self::Warnings);
+        }
+
+        match s.parse::<DiagnosticCode>()? {
+            DiagnosticCode::Warning(code) => Ok(Self::Code(code)),
+            DiagnosticCode::Error(code) => {
+                Err(format!("`{code}` is an error, only the level of warnings can be changed"))
+            }
+        }
+    }
+}
+
+impl fmt::Display for LintTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warnings => write!(f, "warnings"),
+            Self::Code(code) => write!(f, "{code}"),
         }
     }
 }
 
+/// How a warning is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// The warning isn't reported.
+    Allow,
+
+    /// The warning is reported as a warning.
+    Warn,
+
+    /// The warning is reported as an error.
+    Deny,
+}
+
+/// How the progress of the stages is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// Draw a progress bar for each stage.
+    Bar,
+
+    /// Write each report as a JSON message on its own line.
+    Json,
+}
+
+impl fmt::Display for ProgressMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bar => write!(f, "bar"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Developer commands that can be run by the compiler instead of the
+/// normal pipeline.
+#[derive(Debug, Clone, Subcommand)]
+pub enum CompilerCommand {
+    /// Print the tree of scopes that are entered whilst typechecking the
+    /// given file, including the members of each scope and any bindings
+    /// that shadow another binding.
+    Scopes {
+        /// The file to print the scopes of.
+        file: PathBuf,
+
+        /// Whether to print the scopes as an indented tree, or as a
+        /// `graphviz` graph.
+        #[arg(long, default_value_t = ScopeDumpMode::Tree)]
+        mode: ScopeDumpMode,
+    },
+
+    /// Draw a diagram of the memory of a type that is defined in the given
+    /// file, showing the offsets of each of the fields, the padding between
+    /// them, and the tag of each variant of an enum.
+    ExplainLayout {
+        /// The file that defines the type.
+        file: PathBuf,
+
+        /// The name of the type to explain the layout of.
+        ty: String,
+    },
+
+    /// Print the call graph of all of the functions that are lowered from
+    /// the given file, marking the functions that are reachable from the
+    /// entry point of the program.
+    Callgraph {
+        /// The file to print the call graph of.
+        file: PathBuf,
+
+        /// Whether to print the call graph in the `graphviz` format, or as
+        /// a JSON document.
+        #[arg(long, default_value_t = CallGraphDumpMode::Dot)]
+        mode: CallGraphDumpMode,
+
+        /// Only print the functions that are reachable from the function
+        /// with the given name.
+        #[arg(long)]
+        from: Option<String>,
+    },
+
+    /// Compile the given file, and compile it again whenever it, or any of
+    /// the modules that it imports, changes.
+    Watch {
+        /// The file to compile.
+        file: PathBuf,
+    },
+
+    /// Read messages from the standard input which start compilation
+    /// sessions, i.e. to check a file or to evaluate an expression, and write
+    /// the messages of each session to the standard output as JSON.
+    Listen,
+
+    /// Print the extended explanation of an error or a warning code, i.e.
+    /// `hashc explain E0020`.
+    Explain {
+        /// The code to explain.
+        code: DiagnosticCode,
+    },
+}
+
 /// What optimisation level the compiler should run at.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum OptimisationLevel {
@@ -359,6 +679,85 @@ impl fmt::Display for OptimisationLevel {
     }
 }
 
+/// What sanitiser instrumentation the compiler should emit into the
+/// program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SanitizeMode {
+    /// Don't emit any additional instrumentation.
+    #[default]
+    None,
+
+    /// Keep all overflow and bounds checks even at high optimisation
+    /// levels, and report the values of the operands when a check fails.
+    /// This trades speed for more detailed diagnostics whilst testing.
+    Checks,
+}
+
+impl SanitizeMode {
+    /// Whether the checked operations should always be kept.
+    pub fn keeps_checks(&self) -> bool {
+        matches!(self, Self::Checks)
+    }
+
+    /// Get the sanitiser mode as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Checks => "checks",
+        }
+    }
+}
+
+impl fmt::Display for SanitizeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// When the compiler should write diagnostics and logs with colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColourMode {
+    /// Use colours if the error stream is a terminal, and the `NO_COLOR`
+    /// environment variable is not set.
+    #[default]
+    Auto,
+
+    /// Always use colours.
+    Always,
+
+    /// Never use colours.
+    Never,
+}
+
+impl ColourMode {
+    /// Whether colours should be used in this mode.
+    pub fn use_colours(&self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                let no_colour = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !no_colour && io::stderr().is_terminal()
+            }
+        }
+    }
+
+    /// Get the colour mode as a string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+}
+
+impl fmt::Display for ColourMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Settings that relate to stages that exclusively operate on the
 /// AST that is generated by the parsing, these could be stages that
 /// re-write the AST, analyse it or modify it in some way.
@@ -386,46 +785,220 @@ impl Default for AstSettings {
 /// Settings that relate to the IR stage of the compiler, these include if the
 /// IR should be dumped (and in which mode), whether the IR should be optimised,
 /// whether the IR should use `checked` operations, etc.
-#[derive(Debug, Clone, Copy, Args)]
+#[derive(Debug, Clone, Args)]
 pub struct LoweringSettings {
     /// Whether the IR should dump all lowered bodies, rather than
     /// relying on user directives to select specific bodies.
     #[arg(name = "ir-dump", long = "ir-dump", default_value_t = false)]
     pub dump: bool,
 
-    /// What kind of dumping mode should it be, either being "pretty"
-    /// or "graphviz" mode.
+    /// What kind of dumping mode should it be, either being "pretty",
+    /// "summary", "graphviz", "call-graph", "stack-usage", "json" or "binary"
+    /// mode.
     #[arg(long="ir-dump-mode", default_value_t = IrDumpMode::Pretty)]
     pub dump_mode: IrDumpMode,
 
+    /// Annotate each statement and terminator in the "pretty" IR dump with
+    /// the source location that it originated from.
+    #[arg(long = "ir-dump-spans", default_value_t = false)]
+    pub dump_spans: bool,
+
+    /// Whether the terminators in the "pretty" IR dump should show the blocks
+    /// that they jump to, e.g. `--ir-dump-edges=false`.
+    #[arg(long = "ir-dump-edges", default_value_t = true, action = ArgAction::Set)]
+    pub dump_edges: bool,
+
+    /// The maximum number of elements of an array constant that are written
+    /// in the "pretty" IR dump, any remaining elements are elided with `..`.
+    #[arg(long = "ir-dump-array-limit", default_value_t = 16)]
+    pub dump_array_limit: usize,
+
     /// Use checked operations when emitting IR, this is usually derived whether
     /// the compiler is building a debug variant or not.
     #[arg(long = "ir-checked-operations", default_value_t = true)]
     pub checked_operations: bool,
+
+    /// Trap with an assertion when a `match` on an enum reads a discriminant
+    /// that doesn't belong to any of the variants of the enum, rather than
+    /// assuming that the discriminant is always valid. This is useful when
+    /// enums are read from memory that might be corrupt, i.e. after calling
+    /// a foreign function.
+    #[arg(long = "ir-checked-discriminants", default_value_t = false)]
+    pub checked_discriminants: bool,
+
+    /// Retain all `nop`, `live` and `dead` statements in the emitted IR,
+    /// rather than compacting the statements of each block. This is useful
+    /// for debugging optimisation passes.
+    #[arg(long = "ir-retain-markers", default_value_t = false)]
+    pub retain_markers: bool,
+
+    /// Remove all `assume(..)` statements from the emitted IR, so that the
+    /// assumptions that are derived from checked operations are not
+    /// exploited by the code generation backend.
+    #[arg(long = "ir-strip-assumptions", default_value_t = false)]
+    pub strip_assumptions: bool,
+
+    /// Validate the structure of each IR body after it has been lowered, and
+    /// after each optimisation pass that is applied to it. This is useful
+    /// for catching malformed IR before it reaches the code generation stage.
+    #[arg(long = "validate-ir", default_value_t = false)]
+    pub validate: bool,
+
+    /// How the layouts of types that are requested with `#layout_of` should
+    /// be printed, either being "pretty", "json", "table", "diagram" or "llvm"
+    /// mode.
+    #[arg(long = "layout-dump-mode", default_value_t = LayoutDumpMode::Pretty)]
+    pub layout_dump_mode: LayoutDumpMode,
+
+    /// The name of a type whose layout should be printed after lowering, as
+    /// if the type was annotated with `#layout_of`.
+    #[arg(long = "explain-layout")]
+    pub explain_layout: Option<String>,
+
+    /// How the call graph should be printed when the IR is dumped in the
+    /// "call-graph" mode, either being "dot" or "json" mode.
+    #[arg(long = "call-graph-mode", default_value_t = CallGraphDumpMode::Dot)]
+    pub call_graph_mode: CallGraphDumpMode,
+
+    /// The name of a function that the call graph should be restricted to,
+    /// only the functions that are reachable from it are printed. Otherwise,
+    /// the functions that are reachable from the entry point are marked. This
+    /// also restricts the stack usage report in the same way.
+    #[arg(long = "call-graph-from")]
+    pub call_graph_from: Option<String>,
+
+    /// Print the section and offset that each global allocation is placed
+    /// at once the bodies have been optimised, allocations that are pooled
+    /// with another allocation share its offset.
+    #[arg(long = "print-globals", default_value_t = false)]
+    pub print_globals: bool,
 }
 
 impl Default for LoweringSettings {
     fn default() -> Self {
-        Self { dump_mode: IrDumpMode::Pretty, checked_operations: true, dump: false }
+        Self {
+            dump_mode: IrDumpMode::Pretty,
+            dump_spans: false,
+            dump_edges: true,
+            dump_array_limit: 16,
+            checked_operations: true,
+            checked_discriminants: false,
+            retain_markers: false,
+            strip_assumptions: false,
+            validate: false,
+            dump: false,
+            layout_dump_mode: LayoutDumpMode::Pretty,
+            explain_layout: None,
+            call_graph_mode: CallGraphDumpMode::Dot,
+            call_graph_from: None,
+            print_globals: false,
+        }
     }
 }
 
 /// Enum representing the different options for dumping the IR. It can either
-/// be emitted in the pretty-printing format, or in the `graphviz` format.
+/// be emitted in the pretty-printing format, in the `graphviz` format (either
+/// as the control flow graph of each body, or as the call graph of the whole
+/// program), as a report of the stack usage of each function, as a JSON
+/// document, or in a compact binary format that can be read back by other
+/// tools.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum IrDumpMode {
     /// Dump the generated IR using a pretty-printed format
     Pretty,
 
+    /// Dump only the blocks of the generated IR and the edges between them,
+    /// without any of the statements
+    Summary,
+
     /// Dump the generated IR using the `graphviz` format
     Graph,
+
+    /// Dump the call graph of the generated IR, either using the `graphviz`
+    /// format or as a JSON document
+    CallGraph,
+
+    /// Dump an estimate of the stack usage of each function, computed from
+    /// the call graph and the layouts of the locals of each body, along with
+    /// any recursive cycles within the call graph
+    StackUsage,
+
+    /// Dump the generated IR as a structured JSON document
+    Json,
+
+    /// Dump the generated IR in the compact binary format
+    Binary,
 }
 
 impl fmt::Display for IrDumpMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Pretty => write!(f, "pretty"),
+            Self::Summary => write!(f, "summary"),
             Self::Graph => write!(f, "graph"),
+            Self::CallGraph => write!(f, "call-graph"),
+            Self::StackUsage => write!(f, "stack-usage"),
+            Self::Json => write!(f, "json"),
+            Self::Binary => write!(f, "binary"),
+        }
+    }
+}
+
+/// Enum representing the different options for printing the call graph of
+/// the lowered bodies, either in the `graphviz` format, or as a JSON
+/// document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CallGraphDumpMode {
+    /// Dump the call graph using the `graphviz` format
+    Dot,
+
+    /// Dump the call graph as a structured JSON document
+    Json,
+}
+
+impl fmt::Display for CallGraphDumpMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dot => write!(f, "dot"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Enum representing the different options for printing the layout of a
+/// type. It can either be drawn as a diagram of boxes, emitted as a JSON
+/// document, written as a compact table of the fields of the type, drawn as
+/// a diagram of the bytes of the type, or written as the LLVM type that the
+/// type is lowered into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LayoutDumpMode {
+    /// Draw the layout as a diagram of the fields of the type
+    Pretty,
+
+    /// Emit the layout as a structured JSON document
+    Json,
+
+    /// Write the layout as a table of the offset, size and alignment of
+    /// each field
+    Table,
+
+    /// Draw the bytes of the layout, marking the padding, tags and niches
+    /// of the type
+    Diagram,
+
+    /// Write the LLVM struct type that the layout is lowered into, including
+    /// the padding between the fields
+    Llvm,
+}
+
+impl fmt::Display for LayoutDumpMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pretty => write!(f, "pretty"),
+            Self::Json => write!(f, "json"),
+            Self::Table => write!(f, "table"),
+            Self::Diagram => write!(f, "diagram"),
+            Self::Llvm => write!(f, "llvm"),
         }
     }
 }
@@ -445,11 +1018,36 @@ pub struct SemanticSettings {
     /// Whether the compiler should monomorphise the generated TIR.
     #[arg(long = "tir-mono", default_value_t = true)]
     pub mono_tir: bool,
+
+    /// Whether the compiler should dump the scopes that are entered whilst
+    /// typechecking each module, and in which mode.
+    #[arg(long = "tir-dump-scopes")]
+    pub dump_scopes: Option<ScopeDumpMode>,
 }
 
 impl Default for SemanticSettings {
     fn default() -> Self {
-        Self { dump_tir: false, eval_tir: false, mono_tir: true }
+        Self { dump_tir: false, eval_tir: false, mono_tir: true, dump_scopes: None }
+    }
+}
+
+/// Enum representing the different options for dumping the scopes of each
+/// module, either as an indented tree, or in the `graphviz` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScopeDumpMode {
+    /// Dump the scopes as an indented tree.
+    Tree,
+
+    /// Dump the scopes using the `graphviz` format.
+    Graph,
+}
+
+impl fmt::Display for ScopeDumpMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tree => write!(f, "tree"),
+            Self::Graph => write!(f, "graph"),
+        }
     }
 }
 
@@ -472,8 +1070,22 @@ pub struct CodeGenSettings {
     /// be that some functions/expressions are evaluated at compile-time via the
     /// Hash VM which may mean that the code generation backend for that one
     /// might differ from the overall code generation backend.
-    #[arg(long="backend", default_value_t = CodeGenBackend::default())]
-    pub backend: CodeGenBackend,
+    ///
+    /// If no backend is given, the [default](CodeGenBackend::default) backend
+    /// is used, see [`CodeGenSettings::backend`].
+    #[arg(long = "backend")]
+    pub backend: Option<CodeGenBackend>,
+
+    /// The size of the stack of the Hash VM in bytes, which bounds how deeply
+    /// a program that is run by the VM may recurse. If the size is [None],
+    /// the default size of the VM is used.
+    #[arg(long = "stack-size")]
+    pub stack_size: Option<usize>,
+
+    /// Print each instruction that is run by the Hash VM, along with the
+    /// registers of the instruction and the depth of the stack.
+    #[arg(long = "vm-trace", default_value_t = false)]
+    pub trace_vm: bool,
 
     /// An optionally specified path to a file that should be used to
     /// write the executable to. If the path is [`None`], the executable
@@ -481,6 +1093,11 @@ pub struct CodeGenSettings {
     #[arg(long = "output-path")]
     pub output_path: Option<PathBuf>,
 
+    /// Additional directories that the linker searches for libraries, i.e.
+    /// the libraries that foreign items are linked against.
+    #[arg(long = "includes", value_delimiter = ',')]
+    pub library_paths: Vec<PathBuf>,
+
     /// Emit the generated IR to standard output.
     #[arg(long = "bc-dump", default_value_t = false)]
     pub dump_bytecode: bool,
@@ -514,6 +1131,14 @@ fn target_value_parser(s: &str) -> Result<Target, String> {
     Target::search(s).ok_or_else(|| format!("unknown target: {}", s))
 }
 
+impl CodeGenSettings {
+    /// The code generation backend that is used, this is the backend that
+    /// was given with `--backend`, or the default backend otherwise.
+    pub fn backend(&self) -> CodeGenBackend {
+        self.backend.unwrap_or_default()
+    }
+}
+
 impl Default for CodeGenSettings {
     fn default() -> Self {
         Self {
@@ -524,7 +1149,10 @@ impl Default for CodeGenSettings {
                 target: Target::search(HOST_TARGET_TRIPLE).unwrap_or_default(),
             },
             backend: Default::default(),
+            stack_size: Default::default(),
+            trace_vm: Default::default(),
             output_path: Default::default(),
+            library_paths: Default::default(),
             dump_bytecode: Default::default(),
             dump_assembly: Default::default(),
             dump_link_line: Default::default(),
@@ -532,6 +1160,49 @@ impl Default for CodeGenSettings {
     }
 }
 
+/// The artefacts that the compiler can produce, selected with `--emit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EmitKind {
+    /// Print the parsed AST of all the modules.
+    Ast,
+
+    /// Print the TIR of all the modules.
+    Tir,
+
+    /// Print the IR of all the lowered bodies.
+    Ir,
+
+    /// Print the layouts of all the data types that have been lowered.
+    Layout,
+
+    /// Print the bytecode that is generated for the Hash VM.
+    Bytecode,
+
+    /// Emit an object file for each module, without linking them.
+    Obj,
+
+    /// Emit an assembly file for each module.
+    Asm,
+
+    /// Link the object files into an executable.
+    Exe,
+}
+
+impl fmt::Display for EmitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ast => write!(f, "ast"),
+            Self::Tir => write!(f, "tir"),
+            Self::Ir => write!(f, "ir"),
+            Self::Layout => write!(f, "layout"),
+            Self::Bytecode => write!(f, "bytecode"),
+            Self::Obj => write!(f, "obj"),
+            Self::Asm => write!(f, "asm"),
+            Self::Exe => write!(f, "exe"),
+        }
+    }
+}
+
 /// All of the current possible code generation backends that
 /// are available.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
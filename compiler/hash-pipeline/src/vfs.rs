@@ -0,0 +1,186 @@
+//! Hash Compiler virtual file system. All of the files that the compiler
+//! reads, i.e. modules and their imports, are read through a [FileSystem]
+//! which is stored within the [Workspace]. This allows for the contents of a
+//! file to be provided from memory, e.g. the unsaved buffers of an editor,
+//! or for the compiler to run without touching the disk at all.
+//!
+//! All of the paths that are stored by the in-memory file systems are
+//! normalised with [normalise_path], so that the same file can't be stored
+//! under several paths.
+//!
+//! [Workspace]: crate::workspace::Workspace
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use hash_utils::{fxhash::FxHashMap, parking_lot::RwLock, path::normalise_path};
+
+/// The kind of an entry within a [FileSystem].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    File,
+    Directory,
+}
+
+/// A [FileSystem] provides the contents of files and the structure of the
+/// directories that contain them.
+pub trait FileSystem: Send + Sync {
+    /// Read the contents of the file at the given path.
+    fn read(&self, path: &Path) -> io::Result<String>;
+
+    /// Get the kind of the entry at the given path, or [None] if there is no
+    /// such entry.
+    fn kind(&self, path: &Path) -> Option<FileKind>;
+
+    /// Get the canonical form of the given path.
+    fn canonicalise(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Check whether there is an entry at the given path.
+    fn exists(&self, path: &Path) -> bool {
+        self.kind(path).is_some()
+    }
+
+    /// Check whether the entry at the given path is a directory.
+    fn is_dir(&self, path: &Path) -> bool {
+        self.kind(path) == Some(FileKind::Directory)
+    }
+}
+
+/// The [FileSystem] of the operating system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn kind(&self, path: &Path) -> Option<FileKind> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(if metadata.is_dir() { FileKind::Directory } else { FileKind::File })
+    }
+
+    fn canonicalise(&self, path: &Path) -> io::Result<PathBuf> {
+        path.canonicalize()
+    }
+}
+
+/// A collection of files that are stored in memory. A directory exists for
+/// every ancestor of a stored file.
+#[derive(Debug, Default, Clone)]
+struct FileMap {
+    files: FxHashMap<PathBuf, String>,
+}
+
+impl FileMap {
+    fn read(&self, path: &Path) -> Option<String> {
+        self.files.get(&normalise_path(path)).cloned()
+    }
+
+    fn kind(&self, path: &Path) -> Option<FileKind> {
+        let path = normalise_path(path);
+
+        if self.files.contains_key(&path) {
+            Some(FileKind::File)
+        } else if self.files.keys().any(|file| file.starts_with(&path)) {
+            Some(FileKind::Directory)
+        } else {
+            None
+        }
+    }
+}
+
+/// Create the error for a path that doesn't exist.
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("`{}` doesn't exist", path.display()))
+}
+
+/// A [FileSystem] that stores files in memory on top of another [FileSystem].
+/// The files that are stored in memory take precedence over the files of the
+/// underlying [FileSystem], i.e. they "overlay" them.
+pub struct OverlayFileSystem {
+    /// The [FileSystem] that is consulted for any file that isn't stored in
+    /// memory.
+    base: Arc<dyn FileSystem>,
+
+    /// The files that are stored in memory.
+    overlay: RwLock<FileMap>,
+}
+
+impl OverlayFileSystem {
+    /// Create a new [OverlayFileSystem] on top of the given [FileSystem].
+    pub fn new(base: Arc<dyn FileSystem>) -> Self {
+        Self { base, overlay: RwLock::new(FileMap::default()) }
+    }
+
+    /// Store the contents of the file at the given path in memory, replacing
+    /// any contents that were previously stored.
+    pub fn set_file(&self, path: impl AsRef<Path>, contents: impl Into<String>) {
+        self.overlay.write().files.insert(normalise_path(path), contents.into());
+    }
+
+    /// Remove the file at the given path from memory, which makes the file of
+    /// the underlying [FileSystem] visible again.
+    pub fn remove_file(&self, path: impl AsRef<Path>) -> Option<String> {
+        self.overlay.write().files.remove(&normalise_path(path))
+    }
+
+    /// Create a [SnapshotFileSystem] of the files that are currently stored in
+    /// memory.
+    pub fn snapshot(&self) -> SnapshotFileSystem {
+        SnapshotFileSystem { files: self.overlay.read().clone() }
+    }
+}
+
+impl FileSystem for OverlayFileSystem {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        match self.overlay.read().read(path) {
+            Some(contents) => Ok(contents),
+            None => self.base.read(path),
+        }
+    }
+
+    fn kind(&self, path: &Path) -> Option<FileKind> {
+        self.overlay.read().kind(path).or_else(|| self.base.kind(path))
+    }
+
+    fn canonicalise(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.overlay.read().kind(path) {
+            Some(_) => Ok(normalise_path(path)),
+            None => self.base.canonicalise(path),
+        }
+    }
+}
+
+/// A read-only [FileSystem] of files that are stored in memory.
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotFileSystem {
+    files: FileMap,
+}
+
+impl SnapshotFileSystem {
+    /// Create a [SnapshotFileSystem] from the paths and contents of files.
+    pub fn from_files(files: impl IntoIterator<Item = (PathBuf, String)>) -> Self {
+        let files = files.into_iter().map(|(path, contents)| (normalise_path(path), contents));
+        Self { files: FileMap { files: files.collect() } }
+    }
+}
+
+impl FileSystem for SnapshotFileSystem {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        self.files.read(path).ok_or_else(|| not_found(path))
+    }
+
+    fn kind(&self, path: &Path) -> Option<FileKind> {
+        self.files.kind(path)
+    }
+
+    fn canonicalise(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.files.kind(path) {
+            Some(_) => Ok(normalise_path(path)),
+            None => Err(not_found(path)),
+        }
+    }
+}
@@ -5,7 +5,11 @@
 //! given [ModuleEntry]. This can only be known by the [SourceMap] which stores
 //! all of the relevant [SourceId]s and their corresponding sources.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use hash_ast::{
     ast::OwnsAstNode,
@@ -22,7 +26,10 @@ use hash_utils::{
 
 use crate::{
     error::PipelineError,
-    settings::{CompilerSettings, CompilerStageKind},
+    interface::CancellationToken,
+    progress::Progress,
+    settings::{CompilerSettings, CompilerStageKind, EmitKind},
+    vfs::{FileSystem, RealFileSystem},
 };
 
 bitflags! {
@@ -131,6 +138,11 @@ impl StageInfo {
         self.0.get(&source).copied().unwrap_or(SourceStageInfo::empty())
     }
 
+    /// Get all of the sources that have been added.
+    pub fn sources(&self) -> impl Iterator<Item = SourceId> + '_ {
+        self.0.keys().copied()
+    }
+
     /// Set a particular flag for all sources.
     pub fn set_all(&mut self, info: SourceStageInfo) {
         for (_, stage) in self.0.iter_mut() {
@@ -172,9 +184,27 @@ pub struct Workspace {
     /// N.B. To compute the executable path, use [`Workspace::executable_path`].
     pub executable_path: Option<PathBuf>,
 
+    /// The [FileSystem] that the sources of the workspace are read from,
+    /// which is the file system of the operating system unless it is
+    /// replaced, e.g. by one that contains the unsaved buffers of an editor.
+    pub file_system: Arc<dyn FileSystem>,
+
+    /// The [CancellationToken] of the current compilation, which the stages
+    /// check in order to stop working on a compilation that is no longer
+    /// needed.
+    pub cancellation: CancellationToken,
+
+    /// Where the long-running stages report their progress to.
+    pub progress: Progress,
+
     /// Dependency map between sources and modules.
     dependencies: FxHashMap<SourceId, FxHashSet<ModuleId>>,
 
+    /// The modules that have been invalidated since the last time that the
+    /// workspace was lowered, the stages that keep items of a source across
+    /// compilations use this to remove the items of these modules.
+    pub stale_sources: FxHashSet<SourceId>,
+
     /// Stores all of the generated AST for modules and nodes.
     pub node_map: NodeMap,
 
@@ -204,8 +234,12 @@ impl Workspace {
             name,
             output_directory,
             executable_path,
+            file_system: Arc::new(RealFileSystem),
+            cancellation: CancellationToken::new(),
+            progress: Progress::default(),
             node_map: NodeMap::new(),
             dependencies: FxHashMap::default(),
+            stale_sources: FxHashSet::default(),
             code_map: CodeMap::default(),
             source_stage_info: StageInfo::new(),
         })
@@ -253,7 +287,9 @@ impl Workspace {
 
     /// Check whether this [Workspace] will yield an executable.
     pub fn yields_executable(&self, settings: &CompilerSettings) -> bool {
-        settings.stage >= CompilerStageKind::Build && SourceMapUtils::entry_point().is_some()
+        settings.stage >= CompilerStageKind::Build
+            && settings.emits(EmitKind::Exe)
+            && SourceMapUtils::entry_point().is_some()
     }
 
     /// Get the bitcode path for a particular [ModuleId]. This does not
@@ -289,6 +325,8 @@ impl Workspace {
     /// [ModuleEntry]. Returns the created [SourceId] from adding it to the
     /// source map.
     pub fn add_module(&mut self, path: PathBuf, kind: ModuleKind) -> SourceId {
+        // A module that is added again replaces the previous version of it.
+        self.invalidate_module(&path);
         let id = SourceMapUtils::reserve_module(path, kind);
 
         // Add this source to the node map, and to the stage info
@@ -298,6 +336,57 @@ impl Workspace {
         id
     }
 
+    /// Get the paths of all of the modules that have been added to the
+    /// [Workspace], excluding the prelude.
+    pub fn module_paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<_> = self
+            .source_stage_info
+            .sources()
+            .filter(|source| source.is_module() && !source.is_prelude())
+            .map(|source| SourceMapUtils::map(source, |source| source.path().to_path_buf()))
+            .collect();
+
+        // An invalidated module has the same path as the module that replaces it.
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// Invalidate the module at the given path, and all of the modules that
+    /// depend on it. The next time that one of these modules is imported, it
+    /// is parsed and analysed again as a new source.
+    ///
+    /// The invalidated sources are marked as having gone through all of the
+    /// stages, so that none of the stages process them again.
+    pub fn invalidate_module(&mut self, path: &Path) {
+        let Some(source) = SourceMapUtils::id_by_path(path) else {
+            return;
+        };
+
+        let mut queue = vec![source];
+        let mut visited = FxHashSet::default();
+
+        while let Some(source) = queue.pop() {
+            if !visited.insert(source) {
+                continue;
+            }
+
+            let module = ModuleId::from(source);
+            SourceMapUtils::forget_module_path(module);
+            self.source_stage_info.add(source, SourceStageInfo::all());
+            self.stale_sources.insert(source);
+
+            queue.extend(
+                self.dependencies
+                    .iter()
+                    .filter(|(dependent, dependencies)| {
+                        dependent.is_module() && dependencies.contains(&module)
+                    })
+                    .map(|(dependent, _)| *dependent),
+            );
+        }
+    }
+
     /// Add a module dependency specified by a [SourceId] to a specific source
     /// specified by a [SourceId].
     pub fn add_dependency(&mut self, source_id: SourceId, dependency: ModuleId) {
@@ -318,7 +407,7 @@ impl Workspace {
             dump_ast(node.into(), mode, character_set, writer)?;
         }
 
-        for module in self.node_map.iter_modules() {
+        for (_, module) in self.node_map.iter_modules() {
             dump_ast(module.node_ref().into(), mode, character_set, writer)?;
         }
 
@@ -362,3 +451,30 @@ impl CodeMap {
         self.object_map.values()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use hash_source::ModuleKind;
+
+    use super::Workspace;
+    use crate::settings::CompilerSettings;
+
+    #[test]
+    fn test_module_paths() {
+        let mut workspace = Workspace::new(&CompilerSettings::default()).unwrap();
+        let dir = PathBuf::from("workspace");
+
+        // The prelude is the first module that is added, and it is never listed.
+        workspace.add_module(dir.join("prelude.hash"), ModuleKind::Prelude);
+        workspace.add_module(dir.join("main.hash"), ModuleKind::EntryPoint);
+        workspace.add_module(dir.join("lib.hash"), ModuleKind::Normal);
+
+        // A module that is added again replaces the previous version of it, so
+        // its path is only listed once.
+        workspace.add_module(dir.join("lib.hash"), ModuleKind::Normal);
+
+        assert_eq!(workspace.module_paths(), [dir.join("lib.hash"), dir.join("main.hash")]);
+    }
+}
@@ -9,4 +9,5 @@ pub mod report;
 pub mod reporter;
 
 pub use hash_error_codes;
+pub use render::set_render_width;
 pub use unicode_normalization;
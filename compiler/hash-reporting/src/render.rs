@@ -11,6 +11,8 @@
 use std::{
     fmt,
     iter::{once, repeat},
+    mem,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use hash_source::{
@@ -24,6 +26,7 @@ use hash_utils::{
 
 use crate::report::{
     ReportCodeBlock, ReportCodeBlockInfo, ReportElement, ReportKind, ReportNote, ReportNoteKind,
+    ReportSuggestion,
 };
 
 /// Character used to denote the span of the diagnostic for the `line` view.
@@ -39,6 +42,93 @@ const DIAGNOSTIC_CONNECTING_CHAR: &str = "|";
 /// center of the block are skipped.
 const LINE_SKIP_THRESHOLD: usize = 6;
 
+/// The text that replaces the parts of a source line that are elided.
+const ELISION: &str = "...";
+
+/// The smallest width that messages are wrapped to and source lines are elided
+/// to, regardless of the render width.
+const MIN_RENDER_WIDTH: usize = 20;
+
+/// The width that reports are rendered within, `0` denotes that there is no
+/// limit on the width.
+static RENDER_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Set the width, in columns, that reports are rendered within. Labels and
+/// notes are wrapped to fit within the width, and long source lines are
+/// elided around the highlighted span. If no width is specified, then reports
+/// are rendered without any limit on the width.
+pub fn set_render_width(width: Option<usize>) {
+    RENDER_WIDTH.store(width.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Get the width that is available for the contents of a line which begins
+/// with a prefix of `prefix` characters, if there is a render width.
+fn available_width(prefix: usize) -> Option<usize> {
+    match RENDER_WIDTH.load(Ordering::Relaxed) {
+        0 => None,
+        width => Some(width.saturating_sub(prefix).max(MIN_RENDER_WIDTH)),
+    }
+}
+
+/// Wrap each of the lines of the `message` so that they are at most `width`
+/// characters wide. Lines are only broken at whitespace, so words that are
+/// longer than the `width` are kept on their own line.
+fn wrap_lines(message: &str, width: Option<usize>) -> Vec<String> {
+    let Some(width) = width else {
+        return message.lines().map(str::to_string).collect();
+    };
+
+    let mut lines = vec![];
+
+    for line in message.lines() {
+        let mut words = line.split(' ');
+        let mut current = words.next().unwrap_or_default().to_string();
+
+        for word in words {
+            let fits = current.chars().count() + 1 + word.chars().count() <= width;
+
+            if fits || current.trim().is_empty() {
+                current.push(' ');
+            } else {
+                lines.push(mem::take(&mut current));
+            }
+
+            current.push_str(word);
+        }
+
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Elide the start and the end of the `line` so that it is at most `width`
+/// characters wide, keeping the characters between the `start` and `end`
+/// columns visible. Returns the elided line, along with the `start` and `end`
+/// columns within the elided line.
+fn elide_line(line: &str, start: usize, end: usize, width: usize) -> (String, usize, usize) {
+    let chars: Vec<char> = line.chars().collect();
+
+    if chars.len() <= width {
+        return (line.to_string(), start, end);
+    }
+
+    // The span is placed in the middle of the visible part of the line.
+    let span = end.saturating_sub(start);
+    let visible = width.saturating_sub(2 * ELISION.len()).max(span);
+    let left = start.saturating_sub(visible.saturating_sub(span) / 2);
+    let left = left.min(chars.len().saturating_sub(visible));
+    let right = (left + visible).min(chars.len());
+
+    let prefix = if left > 0 { ELISION } else { "" };
+    let suffix = if right < chars.len() { ELISION } else { "" };
+    let elided =
+        prefix.chars().chain(chars[left..right].iter().copied()).chain(suffix.chars()).collect();
+
+    let shift = |column: usize| column - left + prefix.len();
+    (elided, shift(start), shift(end))
+}
+
 /// This function holds inner rules for calculating what the selected top
 /// and bottom buffer sizes should be.
 fn adjust_initial_span_size(span: usize) -> usize {
@@ -200,11 +290,13 @@ impl ReportCodeBlock {
             )
         };
 
-        let line_count = self.code_message.lines().count();
+        // The message is wrapped to fit after the connector and the offset.
+        let lines =
+            wrap_lines(&self.code_message, available_width(longest_indent_width + 3 + offset));
 
         // If the number of lines is zero, then we still want to print
         // the initial prefix.
-        if line_count == 0 {
+        if lines.is_empty() {
             let line: String = once(initial_prefix.as_str())
                 .chain(once(" "))
                 .chain(once(self.code_message.as_str()))
@@ -212,11 +304,11 @@ impl ReportCodeBlock {
             return write_line(&line);
         }
 
-        for (index, line) in self.code_message.lines().enumerate() {
+        for (index, line) in lines.iter().enumerate() {
             let message_line: String = if index == 0 {
-                once(initial_prefix.as_str()).chain(once(" ")).chain(once(line)).collect()
+                once(initial_prefix.as_str()).chain(once(" ")).chain(once(line.as_str())).collect()
             } else {
-                repeat(" ").take(offset).chain(once(line)).collect()
+                repeat(" ").take(offset).chain(once(line.as_str())).collect()
             };
 
             write_line(&message_line)?;
@@ -239,6 +331,9 @@ impl ReportCodeBlock {
     /// 2 |
     /// 3 |   // main := () => {
     /// ```
+    ///
+    /// If the lines don't fit within the render width, then the highlighted
+    /// line is elided around the span, and the other lines are cut off.
     fn render_line_view(
         &self,
         f: &mut fmt::Formatter,
@@ -251,9 +346,19 @@ impl ReportCodeBlock {
 
         let (start_row, end_row) = span.rows();
         let (start_column, end_column) = span.columns();
+        let width = available_width(longest_indent_width + 5);
 
         // Print each selected line with the line number
         for (index, line) in error_view {
+            let (line, start_column, end_column) = match width {
+                Some(width) if index == start_row => {
+                    elide_line(line, start_column, end_column, width)
+                }
+                Some(width) => (elide_line(line, 0, 0, width).0, start_column, end_column),
+                None => (line.to_string(), start_column, end_column),
+            };
+            let line = line.as_str();
+
             let index_str = format!("{:>longest_indent_width$}", index + 1);
 
             let line_number = if (start_row..=end_row).contains(&index) {
@@ -490,8 +595,9 @@ impl ReportNote {
         //
         // We add the 4 chars for the `: = `
         let label_length = longest_indent_width + 4 + self.label.as_str().len();
+        let lines = wrap_lines(&self.message, available_width(label_length + 1));
 
-        for (index, line) in self.message.lines().enumerate() {
+        for (index, line) in lines.iter().enumerate() {
             // The first line is special because we want to add the
             // note.
             if index == 0 {
@@ -512,6 +618,64 @@ impl ReportNote {
     }
 }
 
+impl ReportSuggestion {
+    /// Compute the line that the suggestion applies to with the replacement
+    /// written in place of the span, along with the index of the line. There
+    /// is no preview if the span or the replacement spans multiple lines.
+    fn preview(&self) -> Option<(usize, String)> {
+        SourceMapUtils::map(self.span.id, |source| {
+            let RowColRange { start, end } = source.row_cols(self.span.range);
+
+            if start.row != end.row || self.replacement.contains('\n') {
+                return None;
+            }
+
+            let contents = source.contents().0;
+            let before = contents.get(..self.span.range.start())?;
+            let after = contents.get(self.span.range.end() + 1..)?;
+
+            let before = before.rsplit('\n').next().unwrap_or_default();
+            let after = after.split('\n').next().unwrap_or_default();
+            Some((start.row, format!("{before}{}{after}", self.replacement)))
+        })
+    }
+
+    /// Get the width of the line number of the preview of the suggestion.
+    pub(crate) fn indent_width(&self) -> usize {
+        self.preview().map_or(0, |(row, _)| (row + 1).to_string().len())
+    }
+
+    /// Render the [ReportSuggestion] as a `help` note, which is followed by
+    /// the line that the suggestion applies to with the replacement applied,
+    /// i.e.
+    ///
+    /// ```text
+    ///   = help: replace with `:=`
+    ///   |
+    /// 1 |   a := "2";
+    /// ```
+    pub(crate) fn render(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        longest_indent_width: usize,
+    ) -> fmt::Result {
+        let message = format!("replace with `{}`", self.replacement);
+        ReportNote::new(ReportNoteKind::Help, message).render(f, longest_indent_width)?;
+
+        if let Some((row, line)) = self.preview() {
+            writeln!(f, "{} {}", " ".repeat(longest_indent_width), highlight(Colour::Blue, "|"))?;
+            writeln!(
+                f,
+                "{:>longest_indent_width$} {}   {line}",
+                row + 1,
+                highlight(Colour::Blue, "|")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 impl ReportElement {
     pub(crate) fn render(
         &self,
@@ -524,6 +688,47 @@ impl ReportElement {
                 code_block.render(f, longest_indent_width, report_kind)
             }
             ReportElement::Note(note) => note.render(f, longest_indent_width),
+            ReportElement::Suggestion(suggestion) => suggestion.render(f, longest_indent_width),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{elide_line, wrap_lines};
+
+    #[test]
+    fn test_wrap_lines() {
+        let message = "expected a value of type `i32`\n  but got `str`";
+        assert_eq!(
+            wrap_lines(message, None),
+            vec!["expected a value of type `i32`", "  but got `str`"]
+        );
+        assert_eq!(
+            wrap_lines(message, Some(16)),
+            vec!["expected a value", "of type `i32`", "  but got `str`"]
+        );
+
+        // Words that are longer than the width are kept on their own line.
+        assert_eq!(wrap_lines("a `very_long_name`", Some(4)), vec!["a", "`very_long_name`"]);
+    }
+
+    #[test]
+    fn test_elide_line() {
+        let line = format!("{}x := 1;{}", "a".repeat(40), "b".repeat(40));
+
+        // Lines that fit are not elided.
+        assert_eq!(elide_line("x := 1;", 0, 1, 20), ("x := 1;".to_string(), 0, 1));
+
+        // The span is kept visible, and the columns are moved along with it.
+        let (elided, start, end) = elide_line(&line, 40, 41, 20);
+        assert_eq!(elided.chars().count(), 20);
+        assert!(elided.starts_with("...") && elided.ends_with("..."));
+        assert_eq!(&elided[start..end], "x");
+
+        // There is nothing to elide before a span at the start of the line.
+        let (elided, start, _) = elide_line(&line, 0, 1, 20);
+        assert_eq!(start, 0);
+        assert!(elided.starts_with('a') && elided.ends_with("..."));
+    }
+}
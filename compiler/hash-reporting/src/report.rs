@@ -1,7 +1,7 @@
 //! Hash diagnostic report data structures.
 use std::{cell::OnceCell, fmt};
 
-use hash_error_codes::error_codes::HashErrorCode;
+use hash_error_codes::DiagnosticCode;
 use hash_source::{
     location::{RowColRange, Span},
     SourceMapUtils,
@@ -45,7 +45,7 @@ impl ReportKind {
     }
 
     /// Get the string label associated with the [ReportKind].
-    pub(crate) fn message(&self) -> &'static str {
+    pub fn message(&self) -> &'static str {
         match self {
             ReportKind::Error => "error",
             ReportKind::Internal => "internal",
@@ -136,12 +136,34 @@ impl ReportCodeBlock {
     }
 }
 
+/// A suggested fix for a [Report], which replaces the source at the given
+/// [Span] with the `replacement`. Suggestions are machine-applicable, the
+/// `replacement` is exactly the text that should be written instead of the
+/// contents of the span.
+#[derive(Debug, Clone)]
+pub struct ReportSuggestion {
+    /// The span of the source that is replaced.
+    pub span: Span,
+
+    /// The text that replaces the contents of the span.
+    pub replacement: String,
+}
+
+impl ReportSuggestion {
+    /// Create a new [ReportSuggestion] from a [Span] and a replacement.
+    pub fn new(span: Span, replacement: impl ToString) -> Self {
+        Self { span, replacement: replacement.to_string() }
+    }
+}
+
 /// Enumeration representing types of components of a [Report]. A [Report] can
-/// be made of either [ReportCodeBlock]s or [ReportNote]s.
+/// be made of either [ReportCodeBlock]s, [ReportNote]s or
+/// [ReportSuggestion]s.
 #[derive(Debug, Clone)]
 pub enum ReportElement {
     CodeBlock(ReportCodeBlock),
     Note(ReportNote),
+    Suggestion(ReportSuggestion),
 }
 
 /// Create a `help` note with the given message.
@@ -174,8 +196,9 @@ pub struct Report {
     pub kind: ReportKind,
     /// A title for the report.
     pub title: String,
-    /// An optional associated general error code with the report.
-    pub error_code: Option<HashErrorCode>,
+    /// An optional associated code of the report, which is either an error
+    /// or a warning code.
+    pub code: Option<DiagnosticCode>,
     /// A vector of additional [ReportElement]s in order to add additional
     /// context to errors.
     pub contents: Vec<ReportElement>,
@@ -196,6 +219,15 @@ impl Report {
         self.kind == ReportKind::Warning
     }
 
+    /// Get the primary [Span] of the [Report], this is the span of the first
+    /// code block of the report.
+    pub fn primary_span(&self) -> Option<Span> {
+        self.contents.iter().find_map(|element| match element {
+            ReportElement::CodeBlock(block) => Some(block.span),
+            _ => None,
+        })
+    }
+
     /// Add a title to the [Report].
     pub fn title(&mut self, title: impl ToString) -> &mut Self {
         self.title = title.to_string();
@@ -208,9 +240,9 @@ impl Report {
         self
     }
 
-    /// Add an associated [HashErrorCode] to the [Report].
-    pub fn code(&mut self, error_code: HashErrorCode) -> &mut Self {
-        self.error_code = Some(error_code);
+    /// Add an associated [DiagnosticCode] to the [Report].
+    pub fn code(&mut self, code: impl Into<DiagnosticCode>) -> &mut Self {
+        self.code = Some(code.into());
         self
     }
 
@@ -263,6 +295,12 @@ impl Report {
         )))
     }
 
+    /// Add a [ReportSuggestion] to the [Report] that replaces the source at
+    /// the given location with the `replacement`.
+    pub fn add_suggestion(&mut self, location: Span, replacement: impl ToString) -> &mut Self {
+        self.add_element(ReportElement::Suggestion(ReportSuggestion::new(location, replacement)))
+    }
+
     /// Add a [ReportElement] to the report.
     pub fn add_element(&mut self, element: ReportElement) -> &mut Self {
         self.contents.push(element);
@@ -275,7 +313,7 @@ impl Default for Report {
         Self {
             kind: ReportKind::Error,
             title: "Bottom text".to_string(),
-            error_code: None,
+            code: None,
             contents: vec![],
         }
     }
@@ -283,17 +321,14 @@ impl Default for Report {
 
 impl fmt::Display for Report {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Add the optional error code to the general message...
-        let error_code_fmt = match self.error_code {
-            Some(error_code) => highlight(
-                self.kind.as_colour() | Modifier::Bold,
-                format!("[{:0>4}]", error_code.to_num()),
-            ),
+        // Add the optional code to the general message...
+        let code_fmt = match self.code {
+            Some(code) => highlight(self.kind.as_colour() | Modifier::Bold, format!("[{code}]")),
             None => String::new(),
         };
 
         // Add the general note about the report...
-        writeln!(f, "{}{}: {}", self.kind, error_code_fmt, highlight(Modifier::Bold, &self.title),)?;
+        writeln!(f, "{}{}: {}", self.kind, code_fmt, highlight(Modifier::Bold, &self.title),)?;
 
         let longest_indent_width =
             self.contents.iter().fold(0, |longest_indent_width, element| match element {
@@ -302,6 +337,9 @@ impl fmt::Display for Report {
                         code_block.info(source).indent_width.max(longest_indent_width)
                     })
                 }
+                ReportElement::Suggestion(suggestion) => {
+                    suggestion.indent_width().max(longest_indent_width)
+                }
                 ReportElement::Note(_) => longest_indent_width,
             });
 
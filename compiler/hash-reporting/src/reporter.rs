@@ -3,9 +3,47 @@
 //! Has a fluent API for creating reports in a declarative way.
 use std::fmt;
 
+use hash_error_codes::DiagnosticCode;
+use hash_source::location::Span;
+use hash_utils::fxhash::FxHashSet;
+
 use crate::report::{Report, ReportKind};
 pub type Reports = Vec<Report>;
 
+/// Remove all of the [Report]s that are identical to a previous report, the
+/// order of the remaining reports is preserved. Two reports are identical if
+/// they have the same kind, code and primary span, reports without a code must
+/// also have the same title. Reports without a span are never removed.
+pub fn deduplicate_reports<'r>(reports: impl IntoIterator<Item = &'r Report>) -> Vec<&'r Report> {
+    let mut seen: FxHashSet<(ReportKind, Option<DiagnosticCode>, Option<&str>, Span)> =
+        FxHashSet::default();
+
+    reports
+        .into_iter()
+        .filter(|&report| {
+            let Some(span) = report.primary_span() else { return true };
+            let title = if report.code.is_none() { Some(report.title.as_str()) } else { None };
+            seen.insert((report.kind, report.code, title, span))
+        })
+        .collect()
+}
+
+/// Only keep the first `limit` errors within the [Report]s, all of the other
+/// reports are kept. Returns the number of errors that were removed.
+pub fn limit_errors(reports: &mut Vec<&Report>, limit: usize) -> usize {
+    let mut errors = 0;
+
+    reports.retain(|report| {
+        if report.is_error() {
+            errors += 1;
+        }
+
+        !report.is_error() || errors <= limit
+    });
+
+    errors.saturating_sub(limit)
+}
+
 /// Facilitates the creation of lists of [Report]s in a declarative way.
 #[derive(Debug, Default)]
 pub struct Reporter {
@@ -21,8 +21,8 @@ use hash_utils::{derive_more::Constructor, index_vec::IndexVec};
 use crate::{
     repr_stores,
     ty::{Adt, AdtRepresentation, Mutability, RefKind, ReprTy, ReprTyId, VariantIdx},
-    CommonLayouts, FieldLayout, Layout, LayoutId, LayoutShape, LayoutStorage, LayoutStore,
-    PointeeInfo, PointerKind, TyInfo, Variants,
+    CommonLayouts, DiscriminantReadInfo, FieldLayout, Layout, LayoutId, LayoutShape, LayoutStorage,
+    LayoutStore, PointeeInfo, PointerKind, TyInfo, Variants,
 };
 
 /// This describes the collection of errors that can occur
@@ -30,7 +30,7 @@ use crate::{
 /// report that either a type within a layout cannot be
 /// computed because the size is unknown, it is too large, or
 /// it is an invalid type.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LayoutError {
     /// Overflow. The computed layout exceeds the maximum object size
     /// specified on the target platform. For more information, see
@@ -41,6 +41,10 @@ pub enum LayoutError {
     /// for when the type that is given does not have a well
     /// defined layout.
     Unknown(ReprTyId),
+
+    /// The type is specified to be a SIMD vector, but it is not made up
+    /// of one or more fields of the same integer or float type.
+    InvalidSimd(ReprTyId),
 }
 
 /// This is an auxiliary implementation of computing the
@@ -145,6 +149,18 @@ impl<'l> LayoutComputer<'l> {
         Ok(self.layout_of_ty(ty)?.size())
     }
 
+    /// Compute the [DiscriminantReadInfo] of the given type, this describes
+    /// how the discriminant of a value of the type is read, i.e. whether it
+    /// is a known constant, or where the tag is stored and how the
+    /// discriminant is encoded within it.
+    pub fn discriminant_read_info(
+        &self,
+        ty: ReprTyId,
+    ) -> Result<DiscriminantReadInfo, LayoutError> {
+        let layout = self.layout_of_ty(ty)?;
+        Ok(TyInfo::new(ty, layout).discriminant_read_info(*self))
+    }
+
     /// This is the entry point of the layout computation engine. From
     /// here, the [Layout] of a type will be computed all the way recursively
     /// until all of the leaves of the type are also turned into [Layout]s.
@@ -185,7 +201,10 @@ impl<'l> LayoutComputer<'l> {
             ReprTy::Bool => Ok(self.common_layouts().bool),
             ReprTy::Char => Ok(self.common_layouts().char),
             ReprTy::Never => Ok(self.common_layouts().never),
-            ReprTy::Ref(pointee, _, kind @ (RefKind::Raw | RefKind::Normal)) => {
+            // Reference counted pointers have the same representation as normal
+            // references, except that they point to the control block of the
+            // value, see [`ReprTy::rc_box`].
+            ReprTy::Ref(pointee, _, kind) => {
                 let mut data_ptr = scalar_unit(ScalarKind::Pointer(AddressSpace::DATA));
 
                 // If the reference is raw, then we cannot assume that the pointer
@@ -212,11 +231,6 @@ impl<'l> LayoutComputer<'l> {
                 Ok(Layout::create(layout))
             }
 
-            // @@Todo: figure out how to handle rc pointers, probably the same
-            // as normal ones, but the underlying type of the pointer may be
-            // wrapped in some kind of `Rc` struct?
-            ReprTy::Ref(_, _, RefKind::Rc) => Err(LayoutError::Unknown(ty_id)),
-
             // Slices and strings are treated as "unsized" layouts since they
             // are just pointers to the actual data. In terms of `ReprTy`s `str
             // and `[T]` are always behind a pointer.
@@ -294,6 +308,16 @@ impl<'l> LayoutComputer<'l> {
                     None => VariantIdx::new(0),
                 };
 
+                // SIMD vectors are laid out as the elements of a vector, and
+                // are passed around as vectors.
+                if adt.metadata.is_simd() {
+                    return self.compute_layout_of_simd(
+                        ty_id,
+                        adt,
+                        &field_layout_table[VariantIdx::new(0)],
+                    );
+                }
+
                 // If it is a struct, tuple or an enum with a single variant,
                 // then we treat it as a uni-variant.
                 if adt.flags.is_struct()
@@ -380,6 +404,12 @@ impl<'l> LayoutComputer<'l> {
     ///
     /// N.B. If layout optimisations are not applicable, then steps 2-3 are not
     /// applied.
+    ///
+    /// If the [AdtRepresentation] is packed, then every field is treated as
+    /// having an alignment of one byte, and hence no padding is inserted
+    /// between the fields. If the representation specifies a custom
+    /// alignment, then the alignment of the layout is raised to at least the
+    /// specified alignment, and the size is rounded up to a multiple of it.
     fn compute_layout_of_univariant(
         &self,
         index: VariantIdx,
@@ -389,7 +419,9 @@ impl<'l> LayoutComputer<'l> {
     ) -> Option<Layout> {
         let dl = self.data_layout();
 
-        let mut alignment = dl.aggregate_align;
+        let packed = representation.is_packed();
+        let mut alignment =
+            if packed { Alignments::new(Alignment::ONE) } else { dl.aggregate_align };
         let mut inverse_memory_map: Vec<u32> = (0..field_layouts.len() as u32).collect();
 
         // If we can perform a re-ordering of the fields based on
@@ -446,10 +478,14 @@ impl<'l> LayoutComputer<'l> {
                 // Update the offset and alignment of the whole layout based
                 // on if the alignment of the field is larger than the current
                 // alignment of the layout.
-                offset = offset.align_to(layout.alignment.abi);
-                offsets[i as usize] = FieldLayout { offset, size: layout.size };
+                // If the layout is packed, then the fields are placed directly
+                // after one another.
+                if !packed {
+                    offset = offset.align_to(layout.alignment.abi);
+                    alignment = alignment.max(layout.alignment);
+                }
 
-                alignment = alignment.max(layout.alignment);
+                offsets[i as usize] = FieldLayout { offset, size: layout.size };
 
                 // Now increase the offset by the size of the field.
                 offset = offset.checked_add(layout.size, dl)?;
@@ -457,6 +493,12 @@ impl<'l> LayoutComputer<'l> {
             })?;
         }
 
+        // Raise the alignment of the layout if the representation specifies
+        // a custom alignment.
+        if let Some(custom_alignment) = representation.alignment {
+            alignment = alignment.max(Alignments::new(custom_alignment));
+        }
+
         // Now we can compute the size of the layout, we take the last
         // computed "offset" and then align it to the specified ABI
         // alignment.
@@ -1016,6 +1058,87 @@ impl<'l> LayoutComputer<'l> {
         }))
     }
 
+    /// Compute the layout of a struct that is specified to be a SIMD vector via
+    /// `#repr("simd")`. The struct must have at least one field, and all of
+    /// the fields must be of the same integer or float type. The fields are
+    /// laid out in declaration order with no padding, and the alignment of
+    /// the vector is taken from the target's vector alignments if it
+    /// specifies one for the size of the vector.
+    fn compute_layout_of_simd(
+        &self,
+        ty_id: ReprTyId,
+        adt: &Adt,
+        field_layouts: &[LayoutId],
+    ) -> Result<LayoutId, LayoutError> {
+        let dl = self.data_layout();
+        let fields = &adt.variants[VariantIdx::new(0)].fields;
+
+        let Some(first) = fields.first() else {
+            return Err(LayoutError::InvalidSimd(ty_id));
+        };
+
+        if fields.iter().any(|field| field.ty != first.ty) {
+            return Err(LayoutError::InvalidSimd(ty_id));
+        }
+
+        let (element_size, element_alignment, kind) =
+            field_layouts[0].map(|layout| (layout.size, layout.alignment, layout.abi));
+
+        let kind = match kind {
+            AbiRepresentation::Scalar(
+                scalar @ Scalar::Initialised {
+                    kind: ScalarKind::Int { .. } | ScalarKind::Float { .. },
+                    ..
+                },
+            ) => scalar,
+            _ => return Err(LayoutError::InvalidSimd(ty_id)),
+        };
+
+        let elements = fields.len() as u64;
+        let size = element_size.checked_mul(elements, dl).ok_or(LayoutError::Overflow)?;
+
+        // Use the alignment that the target specifies for vectors of this
+        // size, and otherwise fallback to the natural alignment of a vector,
+        // which is the next power of two of its size.
+        let alignment = dl
+            .vector_align
+            .iter()
+            .find(|(vector_size, _)| *vector_size == size)
+            .map(|(_, alignment)| *alignment)
+            .or_else(|| {
+                Alignment::from_bytes(size.bytes().next_power_of_two()).ok().map(Alignments::new)
+            })
+            .ok_or(LayoutError::Overflow)?
+            .max(element_alignment);
+
+        let offsets = (0..elements)
+            .map(|index| FieldLayout { offset: element_size * index, size: element_size });
+
+        Ok(Layout::create(Layout {
+            shape: LayoutShape::Aggregate {
+                fields: offsets.collect(),
+                memory_map: (0..elements as u32).collect(),
+            },
+            variants: Variants::Single { index: VariantIdx::new(0) },
+            abi: AbiRepresentation::Vector { elements, kind },
+            size: size.align_to(alignment.abi),
+            alignment,
+        }))
+    }
+
+    /// Get the type of the control block that a reference counted pointer to
+    /// `pointee` points to, see [`ReprTy::rc_box`]. The control block is only
+    /// created once for each pointee.
+    fn rc_box_of(&self, pointee: ReprTyId) -> ReprTyId {
+        if let Some(rc_box) = self.ctx().rc_box_cache.borrow().get(&pointee) {
+            return *rc_box;
+        }
+
+        let rc_box = ReprTy::create(ReprTy::rc_box(pointee));
+        self.ctx().rc_box_cache.borrow_mut().insert(pointee, rc_box);
+        rc_box
+    }
+
     /// This function computes the layout information of a pointee of a pointer
     /// at a given offset.
     pub fn compute_layout_info_of_pointee_at(
@@ -1028,6 +1151,15 @@ impl<'l> LayoutComputer<'l> {
             return *pointee_info;
         }
 
+        // The control block of a reference counted pointer is looked up here
+        // since the type cannot be created whilst the type store is being read.
+        let rc_box = match info.ty.value() {
+            ReprTy::Ref(pointee, _, RefKind::Rc) if offset == Size::ZERO => {
+                Some(self.rc_box_of(pointee))
+            }
+            _ => None,
+        };
+
         let result = info.ty.map(|ty| match ty {
             ReprTy::Fn { .. } if offset == Size::ZERO => {
                 let (size, alignment) =
@@ -1036,15 +1168,20 @@ impl<'l> LayoutComputer<'l> {
             }
             ReprTy::Ref(pointee, mutability, ref_kind) if offset.bytes() == 0 => {
                 // @@Todo: be more sophisticated with different pointer kinds, and
-                // also deal with Rc specifics here..., and potentially disabling
-                // this optimisation if we are building in debug mode.
+                // potentially disabling this optimisation if we are building in
+                // debug mode.
+                //
+                // The control block of a reference counted pointer is shared
+                // between all of the owners of the value, and the reference
+                // counts are modified through immutable pointers, so nothing
+                // can be assumed about the pointer.
                 let kind = match (mutability, ref_kind) {
-                    (_, RefKind::Raw) => None,
+                    (_, RefKind::Raw | RefKind::Rc) => None,
                     (Mutability::Mutable, _) => Some(PointerKind::Shared),
                     (Mutability::Immutable, _) => Some(PointerKind::Frozen),
                 };
 
-                self.layout_of_ty(*pointee).ok().map(|layout| {
+                self.layout_of_ty(rc_box.unwrap_or(*pointee)).ok().map(|layout| {
                     let (size, alignment) =
                         layout.map(|layout| (layout.size, layout.alignment.abi));
                     PointeeInfo { size, alignment, kind }
@@ -1068,11 +1205,14 @@ impl<'l> LayoutComputer<'l> {
                     // @@Copying: we can't really do anything about this copy...
                     let shape = variant.layout.borrow().shape.clone();
 
-                    for i in 0..shape.count() {
-                        let field_start = shape.offset(i);
-
+                    for (i, field_start) in shape.fields_in_memory_order() {
                         if field_start <= offset {
-                            let field = variant.field(*self, i);
+                            let field = variant.try_field(*self, i).unwrap_or_else(|err| {
+                                panic!(
+                                    "cannot compute pointee of `{}` at {offset:?}: {err}",
+                                    info.ty
+                                )
+                            });
                             let size = field.size();
 
                             result = if ptr_end <= field_start + size {
@@ -7,7 +7,7 @@
 use std::fmt::Debug;
 
 use hash_source::constant::{AllocId, Scalar};
-use hash_storage::store::statics::StoreId;
+use hash_storage::store::statics::{SingleStoreValue, StoreId};
 use hash_target::data_layout::HasDataLayout;
 // Re-export the "primitives" from the hash-target crate so that everyone can use
 // them who depends on `hash-source`
@@ -16,7 +16,7 @@ pub use hash_target::size::Size;
 use hash_utils::{derive_more::Constructor, num_bigint::BigInt};
 use paste::paste;
 
-use crate::ty::{ReprTyId, COMMON_REPR_TYS};
+use crate::ty::{InstanceId, ReprTy, ReprTyId, COMMON_REPR_TYS};
 
 /// A [Const] represents a constant value within the Hash IR. This can
 /// be anything that can be represented as a constant, including ABI scalars,
@@ -63,6 +63,17 @@ impl Const {
         )
     }
 
+    /// Create a function pointer constant which refers to the address of the
+    /// function [InstanceId]. The type of the constant is the function
+    /// pointer type that is derived from the instance signature and ABI.
+    pub fn fn_addr(instance: InstanceId) -> Self {
+        let (params, return_ty, abi) =
+            instance.map(|instance| (instance.params, instance.ret_ty, instance.abi));
+        let ty = ReprTy::create(ReprTy::Fn { params, return_ty, abi });
+
+        Self::new(ty, ConstKind::FnAddr(instance))
+    }
+
     /// Create a new scalar [Const] from a given type and [Scalar] value.
     pub fn scalar(scalar: Scalar, ty: ReprTyId) -> Self {
         Self::new(ty, ConstKind::Scalar(scalar))
@@ -107,17 +118,22 @@ impl Const {
         }
     }
 
-    /// Create a new [Const] from a integer with the given type.
+    /// Create a new [Const] from a integer with the given type. The type may
+    /// be any scalar type that can be switched on, i.e. a `bool`, a `char`, an
+    /// integer, or an opaque scalar such as a pointer, which keeps the raw
+    /// bits of the value.
     pub fn from_scalar_like<C: HasDataLayout>(value: u128, ty: ReprTyId, ctx: &C) -> Self {
-        let kind = if ty == COMMON_REPR_TYS.bool {
+        let pointer_size = ctx.data_layout().pointer_size;
+
+        let scalar = match ty.value() {
             // @@FixMe: we're converting from one to another... seems dumb!
-            ConstKind::Scalar(Scalar::from_bool(value != 0))
-        } else {
-            let size = Into::<IntTy>::into(ty.value()).size(ctx.data_layout().pointer_size);
-            ConstKind::Scalar(Scalar::from_uint(value, size))
+            ReprTy::Bool => Scalar::from_bool(value != 0),
+            ReprTy::Char => Scalar::from_uint(value, Size::from_bytes(4)),
+            ReprTy::Ref(..) | ReprTy::Fn { .. } => Scalar::from_uint(value, pointer_size),
+            ty => Scalar::from_uint(value, IntTy::from(ty).size(pointer_size)),
         };
 
-        Const { ty, kind }
+        Const { ty, kind: ConstKind::Scalar(scalar) }
     }
 
     /// Create a boolean constant.
@@ -145,10 +161,15 @@ impl Const {
         }
     }
 
+    /// Convert the [Const] into a [BigInt]. Big integer constants are stored
+    /// as an allocation of the signed bytes of the value.
     pub fn as_big_int(&self) -> BigInt {
         match self.kind {
-            ConstKind::Scalar(scalar) => scalar.to_big_int(self.ty.is_signed()),
+            ConstKind::Scalar(scalar) => {
+                scalar.to_big_int(self.ty.is_signed() || self.ty == COMMON_REPR_TYS.ibig)
+            }
             ConstKind::Pair { data, .. } => data.to_big_int(),
+            ConstKind::Alloc { alloc, .. } if self.ty.is_big_int() => alloc.to_big_int(),
             _ => panic!("cannot cast to bigint"),
         }
     }
@@ -225,4 +246,8 @@ pub enum ConstKind {
         /// The allocation itself.
         alloc: AllocId,
     },
+
+    /// The address of a function, this is used to represent function
+    /// pointers that are created by referring to a function by name.
+    FnAddr(InstanceId),
 }
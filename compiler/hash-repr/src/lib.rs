@@ -10,14 +10,18 @@ pub mod write;
 
 use std::{
     cell::{Ref, RefCell},
+    fmt,
     num::NonZeroUsize,
     sync::OnceLock,
 };
 
-use compute::LayoutComputer;
+use compute::{LayoutComputer, LayoutError};
 use hash_storage::{
     static_single_store,
-    store::statics::{SingleStoreValue, StoreId},
+    store::{
+        register_store_sizes,
+        statics::{SingleStoreValue, StoreId},
+    },
     stores,
 };
 use hash_target::{
@@ -77,7 +81,10 @@ static STORES: OnceLock<RepresentationStores> = OnceLock::new();
 
 /// Access the global [`LayoutStores`] instance.
 pub(crate) fn repr_stores() -> &'static RepresentationStores {
-    STORES.get_or_init(RepresentationStores::new)
+    STORES.get_or_init(|| {
+        register_store_sizes("repr", || repr_stores().sizes());
+        RepresentationStores::new()
+    })
 }
 
 /// Used to cache the [Layout]s that are created from [ReprTyId]s.
@@ -92,6 +99,10 @@ pub struct LayoutStorage {
     /// Cache for information about pointees with a particular offset.
     pointee_info_cache: RefCell<FxHashMap<(ReprTyId, Size), Option<PointeeInfo>>>,
 
+    /// Cache for the control blocks of reference counted pointers, by the
+    /// type of the value that they point to.
+    rc_box_cache: RefCell<FxHashMap<ReprTyId, ReprTyId>>,
+
     /// A reference to the [TargetDataLayout] of the current
     /// session.
     pub data_layout: TargetDataLayout,
@@ -111,6 +122,7 @@ impl LayoutStorage {
             common_layouts,
             cache: RefCell::new(FxHashMap::default()),
             pointee_info_cache: RefCell::new(FxHashMap::default()),
+            rc_box_cache: RefCell::new(FxHashMap::default()),
             data_layout,
         }
     }
@@ -178,6 +190,46 @@ create_common_layout_table!(
     usize: ReprTy::UInt(UIntTy::USize),
 );
 
+/// An error that occurs when a field or a variant of a [TyInfo] is accessed
+/// with an index that the type does not have. These errors denote a bug in
+/// the compiler, and so they record the type and the index that was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessError {
+    /// The type does not contain any fields, i.e. it is an integer.
+    NoFields { ty: ReprTyId },
+
+    /// The type has `count` fields, but a field at `index` was accessed.
+    FieldOutOfBounds { ty: ReprTyId, index: usize, count: usize },
+
+    /// The type has `count` variants, but the variant `variant` was accessed.
+    VariantOutOfBounds { ty: ReprTyId, variant: VariantIdx, count: usize },
+
+    /// The layout of the accessed field could not be computed.
+    Layout { ty: ReprTyId, error: LayoutError },
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessError::NoFields { ty } => {
+                write!(f, "type `{ty}` does not contain fields")
+            }
+            AccessError::FieldOutOfBounds { ty, index, count } => {
+                write!(f, "field `{index}` is out of bounds for type `{ty}` with {count} fields")
+            }
+            AccessError::VariantOutOfBounds { ty, variant, count } => {
+                write!(
+                    f,
+                    "variant `{variant}` is out of bounds for type `{ty}` with {count} variants"
+                )
+            }
+            AccessError::Layout { ty, error } => {
+                write!(f, "cannot compute the layout of field type `{ty}`: {error:?}")
+            }
+        }
+    }
+}
+
 /// [TyInfo] stores a reference to the type, and a reference to the
 /// layout information about the type.
 #[derive(Debug, Clone, Copy)]
@@ -215,6 +267,40 @@ impl TyInfo {
         self.layout.is_uninhabited()
     }
 
+    /// Check if the ABI is a vector.
+    pub fn is_vector(&self) -> bool {
+        self.layout.is_vector()
+    }
+
+    /// Compute the [DiscriminantReadInfo] of the type, this describes how
+    /// the discriminant of a value of this type is read from the layout.
+    pub fn discriminant_read_info(&self, ctx: LayoutComputer) -> DiscriminantReadInfo {
+        self.with_info(|_, ty, layout| {
+            if layout.abi.is_uninhabited() {
+                return DiscriminantReadInfo::Uninhabited;
+            }
+
+            match layout.variants {
+                Variants::Single { index } => {
+                    let value = ty.discriminant_for_variant(index).map_or(
+                        index.raw() as u128,
+                        |(discriminant_ty, value)| {
+                            discriminant_ty.size(ctx.data_layout().pointer_size).truncate(value)
+                        },
+                    );
+
+                    DiscriminantReadInfo::Constant { value }
+                }
+                Variants::Multiple { tag, field, .. } => DiscriminantReadInfo::Tag {
+                    field,
+                    offset: layout.shape.offset(field),
+                    tag,
+                    encoding: TagEncoding::Direct,
+                },
+            }
+        })
+    }
+
     /// Perform a mapping over the [ReprTy] and [Layout] associated with
     /// this [LayoutWriter].
     fn with_info<F, T>(&self, f: F) -> T
@@ -226,7 +312,20 @@ impl TyInfo {
 
     /// Compute the type of a "field with in a layout" and return the
     /// [LayoutId] associated with the field.
+    ///
+    /// This panics if the field cannot be accessed, use [`TyInfo::try_field`]
+    /// to handle the error instead.
     pub fn field(&self, ctx: LayoutComputer, field_index: usize) -> Self {
+        self.try_field(ctx, field_index).unwrap_or_else(|err| panic!("TyInfo::field: {err}"))
+    }
+
+    /// Compute the type of a "field with in a layout" and return the
+    /// [LayoutId] associated with the field, or an [AccessError] if the
+    /// type does not have a field at the given index.
+    pub fn try_field(&self, ctx: LayoutComputer, field_index: usize) -> Result<Self, AccessError> {
+        let out_of_bounds =
+            |count: usize| AccessError::FieldOutOfBounds { ty: self.ty, index: field_index, count };
+
         let ty = self.with_info(|_, ty, layout| match ty {
             ReprTy::Int(_)
             | ReprTy::UInt(_)
@@ -235,37 +334,50 @@ impl TyInfo {
             | ReprTy::Char
             | ReprTy::Never
             | ReprTy::FnDef { .. }
-            | ReprTy::Fn { .. } => {
-                panic!("TyInfo::field on a type `{}` that does not contain fields", ty)
-            }
+            | ReprTy::Fn { .. } => Err(AccessError::NoFields { ty: self.ty }),
 
             // Handle pointers that might have additional information attached to them, i.e.
             // `str` and `[T]` types.
             ReprTy::Ref(pointee, _, _) => {
                 // We just create a `void*` pointer...
                 if field_index == 0 {
-                    return COMMON_REPR_TYS.void_ptr;
+                    return Ok(COMMON_REPR_TYS.void_ptr);
                 }
 
                 // Deal with loading metadata for the pointer, for now it is either a slice
                 // or a string which only contain the length of the data.
                 pointee.map(|ty| match ty {
-                    ReprTy::Str | ReprTy::Slice(_) => COMMON_REPR_TYS.usize,
-                    ty => {
-                        unreachable!("TyInfo::field cannot read metadata for pointer type `{ty:?}`")
-                    }
+                    ReprTy::Str | ReprTy::Slice(_) if field_index == 1 => Ok(COMMON_REPR_TYS.usize),
+                    ReprTy::Str | ReprTy::Slice(_) => Err(out_of_bounds(2)),
+                    _ => Err(out_of_bounds(1)),
                 })
             }
 
-            ReprTy::Str => COMMON_REPR_TYS.u8,
-            ReprTy::Slice(element) | ReprTy::Array { ty: element, .. } => *element,
+            ReprTy::Str => Ok(COMMON_REPR_TYS.u8),
+            ReprTy::Slice(element) => Ok(*element),
+            ReprTy::Array { ty: element, length } if field_index < *length => Ok(*element),
+            ReprTy::Array { length, .. } => Err(out_of_bounds(*length)),
             ReprTy::Adt(id) => match layout.variants {
-                Variants::Single { index } => {
-                    id.map(|adt| adt.variants[index].fields[field_index].ty)
+                Variants::Single { index } => id.map(|adt| {
+                    let variant =
+                        adt.variants.get(index).ok_or(AccessError::VariantOutOfBounds {
+                            ty: self.ty,
+                            variant: index,
+                            count: adt.variants.len(),
+                        })?;
+
+                    variant
+                        .fields
+                        .get(field_index)
+                        .map(|field| field.ty)
+                        .ok_or_else(|| out_of_bounds(variant.fields.len()))
+                }),
+                Variants::Multiple { tag, .. } if field_index < layout.shape.count() => {
+                    Ok(tag.kind().to_repr_ty())
                 }
-                Variants::Multiple { tag, .. } => tag.kind().to_repr_ty(),
+                Variants::Multiple { .. } => Err(out_of_bounds(layout.shape.count())),
             },
-        });
+        })?;
 
         // If the field layout lookup created a new layout in place,
         // then we need to intern that layout here, add a cache entry
@@ -274,15 +386,30 @@ impl TyInfo {
         let id = { ctx.ctx().cache.borrow().get(&ty).copied() };
 
         if let Some(layout) = id {
-            TyInfo { ty, layout }
+            Ok(TyInfo { ty, layout })
         } else {
-            TyInfo { ty, layout: ctx.layout_of_ty(ty).unwrap() }
+            let layout = ctx.layout_of_ty(ty).map_err(|error| AccessError::Layout { ty, error })?;
+            Ok(TyInfo { ty, layout })
         }
     }
 
     /// Fetch the [Layout] for a variant of the currently
     /// given [Layout].
+    ///
+    /// This panics if the variant cannot be accessed, use
+    /// [`TyInfo::try_for_variant`] to handle the error instead.
     pub fn for_variant(&self, ctx: LayoutComputer, variant: VariantIdx) -> Self {
+        self.try_for_variant(ctx, variant)
+            .unwrap_or_else(|err| panic!("TyInfo::for_variant: {err}"))
+    }
+
+    /// Fetch the [Layout] for a variant of the currently given [Layout], or
+    /// an [AccessError] if the type does not have the given variant.
+    pub fn try_for_variant(
+        &self,
+        ctx: LayoutComputer,
+        variant: VariantIdx,
+    ) -> Result<Self, AccessError> {
         // We have to `.value()` since we might be creating a layout whilst holding
         // a reference to a layout.
         let layout = self.layout.value();
@@ -297,14 +424,17 @@ impl TyInfo {
                 self.layout
             }
             Variants::Single { .. } => {
-                let adt = self.ty.borrow().as_adt();
-                let fields = adt.map(|adt| {
-                    if adt.variants.is_empty() {
-                        panic!("layout::for_variant called on a zero-variant enum")
-                    }
-
-                    adt.variant(variant).fields.len()
-                });
+                let fields = self.ty.map(|ty| match ty {
+                    ReprTy::Adt(id) => id.map(|adt| match adt.variants.get(variant) {
+                        Some(variant) => Ok(variant.fields.len()),
+                        None => Err(AccessError::VariantOutOfBounds {
+                            ty: self.ty,
+                            variant,
+                            count: adt.variants.len(),
+                        }),
+                    }),
+                    _ => Err(AccessError::VariantOutOfBounds { ty: self.ty, variant, count: 1 }),
+                })?;
 
                 // Create a new layout with basically a ZST that is
                 // un-inhabited... i.e. `never` or create a union across
@@ -324,11 +454,72 @@ impl TyInfo {
             }
 
             // @@Verify: should we copy the layout of the variant here?
-            Variants::Multiple { ref variants, .. } => variants[variant],
+            Variants::Multiple { ref variants, .. } => match variants.get(variant) {
+                Some(layout) => *layout,
+                None => {
+                    let count = variants.len();
+                    return Err(AccessError::VariantOutOfBounds { ty: self.ty, variant, count });
+                }
+            },
         };
 
-        Self::new(self.ty, variant)
+        Ok(Self::new(self.ty, variant))
     }
+
+    /// Iterate over all of the fields of the type by increasing offsets. Types
+    /// with a [`LayoutShape::Primitive`] shape have no fields.
+    pub fn fields_in_memory_order<'l>(
+        &self,
+        ctx: LayoutComputer<'l>,
+    ) -> impl Iterator<Item = FieldInfo> + 'l {
+        let info = *self;
+        let fields =
+            self.layout.map(|layout| layout.shape.fields_in_memory_order().collect::<Vec<_>>());
+
+        fields.into_iter().map(move |(index, offset)| FieldInfo {
+            index,
+            offset,
+            info: info.field(ctx, index),
+        })
+    }
+
+    /// Iterate over all of the fields of the type that are not zero-sized by
+    /// increasing offsets.
+    pub fn non_zst_fields<'l>(
+        &self,
+        ctx: LayoutComputer<'l>,
+    ) -> impl Iterator<Item = FieldInfo> + 'l {
+        self.fields_in_memory_order(ctx).filter(|field| !field.info.is_zst())
+    }
+
+    /// Iterate over the variants of the type. A type with a single variant
+    /// yields itself, otherwise the [TyInfo] of each variant is yielded.
+    pub fn variants(&self) -> impl Iterator<Item = (VariantIdx, TyInfo)> {
+        let info = *self;
+        let variants = self.layout.map(|layout| match &layout.variants {
+            Variants::Single { index } => vec![(*index, info.layout)],
+            Variants::Multiple { variants, .. } => {
+                variants.iter_enumerated().map(|(index, layout)| (index, *layout)).collect()
+            }
+        });
+
+        variants.into_iter().map(move |(index, layout)| (index, TyInfo::new(info.ty, layout)))
+    }
+}
+
+/// A field of a [TyInfo], which is yielded when iterating over the fields
+/// of a type in the order that they appear in memory.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldInfo {
+    /// The "source order" index of the field, this is the index that is used
+    /// to access the field with [`TyInfo::field`].
+    pub index: usize,
+
+    /// The offset of the field within the parent layout.
+    pub offset: Size,
+
+    /// The type and layout of the field.
+    pub info: TyInfo,
 }
 
 /// Represents the [Layout] of a particular type in Hash. This captures
@@ -550,6 +741,15 @@ impl LayoutShape {
             LayoutShape::Aggregate { .. } => inverse[i],
         })
     }
+
+    /// Iterate over the fields of the [LayoutShape] by increasing offsets,
+    /// yielding the "source order" index and the offset of each field. Unlike
+    /// [`LayoutShape::iter_increasing_offsets`], a [`LayoutShape::Primitive`]
+    /// has no fields.
+    pub fn fields_in_memory_order(&self) -> impl Iterator<Item = (usize, Size)> + '_ {
+        let count = if *self == LayoutShape::Primitive { 0 } else { self.count() };
+        self.iter_increasing_offsets().take(count).map(|index| (index, self.offset(index)))
+    }
 }
 
 /// Represents the layout of a type that has multiple variants. If the
@@ -579,6 +779,52 @@ pub enum Variants {
     },
 }
 
+/// Describes how the discriminant of a value is encoded within the tag
+/// of a [Variants::Multiple] layout.
+///
+/// @@Future: add a `Niche` encoding which stores the discriminant within the
+/// invalid values of a field of one of the variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagEncoding {
+    /// The tag holds the discriminant value of the variant, truncated to
+    /// the size of the tag.
+    Direct,
+}
+
+/// A recipe that describes how the discriminant of a value of a particular
+/// type is read, this is computed from the [Layout] of the type, and hence
+/// consumers don't need to make assumptions about how the discriminant is
+/// stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscriminantReadInfo {
+    /// The type has no valid values, and hence the discriminant can never
+    /// be read.
+    Uninhabited,
+
+    /// The type only has a single variant, so the discriminant is a known
+    /// constant, and nothing needs to be read from the value.
+    Constant {
+        /// The value of the discriminant, truncated to the size of the
+        /// discriminant type.
+        value: u128,
+    },
+
+    /// The discriminant is read from the tag of the value.
+    Tag {
+        /// The field of the layout which stores the tag.
+        field: usize,
+
+        /// The offset of the tag within the value.
+        offset: Size,
+
+        /// The scalar that is used to represent the tag.
+        tag: Scalar,
+
+        /// How the discriminant is encoded within the tag.
+        encoding: TagEncoding,
+    },
+}
+
 // Define a new key to represent a particular layout.
 
 static_single_store!(
@@ -601,6 +847,11 @@ impl LayoutId {
         self.borrow().abi.is_uninhabited()
     }
 
+    /// Check if the layout has a vector ABI.
+    pub fn is_vector(&self) -> bool {
+        self.borrow().abi.is_vector()
+    }
+
     /// Compute the [Size] of a given [LayoutId].
     pub fn size(&self) -> Size {
         self.borrow().size
@@ -621,3 +872,85 @@ impl LayoutId {
 pub trait HasLayout {
     fn layout_computer(&self) -> LayoutComputer;
 }
+
+#[cfg(test)]
+mod tests {
+    use hash_target::data_layout::TargetDataLayout;
+
+    use crate::{
+        compute::LayoutComputer,
+        ty::{ReprTy, VariantIdx, COMMON_REPR_TYS},
+        AccessError, LayoutStorage, TyInfo,
+    };
+
+    #[test]
+    fn test_fields_in_memory_order() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let ty =
+            ReprTy::make_tuple(&[COMMON_REPR_TYS.u8, COMMON_REPR_TYS.unit, COMMON_REPR_TYS.i32]);
+        let info = TyInfo::new(ty, lc.layout_of_ty(ty).unwrap());
+
+        let fields = info.fields_in_memory_order(lc).collect::<Vec<_>>();
+        assert_eq!(fields.len(), 3);
+        assert!(fields.windows(2).all(|pair| pair[0].offset <= pair[1].offset));
+
+        for field in &fields {
+            assert_eq!(field.offset, info.layout.offset_of(field.index));
+            assert_eq!(field.info.ty, info.field(lc, field.index).ty);
+        }
+
+        let mut indices = fields.iter().map(|field| field.index).collect::<Vec<_>>();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        // The unit field is skipped when iterating over non-zero-sized fields.
+        let non_zst = info.non_zst_fields(lc).map(|field| field.index).collect::<Vec<_>>();
+        assert_eq!(non_zst.len(), 2);
+        assert!(!non_zst.contains(&1));
+
+        // Primitive types have no fields.
+        let int = TyInfo::new(COMMON_REPR_TYS.i32, lc.layout_of_ty(COMMON_REPR_TYS.i32).unwrap());
+        assert_eq!(int.fields_in_memory_order(lc).count(), 0);
+    }
+
+    #[test]
+    fn test_variants_of_single_variant_ty() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let ty = ReprTy::make_tuple(&[COMMON_REPR_TYS.u8, COMMON_REPR_TYS.i32]);
+        let info = TyInfo::new(ty, lc.layout_of_ty(ty).unwrap());
+
+        let variants = info.variants().collect::<Vec<_>>();
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].0, VariantIdx::new(0));
+        assert_eq!(variants[0].1.layout, info.layout);
+    }
+
+    #[test]
+    fn test_checked_access() {
+        let lcx = LayoutStorage::new(TargetDataLayout::default());
+        let lc = LayoutComputer::new(&lcx);
+
+        let ty = ReprTy::make_tuple(&[COMMON_REPR_TYS.u8, COMMON_REPR_TYS.i32]);
+        let info = TyInfo::new(ty, lc.layout_of_ty(ty).unwrap());
+
+        assert_eq!(info.try_field(lc, 1).unwrap().ty, COMMON_REPR_TYS.i32);
+        assert_eq!(
+            info.try_field(lc, 2).unwrap_err(),
+            AccessError::FieldOutOfBounds { ty, index: 2, count: 2 }
+        );
+        assert_eq!(
+            info.try_for_variant(lc, VariantIdx::new(1)).unwrap_err(),
+            AccessError::VariantOutOfBounds { ty, variant: VariantIdx::new(1), count: 1 }
+        );
+
+        let int = TyInfo::new(COMMON_REPR_TYS.i32, lc.layout_of_ty(COMMON_REPR_TYS.i32).unwrap());
+        assert_eq!(
+            int.try_field(lc, 0).unwrap_err(),
+            AccessError::NoFields { ty: COMMON_REPR_TYS.i32 }
+        );
+    }
+}
@@ -22,6 +22,7 @@ use hash_storage::{
 };
 use hash_target::{
     abi::{self, Abi, Integer, ScalarKind},
+    alignment::Alignment,
     data_layout::HasDataLayout,
     discriminant::Discriminant,
     primitives::BigIntTy,
@@ -120,6 +121,10 @@ pub struct Instance {
     /// into possibly some different code.
     pub is_intrinsic: bool,
 
+    /// If the function accepts a variable number of arguments after its
+    /// parameters, i.e. a foreign function such as `printf`.
+    pub is_c_variadic: bool,
+
     /// If the function instance originates from a generic function.
     polymoprhpic_origin: bool,
 }
@@ -136,6 +141,7 @@ impl Instance {
         Self {
             name,
             is_intrinsic: false,
+            is_c_variadic: false,
             params,
             source,
             ret_ty,
@@ -159,6 +165,11 @@ impl Instance {
     pub fn is_intrinsic(&self) -> bool {
         self.is_intrinsic
     }
+
+    /// Check if the [Instance] is a C-variadic function.
+    pub fn is_c_variadic(&self) -> bool {
+        self.is_c_variadic
+    }
 }
 
 static_single_store!(
@@ -240,6 +251,10 @@ pub enum ReprTy {
 
         /// The return type of the function.
         return_ty: ReprTyId,
+
+        /// The calling convention of the functions that the pointer can
+        /// refer to.
+        abi: Abi,
     },
 
     /// A function definition, it has an associated instance which denotes
@@ -273,6 +288,47 @@ impl ReprTy {
         ReprTy::create(ReprTy::tuple(tys))
     }
 
+    /// Make the control block that a reference counted pointer to `ty` points
+    /// to. The control block stores the number of strong and weak references
+    /// to the value, followed by the value itself. The fields of the control
+    /// block are never re-ordered so that the runtime can rely on the offsets
+    /// of the counts.
+    pub fn rc_box(ty: ReprTyId) -> ReprTy {
+        let name: Identifier = "RcBox".into();
+        let fields = vec![
+            AdtField { name: "strong".into(), ty: COMMON_REPR_TYS.usize },
+            AdtField { name: "weak".into(), ty: COMMON_REPR_TYS.usize },
+            AdtField { name: "value".into(), ty },
+        ];
+
+        let variants = index_vec![AdtVariant::singleton(name, fields)];
+        let mut adt = Adt::new_with_flags(name, variants, AdtFlags::STRUCT);
+        adt.metadata.add_flags(RepresentationFlags::C_LIKE);
+        adt.substitutions = Some(ReprTyListId::seq([ty]));
+
+        Self::Adt(Adt::create(adt))
+    }
+
+    /// Make the representation of a big integer type. A big integer is
+    /// represented as a pointer to the allocation that stores the digits of
+    /// the integer, and the number of digits that are stored. The sign of an
+    /// `ibig` is stored as the sign of the length.
+    ///
+    /// @@Todo: constants of big integers are stored as the raw bytes of the
+    /// value, they should be converted into the digit representation when
+    /// they are lowered into code.
+    pub fn big_int(ty: BigIntTy, digits: ReprTyId, length: ReprTyId) -> ReprTy {
+        let name: Identifier = ty.to_name().into();
+        let fields = vec![
+            AdtField { name: "digits".into(), ty: digits },
+            AdtField { name: "length".into(), ty: length },
+        ];
+
+        let variants = index_vec![AdtVariant::singleton(name, fields)];
+        let adt = Adt::new_with_flags(name, variants, AdtFlags::STRUCT);
+        Self::Adt(Adt::create(adt))
+    }
+
     /// Create a reference type to the provided [ReprTy].
     pub fn make_ref(ty: ReprTy, mutability: Mutability, kind: RefKind) -> ReprTyId {
         Self::create(Self::Ref(Self::create(ty), mutability, kind))
@@ -458,6 +514,36 @@ impl ReprTy {
             _ => None,
         }
     }
+
+    /// Check whether a value of this type requires some cleanup to be
+    /// performed when it goes out of scope. Currently, this is only the
+    /// case for reference counted references, and any aggregates that
+    /// contain them. The fields of a `union` are never dropped since it
+    /// is not known which field is active.
+    pub fn needs_drop(&self) -> bool {
+        match self {
+            ReprTy::Ref(_, _, RefKind::Rc) => true,
+            ReprTy::Array { ty, length } => *length > 0 && ty.needs_drop(),
+            ReprTy::Adt(id) => id.map(|adt| {
+                !adt.flags.is_union()
+                    && adt
+                        .variants
+                        .iter()
+                        .any(|variant| variant.fields.iter().any(|field| field.ty.needs_drop()))
+            }),
+            ReprTy::Int(_)
+            | ReprTy::UInt(_)
+            | ReprTy::Float(_)
+            | ReprTy::Str
+            | ReprTy::Bool
+            | ReprTy::Char
+            | ReprTy::Never
+            | ReprTy::Ref(_, _, _)
+            | ReprTy::Slice(_)
+            | ReprTy::FnDef { .. }
+            | ReprTy::Fn { .. } => false,
+        }
+    }
 }
 
 impl From<ReprTy> for IntTy {
@@ -520,6 +606,16 @@ impl ReprTyId {
     pub fn is_str(&self) -> bool {
         self.borrow().is_str()
     }
+
+    /// Check whether a value of the type needs to be dropped.
+    pub fn needs_drop(&self) -> bool {
+        self.borrow().needs_drop()
+    }
+
+    /// Check if the type is a big integer, i.e. `ibig` or `ubig`.
+    pub fn is_big_int(&self) -> bool {
+        *self == COMMON_REPR_TYS.ibig || *self == COMMON_REPR_TYS.ubig
+    }
 }
 
 static_sequence_store_indirect!(
@@ -560,6 +656,12 @@ macro_rules! create_common_ty_table {
                     $($name,)*
                 }
             }
+
+            /// Iterate over all of the common types in the table, the order
+            /// of the types is always the same.
+            pub fn iter(&self) -> impl Iterator<Item = ReprTyId> {
+                [$(self.$name,)*].into_iter()
+            }
         }
 
         impl Default for CommonReprTys {
@@ -612,8 +714,9 @@ create_common_ty_table!(
     // ------------------------------------------
     // BigInts
     // ------------------------------------------
-    ubig: ReprTy::Slice(u64),
-    ibig: ReprTy::tuple(&[bool, ubig]),
+    big_int_digits: ReprTy::Ref(u64, Mutability::Immutable, RefKind::Raw),
+    ubig: ReprTy::big_int(BigIntTy::UBig, big_int_digits, usize),
+    ibig: ReprTy::big_int(BigIntTy::IBig, big_int_digits, isize),
 
     // ------------------------------------------
     // Pointer types
@@ -662,9 +765,12 @@ impl fmt::Display for &ReprTy {
             }
             ReprTy::Adt(adt) => write!(f, "{}", adt),
 
-            ReprTy::Fn { params, return_ty, .. } => {
+            ReprTy::Fn { params, return_ty, abi: Abi::Hash } => {
                 write!(f, "({}) -> {}", params, return_ty)
             }
+            ReprTy::Fn { params, return_ty, abi } => {
+                write!(f, "#convention(\"{abi}\") ({}) -> {}", params, return_ty)
+            }
             ReprTy::FnDef { instance } => {
                 write!(f, "{}", instance.borrow().name)
             }
@@ -867,7 +973,9 @@ impl Adt {
     }
 
     /// Compute the discriminant type of this ADT, assuming that this
-    /// is an `enum` or a `union`.
+    /// is an `enum` or a `union`. If the variants of an `enum` have been
+    /// assigned discriminant values, then the type of those values is used
+    /// since it is large enough to represent all of them.
     pub fn discriminant_ty(&self) -> IntTy {
         debug_assert!(self.flags.is_enum() || self.flags.is_union());
 
@@ -875,6 +983,12 @@ impl Adt {
             return ty;
         }
 
+        if self.flags.is_enum()
+            && let Some(variant) = self.variants.first()
+        {
+            return variant.discriminant.ty;
+        }
+
         // Compute the maximum number of bits needed for the discriminant.
         let max = self.variants.len() as u64;
         let bits = max.leading_zeros();
@@ -1005,21 +1119,30 @@ bitflags! {
         /// The ADT is a C-like type, and hence adheres to the C ABI and C
         /// layout rules.
         const C_LIKE = 0b00000001;
+
+        /// The ADT is packed, and hence all of the fields are laid out
+        /// without any padding between them.
+        const PACKED = 0b00000010;
+
+        /// The ADT is a SIMD vector, and hence all of the fields are laid
+        /// out as the elements of a vector.
+        const SIMD = 0b00000100;
     }
 }
 
 /// Options that are regarding the representation of the ADT. This includes
 /// options about alignment, padding, etc.
 ///
-/// @@Future: add user configurable options for the ADT.
-///     - add `alignment` configuration
-///     - add `pack` configuration
-///     - add layout randomisation configuration
+/// @@Future: add layout randomisation configuration.
 #[derive(Clone, Debug, Default)]
 pub struct AdtRepresentation {
     /// Whether to use a specific type for the discriminant.
     pub discriminant: Option<IntTy>,
 
+    /// A minimum alignment that the type should have, this is specified
+    /// with `#[repr("align(n)")]`.
+    pub alignment: Option<Alignment>,
+
     /// Flags that determine the representation of the type. Currently, if
     /// no flags are set the type is treated normally, if the `C_LIKE` flag
     /// is set, then the type is treated as a C-like type, and hence adheres
@@ -1033,16 +1156,35 @@ impl AdtRepresentation {
         self.representation |= flags;
     }
 
+    /// Get the [RepresentationFlags] of the ADT.
+    pub fn flags(&self) -> RepresentationFlags {
+        self.representation
+    }
+
     /// Check if the representation of the ADT is specified to
     /// be in C-style layout.
     pub fn is_c_like(&self) -> bool {
         self.representation.contains(RepresentationFlags::C_LIKE)
     }
 
+    /// Check if the representation of the ADT is specified to
+    /// be packed, i.e. without any padding between fields.
+    pub fn is_packed(&self) -> bool {
+        self.representation.contains(RepresentationFlags::PACKED)
+    }
+
+    /// Check if the representation of the ADT is specified to
+    /// be a SIMD vector.
+    pub fn is_simd(&self) -> bool {
+        self.representation.contains(RepresentationFlags::SIMD)
+    }
+
     /// Check whether the [AdtRepresentation] permits the re-ordering
-    /// of struct fields in order to optimise for memory layout.
+    /// of struct fields in order to optimise for memory layout. The fields
+    /// of a packed ADT are kept in their declared order, since no padding can
+    /// be saved by re-ordering them.
     pub fn inhibits_struct_field_reordering(&self) -> bool {
-        self.is_c_like()
+        self.is_c_like() || self.is_simd() || self.is_packed()
     }
 
     /// Check whether the [AdtRepresentation] (an underlying `union`) permits
@@ -1,5 +1,10 @@
 //! Contains logic for displaying a computed [Layout] in a pretty format
-//! that can be queried by users.
+//! that can be queried by users. The layout can also be written as a JSON
+//! document with the [LayoutJsonWriter], or as a compact table of the fields
+//! with the [LayoutTableWriter], which are easier for tooling and tests to
+//! inspect. The [LayoutDiagramWriter] draws the individual bytes of a layout,
+//! which is used by the `explain-layout` command, and the [LayoutLlvmWriter]
+//! writes the LLVM type that the layout is lowered into.
 //!
 //! @@Improvements:
 //!
@@ -16,14 +21,21 @@
 //!
 //! 2. Add unit tests for some layout printing
 
-use std::{fmt, iter};
+use std::{
+    fmt::{self, Write},
+    iter,
+};
 
 use hash_source::identifier::Identifier;
 use hash_storage::store::statics::StoreId;
 use hash_target::{
-    abi::AbiRepresentation, data_layout::HasDataLayout, primitives::IntTy, size::Size,
+    abi::{AbiRepresentation, AddressSpace, Integer, Scalar, ScalarKind},
+    alignment::Alignment,
+    data_layout::HasDataLayout,
+    primitives::{FloatTy, IntTy},
+    size::Size,
 };
-use hash_utils::{index_vec::index_vec, tree_writing::CharacterSet};
+use hash_utils::{index_vec::index_vec, json::JsonValue, tree_writing::CharacterSet};
 
 use crate::{
     compute::LayoutComputer,
@@ -934,3 +946,888 @@ impl fmt::Display for LayoutWriter<'_> {
         })
     }
 }
+
+/// A field within a [Layout], this is the flattened description of the
+/// fields of a type that is used by the [LayoutJsonWriter], the
+/// [LayoutTableWriter] and the [LayoutDiagramWriter].
+struct FieldRow {
+    /// The name of the field.
+    name: Identifier,
+
+    /// The type of the field.
+    ty: String,
+
+    /// The offset of the field within the layout.
+    offset: Size,
+
+    /// The size of the field.
+    size: Size,
+
+    /// The ABI alignment of the field.
+    align: Option<Alignment>,
+
+    /// Whether the field is a scalar that has invalid values, i.e. a niche.
+    niche: bool,
+}
+
+impl FieldRow {
+    /// Collect all of the fields of the given `variant` of the [Layout] in
+    /// increasing order of their offsets. Only aggregate layouts have
+    /// fields.
+    fn collect(
+        ty: &ReprTy,
+        layout: &Layout,
+        variant: VariantIdx,
+        ctx: LayoutComputer<'_>,
+    ) -> Vec<Self> {
+        let LayoutShape::Aggregate { fields, .. } = &layout.shape else {
+            return vec![];
+        };
+
+        let scalar_field = |index: usize, scalar: Scalar| {
+            let niche = !scalar.is_always_valid(&ctx);
+            (Identifier::num(index), scalar.to_string(), Some(scalar.align(&ctx).abi), niche)
+        };
+
+        let field_tys = match (layout.abi, ty) {
+            (_, ReprTy::Adt(adt)) => adt.map(|adt| {
+                adt.variant(variant)
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let (align, niche) =
+                            ctx.layout_of_ty(field.ty).ok().map_or((None, false), |layout| {
+                                layout.map(|layout| {
+                                    let niche = match layout.abi {
+                                        AbiRepresentation::Scalar(scalar) => {
+                                            !scalar.is_always_valid(&ctx)
+                                        }
+                                        _ => false,
+                                    };
+
+                                    (Some(layout.alignment.abi), niche)
+                                })
+                            });
+
+                        (field.name, field.ty.to_string(), align, niche)
+                    })
+                    .collect::<Vec<_>>()
+            }),
+            (AbiRepresentation::Scalar(scalar), _) => vec![scalar_field(0, scalar)],
+            (AbiRepresentation::Pair(scalar_1, scalar_2), _) => {
+                vec![scalar_field(0, scalar_1), scalar_field(1, scalar_2)]
+            }
+            _ => return vec![],
+        };
+
+        layout
+            .shape
+            .iter_increasing_offsets()
+            .map(|index| {
+                let (name, ty, align, niche) = &field_tys[index];
+                let FieldLayout { offset, size } = fields[index];
+                Self { name: *name, ty: ty.clone(), offset, size, align: *align, niche: *niche }
+            })
+            .collect()
+    }
+
+    /// Convert the [FieldRow] into a [JsonValue].
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", JsonValue::string(self.name)),
+            ("ty", JsonValue::string(&self.ty)),
+            ("offset", JsonValue::from(self.offset.bytes_usize())),
+            ("size", JsonValue::from(self.size.bytes_usize())),
+            ("align", self.align.map_or(JsonValue::Null, |align| json_bytes(align.bytes()))),
+        ])
+    }
+}
+
+/// Create a [JsonValue::Number] from a number of bytes.
+fn json_bytes(bytes: u64) -> JsonValue {
+    JsonValue::from(usize::try_from(bytes).unwrap())
+}
+
+/// Get the name of the [LayoutShape].
+fn shape_name(shape: &LayoutShape) -> &'static str {
+    match shape {
+        LayoutShape::Primitive => "primitive",
+        LayoutShape::Union { .. } => "union",
+        LayoutShape::Array { .. } => "array",
+        LayoutShape::Aggregate { .. } => "aggregate",
+    }
+}
+
+/// Get a readable description of the [AbiRepresentation].
+fn abi_name(abi: AbiRepresentation) -> String {
+    match abi {
+        AbiRepresentation::Uninhabited => "uninhabited".to_string(),
+        AbiRepresentation::Scalar(scalar) => format!("scalar({scalar})"),
+        AbiRepresentation::Pair(scalar_1, scalar_2) => format!("pair({scalar_1}, {scalar_2})"),
+        AbiRepresentation::Vector { elements, kind } => format!("vector({kind}; {elements})"),
+        AbiRepresentation::Aggregate => "aggregate".to_string(),
+    }
+}
+
+/// The [LayoutJsonWriter] writes a [Layout] as a JSON document, this includes
+/// the shape, ABI, size and alignment of the layout, and the offsets of each
+/// of the fields. For types with multiple variants, the tag and the fields
+/// of each variant are written:
+/// ```json
+/// {
+///   "ty": "Option<i32>",
+///   "size": 8,
+///   "align": 4,
+///   "abi": "pair(i32, i32)",
+///   "shape": "aggregate",
+///   "tag": { "ty": "i32", "offset": 0, "size": 4 },
+///   "variants": [ { "name": "None", "discriminant": "0", "fields": [] }, ... ]
+/// }
+/// ```
+pub struct LayoutJsonWriter<'l> {
+    /// The layout and associated [ReprTy] to be written.
+    pub ty_info: TyInfo,
+
+    /// The current context for writing the layout.
+    pub ctx: LayoutComputer<'l>,
+}
+
+impl<'l> LayoutJsonWriter<'l> {
+    /// Create a new [LayoutJsonWriter].
+    pub fn new(ty_info: TyInfo, ctx: LayoutComputer<'l>) -> Self {
+        Self { ty_info, ctx }
+    }
+
+    /// Convert the [Layout] into a [JsonValue].
+    pub fn to_json(&self) -> JsonValue {
+        let ctx = self.ctx;
+
+        self.ty_info.ty.map(|ty| {
+            self.ty_info.layout.map(|layout| {
+                let mut entries = vec![
+                    ("ty", JsonValue::string(self.ty_info.ty)),
+                    ("size", JsonValue::from(layout.size.bytes_usize())),
+                    ("align", json_bytes(layout.alignment.abi.bytes())),
+                    ("preferred_align", json_bytes(layout.alignment.preferred.bytes())),
+                    ("abi", JsonValue::string(abi_name(layout.abi))),
+                    ("shape", JsonValue::string(shape_name(&layout.shape))),
+                ];
+
+                match layout.variants {
+                    Variants::Single { index } => {
+                        let fields = FieldRow::collect(ty, layout, index, ctx);
+                        entries.push((
+                            "fields",
+                            JsonValue::Array(fields.iter().map(FieldRow::to_json).collect()),
+                        ));
+                    }
+                    Variants::Multiple { tag, field, ref variants } => {
+                        entries.push((
+                            "tag",
+                            JsonValue::object([
+                                ("ty", JsonValue::string(tag.kind().int_ty())),
+                                (
+                                    "offset",
+                                    JsonValue::from(layout.shape.offset(field).bytes_usize()),
+                                ),
+                                ("size", JsonValue::from(tag.size(&ctx).bytes_usize())),
+                            ]),
+                        ));
+
+                        let adt = ty.as_adt();
+                        let variants = variants
+                            .iter_enumerated()
+                            .map(|(index, variant_layout)| {
+                                let fields = variant_layout.map(|variant_layout| {
+                                    FieldRow::collect(ty, variant_layout, index, ctx)
+                                });
+                                let (name, discriminant) = adt.map(|adt| {
+                                    let variant = adt.variant(index);
+                                    let ptr_size = ctx.data_layout().pointer_size;
+                                    (variant.name, variant.discriminant.to_string(ptr_size))
+                                });
+
+                                JsonValue::object([
+                                    ("name", JsonValue::string(name)),
+                                    ("discriminant", JsonValue::String(discriminant)),
+                                    (
+                                        "fields",
+                                        JsonValue::Array(
+                                            fields.iter().map(FieldRow::to_json).collect(),
+                                        ),
+                                    ),
+                                ])
+                            })
+                            .collect();
+
+                        entries.push(("variants", JsonValue::Array(variants)));
+                    }
+                }
+
+                JsonValue::object(entries)
+            })
+        })
+    }
+}
+
+impl fmt::Display for LayoutJsonWriter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+/// The [LayoutTableWriter] writes a [Layout] as a compact table of the fields
+/// of the type, with a row for each field in increasing order of offset:
+/// ```notrust
+/// Layout of `Value` (size=12b align=4b):
+/// field | offset | size | align
+/// x     | 0b     | 4b   | 4b
+/// y     | 4b     | 4b   | 4b
+/// z     | 8b     | 2b   | 2b
+/// ```
+/// For types with multiple variants, the tag is written as the first row,
+/// and the fields of each variant are prefixed with the name of the variant.
+pub struct LayoutTableWriter<'l> {
+    /// The layout and associated [ReprTy] to be written.
+    pub ty_info: TyInfo,
+
+    /// The current context for writing the layout.
+    pub ctx: LayoutComputer<'l>,
+}
+
+impl<'l> LayoutTableWriter<'l> {
+    /// Create a new [LayoutTableWriter].
+    pub fn new(ty_info: TyInfo, ctx: LayoutComputer<'l>) -> Self {
+        Self { ty_info, ctx }
+    }
+
+    /// Compute all of the rows of the table, excluding the header.
+    fn rows(&self) -> Vec<[String; 4]> {
+        let ctx = self.ctx;
+        let to_row = |name: String, row: &FieldRow| {
+            let align = row.align.map_or("-".to_string(), |align| align.to_string());
+            [name, row.offset.to_string(), row.size.to_string(), align]
+        };
+
+        self.ty_info.ty.map(|ty| {
+            self.ty_info.layout.map(|layout| match layout.variants {
+                Variants::Single { index } => FieldRow::collect(ty, layout, index, ctx)
+                    .iter()
+                    .map(|row| to_row(row.name.to_string(), row))
+                    .collect(),
+                Variants::Multiple { tag, field, ref variants } => {
+                    let tag_size = tag.size(&ctx);
+                    let tag_row = [
+                        format!("<tag ({})>", tag.kind().int_ty()),
+                        layout.shape.offset(field).to_string(),
+                        tag_size.to_string(),
+                        tag.align(&ctx).abi.to_string(),
+                    ];
+
+                    let adt = ty.as_adt();
+                    let variant_rows = variants.iter_enumerated().flat_map(|(index, layout)| {
+                        let name = adt.map(|adt| adt.variant(index).name);
+
+                        layout
+                            .map(|layout| FieldRow::collect(ty, layout, index, ctx))
+                            .iter()
+                            .map(|row| to_row(format!("{name}.{}", row.name), row))
+                            .collect::<Vec<_>>()
+                    });
+
+                    iter::once(tag_row).chain(variant_rows).collect()
+                }
+            })
+        })
+    }
+}
+
+impl fmt::Display for LayoutTableWriter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.ty_info.layout.map(|layout| {
+            writeln!(
+                f,
+                "Layout of `{}` (size={} align={}):",
+                self.ty_info.ty, layout.size, layout.alignment.abi
+            )
+        })?;
+
+        let header = ["field", "offset", "size", "align"].map(|title| title.to_string());
+        let rows = self.rows();
+
+        // Compute the width of each of the columns, so that all of the
+        // columns are aligned.
+        let mut widths = header.clone().map(|title| title.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        for row in iter::once(&header).chain(rows.iter()) {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell: <width$}"))
+                .collect::<Vec<_>>()
+                .join(" | ");
+
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The number of bytes that are drawn on each line of a diagram that is
+/// written by the [LayoutDiagramWriter].
+const DIAGRAM_BYTES_PER_LINE: u64 = 16;
+
+/// The number of characters that each byte occupies within a diagram.
+const DIAGRAM_BYTE_WIDTH: usize = 4;
+
+/// The character that is used to hatch padding bytes within a diagram.
+const DIAGRAM_PADDING: char = '/';
+
+/// The marker that is appended to the names of fields that have a niche.
+const DIAGRAM_NICHE_MARKER: char = '*';
+
+/// What a [DiagramSegment] of a diagram represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiagramSegmentKind {
+    /// A field of the type.
+    Field,
+
+    /// The tag of an enum.
+    Tag,
+
+    /// Padding bytes between, or after the fields of the type.
+    Padding,
+}
+
+/// A contiguous range of bytes within a [DiagramRow].
+#[derive(Debug, Clone)]
+struct DiagramSegment {
+    /// The offset of the first byte of the segment.
+    start: u64,
+
+    /// The offset after the last byte of the segment.
+    end: u64,
+
+    /// The label that is written within the segment.
+    label: String,
+
+    /// What the segment represents.
+    kind: DiagramSegmentKind,
+}
+
+/// A single row of a diagram, i.e. a variant of an enum, or a field of a
+/// union.
+struct DiagramRow {
+    /// An optional title that is written above the row.
+    title: Option<String>,
+
+    /// The segments of the row, in increasing order of offset. The segments
+    /// cover all of the bytes of the type.
+    segments: Vec<DiagramSegment>,
+}
+
+impl DiagramRow {
+    /// Create a [DiagramRow] from the given fields, filling in any bytes that
+    /// aren't covered by a field with padding.
+    fn new(title: Option<String>, mut fields: Vec<DiagramSegment>, size: u64) -> Self {
+        fields.sort_by_key(|segment| segment.start);
+
+        let mut segments = vec![];
+        let mut offset = 0;
+
+        let padding = |start, end| DiagramSegment {
+            start,
+            end,
+            label: String::new(),
+            kind: DiagramSegmentKind::Padding,
+        };
+
+        for field in fields {
+            if field.start > offset {
+                segments.push(padding(offset, field.start));
+            }
+
+            offset = field.end;
+            segments.push(field);
+        }
+
+        if offset < size {
+            segments.push(padding(offset, size));
+        }
+
+        Self { title, segments }
+    }
+}
+
+/// The [LayoutDiagramWriter] draws the memory of a [Layout] as a diagram of
+/// boxes, where each byte of the type occupies a column of the diagram:
+/// ```notrust
+/// Layout of `Value` (size=12b align=4b):
+///
+/// 0   1   2   3   4   5   6   7   8   9   10  11
+/// +---------------+---+-----------+---------------+
+/// | x             | y*|///////////| z             |
+/// +---------------+---+-----------+---------------+
+///
+/// `/` padding, `*` field has a niche
+/// ```
+///
+/// Padding bytes are hatched, and fields that have invalid values which could
+/// be used to store the tag of an enum (niches) are marked. For enums, each
+/// of the variants is drawn as a separate row, including the tag of the enum.
+/// Large types are split over multiple lines.
+pub struct LayoutDiagramWriter<'l> {
+    /// The layout and associated [ReprTy] to be written.
+    pub ty_info: TyInfo,
+
+    /// The current context for writing the layout.
+    pub ctx: LayoutComputer<'l>,
+
+    /// A config that stores all of the characters that are used to
+    /// draw the boxes of the diagram.
+    pub config: LayoutWriterConfig,
+}
+
+impl<'l> LayoutDiagramWriter<'l> {
+    /// Create a new [LayoutDiagramWriter] that uses "ascii" characters.
+    pub fn new(ty_info: TyInfo, ctx: LayoutComputer<'l>) -> Self {
+        Self { ty_info, ctx, config: LayoutWriterConfig::ascii() }
+    }
+
+    /// Create a new [LayoutDiagramWriter] with a config.
+    pub fn new_with_config(
+        ty_info: TyInfo,
+        ctx: LayoutComputer<'l>,
+        config: LayoutWriterConfig,
+    ) -> Self {
+        Self { ty_info, ctx, config }
+    }
+
+    /// Convert the given [FieldRow]s into [DiagramSegment]s.
+    fn field_segments(rows: &[FieldRow]) -> impl Iterator<Item = DiagramSegment> + '_ {
+        rows.iter().filter(|row| row.size.bytes() > 0).map(|row| {
+            let marker = if row.niche { DIAGRAM_NICHE_MARKER.to_string() } else { String::new() };
+
+            DiagramSegment {
+                start: row.offset.bytes(),
+                end: row.offset.bytes() + row.size.bytes(),
+                label: format!("{}{marker}", row.name),
+                kind: DiagramSegmentKind::Field,
+            }
+        })
+    }
+
+    /// Compute all of the rows of the diagram.
+    fn rows(&self) -> Vec<DiagramRow> {
+        let ctx = self.ctx;
+
+        self.ty_info.ty.map(|ty| {
+            self.ty_info.layout.map(|layout| {
+                let size = layout.size.bytes();
+
+                match layout.variants {
+                    // The fields of a union all overlap, so each field is
+                    // drawn on a separate row.
+                    Variants::Single { .. }
+                        if matches!(layout.shape, LayoutShape::Union { .. }) =>
+                    {
+                        let ReprTy::Adt(adt) = ty else {
+                            return vec![];
+                        };
+
+                        adt.borrow()
+                            .univariant()
+                            .fields
+                            .iter()
+                            .map(|field| {
+                                let field_size = ctx
+                                    .layout_of_ty(field.ty)
+                                    .map_or(0, |layout| layout.size().bytes());
+                                let segment = DiagramSegment {
+                                    start: 0,
+                                    end: field_size,
+                                    label: field.name.to_string(),
+                                    kind: DiagramSegmentKind::Field,
+                                };
+
+                                let segments = if field_size > 0 { vec![segment] } else { vec![] };
+                                DiagramRow::new(None, segments, size)
+                            })
+                            .collect()
+                    }
+                    Variants::Single { index } => {
+                        let rows = FieldRow::collect(ty, layout, index, ctx);
+                        let mut segments = Self::field_segments(&rows).collect::<Vec<_>>();
+
+                        // Primitives and arrays have no fields, so the whole
+                        // type is drawn as a single box.
+                        if rows.is_empty() && size > 0 {
+                            segments.push(DiagramSegment {
+                                start: 0,
+                                end: size,
+                                label: self.ty_info.ty.to_string(),
+                                kind: DiagramSegmentKind::Field,
+                            });
+                        }
+
+                        vec![DiagramRow::new(None, segments, size)]
+                    }
+                    Variants::Multiple { tag, field, ref variants } => {
+                        let tag_start = layout.shape.offset(field).bytes();
+                        let tag_segment = DiagramSegment {
+                            start: tag_start,
+                            end: tag_start + tag.size(&ctx).bytes(),
+                            label: "tag".to_string(),
+                            kind: DiagramSegmentKind::Tag,
+                        };
+
+                        let adt = ty.as_adt();
+                        variants
+                            .iter_enumerated()
+                            .map(|(index, variant_layout)| {
+                                let rows = variant_layout.map(|variant_layout| {
+                                    FieldRow::collect(ty, variant_layout, index, ctx)
+                                });
+                                let title = adt.map(|adt| {
+                                    let variant = adt.variant(index);
+                                    let ptr_size = ctx.data_layout().pointer_size;
+                                    format!(
+                                        "`{}` (discriminant = {}):",
+                                        variant.name,
+                                        variant.discriminant.to_string(ptr_size)
+                                    )
+                                });
+
+                                let segments = iter::once(tag_segment.clone())
+                                    .chain(Self::field_segments(&rows))
+                                    .collect();
+
+                                DiagramRow::new(Some(title), segments, size)
+                            })
+                            .collect()
+                    }
+                }
+            })
+        })
+    }
+
+    /// Write a horizontal edge of the boxes of the segments that are within
+    /// the bytes `start..end`.
+    fn write_edge(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        segments: &[DiagramSegment],
+        (start, end): (u64, u64),
+        top: bool,
+    ) -> fmt::Result {
+        let config = &self.config;
+        let (left, center, right) = if top {
+            (config.top_left, config.center_top, config.top_right)
+        } else {
+            (config.bottom_left, config.center_bottom, config.bottom_right)
+        };
+
+        let mut line = String::new();
+
+        for byte in start..end {
+            if byte == start {
+                line.push(left);
+            } else if segments.iter().any(|segment| segment.start == byte) {
+                line.push(center);
+            } else {
+                line.push(config.horizontal);
+            }
+
+            line.extend(iter::repeat(config.horizontal).take(DIAGRAM_BYTE_WIDTH - 1));
+        }
+
+        line.push(right);
+        writeln!(f, "{line}")
+    }
+
+    /// Write the given [DiagramRow], splitting it over multiple lines if it is
+    /// wider than [DIAGRAM_BYTES_PER_LINE].
+    fn write_row(&self, f: &mut fmt::Formatter<'_>, row: &DiagramRow, size: u64) -> fmt::Result {
+        if let Some(title) = &row.title {
+            writeln!(f, "{title}")?;
+        }
+
+        for start in (0..size).step_by(DIAGRAM_BYTES_PER_LINE as usize) {
+            let end = (start + DIAGRAM_BYTES_PER_LINE).min(size);
+
+            // Clip all of the segments to the bytes that are on this line, the
+            // label of a segment is only written on the line that it begins.
+            let segments = row
+                .segments
+                .iter()
+                .filter(|segment| segment.start < end && segment.end > start)
+                .map(|segment| DiagramSegment {
+                    start: segment.start.max(start),
+                    end: segment.end.min(end),
+                    label: if segment.start >= start {
+                        segment.label.clone()
+                    } else {
+                        String::new()
+                    },
+                    kind: segment.kind,
+                })
+                .collect::<Vec<_>>();
+
+            let ruler = (start..end).fold(String::new(), |mut ruler, byte| {
+                let _ = write!(ruler, "{byte: <DIAGRAM_BYTE_WIDTH$}");
+                ruler
+            });
+            writeln!(f, "{}", ruler.trim_end())?;
+
+            self.write_edge(f, &segments, (start, end), true)?;
+
+            let mut line = String::new();
+            for segment in &segments {
+                let width = (segment.end - segment.start) as usize * DIAGRAM_BYTE_WIDTH - 1;
+                line.push(self.config.vertical);
+
+                let contents = match segment.kind {
+                    DiagramSegmentKind::Padding => DIAGRAM_PADDING.to_string().repeat(width),
+                    DiagramSegmentKind::Field | DiagramSegmentKind::Tag => {
+                        // Labels that don't fit within the segment are truncated.
+                        let label = format!(" {}", segment.label);
+                        format!("{: <width$}", label.chars().take(width).collect::<String>())
+                    }
+                };
+
+                line.push_str(&contents);
+            }
+
+            line.push(self.config.vertical);
+            writeln!(f, "{line}")?;
+
+            self.write_edge(f, &segments, (start, end), false)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for LayoutDiagramWriter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.ty_info.layout.map(|layout| {
+            writeln!(
+                f,
+                "Layout of `{}` (size={} align={}):",
+                self.ty_info.ty, layout.size, layout.alignment.abi
+            )
+            .map(|_| layout.size.bytes())
+        })?;
+
+        if size == 0 {
+            return writeln!(f, "<zero-sized type>");
+        }
+
+        let rows = self.rows();
+
+        for row in &rows {
+            writeln!(f)?;
+            self.write_row(f, row, size)?;
+        }
+
+        // Write a legend for any of the markers that were used.
+        let segments = || rows.iter().flat_map(|row| row.segments.iter());
+        let mut legend = vec![];
+
+        if segments().any(|segment| segment.kind == DiagramSegmentKind::Padding) {
+            legend.push(format!("`{DIAGRAM_PADDING}` padding"));
+        }
+
+        if segments().any(|segment| segment.label.ends_with(DIAGRAM_NICHE_MARKER)) {
+            legend.push(format!("`{DIAGRAM_NICHE_MARKER}` field has a niche"));
+        }
+
+        if !legend.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{}", legend.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The [LayoutLlvmWriter] writes the LLVM type that a [Layout] is lowered
+/// into by the LLVM backend, in the textual format of LLVM IR:
+/// ```notrust
+/// %Value = type { i32, i16, [2 x i8], i64 }
+/// ```
+/// Padding between fields is written as an array of integers, and a struct
+/// whose fields can't all be aligned is written as a packed struct, i.e.
+/// `<{ i8, i32 }>`. For enums with multiple variants, the type of the enum
+/// is followed by the type of each of the variants. This makes it easy to
+/// compare a layout with the types that other compilers emit for the
+/// equivalent C or Rust type.
+pub struct LayoutLlvmWriter<'l> {
+    /// The layout and associated [ReprTy] to be written.
+    pub ty_info: TyInfo,
+
+    /// The current context for writing the layout.
+    pub ctx: LayoutComputer<'l>,
+}
+
+impl<'l> LayoutLlvmWriter<'l> {
+    /// Create a new [LayoutLlvmWriter].
+    pub fn new(ty_info: TyInfo, ctx: LayoutComputer<'l>) -> Self {
+        Self { ty_info, ctx }
+    }
+
+    /// Compute the name of the LLVM struct type of the given [TyInfo], or
+    /// [None] if the type is lowered into an anonymous type. Only ADTs that
+    /// aren't tuples, and that aren't passed as scalars, are named.
+    fn name(info: TyInfo) -> Option<String> {
+        let is_struct = info.layout.map(|layout| {
+            matches!(layout.abi, AbiRepresentation::Aggregate | AbiRepresentation::Uninhabited)
+                && !matches!(layout.shape, LayoutShape::Array { .. })
+        });
+
+        if !is_struct {
+            return None;
+        }
+
+        info.ty.map(|ty| {
+            let ReprTy::Adt(adt) = ty else { return None };
+
+            adt.map(|adt| {
+                if adt.flags.is_tuple() {
+                    return None;
+                }
+
+                // Layouts of a specific variant are named after the variant.
+                let name = info.layout.map(|layout| match layout.variants {
+                    Variants::Single { index } if adt.flags.is_enum() => {
+                        format!("{}::{}", adt.name, adt.variants[index].name)
+                    }
+                    _ => adt.name.to_string(),
+                });
+
+                // Names that aren't plain identifiers have to be quoted.
+                if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    Some(format!("%{name}"))
+                } else {
+                    Some(format!("%\"{name}\""))
+                }
+            })
+        })
+    }
+
+    /// Write the LLVM type of a [Scalar].
+    fn scalar(scalar: Scalar) -> String {
+        match scalar.kind() {
+            ScalarKind::Int { kind, .. } => format!("i{}", kind.size().bits()),
+            ScalarKind::Float { kind: FloatTy::F32 } => "float".to_string(),
+            ScalarKind::Float { kind: FloatTy::F64 } => "double".to_string(),
+            ScalarKind::Pointer(AddressSpace::DATA) => "ptr".to_string(),
+            ScalarKind::Pointer(AddressSpace(space)) => format!("ptr addrspace({space})"),
+        }
+    }
+
+    /// Write the array of integers that fills `size` bytes of padding with
+    /// the given alignment.
+    fn padding(&self, size: Size, alignment: Alignment) -> String {
+        let unit = Integer::approximate_alignment(&self.ctx, alignment);
+        format!("[{} x i{}]", size.bytes() / unit.size().bytes(), unit.size().bits())
+    }
+
+    /// Write the LLVM type of the given [TyInfo] as it appears within another
+    /// type, named struct types are referred to by their name.
+    fn ty(&self, info: TyInfo) -> String {
+        Self::name(info).unwrap_or_else(|| self.body(info))
+    }
+
+    /// Write the LLVM type of the given [TyInfo], writing out the fields of
+    /// named struct types.
+    fn body(&self, info: TyInfo) -> String {
+        info.layout.map(|layout| match layout.abi {
+            AbiRepresentation::Scalar(scalar) => Self::scalar(scalar),
+            AbiRepresentation::Vector { elements, kind } => {
+                format!("<{elements} x {}>", Self::scalar(kind))
+            }
+            AbiRepresentation::Pair(a, b) => {
+                format!("{{ {}, {} }}", Self::scalar(a), Self::scalar(b))
+            }
+            AbiRepresentation::Uninhabited | AbiRepresentation::Aggregate => match layout.shape {
+                LayoutShape::Primitive | LayoutShape::Union { .. } => {
+                    format!("{{ {} }}", self.padding(layout.size, layout.alignment.abi))
+                }
+                LayoutShape::Array { elements, .. } => {
+                    format!("[{elements} x {}]", self.ty(info.field(self.ctx, 0)))
+                }
+                LayoutShape::Aggregate { .. } => self.aggregate(info, layout),
+            },
+        })
+    }
+
+    /// Write the fields of an aggregate [Layout] in increasing order of
+    /// offset, padding them in the same way as the LLVM backend.
+    fn aggregate(&self, info: TyInfo, layout: &Layout) -> String {
+        let mut packed = false;
+        let mut offset = Size::ZERO;
+        let mut previous_alignment = layout.alignment.abi;
+        let mut fields = vec![];
+
+        for index in layout.shape.iter_increasing_offsets() {
+            let target_offset = layout.shape.offset(index);
+            let field = info.field(self.ctx, index);
+            let (field_size, field_alignment) =
+                field.layout.map(|field| (field.size, field.alignment.abi));
+
+            let alignment = layout.alignment.abi.min(field_alignment).restrict_to(target_offset);
+            packed |= alignment < field_alignment;
+
+            if target_offset > offset {
+                let padding_alignment = previous_alignment.min(alignment);
+                fields.push(self.padding(target_offset - offset, padding_alignment));
+            }
+
+            fields.push(self.ty(field));
+            offset = target_offset + field_size;
+            previous_alignment = alignment;
+        }
+
+        if layout.shape.count() > 0 && layout.size > offset {
+            fields.push(self.padding(layout.size - offset, previous_alignment));
+        }
+
+        match (fields.is_empty(), packed) {
+            (true, _) => "{}".to_string(),
+            (false, false) => format!("{{ {} }}", fields.join(", ")),
+            (false, true) => format!("<{{ {} }}>", fields.join(", ")),
+        }
+    }
+
+    /// Write a single type definition, types that don't lower to a named
+    /// struct are written as a comment.
+    fn write_definition(&self, f: &mut fmt::Formatter<'_>, info: TyInfo) -> fmt::Result {
+        match Self::name(info) {
+            Some(name) => writeln!(f, "{name} = type {}", self.body(info)),
+            None => writeln!(f, "; `{}` = {}", info.ty, self.body(info)),
+        }
+    }
+}
+
+impl fmt::Display for LayoutLlvmWriter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_definition(f, self.ty_info)?;
+
+        self.ty_info.layout.map(|layout| {
+            if let Variants::Multiple { ref variants, .. } = layout.variants {
+                for &variant in variants.iter() {
+                    self.write_definition(f, TyInfo::new(self.ty_info.ty, variant))?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
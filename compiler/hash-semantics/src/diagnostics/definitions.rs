@@ -1,12 +1,15 @@
 //! Error-related data structures for errors that occur during typechecking.
 use hash_exhaustiveness::diagnostics::{ExhaustivenessError, ExhaustivenessWarning};
-use hash_source::location::Span;
+use hash_source::{identifier::Identifier, location::Span};
 use hash_target::discriminant::Discriminant;
 use hash_tir::tir::{SymbolId, TermId};
-use hash_typecheck::diagnostics::TcError;
+use hash_typecheck::diagnostics::{TcError, TcWarning};
 use hash_utils::thin_vec::ThinVec;
 
-use crate::passes::resolution::{pat_binds::Bind, scoping::ContextKind};
+use crate::passes::resolution::{
+    pat_binds::Bind,
+    scoping::{ContextKind, ImportSuggestion},
+};
 
 pub type SemanticResult<T> = Result<T, SemanticError>;
 
@@ -25,8 +28,16 @@ pub enum SemanticError {
     /// Module patterns are not yet supported.
     ModulePatternsNotSupported { location: Span },
 
-    /// Some specified symbol was not found.
-    SymbolNotFound { symbol: SymbolId, location: Span, looking_in: ContextKind },
+    /// Some specified symbol was not found, `suggestions` are similarly
+    /// named symbols that are visible from the location, and `imports` are
+    /// other modules that define the symbol.
+    SymbolNotFound {
+        symbol: SymbolId,
+        location: Span,
+        looking_in: ContextKind,
+        suggestions: Vec<Identifier>,
+        imports: Vec<ImportSuggestion>,
+    },
 
     /// Cannot use a module in a value position.
     CannotUseModuleInValuePosition { location: Span },
@@ -189,6 +200,9 @@ pub enum SemanticWarning {
     /// A warning that comes from exhaustive pattern checking and
     /// analysis.
     ExhaustivenessWarning { warning: ExhaustivenessWarning },
+
+    /// A warning that comes from typechecking.
+    TypeWarning { warning: TcWarning },
 }
 
 impl From<ExhaustivenessWarning> for SemanticWarning {
@@ -196,3 +210,9 @@ impl From<ExhaustivenessWarning> for SemanticWarning {
         Self::ExhaustivenessWarning { warning }
     }
 }
+
+impl From<TcWarning> for SemanticWarning {
+    fn from(warning: TcWarning) -> Self {
+        Self::TypeWarning { warning }
+    }
+}
@@ -4,9 +4,13 @@ use hash_reporting::{
 };
 use hash_tir::tir::HasAstNodeId;
 use hash_typecheck::diagnostics::TcReporter;
+use hash_utils::itertools::Itertools;
 
 use super::definitions::{SemanticError, SemanticWarning};
-use crate::{env::SemanticEnv, passes::resolution::scoping::ContextKind};
+use crate::{
+    env::SemanticEnv,
+    passes::resolution::scoping::{ContextKind, ImportSuggestion},
+};
 
 /// Builds [`Reports`] from semantic errors and warnings.
 pub struct SemanticReporter;
@@ -28,6 +32,9 @@ impl SemanticReporter {
             SemanticWarning::ExhaustivenessWarning { warning } => {
                 warning.add_to_reports(reporter);
             }
+            SemanticWarning::TypeWarning { warning } => {
+                TcReporter::add_warning_to_reporter(warning, reporter)
+            }
             SemanticWarning::Compound { warnings } => {
                 for warning in warnings {
                     Self::add_warning_to_reporter(warning, reporter);
@@ -61,7 +68,13 @@ impl SemanticReporter {
                     Self::add_error_to_reporter(env, error, reporter);
                 }
             }
-            SemanticError::SymbolNotFound { symbol, location, looking_in } => {
+            SemanticError::SymbolNotFound {
+                symbol,
+                location,
+                looking_in,
+                suggestions,
+                imports,
+            } => {
                 let def_name = format!("{}", looking_in);
                 let search_name = *symbol;
                 let noun = match looking_in {
@@ -84,6 +97,27 @@ impl SemanticReporter {
                         ));
                     }
                 }
+
+                if let Some((last, rest)) = suggestions.split_last() {
+                    let names = if rest.is_empty() {
+                        format!("`{last}`")
+                    } else {
+                        format!(
+                            "{} or `{last}`",
+                            rest.iter().map(|name| format!("`{name}`")).join(", ")
+                        )
+                    };
+
+                    error.add_help(format!("a similar {noun} exists: {names}"));
+                }
+
+                for ImportSuggestion { module, path } in imports {
+                    error.add_help(format!(
+                        "`{search_name}` is defined in module `{module}`, consider importing \
+                         it using `{module} := import(\"{path}\")` and then referring to it \
+                         as `{module}::{search_name}`"
+                    ));
+                }
             }
             SemanticError::CannotUseModuleInValuePosition { location } => {
                 let error = reporter
@@ -219,7 +253,10 @@ impl SemanticReporter {
                     .add_info("you need to specify which variant of this data type you want");
             }
             SemanticError::EntryPointNotFound => {
-                let error = reporter.error().title("no entry point specified");
+                let error = reporter
+                    .error()
+                    .code(HashErrorCode::MissingEntryPoint)
+                    .title("no entry point specified");
                 error.add_note(
                     "when building an executable, an entry point must be specified in the source.\nThis can be done by using the `main` keyword, or by using the `#entry_point` directive."
                 );
@@ -1,6 +1,8 @@
 use hash_ast::node_map::HasNodeMap;
 use hash_ir::HasIrCtx;
-use hash_pipeline::settings::HasCompilerSettings;
+use hash_pipeline::{
+    interface::CancellationToken, progress::Progress, settings::HasCompilerSettings,
+};
 use hash_reporting::diagnostic::{Diagnostics, HasDiagnostics};
 use hash_repr::HasLayout;
 use hash_source::entry_point::EntryPointState;
@@ -32,6 +34,12 @@ pub trait SemanticEnv:
     fn storage(&self) -> &SemanticStorage;
     fn storage_mut(&mut self) -> &mut SemanticStorage;
 
+    /// The [CancellationToken] of the current compilation.
+    fn cancellation(&self) -> &CancellationToken;
+
+    /// Where the progress of the analysis is reported to.
+    fn progress(&self) -> &Progress;
+
     fn prelude_mod(&self) -> &OnceCell<ModDefId> {
         &self.storage().distinguished_items.prelude_mod
     }
@@ -5,6 +5,8 @@
 
 #![feature(decl_macro, slice_pattern, let_chains, if_let_guard, cell_update, try_blocks)]
 
+use std::io::Write;
+
 use diagnostics::{
     definitions::{SemanticError, SemanticWarning},
     reporting::SemanticReporter,
@@ -13,7 +15,8 @@ use env::{HasSemanticDiagnostics, SemanticEnv};
 use hash_ast::node_map::{HasNodeMap, NodeMap};
 use hash_ir::{HasIrCtx, IrCtx};
 use hash_pipeline::{
-    interface::{CompilerInterface, CompilerResult, CompilerStage},
+    interface::{CancellationToken, CompilerInterface, CompilerResult, CompilerStage},
+    progress::Progress,
     settings::{CompilerSettings, CompilerStageKind, HasCompilerSettings},
     workspace::Workspace,
 };
@@ -88,6 +91,12 @@ impl<Ctx: SemanticAnalysisCtxQuery> CompilerStage<Ctx> for SemanticAnalysis {
         let analyser = passes::Analyser::new(&env);
         analyser.try_or_add_error(analyser.visit_source(entry_point));
 
+        // The diagnostics of a cancelled compilation, or of a compilation that
+        // exceeded the memory limit are incomplete, since the analysis stopped
+        // part way through.
+        env.cancellation().check()?;
+        env.settings().memory_limit().check(CompilerStageKind::Analysis, None)?;
+
         // Handle any diagnostics that were emitted
         if env.diagnostics().has_diagnostics() {
             Err(env.diagnostics().into_reports(
@@ -103,7 +112,14 @@ impl<Ctx: SemanticAnalysisCtxQuery> CompilerStage<Ctx> for SemanticAnalysis {
         self.metrics.clone().into()
     }
 
-    fn cleanup(&mut self, _entry_point: SourceId, _stage_data: &mut Ctx) {}
+    fn cleanup(&mut self, _entry_point: SourceId, ctx: &mut Ctx) {
+        let mut stdout = ctx.output_stream();
+        let dumps = std::mem::take(ctx.data().semantic_storage.scope_dumps.get_mut());
+
+        for dump in dumps {
+            writeln!(stdout, "{dump}").unwrap();
+        }
+    }
 }
 
 /// The `SemanticEnv` trait can be implemented through access to the
@@ -176,4 +192,12 @@ impl<'env> SemanticEnv for SemanticEnvImpl<'env> {
     fn storage_mut(&mut self) -> &mut SemanticStorage {
         self.ctx.semantic_storage
     }
+
+    fn cancellation(&self) -> &CancellationToken {
+        &self.ctx.workspace.cancellation
+    }
+
+    fn progress(&self) -> &Progress {
+        &self.ctx.workspace.progress
+    }
 }
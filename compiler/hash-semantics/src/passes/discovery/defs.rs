@@ -181,6 +181,13 @@ impl<'env, E: SemanticEnv + 'env> DiscoveryPass<'env, E> {
 
     /// Create or get an existing module definition by `[SourceId]`.
     pub fn create_or_get_module_mod_def(&self, module_id: ModuleId) -> ModDefId {
+        // A module that was discovered by a previous compilation isn't known
+        // to the `ast_info` of this one, i.e. an unchanged module that is
+        // imported from an entry point that has changed.
+        if let Some(existing) = self.storage().source_mod_defs.borrow().get(&module_id.into()) {
+            return *existing;
+        }
+
         let source_node_id = self.node_map().get_module(module_id).node_ref().id();
         match self.ast_info.mod_defs().get_data_by_node(source_node_id) {
             Some(existing) => existing,
@@ -352,11 +359,16 @@ impl<'env, E: SemanticEnv + 'env> DiscoveryPass<'env, E> {
         &self,
         node: AstNodeRef<ast::Declaration>,
     ) -> bool {
-        let def_node_id = match node.value.body() {
+        // Declarations without a value are always bindings.
+        let Some(value) = node.value.as_ref() else {
+            return false;
+        };
+
+        let def_node_id = match value.body() {
             // If the declaration is a block, we need to get the
             // right node to look up the members
             ast::Expr::Block(block) => block.data.id(),
-            _ => node.value.id(),
+            _ => value.id(),
         };
 
         // Function definitions are not considered module members in stack
@@ -404,14 +416,15 @@ impl<'env, E: SemanticEnv + 'env> DiscoveryPass<'env, E> {
         // The `def_node_id` is the `AstNodeId` of the actual definition value that
         // this declaration is pointing to. For example, in `Y := mod {...}`, the `mod`
         // node's ID (which is a block) would be `def_node_id`.
-        let def_node_id = match node.value.body() {
+        let value = node.value.as_ref()?;
+        let def_node_id = match value.body() {
             // If the declaration is a block, we need to get the
             // right node to look up the members
             ast::Expr::Block(block) => block.data.id(),
-            _ => node.value.id(),
+            _ => value.id(),
         };
 
-        match node.value.body() {
+        match value.body() {
             // Import
             ast::Expr::Import(import_expr) => {
                 let source_id = import_expr.data.source;
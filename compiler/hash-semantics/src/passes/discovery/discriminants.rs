@@ -173,18 +173,25 @@ impl<'env, E: SemanticEnv> DiscoveryPass<'env, E> {
                     annotation_origin: discr_ty.span(),
                     discriminant: next_discr,
                 });
-            } else if discr_ty.is_signed() {
-                // @@Hack @@TIRConsts: we manually truncate the value of the discriminant to
-                // being a u128 so that we can detect whether it overflows
-                // or not. However, we should remove this when we can use
-                // the `Const` format in the TIR which will automatically perform
-                // the truncation.
-                next_discr.value = discr_ty.size(self.target().ptr_size()).sign_extend(value);
             }
 
+            // @@Hack @@TIRConsts: the value of the discriminant is parsed as a full
+            // `i128`, so we manually truncate it to the size of the discriminant
+            // type. This is the same encoding that is produced when incrementing
+            // the discriminant, which means that explicit and implicit values can
+            // be compared with one another. However, we should remove this when
+            // we can use the `Const` format in the TIR which will automatically
+            // perform the truncation.
+            next_discr.value = discr_ty.size(self.target().ptr_size()).truncate(value);
+
+            // The constant is re-created with the discriminant type, rather than
+            // the type that the attribute argument was parsed with.
+            let constant =
+                Const::from_scalar_like(next_discr.value, (*discr_ty).to_repr_ty(), self.target());
+
             *prev_discr = Some(next_discr);
             discrs.push(Node::at(next_discr, origin));
-            Ok(create_term_from_const(arg.value, origin))
+            Ok(create_term_from_const(constant, origin))
         } else {
             let origin = NodeOrigin::Given(variant.id());
 
@@ -5,8 +5,13 @@ use hash_ast::{
     ast_visitor_default_impl,
     visitor::walk,
 };
+use hash_attrs::{
+    attr::{attr_store, ConventionAttr},
+    builtin::attrs,
+};
 use hash_reporting::macros::panic_on_span;
 use hash_storage::store::statics::SequenceStoreValue;
+use hash_target::abi::Abi;
 use hash_tir::{
     stack::Stack,
     tir::{
@@ -96,7 +101,7 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
                             node.pat.ast_ref(),
                             stack_id,
                             name,
-                            Some(&node.value),
+                            node.value.as_ref(),
                         );
                     }
                 }
@@ -141,6 +146,7 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
         node: ast::AstNodeRef<ast::Module>,
     ) -> Result<Self::ModuleRet, Self::Error> {
         let mod_def_id = self.create_or_get_module_mod_def(self.source.into());
+        self.storage().source_mod_defs.borrow_mut().insert(self.source, mod_def_id);
 
         // Traverse the module
         self.enter_def(node, mod_def_id, || walk::walk_module(self, node))?;
@@ -263,14 +269,20 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
         // Get the function name from the name hint.
         let fn_def_name = self.take_name_hint_or_create_internal_name(node.id());
 
+        // Foreign function declarations don't have a body, and so the body is
+        // attributed to the declaration itself.
+        let body_id = node.fn_body.as_ref().map_or(node.id(), |body| body.id());
+
         // Create a function definition
         let fn_def_id = Node::create_at(
             FnDef {
                 name: fn_def_name,
-                body: Term::hole(NodeOrigin::Given(node.fn_body.id())),
+                body: Term::hole(NodeOrigin::Given(body_id)),
                 ty: FnTy {
                     implicit: false,
-                    is_unsafe: false,
+                    is_unsafe: attr_store().node_has_attr(node.id(), attrs::UNSAFE_FN),
+                    is_c_variadic: node.c_variadic,
+                    abi: ConventionAttr::abi_of_fn(node.id()),
                     params: self
                         .create_hole_params_from_params(Some(&node.params), node.params.id()),
                     pure: false,
@@ -278,7 +290,7 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
                         .return_ty
                         .as_ref()
                         .map(|ty| Ty::hole(NodeOrigin::Given(ty.id())))
-                        .unwrap_or_else(|| Ty::hole(NodeOrigin::InferredFrom(node.fn_body.id()))),
+                        .unwrap_or_else(|| Ty::hole(NodeOrigin::InferredFrom(body_id))),
                 },
             },
             NodeOrigin::Given(node.id()),
@@ -308,6 +320,8 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
                 ty: FnTy {
                     implicit: true,
                     is_unsafe: false,
+                    is_c_variadic: false,
+                    abi: Abi::Hash,
                     params: self
                         .create_hole_params_from_ty_params(Some(&node.params), node.params.id()),
                     pure: true,
@@ -375,6 +389,8 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
             FnTy {
                 implicit: true,
                 is_unsafe: false,
+                is_c_variadic: false,
+                abi: Abi::Hash,
                 params: self
                     .create_hole_params_from_ty_params(Some(&node.params), node.params.id()),
                 pure: true,
@@ -396,6 +412,8 @@ impl<E: SemanticEnv> ast::AstVisitor for DiscoveryPass<'_, E> {
             FnTy {
                 implicit: false,
                 is_unsafe: false,
+                is_c_variadic: false,
+                abi: Abi::Hash,
                 params: self.create_hole_params_from_params(Some(&node.params), node.params.id()),
                 pure: false,
                 return_ty: Ty::hole(NodeOrigin::Given(node.return_ty.id())),
@@ -4,12 +4,14 @@
 //! Typing errors are reported during this pass.
 
 use hash_ast::ast;
-use hash_pipeline::settings::CompilerStageKind;
+use hash_messaging::CompilerMessagingFormat;
+use hash_pipeline::settings::{CompilerStageKind, EmitKind, ScopeDumpMode};
 use hash_source::{ModuleKind, SourceId};
 use hash_storage::store::statics::SequenceStoreValue;
 use hash_tir::{
     context::Context,
     dump::dump_tir,
+    scopes::ScopeTree,
     tir::{Arg, CallTerm, Node, NodeId, Term, TermId},
 };
 use hash_typecheck::{env::TcEnv, options::normalisation::NormalisationMode};
@@ -95,7 +97,7 @@ impl<E: SemanticEnv> AnalysisPass for EvaluationPass<'_, E> {
         let term = self.ast_info.terms().get_data_by_node(node.id()).unwrap();
 
         // Potentially dump the TIR and evaluate it depending on flags.
-        if self.settings().semantic_settings.dump_tir {
+        if self.settings().emits(EmitKind::Tir) {
             dump_tir(term);
         }
 
@@ -104,8 +106,13 @@ impl<E: SemanticEnv> AnalysisPass for EvaluationPass<'_, E> {
         let tc = env.checker(&context);
         tc.normalisation_opts.mode.set(NormalisationMode::Full);
         let result = tc.normalise_node_no_signals(term)?;
+        self.storage().evaluations.borrow_mut().insert(source, result);
 
-        stream_less_writeln!("{}", result);
+        // The output stream only carries messages when messages are written as
+        // JSON, and so the result is reported by the embedder instead.
+        if self.settings().message_format == CompilerMessagingFormat::Normal {
+            stream_less_writeln!("{}", result);
+        }
 
         Ok(())
     }
@@ -122,10 +129,25 @@ impl<E: SemanticEnv> AnalysisPass for EvaluationPass<'_, E> {
         // Potentially dump the TIR and evaluate it depending on flags.
         let settings = self.settings().semantic_settings();
 
-        if settings.dump_tir {
+        if self.settings().emits(EmitKind::Tir) {
             dump_tir(mod_def_id);
         }
 
+        if let Some(mode) = settings.dump_scopes
+            && !(self.settings().prelude_is_quiet && source.is_prelude())
+        {
+            let tree = ScopeTree::new(mod_def_id);
+            let mut output = String::new();
+
+            match mode {
+                ScopeDumpMode::Tree => tree.write_tree(&mut output),
+                ScopeDumpMode::Graph => tree.write_graph(&mut output),
+            }
+            .unwrap();
+
+            self.storage().scope_dumps.borrow_mut().push(output.trim_end().to_string());
+        }
+
         if settings.eval_tir {
             if let Some(term) = main_call_term {
                 let context = Context::new();
@@ -15,6 +15,10 @@ pub mod inference;
 pub mod resolution;
 pub mod tc_env_impl;
 
+/// The number of passes that are run on each source, which is reported as
+/// the progress of the analysis.
+const PASSES: usize = 5;
+
 /// The base semantic analysis visitor, which runs each analysis pass in
 /// order on the AST.
 #[derive(Constructor, Deref)]
@@ -28,22 +32,28 @@ impl<'env, E: SemanticEnv> Analyser<'env, E> {
         // AST info for discovery and resolution passes.
         // @@Todo: refactor this to have each stage return its own AST info.
         let ast_info = AstInfo::new();
+        let progress = self.env.progress();
 
         // Discover all definitions in the source.
         DiscoveryPass::new(self.env, &ast_info, source).pass_source(source)?;
+        progress.report("analysis", 1, PASSES);
 
         // Resolve all symbols in the source and create TIR terms.
         ResolutionPass::new(self.env, &ast_info).pass_source(source)?;
+        progress.report("analysis", 2, PASSES);
 
         // Infer all types in the source.
         //
         // This needs to be run twice, once to infer the headers of the
         // definitions, and once to infer their bodies.
         InferencePass::new(self.env, &ast_info).pass_source(source)?;
+        progress.report("analysis", 3, PASSES);
         InferencePass::new(self.env, &ast_info).pass_source(source)?;
+        progress.report("analysis", 4, PASSES);
 
         // Potentially evaluate terms
         EvaluationPass::new(self.env, &ast_info).pass_source(source)?;
+        progress.report("analysis", 5, PASSES);
 
         Ok(())
     }
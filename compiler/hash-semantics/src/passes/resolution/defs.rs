@@ -149,9 +149,10 @@ impl<'env, E: SemanticEnv + 'env> ResolutionPass<'env, E> {
     fn use_expr_as_mod_def_declaration_and_get_rhs(
         member_expr: ast::AstNodeRef<'_, ast::Expr>,
     ) -> ast::AstNodeRef<'_, ast::Expr> {
-        // By this point, all members should be declarations (caught at pre-TC)
+        // By this point, all members should be declarations with a value
+        // (caught at pre-TC)
         match member_expr.body() {
-            ast::Expr::Declaration(decl) => decl.value.ast_ref(),
+            ast::Expr::Declaration(decl) => decl.value.as_ref().unwrap().ast_ref(),
             ast::Expr::Macro(invocation) => {
                 // Recurse to the inner declaration
                 Self::use_expr_as_mod_def_declaration_and_get_rhs(invocation.subject.ast_ref())
@@ -191,6 +192,13 @@ impl<'env, E: SemanticEnv + 'env> ResolutionPass<'env, E> {
                         }
                     }
                     ModMemberValue::Mod(mod_def_id) => {
+                        // The module definition might be the subject of a directive, i.e.
+                        // an `#[extern("C")] mod { ... }` block.
+                        let mut member_rhs_expr = member_rhs_expr;
+                        while let ast::Expr::Macro(invocation) = member_rhs_expr.body() {
+                            member_rhs_expr = invocation.subject.ast_ref();
+                        }
+
                         // If be a module definition node, recurse into it.
                         match member_rhs_expr.body() {
                             ast::Expr::ModDef(mod_def) => {
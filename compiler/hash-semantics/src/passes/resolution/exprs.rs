@@ -18,14 +18,15 @@ use hash_storage::store::{
 use hash_tir::{
     intrinsics::{
         definitions::{equal_ty, Intrinsic},
-        utils::{bool_term, create_term_from_const},
+        utils::{bool_term, create_term_from_const, create_term_from_usize_lit},
     },
     tir::{
         blocks::{BlockStatement, BlockTerm, Decl},
         commands::AssignTerm,
         AccessTerm, AnnotTerm, Arg, ArgsId, ArrayTerm, CallTerm, DataTy, DerefTerm, IndexTerm, Lit,
         LoopControlTerm, LoopTerm, MatchCase, MatchTerm, Node, NodeId, NodeOrigin, ParamIndex,
-        RefKind, RefTerm, ReturnTerm, Term, TermId, TupleTerm, Ty, TyOfTerm, UnsafeTerm, VarTerm,
+        RefKind, RefTerm, ReturnTerm, Term, TermId, TupleTerm, Ty, TyOfTerm, TypedHole, UnsafeTerm,
+        VarTerm,
     },
 };
 use hash_utils::itertools::Itertools;
@@ -172,6 +173,7 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
             ast::Expr::Continue(continue_statement) => {
                 self.make_term_from_ast_continue_statement(node.with_body(continue_statement))?
             }
+            ast::Expr::Hole(_) => Term::from(TypedHole, NodeOrigin::Given(node.id())),
             ast::Expr::Assign(assign_statement) => {
                 self.make_term_from_ast_assign_expr(node.with_body(assign_statement))?
             }
@@ -194,6 +196,15 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
             ast::Expr::UnaryExpr(unary_expr) => {
                 self.make_term_from_ast_unary_expr(node.with_body(unary_expr))?
             }
+            ast::Expr::ChainedComparison(_) => {
+                panic_on_span!(
+                    node.span(),
+                    "Found non-desugared chained comparison in make_term_from_ast_expr"
+                )
+            }
+            ast::Expr::Range(_) => {
+                panic_on_span!(node.span(), "Found non-desugared range in make_term_from_ast_expr")
+            }
 
             ast::Expr::Import(import_expr) => {
                 let source_id = import_expr.data.source;
@@ -452,25 +463,38 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
         &self,
         node: AstNodeRef<ast::Declaration>,
     ) -> SemanticResult<Node<Decl>> {
+        // The `else` block is resolved before the bindings of the declaration
+        // are registered, since they are not in scope within it.
+        let otherwise = node.otherwise.as_ref().map(|otherwise| {
+            self.try_or_add_error(self.make_term_from_ast_block(otherwise.ast_ref()))
+        });
+
         self.scoping().register_declaration(node);
 
         // Pattern
         let pat =
             self.try_or_add_error(self.make_pat_from_ast_pat_and_check_binds(node.pat.ast_ref()));
 
-        // Initialiser:
-        let value = self.try_or_add_error(self.make_term_from_ast_expr(node.value.ast_ref()));
+        // Initialiser, if there is one:
+        let value = node
+            .value
+            .as_ref()
+            .map(|value| self.try_or_add_error(self.make_term_from_ast_expr(value.ast_ref())));
 
         // Type annotation:
         let ty = match node.ty.as_ref() {
             Some(ty) => self.try_or_add_error(self.make_ty_from_ast_ty(ty.ast_ref())),
-            None => Some(Ty::hole(NodeOrigin::InferredFrom(node.value.id()))),
+            None => {
+                let origin = node.value.as_ref().map_or(node.id(), |value| value.id());
+                Some(Ty::hole(NodeOrigin::InferredFrom(origin)))
+            }
         };
 
-        match (pat, ty, value) {
-            (Some(pat), Some(ty), Some(value)) => {
-                Ok(Node::at(Decl { bind_pat: pat, ty, value }, NodeOrigin::Given(node.id())))
-            }
+        match (pat, ty, value, otherwise) {
+            (Some(pat), Some(ty), None | Some(Some(_)), None | Some(Some(_))) => Ok(Node::at(
+                Decl { bind_pat: pat, ty, value: value.flatten(), otherwise: otherwise.flatten() },
+                NodeOrigin::Given(node.id()),
+            )),
             _ => {
                 // If pat had an error, then we can't make a term, and the
                 // error will have been added already.
@@ -489,6 +513,7 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
             Term::Ref(RefTerm {
                 kind: match node.kind {
                     ast::RefKind::Raw => RefKind::Raw,
+                    ast::RefKind::Rc => RefKind::Rc,
                     ast::RefKind::Normal => RefKind::Local,
                 },
                 mutable: node
@@ -617,8 +642,12 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
         &self,
         node: AstNodeRef<ast::MatchBlock>,
     ) -> SemanticResult<TermId> {
-        // First convert the subject
-        let subject = self.try_or_add_error(self.make_term_from_ast_expr(node.subject.ast_ref()));
+        // First convert the subject, which is the iterator if this is a
+        // de-sugared `for` loop
+        let subject = self.try_or_add_error(match node.origin {
+            ast::MatchOrigin::For => self.make_term_from_ast_for_iterator(node.subject.ast_ref()),
+            _ => self.make_term_from_ast_expr(node.subject.ast_ref()),
+        });
 
         // Convert all the cases and their bodies
         let cases = Node::create_at(
@@ -657,6 +686,49 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
         }
     }
 
+    /// Make a term from the iterator of a de-sugared `for` loop.
+    ///
+    /// The iterator is wrapped in a call to [`Intrinsic::ForRange`] if it is a
+    /// range `<lo>..<hi>` or `<lo>..<<hi>`, and in a call to
+    /// [`Intrinsic::ForItems`] otherwise. Lowering recognises these calls and
+    /// turns the loop into an index-based loop.
+    fn make_term_from_ast_for_iterator(
+        &self,
+        node: AstNodeRef<ast::Expr>,
+    ) -> SemanticResult<TermId> {
+        let origin = NodeOrigin::Given(node.id());
+
+        let (intrinsic, args) = match node.body() {
+            ast::Expr::Range(ast::RangeExpr { lo, hi, end }) => {
+                let lo = self.make_term_from_ast_expr(lo.ast_ref())?;
+                let hi = self.make_term_from_ast_expr(hi.ast_ref())?;
+                let inclusive = bool_term(*end == ast::RangeEnd::Included, origin);
+
+                let typeof_lo = Term::from(TyOfTerm { term: lo }, origin);
+                (Intrinsic::ForRange, vec![typeof_lo, lo, hi, inclusive])
+            }
+            _ => {
+                let items = self.make_term_from_ast_expr(node)?;
+
+                // The type of the yielded items is the type of the first item
+                let zero = create_term_from_usize_lit(self, 0, origin);
+                let first = Term::from(IndexTerm { subject: items, index: zero }, origin);
+                let typeof_item = Term::from(TyOfTerm { term: first }, origin);
+                let typeof_items = Term::from(TyOfTerm { term: items }, origin);
+                (Intrinsic::ForItems, vec![typeof_item, typeof_items, items])
+            }
+        };
+
+        Ok(Term::from(
+            CallTerm {
+                subject: Term::from(intrinsic, origin),
+                args: Arg::seq_positional(args, origin),
+                implicit: false,
+            },
+            origin,
+        ))
+    }
+
     /// Make a term from an [`ast::BodyBlock`].
     ///
     /// If this block is not from a stack scope, this will panic.
@@ -699,7 +771,6 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
                     .filter_map(|statement| {
                         if let ast::Expr::Declaration(declaration) = statement.body() {
                             // Handle declarations using `BlockStatement::Decl`
-                            self.scoping().register_declaration(node.with_body(declaration));
                             let decl =
                                 self.try_or_add_error(self.make_decl_from_ast_declaration(
                                     statement.with_body(declaration),
@@ -805,7 +876,7 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
     fn make_term_from_some_ast_fn_def(
         &self,
         params: AstParams<'_>,
-        body: &AstNode<ast::Expr>,
+        body: Option<&AstNode<ast::Expr>>,
         return_ty: &Option<AstNode<ast::Ty>>,
         node_id: AstNodeId,
     ) -> SemanticResult<TermId> {
@@ -846,8 +917,25 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
                     fn_def_id.borrow_mut().ty.return_ty = return_ty;
                 }
 
-                let return_value =
-                    self.try_or_add_error(self.make_term_from_ast_expr(body.ast_ref()));
+                let return_value = match body {
+                    Some(body) => {
+                        self.try_or_add_error(self.make_term_from_ast_expr(body.ast_ref()))
+                    }
+                    // Foreign function declarations don't have a body, and are never
+                    // lowered. So, the body is made to be `abort()` which always
+                    // type checks against the return type.
+                    None => {
+                        let origin = NodeOrigin::Given(node_id);
+                        Some(Term::from(
+                            CallTerm {
+                                subject: Term::from(Intrinsic::Abort, origin),
+                                args: Node::create_at(Node::<Arg>::empty_seq(), origin),
+                                implicit: false,
+                            },
+                            origin,
+                        ))
+                    }
+                };
 
                 // Modify the existing fn def for the return value:
                 if let Some(return_value) = return_value {
@@ -873,7 +961,7 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
     ) -> SemanticResult<TermId> {
         self.make_term_from_some_ast_fn_def(
             AstParams::Ty(&node.params),
-            &node.fn_body,
+            Some(&node.fn_body),
             &node.return_ty,
             node.id(),
         )
@@ -886,7 +974,7 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
     ) -> SemanticResult<TermId> {
         self.make_term_from_some_ast_fn_def(
             AstParams::Param(&node.params),
-            &node.fn_body,
+            node.fn_body.as_ref(),
             &node.return_ty,
             node.id(),
         )
@@ -2,17 +2,19 @@
 use std::{collections::HashMap, fmt};
 
 use hash_ast::ast::{self, AstNodeId};
-use hash_source::identifier::Identifier;
+use hash_source::{identifier::Identifier, SourceMapUtils};
 use hash_storage::store::{statics::StoreId, SequenceStoreKey, TrivialSequenceStoreKey};
 use hash_tir::{
     stack::StackId,
     term_as_variant,
     tir::{
-        CtorDefId, DataDefCtors, DataDefId, FnTy, ModDefId, ModMemberId, NodeOrigin, NodesId,
-        ParamId, SymbolId, TupleTy,
+        CtorDefId, DataDefCtors, DataDefId, FnTy, ModDefId, ModKind, ModMemberId, NodeOrigin,
+        NodesId, ParamId, SymbolId, TupleTy,
     },
 };
-use hash_utils::{derive_more::Deref, state::HeavyState};
+use hash_utils::{
+    derive_more::Deref, distance::closest_matches, path::relative_path, state::HeavyState,
+};
 
 use super::paths::NonTerminalResolvedPathComponent;
 use crate::{
@@ -21,6 +23,23 @@ use crate::{
     passes::ast_info::AstInfo,
 };
 
+/// The maximum number of suggestions that are given when a name cannot be
+/// found.
+const MAX_NAME_SUGGESTIONS: usize = 3;
+
+/// A suggestion to import a module which defines a name that could not be
+/// found in the current scope.
+#[derive(Debug, Clone)]
+pub struct ImportSuggestion {
+    /// The name of the module that defines the item, this is the name that
+    /// the module would be bound to when it is imported.
+    pub module: Identifier,
+
+    /// The path of the module, relative to the module that the name could
+    /// not be found in.
+    pub path: String,
+}
+
 /// The kind of context we are in.
 ///
 /// Either we are trying to resolve a symbol in the environment, or we are
@@ -141,6 +160,8 @@ impl<'env, E: SemanticEnv + 'env> Scoping<'env, E> {
                 symbol: SymbolId::from_name(name, NodeOrigin::Given(node)),
                 location: node.span(),
                 looking_in,
+                suggestions: self.suggest_similar_names(name),
+                imports: self.suggest_imports(name, node),
             })?;
 
         // @@Todo: Ensure that we are in the correct context for the binding.
@@ -157,6 +178,79 @@ impl<'env, E: SemanticEnv + 'env> Scoping<'env, E> {
         Ok(symbol)
     }
 
+    /// Find the names of the bindings that are visible from the current
+    /// scope which are similar to the given `name`, these are suggested
+    /// when the `name` cannot be found.
+    ///
+    /// This uses the same scopes as [`Self::lookup_symbol_by_name()`].
+    fn suggest_similar_names(&self, name: Identifier) -> Vec<Identifier> {
+        let bindings = self.bindings_by_name.get();
+        let scopes = match self.get_current_context_kind() {
+            ContextKind::Access(_, _) => &bindings[bindings.len().saturating_sub(1)..],
+            ContextKind::Environment => &bindings[..],
+        };
+
+        let candidates =
+            scopes.iter().flat_map(|(_, names)| names.keys().map(|candidate| candidate.as_str()));
+
+        closest_matches(name.as_str(), candidates, MAX_NAME_SUGGESTIONS)
+            .into_iter()
+            .map(Identifier::from)
+            .collect()
+    }
+
+    /// Find the other modules in the workspace that define a member with
+    /// the given `name`, and suggest how to import them. Module members are
+    /// only suggested when the name is being looked up in the environment,
+    /// since a member access already refers to a specific definition.
+    fn suggest_imports(&self, name: Identifier, node: AstNodeId) -> Vec<ImportSuggestion> {
+        if !matches!(self.get_current_context_kind(), ContextKind::Environment) {
+            return vec![];
+        }
+
+        let current = node.source();
+        let mut sources = vec![];
+
+        // Find all of the modules that define a member with the given name.
+        self.ast_info.mod_defs().iter_with(|_, mod_def| {
+            let mod_def = mod_def.borrow();
+
+            if let ModKind::Source(source) = mod_def.kind
+                && source != current
+                && !source.is_prelude()
+                && !sources.contains(&source)
+                && mod_def.get_mod_member_by_ident(name).is_some()
+            {
+                sources.push(source);
+            }
+        });
+
+        let Some(base) = SourceMapUtils::map(current, |source| {
+            source.canonicalised_path().parent().map(|parent| parent.to_path_buf())
+        }) else {
+            return vec![];
+        };
+
+        let mut suggestions = sources
+            .into_iter()
+            .map(|source| {
+                SourceMapUtils::map(source, |source| {
+                    let path = relative_path(&base, source.canonicalised_path().with_extension(""));
+
+                    ImportSuggestion {
+                        module: source.name().into(),
+                        path: path.to_string_lossy().replace('\\', "/"),
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // Sort the suggestions so that they are deterministic.
+        suggestions.sort_by(|a, b| a.path.cmp(&b.path));
+        suggestions.truncate(MAX_NAME_SUGGESTIONS);
+        suggestions
+    }
+
     /// Run a function in a new scope, and then exit the scope.
     pub(super) fn enter_scope<T>(&self, context_kind: ContextKind, f: impl FnOnce() -> T) -> T {
         self.bindings_by_name.enter_and_exit(
@@ -257,6 +257,7 @@ impl<E: SemanticEnv> ResolutionPass<'_, E> {
                 kind: match node.kind.as_ref() {
                     Some(kind) => match kind.body() {
                         ast::RefKind::Raw => RefKind::Raw,
+                        ast::RefKind::Rc => RefKind::Rc,
                         ast::RefKind::Normal => RefKind::Local,
                     },
                     None => RefKind::Local,
@@ -94,4 +94,8 @@ impl<E: SemanticEnv> TcEnv for TcEnvImpl<'_, E> {
     fn current_source(&self) -> SourceId {
         self.source
     }
+
+    fn should_stop(&self) -> bool {
+        self.env.cancellation().is_cancelled() || self.env.settings().memory_limit().is_exceeded()
+    }
 }
@@ -1,3 +1,7 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use hash_source::SourceId;
+use hash_tir::tir::{ModDefId, TermId};
 use hash_tir_utils::lower::TyCache;
 
 use crate::{prelude::DistinguishedItems, progress::AnalysisProgress};
@@ -15,4 +19,15 @@ pub struct SemanticStorage {
 
     /// The conversion cache between TIR types to Repr types.
     pub repr_ty_cache: TyCache,
+
+    /// The module definition of each source that has been discovered, this
+    /// allows the TIR of a source to be inspected after analysis.
+    pub source_mod_defs: RefCell<HashMap<SourceId, ModDefId>>,
+
+    /// The result of evaluating each interactive source.
+    pub evaluations: RefCell<HashMap<SourceId, TermId>>,
+
+    /// The scope trees that were requested with `--tir-dump-scopes`, these
+    /// are written to the output stream once the analysis has finished.
+    pub scope_dumps: RefCell<Vec<String>>,
 }
@@ -87,4 +87,11 @@ impl<T: fmt::Debug + Copy> EntryPointState<T> {
         self.item.set(Some((def, kind)));
         Some(())
     }
+
+    /// Specify the entry point of the program, replacing any entry point
+    /// that was previously defined. This is used when the entry point is
+    /// re-computed between pipeline runs.
+    pub fn replace(&self, def: T, kind: EntryPointKind) {
+        self.item.set(Some((def, kind)));
+    }
 }
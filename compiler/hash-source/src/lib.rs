@@ -402,6 +402,13 @@ impl SourceMapUtils {
         source
     }
 
+    /// Remove the path of the given module from the [SourceMap], so that a
+    /// module that is next reserved with the same path is given a new
+    /// [SourceId]. The module itself remains in the [SourceMap].
+    pub fn forget_module_path(id: ModuleId) {
+        SOURCE_MAP.write().module_paths.retain(|_, module| *module != id);
+    }
+
     pub fn set_module_source(id: SourceId, contents: String) {
         let mut map = SOURCE_MAP.write();
         let id: ModuleId = id.into();
@@ -136,6 +136,23 @@ impl Span {
         self.range.is_empty()
     }
 
+    /// Compare the position of two [Span]s. Spans are ordered by the path of
+    /// the source that they reference, and then by their position within the
+    /// source. Unlike the ordering of [SourceId]s, which depends on the order
+    /// that sources were loaded in, this ordering is stable between runs.
+    pub fn cmp_position(&self, other: &Self) -> cmp::Ordering {
+        let key = |span: &Self| SourceMapUtils::map(span.id, |source| source.path().to_path_buf());
+
+        if self.id == other.id {
+            return self.range.start().cmp(&other.range.start());
+        }
+
+        key(self)
+            .cmp(&key(other))
+            .then_with(|| self.id.value().cmp(&other.id.value()))
+            .then_with(|| self.range.start().cmp(&other.range.start()))
+    }
+
     /// Format the [ByteRange] into a file path with a column and row number.
     pub fn fmt_range(&self) -> String {
         SourceMapUtils::map(self.id, |source| format!("{}", source.row_cols(self.range)))
@@ -153,6 +170,39 @@ impl Span {
         })
     }
 
+    /// Format the start of the [Span] into a file path with a column and row
+    /// number.
+    ///
+    /// The span is formatted into the following format:
+    /// ```notrust
+    /// <path>:<start.row>:<start.column>
+    /// ```
+    pub fn fmt_start_path(&self) -> String {
+        SourceMapUtils::map(self.id, |source| {
+            format!(
+                "{}:{}",
+                source.canonicalised_path().display(),
+                source.row_cols(self.range).start
+            )
+        })
+    }
+
+    /// Format the [Span] into a short location that is made of the name of
+    /// the file and the line on which the [Span] starts.
+    ///
+    /// The span is formatted into the following format:
+    /// ```notrust
+    /// <file>:<start.row>
+    /// ```
+    pub fn fmt_location(&self) -> String {
+        SourceMapUtils::map(self.id, |source| {
+            let path = source.canonicalised_path();
+            let file = path.file_name().unwrap_or(path.as_os_str());
+
+            format!("{}:{}", file.to_string_lossy(), source.row_cols(self.range).start.row + 1)
+        })
+    }
+
     /// Get the contents of the [Span] from the [SpannedSource].
     pub fn contents(&self) -> String {
         SourceMapUtils::map(self.id, |source| source.hunk(self.range).to_string())
@@ -5,6 +5,8 @@ use parking_lot::{
     MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
 };
 
+use super::StoreSize;
+
 /// Represents a key that can be used to index a [`Store`].
 pub trait StoreKey: Copy + Eq {
     /// Turn the key into an index.
@@ -226,6 +228,12 @@ impl<K: StoreKey, V: Clone> Store<K, V> for DefaultStore<K, V> {
     }
 }
 
+impl<K, V> StoreSize for DefaultStore<K, V> {
+    fn size_in_bytes(&self) -> usize {
+        self.data.read().capacity() * std::mem::size_of::<V>()
+    }
+}
+
 #[cfg(test)]
 mod test_super {
     // Ensuring macros expand correctly:
@@ -6,10 +6,12 @@ pub use base::*;
 
 pub mod partial;
 pub mod sequence;
+pub mod size;
 pub mod statics;
 
 pub use partial::*;
 pub use sequence::*;
+pub use size::*;
 
 /// This macro creates a storages struct, as well as accompanying creation and
 /// access methods, for the given sequence of stores.
@@ -33,6 +35,18 @@ macro_rules! stores {
                   &self.$name
               }
           )*
+
+          /// Get the size in bytes of each of the stores, by name.
+          pub fn sizes(&self) -> Vec<(&'static str, usize)> {
+              use $crate::store::StoreSize;
+              vec![$((stringify!($name), self.$name.size_in_bytes())),*]
+          }
+      }
+
+      impl $crate::store::StoreSize for $store_name {
+          fn size_in_bytes(&self) -> usize {
+              self.sizes().into_iter().map(|(_, size)| size).sum()
+          }
       }
 
       impl Default for $store_name {
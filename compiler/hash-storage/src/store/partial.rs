@@ -11,6 +11,8 @@ use dashmap::{
 };
 use fxhash::FxBuildHasher;
 
+use super::StoreSize;
+
 /// The internal data structure for a [`PartialStore`].
 pub type PartialStoreInternalData<Key, Value> = DashMap<Key, Value, FxBuildHasher>;
 pub type PartialStoreBorrowHandle<'a, Key, Value> = Ref<'a, Key, Value, FxBuildHasher>;
@@ -169,3 +171,9 @@ impl<K: Copy + Eq + Hash, V> PartialStore<K, V> for DefaultPartialStore<K, V> {
         &self.data
     }
 }
+
+impl<K: Eq + Hash, V> StoreSize for DefaultPartialStore<K, V> {
+    fn size_in_bytes(&self) -> usize {
+        self.data.capacity() * std::mem::size_of::<(K, V)>()
+    }
+}
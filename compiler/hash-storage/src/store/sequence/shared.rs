@@ -5,6 +5,7 @@ use parking_lot::{
 };
 
 use super::SequenceStoreKey;
+use crate::store::StoreSize;
 
 /// The internal data of a store.
 pub type SequenceStoreInternalData<Value> = RwLock<Vec<Value>>;
@@ -323,3 +324,9 @@ impl<K: SequenceStoreKey, V: Clone> SequenceStore<K, V> for DefaultSequenceStore
         &self.data
     }
 }
+
+impl<K, V> StoreSize for DefaultSequenceStore<K, V> {
+    fn size_in_bytes(&self) -> usize {
+        self.data.read().capacity() * std::mem::size_of::<V>()
+    }
+}
@@ -0,0 +1,81 @@
+//! Approximate accounting of the memory that is used by the stores. The
+//! compiler registers its major collections of stores, e.g. the TIR stores,
+//! so that the memory limit of a compilation can report which of the stores
+//! is using the most memory.
+use std::sync::RwLock;
+
+/// A store that can report the approximate number of bytes that it has
+/// allocated. Only the memory that is owned by the store itself is counted,
+/// not the memory that is owned by the values, e.g. the contents of a `Vec`
+/// that is stored in the store.
+pub trait StoreSize {
+    /// Get the approximate number of bytes that the store has allocated.
+    fn size_in_bytes(&self) -> usize;
+}
+
+/// A function which reports the size of each store in a collection of stores,
+/// by name.
+pub type StoreSizeReporter = fn() -> Vec<(&'static str, usize)>;
+
+/// All of the collections of stores that have been registered, by the name of
+/// the collection.
+static REPORTERS: RwLock<Vec<(&'static str, StoreSizeReporter)>> = RwLock::new(Vec::new());
+
+/// Register a collection of stores with the given `group` name, so that their
+/// sizes are included in [`store_sizes()`].
+pub fn register_store_sizes(group: &'static str, reporter: StoreSizeReporter) {
+    REPORTERS.write().unwrap().push((group, reporter));
+}
+
+/// Get the size in bytes of every registered store, from the largest to the
+/// smallest. Each store is named by its group and its name, e.g. `tir.term`.
+pub fn store_sizes() -> Vec<(String, usize)> {
+    // The reporters are copied so that the lock isn't held whilst they run,
+    // since a reporter might be the first to access its stores.
+    let reporters = REPORTERS.read().unwrap().clone();
+
+    let mut sizes: Vec<_> = reporters
+        .into_iter()
+        .flat_map(|(group, reporter)| {
+            reporter().into_iter().map(move |(name, size)| (format!("{group}.{name}"), size))
+        })
+        .collect();
+
+    sizes.sort_by(|(_, left), (_, right)| right.cmp(left));
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        new_store_key,
+        store::{DefaultPartialStore, DefaultStore, PartialStore, Store},
+        stores,
+    };
+
+    new_store_key!(pub TestKey, derives = Debug);
+
+    stores! {
+        TestStores;
+        values: DefaultStore<TestKey, u64>,
+        names: DefaultPartialStore<TestKey, u32>,
+    }
+
+    #[test]
+    fn registered_stores_are_reported_from_the_largest() {
+        static STORES: std::sync::OnceLock<TestStores> = std::sync::OnceLock::new();
+        let stores = STORES.get_or_init(TestStores::new);
+
+        let key = stores.values().create(1);
+        stores.names().insert(key, 2);
+        register_store_sizes("test", || STORES.get().unwrap().sizes());
+
+        let sizes: Vec<_> =
+            store_sizes().into_iter().filter(|(name, _)| name.starts_with("test.")).collect();
+        assert_eq!(sizes.len(), 2);
+        assert!(sizes[0].1 >= sizes[1].1);
+        assert!(sizes.iter().any(|(name, size)| name == "test.values" && *size >= 8));
+        assert_eq!(stores.size_in_bytes(), sizes.iter().map(|(_, size)| size).sum());
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
     data_layout::HasDataLayout,
     primitives::{FloatTy, IntTy, SIntTy, UIntTy},
     size::Size,
+    TargetArch,
 };
 
 /// ABI representation of an [`ScalarKind::Int`] type. This is
@@ -462,6 +463,16 @@ impl AbiRepresentation {
     pub fn is_uninhabited(&self) -> bool {
         matches!(self, AbiRepresentation::Uninhabited)
     }
+
+    /// Check if the [AbiRepresentation] is a vector.
+    pub fn is_vector(&self) -> bool {
+        matches!(self, AbiRepresentation::Vector { .. })
+    }
+
+    /// Check if the [AbiRepresentation] is an aggregate.
+    pub fn is_aggregate(&self) -> bool {
+        matches!(self, AbiRepresentation::Aggregate)
+    }
 }
 
 /// An identifier that specifies the address space that some operation
@@ -489,4 +500,36 @@ pub enum Abi {
     /// The default ABI, which attempts to perform optimisations
     /// that are not possible with the C ABI.
     Hash,
+
+    /// The `fastcall` convention of 32-bit x86 targets, which passes the
+    /// first two integer arguments in registers.
+    Fast,
+
+    /// The convention that is used by the system libraries of the target,
+    /// this is `stdcall` on 32-bit x86 Windows targets, and the C ABI on
+    /// all other targets.
+    System,
+}
+
+impl Abi {
+    /// Check whether the ABI can be used when compiling for the given
+    /// [TargetArch].
+    pub fn is_supported_on(&self, arch: TargetArch) -> bool {
+        match self {
+            Abi::C | Abi::Cold | Abi::Hash | Abi::System => true,
+            Abi::Fast => arch == TargetArch::X86,
+        }
+    }
+}
+
+impl fmt::Display for Abi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Abi::C => write!(f, "C"),
+            Abi::Cold => write!(f, "cold"),
+            Abi::Hash => write!(f, "hash"),
+            Abi::Fast => write!(f, "fast"),
+            Abi::System => write!(f, "system"),
+        }
+    }
 }
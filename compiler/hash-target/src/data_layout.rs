@@ -3,8 +3,10 @@
 //! layout. Furthermore, this module contains logic about parsing
 //! layout from a "layout specification" string, more information
 //! about this can be found (https://llvm.org/docs/LangRef.html#data-layout)[here].
+//! A [TargetDataLayout] can also be written back into such a string, which
+//! parses into an equivalent layout.
 
-use std::num::ParseIntError;
+use std::{fmt, num::ParseIntError};
 
 use crate::{
     abi::{AddressSpace, Integer},
@@ -50,7 +52,7 @@ impl Endian {
 /// lay'd out for a specific target. The layout of a target
 /// is specified as a "layout specification" string, more information
 /// about this can be found (https://llvm.org/docs/LangRef.html#data-layout)[here].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TargetDataLayout {
     /// The kind of Endianness that the target uses.
     pub endian: Endian,
@@ -167,6 +169,7 @@ impl Default for TargetDataLayout {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TargetDataLayoutParseError<'a> {
     /// The specified data layout string was invalid, and could
     /// not be parsed into separate components.
@@ -297,6 +300,25 @@ impl TargetDataLayout {
                     }
                 }
 
+                // Vector alignments, which either override the alignment of
+                // an existing vector size or add a new one.
+                [s, ref values @ ..] if s.starts_with('v') => {
+                    let size = Size::from_bits(s[1..].parse::<u64>().map_err(|err| {
+                        TargetDataLayoutParseError::InvalidBits {
+                            kind: "size",
+                            bit: s,
+                            cause: s,
+                            err,
+                        }
+                    })?);
+                    let alignments = Self::parse_alignment_specification(values, s)?;
+
+                    match data_layout.vector_align.iter_mut().find(|(vector, _)| *vector == size) {
+                        Some((_, align)) => *align = alignments,
+                        None => data_layout.vector_align.push((size, alignments)),
+                    }
+                }
+
                 // Everything else is ignored since it is not relevant to
                 // the layout of data.
                 _ => {}
@@ -341,3 +363,173 @@ impl TargetDataLayout {
         Ok(Alignments { abi, preferred })
     }
 }
+
+/// Write the ":<abi>[:<pref>]" alignment specification of a component, the
+/// preferred alignment is omitted if it is the same as the ABI alignment.
+fn write_alignment_specification(f: &mut fmt::Formatter<'_>, align: Alignments) -> fmt::Result {
+    write!(f, ":{}", align.abi.bits())?;
+
+    if align.preferred != align.abi {
+        write!(f, ":{}", align.preferred.bits())?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for TargetDataLayout {
+    /// Write the [TargetDataLayout] as a "layout specification" string. Every
+    /// component is written explicitly, so that parsing the string does not
+    /// depend on the LLVM defaults.
+    ///
+    /// N.B. Components that aren't represented by the [TargetDataLayout], i.e.
+    /// the name mangling or the native integer widths, are not written. The
+    /// `c_style_enum_min_size` is also not part of the string, it is derived
+    /// from the [crate::Target] instead.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.endian {
+            Endian::Little => write!(f, "e")?,
+            Endian::Big => write!(f, "E")?,
+        }
+
+        if self.instruction_address_space != AddressSpace::DATA {
+            write!(f, "-P{}", self.instruction_address_space.0)?;
+        }
+
+        write!(f, "-p:{}", self.pointer_size.bits())?;
+        write_alignment_specification(f, self.pointer_align)?;
+
+        for (bits, align) in [
+            (1, self.i1_align),
+            (8, self.i8_align),
+            (16, self.i16_align),
+            (32, self.i32_align),
+            (64, self.i64_align),
+            (128, self.i128_align),
+        ] {
+            write!(f, "-i{bits}")?;
+            write_alignment_specification(f, align)?;
+        }
+
+        write!(f, "-f32")?;
+        write_alignment_specification(f, self.f32_align)?;
+        write!(f, "-f64")?;
+        write_alignment_specification(f, self.f64_align)?;
+
+        for (size, align) in &self.vector_align {
+            write!(f, "-v{}", size.bits())?;
+            write_alignment_specification(f, *align)?;
+        }
+
+        write!(f, "-a")?;
+        write_alignment_specification(f, self.aggregate_align)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{targets::AVAILABLE_TARGETS, Target};
+
+    /// Parse the given "layout specification" string, expecting it to be
+    /// valid.
+    fn parse(input: &str) -> TargetDataLayout {
+        TargetDataLayout::parse_from_llvm_data_layout_string(input).unwrap()
+    }
+
+    fn bytes(alignments: Alignments) -> (u64, u64) {
+        (alignments.abi.bytes(), alignments.preferred.bytes())
+    }
+
+    #[test]
+    fn test_round_trip_default_layout() {
+        let layout = TargetDataLayout::default();
+        assert_eq!(parse(&layout.to_string()), layout);
+    }
+
+    #[test]
+    fn test_round_trip_target_layouts() {
+        for triple in AVAILABLE_TARGETS {
+            let target = Target::search(triple).unwrap();
+            let layout = target.parse_data_layout().unwrap();
+
+            // The enum size isn't part of the string, it comes from the target.
+            let mut parsed = parse(&layout.to_string());
+            parsed.c_style_enum_min_size = layout.c_style_enum_min_size;
+
+            assert_eq!(parsed, layout, "for target `{triple}`");
+        }
+    }
+
+    #[test]
+    fn test_round_trip_custom_layout() {
+        let layout = parse("E-P1-p:32:32-i64:64-i128:128-f64:32:64-v96:128-v64:32-a:0:32");
+
+        assert_eq!(layout.endian, Endian::Big);
+        assert_eq!(layout.instruction_address_space, AddressSpace(1));
+        assert_eq!(layout.pointer_size, Size::from_bits(32));
+        assert_eq!(bytes(layout.f64_align), (4, 8));
+        assert_eq!(bytes(layout.aggregate_align), (1, 4));
+
+        // `v64` overrides the default alignment, and `v96` is added.
+        assert_eq!(
+            layout
+                .vector_align
+                .iter()
+                .map(|(size, align)| (size.bits(), bytes(*align)))
+                .collect::<Vec<_>>(),
+            vec![(64, (4, 4)), (128, (16, 16)), (96, (16, 16))]
+        );
+
+        assert_eq!(parse(&layout.to_string()), layout);
+    }
+
+    #[test]
+    fn test_known_target_layouts() {
+        let x86_64 =
+            parse("e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128");
+
+        // Address spaced pointers don't affect the pointer of the default
+        // address space, and `i128` uses the alignment of `i64` since it is the
+        // largest integer that is specified.
+        assert_eq!(x86_64.pointer_size, Size::from_bits(64));
+        assert_eq!(bytes(x86_64.pointer_align), (8, 8));
+        assert_eq!(bytes(x86_64.i64_align), (8, 8));
+        assert_eq!(bytes(x86_64.i128_align), (8, 8));
+        assert_eq!(x86_64.ptr_sized_integer(), Integer::I64);
+
+        let aarch64 = parse("e-m:o-i64:64-i128:128-n32:64-S128");
+        assert_eq!(bytes(aarch64.i128_align), (16, 16));
+
+        // All of the integers are at most 2-byte aligned on a 16-bit target.
+        let msp430 = parse("e-m:e-p:16:16-i32:16-i64:16-f32:16-f64:16-a:8-n8:16-S16");
+        assert_eq!(msp430.pointer_size, Size::from_bits(16));
+        assert_eq!(bytes(msp430.pointer_align), (2, 2));
+        assert_eq!(bytes(msp430.i64_align), (2, 2));
+        assert_eq!(bytes(msp430.i128_align), (2, 2));
+        assert_eq!(msp430.ptr_sized_integer(), Integer::I16);
+        assert_eq!(msp430.obj_size_bound(), 1 << 15);
+    }
+
+    #[test]
+    fn test_invalid_layouts() {
+        let parse = TargetDataLayout::parse_from_llvm_data_layout_string;
+
+        assert!(matches!(parse(""), Err(TargetDataLayoutParseError::Malformed { .. })));
+        assert!(matches!(
+            parse("e-Px"),
+            Err(TargetDataLayoutParseError::InvalidAddressSpace { addr_space: "Px", .. })
+        ));
+        assert!(matches!(
+            parse("e-i64"),
+            Err(TargetDataLayoutParseError::MissingAlignment { cause: "i64" })
+        ));
+        assert!(matches!(
+            parse("e-i64:24"),
+            Err(TargetDataLayoutParseError::InvalidAlignment { cause: "i64" })
+        ));
+        assert!(matches!(
+            parse("e-vx:64"),
+            Err(TargetDataLayoutParseError::InvalidBits { kind: "size", bit: "vx", .. })
+        ));
+    }
+}
@@ -6,6 +6,7 @@ pub mod alignment;
 pub mod data_layout;
 pub mod discriminant;
 pub mod link;
+pub mod overflow;
 pub mod primitives;
 pub mod size;
 pub mod targets;
@@ -22,6 +23,7 @@ use link::{
     link_env, Cc, CodeModel, FramePointer, LinkEnv, LinkageArgs, LinkerFlavour, Lld,
     RelocationModel,
 };
+use overflow::IntOverflowSemantics;
 use size::Size;
 use targets::load_target;
 
@@ -54,7 +56,7 @@ impl Platform {
 
 /// Represents the available target architectures that the compiler can compiler
 /// for.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TargetArch {
     /// x86 32-bit target architecture.
     X86,
@@ -68,6 +70,9 @@ pub enum TargetArch {
     /// ARM 32-bit target architecture.
     Arm,
 
+    /// MSP430 16-bit target architecture.
+    Msp430,
+
     /// Used for when the target name is not known, but can
     /// still be compiled for.
     Unknown,
@@ -81,6 +86,7 @@ impl TargetArch {
             "x86_64" | "x86-64" | "x64" => Self::X86_64,
             "aarch64" => Self::Aarch64,
             "arm" => Self::Arm,
+            "msp430" => Self::Msp430,
             _ => Self::Unknown,
         }
     }
@@ -92,6 +98,7 @@ impl TargetArch {
             TargetArch::X86_64 => "x86-64",
             TargetArch::Aarch64 => "aarch64",
             TargetArch::Arm => "arm",
+            TargetArch::Msp430 => "msp430",
             TargetArch::Unknown => "unknown",
         }
     }
@@ -104,6 +111,7 @@ impl Display for TargetArch {
             TargetArch::X86_64 => write!(f, "x86_64"),
             TargetArch::Aarch64 => write!(f, "aarch64"),
             TargetArch::Arm => write!(f, "arm"),
+            TargetArch::Msp430 => write!(f, "msp430"),
             TargetArch::Unknown => write!(f, "unknown"),
         }
     }
@@ -114,9 +122,7 @@ impl Display for TargetArch {
 pub struct Target {
     /// The size of the pointer for the target in bits.
     ///
-    /// N.B. It is an invariant for the pointer size to be
-    /// larger than 8, there is no current host system that
-    /// supports a pointer size larger than 8 bits.
+    /// N.B. The pointer size must be either 16, 32 or 64 bits.
     pub pointer_bit_width: usize,
 
     /// The string name of the operating system that the target
@@ -160,6 +166,10 @@ pub struct Target {
     /// The integer width of the target in bits.
     pub c_int_width: u8,
 
+    /// The semantics of the integer operations that can overflow when they
+    /// are not checked.
+    pub int_overflow: IntOverflowSemantics,
+
     /// The default visibility for symbols in this target should be "hidden"
     /// rather than "default"
     pub default_hidden_visibility: bool,
@@ -240,14 +250,17 @@ impl Target {
         Size::from_bits(self.pointer_bit_width as u64)
     }
 
-    /// Adjust a given [ABI] to match the specifics of the
-    /// target. Currently, we don't support any other ABI
-    /// except "Hash", so this function will do nothing. However,
-    /// when we support various other CPP ABIs like "stdcall", "fastcall",
-    /// "vectorcall", etc, this function will be used to adjust the
-    /// ABI to match the target details.
+    /// Adjust a given [Abi] to match the specifics of the target. The
+    /// [`Abi::System`] ABI is only distinct from the C ABI on 32-bit x86
+    /// Windows targets, where it is `stdcall`.
+    ///
+    /// @@Future: when we support other ABIs like "vectorcall", this function
+    /// will be used to adjust them to match the target details.
     pub fn adjust_abi(&self, abi: Abi) -> Abi {
-        abi
+        match abi {
+            Abi::System if self.arch != TargetArch::X86 || !self.is_like_windows() => Abi::C,
+            abi => abi,
+        }
     }
 
     /// Find and load the specified target from a target triple.
@@ -339,6 +352,7 @@ impl Default for Target {
 
             // ABI related options
             c_int_width: 32,
+            int_overflow: IntOverflowSemantics::default(),
 
             // This is the default data-layout string that is specified within
             // the LLVM IR Language reference, for any registered platform this
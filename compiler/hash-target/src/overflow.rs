@@ -0,0 +1,47 @@
+//! Defines the semantics of the integer operations whose result isn't
+//! defined for all of their operands, i.e. exponentiation (`^^`) and bit
+//! shifts (`<<` and `>>`).
+//!
+//! When operations are checked, every operation whose result can't be
+//! represented fails with an assertion:
+//!
+//! - `lhs ^^ rhs` fails with an overflow assertion if the result doesn't fit
+//!   within the type of `lhs`, and with a negative exponent assertion if `rhs`
+//!   is a negative signed integer.
+//!
+//! - `lhs << rhs` and `lhs >> rhs` fail with a shift amount assertion if `rhs`
+//!   is not smaller than the bit-width of `lhs`. The amount is always treated
+//!   as unsigned, and so a negative amount is always too large.
+//!
+//! When operations are unchecked, the result is always defined:
+//!
+//! - `lhs ^^ rhs` wraps around on overflow like all other arithmetic, and a
+//!   negative exponent is treated as zero, i.e. the result is `1`.
+//!
+//! - `lhs << rhs` and `lhs >> rhs` depend on the [ShiftAmountSemantics] of the
+//!   target when `rhs` is too large.
+
+/// How an unchecked bit shift treats an amount that is not smaller than the
+/// bit-width of the value that is being shifted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShiftAmountSemantics {
+    /// The amount is masked to the bit-width of the value, i.e. `x << n` is
+    /// `x << (n % bits)`. This is what the shift instructions of `x86_64` and
+    /// `aarch64` do.
+    #[default]
+    Mask,
+
+    /// The value is shifted out entirely, i.e. left shifts and logical right
+    /// shifts produce `0`, and arithmetic right shifts produce `0` or `-1`
+    /// depending on the sign of the value. This is what the shift
+    /// instructions of 32-bit `arm` do.
+    Saturate,
+}
+
+/// The configuration of the integer operations whose results depend on the
+/// target when they are not checked.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntOverflowSemantics {
+    /// How an unchecked bit shift treats an amount that is too large.
+    pub shift_amount: ShiftAmountSemantics,
+}
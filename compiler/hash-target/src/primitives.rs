@@ -460,7 +460,7 @@ impl NormalisedIntTy {
 #[cfg(test)]
 mod tests {
     use crate::{
-        primitives::{SIntTy, UIntTy},
+        primitives::{IntTy, SIntTy, UIntTy},
         size::Size,
     };
 
@@ -473,6 +473,9 @@ mod tests {
         assert_eq!(SIntTy::ISize.max(Size::from_bytes(4)), i32::MAX as i128);
         assert_eq!(SIntTy::ISize.min(Size::from_bytes(4)), i32::MIN as i128);
 
+        assert_eq!(SIntTy::ISize.max(Size::from_bytes(2)), i16::MAX as i128);
+        assert_eq!(SIntTy::ISize.min(Size::from_bytes(2)), i16::MIN as i128);
+
         // Check that computing the size of each type with pointer widths
         // is consistent.
         assert_eq!(SIntTy::ISize.size(Size::from_bytes(8)), Size::from_bytes(8));
@@ -485,8 +488,21 @@ mod tests {
         // returns 0.
         assert_eq!(UIntTy::USize.max(Size::from_bytes(8)), usize::MAX as u128);
         assert_eq!(UIntTy::USize.max(Size::from_bytes(4)), u32::MAX as u128);
+        assert_eq!(UIntTy::USize.max(Size::from_bytes(2)), u16::MAX as u128);
 
         assert_eq!(UIntTy::USize.size(Size::from_bytes(4)), Size::from_bytes(4));
         assert_eq!(UIntTy::USize.size(Size::from_bytes(8)), Size::from_bytes(8));
+        assert_eq!(UIntTy::USize.size(Size::from_bytes(2)), Size::from_bytes(2));
+    }
+
+    #[test]
+    fn test_normalise_pointer_sized_int() {
+        let ptr_size = Size::from_bytes(2);
+
+        assert_eq!(UIntTy::USize.normalise(ptr_size), UIntTy::U16);
+        assert_eq!(SIntTy::ISize.normalise(ptr_size), SIntTy::I16);
+        assert_eq!(IntTy::Int(SIntTy::ISize).numeric_min(ptr_size), 0x8000);
+        assert_eq!(IntTy::Int(SIntTy::ISize).numeric_max(ptr_size), 0x7fff);
+        assert_eq!(IntTy::UInt(UIntTy::USize).numeric_max(ptr_size), 0xffff);
     }
 }
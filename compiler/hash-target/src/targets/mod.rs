@@ -30,4 +30,5 @@ register_targets! {
     ("x86_64-apple-darwin", x86_apple_darwin),
     ("aarch64-apple-darwin", aarch64_apple_darwin),
     ("x86_64-pc-windows-msvc", x86_64_pc_windows_msvc),
+    ("msp430-none-elf", msp430_none_elf),
 }
@@ -0,0 +1,18 @@
+//! Target specifications for the msp430-none-elf target, which is a
+//! bare-metal target with 16-bit pointers.
+
+use crate::{link::RelocationModel, Target, TargetArch};
+
+pub fn target() -> Target {
+    Target {
+        name: "msp430-none-elf".into(),
+        arch: TargetArch::Msp430,
+        cpu: "msp430".into(),
+        os: "none".into(),
+        pointer_bit_width: 16,
+        c_int_width: 16,
+        data_layout: "e-m:e-p:16:16-i32:16-i64:16-f32:16-f64:16-a:8-n8:16-S16".into(),
+        relocation_mode: RelocationModel::Static,
+        ..Default::default()
+    }
+}
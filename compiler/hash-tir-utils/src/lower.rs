@@ -6,6 +6,7 @@ use std::cell::RefCell;
 use hash_attrs::builtin::attrs;
 use hash_ir::{
     lang_items::LangItem,
+    link::LinkItem,
     ty::{AdtHelpers, InstanceHelpers},
     HasIrCtx,
 };
@@ -202,7 +203,7 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
                 let adt = Adt::new_with_flags("tuple".into(), index_vec![variant], flags);
                 ReprTy::Adt(Adt::create(adt))
             }
-            Ty::FnTy(FnTy { params, return_ty, .. }) => {
+            Ty::FnTy(FnTy { params, return_ty, abi, .. }) => {
                 let params = ReprTyListId::seq(
                     params
                         .elements()
@@ -211,7 +212,7 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
                         .map(|param| self.repr_ty_from_tir_ty(param.ty)),
                 );
                 let return_ty = self.repr_ty_from_tir_ty(return_ty);
-                ReprTy::Fn { params, return_ty }
+                ReprTy::Fn { params, return_ty, abi }
             }
             Ty::RefTy(RefTy { kind, mutable, ty }) => {
                 let ty = self.repr_ty_from_tir_ty(ty);
@@ -265,6 +266,21 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
             let is_lang = instance.has_attr(attrs::LANG);
             let name = instance.name();
 
+            // If the instance is a foreign function that is linked against a
+            // specific library, then we record where the symbol comes from. The
+            // symbol name can be overridden using `#link_name`.
+            let link = instance
+                .get_attr(attrs::LINK)
+                .filter(|_| instance.has_attr(attrs::FOREIGN))
+                .map(|attr| {
+                    let library = attr.get_arg(0).unwrap().value.as_alloc().to_str();
+                    let symbol = instance.get_attr(attrs::LINK_NAME).map_or(name, |attr| {
+                        attr.get_arg(0).unwrap().value.as_alloc().to_str().into()
+                    });
+
+                    LinkItem { library: library.into(), symbol }
+                });
+
             // Check if the instance has the `lang` attribute, specifying that it is
             // the lang-item attribute.
             let instance = Instance::create(instance);
@@ -275,6 +291,10 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
                 self.ir_ctx().lang_items_mut().set(item, instance, ty);
             }
 
+            if let Some(item) = link {
+                self.ir_ctx().link_items_mut().set(instance, item);
+            }
+
             (ty, ShouldCache::Yes)
         })
     }
@@ -291,7 +311,7 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
         let attr_id = fn_def.node_id_ensured();
 
         let source = fn_def.span().map(|location| location.id);
-        let FnTy { params, return_ty, .. } = ty;
+        let FnTy { params, return_ty, is_c_variadic, abi, .. } = ty;
 
         let params = ReprTyListId::seq(
             params.elements().borrow().iter().map(|param| self.repr_ty_from_tir_ty(param.ty)),
@@ -299,7 +319,13 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
         let ret_ty = self.repr_ty_from_tir_ty(return_ty);
         let ident = name.ident();
 
-        Instance::new(ident, source, params, ret_ty, attr_id)
+        // The ABI of the function is recorded in its type, so that pointers
+        // to the function are called with the same convention.
+        let mut instance = Instance::new(ident, source, params, ret_ty, attr_id);
+        instance.is_c_variadic = is_c_variadic;
+        instance.abi = abi;
+
+        instance
     }
 
     /// Convert the [DataTy] into an [`ReprTy::Adt`]. The [DataTy] specifies a
@@ -379,13 +405,19 @@ impl<E: TyLowerEnv> TyLower<'_, E> {
                 // default to using the index of the variant as the
                 // discriminant.
                 //
+                // The value of the discriminant is always stored as the raw bits of the
+                // discriminant type, i.e. a `-1` of an `i8` is stored as `0xff`. Explicit
+                // values may be stored in a wider type, so they must be truncated.
+                //
                 // @@Hack @@TIRConsts
                 let discriminant = if let Some(discriminant_term) = ctor.discriminant
                     && let Some(ref value) = try_use_term_as_const(self.env, discriminant_term)
                 {
                     let scalar = value.as_scalar();
+                    let size = ty.size(self.env.target().ptr_size());
+
                     Discriminant {
-                        value: scalar.to_bits(scalar.size()).unwrap(),
+                        value: size.truncate(scalar.to_bits(scalar.size()).unwrap()),
                         ty,
                         kind: DiscriminantKind::Explicit,
                     }
@@ -2,7 +2,7 @@
 //! program, specifically their types.
 use std::hash::Hash;
 
-use hash_storage::store::{DefaultPartialStore, PartialCloneStore, PartialStore};
+use hash_storage::store::{DefaultPartialStore, PartialCloneStore, PartialStore, StoreSize};
 
 use crate::tir::{ArgsId, FnDefId, FnTy, ParamsId, PatArgsId, PatId, TermId, TyId};
 
@@ -56,6 +56,12 @@ macro_rules! atom_info {
                 Self::new()
             }
         }
+
+        impl StoreSize for AtomInfoStore {
+            fn size_in_bytes(&self) -> usize {
+                0 $(+ self.$name.size_in_bytes())*
+            }
+        }
     };
 }
 
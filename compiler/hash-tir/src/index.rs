@@ -0,0 +1,89 @@
+//! An index of the terms and patterns of a module, recording the inferred
+//! type of each term that originates from the source, and where each
+//! variable that is referenced is defined. This is used by the language
+//! server in order to answer hover and go-to-definition requests, without
+//! needing to walk the TIR for every request.
+
+use std::ops::ControlFlow;
+
+use hash_source::{location::Span, SourceId};
+use hash_storage::store::statics::StoreId;
+
+use crate::{
+    atom_info::ItemInAtomInfo,
+    stores::tir_stores,
+    tir::{HasAstNodeId, ModDefId, Term, TyId},
+    visitor::{Atom, Visit, Visitor},
+};
+
+/// A single term or pattern within a [ModuleIndex].
+#[derive(Debug, Clone, Copy)]
+pub struct IndexEntry {
+    /// Where the term or pattern is within the source.
+    pub span: Span,
+
+    /// The inferred type of the term or pattern, this is [None] if the
+    /// inference of the term failed.
+    pub ty: Option<TyId>,
+
+    /// Where the binding that the term refers to is defined, if the term is
+    /// a variable.
+    pub definition: Option<Span>,
+}
+
+/// The index of all of the terms and patterns of a module.
+#[derive(Debug, Clone)]
+pub struct ModuleIndex {
+    /// The entries of the index, ordered by where they start.
+    entries: Vec<IndexEntry>,
+}
+
+impl ModuleIndex {
+    /// Build the [ModuleIndex] of the given module definition, only the terms
+    /// and patterns that originate from the given `source` are indexed.
+    pub fn new(source: SourceId, mod_def: ModDefId) -> Self {
+        let mut entries = vec![];
+
+        Visitor::new().visit(mod_def, &mut |atom: Atom| {
+            let entry = match atom {
+                Atom::Term(term) => term.span().map(|span| IndexEntry {
+                    span,
+                    ty: tir_stores().atom_info().try_get_inferred_ty(term),
+                    definition: match *term.value() {
+                        Term::Var(var) => var.symbol.span(),
+                        _ => None,
+                    },
+                }),
+                Atom::Pat(pat) => pat.span().map(|span| IndexEntry {
+                    span,
+                    ty: tir_stores().atom_info().try_get_inferred_ty(pat),
+                    definition: None,
+                }),
+                Atom::FnDef(_) | Atom::Lit(_) => None,
+            };
+
+            entries.extend(entry.filter(|entry| entry.span.id == source));
+            ControlFlow::Continue(())
+        });
+
+        entries.sort_by_key(|entry| entry.span.range.start());
+        Self { entries }
+    }
+
+    /// Get all of the entries of the index.
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// Find the innermost entry that contains the given byte offset of the
+    /// source. Entries with an inferred type are preferred, since terms that
+    /// are generated from the same source, i.e. the implicit arguments of a
+    /// call, might not have been inferred.
+    pub fn entry_at(&self, offset: usize) -> Option<&IndexEntry> {
+        self.entries
+            .iter()
+            .take_while(|entry| entry.span.range.start() <= offset)
+            .filter(|entry| offset <= entry.span.range.end())
+            .min_by_key(|entry| (entry.ty.is_none(), entry.span.range.len()))
+    }
+}
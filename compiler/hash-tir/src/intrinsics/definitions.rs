@@ -14,7 +14,7 @@ use paste::paste;
 use crate::{
     building::gen::{
         args, indexed_enum_def, params, primitive_with_params, ref_ty, sym, term, ty, unit_term,
-        Type,
+        unit_ty, Type,
     },
     intrinsics::utils::{try_use_term_as_const, try_use_term_as_integer_lit},
     make_intrinsics, make_primitives,
@@ -43,6 +43,66 @@ make_intrinsics! {
         Ok(None)
     };
 
+    read := (T: Type(), ptr: ref_ty(ty(T), RefKind::Raw, false)) -> ty(T) => |env| {
+        // Reading from a raw pointer can only happen at runtime.
+        Ok(None)
+    };
+
+    write := (T: Type(), ptr: ref_ty(ty(T), RefKind::Raw, true), value: ty(T)) -> unit_ty() => |env| {
+        // Writing to a raw pointer can only happen at runtime.
+        Ok(None)
+    };
+
+    variant_count := (T: Type()) -> usize_gen_ty() => |env| {
+        // The number of variants is computed during lowering.
+        Ok(None)
+    };
+
+    discriminant_value := (T: Type(), value: ty(T)) -> usize_gen_ty() => |env| {
+        // The discriminant is read from the value during lowering.
+        Ok(None)
+    };
+
+    enum_from_discriminant := (T: Type(), value: usize_gen_ty()) -> option_gen_ty(ty(T)) => |env| {
+        // The discriminant is checked against the variants during lowering.
+        Ok(None)
+    };
+
+    for_items := (T: Type(), I: Type(), items: ty(I)) -> option_gen_ty(ty(T)) => |env| {
+        // The `for` loop over the items is lowered into an index-based loop.
+        Ok(None)
+    };
+
+    for_range := (T: Type(), start: ty(T), end: ty(T), inclusive: bool_gen_ty()) -> option_gen_ty(ty(T)) => |env| {
+        // The `for` loop over the range is lowered into an index-based loop.
+        Ok(None)
+    };
+
+    derived_eq := (T: Type(), a: ty(T), b: ty(T)) -> bool_gen_ty() => |env| {
+        // The comparison is synthesised from `#derive("eq")` during lowering.
+        Ok(None)
+    };
+
+    derived_hash := (T: Type(), value: ty(T)) -> u64_gen_ty() => |env| {
+        // The hash is synthesised from `#derive("hash")` during lowering.
+        Ok(None)
+    };
+
+    derived_debug := (T: Type(), value: ty(T)) -> unit_ty() => |env| {
+        // The printer is synthesised from `#derive("debug")` during lowering.
+        Ok(None)
+    };
+
+    derived_serialize := (T: Type(), value: ty(T), buf: ref_ty(u8_gen_ty(), RefKind::Raw, false), offset: usize_gen_ty()) -> usize_gen_ty() => |env| {
+        // The encoder is synthesised from `#derive("serialize")` during lowering.
+        Ok(None)
+    };
+
+    derived_deserialize := (T: Type(), buf: ref_ty(u8_gen_ty(), RefKind::Raw, false), offset: usize_gen_ty(), out: ref_ty(ty(T), RefKind::Local, true)) -> usize_gen_ty() => |env| {
+        // The decoder is synthesised from `#derive("serialize")` during lowering.
+        Ok(None)
+    };
+
     memcpy := (dest: ref_ty(u8_gen_ty(), RefKind::Raw, false), src: ref_ty(u8_gen_ty(), RefKind::Raw, false), len: usize_gen_ty()) -> ref_ty(u8_gen_ty(), RefKind::Raw, false) => |env| {
         // @@Todo: actually perform memcpy (Probably on an `AllocId`).
         Ok(None)
@@ -166,6 +226,23 @@ make_intrinsics! {
     };
 }
 
+impl Intrinsic {
+    /// Whether the intrinsic performs an operation that can violate the
+    /// memory rules of the language, and so should only be used from
+    /// within an `unsafe` block.
+    pub fn is_unsafe(&self) -> bool {
+        matches!(
+            self,
+            Intrinsic::PtrOffset
+                | Intrinsic::Read
+                | Intrinsic::Write
+                | Intrinsic::Transmute
+                | Intrinsic::DerivedSerialize
+                | Intrinsic::DerivedDeserialize
+        )
+    }
+}
+
 make_primitives! {
     bool := data (true: bool, false: bool);
 
@@ -206,7 +283,7 @@ make_primitives! {
     i32 := primitive (numeric_ctors(32, NumericCtorFlags::IS_SIGNED));
     i64 := primitive (numeric_ctors(64, NumericCtorFlags::IS_SIGNED));
     i128 := primitive (numeric_ctors(128, NumericCtorFlags::IS_SIGNED));
-    isize := primitive (numeric_ctors(64, NumericCtorFlags::IS_SIGNED & NumericCtorFlags::IS_PLATFORM));
+    isize := primitive (numeric_ctors(64, NumericCtorFlags::IS_SIGNED | NumericCtorFlags::IS_PLATFORM));
     ibig := primitive (numeric_ctors(0, NumericCtorFlags::IS_SIGNED));
 
     u8 := primitive (numeric_ctors(8, NumericCtorFlags::empty()));
@@ -11,7 +11,9 @@ pub mod atom_info;
 pub mod building;
 pub mod context;
 pub mod dump;
+pub mod index;
 pub mod intrinsics;
+pub mod scopes;
 pub mod stack;
 pub mod stores;
 pub mod sub;
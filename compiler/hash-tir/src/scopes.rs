@@ -0,0 +1,379 @@
+//! Utilities for visualising the scopes that are entered whilst the TIR is
+//! being typechecked. The scope tree of a module is reconstructed from the
+//! TIR by following the same structure that the typechecker uses, i.e.
+//! modules contain definitions, functions and data definitions bind their
+//! parameters, and each block term introduces a stack scope.
+//!
+//! This is used by the `hashc scopes` developer command in order to debug
+//! name resolution issues, for example when a binding unexpectedly shadows
+//! another binding.
+
+use std::{fmt, ops::ControlFlow};
+
+use hash_source::{identifier::Identifier, location::Span};
+use hash_storage::store::{statics::StoreId, TrivialSequenceStoreKey};
+
+use crate::{
+    context::ScopeKind,
+    tir::{
+        DataDefCtors, DataDefId, FnDefId, HasAstNodeId, ModDefId, ModMemberValue, ParamsId,
+        SymbolId, Term, TermId,
+    },
+    visitor::{Atom, Visit, Visitor},
+};
+
+/// A member that is bound within a scope of the [ScopeTree].
+#[derive(Debug, Clone, Copy)]
+pub struct ScopeMember {
+    /// The name of the member.
+    pub name: SymbolId,
+
+    /// Where the member is defined, if it originates from the source.
+    pub span: Option<Span>,
+}
+
+impl ScopeMember {
+    /// Create a new [ScopeMember], the span of the binding is preferred
+    /// over the given `fallback` span.
+    fn new(name: SymbolId, fallback: Option<Span>) -> Self {
+        Self { name, span: name.span().or(fallback) }
+    }
+}
+
+/// A single scope within the [ScopeTree].
+#[derive(Debug, Clone)]
+pub struct ScopeNode {
+    /// The kind of the scope.
+    pub kind: ScopeKind,
+
+    /// Where the scope is defined, if it originates from the source.
+    pub span: Option<Span>,
+
+    /// The members that are bound by the scope, in the order that they
+    /// are bound.
+    pub members: Vec<ScopeMember>,
+
+    /// Any scopes that are nested within this scope.
+    pub children: Vec<ScopeNode>,
+}
+
+impl ScopeNode {
+    /// Create the scope of a module definition.
+    fn mod_def(mod_def: ModDefId) -> Self {
+        let mut node = Self::empty(ScopeKind::Mod(mod_def), mod_def.span());
+        node.add_mod_members(mod_def);
+        node
+    }
+
+    /// Create the scope of a function definition, the parameters of the
+    /// function are bound by the scope.
+    fn fn_def(fn_def: FnDefId) -> Self {
+        let fn_def_value = fn_def.value();
+        let mut node = Self::empty(ScopeKind::Fn(fn_def), fn_def.span());
+
+        node.add_params(fn_def_value.ty.params);
+        node.children = Self::nested_scopes(fn_def_value.body, None);
+        node
+    }
+
+    /// Create the scope of a data definition, each constructor of the data
+    /// definition introduces a nested scope for its own parameters.
+    fn data_def(data_def: DataDefId) -> Self {
+        let data_def_value = data_def.value();
+        let mut node = Self::empty(ScopeKind::Data(data_def), data_def.span());
+        node.add_params(data_def_value.params);
+
+        if let DataDefCtors::Defined(ctors) = data_def_value.ctors {
+            for ctor in ctors.iter() {
+                let mut child = Self::empty(ScopeKind::Ctor(ctor), ctor.span());
+                child.add_params(ctor.borrow().params);
+                node.children.push(child);
+            }
+        }
+
+        node
+    }
+
+    /// Create the scope that is introduced by the block `term`.
+    fn block(term: TermId) -> Option<Self> {
+        let Term::Block(block) = *term.value() else {
+            return None;
+        };
+
+        let stack = block.stack_id.value();
+        let mut node = Self::empty(ScopeKind::Stack(block.stack_id), term.span());
+
+        if let Some(local_mod_def) = stack.local_mod_def {
+            node.add_mod_members(local_mod_def);
+        }
+
+        node.members.extend(stack.members.iter().map(|member| {
+            ScopeMember::new(member.name, member.value.and_then(|value| value.span()))
+        }));
+        // Functions that are members of the local module definition are
+        // also found when visiting the block, avoid adding them twice.
+        for child in Self::nested_scopes(term, Some(term)) {
+            if let ScopeKind::Fn(fn_def) = child.kind
+                && node
+                    .children
+                    .iter()
+                    .any(|other| matches!(other.kind, ScopeKind::Fn(other) if other == fn_def))
+            {
+                continue;
+            }
+
+            node.children.push(child);
+        }
+
+        Some(node)
+    }
+
+    fn empty(kind: ScopeKind, span: Option<Span>) -> Self {
+        Self { kind, span, members: vec![], children: vec![] }
+    }
+
+    /// Add the members of the given module to the scope, any member that
+    /// is itself a definition also introduces a nested scope.
+    fn add_mod_members(&mut self, mod_def: ModDefId) {
+        for member in mod_def.borrow().members.iter() {
+            let member = member.value();
+            let child = match member.value {
+                ModMemberValue::Data(data_def) => Some(Self::data_def(data_def)),
+                ModMemberValue::Mod(mod_def) => Some(Self::mod_def(mod_def)),
+                ModMemberValue::Fn(fn_def) => Some(Self::fn_def(fn_def)),
+                ModMemberValue::Intrinsic(_) => None,
+            };
+
+            self.members
+                .push(ScopeMember::new(member.name, child.as_ref().and_then(|child| child.span)));
+            self.children.extend(child);
+        }
+    }
+
+    fn add_params(&mut self, params: ParamsId) {
+        self.members
+            .extend(params.iter().map(|param| ScopeMember::new(param.borrow().name, param.span())));
+    }
+
+    /// Find all of the scopes that are directly nested within the given
+    /// `term`. The `skip` term is the term that introduced the current
+    /// scope, and so it is not considered as a nested scope.
+    ///
+    /// Functions that are only referred to by the `term`, i.e. a recursive
+    /// call, are not nested scopes, since they are not defined within it.
+    fn nested_scopes(term: TermId, skip: Option<TermId>) -> Vec<Self> {
+        let mut scopes = vec![];
+        let outer = term.span();
+
+        Visitor::new().visit(term, &mut |atom: Atom| match atom {
+            Atom::FnDef(fn_def) => {
+                if is_within(fn_def.span(), outer) {
+                    scopes.push(Self::fn_def(fn_def));
+                }
+
+                ControlFlow::Break(())
+            }
+            Atom::Term(term) if Some(term) != skip => match Self::block(term) {
+                Some(block) => {
+                    scopes.push(block);
+                    ControlFlow::Break(())
+                }
+                None => ControlFlow::Continue(()),
+            },
+            Atom::Term(_) | Atom::Lit(_) | Atom::Pat(_) => ControlFlow::Continue(()),
+        });
+
+        scopes
+    }
+
+    /// Get a short label that describes the scope.
+    fn label(&self) -> String {
+        match self.kind {
+            ScopeKind::Ctor(ctor) => format!("ctor {}", ctor.borrow().name),
+            ScopeKind::Stack(_) => "stack".to_string(),
+            kind => kind.to_string(),
+        }
+    }
+}
+
+/// The tree of scopes that are introduced by a module.
+#[derive(Debug, Clone)]
+pub struct ScopeTree {
+    /// The scope of the module itself.
+    pub root: ScopeNode,
+}
+
+impl ScopeTree {
+    /// Reconstruct the [ScopeTree] of the given module definition.
+    pub fn new(mod_def: ModDefId) -> Self {
+        Self { root: ScopeNode::mod_def(mod_def) }
+    }
+
+    /// Walk over all of the scopes within the tree in pre-order, for each
+    /// member of a scope, `f` receives the member that it shadows from an
+    /// enclosing scope or from earlier within the same scope, if any.
+    fn walk(&self, f: &mut impl FnMut(ScopeEvent<'_>) -> fmt::Result) -> fmt::Result {
+        fn walk_node(
+            node: &ScopeNode,
+            parent: Option<usize>,
+            depth: usize,
+            counter: &mut usize,
+            visible: &mut Vec<(Identifier, ScopeMember)>,
+            f: &mut impl FnMut(ScopeEvent<'_>) -> fmt::Result,
+        ) -> fmt::Result {
+            let index = *counter;
+            *counter += 1;
+
+            f(ScopeEvent::Enter { node, index, parent, depth })?;
+
+            let visible_len = visible.len();
+
+            for member in &node.members {
+                let name = member.name.value().name;
+                let shadows = name.and_then(|name| {
+                    visible
+                        .iter()
+                        .rev()
+                        .find(|(other, binding)| *other == name && binding.name != member.name)
+                        .map(|(_, binding)| *binding)
+                });
+
+                f(ScopeEvent::Member { member, shadows, depth })?;
+
+                if let Some(name) = name {
+                    visible.push((name, *member));
+                }
+            }
+
+            for child in &node.children {
+                walk_node(child, Some(index), depth + 1, counter, visible, f)?;
+            }
+
+            visible.truncate(visible_len);
+            f(ScopeEvent::Exit { index })
+        }
+
+        walk_node(&self.root, None, 0, &mut 0, &mut vec![], f)
+    }
+
+    /// Write the [ScopeTree] as indented text.
+    pub fn write_tree(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        self.walk(&mut |event| match event {
+            ScopeEvent::Enter { node, depth, .. } => {
+                writeln!(
+                    f,
+                    "{:indent$}{}{}",
+                    "",
+                    node.label(),
+                    fmt_span(node.span),
+                    indent = depth * 2
+                )
+            }
+            ScopeEvent::Member { member, shadows, depth } => {
+                write!(
+                    f,
+                    "{:indent$}- {}{}",
+                    "",
+                    member.name,
+                    fmt_span(member.span),
+                    indent = depth * 2 + 2
+                )?;
+
+                if let Some(shadows) = shadows {
+                    write!(f, ", shadows {}{}", shadows.name, fmt_span(shadows.span))?;
+                }
+
+                writeln!(f)
+            }
+            ScopeEvent::Exit { .. } => Ok(()),
+        })
+    }
+
+    /// Write the [ScopeTree] as a `graphviz` graph, each scope is a
+    /// node that lists its members and has an edge to each of its nested
+    /// scopes.
+    pub fn write_graph(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut label = String::new();
+
+        writeln!(f, "digraph scopes {{")?;
+        writeln!(f, "    node [shape=record, fontname=\"monospace\"];")?;
+
+        self.walk(&mut |event| match event {
+            ScopeEvent::Enter { node, index, parent, .. } => {
+                if let Some(parent) = parent {
+                    writeln!(f, "    s{parent} -> s{index};")?;
+                }
+
+                label = escape_label(&format!("{}{}", node.label(), fmt_span(node.span)));
+                Ok(())
+            }
+            ScopeEvent::Member { member, shadows, .. } => {
+                let mut line = format!("{}{}", member.name, fmt_span(member.span));
+
+                if let Some(shadows) = shadows {
+                    line.push_str(&format!(", shadows {}{}", shadows.name, fmt_span(shadows.span)));
+                }
+
+                label.push_str(&format!("|{}\\l", escape_label(&line)));
+                Ok(())
+            }
+            ScopeEvent::Exit { index } => {
+                writeln!(f, "    s{index} [label=\"{{{label}}}\"];")
+            }
+        })?;
+
+        writeln!(f, "}}")
+    }
+}
+
+/// An event that occurs whilst walking over the [ScopeTree].
+enum ScopeEvent<'a> {
+    /// Entering a scope, `parent` is the index of the enclosing scope.
+    Enter { node: &'a ScopeNode, index: usize, parent: Option<usize>, depth: usize },
+
+    /// A member of the current scope, and the member that it shadows.
+    Member { member: &'a ScopeMember, shadows: Option<ScopeMember>, depth: usize },
+
+    /// Exiting the scope at `index`, this occurs after all of the nested
+    /// scopes have been exited.
+    Exit { index: usize },
+}
+
+/// Check whether the `inner` span is contained within the `outer` span.
+fn is_within(inner: Option<Span>, outer: Option<Span>) -> bool {
+    match (inner, outer) {
+        (Some(inner), Some(outer)) => {
+            inner.id == outer.id
+                && outer.range.start() <= inner.range.start()
+                && inner.range.end() <= outer.range.end()
+        }
+        _ => false,
+    }
+}
+
+/// Format an optional [Span] as a location suffix.
+fn fmt_span(span: Option<Span>) -> String {
+    span.map(|span| format!(" @ {}", span.fmt_range())).unwrap_or_default()
+}
+
+/// Escape any characters that have a special meaning within a `graphviz`
+/// record label.
+fn escape_label(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+
+    for ch in label.chars() {
+        if matches!(ch, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+            escaped.push('\\');
+        }
+
+        escaped.push(ch);
+    }
+
+    escaped
+}
+
+impl fmt::Display for ScopeTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_tree(f)
+    }
+}
@@ -3,7 +3,7 @@
 //! of nodes.
 use std::sync::OnceLock;
 
-use hash_storage::stores;
+use hash_storage::{store::register_store_sizes, stores};
 
 use crate::{
     atom_info::AtomInfoStore,
@@ -58,7 +58,10 @@ static STORES: OnceLock<Stores> = OnceLock::new();
 
 /// Access the global [`Stores`] instance.
 pub fn tir_stores() -> &'static Stores {
-    STORES.get_or_init(Stores::new)
+    STORES.get_or_init(|| {
+        register_store_sizes("tir", || tir_stores().sizes());
+        Stores::new()
+    })
 }
 
 // Below are some helper macros for defining TIR nodes:
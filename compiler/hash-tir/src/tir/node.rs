@@ -6,7 +6,7 @@
 //!
 //! Nodes normally live in stores, which can be created through the
 //! `crate::stores::tir_node_*` macros.
-use core::fmt;
+use core::{cmp::Ordering, fmt};
 
 use hash_ast::ast::AstNodeId;
 use hash_source::{location::Span, SourceId};
@@ -39,6 +39,19 @@ pub trait HasAstNodeId {
     fn source(&self) -> Option<SourceId> {
         self.node_id().map(|n| n.source())
     }
+
+    /// Compare the source position of this node with another node, nodes
+    /// that don't originate from the source are ordered last. This should
+    /// be used to order nodes in any user-facing output, since the order of
+    /// the stores depends on the order in which sources were analysed.
+    fn cmp_position(&self, other: &Self) -> Ordering {
+        match (self.span(), other.span()) {
+            (Some(a), Some(b)) => a.cmp_position(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
 }
 
 impl<T: HasAstNodeId> HasAstNodeId for &T {
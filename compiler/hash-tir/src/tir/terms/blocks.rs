@@ -19,11 +19,17 @@ use crate::{
 ///
 /// Depending on the `bind_pat` used, this can be used to declare a single or
 /// multiple variables.
+///
+/// If the `bind_pat` is refutable, then `otherwise` is the diverging term that
+/// is evaluated when the `value` doesn't match the pattern. A declaration
+/// without a `value` leaves the variables uninitialised until they are
+/// assigned to.
 #[derive(Debug, Clone, Copy)]
 pub struct Decl {
     pub bind_pat: PatId,
     pub ty: TyId,
-    pub value: TermId,
+    pub value: Option<TermId>,
+    pub otherwise: Option<TermId>,
 }
 
 /// A statement in a block.
@@ -49,21 +55,28 @@ pub struct BlockTerm {
 
 impl fmt::Display for Decl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let value = match *self.bind_pat.value() {
-            Pat::Binding(binding_pat) => {
-                match *self.value.value() {
-                    // If a function is being declared, print the body, otherwise just
-                    // its name.
-                    Term::Fn(fn_def_id) if fn_def_id.map(|def| def.name == binding_pat.name) => {
-                        fn_def_id.to_string()
-                    }
-                    _ => self.value.to_string(),
+        write!(f, "{}: {}", self.bind_pat, self.ty)?;
+
+        if let Some(value) = self.value {
+            let value = match (*self.bind_pat.value(), *value.value()) {
+                // If a function is being declared, print the body, otherwise just
+                // its name.
+                (Pat::Binding(binding_pat), Term::Fn(fn_def_id))
+                    if fn_def_id.map(|def| def.name == binding_pat.name) =>
+                {
+                    fn_def_id.to_string()
                 }
-            }
-            _ => self.value.to_string(),
-        };
+                _ => value.to_string(),
+            };
+
+            write!(f, " = {}", value)?;
+        }
+
+        if let Some(otherwise) = self.otherwise {
+            write!(f, " else {}", otherwise)?;
+        }
 
-        write!(f, "{}: {} = {}", self.bind_pat, self.ty, value,)
+        Ok(())
     }
 }
 
@@ -2,7 +2,8 @@
 
 use std::fmt::Display;
 
-use hash_storage::store::statics::StoreId;
+use hash_storage::store::{statics::StoreId, SequenceStoreKey};
+use hash_target::abi::Abi;
 use typed_builder::TypedBuilder;
 
 use crate::{
@@ -42,6 +43,20 @@ pub struct FnTy {
     /// marked as unsafe.
     #[builder(default = false)]
     pub is_unsafe: bool,
+    /// Whether the function is C-variadic.
+    ///
+    /// C-variadic functions look like `(a: A, ...) -> B`, and can be called
+    /// with any number of additional arguments after `a`. Only `#foreign`
+    /// functions can be C-variadic.
+    #[builder(default = false)]
+    pub is_c_variadic: bool,
+    /// The calling convention of the function.
+    ///
+    /// This is given by the `#convention(...)` attribute of a function
+    /// definition, and is [`Abi::C`] for `#foreign` functions. Functions can
+    /// only be used in place of each other if their conventions match.
+    #[builder(default = Abi::Hash)]
+    pub abi: Abi,
     /// The parameters of the function.
     pub params: ParamsId,
     /// The return type of the function.
@@ -96,6 +111,9 @@ pub struct CallTerm {
 
 impl Display for FnTy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.abi != Abi::Hash {
+            write!(f, "#convention(\"{}\") ", self.abi)?;
+        }
         if self.is_unsafe {
             write!(f, "unsafe ")?;
         }
@@ -114,6 +132,14 @@ impl Display for FnTy {
 
         write!(f, "{}", self.params)?;
 
+        if self.is_c_variadic {
+            if self.params.is_empty() {
+                write!(f, "...")?;
+            } else {
+                write!(f, ", ...")?;
+            }
+        }
+
         if self.implicit {
             write!(f, ">")?;
         } else {
@@ -24,3 +24,20 @@ impl fmt::Display for Hole {
         write!(f, "h{}", self.0)
     }
 }
+
+/// A typed hole, which is written by the user as `?` or `_` in place of a
+/// term.
+///
+/// Unlike a [Hole], a typed hole is never filled by the type checker. Instead,
+/// checking a typed hole always fails and reports the type that is expected in
+/// its place, along with the types of the bindings that are in scope. This is
+/// useful for type-directed development, since a program can be written
+/// incrementally by replacing typed holes with terms of the reported type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TypedHole;
+
+impl fmt::Display for TypedHole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "?")
+    }
+}
@@ -149,6 +149,7 @@ pub enum Term {
 
     /// Holes
     Hole(Hole),
+    TypedHole(TypedHole),
 
     /// Intrinsics
     Intrinsic(Intrinsic),
@@ -316,6 +317,7 @@ impl fmt::Display for Term {
             Term::Ref(ref_term) => write!(f, "{}", ref_term),
             Term::Deref(deref_term) => write!(f, "{}", deref_term),
             Term::Hole(hole) => write!(f, "{}", *hole),
+            Term::TypedHole(typed_hole) => write!(f, "{}", typed_hole),
             Term::Index(index) => {
                 write!(f, "{}", index)
             }
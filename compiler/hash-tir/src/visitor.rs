@@ -277,7 +277,7 @@ impl Visit<TermId> for Visitor {
                 Term::TyOf(type_of_term) => self.try_visit(type_of_term.term, f),
                 Term::Ref(ref_term) => self.try_visit(ref_term.subject, f),
                 Term::Deref(deref_term) => self.try_visit(deref_term.subject, f),
-                Term::Hole(_) => Ok(()),
+                Term::Hole(_) | Term::TypedHole(_) => Ok(()),
                 Term::Intrinsic(_) => Ok(()),
                 Ty::TupleTy(tuple_ty) => self.try_visit(tuple_ty.data, f),
                 Ty::FnTy(fn_ty) => {
@@ -423,6 +423,7 @@ impl Map<TermId> for Visitor {
                     Ok(Term::from(DerefTerm { subject }, origin))
                 }
                 Term::Hole(hole_term) => Ok(Term::from(hole_term, origin)),
+                Term::TypedHole(typed_hole) => Ok(Term::from(typed_hole, origin)),
                 Term::Intrinsic(intrinsic) => Ok(Term::from(intrinsic, origin)),
                 Ty::TupleTy(tuple_ty) => {
                     let data = self.try_map(tuple_ty.data, f)?;
@@ -437,6 +438,8 @@ impl Map<TermId> for Visitor {
                             return_ty,
                             implicit: fn_ty.implicit,
                             is_unsafe: fn_ty.is_unsafe,
+                            is_c_variadic: fn_ty.is_c_variadic,
+                            abi: fn_ty.abi,
                             pure: fn_ty.pure,
                         },
                         origin,
@@ -542,7 +545,12 @@ impl Visit<BlockStatementsId> for Visitor {
                 BlockStatement::Decl(decl) => {
                     self.try_visit(decl.bind_pat, f)?;
                     self.try_visit(decl.ty, f)?;
-                    self.try_visit(decl.value, f)?;
+                    if let Some(value) = decl.value {
+                        self.try_visit(value, f)?;
+                    }
+                    if let Some(otherwise) = decl.otherwise {
+                        self.try_visit(otherwise, f)?;
+                    }
                 }
                 BlockStatement::Expr(expr) => {
                     self.try_visit(expr, f)?;
@@ -564,9 +572,11 @@ impl Map<BlockStatementsId> for Visitor {
                 BlockStatement::Decl(decl) => {
                     let bind_pat = self.try_map(decl.bind_pat, f)?;
                     let ty = self.try_map(decl.ty, f)?;
-                    let value = self.try_map(decl.value, f)?;
+                    let value = decl.value.map(|value| self.try_map(value, f)).transpose()?;
+                    let otherwise =
+                        decl.otherwise.map(|otherwise| self.try_map(otherwise, f)).transpose()?;
                     new_list.push(Node::at(
-                        BlockStatement::Decl(Decl { ty, bind_pat, value }),
+                        BlockStatement::Decl(Decl { ty, bind_pat, value, otherwise }),
                         statement.origin,
                     ));
                 }
@@ -769,6 +779,8 @@ impl Map<FnDefId> for Visitor {
                             return_ty,
                             implicit: fn_def.ty.implicit,
                             is_unsafe: fn_def.ty.is_unsafe,
+                            is_c_variadic: fn_def.ty.is_c_variadic,
+                            abi: fn_def.ty.abi,
                             pure: fn_def.ty.pure,
                         },
                         body,
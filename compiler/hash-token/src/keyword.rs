@@ -34,6 +34,7 @@ pub enum Keyword {
     Impl,
     True,
     Type,
+    Rc,
 }
 
 impl fmt::Display for Keyword {
@@ -70,6 +71,7 @@ static KEYWORDS: phf::Map<&'static str, Keyword> = phf_map! {
     "mod" => Keyword::Mod,
     "impl" => Keyword::Impl,
     "type" => Keyword::Type,
+    "rc" => Keyword::Rc,
 };
 
 #[inline(always)]
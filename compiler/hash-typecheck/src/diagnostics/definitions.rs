@@ -1,11 +1,13 @@
 //! Definitions for errors and warnings that can occur during typechecking.
 
 use hash_ast_utils::lit::LitParseError;
+use hash_attrs::attr::DeriveAttr;
+use hash_ir::ir::UnsafeOpKind;
 use hash_reporting::diagnostic::IntoCompound;
 use hash_tir::{
     tir::{
         fns::FnDefId, NodeOrigin, ParamError, ParamIndex, ParamsId, PatId, SomeParamsOrArgsId,
-        TermId, TyId,
+        SymbolId, TermId, TyId,
     },
     visitor::Atom,
 };
@@ -22,6 +24,14 @@ pub enum WrongTermKind {
     NotARecord,
     /// Cannot index because the term is not an array.
     NotAnArray,
+    /// Cannot inspect the variants of a type because it is not an enum.
+    NotAnEnum,
+    /// Cannot create a value of an enum from a discriminant because some of
+    /// its variants have fields.
+    NotAFieldlessEnum,
+    /// Cannot use a derived item of a type because the type does not derive
+    /// it.
+    NotDerived { derive: DeriveAttr },
     /// Cannot use the given term because it is not of the correct type.
     NotOfType { correct_ty: TyId },
 }
@@ -93,6 +103,15 @@ pub enum TcError {
     /// local variables in the block.
     TryingToReferenceLocalsInType { ty: TyId },
 
+    /// The `else` block of a declaration doesn't diverge, `ty` is the type
+    /// that it was inferred to have.
+    NonDivergingDeclarationElse { term: TermId, ty: TyId },
+
+    /// A typed hole was found, `expected` is the type that the hole should
+    /// be filled with, and `bindings` are the typed bindings that are in
+    /// scope at the hole.
+    TypedHole { term: TermId, expected: TyId, bindings: Vec<(SymbolId, TyId)> },
+
     /// An error related to argument/parameter matching.
     #[from]
     ParamMatch(ParamError),
@@ -105,6 +124,10 @@ pub enum TcError {
     /// An error that occurred in an intrinsic.
     /// This is a custom error message determined by the intrinsic.
     Intrinsic(String),
+
+    /// An operation that requires an `unsafe` block was used outside
+    /// of an `unsafe` block.
+    UnsafeOperation { kind: UnsafeOpKind, term: TermId },
 }
 
 impl IntoCompound for TcError {
@@ -112,3 +135,11 @@ impl IntoCompound for TcError {
         TcError::Compound { errors }
     }
 }
+
+/// A warning that occurs during typechecking.
+#[derive(Clone, Debug)]
+pub enum TcWarning {
+    /// An `unsafe` block that does not contain any unsafe operations, or
+    /// one that is `nested` within another `unsafe` block.
+    UnusedUnsafe { term: TermId, nested: bool },
+}
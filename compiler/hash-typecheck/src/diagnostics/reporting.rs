@@ -5,14 +5,14 @@
 use std::fmt;
 
 use hash_reporting::{
-    hash_error_codes::error_codes::HashErrorCode,
+    hash_error_codes::{error_codes::HashErrorCode, warning_codes::HashWarningCode},
     reporter::{Reporter, Reports},
 };
-use hash_storage::store::SequenceStoreKey;
-use hash_tir::tir::{HasAstNodeId, NodeId, NodeOrigin, ParamError, SomeParamsOrArgsId};
+use hash_storage::store::{statics::StoreId, SequenceStoreKey};
+use hash_tir::tir::{HasAstNodeId, NodeId, NodeOrigin, ParamError, SomeParamsOrArgsId, Ty};
 
 use super::definitions::WrongTermKind;
-use crate::diagnostics::definitions::TcError;
+use crate::diagnostics::definitions::{TcError, TcWarning};
 
 /// Unit struct that contains the typechecking reporting implementation.
 pub struct TcReporter;
@@ -37,7 +37,10 @@ impl TcReporter {
         match error {
             TcError::Signal => {}
             TcError::Blocked(location) => {
-                let error = reporter.error().title("blocked while typechecking".to_string());
+                let error = reporter
+                    .error()
+                    .code(HashErrorCode::BlockedTypechecking)
+                    .title("blocked while typechecking".to_string());
 
                 if let Some(location) = location.span() {
                     error.add_span(location);
@@ -146,6 +149,13 @@ impl TcReporter {
                     WrongTermKind::NotAFunction => "function".to_string(),
                     WrongTermKind::NotARecord => "record".to_string(),
                     WrongTermKind::NotAnArray => "array".to_string(),
+                    WrongTermKind::NotAnEnum => "enum".to_string(),
+                    WrongTermKind::NotAFieldlessEnum => {
+                        "enum whose variants have no fields".to_string()
+                    }
+                    WrongTermKind::NotDerived { derive } => {
+                        format!("type which derives `{derive}`")
+                    }
                     WrongTermKind::NotOfType { correct_ty } => {
                         format!("value of type `{}`", *correct_ty)
                     }
@@ -229,6 +239,18 @@ impl TcReporter {
             TcError::Intrinsic(msg) => {
                 let _error = reporter.error().code(HashErrorCode::TypeMismatch).title(msg);
             }
+            TcError::UnsafeOperation { kind, term } => {
+                let error = reporter
+                    .error()
+                    .code(HashErrorCode::UnsafeOperationOutsideUnsafe)
+                    .title(format!("{kind} is unsafe and requires an `unsafe` block"));
+
+                if let Some(location) = term.span() {
+                    error.add_labelled_span(location, format!("{kind} occurs here"));
+                }
+
+                error.add_help("wrap the expression in an `unsafe { ... }` block");
+            }
             TcError::MismatchingArrayLengths { expected_len, got_len } => {
                 let error =
                     reporter.error().code(HashErrorCode::ParameterLengthMismatch).title(format!(
@@ -318,6 +340,88 @@ impl TcReporter {
                     error.add_labelled_span(location, "type containing locals");
                 }
             }
+            TcError::NonDivergingDeclarationElse { term, ty } => {
+                let error = reporter
+                    .error()
+                    .code(HashErrorCode::TypeMismatch)
+                    .title("the `else` block of a declaration must diverge");
+                if let Some(location) = term.span() {
+                    error.add_labelled_span(
+                        location,
+                        format!("this is of type `{}` instead of `never`", *ty),
+                    );
+                }
+                error.add_note(
+                    "the `else` block is run if the value doesn't match the pattern, so it \
+                    must `return`, `break`, `continue` or `panic`",
+                );
+            }
+            TcError::TypedHole { term, expected, bindings } => {
+                let expected_is_known = !matches!(*expected.value(), Ty::Hole(_));
+                let title = if expected_is_known {
+                    format!("found hole of type `{}`", *expected)
+                } else {
+                    "found hole of unknown type".to_string()
+                };
+
+                let error = reporter.error().code(HashErrorCode::TypedHole).title(title);
+
+                if let Some(location) = term.span() {
+                    if expected_is_known {
+                        error.add_labelled_span(
+                            location,
+                            format!("replace this hole with a term of type `{}`", *expected),
+                        );
+                    } else {
+                        error.add_labelled_span(
+                            location,
+                            "the type of this hole could not be inferred",
+                        );
+                    }
+                }
+
+                if bindings.is_empty() {
+                    error.add_info("there are no bindings in scope");
+                } else {
+                    let bindings = bindings
+                        .iter()
+                        .map(|(name, ty)| format!("  {}: {}", *name, *ty))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    error.add_info(format!("bindings in scope:\n{bindings}"));
+                }
+            }
+        }
+    }
+
+    /// Format the warning nicely and return it as a set of reports.
+    pub fn format_warning(warning: &TcWarning) -> Reports {
+        let mut builder = Reporter::new();
+        Self::add_warning_to_reporter(warning, &mut builder);
+        builder.into_reports()
+    }
+
+    /// Format the warning nicely and add it to the given reporter.
+    pub fn add_warning_to_reporter(warning: &TcWarning, reporter: &mut Reporter) {
+        match warning {
+            TcWarning::UnusedUnsafe { term, nested } => {
+                let warning = reporter
+                    .warning()
+                    .code(HashWarningCode::UnusedUnsafe)
+                    .title("unnecessary `unsafe` block");
+
+                let label = if *nested {
+                    "this block is already within an `unsafe` block"
+                } else {
+                    "this block contains no unsafe operations"
+                };
+
+                if let Some(location) = term.span() {
+                    warning.add_labelled_span(location, label);
+                }
+
+                warning.add_help("remove the `unsafe` block");
+            }
         }
     }
 }
@@ -1,6 +1,6 @@
 use hash_exhaustiveness::diagnostics::{ExhaustivenessError, ExhaustivenessWarning};
 use hash_ir::HasIrCtx;
-use hash_pipeline::settings::HasCompilerSettings;
+use hash_pipeline::settings::{HasCompilerSettings, TraceTarget};
 use hash_reporting::diagnostic::{Diagnostics, HasDiagnostics};
 use hash_repr::HasLayout;
 use hash_source::{entry_point::EntryPointState, SourceId};
@@ -10,17 +10,18 @@ use hash_tir_utils::lower::{HasTyCache, TyLowerEnv};
 use hash_utils::{profiling::HasMetrics, state::LightState};
 
 use crate::{
-    diagnostics::TcError,
+    diagnostics::{TcError, TcWarning},
     options::{normalisation::NormalisationOptions, unification::UnificationOptions},
-    tc::{FnInferMode, Tc},
+    tc::{FnInferMode, Tc, UnsafeMode},
+    utils::tracing::UnificationTracer,
 };
 
 /// A wrapper trait around `HasDiagnostics` for specifically diagnostics that
 /// can accomodate `TcError`s, `ExhaustivenessError`s and
-/// `ExhaustivenessWarning`s. (and `TcWarning`s in the future.)
+/// `ExhaustivenessWarning`s and `TcWarning`s.
 pub trait HasTcDiagnostics: HasDiagnostics<Diagnostics = Self::TcDiagnostics> {
     type ForeignError: From<TcError> + From<ExhaustivenessError>;
-    type ForeignWarning: From<ExhaustivenessWarning>;
+    type ForeignWarning: From<ExhaustivenessWarning> + From<TcWarning>;
     type TcDiagnostics: Diagnostics<Error = Self::ForeignError, Warning = Self::ForeignWarning>;
 }
 
@@ -45,19 +46,36 @@ pub trait TcEnv:
     /// The current source ID.
     fn current_source(&self) -> SourceId;
 
+    /// Whether the typechecker should stop before checking any further
+    /// modules, i.e. when the compilation has been cancelled, or when it has
+    /// exceeded its memory limit.
+    fn should_stop(&self) -> bool {
+        false
+    }
+
     /// Whether the typechecker should monomorphise all pure functions.
     fn should_monomorphise(&self) -> bool {
         self.settings().semantic_settings.mono_tir
     }
 
+    /// Whether the typechecker should trace unifications, the prelude is
+    /// not traced if it is quiet.
+    fn should_trace(&self) -> bool {
+        let settings = self.settings();
+        settings.is_tracing(TraceTarget::Tc)
+            && !(settings.prelude_is_quiet && self.current_source().is_prelude())
+    }
+
     /// Create a new typechecker using the given context.
     fn checker<'a>(&'a self, context: &'a Context) -> Tc<Self> {
         Tc {
             env: self,
             context,
             fn_infer_mode: LightState::new(FnInferMode::Body),
+            unsafe_mode: LightState::new(UnsafeMode::Safe),
             unification_opts: UnificationOptions::default(),
             normalisation_opts: NormalisationOptions::default(),
+            tracer: UnificationTracer::new(self.should_trace()),
         }
     }
 }
@@ -48,15 +48,40 @@ impl<E: TcEnv> OperationsOn<BlockTerm> for Tc<'_, E> {
                 let ty_to_check_divergence = match *statement.value() {
                     BlockStatement::Decl(decl) => {
                         self.check_ty(decl.ty)?;
-                        self.check_node(decl.value, decl.ty)?;
-                        self.check_node(decl.bind_pat, (decl.ty, Some(decl.value)))?;
+                        if let Some(value) = decl.value {
+                            self.check_node(value, decl.ty)?;
+                        }
+                        self.check_node(decl.bind_pat, (decl.ty, decl.value))?;
 
-                        // Check that the binding pattern of the declaration is irrefutable.
                         let mut eck = self.exhaustiveness_checker(decl.bind_pat);
 
-                        self.env.record("exhaustiveness", |_| {
-                            eck.is_pat_irrefutable(&[decl.bind_pat], decl.ty, None)
-                        });
+                        match decl.otherwise {
+                            // If the declaration has an `else` block, then the pattern
+                            // may be refutable, but the block must diverge.
+                            Some(otherwise) => {
+                                let otherwise_ty = Ty::hole_for(otherwise);
+                                self.check_node(otherwise, otherwise_ty)?;
+
+                                if !self.is_uninhabitable(otherwise_ty)? {
+                                    return Err(TcError::NonDivergingDeclarationElse {
+                                        term: otherwise,
+                                        ty: otherwise_ty,
+                                    });
+                                }
+
+                                self.env.record("exhaustiveness", |_| {
+                                    eck.is_declaration_pat_refutable(decl.bind_pat, decl.ty)
+                                });
+                            }
+                            // Otherwise, check that the binding pattern of the
+                            // declaration is irrefutable.
+                            None => {
+                                self.env.record("exhaustiveness", |_| {
+                                    eck.is_pat_irrefutable(&[decl.bind_pat], decl.ty, None)
+                                });
+                            }
+                        }
+
                         self.append_exhaustiveness_diagnostics(eck);
 
                         decl.ty
@@ -77,6 +102,11 @@ impl<E: TcEnv> OperationsOn<BlockTerm> for Tc<'_, E> {
             if diverges {
                 match *annotation_ty.value() {
                     Ty::Hole(_) => {
+                        // The return value is still checked, since it is lowered
+                        // even though it is unreachable.
+                        let return_value_ty = Ty::hole_for(block_term.expr);
+                        self.check_node(block_term.expr, return_value_ty)?;
+
                         // If it diverges, we can just infer the return type as `never`.
                         let block_term_ty =
                             Ty::expect_is(original_term_id, never_ty(NodeOrigin::Expected));
@@ -116,12 +146,17 @@ impl<E: TcEnv> OperationsOn<BlockTerm> for Tc<'_, E> {
 
             for statement in block_term.statements.iter() {
                 match *statement.value() {
-                    BlockStatement::Decl(mut decl_term) => {
-                        decl_term.value =
-                            self.normalise_nested_node_and_record(decl_term.value, &st)?;
+                    BlockStatement::Decl(decl_term) => {
+                        // The bindings of a declaration without a value are
+                        // not known until they are assigned to.
+                        let Some(value) = decl_term.value else {
+                            continue;
+                        };
+
+                        let value = self.normalise_nested_node_and_record(value, &st)?;
 
                         match self.match_value_and_get_binds(
-                            decl_term.value,
+                            value,
                             decl_term.bind_pat,
                             &mut |name, term_id| {
                                 self.context().add_untyped_assignment(name, term_id)
@@ -130,9 +165,16 @@ impl<E: TcEnv> OperationsOn<BlockTerm> for Tc<'_, E> {
                             MatchResult::Successful => {
                                 // All good
                             }
-                            MatchResult::Failed => {
-                                panic!("Non-exhaustive let-binding: {}", decl_term)
-                            }
+                            MatchResult::Failed => match decl_term.otherwise {
+                                // The `else` block diverges, so the rest of the block
+                                // is never evaluated.
+                                Some(otherwise) => {
+                                    return normalised_to(
+                                        self.normalise_node_and_record(otherwise, &st)?,
+                                    );
+                                }
+                                None => panic!("Non-exhaustive let-binding: {}", decl_term),
+                            },
                             MatchResult::Stuck => {
                                 info!("Stuck evaluating let-binding: {}", decl_term);
                             }
@@ -1,11 +1,19 @@
 use std::ops::ControlFlow;
 
-use hash_storage::store::statics::StoreId;
+use hash_attrs::{attr::attr_store, builtin::attrs};
+use hash_ir::ir::UnsafeOpKind;
+use hash_storage::store::{
+    statics::{SequenceStoreValue, StoreId},
+    SequenceStoreKey, TrivialSequenceStoreKey,
+};
 use hash_tir::{
     atom_info::ItemInAtomInfo,
     context::{HasContext, ScopeKind},
     intrinsics::make::IsIntrinsic,
-    tir::{Arg, CallTerm, NodeId, NodesId, Term, TermId, Ty, TyId},
+    tir::{
+        Arg, ArgsId, CallTerm, HasAstNodeId, Node, NodeId, NodesId, ParamError, ParamIndex,
+        ParamsId, Term, TermId, Ty, TyId,
+    },
     visitor::Map,
 };
 use itertools::Itertools;
@@ -70,22 +78,63 @@ impl<E: TcEnv> OperationsOn<CallTerm> for Tc<'_, E> {
                     let copied_params = self.visitor().copy(fn_ty.params);
                     let copied_return_ty = self.visitor().copy(fn_ty.return_ty);
 
+                    // The arguments that are passed in the variadic part of a call to
+                    // a C-variadic function have no parameters to be checked against,
+                    // so their types are inferred on their own.
+                    let (fixed_args, variadic_args) = if fn_ty.is_c_variadic {
+                        self.split_c_variadic_args(fn_ty.params, call_term.args)?
+                    } else {
+                        (call_term.args, vec![])
+                    };
+
+                    for arg in &variadic_args {
+                        self.check_node(arg.value, Ty::hole_for(arg.value))?;
+                    }
+
                     let mut fn_call_term = *call_term;
-                    self.check_node_scoped(
-                        fn_call_term.args,
-                        copied_params,
-                        |inferred_fn_call_args| {
-                            fn_call_term.args = inferred_fn_call_args;
-                            original_term_id
-                                .set(original_term_id.value().with_data(fn_call_term.into()));
-
-                            self.substituter().apply_sub_from_context(copied_return_ty);
-                            self.check_by_unify(copied_return_ty, annotation_ty)?;
-                            Ok(())
-                        },
-                    )?;
+                    self.check_node_scoped(fixed_args, copied_params, |inferred_fn_call_args| {
+                        fn_call_term.args = if variadic_args.is_empty() {
+                            inferred_fn_call_args
+                        } else {
+                            let args = inferred_fn_call_args
+                                .iter()
+                                .map(|arg| arg.value())
+                                .chain(variadic_args.iter().cloned())
+                                .collect_vec();
+                            Node::create_at(Node::seq(args), call_term.args.origin())
+                        };
+                        original_term_id
+                            .set(original_term_id.value().with_data(fn_call_term.into()));
+
+                        self.substituter().apply_sub_from_context(copied_return_ty);
+                        self.check_by_unify(copied_return_ty, annotation_ty)?;
+                        Ok(())
+                    })?;
 
                     self.substituter().apply_sub_from_context(fn_call_term.subject);
+
+                    if let Term::Intrinsic(intrinsic) = *fn_call_term.subject.value() {
+                        self.check_intrinsic_call_args(intrinsic, fn_call_term.args)?;
+                    }
+
+                    // Check whether the call is to an intrinsic or function that
+                    // is considered to be unsafe.
+                    let unsafe_op = match *fn_call_term.subject.value() {
+                        Term::Intrinsic(intrinsic) => Self::unsafe_op_of_intrinsic(intrinsic),
+                        Term::Fn(fn_def)
+                            if attr_store()
+                                .node_has_attr(fn_def.node_id_ensured(), attrs::FOREIGN) =>
+                        {
+                            Some(UnsafeOpKind::ExternCall)
+                        }
+                        _ if fn_ty.is_unsafe => Some(UnsafeOpKind::UnsafeCall),
+                        _ => None,
+                    };
+
+                    if let Some(kind) = unsafe_op {
+                        self.record_unsafe_op(kind, original_term_id);
+                    }
+
                     self.potentially_monomorphise_fn_call(original_term_id, fn_ty, annotation_ty)?;
 
                     Ok(())
@@ -167,3 +216,41 @@ impl<E: TcEnv> OperationsOn<CallTerm> for Tc<'_, E> {
         Ok(())
     }
 }
+
+impl<E: TcEnv> Tc<'_, E> {
+    /// Split the arguments of a call to a C-variadic function into the
+    /// arguments that correspond to the parameters of the function, and the
+    /// arguments that are passed in the variadic part of the call.
+    ///
+    /// The variadic arguments have no parameter that they could refer to, and
+    /// so they must all be positional.
+    fn split_c_variadic_args(
+        &self,
+        params: ParamsId,
+        args: ArgsId,
+    ) -> TcResult<(ArgsId, Vec<Node<Arg>>)> {
+        if args.len() <= params.len() {
+            return Ok((args, vec![]));
+        }
+
+        let mut fixed_args = vec![];
+        let mut variadic_args = vec![];
+
+        for (index, arg_id) in args.iter().enumerate() {
+            let arg = arg_id.value();
+
+            if index < params.len() {
+                fixed_args.push(arg);
+            } else if let ParamIndex::Position(_) = arg.target {
+                variadic_args.push(arg);
+            } else {
+                return Err(TcError::ParamMatch(ParamError::ArgNameNotFoundInParams {
+                    arg: arg_id.into(),
+                    params,
+                }));
+            }
+        }
+
+        Ok((Node::create_at(Node::seq(fixed_args), args.origin()), variadic_args))
+    }
+}
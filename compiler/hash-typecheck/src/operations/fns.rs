@@ -12,7 +12,7 @@ use crate::{
     diagnostics::TcResult,
     env::TcEnv,
     options::normalisation::{already_normalised, NormaliseResult},
-    tc::{FnInferMode, Tc},
+    tc::{FnInferMode, Tc, UnsafeMode},
     traits::{OperationsOn, OperationsOnNode, ScopedOperationsOnNode},
 };
 
@@ -69,7 +69,11 @@ impl<E: TcEnv> OperationsOn<FnTy> for Tc<'_, E> {
 impl<E: TcEnv> Tc<'_, E> {
     /// Whether two function types match in terms of their modality.
     fn fn_modalities_match(&self, f1: FnTy, f2: FnTy) -> bool {
-        f1.implicit == f2.implicit && f1.is_unsafe == f2.is_unsafe && f1.pure == f2.pure
+        f1.implicit == f2.implicit
+            && f1.is_unsafe == f2.is_unsafe
+            && f1.pure == f2.pure
+            && f1.is_c_variadic == f2.is_c_variadic
+            && f1.abi == f2.abi
     }
 
     fn check_fn_def_id_annotation(
@@ -134,10 +138,14 @@ impl<E: TcEnv> OperationsOn<FnDefId> for Tc<'_, E> {
 
         let fn_def = fn_def_id.value();
 
-        self.context().enter_scope(ScopeKind::Fn(fn_def_id), || {
-            self.check_node_scoped(fn_def.ty.params, (), |()| {
-                self.check_node(fn_def.ty.return_ty, Ty::universe_of(fn_def.ty.return_ty))?;
-                self.check_node(fn_def.body, fn_def.ty.return_ty)
+        // The body of the function is never within an `unsafe` block, even if
+        // the function itself is defined in one.
+        self.unsafe_mode.enter(UnsafeMode::Safe, || {
+            self.context().enter_scope(ScopeKind::Fn(fn_def_id), || {
+                self.check_node_scoped(fn_def.ty.params, (), |()| {
+                    self.check_node(fn_def.ty.return_ty, Ty::universe_of(fn_def.ty.return_ty))?;
+                    self.check_node(fn_def.body, fn_def.ty.return_ty)
+                })
             })
         })?;
 
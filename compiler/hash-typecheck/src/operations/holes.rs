@@ -1,10 +1,16 @@
 use std::ops::ControlFlow;
 
 use hash_storage::store::statics::StoreId;
-use hash_tir::tir::{Hole, TermId, TyId, VarTerm};
+use hash_tir::{
+    context::{HasContext, ScopeKind},
+    tir::{Hole, SymbolId, TermId, TyId, TypedHole, VarTerm},
+};
 
 use crate::{
-    diagnostics::TcResult, env::TcEnv, options::normalisation::NormaliseResult, tc::Tc,
+    diagnostics::{TcError, TcResult},
+    env::TcEnv,
+    options::normalisation::{already_normalised, NormaliseResult},
+    tc::Tc,
     traits::OperationsOn,
 };
 
@@ -20,6 +26,36 @@ impl<E: TcEnv> Tc<'_, E> {
         self.add_unification(hole.0, sub_dest);
         Ok(())
     }
+
+    /// Get all of the named bindings that are currently in scope and have a
+    /// known type, in the order that they were bound. Bindings that are
+    /// shadowed by a later binding of the same name are omitted, as well as
+    /// the members of modules since they are always accessible by name.
+    pub fn get_typed_bindings_in_scope(&self) -> Vec<(SymbolId, TyId)> {
+        let mut bindings: Vec<(SymbolId, TyId)> = vec![];
+
+        for scope_index in self.context().get_scope_indices().rev() {
+            if matches!(
+                self.context().get_scope(scope_index).kind,
+                ScopeKind::Mod(_) | ScopeKind::Intrinsic(_)
+            ) {
+                continue;
+            }
+
+            self.context().for_decls_of_scope_rev(scope_index, |decl| {
+                let (Some(name), Some(ty)) = (decl.name.value().name, decl.ty) else {
+                    return;
+                };
+
+                if !bindings.iter().any(|(other, _)| other.value().name == Some(name)) {
+                    bindings.push((decl.name, ty));
+                }
+            });
+        }
+
+        bindings.reverse();
+        bindings
+    }
 }
 
 impl<E: TcEnv> OperationsOn<Hole> for Tc<'_, E> {
@@ -59,3 +95,41 @@ impl<E: TcEnv> OperationsOn<Hole> for Tc<'_, E> {
         }
     }
 }
+
+impl<E: TcEnv> OperationsOn<TypedHole> for Tc<'_, E> {
+    type AnnotNode = TyId;
+    type Node = TermId;
+
+    fn check(
+        &self,
+        _item: &mut TypedHole,
+        item_ty: Self::AnnotNode,
+        item_node: Self::Node,
+    ) -> TcResult<()> {
+        // A typed hole never typechecks, instead we report the type that is
+        // expected in its place, and what is available to fill it with.
+        Err(TcError::TypedHole {
+            term: item_node,
+            expected: item_ty,
+            bindings: self.get_typed_bindings_in_scope(),
+        })
+    }
+
+    fn try_normalise(
+        &self,
+        _item: TypedHole,
+        _item_node: Self::Node,
+    ) -> NormaliseResult<ControlFlow<Self::Node>> {
+        already_normalised()
+    }
+
+    fn unify(
+        &self,
+        _src: &mut TypedHole,
+        _target: &mut TypedHole,
+        src_id: Self::Node,
+        target_id: Self::Node,
+    ) -> TcResult<()> {
+        self.mismatching_atoms(src_id, target_id)
+    }
+}
@@ -1,17 +1,111 @@
 use std::ops::ControlFlow;
 
+use hash_attrs::attr::DeriveAttr;
+use hash_storage::store::{statics::StoreId, SequenceStoreKey, TrivialSequenceStoreKey};
 use hash_tir::{
     intrinsics::{definitions::Intrinsic, make::IsIntrinsic},
-    tir::{NodeOrigin, Term, TermId, TyId},
+    tir::{ArgsId, HasAstNodeId, NodeOrigin, Term, TermId, Ty, TyId},
 };
 
 use crate::{
+    diagnostics::{TcError, TcResult, WrongTermKind},
     env::TcEnv,
     options::normalisation::{already_normalised, NormaliseResult},
     tc::Tc,
     traits::OperationsOn,
 };
 
+impl<E: TcEnv> Tc<'_, E> {
+    /// Check any additional constraints on the arguments of an intrinsic call
+    /// which cannot be expressed by the type of the intrinsic itself.
+    pub fn check_intrinsic_call_args(&self, intrinsic: Intrinsic, args: ArgsId) -> TcResult<()> {
+        match intrinsic {
+            // These intrinsics are only defined for enum types.
+            Intrinsic::VariantCount | Intrinsic::DiscriminantValue => {
+                let ty = args.at(0).unwrap().borrow().value;
+                let ty = self.normalise_node_no_signals(ty)?;
+
+                match *ty.value() {
+                    Ty::DataTy(data_ty) if data_ty.data_def.borrow().discriminant_ty.is_some() => {
+                        Ok(())
+                    }
+                    // If the type isn't known yet, i.e. it is a parameter of a
+                    // polymorphic function, then we can't check it here.
+                    Term::Var(_) | Term::Hole(_) => Ok(()),
+                    _ => Err(TcError::WrongTerm {
+                        term: ty,
+                        inferred_term_ty: ty,
+                        kind: WrongTermKind::NotAnEnum,
+                    }),
+                }
+            }
+            // An enum can only be created from a discriminant if none of its
+            // variants have fields.
+            Intrinsic::EnumFromDiscriminant => {
+                let ty = args.at(0).unwrap().borrow().value;
+                let ty = self.normalise_node_no_signals(ty)?;
+
+                match *ty.value() {
+                    Ty::DataTy(data_ty) if data_ty.data_def.borrow().discriminant_ty.is_some() => {
+                        let ctors = data_ty.data_def.borrow().ctors.assert_defined();
+
+                        if ctors.iter().all(|ctor| ctor.borrow().params.len() == 0) {
+                            Ok(())
+                        } else {
+                            Err(TcError::WrongTerm {
+                                term: ty,
+                                inferred_term_ty: ty,
+                                kind: WrongTermKind::NotAFieldlessEnum,
+                            })
+                        }
+                    }
+                    Term::Var(_) | Term::Hole(_) => Ok(()),
+                    _ => Err(TcError::WrongTerm {
+                        term: ty,
+                        inferred_term_ty: ty,
+                        kind: WrongTermKind::NotAnEnum,
+                    }),
+                }
+            }
+            // These intrinsics are only defined for types which derive the
+            // respective item.
+            Intrinsic::DerivedEq
+            | Intrinsic::DerivedHash
+            | Intrinsic::DerivedDebug
+            | Intrinsic::DerivedSerialize
+            | Intrinsic::DerivedDeserialize => {
+                let derive = match intrinsic {
+                    Intrinsic::DerivedEq => DeriveAttr::Eq,
+                    Intrinsic::DerivedHash => DeriveAttr::Hash,
+                    Intrinsic::DerivedDebug => DeriveAttr::Debug,
+                    Intrinsic::DerivedSerialize => DeriveAttr::Serialize,
+                    _ => DeriveAttr::Deserialize,
+                };
+                let ty = args.at(0).unwrap().borrow().value;
+                let ty = self.normalise_node_no_signals(ty)?;
+
+                match *ty.value() {
+                    Ty::DataTy(data_ty)
+                        if data_ty
+                            .data_def
+                            .node_id()
+                            .is_some_and(|id| DeriveAttr::on_node(id).contains(&derive)) =>
+                    {
+                        Ok(())
+                    }
+                    Term::Var(_) | Term::Hole(_) => Ok(()),
+                    _ => Err(TcError::WrongTerm {
+                        term: ty,
+                        inferred_term_ty: ty,
+                        kind: WrongTermKind::NotDerived { derive },
+                    }),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 impl<E: TcEnv> OperationsOn<Intrinsic> for Tc<'_, E> {
     type AnnotNode = TyId;
     type Node = TermId;
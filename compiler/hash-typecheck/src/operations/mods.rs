@@ -20,6 +20,12 @@ impl<E: TcEnv> OperationsOnNode<ModDefId> for Tc<'_, E> {
     type AnnotNode = ();
 
     fn check_node(&self, mod_def_id: ModDefId, _: ()) -> crate::diagnostics::TcResult<()> {
+        // The reason for stopping is reported by the semantic analysis stage,
+        // so we only need to stop checking.
+        if self.env.should_stop() {
+            return Err(TcError::Signal);
+        }
+
         self.context().enter_scope(mod_def_id.into(), || {
             let members = mod_def_id.borrow().members;
             let mut error_state = ErrorState::new();
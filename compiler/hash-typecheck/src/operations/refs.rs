@@ -1,7 +1,10 @@
 use std::ops::ControlFlow;
 
+use hash_ir::ir::UnsafeOpKind;
 use hash_storage::store::statics::StoreId;
-use hash_tir::tir::{DerefTerm, NodeId, NodeOrigin, RefTerm, RefTy, Term, TermId, Ty, TyId};
+use hash_tir::tir::{
+    DerefTerm, NodeId, NodeOrigin, RefKind, RefTerm, RefTy, Term, TermId, Ty, TyId,
+};
 
 use crate::{
     diagnostics::TcError,
@@ -84,13 +87,20 @@ impl<E: TcEnv> OperationsOn<DerefTerm> for Tc<'_, E> {
         &self,
         deref_term: &mut DerefTerm,
         annotation_ty: Self::AnnotNode,
-        _item_node: Self::Node,
+        original_term_id: Self::Node,
     ) -> crate::diagnostics::TcResult<()> {
         let deref_inner_inferred = Ty::hole_for(deref_term.subject);
         self.check_node(deref_term.subject, deref_inner_inferred)?;
 
         let dereferenced_ty = match *deref_inner_inferred.value() {
-            Ty::RefTy(ref_ty) => ref_ty.ty,
+            Ty::RefTy(ref_ty) => {
+                // Dereferencing a raw pointer is an unsafe operation.
+                if ref_ty.kind == RefKind::Raw {
+                    self.record_unsafe_op(UnsafeOpKind::RawDeref, original_term_id);
+                }
+
+                ref_ty.ty
+            }
             _ => {
                 return Err(TcError::CannotDeref {
                     subject: deref_term.subject,
@@ -1,15 +1,46 @@
 use std::ops::ControlFlow;
 
-use hash_tir::tir::{TermId, TyId, UnsafeTerm};
+use hash_ir::ir::UnsafeOpKind;
+use hash_reporting::diagnostic::Diagnostics;
+use hash_tir::{
+    intrinsics::definitions::Intrinsic,
+    tir::{TermId, TyId, UnsafeTerm},
+};
 
 use crate::{
-    diagnostics::TcResult,
+    diagnostics::{TcError, TcResult, TcWarning},
     env::TcEnv,
     options::normalisation::{normalised_option, NormaliseResult},
-    tc::Tc,
+    tc::{Tc, UnsafeMode},
     traits::{OperationsOn, OperationsOnNode},
 };
 
+impl<E: TcEnv> Tc<'_, E> {
+    /// Record that an unsafe operation of the given kind occurs at `term`. If
+    /// the operation does not occur within an `unsafe` block, then an error
+    /// is emitted.
+    pub fn record_unsafe_op(&self, kind: UnsafeOpKind, term: TermId) {
+        match self.unsafe_mode.get() {
+            UnsafeMode::Safe => {
+                self.diagnostics().add_error(TcError::UnsafeOperation { kind, term }.into())
+            }
+            UnsafeMode::Unsafe { .. } => self.unsafe_mode.set(UnsafeMode::Unsafe { used: true }),
+        }
+    }
+
+    /// Get the kind of unsafe operation that a call to the given [Intrinsic]
+    /// performs, if any.
+    pub fn unsafe_op_of_intrinsic(intrinsic: Intrinsic) -> Option<UnsafeOpKind> {
+        match intrinsic {
+            Intrinsic::PtrOffset => Some(UnsafeOpKind::PtrOffset),
+            Intrinsic::Read | Intrinsic::DerivedDeserialize => Some(UnsafeOpKind::PtrRead),
+            Intrinsic::Write | Intrinsic::DerivedSerialize => Some(UnsafeOpKind::PtrWrite),
+            Intrinsic::Transmute => Some(UnsafeOpKind::Transmute),
+            _ => None,
+        }
+    }
+}
+
 impl<E: TcEnv> OperationsOn<UnsafeTerm> for Tc<'_, E> {
     type AnnotNode = TyId;
     type Node = TermId;
@@ -18,10 +49,28 @@ impl<E: TcEnv> OperationsOn<UnsafeTerm> for Tc<'_, E> {
         &self,
         unsafe_term: &mut UnsafeTerm,
         annotation_ty: Self::AnnotNode,
-        _: Self::Node,
+        original_term_id: Self::Node,
     ) -> TcResult<()> {
-        // @@Todo
-        self.check_node(unsafe_term.inner, annotation_ty)?;
+        // An `unsafe` block that is within another `unsafe` block is always
+        // redundant, so any operations within it count towards the outer block.
+        if let UnsafeMode::Unsafe { .. } = self.unsafe_mode.get() {
+            self.diagnostics().add_warning(
+                TcWarning::UnusedUnsafe { term: original_term_id, nested: true }.into(),
+            );
+            return self.check_node(unsafe_term.inner, annotation_ty);
+        }
+
+        let used = self.unsafe_mode.enter(UnsafeMode::Unsafe { used: false }, || {
+            self.check_node(unsafe_term.inner, annotation_ty)?;
+            TcResult::Ok(self.unsafe_mode.get() == UnsafeMode::Unsafe { used: true })
+        })?;
+
+        if !used {
+            self.diagnostics().add_warning(
+                TcWarning::UnusedUnsafe { term: original_term_id, nested: false }.into(),
+            );
+        }
+
         Ok(())
     }
 
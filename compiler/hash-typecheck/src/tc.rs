@@ -12,7 +12,7 @@ use hash_utils::{derive_more::Deref, state::LightState};
 use crate::{
     env::{HasTcEnv, TcEnv},
     options::{normalisation::NormalisationOptions, unification::UnificationOptions},
-    utils::substitution::Substituter,
+    utils::{substitution::Substituter, tracing::UnificationTracer},
 };
 
 /// The mode in which to infer the type of a function.
@@ -29,6 +29,17 @@ pub enum FnInferMode {
     Body,
 }
 
+/// Whether the typechecker is currently checking a term that is within an
+/// `unsafe` block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsafeMode {
+    /// Not within an `unsafe` block, unsafe operations are not allowed.
+    Safe,
+    /// Within an `unsafe` block, `used` denotes whether an unsafe operation
+    /// has been found in the block so far.
+    Unsafe { used: bool },
+}
+
 /// This struct represents the typechecker.
 ///
 /// It holds the state of the typechecker, which consists of
@@ -36,14 +47,18 @@ pub enum FnInferMode {
 ///   environment of the compiler, to operate
 /// - the `Context`, which is the current set of bindings the typechecker should
 ///   operate on.
+/// - the `UnificationTracer`, which records the unifications that are performed
+///   if tracing is enabled.
 #[derive(Deref)]
 pub struct Tc<'tc, E> {
     #[deref]
     pub env: &'tc E,
     pub context: &'tc Context,
     pub fn_infer_mode: LightState<FnInferMode>,
+    pub unsafe_mode: LightState<UnsafeMode>,
     pub unification_opts: UnificationOptions,
     pub normalisation_opts: NormalisationOptions,
+    pub tracer: UnificationTracer,
 }
 
 impl<E: TcEnv> Tc<'_, E> {
@@ -10,4 +10,5 @@ pub mod matching;
 pub mod normalisation;
 pub mod purity;
 pub mod substitution;
+pub mod tracing;
 pub mod unification;
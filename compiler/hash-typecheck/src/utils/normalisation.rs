@@ -171,8 +171,10 @@ impl<'env, T: TcEnv + 'env> Tc<'env, T> {
         })?;
 
         if st.has_normalised() {
+            self.env.count("normalise::changed");
             Ok(Some(result))
         } else {
+            self.env.count("normalise::unchanged");
             Ok(None)
         }
     }
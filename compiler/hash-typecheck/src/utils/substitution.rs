@@ -62,6 +62,10 @@ impl<'a, T: TcEnv> Substituter<'a, T> {
     where
         Visitor: Visit<U>,
     {
+        if !sub.is_empty() {
+            self.tc.tracer.record(|| format!("apply substitution {sub}"));
+        }
+
         self.traversing_utils
             .visit(item, &mut |atom| self.apply_sub_to_atom_in_place_once(atom, sub));
     }
@@ -0,0 +1,79 @@
+//! Utilities for tracing the unifications that are performed by the
+//! typechecker. Tracing is enabled with the `--trace=tc` flag, and records
+//! each unification attempt, the unifications that it performs itself, any
+//! substitutions that are applied, and any unifications that are skipped
+//! since the two terms are identical. Once the outermost unification has
+//! finished, the recorded tree is printed.
+
+use std::cell::RefCell;
+
+use hash_utils::{
+    stream_less_writeln,
+    tree_writing::{TreeNode, TreeWriter},
+};
+
+use crate::diagnostics::TcResult;
+
+/// Records a tree of the unifications that are performed by the typechecker.
+#[derive(Debug, Default)]
+pub struct UnificationTracer {
+    /// Whether tracing is enabled.
+    enabled: bool,
+
+    /// The unifications that are currently in progress, the last node is
+    /// the innermost unification.
+    stack: RefCell<Vec<TreeNode>>,
+}
+
+impl UnificationTracer {
+    /// Create a new [UnificationTracer].
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, stack: RefCell::new(vec![]) }
+    }
+
+    /// Whether tracing is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Trace the unification that is performed by `f`, any events that are
+    /// recorded whilst running `f` are nested within the unification. The
+    /// `label` is only computed if tracing is enabled.
+    pub fn trace<T>(
+        &self,
+        label: impl FnOnce() -> String,
+        f: impl FnOnce() -> TcResult<T>,
+    ) -> TcResult<T> {
+        if !self.enabled {
+            return f();
+        }
+
+        self.stack.borrow_mut().push(TreeNode::leaf(label()));
+        let result = f();
+
+        let mut node = self.stack.borrow_mut().pop().unwrap();
+        node.label =
+            format!("{}: {}", node.label, if result.is_ok() { "ok" } else { "failed" }).into();
+        self.add(node);
+
+        result
+    }
+
+    /// Record an event within the current unification. Events that occur
+    /// outside of a unification are not recorded, since they would not be
+    /// related to any unification.
+    pub fn record(&self, label: impl FnOnce() -> String) {
+        if self.enabled && !self.stack.borrow().is_empty() {
+            self.add(TreeNode::leaf(label()));
+        }
+    }
+
+    /// Add the given node to the current unification, or print it if there
+    /// is no unification in progress.
+    fn add(&self, node: TreeNode) {
+        match self.stack.borrow_mut().last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => stream_less_writeln!("[TC trace]:\n{}", TreeWriter::new(&node)),
+        }
+    }
+}
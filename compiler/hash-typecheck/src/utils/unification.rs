@@ -18,6 +18,7 @@ use crate::{
 impl<E: TcEnv> Tc<'_, E> {
     /// Add the given substitutions to the context.
     pub fn add_sub_to_scope(&self, sub: &Sub) {
+        self.tracer.record(|| format!("add substitution {sub}"));
         self.context().add_sub_to_scope(sub);
     }
 
@@ -4,14 +4,37 @@
 use std::{cell::Cell, collections::HashSet, mem};
 
 use hash_ast::{
-    ast::{self, AstNodeRef, Block, BlockExpr, Expr, ExprMacroInvocation},
+    ast::{self, AssignExpr, AstNodeRef, Block, BlockExpr, Expr, ExprMacroInvocation},
     origin::BlockOrigin,
     visitor::AstVisitorMutSelf,
 };
+use hash_source::location::{ByteRange, Span};
 
 use super::SemanticAnalyser;
 use crate::diagnostics::error::AnalysisErrorKind;
 
+/// If the given statement is an assignment to a variable, i.e. `a = 2`, then
+/// get the span of the `=` within the assignment.
+fn assignment_eq_span(statement: AstNodeRef<Expr>) -> Option<Span> {
+    let Expr::Assign(AssignExpr { lhs, rhs }) = statement.body() else { return None };
+
+    if !matches!(lhs.body(), Expr::Variable(_)) {
+        return None;
+    }
+
+    // The `=` is somewhere between the two sides of the assignment.
+    let (lhs, rhs) = (lhs.span(), rhs.span());
+    let start = lhs.range.end() + 1;
+
+    if rhs.range.start() <= start {
+        return None;
+    }
+
+    let between = Span::new(ByteRange::new(start, rhs.range.start() - 1), lhs.id);
+    let offset = between.map_contents(|contents| contents.find('='))?;
+    Some(Span::new(ByteRange::singleton(start + offset), lhs.id))
+}
+
 impl SemanticAnalyser {
     /// This function will verify that all of the given expressions are
     /// declarations. Additionally, the function checks that all of the
@@ -35,9 +58,9 @@ impl SemanticAnalyser {
         for (index, statement) in members.enumerate() {
             let current = Cell::new(statement);
 
-            let mut emit_err = |this: &mut Self| {
+            let mut emit_err = |this: &mut Self, assignment: Option<Span>| {
                 this.append_error(
-                    AnalysisErrorKind::NonDeclarativeExpression { origin },
+                    AnalysisErrorKind::NonDeclarativeExpression { origin, assignment },
                     statement,
                 );
                 error_indices.insert(index);
@@ -65,17 +88,17 @@ impl SemanticAnalyser {
                                 // report that the entire block is
                                 // invalid.
                                 if !indices.is_empty() {
-                                    emit_err(self)
+                                    emit_err(self, None)
                                 }
                             }
-                            _ => emit_err(self),
+                            _ => emit_err(self, None),
                         }
 
                         break;
                     }
                     _ => {
                         if !allowed_top_level_expr(current_value) {
-                            emit_err(self);
+                            emit_err(self, assignment_eq_span(current_value));
                         }
                         break;
                     }
@@ -105,11 +128,39 @@ impl SemanticAnalyser {
         // We have to manually walk this block because we want to skip any erroneous
         // statements.
         for (index, statement) in members.iter().enumerate() {
-            if errors.contains(&index) {
+            if !errors.contains(&index) {
                 self.visit_expr(statement.ast_ref()).unwrap();
             }
         }
 
         self.current_block = old_block_origin;
     }
+
+    /// This function is used to check that all of the members of an
+    /// `#extern(...)` module are function declarations. Any members that
+    /// aren't declarations are reported by
+    /// [`Self::check_members_are_declarative`].
+    pub(crate) fn check_extern_members<'a>(
+        &mut self,
+        members: impl Iterator<Item = ast::AstNodeRef<'a, ast::Expr>>,
+    ) {
+        // Skip past any directives that are applied onto the member.
+        let skip_macros = |mut expr: AstNodeRef<'a, Expr>| {
+            while let Expr::Macro(ExprMacroInvocation { subject, .. }) = expr.body {
+                expr = subject.ast_ref();
+            }
+
+            expr
+        };
+
+        for member in members {
+            if let Expr::Declaration(decl) = skip_macros(member).body
+                && !decl.value.as_ref().is_some_and(|value| {
+                    matches!(skip_macros(value.ast_ref()).body, Expr::FnDef(_))
+                })
+            {
+                self.append_error(AnalysisErrorKind::NonFnExternMember, member);
+            }
+        }
+    }
 }
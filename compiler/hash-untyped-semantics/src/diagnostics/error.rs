@@ -52,8 +52,10 @@ pub(crate) enum AnalysisErrorKind {
     UsingReturnOutsideOfFn,
 
     /// When there is a non-declarative expression in either the root scope
-    /// (module) or in a `impl` / `mod` block.
-    NonDeclarativeExpression { origin: BlockOrigin },
+    /// (module) or in a `impl` / `mod` block. If the expression is an
+    /// assignment to a variable, i.e. `a = 2`, then `assignment` is the span of
+    /// the `=`, since the assignment was likely meant to be a declaration.
+    NonDeclarativeExpression { origin: BlockOrigin, assignment: Option<Span> },
 
     /// When a pattern is used within a particular context that is not allowed
     ///
@@ -70,6 +72,14 @@ pub(crate) enum AnalysisErrorKind {
     /// non-constant blocks like function bodies.
     IllegalBindingVisibilityModifier { modifier: Visibility, origin: BlockOrigin },
 
+    /// When a declaration within a constant block like the root scope (module)
+    /// or a `impl` / `mod` block has an `else` block.
+    IllegalDeclarationElse { origin: BlockOrigin },
+
+    /// When a declaration within a constant block like the root scope (module)
+    /// or a `impl` / `mod` block has no value.
+    MissingDeclarationValue { origin: BlockOrigin },
+
     /// When a field within a struct, tuple or other form is missing both a type
     /// annotation and a default value, which means that there is not enough
     /// information at later stages to deduce the type of the field.
@@ -90,6 +100,22 @@ pub(crate) enum AnalysisErrorKind {
     /// When a range ending is specified as exclusive, but doesn't specify a
     /// terminating value.
     ExclusiveRangeWithNoEnding,
+
+    /// When a function that is not marked as `#foreign` specifies that it
+    /// accepts a variable number of arguments with `...`.
+    NonForeignCVariadicFn,
+
+    /// When a function that is not marked as `#foreign` is declared without a
+    /// body.
+    MissingFnBody,
+
+    /// When a function that is not marked as `#foreign` specifies the library
+    /// that it is linked against with `#link(...)`.
+    NonForeignLinkedFn,
+
+    /// When a member of an `#extern(...)` module is not a function
+    /// declaration.
+    NonFnExternMember,
 }
 
 impl From<AnalysisError> for Reports {
@@ -119,7 +145,9 @@ impl From<AnalysisError> for Reports {
                     ReportElement::CodeBlock(ReportCodeBlock::new(err.location, "here")),
                 );
             }
-            AnalysisErrorKind::NonDeclarativeExpression { origin } => {
+            AnalysisErrorKind::NonDeclarativeExpression { origin, assignment } => {
+                error.code(HashErrorCode::NonDeclarativeExpression);
+
                 error.title(format!(
                     "non-declarative expressions are not allowed in `{origin}` pattern"
                 ));
@@ -128,8 +156,14 @@ impl From<AnalysisError> for Reports {
                     err.location,
                     "not allowed here",
                 )));
+
+                if let Some(span) = assignment {
+                    error.add_suggestion(span, ":=");
+                }
             }
             AnalysisErrorKind::IllegalBindingMutability => {
+                error.code(HashErrorCode::ItemMustBeImmutable);
+
                 error.title("top-level declaration cannot be mutable");
 
                 error
@@ -143,6 +177,8 @@ impl From<AnalysisError> for Reports {
                     )));
             }
             AnalysisErrorKind::IllegalBindingVisibilityModifier { modifier, origin } => {
+                error.code(HashErrorCode::IllegalVisibilityModifier);
+
                 error.title(format!(
                     "declarations in {origin} blocks cannot have visibility modifiers"
                 ));
@@ -163,7 +199,41 @@ impl From<AnalysisError> for Reports {
                         "consider removing the visibility modifier",
                     )));
             }
+            AnalysisErrorKind::IllegalDeclarationElse { origin } => {
+                error.code(HashErrorCode::IllegalDeclarationElse);
+
+                error.title(format!(
+                    "declarations in `{origin}` blocks cannot have an `else` block"
+                ));
+
+                error
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "not allowed here",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "consider using an irrefutable pattern in the declaration",
+                    )));
+            }
+            AnalysisErrorKind::MissingDeclarationValue { origin } => {
+                error.code(HashErrorCode::MissingDeclarationValue);
+
+                error.title(format!("declarations in `{origin}` blocks must have a value"));
+
+                error
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "declared here",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "consider giving the declaration a value with `= ...`",
+                    )));
+            }
             AnalysisErrorKind::InsufficientTypeAnnotations { origin } => {
+                error.code(HashErrorCode::MissingFieldAnnotation);
+
                 error.title(format!(
                     "`{}` {} does not have enough information",
                     origin,
@@ -185,6 +255,8 @@ impl From<AnalysisError> for Reports {
                     )));
             }
             AnalysisErrorKind::DisallowedFloatPat => {
+                error.code(HashErrorCode::DisallowedFloatPat);
+
                 error.title("float literals are disallowed within a pattern position");
 
                 error
@@ -195,6 +267,8 @@ impl From<AnalysisError> for Reports {
                     )));
             }
             AnalysisErrorKind::InconsistentFieldNaming { naming_expectation, origin } => {
+                error.code(HashErrorCode::InconsistentFieldNaming);
+
                 error.title(format!("mismatching naming convention of fields within a {origin}"));
 
                 error
@@ -208,6 +282,8 @@ impl From<AnalysisError> for Reports {
                     )));
             }
             AnalysisErrorKind::SelfInFreeStandingFn => {
+                error.code(HashErrorCode::SelfInFreeStandingFn);
+
                 error.title("`self` parameter is only allowed in associated functions");
 
                 error.add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
@@ -216,9 +292,67 @@ impl From<AnalysisError> for Reports {
                 )));
             }
             AnalysisErrorKind::ExclusiveRangeWithNoEnding => {
+                error.code(HashErrorCode::MissingRangeEnd);
+
                 error.title(format!("incomplete range ending, ranges that specify a `{}` must specify an ending range operand", RangeEnd::Excluded))
                 .add_labelled_span(err.location, "add an ending range operand here");
             }
+            AnalysisErrorKind::NonForeignCVariadicFn => {
+                error.code(HashErrorCode::NonForeignVariadicFn);
+
+                error.title("only `#foreign` functions can accept a variable number of arguments");
+
+                error
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this function is not `#foreign`",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "consider removing the `...` from the parameters",
+                    )));
+            }
+            AnalysisErrorKind::MissingFnBody => {
+                error.code(HashErrorCode::MissingFnBody);
+
+                error.title("only `#foreign` functions can be declared without a body");
+
+                error
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this function is not `#foreign`",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "consider adding a body to the function with `=> ...`, or declaring \
+                        it within an `#extern(\"C\")` module",
+                    )));
+            }
+            AnalysisErrorKind::NonForeignLinkedFn => {
+                error.code(HashErrorCode::NonForeignLinkedFn);
+
+                error.title("only `#foreign` functions can be linked against a library");
+
+                error
+                    .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                        err.location,
+                        "this function is not `#foreign`",
+                    )))
+                    .add_element(ReportElement::Note(ReportNote::new(
+                        ReportNoteKind::Help,
+                        "consider marking the function as `#foreign`, or removing the `#link(...)`",
+                    )));
+            }
+            AnalysisErrorKind::NonFnExternMember => {
+                error.code(HashErrorCode::NonFnExternMember);
+
+                error.title("`#extern` modules can only contain function declarations");
+
+                error.add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
+                    err.location,
+                    "this is not a function declaration",
+                )));
+            }
         };
 
         reporter.into_reports()
@@ -2,6 +2,7 @@
 
 use hash_ast::ast::{AstNodeId, AstNodeRef};
 use hash_reporting::{
+    hash_error_codes::warning_codes::HashWarningCode,
     report::{ReportCodeBlock, ReportElement, ReportNote, ReportNoteKind},
     reporter::{Reporter, Reports},
 };
@@ -46,6 +47,7 @@ impl From<AnalysisWarning> for Reports {
         match warn.kind {
             AnalysisWarningKind::UselessExpression => {
                 warning
+                    .code(HashWarningCode::UselessExpression)
                     .title("this expression is useless")
                     .add_element(ReportElement::CodeBlock(ReportCodeBlock::new(
                         warn.location,
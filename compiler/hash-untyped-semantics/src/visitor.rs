@@ -11,6 +11,7 @@ use hash_ast::{
     origin::BlockOrigin,
     visitor::AstVisitorMutSelf,
 };
+use hash_attrs::{attr::attr_store, builtin::attrs};
 use hash_reporting::macros::panic_on_span;
 
 use crate::{
@@ -34,6 +35,7 @@ impl AstVisitorMutSelf for SemanticAnalyser {
         IfClause,
         IfBlock,
         BodyBlock,
+        Declaration,
         ReturnStatement,
         BreakStatement,
         ContinueStatement,
@@ -63,6 +65,24 @@ impl AstVisitorMutSelf for SemanticAnalyser {
         &mut self,
         node: ast::AstNodeRef<ast::FnDef>,
     ) -> Result<Self::FnDefRet, Self::Error> {
+        // Only `#foreign` functions can accept a variable number of arguments,
+        // since a Hash function has no way of accessing them.
+        if node.c_variadic && !attr_store().node_has_attr(node.id(), attrs::FOREIGN) {
+            self.append_error(AnalysisErrorKind::NonForeignCVariadicFn, node);
+        }
+
+        if node.fn_body.is_none() && !attr_store().node_has_attr(node.id(), attrs::FOREIGN) {
+            self.append_error(AnalysisErrorKind::MissingFnBody, node);
+        }
+
+        // Only `#foreign` functions are resolved from a library, so linking
+        // any other function against one has no effect.
+        if attr_store().node_has_attr(node.id(), attrs::LINK)
+            && !attr_store().node_has_attr(node.id(), attrs::FOREIGN)
+        {
+            self.append_error(AnalysisErrorKind::NonForeignLinkedFn, node);
+        }
+
         // Swap the values with a new `true` and save the old state.
         let last_in_fn = mem::replace(&mut self.is_in_fn, true);
         let _ = walk_mut_self::walk_fn_def(self, node);
@@ -150,6 +170,10 @@ impl AstVisitorMutSelf for SemanticAnalyser {
         &mut self,
         node: ast::AstNodeRef<ast::ModDef>,
     ) -> Result<Self::ModDefRet, Self::Error> {
+        if attr_store().node_has_attr(node.id(), attrs::EXTERN) {
+            self.check_extern_members(node.entries.ast_ref_iter());
+        }
+
         self.check_constant_scope_members(&node.body().entries, BlockOrigin::Mod);
         Ok(())
     }
@@ -202,6 +226,35 @@ impl AstVisitorMutSelf for SemanticAnalyser {
         Ok(())
     }
 
+    type DeclarationRet = ();
+
+    fn visit_declaration(
+        &mut self,
+        node: ast::AstNodeRef<ast::Declaration>,
+    ) -> Result<Self::DeclarationRet, Self::Error> {
+        // Declarations within constant blocks cannot run any code if the
+        // value doesn't match the pattern, so an `else` block isn't allowed.
+        if let Some(otherwise) = &node.body().otherwise
+            && self.is_in_constant_block()
+        {
+            self.append_error(
+                AnalysisErrorKind::IllegalDeclarationElse { origin: self.current_block },
+                otherwise.ast_ref(),
+            );
+        }
+
+        // Only the declarations within a body block can be initialised later.
+        if node.body().value.is_none() && self.is_in_constant_block() {
+            self.append_error(
+                AnalysisErrorKind::MissingDeclarationValue { origin: self.current_block },
+                node,
+            );
+        }
+
+        let _ = walk_mut_self::walk_declaration(self, node);
+        Ok(())
+    }
+
     type ReturnStatementRet = ();
 
     fn visit_return_statement(
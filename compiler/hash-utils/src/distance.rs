@@ -0,0 +1,77 @@
+//! Utilities for computing the distance between strings, this is used to
+//! provide suggestions when a name cannot be found, e.g. "did you mean ...?".
+
+/// Compute the Levenshtein edit distance between `a` and `b`, i.e. the
+/// number of single character insertions, deletions and substitutions that
+/// are required to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+
+    // We only keep the previous row of the distance matrix.
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Find the candidates that are closest to the given `name`, at most
+/// `limit` candidates are returned, ordered by their distance to the `name`.
+/// Any candidate that is further away than a third of the length of `name`
+/// is not considered to be similar, since it is unlikely to be a typo.
+pub fn closest_matches<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    limit: usize,
+) -> Vec<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    let mut matches = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+
+    // Sort by the distance and then the name of the candidate so that the
+    // suggestions are deterministic.
+    matches.sort_unstable();
+    matches.dedup_by_key(|(_, candidate)| *candidate);
+    matches.into_iter().take(limit).map(|(_, candidate)| candidate).collect()
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("foo", ""), 3);
+        assert_eq!(edit_distance("", "foo"), 3);
+        assert_eq!(edit_distance("foo", "foo"), 0);
+        assert_eq!(edit_distance("foo", "fop"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("println", "pritnln"), 2);
+    }
+
+    #[test]
+    fn test_closest_matches() {
+        let candidates = ["print", "println", "panic", "printer", "x"];
+
+        assert_eq!(closest_matches("prinltn", candidates, 3), vec!["print", "println"]);
+        assert_eq!(closest_matches("prin", candidates, 3), vec!["print"]);
+        assert_eq!(closest_matches("printe", candidates, 1), vec!["print"]);
+        assert_eq!(closest_matches("y", ["x"], 3), vec!["x"]);
+        assert!(closest_matches("unrelated", candidates, 3).is_empty());
+    }
+}
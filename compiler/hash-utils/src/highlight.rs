@@ -1,5 +1,23 @@
 //! Hash Compiler error and warning reporting module.
-use std::ops::BitOr;
+use std::{
+    ops::BitOr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Whether [highlight] applies the escape codes of the highlighter to the
+/// message, this is enabled unless it is disabled by [set_colours_enabled].
+static COLOURS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable the escape codes that are written by [highlight]. When
+/// disabled, messages are written without any colours or text effects.
+pub fn set_colours_enabled(enabled: bool) {
+    COLOURS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Check whether [highlight] writes escape codes.
+pub fn colours_enabled() -> bool {
+    COLOURS_ENABLED.load(Ordering::Relaxed)
+}
 
 /// Variants of highlighter colour that can be used.
 pub enum Colour {
@@ -95,10 +113,15 @@ impl Highlighter for Decoration {
 
 /// General function to apply a highlighter on a string. This will call the
 /// provided [Highlighter] implementation and then apply it to the passed
-/// message, resetting the effect at the end of the message.
+/// message, resetting the effect at the end of the message. If colours are
+/// disabled, the message is returned as is.
 pub fn highlight(highlighter: impl Highlighter, message: impl ToString) -> String {
     const RESET: &str = "\u{001b}[0m";
 
+    if !colours_enabled() {
+        return message.to_string();
+    }
+
     highlighter
         .escape_code()
         .chars()
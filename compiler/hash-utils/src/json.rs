@@ -0,0 +1,373 @@
+//! A minimal JSON document representation, this is used by the various
+//! compiler dumps that can be emitted as JSON so that they can be consumed
+//! by external tooling, and by the messages that the compiler reads and
+//! writes, i.e. the requests of the language server.
+
+use std::{fmt, iter::Peekable, str::Chars};
+
+/// A simple representation of a JSON value which is used to build up the
+/// document before it is written, or which is read from a document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    /// The `null` value.
+    Null,
+
+    /// A boolean value.
+    Bool(bool),
+
+    /// A numeric value.
+    Number(f64),
+
+    /// A string value, this will be escaped when it is written.
+    String(String),
+
+    /// An array of values.
+    Array(Vec<JsonValue>),
+
+    /// An object with a collection of ordered keys and values.
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// An error that occurred whilst reading a JSON document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    /// A description of what went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JSON: {}", self.message)
+    }
+}
+
+impl JsonValue {
+    /// Create a [JsonValue::Object] from the given keys and values.
+    pub fn object<'k>(entries: impl IntoIterator<Item = (&'k str, JsonValue)>) -> Self {
+        Self::Object(entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    /// Create a new [JsonValue::String] from anything that can be displayed.
+    pub fn string(value: impl ToString) -> Self {
+        Self::String(value.to_string())
+    }
+
+    /// Read a single JSON document, any trailing characters that aren't
+    /// whitespace are an error.
+    pub fn parse(input: &str) -> Result<Self, JsonError> {
+        let mut reader = JsonReader { chars: input.chars().peekable() };
+        let value = reader.value()?;
+
+        reader.skip_whitespace();
+        match reader.chars.next() {
+            Some(c) => reader.error(format!("unexpected `{c}` after the document")),
+            None => Ok(value),
+        }
+    }
+
+    /// Get the value of the given key, if this is an object that contains it.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => {
+                entries.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Follow a path of keys through nested objects.
+    pub fn path(&self, keys: &[&str]) -> Option<&JsonValue> {
+        keys.iter().try_fold(self, |value, key| value.get(key))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as an unsigned integer, i.e. a position or a version.
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Number(value) if *value >= 0.0 && value.fract() == 0.0 => {
+                Some(*value as usize)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Write the value on a single line, this is used for messages that are
+    /// written one per line, i.e. `{ "stage": "lower", "completed": 3 }`.
+    pub fn to_single_line(&self) -> String {
+        // Any newlines within strings are escaped, so all of the lines are
+        // from the layout of the document.
+        self.to_string().lines().map(str::trim).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Write the value with the given indentation level.
+    fn write(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{value}"),
+            JsonValue::Number(value) if value.fract() == 0.0 && value.abs() < 1e15 => {
+                write!(f, "{}", *value as i64)
+            }
+            JsonValue::Number(value) => write!(f, "{value}"),
+            JsonValue::String(value) => write_escaped(f, value),
+            JsonValue::Array(items) if items.is_empty() => write!(f, "[]"),
+            JsonValue::Array(items) => {
+                writeln!(f, "[")?;
+
+                for (index, item) in items.iter().enumerate() {
+                    write!(f, "{: <1$}", "", (indent + 1) * 2)?;
+                    item.write(f, indent + 1)?;
+
+                    if index + 1 < items.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+
+                write!(f, "{: <1$}]", "", indent * 2)
+            }
+            JsonValue::Object(entries) if entries.is_empty() => write!(f, "{{}}"),
+            JsonValue::Object(entries) => {
+                writeln!(f, "{{")?;
+
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    write!(f, "{: <1$}", "", (indent + 1) * 2)?;
+                    write_escaped(f, key)?;
+                    write!(f, ": ")?;
+                    value.write(f, indent + 1)?;
+
+                    if index + 1 < entries.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+
+                write!(f, "{: <1$}}}", "", indent * 2)
+            }
+        }
+    }
+}
+
+impl From<usize> for JsonValue {
+    fn from(value: usize) -> Self {
+        JsonValue::Number(value as f64)
+    }
+}
+
+impl From<i64> for JsonValue {
+    fn from(value: i64) -> Self {
+        JsonValue::Number(value as f64)
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Bool(value)
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Write a string as a JSON string literal, escaping any characters that
+/// need to be escaped.
+fn write_escaped(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+
+    for ch in value.chars() {
+        match ch {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            ch if ch.is_control() => write!(f, "\\u{:04x}", ch as u32)?,
+            ch => write!(f, "{ch}")?,
+        }
+    }
+
+    write!(f, "\"")
+}
+
+/// Reads a [JsonValue] value from a stream of characters.
+struct JsonReader<'s> {
+    chars: Peekable<Chars<'s>>,
+}
+
+impl JsonReader<'_> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, JsonError> {
+        Err(JsonError { message: message.into() })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+    }
+
+    /// Expect that the next characters are the given keyword.
+    fn keyword(&mut self, keyword: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        for expected in keyword.chars() {
+            if self.chars.next() != Some(expected) {
+                return self.error(format!("expected `{keyword}`"));
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        self.skip_whitespace();
+
+        match self.chars.peek().copied() {
+            Some('n') => self.keyword("null", JsonValue::Null),
+            Some('t') => self.keyword("true", JsonValue::Bool(true)),
+            Some('f') => self.keyword("false", JsonValue::Bool(false)),
+            Some('"') => self.string().map(JsonValue::String),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => self.error(format!("unexpected `{c}`")),
+            None => self.error("unexpected end of the document"),
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let mut number = String::new();
+
+        while let Some(c) =
+            self.chars.next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            number.push(c);
+        }
+
+        match number.parse() {
+            Ok(value) => Ok(JsonValue::Number(value)),
+            Err(_) => self.error(format!("invalid number `{number}`")),
+        }
+    }
+
+    /// Read the four hexadecimal digits of a `\u` escape.
+    fn code_unit(&mut self) -> Result<u32, JsonError> {
+        let digits: String = (0..4).filter_map(|_| self.chars.next()).collect();
+
+        match u32::from_str_radix(&digits, 16) {
+            Ok(unit) if digits.len() == 4 => Ok(unit),
+            _ => self.error(format!("invalid escape `\\u{digits}`")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.chars.next();
+        let mut value = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => {
+                    let c = match self.chars.next() {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let mut unit = self.code_unit()?;
+
+                            // Characters outside of the basic plane are escaped as a
+                            // surrogate pair.
+                            if (0xD800..0xDC00).contains(&unit)
+                                && self.chars.next() == Some('\\')
+                                && self.chars.next() == Some('u')
+                            {
+                                let low = self.code_unit()?;
+                                unit =
+                                    0x10000 + ((unit - 0xD800) << 10) + (low.wrapping_sub(0xDC00));
+                            }
+
+                            char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        Some(c) => c,
+                        None => return self.error("unterminated string"),
+                    };
+
+                    value.push(c);
+                }
+                Some(c) => value.push(c),
+                None => return self.error("unterminated string"),
+            }
+        }
+    }
+
+    /// Read the items of an array or the entries of an object, which are
+    /// separated by commas and end with the given character.
+    fn items(
+        &mut self,
+        end: char,
+        mut item: impl FnMut(&mut Self) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError> {
+        self.chars.next();
+        self.skip_whitespace();
+
+        if self.chars.next_if_eq(&end).is_some() {
+            return Ok(());
+        }
+
+        loop {
+            item(self)?;
+            self.skip_whitespace();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(c) if c == end => return Ok(()),
+                _ => return self.error(format!("expected `,` or `{end}`")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        let mut items = vec![];
+        self.items(']', |reader| {
+            items.push(reader.value()?);
+            Ok(())
+        })?;
+
+        Ok(JsonValue::Array(items))
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        let mut entries = vec![];
+        self.items('}', |reader| {
+            reader.skip_whitespace();
+
+            if reader.chars.peek() != Some(&'"') {
+                return reader.error("expected a key");
+            }
+
+            let key = reader.string()?;
+            reader.skip_whitespace();
+
+            if reader.chars.next() != Some(':') {
+                return reader.error("expected `:`");
+            }
+
+            entries.push((key, reader.value()?));
+            Ok(())
+        })?;
+
+        Ok(JsonValue::Object(entries))
+    }
+}
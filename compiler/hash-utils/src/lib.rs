@@ -12,8 +12,10 @@
 pub mod assert;
 pub mod counter;
 pub mod crash;
+pub mod distance;
 pub mod graph;
 pub mod highlight;
+pub mod json;
 pub mod logging;
 pub mod path;
 pub mod printing;
@@ -23,6 +25,7 @@ pub mod scoping;
 pub mod stack;
 pub mod state;
 pub mod temp_writer;
+pub mod terminal;
 pub mod tree_writing;
 
 // Re-export commonly used packages
@@ -2,7 +2,7 @@
 
 use std::{
     fs::canonicalize,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
 /// Function to apply formatting onto a path when printing it.
@@ -23,3 +23,62 @@ pub fn adjust_canonicalisation<P: AsRef<Path>>(p: P) -> PathBuf {
         path
     }
 }
+
+/// Compute the path of `path` relative to the directory `base`. Both of the
+/// paths are expected to be absolute, or to be relative to the same
+/// directory.
+pub fn relative_path(base: impl AsRef<Path>, path: impl AsRef<Path>) -> PathBuf {
+    let base = base.as_ref().components().collect::<Vec<_>>();
+    let path = path.as_ref().components().collect::<Vec<_>>();
+
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+
+    let mut relative = PathBuf::new();
+    relative.extend(base[common..].iter().map(|_| ".."));
+    relative.extend(&path[common..]);
+    relative
+}
+
+/// Normalise a path without consulting the file system, i.e. all of the `.`
+/// components are removed, and each `..` component removes the component
+/// that precedes it.
+pub fn normalise_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut normalised = PathBuf::new();
+
+    for component in path.as_ref().components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(normalised.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                normalised.pop();
+            }
+            // The parent of the root is the root itself.
+            Component::ParentDir if normalised.has_root() => {}
+            component => normalised.push(component),
+        }
+    }
+
+    normalised
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path("/a/b", "/a/b/c.hash"), PathBuf::from("c.hash"));
+        assert_eq!(relative_path("/a/b", "/a/c/d.hash"), PathBuf::from("../c/d.hash"));
+        assert_eq!(relative_path("/a/b/c", "/a/d.hash"), PathBuf::from("../../d.hash"));
+        assert_eq!(relative_path("/a", "/a/b/c/d.hash"), PathBuf::from("b/c/d.hash"));
+    }
+
+    #[test]
+    fn test_normalise_path() {
+        assert_eq!(normalise_path("/a/./b/../c.hash"), PathBuf::from("/a/c.hash"));
+        assert_eq!(normalise_path("/../a.hash"), PathBuf::from("/a.hash"));
+        assert_eq!(normalise_path("../a/../../b.hash"), PathBuf::from("../../b.hash"));
+        assert_eq!(normalise_path("./a/b/.."), PathBuf::from("a"));
+    }
+}
@@ -67,6 +67,11 @@ pub struct StageMetrics {
     /// The collected timings for each section of the stage.
     pub metrics: IndexMap<&'static str, MetricEntry>,
 
+    /// The collected counters of the stage, this is used to record how
+    /// often an event occurs within the stage, e.g. the number of times
+    /// that a shortcut is taken.
+    pub counters: IndexMap<&'static str, usize>,
+
     /// Whether to report RSS statisics. This option is useful to
     /// silence stages that are paralelised or out of order meaning that
     /// measuring RSS at various stages of the compiler pipeline produces
@@ -83,6 +88,10 @@ impl StageMetrics {
             self.metrics.entry(name).and_modify(|e| *e += *time).or_insert(*time);
         }
 
+        for (name, count) in &other.counters {
+            *self.counters.entry(name).or_default() += count;
+        }
+
         self.report_rss &= other.report_rss;
     }
 
@@ -90,6 +99,11 @@ impl StageMetrics {
     pub fn iter(&self) -> impl Iterator<Item = (&'static str, MetricEntry)> + '_ {
         self.metrics.iter().map(|(item, time)| (*item, *time))
     }
+
+    /// Create an iterator over the collected counters.
+    pub fn iter_counters(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.counters.iter().map(|(item, count)| (*item, *count))
+    }
 }
 
 /// A trait that can be implemented by a compiler stage in order to
@@ -103,6 +117,11 @@ pub trait HasMutMetrics {
         self.metrics().metrics.entry(name).and_modify(|e| *e += item).or_insert(item);
     }
 
+    /// Increment the counter with the given name.
+    fn count(&mut self, name: &'static str) {
+        *self.metrics().counters.entry(name).or_default() += 1;
+    }
+
     /// Time an item and add the metric to the stage.
     fn record<T>(&mut self, name: &'static str, f: impl FnOnce(&mut Self) -> T) -> T {
         let mut record = MetricEntry::default();
@@ -147,6 +166,9 @@ pub struct CellStageMetrics {
     /// The collected timings for each section of the stage.
     pub timings: RefCell<IndexMap<&'static str, MetricEntry>>,
 
+    /// The collected counters of the stage.
+    pub counters: RefCell<IndexMap<&'static str, usize>>,
+
     pub report_rss: Cell<bool>,
 }
 
@@ -154,6 +176,12 @@ impl CellStageMetrics {
     /// Merge another set of metrics into this one.
     pub fn merge(&self, other: &CellStageMetrics) {
         self.timings.borrow_mut().extend(other.timings.borrow().iter());
+
+        let mut counters = self.counters.borrow_mut();
+        for (name, count) in other.counters.borrow().iter() {
+            *counters.entry(name).or_default() += count;
+        }
+
         self.report_rss.update(|value| value & other.report_rss.get());
     }
 }
@@ -167,6 +195,11 @@ pub trait HasMetrics {
         self.metrics().timings.borrow_mut().entry(name).and_modify(|e| *e += item).or_insert(item);
     }
 
+    /// Increment the counter with the given name.
+    fn count(&self, name: &'static str) {
+        *self.metrics().counters.borrow_mut().entry(name).or_default() += 1;
+    }
+
     /// Time an the execution of item, whilst saving the result to the
     /// metrics.
     fn record<T>(&self, name: &'static str, f: impl FnOnce(&Self) -> T) -> T {
@@ -188,7 +221,11 @@ pub trait HasMetrics {
 
 impl From<CellStageMetrics> for StageMetrics {
     fn from(metrics: CellStageMetrics) -> Self {
-        StageMetrics { metrics: metrics.timings.into_inner(), report_rss: metrics.report_rss.get() }
+        StageMetrics {
+            metrics: metrics.timings.into_inner(),
+            counters: metrics.counters.into_inner(),
+            report_rss: metrics.report_rss.get(),
+        }
     }
 }
 
@@ -253,3 +290,52 @@ cfg_match! {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Stage {
+        metrics: CellStageMetrics,
+    }
+
+    impl HasMetrics for Stage {
+        fn metrics(&self) -> &CellStageMetrics {
+            &self.metrics
+        }
+    }
+
+    #[test]
+    fn stage_metrics_merge_counters() {
+        let mut metrics = StageMetrics::default();
+        metrics.count("reused");
+        metrics.count("reused");
+
+        let mut other = StageMetrics::default();
+        other.count("reused");
+        other.count("inlined");
+
+        metrics.merge(&other);
+
+        // The counters are reported by `--timings` in the order that they
+        // were first recorded.
+        assert_eq!(metrics.iter_counters().collect::<Vec<_>>(), [("reused", 3), ("inlined", 1)]);
+    }
+
+    #[test]
+    fn cell_stage_metrics_merge_counters() {
+        let stage = Stage { metrics: CellStageMetrics::default() };
+        stage.count("reused");
+
+        let other = Stage { metrics: CellStageMetrics::default() };
+        other.count("inlined");
+        other.count("reused");
+        other.count("reused");
+
+        stage.metrics.merge(&other.metrics);
+
+        // The counters are kept when the metrics are converted for `--timings`.
+        let metrics = StageMetrics::from(stage.metrics);
+        assert_eq!(metrics.iter_counters().collect::<Vec<_>>(), [("reused", 3), ("inlined", 1)]);
+    }
+}
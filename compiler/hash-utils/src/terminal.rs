@@ -0,0 +1,46 @@
+//! Utilities to query the terminal that the compiler writes to.
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+/// Get the width of the terminal that the standard error stream is written
+/// to, in columns. The `COLUMNS` environment variable takes precedence over
+/// the width of the terminal. If the standard error stream isn't a terminal,
+/// then there is no width.
+pub fn stderr_width() -> Option<usize> {
+    if !io::stderr().is_terminal() {
+        return None;
+    }
+
+    let columns = env::var("COLUMNS").ok().and_then(|columns| columns.parse::<usize>().ok());
+    if let Some(columns) = columns.filter(|&columns| columns > 0) {
+        return Some(columns);
+    }
+
+    query_stderr_width()
+}
+
+cfg_match! {
+    cfg(unix) => {
+        fn query_stderr_width() -> Option<usize> {
+            use std::mem;
+
+            use libc::{ioctl, winsize, STDERR_FILENO, TIOCGWINSZ};
+
+            unsafe {
+                let mut size: winsize = mem::zeroed();
+                if ioctl(STDERR_FILENO, TIOCGWINSZ, &mut size) == 0 && size.ws_col > 0 {
+                    Some(size.ws_col as usize)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+    _ => {
+        fn query_stderr_width() -> Option<usize> {
+            None
+        }
+    }
+}
@@ -1,4 +1,6 @@
 //! Hash Compiler VM bytecode/instruction set.
+use hash_utils::smallvec::{smallvec, SmallVec};
+
 use crate::register::Register;
 
 /// The VM instruction set.
@@ -36,6 +38,54 @@ pub enum Instruction {
     Push64 {
         l1: Register,
     },
+    /// Allocate an object on the heap with as many elements of the layout
+    /// `layout` as the value of `count`, and write a pointer to the object
+    /// into `dest`. The memory of the object is zeroed.
+    Alloc {
+        dest: Register,
+        layout: u32,
+        count: Register,
+    },
+    /// Load the 8bit cell that `ptr` points to
+    Load8 {
+        dest: Register,
+        ptr: Register,
+    },
+    /// Load the 16bit cell that `ptr` points to
+    Load16 {
+        dest: Register,
+        ptr: Register,
+    },
+    /// Load the 32bit cell that `ptr` points to
+    Load32 {
+        dest: Register,
+        ptr: Register,
+    },
+    /// Load the 64bit cell that `ptr` points to
+    Load64 {
+        dest: Register,
+        ptr: Register,
+    },
+    /// Store an 8bit cell at the location that `ptr` points to
+    Store8 {
+        src: Register,
+        ptr: Register,
+    },
+    /// Store a 16bit cell at the location that `ptr` points to
+    Store16 {
+        src: Register,
+        ptr: Register,
+    },
+    /// Store a 32bit cell at the location that `ptr` points to
+    Store32 {
+        src: Register,
+        ptr: Register,
+    },
+    /// Store a 64bit cell at the location that `ptr` points to
+    Store64 {
+        src: Register,
+        ptr: Register,
+    },
     /// Unsigned integer 8bit Addition
     Add8 {
         l1: Register,
@@ -358,6 +408,12 @@ pub enum Instruction {
     Call {
         func: Register,
     },
+    /// Call a foreign function at the given index within the foreign
+    /// function table. The arguments are read from the registers `r0..rN`,
+    /// and the return value is written into `r0`.
+    CallForeign {
+        func: Register,
+    },
     /// Copy a value from source register to destination register.
     Mov {
         src: Register,
@@ -374,13 +430,13 @@ pub enum Instruction {
         location: Register,
     },
     /// Jump if the comparison value yields a '> zero', or in other words the
-    /// right is greater than left
+    /// left is greater than right
     JmpPos {
         l1: Register,
         location: Register,
     },
     /// Jump if the comparison value yields a '< zero', or in other words the
-    /// left is greater than right
+    /// right is greater than left
     JmpNeg {
         l1: Register,
         location: Register,
@@ -391,10 +447,127 @@ pub enum Instruction {
         l1: Register,
         location: Register,
     },
-    /// Compare both values and store the result in `l1`. This will return
-    /// either a one, zero or negative one.
+    /// Compare both values as unsigned 64bit integers and store the result in
+    /// `l1`. This will return either a one, zero or negative one.
     Cmp {
         l1: Register,
         l2: Register,
     },
+    /// Write an immediate value into the destination register.
+    Write {
+        dest: Register,
+        value: u64,
+    },
+    /// Write a one into `l1` if the last arithmetic operation overflowed, and
+    /// a zero otherwise.
+    ReadOverflow {
+        l1: Register,
+    },
+    /// Stop the execution of the program. The `id` is chosen by whoever
+    /// generated the bytecode, and is used to describe why the execution
+    /// was stopped.
+    Trap {
+        id: u32,
+    },
+}
+
+impl Instruction {
+    /// Get all of the registers that the instruction reads from or writes to.
+    pub fn registers(&self) -> SmallVec<[Register; 3]> {
+        match *self {
+            Instruction::Pop8 { l1 }
+            | Instruction::Pop16 { l1 }
+            | Instruction::Pop32 { l1 }
+            | Instruction::Pop64 { l1 }
+            | Instruction::Push8 { l1 }
+            | Instruction::Push16 { l1 }
+            | Instruction::Push32 { l1 }
+            | Instruction::Push64 { l1 }
+            | Instruction::Not8 { l1 }
+            | Instruction::Not16 { l1 }
+            | Instruction::Not32 { l1 }
+            | Instruction::Not64 { l1 }
+            | Instruction::ReadOverflow { l1 } => smallvec![l1],
+            Instruction::Alloc { dest, count, .. } => smallvec![dest, count],
+            Instruction::Load8 { dest, ptr }
+            | Instruction::Load16 { dest, ptr }
+            | Instruction::Load32 { dest, ptr }
+            | Instruction::Load64 { dest, ptr } => smallvec![dest, ptr],
+            Instruction::Store8 { src, ptr }
+            | Instruction::Store16 { src, ptr }
+            | Instruction::Store32 { src, ptr }
+            | Instruction::Store64 { src, ptr } => smallvec![src, ptr],
+            Instruction::Add8 { l1, l2 }
+            | Instruction::Add16 { l1, l2 }
+            | Instruction::Add32 { l1, l2 }
+            | Instruction::Add64 { l1, l2 }
+            | Instruction::Sub8 { l1, l2 }
+            | Instruction::Sub16 { l1, l2 }
+            | Instruction::Sub32 { l1, l2 }
+            | Instruction::Sub64 { l1, l2 }
+            | Instruction::Div8 { l1, l2 }
+            | Instruction::Div16 { l1, l2 }
+            | Instruction::Div32 { l1, l2 }
+            | Instruction::Div64 { l1, l2 }
+            | Instruction::Mul8 { l1, l2 }
+            | Instruction::Mul16 { l1, l2 }
+            | Instruction::Mul32 { l1, l2 }
+            | Instruction::Mul64 { l1, l2 }
+            | Instruction::Mod8 { l1, l2 }
+            | Instruction::Mod16 { l1, l2 }
+            | Instruction::Mod32 { l1, l2 }
+            | Instruction::Mod64 { l1, l2 }
+            | Instruction::IDiv8 { l1, l2 }
+            | Instruction::IDiv16 { l1, l2 }
+            | Instruction::IDiv32 { l1, l2 }
+            | Instruction::IDiv64 { l1, l2 }
+            | Instruction::IMul8 { l1, l2 }
+            | Instruction::IMul16 { l1, l2 }
+            | Instruction::IMul32 { l1, l2 }
+            | Instruction::IMul64 { l1, l2 }
+            | Instruction::AddF32 { l1, l2 }
+            | Instruction::AddF64 { l1, l2 }
+            | Instruction::SubF32 { l1, l2 }
+            | Instruction::SubF64 { l1, l2 }
+            | Instruction::DivF32 { l1, l2 }
+            | Instruction::DivF64 { l1, l2 }
+            | Instruction::MulF32 { l1, l2 }
+            | Instruction::MulF64 { l1, l2 }
+            | Instruction::ModF32 { l1, l2 }
+            | Instruction::ModF64 { l1, l2 }
+            | Instruction::Xor8 { l1, l2 }
+            | Instruction::Xor16 { l1, l2 }
+            | Instruction::Xor32 { l1, l2 }
+            | Instruction::Xor64 { l1, l2 }
+            | Instruction::Or8 { l1, l2 }
+            | Instruction::Or16 { l1, l2 }
+            | Instruction::Or32 { l1, l2 }
+            | Instruction::Or64 { l1, l2 }
+            | Instruction::And8 { l1, l2 }
+            | Instruction::And16 { l1, l2 }
+            | Instruction::And32 { l1, l2 }
+            | Instruction::And64 { l1, l2 }
+            | Instruction::PowF32 { l1, l2 }
+            | Instruction::PowF64 { l1, l2 }
+            | Instruction::Shl8 { l1, l2 }
+            | Instruction::Shl16 { l1, l2 }
+            | Instruction::Shl32 { l1, l2 }
+            | Instruction::Shl64 { l1, l2 }
+            | Instruction::Shr8 { l1, l2 }
+            | Instruction::Shr16 { l1, l2 }
+            | Instruction::Shr32 { l1, l2 }
+            | Instruction::Shr64 { l1, l2 }
+            | Instruction::Cmp { l1, l2 } => smallvec![l1, l2],
+            Instruction::Call { func } | Instruction::CallForeign { func } => smallvec![func],
+            Instruction::Mov { src, dest } => smallvec![src, dest],
+            Instruction::Jmp { location } => smallvec![location],
+            Instruction::JmpPos { l1, location }
+            | Instruction::JmpNeg { l1, location }
+            | Instruction::JmpZero { l1, location } => smallvec![l1, location],
+            Instruction::Write { dest, .. } => smallvec![dest],
+            Instruction::Syscall { .. } | Instruction::Trap { .. } | Instruction::Return => {
+                smallvec![]
+            }
+        }
+    }
 }
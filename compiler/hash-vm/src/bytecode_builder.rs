@@ -3,11 +3,24 @@
 //! it in the format that the VM expects. This module
 //! might be used by a backend to convert from the Hash IR into
 //! bytecode.
-use crate::bytecode::Instruction;
+use crate::{bytecode::Instruction, register::Register};
+
+/// A location within the program that is being built, the location may be
+/// referred to before it is known, and is patched once the program is built.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Label(usize);
 
 #[derive(Debug, Default)]
 pub struct BytecodeBuilder {
     instructions: Vec<Instruction>,
+
+    /// The location of each [Label], this is [None] for labels that haven't
+    /// been bound yet.
+    labels: Vec<Option<usize>>,
+
+    /// All of the [`Instruction::Write`]s that write the location of a
+    /// [Label], and need to be patched once the program is built.
+    fixups: Vec<(usize, Label)>,
 }
 
 impl BytecodeBuilder {
@@ -15,10 +28,45 @@ impl BytecodeBuilder {
         self.instructions.push(instruction);
         self
     }
+
+    /// Get the location of the next instruction that is added.
+    pub fn position(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Create a new [Label] that isn't bound to any location yet.
+    pub fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+        Label(self.labels.len() - 1)
+    }
+
+    /// Bind the [Label] to the location of the next instruction that is added.
+    pub fn bind_label(&mut self, label: Label) -> &mut Self {
+        debug_assert!(self.labels[label.0].is_none(), "label is bound twice");
+
+        self.labels[label.0] = Some(self.position());
+        self
+    }
+
+    /// Write the location of the [Label] into the `dest` register.
+    pub fn write_label(&mut self, dest: Register, label: Label) -> &mut Self {
+        self.fixups.push((self.position(), label));
+        self.add_instruction(Instruction::Write { dest, value: 0 })
+    }
 }
 
 impl From<BytecodeBuilder> for Vec<Instruction> {
     fn from(builder: BytecodeBuilder) -> Self {
-        builder.instructions
+        let BytecodeBuilder { mut instructions, labels, fixups } = builder;
+
+        for (index, label) in fixups {
+            let location = labels[label.0].expect("label is never bound");
+
+            if let Instruction::Write { value, .. } = &mut instructions[index] {
+                *value = location as u64;
+            }
+        }
+
+        instructions
     }
 }
@@ -20,7 +20,64 @@ impl fmt::Display for StackAccessKind {
 
 #[derive(Debug)]
 pub enum RuntimeError {
-    StackViolationAccess { kind: StackAccessKind, size: u8, total: usize },
+    StackViolationAccess {
+        kind: StackAccessKind,
+        size: u8,
+        total: usize,
+    },
+
+    /// The stack ran out of space, which usually happens when a program
+    /// recurses too deeply.
+    StackOverflow {
+        size: usize,
+    },
+
+    /// A [`Pointer`] was used which doesn't point into an object, or the access
+    /// goes past the end of the object.
+    ///
+    /// [`Pointer`]: crate::heap::Pointer
+    InvalidHeapAccess {
+        address: u64,
+        size: u8,
+    },
+
+    /// An object was allocated with a layout that doesn't exist.
+    UnknownHeapLayout {
+        index: u32,
+    },
+
+    /// An object was allocated that is larger than the VM supports.
+    AllocationTooLarge {
+        size: u64,
+    },
+
+    /// A library that is specified by `#link(...)` could not be loaded.
+    ForeignLibraryNotFound {
+        library: String,
+    },
+
+    /// A symbol could not be found in the library that it was linked against.
+    ForeignSymbolNotFound {
+        library: String,
+        symbol: String,
+    },
+
+    /// The VM cannot call a foreign function with the given signature.
+    UnsupportedForeignSignature {
+        symbol: String,
+    },
+
+    /// A foreign function call referred to an unknown function index.
+    UnknownForeignFn {
+        index: usize,
+    },
+
+    /// The program reached a [`Instruction::Trap`].
+    ///
+    /// [`Instruction::Trap`]: crate::bytecode::Instruction::Trap
+    Trap {
+        id: u32,
+    },
 }
 
 pub type RuntimeResult<T> = Result<T, RuntimeError>;
@@ -32,10 +89,67 @@ impl From<RuntimeError> for Report {
                 Report {
                     kind: ReportKind::Error,
                     title: format!("Stack access violation occurred: tried to `{kind}` {size}bytes from stack, but stack size is {total}" ),
-                    error_code: None,
+                    code: None,
                     contents: vec![],
                 }
             },
+            RuntimeError::StackOverflow { size } => {
+                let mut report = Report::new();
+                report
+                    .kind(ReportKind::Error)
+                    .title("stack overflow")
+                    .add_note(format!("the stack of the VM is {size} bytes"))
+                    .add_help("use `--stack-size` to give the VM a larger stack");
+                report
+            }
+            RuntimeError::InvalidHeapAccess { address, size } => Report {
+                kind: ReportKind::Error,
+                title: format!("invalid heap access of {size} bytes at `{address:#x}`"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::UnknownHeapLayout { index } => Report {
+                kind: ReportKind::Error,
+                title: format!("unknown heap layout `{index}`"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::AllocationTooLarge { size } => Report {
+                kind: ReportKind::Error,
+                title: format!("cannot allocate an object of {size} bytes on the heap"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::ForeignLibraryNotFound { library } => Report {
+                kind: ReportKind::Error,
+                title: format!("failed to load foreign library `{library}`"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::ForeignSymbolNotFound { library, symbol } => Report {
+                kind: ReportKind::Error,
+                title: format!("failed to find symbol `{symbol}` in foreign library `{library}`"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::UnsupportedForeignSignature { symbol } => Report {
+                kind: ReportKind::Error,
+                title: format!("cannot call foreign function `{symbol}` from the VM, its signature is not supported"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::UnknownForeignFn { index } => Report {
+                kind: ReportKind::Error,
+                title: format!("unknown foreign function `{index}`"),
+                code: None,
+                contents: vec![],
+            },
+            RuntimeError::Trap { id } => Report {
+                kind: ReportKind::Error,
+                title: format!("execution was stopped by trap `{id}`"),
+                code: None,
+                contents: vec![],
+            },
         }
     }
 }
@@ -0,0 +1,277 @@
+//! Hash Compiler VM foreign function interface. This module provides a shim
+//! for dynamically loading libraries that are specified with `#link(...)`
+//! and calling foreign functions from within the VM. The arguments to the
+//! foreign function are passed as register sized values, and the signature
+//! of the function is used to determine how the values should be converted
+//! before and after the call.
+//!
+//! Integer and pointer arguments are passed in the general purpose
+//! registers, and floating point arguments are passed in the vector
+//! registers. Each kind of argument is assigned to its registers in order,
+//! independently of the other kind, which is the case under the System V
+//! and AAPCS64 calling conventions.
+//!
+//! @@Future: aggregates and arguments that are passed on the stack are not
+//! currently supported. Supporting them would require a `libffi` style
+//! call interface which builds the call frame dynamically.
+
+use std::ffi::{c_void, CString};
+
+use crate::error::{RuntimeError, RuntimeResult};
+
+/// The maximum number of integer and pointer arguments that a foreign
+/// function may accept.
+pub const MAX_FOREIGN_INT_ARGS: usize = 6;
+
+/// The maximum number of floating point arguments that a foreign function
+/// may accept.
+pub const MAX_FOREIGN_FLOAT_ARGS: usize = 8;
+
+/// The maximum number of arguments that a foreign function may accept.
+pub const MAX_FOREIGN_ARGS: usize = MAX_FOREIGN_INT_ARGS + MAX_FOREIGN_FLOAT_ARGS;
+
+/// The type that all foreign functions that return an integer or a pointer
+/// are called with.
+type IntFn =
+    extern "C" fn(u64, u64, u64, u64, u64, u64, f64, f64, f64, f64, f64, f64, f64, f64) -> u64;
+
+/// The type that all foreign functions that return a float are called with.
+type FloatFn =
+    extern "C" fn(u64, u64, u64, u64, u64, u64, f64, f64, f64, f64, f64, f64, f64, f64) -> f64;
+
+/// Represents the kind of a value that is passed to, or returned from, a
+/// foreign function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForeignValue {
+    /// An integer value of the given size (in bytes).
+    Integer { size: u8, signed: bool },
+
+    /// A floating point value of the given size (in bytes).
+    Float { size: u8 },
+
+    /// A pointer value.
+    Pointer,
+}
+
+impl ForeignValue {
+    /// Convert a value that was returned from a foreign function into a
+    /// register sized value by truncating it, and sign-extending it if
+    /// necessary.
+    fn truncate(self, value: u64) -> u64 {
+        match self {
+            ForeignValue::Integer { size, signed } if size < 8 => {
+                let shift = 64 - (size as u32 * 8);
+
+                if signed {
+                    (((value << shift) as i64) >> shift) as u64
+                } else {
+                    (value << shift) >> shift
+                }
+            }
+            ForeignValue::Float { size: 4 } => value & 0xffff_ffff,
+            _ => value,
+        }
+    }
+}
+
+/// The signature of a foreign function, this is derived from the ABI of the
+/// function instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignSignature {
+    /// The arguments to the function.
+    pub args: Vec<ForeignValue>,
+
+    /// The return value of the function, if the function returns `void`,
+    /// then this is [None].
+    pub ret: Option<ForeignValue>,
+}
+
+impl ForeignSignature {
+    /// Check whether the VM is able to call a function with the given
+    /// signature.
+    pub fn is_supported(&self) -> bool {
+        let floats =
+            self.args.iter().filter(|arg| matches!(arg, ForeignValue::Float { .. })).count();
+
+        floats <= MAX_FOREIGN_FLOAT_ARGS
+            && self.args.len() - floats <= MAX_FOREIGN_INT_ARGS
+            && self.args.iter().chain(self.ret.iter()).all(|arg| match arg {
+                ForeignValue::Integer { size, .. } => *size <= 8,
+                ForeignValue::Float { size } => matches!(size, 4 | 8),
+                ForeignValue::Pointer => true,
+            })
+    }
+}
+
+/// A dynamically loaded library.
+#[derive(Debug)]
+struct ForeignLibrary {
+    /// The name of the library, as specified by `#link(...)`.
+    name: String,
+
+    /// The handle to the library that was returned by `dlopen`.
+    handle: *mut c_void,
+}
+
+impl ForeignLibrary {
+    /// Load the library with the given name. The `c` library is treated
+    /// specially since it is always loaded into the process, and so the
+    /// handle to the process is used instead.
+    #[cfg(unix)]
+    fn open(name: &str) -> RuntimeResult<Self> {
+        let handle = if name == "c" {
+            // SAFETY: passing `NULL` yields a handle to the main program.
+            unsafe { libc::dlopen(std::ptr::null(), libc::RTLD_NOW) }
+        } else {
+            let extension = if cfg!(target_os = "macos") { "dylib" } else { "so" };
+            let path = CString::new(format!("lib{name}.{extension}"))
+                .map_err(|_| RuntimeError::ForeignLibraryNotFound { library: name.to_string() })?;
+
+            // SAFETY: the path is a valid null-terminated string.
+            unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) }
+        };
+
+        if handle.is_null() {
+            return Err(RuntimeError::ForeignLibraryNotFound { library: name.to_string() });
+        }
+
+        Ok(Self { name: name.to_string(), handle })
+    }
+
+    #[cfg(not(unix))]
+    fn open(name: &str) -> RuntimeResult<Self> {
+        Err(RuntimeError::ForeignLibraryNotFound { library: name.to_string() })
+    }
+
+    /// Find the address of the given symbol within the library.
+    #[cfg(unix)]
+    fn symbol(&self, symbol: &str) -> Option<*mut c_void> {
+        let symbol = CString::new(symbol).ok()?;
+
+        // SAFETY: the handle is valid for the lifetime of the library, and
+        // the symbol is a valid null-terminated string.
+        let address = unsafe { libc::dlsym(self.handle, symbol.as_ptr()) };
+        (!address.is_null()).then_some(address)
+    }
+
+    #[cfg(not(unix))]
+    fn symbol(&self, _: &str) -> Option<*mut c_void> {
+        None
+    }
+}
+
+impl Drop for ForeignLibrary {
+    fn drop(&mut self) {
+        // SAFETY: the handle was returned from a successful `dlopen` call.
+        #[cfg(unix)]
+        unsafe {
+            libc::dlclose(self.handle);
+        }
+    }
+}
+
+/// A resolved foreign function.
+#[derive(Debug)]
+struct ForeignFn {
+    /// The address of the function.
+    address: *mut c_void,
+
+    /// The signature of the function.
+    signature: ForeignSignature,
+}
+
+/// The [ForeignFnTable] stores all of the libraries that have been loaded by
+/// the VM, and all of the foreign functions that have been resolved. Foreign
+/// functions are referred to by their index within the table.
+#[derive(Debug, Default)]
+pub struct ForeignFnTable {
+    /// All of the libraries that have been loaded.
+    libraries: Vec<ForeignLibrary>,
+
+    /// All of the functions that have been resolved.
+    fns: Vec<ForeignFn>,
+}
+
+impl ForeignFnTable {
+    /// Resolve the `symbol` from the given `library`, and return the index
+    /// of the foreign function within the table. The library is loaded if
+    /// it hasn't been loaded yet.
+    pub fn resolve(
+        &mut self,
+        library: &str,
+        symbol: &str,
+        signature: ForeignSignature,
+    ) -> RuntimeResult<usize> {
+        if !signature.is_supported() {
+            return Err(RuntimeError::UnsupportedForeignSignature { symbol: symbol.to_string() });
+        }
+
+        let library = match self.libraries.iter().position(|lib| lib.name == library) {
+            Some(index) => &self.libraries[index],
+            None => {
+                self.libraries.push(ForeignLibrary::open(library)?);
+                self.libraries.last().unwrap()
+            }
+        };
+
+        let Some(address) = library.symbol(symbol) else {
+            return Err(RuntimeError::ForeignSymbolNotFound {
+                library: library.name.clone(),
+                symbol: symbol.to_string(),
+            });
+        };
+
+        self.fns.push(ForeignFn { address, signature });
+        Ok(self.fns.len() - 1)
+    }
+
+    /// Call the foreign function at the given index with the provided
+    /// arguments. Arguments that are narrower than a register are passed
+    /// as is, since the callee ignores the upper bits of the register. The
+    /// bits of floats are moved into the vector registers, an `f32` is
+    /// stored in the lower bits of the register.
+    pub(crate) fn call(&self, index: usize, args: &[u64]) -> RuntimeResult<u64> {
+        let Some(ForeignFn { address, signature }) = self.fns.get(index) else {
+            return Err(RuntimeError::UnknownForeignFn { index });
+        };
+
+        let mut ints = [0; MAX_FOREIGN_INT_ARGS];
+        let mut floats = [0.0; MAX_FOREIGN_FLOAT_ARGS];
+        let (mut next_int, mut next_float) = (0, 0);
+
+        for (value, arg) in args.iter().zip(&signature.args) {
+            match arg {
+                ForeignValue::Float { .. } => {
+                    floats[next_float] = f64::from_bits(*value);
+                    next_float += 1;
+                }
+                ForeignValue::Integer { .. } | ForeignValue::Pointer => {
+                    ints[next_int] = *value;
+                    next_int += 1;
+                }
+            }
+        }
+
+        let [a, b, c, d, e, f] = ints;
+        let [g, h, i, j, k, l, m, n] = floats;
+        let address = *address;
+
+        // SAFETY: the signature of the function was checked when it was
+        // resolved. Since the two kinds of arguments are assigned to their
+        // registers independently, the callee only reads the registers of
+        // the arguments that it accepts, and ignores the rest.
+        let value = unsafe {
+            use std::mem::transmute;
+
+            match signature.ret {
+                Some(ForeignValue::Float { .. }) => {
+                    transmute::<_, FloatFn>(address)(a, b, c, d, e, f, g, h, i, j, k, l, m, n)
+                        .to_bits()
+                }
+                _ => transmute::<_, IntFn>(address)(a, b, c, d, e, f, g, h, i, j, k, l, m, n),
+            }
+        };
+
+        Ok(signature.ret.map_or(0, |ret| ret.truncate(value)))
+    }
+}
@@ -1,29 +1,266 @@
-//! Hash Compiler VM heap definitions.
-#![allow(dead_code)]
-use std::iter;
+//! Hash Compiler VM heap definitions. Values that are allocated dynamically,
+//! i.e. aggregates, strings and slices, are stored as objects on the [Heap].
+//! Each object starts with an [ObjectHeader] which describes the layout of
+//! the elements of the object, so that the pointers that are stored within
+//! the object can be found.
+//!
+//! Memory is reclaimed with a mark-and-sweep collector. The roots of the
+//! collection are found conservatively, i.e. every word in the registers
+//! and on the stack that looks like a pointer to an object keeps that object
+//! alive. Pointers within objects are found precisely using the layouts of
+//! the objects.
+use crate::error::{RuntimeError, RuntimeResult};
+
+/// The bit that is set in all [Pointer]s, which makes it unlikely that
+/// a small integer is mistaken for a pointer.
+const POINTER_TAG: u64 = 1 << 63;
+
+/// The number of low bits of a [Pointer] that store the offset within the
+/// object, the bits above it store the index of the object.
+const OFFSET_BITS: u32 = 32;
+
+/// The number of bytes that can be allocated before the first collection.
+const INITIAL_THRESHOLD: usize = 64 * 1024;
+
+/// The layout of the elements of an object, objects have one or more
+/// elements of the same layout, e.g. the characters of a string or the
+/// items of a slice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectLayout {
+    /// The size of each element in bytes.
+    pub size: u32,
+
+    /// The offsets of the words within each element that store a [Pointer].
+    pub pointers: Vec<u32>,
+}
+
+impl ObjectLayout {
+    pub fn new(size: u32, pointers: Vec<u32>) -> Self {
+        Self { size, pointers }
+    }
+
+    /// Create a layout for elements that don't store any pointers.
+    pub fn bytes(size: u32) -> Self {
+        Self { size, pointers: vec![] }
+    }
+}
+
+/// The header of an object, which describes what the object stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectHeader {
+    /// The index of the [ObjectLayout] of the elements of the object.
+    pub layout: u32,
+
+    /// The number of elements of the object.
+    pub count: u32,
+
+    /// Whether the object has been reached during the current collection.
+    marked: bool,
+}
+
+#[derive(Debug)]
+struct Object {
+    header: ObjectHeader,
+    data: Vec<u8>,
+}
+
+/// A pointer to a location within an object on the [Heap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pointer(pub u64);
+
+impl Pointer {
+    fn new(index: usize, offset: u32) -> Self {
+        Pointer(POINTER_TAG | ((index as u64) << OFFSET_BITS) | u64::from(offset))
+    }
+
+    /// Get the index of the object that the pointer points into.
+    fn index(self) -> Option<usize> {
+        (self.0 & POINTER_TAG != 0).then_some(((self.0 & !POINTER_TAG) >> OFFSET_BITS) as usize)
+    }
+
+    /// Get the offset of the pointer within its object.
+    fn offset(self) -> usize {
+        (self.0 & ((1 << OFFSET_BITS) - 1)) as usize
+    }
+}
 
 /// The Heap of the VM.
 #[derive(Debug)]
 pub struct Heap {
-    values: Vec<u8>,
+    /// All of the layouts that objects may have.
+    layouts: Vec<ObjectLayout>,
+
+    /// The objects of the heap, the slots of objects that have been
+    /// reclaimed are [None] until they are reused.
+    objects: Vec<Option<Object>>,
+
+    /// The slots of [`Heap::objects`] that can be reused.
+    free: Vec<usize>,
+
+    /// The number of bytes that all of the objects take up.
+    allocated: usize,
+
+    /// A collection should be made once [`Heap::allocated`] exceeds this
+    /// number of bytes.
+    threshold: usize,
 }
 
-pub struct Pointer(pub u64);
+impl Default for Heap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Heap {
     pub fn new() -> Self {
-        Heap { values: vec![] }
+        Heap {
+            layouts: vec![],
+            objects: vec![],
+            free: vec![],
+            allocated: 0,
+            threshold: INITIAL_THRESHOLD,
+        }
     }
 
-    pub fn allocate(&mut self, size: u64) -> Pointer {
-        let offset = self.values.len();
+    /// Add a layout that objects may be allocated with, and get its index.
+    pub fn add_layout(&mut self, layout: ObjectLayout) -> u32 {
+        self.layouts.push(layout);
+        (self.layouts.len() - 1) as u32
+    }
 
-        self.values.extend(iter::repeat(0).take(size.try_into().unwrap()));
+    /// Get the layout with the given index.
+    pub fn layout(&self, index: u32) -> Option<&ObjectLayout> {
+        self.layouts.get(index as usize)
+    }
+
+    /// Get the number of bytes that all of the objects take up.
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocated
+    }
 
-        Pointer(offset.try_into().unwrap())
+    /// Get the number of objects that haven't been reclaimed.
+    pub fn object_count(&self) -> usize {
+        self.objects.len() - self.free.len()
     }
 
-    pub fn free(&self, _ptr: Pointer) {
-        todo!()
+    /// Whether enough has been allocated since the last collection for
+    /// another collection to be made.
+    pub fn should_collect(&self) -> bool {
+        self.allocated >= self.threshold
+    }
+
+    /// Allocate an object with `count` elements of the given layout. The
+    /// memory of the object is zeroed.
+    pub fn allocate(&mut self, layout: u32, count: u32) -> RuntimeResult<Pointer> {
+        let element =
+            self.layout(layout).ok_or(RuntimeError::UnknownHeapLayout { index: layout })?;
+        let size = u64::from(element.size) * u64::from(count);
+
+        if size >= 1 << OFFSET_BITS {
+            return Err(RuntimeError::AllocationTooLarge { size });
+        }
+
+        let object = Object {
+            header: ObjectHeader { layout, count, marked: false },
+            data: vec![0; size as usize],
+        };
+        self.allocated += object.data.len();
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.objects[index] = Some(object);
+                index
+            }
+            None => {
+                self.objects.push(Some(object));
+                self.objects.len() - 1
+            }
+        };
+
+        Ok(Pointer::new(index, 0))
+    }
+
+    /// Get the header of the object that the pointer points into.
+    pub fn header(&self, ptr: Pointer) -> Option<&ObjectHeader> {
+        self.object(ptr).map(|object| &object.header)
+    }
+
+    /// Read `size` bytes at the location of the pointer.
+    pub fn read(&self, ptr: Pointer, size: u8) -> RuntimeResult<&[u8]> {
+        let (start, end) = (ptr.offset(), ptr.offset() + size as usize);
+
+        self.object(ptr)
+            .and_then(|object| object.data.get(start..end))
+            .ok_or(RuntimeError::InvalidHeapAccess { address: ptr.0, size })
+    }
+
+    /// Write the bytes at the location of the pointer.
+    pub fn write(&mut self, ptr: Pointer, value: &[u8]) -> RuntimeResult<()> {
+        let (start, end) = (ptr.offset(), ptr.offset() + value.len());
+        let size = value.len() as u8;
+
+        ptr.index()
+            .and_then(|index| self.objects.get_mut(index)?.as_mut())
+            .and_then(|object| object.data.get_mut(start..end))
+            .ok_or(RuntimeError::InvalidHeapAccess { address: ptr.0, size })?
+            .copy_from_slice(value);
+
+        Ok(())
+    }
+
+    fn object(&self, ptr: Pointer) -> Option<&Object> {
+        self.objects.get(ptr.index()?)?.as_ref()
+    }
+
+    /// Reclaim all of the objects that can't be reached from the given
+    /// roots, any of the roots may be a word that isn't a pointer. Returns
+    /// the number of bytes that were reclaimed.
+    pub fn collect(&mut self, roots: impl IntoIterator<Item = u64>) -> usize {
+        let mut pending: Vec<_> =
+            roots.into_iter().filter_map(|word| Pointer(word).index()).collect();
+
+        while let Some(index) = pending.pop() {
+            let Some(Some(object)) = self.objects.get_mut(index) else {
+                continue;
+            };
+
+            if object.header.marked {
+                continue;
+            }
+
+            object.header.marked = true;
+
+            let layout = &self.layouts[object.header.layout as usize];
+            let elements = object.data.chunks_exact(layout.size.max(1) as usize);
+
+            for element in elements {
+                for offset in &layout.pointers {
+                    let offset = *offset as usize;
+
+                    if let Some(word) = element.get(offset..offset + 8) {
+                        let word = u64::from_be_bytes(word.try_into().unwrap());
+                        pending.extend(Pointer(word).index());
+                    }
+                }
+            }
+        }
+
+        let mut reclaimed = 0;
+
+        for (index, slot) in self.objects.iter_mut().enumerate() {
+            match slot {
+                Some(object) if object.header.marked => object.header.marked = false,
+                Some(object) => {
+                    reclaimed += object.data.len();
+                    *slot = None;
+                    self.free.push(index);
+                }
+                None => {}
+            }
+        }
+
+        self.allocated -= reclaimed;
+        self.threshold = INITIAL_THRESHOLD.max(self.allocated * 2);
+        reclaimed
     }
 }
@@ -1,5 +1,4 @@
 //! Hash Compiler VM crate.
-mod heap;
 mod stack;
 
 pub mod bytecode;
@@ -7,4 +6,7 @@ pub mod register;
 
 pub mod bytecode_builder;
 pub mod error;
+pub mod ffi;
+pub mod heap;
+pub mod observer;
 pub mod vm;
@@ -0,0 +1,73 @@
+//! Hash Compiler VM observation hooks. A [VmObserver] is notified about every
+//! instruction that the [Interpreter] runs, which can be used to trace the
+//! execution of a program, or to implement a debugger that single-steps
+//! through a program and inspects its frames.
+use std::{fmt, io};
+
+use hash_utils::itertools::Itertools;
+
+use crate::{bytecode::Instruction, register::Register, vm::Interpreter};
+
+/// What the [Interpreter] should do before it runs the next instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmControl {
+    /// Run the instruction.
+    Continue,
+
+    /// Stop running before the instruction, [`Interpreter::run`] then returns
+    /// and the state of the [Interpreter] can be inspected. The execution can
+    /// be resumed with [`Interpreter::step`] or [`Interpreter::run`].
+    Pause,
+}
+
+pub trait VmObserver {
+    /// Called by [`Interpreter::run`] before the `instruction` at `ip` is run.
+    fn before_instruction(
+        &mut self,
+        _vm: &Interpreter,
+        _ip: usize,
+        _instruction: Instruction,
+    ) -> VmControl {
+        VmControl::Continue
+    }
+
+    /// Called after the `instruction` at `ip` has been run successfully.
+    fn after_instruction(&mut self, _vm: &Interpreter, _ip: usize, _instruction: Instruction) {}
+}
+
+impl fmt::Debug for dyn VmObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VmObserver")
+    }
+}
+
+/// A [VmObserver] that writes each instruction that is run, the values of
+/// the registers of the instruction, and the current depth of the stack to
+/// the given stream.
+pub struct TraceObserver<W> {
+    stream: W,
+}
+
+impl<W: io::Write> TraceObserver<W> {
+    pub fn new(stream: W) -> Self {
+        Self { stream }
+    }
+}
+
+impl<W: io::Write> VmObserver for TraceObserver<W> {
+    fn after_instruction(&mut self, vm: &Interpreter, ip: usize, instruction: Instruction) {
+        let registers = instruction
+            .registers()
+            .into_iter()
+            .map(|register| format!("{register}={:#x}", vm.registers().get_register64(register)))
+            .join(" ");
+
+        // Tracing is best effort, a failure to write shouldn't stop the program.
+        let _ = writeln!(
+            self.stream,
+            "{ip:>6}: {instruction:?} [{registers}] depth={} sp={}",
+            vm.frames().len(),
+            vm.registers().get_register64(Register::STACK_POINTER),
+        );
+    }
+}
@@ -1,8 +1,9 @@
 //! Hash Compiler VM register related logic.
+use std::fmt;
 
 /// Register type, we reserve the last 3 [Register] indices (by convention) to
 /// store the stack pointer, instruction pointer and the base pointer.
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
 pub struct Register(u8);
 
 impl Register {
@@ -15,11 +16,28 @@ impl Register {
     /// The current instruction pointer register (RIP)
     pub const INSTRUCTION_POINTER: Register = Register(255);
 
-    pub fn new(index: u8) -> Self {
+    pub const fn new(index: u8) -> Self {
         Self(index)
     }
 }
 
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Register::BASE_POINTER => write!(f, "bp"),
+            Register::STACK_POINTER => write!(f, "sp"),
+            Register::INSTRUCTION_POINTER => write!(f, "ip"),
+            Register(index) => write!(f, "r{index}"),
+        }
+    }
+}
+
+impl fmt::Debug for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
 /// The register set
 #[derive(Debug)]
 pub struct RegisterSet {
@@ -19,15 +19,41 @@ impl Stack {
         Stack { data: vec![0; size], stack_pointer: 0 }
     }
 
+    /// Get the size of the stack in bytes.
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Get the current offset of the top of the stack.
+    pub fn pointer(&self) -> usize {
+        self.stack_pointer
+    }
+
+    /// Get the number of bytes that can still be pushed onto the stack.
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.stack_pointer
+    }
+
+    /// Get all of the bytes that have been pushed onto the stack.
+    pub fn contents(&self) -> &[u8] {
+        &self.data[..self.stack_pointer]
+    }
+
+    /// Discard everything that was pushed onto the stack after the top of the
+    /// stack was at the given offset.
+    pub fn unwind(&mut self, pointer: usize) {
+        debug_assert!(pointer <= self.stack_pointer, "stack is unwound past its top");
+        self.stack_pointer = pointer;
+    }
+
     /// Method that verifies that a particular call to modify the stack storage
     /// is sane and safe.
     pub fn verify_access(&self, access_kind: StackAccessKind, size: u8) -> RuntimeResult<()> {
         match access_kind {
-            StackAccessKind::Pop if self.stack_pointer > (size as usize) => Ok(()),
-            StackAccessKind::Push if self.data.len() - self.stack_pointer > (size as usize) => {
-                Ok(())
-            }
-            _ => Err(RuntimeError::StackViolationAccess {
+            StackAccessKind::Pop if self.stack_pointer >= (size as usize) => Ok(()),
+            StackAccessKind::Push if self.remaining() >= (size as usize) => Ok(()),
+            StackAccessKind::Push => Err(RuntimeError::StackOverflow { size: self.data.len() }),
+            StackAccessKind::Pop => Err(RuntimeError::StackViolationAccess {
                 kind: access_kind,
                 size,
                 total: self.data.len(),
@@ -1,15 +1,40 @@
 //! Hash Compiler virtual machine implementation and bytecode runner.
 
-use std::cell::Cell;
+use std::{cell::Cell, cmp::Ordering};
 
 use crate::{
     bytecode::Instruction,
     error::RuntimeError,
+    ffi::{ForeignFnTable, MAX_FOREIGN_ARGS},
+    heap::{Heap, Pointer},
+    observer::{VmControl, VmObserver},
     register::{Register, RegisterSet},
     stack::Stack,
 };
 
-const DEFAULT_STACK_SIZE: usize = 10_000;
+/// The size of the stack in bytes, unless the [Interpreter] is created with
+/// [`Interpreter::with_stack_size`].
+pub const DEFAULT_STACK_SIZE: usize = 10_000;
+
+/// The number of registers that hold the arguments of a call, starting from
+/// `r0`. Once the call returns, the same registers hold the returned value.
+pub const ARGUMENT_REGISTERS: usize = 16;
+
+/// The number of bytes that a call pushes onto the stack, i.e. the return
+/// address and the base pointer of the caller.
+const FRAME_HEADER_SIZE: usize = 16;
+
+/// A [CallFrame] records a call that hasn't returned yet.
+#[derive(Debug, Clone, Copy)]
+pub struct CallFrame {
+    /// The location of the instruction that follows the call.
+    pub return_address: usize,
+
+    /// The top of the stack once the return address and the base pointer of
+    /// the caller have been pushed, everything above it belongs to the
+    /// callee and is discarded when the callee returns.
+    pub frame_pointer: usize,
+}
 
 /// Interpreter flags represent generated context from the current
 /// execution. This flags store information about the last executed
@@ -39,8 +64,16 @@ pub struct Interpreter {
     instructions: Vec<Instruction>,
     /// We have 256 [Register]s available to the interpreter at any time
     registers: RegisterSet,
-    // /// The interpreter [Heap] containing heap allocated values that are not contained on the
-    // stack heap: Heap,
+    /// All of the foreign functions that the program may call
+    foreign_fns: ForeignFnTable,
+    /// The [CallFrame]s of all of the calls that haven't returned yet, the
+    /// innermost call is last.
+    frames: Vec<CallFrame>,
+    /// The interpreter [Heap] containing heap allocated values that are not
+    /// contained on the stack
+    heap: Heap,
+    /// The [VmObserver] that is notified about each instruction that is run
+    observer: Option<Box<dyn VmObserver>>,
 }
 
 impl Default for Interpreter {
@@ -52,19 +85,33 @@ impl Default for Interpreter {
 impl Interpreter {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_stack_size(DEFAULT_STACK_SIZE)
+    }
+
+    /// Create an [Interpreter] with a stack of the given size in bytes.
+    #[must_use]
+    pub fn with_stack_size(size: usize) -> Self {
         Self {
-            stack: Stack::new(DEFAULT_STACK_SIZE),
+            stack: Stack::new(size),
             instructions: Vec::new(),
             registers: RegisterSet::default(),
             flags: InterpreterFlags::default(),
+            foreign_fns: ForeignFnTable::default(),
+            frames: Vec::new(),
+            heap: Heap::new(),
+            observer: None,
         }
     }
 
     fn run_next_instruction(&mut self) -> Result<(), RuntimeError> {
         let ip = self.get_instruction_pointer();
-        let instruction = self.instructions.get(ip).unwrap();
+        let instruction = self.instructions[ip];
+
+        // The instruction pointer is advanced before the instruction is run,
+        // so that jumps and calls can freely overwrite it.
+        self.set_instruction_pointer(ip + 1);
 
-        match *instruction {
+        match instruction {
             Instruction::Add8 { l1, l2 } => {
                 let r1 = self.registers.get_register8(l1);
                 let r2 = self.registers.get_register8(l2);
@@ -744,13 +791,69 @@ impl Interpreter {
                 let r1 = self.registers.get_register64(l1);
                 let r2 = self.registers.get_register64(l2);
 
-                let value = if r1 >= r2 {
-                    (r1 - r2).to_be_bytes()
-                } else {
-                    (-((r2 - r1) as i64)).to_be_bytes()
+                let value: i64 = match r1.cmp(&r2) {
+                    Ordering::Less => -1,
+                    Ordering::Equal => 0,
+                    Ordering::Greater => 1,
                 };
 
-                self.registers.set_register_8b(l1, &value);
+                self.registers.set_register_8b(l1, &value.to_be_bytes());
+            }
+            Instruction::Write { dest, value } => {
+                self.registers.set_register64(dest, value);
+            }
+            Instruction::ReadOverflow { l1 } => {
+                self.registers.set_register64(l1, self.flags.overflow.get().into());
+            }
+            Instruction::Trap { id } => return Err(RuntimeError::Trap { id }),
+            Instruction::Alloc { dest, layout, count } => {
+                if self.heap.should_collect() {
+                    self.collect_garbage();
+                }
+
+                let count = self.registers.get_register64(count);
+                let count = count
+                    .try_into()
+                    .map_err(|_| RuntimeError::AllocationTooLarge { size: count })?;
+
+                let ptr = self.heap.allocate(layout, count)?;
+                self.registers.set_register64(dest, ptr.0);
+            }
+            Instruction::Load8 { dest, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                let value = self.heap.read(ptr, 1)?;
+                self.registers.set_register_b(dest, value.try_into().unwrap());
+            }
+            Instruction::Load16 { dest, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                let value = self.heap.read(ptr, 2)?;
+                self.registers.set_register_2b(dest, value.try_into().unwrap());
+            }
+            Instruction::Load32 { dest, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                let value = self.heap.read(ptr, 4)?;
+                self.registers.set_register_4b(dest, value.try_into().unwrap());
+            }
+            Instruction::Load64 { dest, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                let value = self.heap.read(ptr, 8)?;
+                self.registers.set_register_8b(dest, value.try_into().unwrap());
+            }
+            Instruction::Store8 { src, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                self.heap.write(ptr, self.registers.get_register_b(src))?;
+            }
+            Instruction::Store16 { src, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                self.heap.write(ptr, self.registers.get_register_2b(src))?;
+            }
+            Instruction::Store32 { src, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                self.heap.write(ptr, self.registers.get_register_4b(src))?;
+            }
+            Instruction::Store64 { src, ptr } => {
+                let ptr = Pointer(self.registers.get_register64(ptr));
+                self.heap.write(ptr, self.registers.get_register_8b(src))?;
             }
             Instruction::Pop8 { l1 } => {
                 // Pop the top byte on top of the stack and put it into the register
@@ -791,19 +894,22 @@ impl Interpreter {
 
             // Function related instructions
             Instruction::Call { func } => {
-                // Save the ip onto the stack
-                self.stack.push64(
-                    &self.registers.get_register64(Register::INSTRUCTION_POINTER).to_be_bytes(),
-                )?;
-                // Save the bp onto the stack
+                // Calls can't be made once the stack has no space for another frame
+                if self.stack.remaining() < FRAME_HEADER_SIZE {
+                    return Err(RuntimeError::StackOverflow { size: self.stack.size() });
+                }
+
+                // Save the return address and the bp onto the stack
+                let return_address = self.get_instruction_pointer();
+                self.stack.push64(&(return_address as u64).to_be_bytes())?;
                 self.stack
                     .push64(&self.registers.get_register64(Register::BASE_POINTER).to_be_bytes())?;
 
-                // Set the new bp as the stack pointer
-                self.registers.set_register64(
-                    Register::BASE_POINTER,
-                    self.registers.get_register64(Register::STACK_POINTER),
-                );
+                let frame_pointer = self.stack.pointer();
+                self.frames.push(CallFrame { return_address, frame_pointer });
+
+                // Set the new bp as the top of the stack
+                self.registers.set_register64(Register::BASE_POINTER, frame_pointer as u64);
 
                 // Jump to the function
                 self.registers.set_register64(
@@ -812,11 +918,10 @@ impl Interpreter {
                 );
             }
             Instruction::Return => {
-                // Set the stack pointer back to the base pointer
-                self.registers.set_register64(
-                    Register::STACK_POINTER,
-                    self.registers.get_register64(Register::BASE_POINTER),
-                );
+                // Discard everything that the callee left on the stack
+                if let Some(frame) = self.frames.pop() {
+                    self.stack.unwind(frame.frame_pointer);
+                }
 
                 // Get the BP from stack and set it
                 self.registers.set_register64(
@@ -830,9 +935,22 @@ impl Interpreter {
                     u64::from_be_bytes(*self.stack.pop64()?),
                 );
             }
+            Instruction::CallForeign { func } => {
+                let index = self.registers.get_register64(func) as usize;
+
+                let args: [u64; MAX_FOREIGN_ARGS] = std::array::from_fn(|register| {
+                    self.registers.get_register64(Register::new(register as u8))
+                });
+
+                let value = self.foreign_fns.call(index, &args)?;
+                self.registers.set_register64(Register::new(0), value);
+            }
             Instruction::Syscall { .. } => todo!(),
         };
 
+        // Keep the sp in sync with the top of the stack
+        self.registers.set_register64(Register::STACK_POINTER, self.stack.pointer() as u64);
+
         Ok(())
     }
 
@@ -850,6 +968,70 @@ impl Interpreter {
         self.instructions = program;
     }
 
+    pub fn program(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Set the [VmObserver] that is notified about each instruction that is
+    /// run, replacing any previous observer.
+    pub fn set_observer(&mut self, observer: impl VmObserver + 'static) {
+        self.observer = Some(Box::new(observer));
+    }
+
+    /// Remove the [VmObserver] of the interpreter.
+    pub fn take_observer(&mut self) -> Option<Box<dyn VmObserver>> {
+        self.observer.take()
+    }
+
+    /// Call the function with the [VmObserver] of the interpreter, if there is
+    /// one.
+    fn notify_observer<T>(&mut self, f: impl FnOnce(&mut dyn VmObserver, &Self) -> T) -> Option<T> {
+        let mut observer = self.observer.take()?;
+        let result = f(observer.as_mut(), self);
+
+        self.observer = Some(observer);
+        Some(result)
+    }
+
+    pub fn foreign_fns(&self) -> &ForeignFnTable {
+        &self.foreign_fns
+    }
+
+    pub fn foreign_fns_mut(&mut self) -> &mut ForeignFnTable {
+        &mut self.foreign_fns
+    }
+
+    pub fn heap(&self) -> &Heap {
+        &self.heap
+    }
+
+    pub fn heap_mut(&mut self) -> &mut Heap {
+        &mut self.heap
+    }
+
+    /// Reclaim all of the objects on the heap that can't be reached from the
+    /// registers or the stack. Returns the number of bytes that were
+    /// reclaimed.
+    pub fn collect_garbage(&mut self) -> usize {
+        let registers =
+            (0..=u8::MAX).map(|index| self.registers.get_register64(Register::new(index)));
+
+        // Values are pushed onto the stack without any alignment, so a pointer
+        // may start at any byte of the stack.
+        let stack = self
+            .stack
+            .contents()
+            .windows(8)
+            .map(|word| u64::from_be_bytes(word.try_into().unwrap()));
+
+        self.heap.collect(registers.chain(stack))
+    }
+
+    /// Get the [CallFrame]s of all of the calls that haven't returned yet.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+
     pub fn registers(&self) -> &RegisterSet {
         &self.registers
     }
@@ -858,24 +1040,40 @@ impl Interpreter {
         &mut self.registers
     }
 
-    pub fn run(&mut self) -> Result<(), RuntimeError> {
+    /// Whether the instruction pointer has moved past the last instruction
+    /// of the program.
+    pub fn is_finished(&self) -> bool {
+        self.get_instruction_pointer() >= self.instructions.len()
+    }
+
+    /// Run the instruction at the current instruction pointer. Unlike
+    /// [`Interpreter::run`], this doesn't give the [VmObserver] a chance to
+    /// pause before the instruction.
+    pub fn step(&mut self) -> Result<(), RuntimeError> {
         let ip = self.get_instruction_pointer();
+        let instruction = self.instructions[ip];
+
+        self.run_next_instruction()?;
+        self.notify_observer(|observer, vm| observer.after_instruction(vm, ip, instruction));
+        Ok(())
+    }
+
+    /// Run the program from the current instruction pointer, until the
+    /// instruction pointer moves past the last instruction of the program,
+    /// or until the [VmObserver] pauses the execution.
+    pub fn run(&mut self) -> Result<(), RuntimeError> {
+        while !self.is_finished() {
+            let ip = self.get_instruction_pointer();
+            let instruction = self.instructions[ip];
+
+            let control = self
+                .notify_observer(|observer, vm| observer.before_instruction(vm, ip, instruction));
 
-        while ip < self.instructions.len() {
-            // Ok, now we need to run the current instruction, so we pass it into the
-            // run_next_instruction, it's possible that the the next instruction
-            // will jump or invoke some kind of exit condition in
-            // the VM, therefore we have to check after each invocation of the instruction
-            // if we can proceed
-            self.run_next_instruction()?;
-
-            // TODO: we probably need to refactor this out into a function as the 'done'
-            // state will become       significantly more complicated...
-            if ip == self.instructions.len() - 1 {
+            if control == Some(VmControl::Pause) {
                 return Ok(());
             }
 
-            self.set_instruction_pointer(ip + 1);
+            self.step()?;
         }
 
         Ok(())
@@ -1,6 +1,19 @@
 //! Hash Compiler VM tests.
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    rc::Rc,
+};
+
 use hash_vm::{
-    bytecode::Instruction, bytecode_builder::BytecodeBuilder, register::Register, vm::Interpreter,
+    bytecode::Instruction,
+    bytecode_builder::BytecodeBuilder,
+    error::RuntimeError,
+    ffi::{ForeignSignature, ForeignValue},
+    heap::{ObjectLayout, Pointer},
+    observer::{TraceObserver, VmControl, VmObserver},
+    register::Register,
+    vm::Interpreter,
 };
 
 #[test]
@@ -22,3 +35,349 @@ fn push_two_and_add() {
     vm.run().unwrap();
     assert_eq!(vm.registers().get_register16(l1), 4);
 }
+
+#[test]
+fn jump_to_label() {
+    let mut builder = BytecodeBuilder::default();
+
+    let l1 = Register::new(0);
+    let location = Register::new(1);
+    let skip = builder.new_label();
+
+    builder.add_instruction(Instruction::Write { dest: l1, value: 1 });
+    builder.write_label(location, skip);
+    builder.add_instruction(Instruction::Jmp { location });
+    builder.add_instruction(Instruction::Write { dest: l1, value: 2 });
+    builder.bind_label(skip);
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+
+    vm.run().unwrap();
+    assert_eq!(vm.registers().get_register64(l1), 1);
+}
+
+#[test]
+fn compare_and_jump() {
+    let mut builder = BytecodeBuilder::default();
+
+    let l1 = Register::new(0);
+    let l2 = Register::new(1);
+    let location = Register::new(2);
+    let result = Register::new(3);
+    let less = builder.new_label();
+
+    builder.add_instruction(Instruction::Write { dest: l1, value: 2 });
+    builder.add_instruction(Instruction::Write { dest: l2, value: 3 });
+    builder.add_instruction(Instruction::Cmp { l1, l2 });
+    builder.write_label(location, less);
+    builder.add_instruction(Instruction::JmpNeg { l1, location });
+    builder.add_instruction(Instruction::Write { dest: result, value: 1 });
+    builder.add_instruction(Instruction::Trap { id: 0 });
+    builder.bind_label(less);
+    builder.add_instruction(Instruction::Write { dest: result, value: 2 });
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+
+    vm.run().unwrap();
+    assert_eq!(vm.registers().get_register64(result), 2);
+}
+
+#[test]
+fn call_and_return() {
+    let mut builder = BytecodeBuilder::default();
+
+    let l1 = Register::new(0);
+    let l2 = Register::new(1);
+    let func = Register::new(2);
+    let (double, end) = (builder.new_label(), builder.new_label());
+
+    builder.add_instruction(Instruction::Write { dest: l1, value: 21 });
+    builder.write_label(func, double);
+    builder.add_instruction(Instruction::Call { func });
+    builder.write_label(func, end);
+    builder.add_instruction(Instruction::Jmp { location: func });
+
+    builder.bind_label(double);
+    builder.add_instruction(Instruction::Mov { src: l1, dest: l2 });
+    builder.add_instruction(Instruction::Add64 { l1, l2 });
+    builder.add_instruction(Instruction::Return);
+    builder.bind_label(end);
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+
+    vm.run().unwrap();
+    assert_eq!(vm.registers().get_register64(l1), 42);
+}
+
+#[test]
+fn call_foreign_fn_with_floats() {
+    let mut builder = BytecodeBuilder::default();
+
+    let x = Register::new(0);
+    let exp = Register::new(1);
+    let func = Register::new(16);
+
+    // `ldexp` takes a float and an integer, which are passed in different
+    // kinds of registers.
+    let signature = ForeignSignature {
+        args: vec![
+            ForeignValue::Float { size: 8 },
+            ForeignValue::Integer { size: 4, signed: true },
+        ],
+        ret: Some(ForeignValue::Float { size: 8 }),
+    };
+
+    let mut vm = Interpreter::new();
+    let index = vm.foreign_fns_mut().resolve("c", "ldexp", signature).unwrap();
+
+    builder.add_instruction(Instruction::Write { dest: func, value: index as u64 });
+    builder.add_instruction(Instruction::CallForeign { func });
+    vm.set_program(builder.into());
+
+    vm.registers_mut().set_register64(x, 1.5f64.to_bits());
+    vm.registers_mut().set_register64(exp, 3);
+
+    vm.run().unwrap();
+    assert_eq!(f64::from_bits(vm.registers().get_register64(x)), 12.0);
+}
+
+#[test]
+fn call_foreign_fn_with_f32() {
+    let mut builder = BytecodeBuilder::default();
+
+    let x = Register::new(0);
+    let func = Register::new(16);
+
+    let signature = ForeignSignature {
+        args: vec![ForeignValue::Float { size: 4 }],
+        ret: Some(ForeignValue::Float { size: 4 }),
+    };
+
+    let mut vm = Interpreter::new();
+    let index = vm.foreign_fns_mut().resolve("c", "cbrtf", signature).unwrap();
+
+    builder.add_instruction(Instruction::Write { dest: func, value: index as u64 });
+    builder.add_instruction(Instruction::CallForeign { func });
+    vm.set_program(builder.into());
+
+    vm.registers_mut().set_register32(x, 27.0f32.to_bits());
+
+    vm.run().unwrap();
+    assert_eq!(vm.registers().get_register64(x), 3.0f32.to_bits() as u64);
+}
+
+#[test]
+fn trap_stops_execution() {
+    let mut builder = BytecodeBuilder::default();
+    let l1 = Register::new(0);
+
+    builder.add_instruction(Instruction::Trap { id: 7 });
+    builder.add_instruction(Instruction::Write { dest: l1, value: 1 });
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+
+    assert!(matches!(vm.run(), Err(RuntimeError::Trap { id: 7 })));
+    assert_eq!(vm.registers().get_register64(l1), 0);
+}
+
+#[test]
+fn return_unwinds_the_frame() {
+    let mut builder = BytecodeBuilder::default();
+
+    let l1 = Register::new(0);
+    let func = Register::new(1);
+    let (leak, end) = (builder.new_label(), builder.new_label());
+
+    builder.write_label(func, leak);
+    builder.add_instruction(Instruction::Call { func });
+    builder.write_label(func, end);
+    builder.add_instruction(Instruction::Jmp { location: func });
+
+    // The callee leaves a value on the stack, which is discarded by the return.
+    builder.bind_label(leak);
+    builder.add_instruction(Instruction::Push64 { l1 });
+    builder.add_instruction(Instruction::Return);
+    builder.bind_label(end);
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+
+    vm.run().unwrap();
+    assert!(vm.frames().is_empty());
+    assert_eq!(vm.registers().get_register64(Register::STACK_POINTER), 0);
+}
+
+#[test]
+fn unbounded_recursion_overflows_the_stack() {
+    let mut builder = BytecodeBuilder::default();
+
+    let func = Register::new(0);
+    let recurse = builder.new_label();
+
+    builder.bind_label(recurse);
+    builder.write_label(func, recurse);
+    builder.add_instruction(Instruction::Call { func });
+
+    let mut vm = Interpreter::with_stack_size(1024);
+    vm.set_program(builder.into());
+
+    assert!(matches!(vm.run(), Err(RuntimeError::StackOverflow { size: 1024 })));
+    assert_eq!(vm.frames().len(), 1024 / 16);
+}
+
+#[test]
+fn store_and_load_from_the_heap() {
+    let mut builder = BytecodeBuilder::default();
+
+    let (ptr, count, value, result) =
+        (Register::new(0), Register::new(1), Register::new(2), Register::new(3));
+
+    builder.add_instruction(Instruction::Write { dest: count, value: 2 });
+    builder.add_instruction(Instruction::Alloc { dest: ptr, layout: 0, count });
+    builder.add_instruction(Instruction::Write { dest: value, value: 8 });
+    builder.add_instruction(Instruction::Add64 { l1: ptr, l2: value });
+    builder.add_instruction(Instruction::Write { dest: value, value: 42 });
+    builder.add_instruction(Instruction::Store64 { src: value, ptr });
+    builder.add_instruction(Instruction::Load64 { dest: result, ptr });
+
+    let mut vm = Interpreter::new();
+    vm.heap_mut().add_layout(ObjectLayout::bytes(8));
+    vm.set_program(builder.into());
+
+    vm.run().unwrap();
+    assert_eq!(vm.registers().get_register64(result), 42);
+    assert_eq!(vm.heap().allocated_bytes(), 16);
+}
+
+#[test]
+fn load_past_the_end_of_an_object() {
+    let mut builder = BytecodeBuilder::default();
+
+    let (ptr, count) = (Register::new(0), Register::new(1));
+
+    builder.add_instruction(Instruction::Write { dest: count, value: 4 });
+    builder.add_instruction(Instruction::Alloc { dest: ptr, layout: 0, count });
+    builder.add_instruction(Instruction::Add64 { l1: ptr, l2: count });
+    builder.add_instruction(Instruction::Load8 { dest: count, ptr });
+
+    let mut vm = Interpreter::new();
+    vm.heap_mut().add_layout(ObjectLayout::bytes(1));
+    vm.set_program(builder.into());
+
+    assert!(matches!(vm.run(), Err(RuntimeError::InvalidHeapAccess { size: 1, .. })));
+}
+
+#[test]
+fn collect_unreachable_objects() {
+    let mut vm = Interpreter::new();
+
+    let node = vm.heap_mut().add_layout(ObjectLayout::new(16, vec![8]));
+    let (head, tail, garbage) = (
+        vm.heap_mut().allocate(node, 1).unwrap(),
+        vm.heap_mut().allocate(node, 1).unwrap(),
+        vm.heap_mut().allocate(node, 1).unwrap(),
+    );
+
+    // The tail is only reachable through the head, which is kept in a register.
+    vm.heap_mut().write(Pointer(head.0 + 8), &tail.0.to_be_bytes()).unwrap();
+    vm.registers_mut().set_register64(Register::new(0), head.0);
+
+    assert_eq!(vm.collect_garbage(), 16);
+    assert!(vm.heap().header(head).is_some());
+    assert!(vm.heap().header(tail).is_some());
+    assert!(vm.heap().header(garbage).is_none());
+
+    vm.registers_mut().set_register64(Register::new(0), 0);
+
+    assert_eq!(vm.collect_garbage(), 32);
+    assert_eq!(vm.heap().object_count(), 0);
+}
+
+/// Pauses before the instruction at `pause_at`, and records every instruction
+/// that is run.
+struct Breakpoint {
+    pause_at: usize,
+    executed: Rc<RefCell<Vec<usize>>>,
+}
+
+impl VmObserver for Breakpoint {
+    fn before_instruction(&mut self, _: &Interpreter, ip: usize, _: Instruction) -> VmControl {
+        if ip == self.pause_at {
+            VmControl::Pause
+        } else {
+            VmControl::Continue
+        }
+    }
+
+    fn after_instruction(&mut self, _: &Interpreter, ip: usize, _: Instruction) {
+        self.executed.borrow_mut().push(ip);
+    }
+}
+
+#[test]
+fn pause_and_step_over_a_breakpoint() {
+    let mut builder = BytecodeBuilder::default();
+    let l1 = Register::new(0);
+
+    builder.add_instruction(Instruction::Write { dest: l1, value: 1 });
+    builder.add_instruction(Instruction::Write { dest: l1, value: 2 });
+    builder.add_instruction(Instruction::Write { dest: l1, value: 3 });
+
+    let executed = Rc::new(RefCell::new(vec![]));
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+    vm.set_observer(Breakpoint { pause_at: 1, executed: executed.clone() });
+
+    vm.run().unwrap();
+    assert!(!vm.is_finished());
+    assert_eq!(vm.registers().get_register64(l1), 1);
+
+    // Step over the breakpoint, and then run until the end of the program.
+    vm.step().unwrap();
+    vm.run().unwrap();
+    assert!(vm.is_finished());
+    assert_eq!(vm.registers().get_register64(l1), 3);
+    assert_eq!(*executed.borrow(), vec![0, 1, 2]);
+}
+
+/// A buffer that the trace is written to, which is shared with the test.
+#[derive(Default, Clone)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn trace_each_instruction() {
+    let mut builder = BytecodeBuilder::default();
+    let (l1, l2) = (Register::new(0), Register::new(1));
+
+    builder.add_instruction(Instruction::Write { dest: l1, value: 2 });
+    builder.add_instruction(Instruction::Write { dest: l2, value: 3 });
+    builder.add_instruction(Instruction::Add64 { l1, l2 });
+
+    let trace = SharedBuffer::default();
+
+    let mut vm = Interpreter::new();
+    vm.set_program(builder.into());
+    vm.set_observer(TraceObserver::new(trace.clone()));
+    vm.run().unwrap();
+
+    let trace = String::from_utf8(trace.0.take()).unwrap();
+    let lines: Vec<_> = trace.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[2].contains("Add64 { l1: r0, l2: r1 } [r0=0x5 r1=0x3] depth=0"));
+}
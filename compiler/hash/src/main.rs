@@ -1,9 +1,13 @@
 //! Hash Compiler entry point.
-use std::panic;
+use std::{io, panic};
 
-use hash_driver::CompilerBuilder;
-use hash_pipeline::settings::CompilerSettings;
-use hash_utils::{clap::Parser, crash::crash_handler, log, logging::CompilerLogger};
+use hash_driver::{listener::listen, watch::watch, CompilerBuilder};
+use hash_pipeline::settings::{CompilerCommand, CompilerSettings};
+use hash_reporting::set_render_width;
+use hash_utils::{
+    clap::Parser, crash::crash_handler, highlight::set_colours_enabled, log,
+    logging::CompilerLogger, terminal,
+};
 
 /// The logger that is used by the compiler for `log!` statements.
 pub static COMPILER_LOGGER: CompilerLogger = CompilerLogger;
@@ -20,7 +24,28 @@ fn main() {
     // Register main thread with the profiler
     profiling::register_thread!("compiler-main");
 
-    let settings = CompilerSettings::parse();
+    let mut settings = CompilerSettings::parse();
+
+    // Reports are rendered to fit within the terminal that they are written to.
+    set_colours_enabled(settings.colour.use_colours());
+    set_render_width(terminal::stderr_width());
+
+    // Explaining a code doesn't run the compiler at all.
+    if let Some(CompilerCommand::Explain { code }) = &settings.command {
+        println!("{code}\n\n{}", code.explanation());
+        return;
+    }
+
+    // The listener writes messages to the standard output, so nothing is
+    // logged since the logs would corrupt the messages.
+    if let Some(CompilerCommand::Listen) = &settings.command {
+        settings.apply_command();
+        listen(settings, io::stdin().lock(), io::stdout());
+        return;
+    }
+
+    let is_watching = matches!(settings.command, Some(CompilerCommand::Watch { .. }));
+    settings.apply_command();
 
     // if debug is specified, we want to log everything that is debug level...
     if settings.debug {
@@ -29,6 +54,11 @@ fn main() {
         log::set_max_level(log::LevelFilter::Info);
     }
 
+    // The watcher compiles the entry point again whenever it changes.
+    if is_watching {
+        watch(settings);
+    }
+
     let mut compiler = CompilerBuilder::build_with_settings(settings);
 
     // Now run on the filename that was specified by the user.